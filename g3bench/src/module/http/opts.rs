@@ -114,6 +114,16 @@ impl HttpClientArgs {
                         .map_err(|e| anyhow!("invalid auth value: {e:?}"))?;
                     req.headers_mut().insert(http::header::AUTHORIZATION, value);
                 }
+                HttpAuth::Bearer(bearer) => {
+                    let value = HeaderValue::try_from(bearer)
+                        .map_err(|e| anyhow!("invalid auth value: {e:?}"))?;
+                    req.headers_mut().insert(http::header::AUTHORIZATION, value);
+                }
+                HttpAuth::Negotiate(negotiate) => {
+                    let value = HeaderValue::try_from(negotiate)
+                        .map_err(|e| anyhow!("invalid auth value: {e:?}"))?;
+                    req.headers_mut().insert(http::header::AUTHORIZATION, value);
+                }
             }
         }
 
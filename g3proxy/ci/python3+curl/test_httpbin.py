@@ -19,6 +19,8 @@ request_target_prefix = None
 no_auth = False
 proxy_tunnel = False
 proxy_masque = False
+proxy_h2 = False
+http_cache = False
 
 ACCEPT_JSON = 'Accept: application/json'
 ACCEPT_HTML = 'Accept: text/html'
@@ -46,6 +48,9 @@ class TestHttpBin(unittest.TestCase):
                 self.c.setopt(pycurl.PROXY, target_proxy)
                 if proxy_ca_cert is not None:
                     self.c.setopt(pycurl.PROXY_CAINFO, proxy_ca_cert)
+                if proxy_h2:
+                    # CURLPROXY_HTTPS2, only negotiated by ALPN
+                    self.c.setopt(pycurl.PROXYTYPE, getattr(pycurl, 'PROXYTYPE_HTTPS2', 3))
                 if proxy_tunnel:
                     self.c.setopt(pycurl.HTTPPROXYTUNNEL, 1)
         if local_resolve is not None:
@@ -109,6 +114,21 @@ class TestHttpBin(unittest.TestCase):
             self.c.perform()
             self.assertEqual(self.c.getinfo(pycurl.RESPONSE_CODE), 401)
 
+    def test_cached_get(self):
+        if not http_cache:
+            self.skipTest('http cache is not enabled in the proxy')
+
+        self.set_url_and_request_target('/cache/60')
+        self.c.perform()
+        self.assertEqual(self.c.getinfo(pycurl.RESPONSE_CODE), 200)
+
+        # the Age header will be added to the cached response
+        headers = BytesIO()
+        self.c.setopt(pycurl.HEADERFUNCTION, headers.write)
+        self.c.perform()
+        self.assertEqual(self.c.getinfo(pycurl.RESPONSE_CODE), 200)
+        self.assertIn(b'\nage:', headers.getvalue().lower())
+
     def test_base64_decode(self):
         self.set_url_and_request_target('/base64/SFRUUEJJTiBpcyBhd2Vzb21l')
         self.c.setopt(pycurl.HTTPHEADER, [ACCEPT_HTML])
@@ -212,6 +232,8 @@ if __name__ == '__main__':
     parser.add_argument('--no-auth', action='store_true', help='No http auth tests')
     parser.add_argument('--proxy-tunnel', action='store_true', help='Tunnel the traffic through the proxy')
     parser.add_argument('--proxy-masque', action='store_true', help='Use masque/http well known uri')
+    parser.add_argument('--proxy-h2', action='store_true', help='Use h2 to the https proxy')
+    parser.add_argument('--http-cache', action='store_true', help='The proxy has http cache enabled')
 
     (args, left_args) = parser.parse_known_args()
 
@@ -229,6 +251,8 @@ if __name__ == '__main__':
     no_auth = args.no_auth
     proxy_tunnel = args.proxy_tunnel
     proxy_masque = args.proxy_masque
+    proxy_h2 = args.proxy_h2
+    http_cache = args.http_cache
 
     left_args.insert(0, sys.argv[0])
 
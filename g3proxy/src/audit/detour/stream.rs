@@ -186,6 +186,10 @@ where
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.server_quit_policy.force_quit() {
                         let _ = force_quit_sender.try_send(());
                         return Err(ServerTaskError::CanceledAsServerQuit)
@@ -0,0 +1,50 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tokio::net::TcpStream;
+
+use g3_types::metrics::NodeName;
+use g3_types::net::{Host, UpstreamAddr};
+use g3_types::resolve::ResolveStrategy;
+
+use crate::resolve::HappyEyeballsResolveJob;
+
+const RESOLUTION_DELAY: Duration = Duration::from_millis(50);
+
+/// connect to the auth server or user source, the domain will be resolved by the given resolver
+pub(super) async fn connect_upstream(
+    upstream: &UpstreamAddr,
+    resolver: &NodeName,
+) -> anyhow::Result<TcpStream> {
+    let port = upstream.port();
+    let ips = match upstream.host() {
+        Host::Ip(ip) => vec![*ip],
+        Host::Domain(domain) => {
+            let handle = crate::resolve::get_handle(resolver)?;
+            let mut job = HappyEyeballsResolveJob::new_dyn(
+                ResolveStrategy::default(),
+                &handle,
+                domain.clone(),
+            )
+            .map_err(|e| anyhow!("failed to create resolve job for {domain}: {e}"))?;
+            job.get_r1_or_first_done(RESOLUTION_DELAY)
+                .await
+                .map_err(|e| anyhow!("failed to resolve {domain}: {e}"))?
+        }
+    };
+
+    let mut last_err = anyhow!("no ip address found for {upstream}");
+    for ip in ips {
+        match TcpStream::connect(SocketAddr::new(ip, port)).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_err = anyhow!("failed to connect to {upstream} via {ip}: {e}"),
+        }
+    }
+    Err(last_err)
+}
@@ -0,0 +1,129 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use arcstr::ArcStr;
+use chrono::Utc;
+use lru::LruCache;
+use tokio::time::Instant;
+
+use g3_types::metrics::NodeName;
+
+use super::User;
+use crate::config::auth::UserConfig;
+
+pub(super) type AuthCacheKey = [u8; 32];
+
+pub(super) fn auth_cache_key(parts: &[&[u8]]) -> AuthCacheKey {
+    let mut hasher = openssl::sha::Sha256::new();
+    for part in parts {
+        hasher.update(&(part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    hasher.finish()
+}
+
+#[derive(Clone)]
+struct AuthDecision {
+    expire: Instant,
+    user: Option<(ArcStr, Arc<User>)>,
+}
+
+/// the cache for results returned by remote auth backends
+pub(super) struct AuthResultCache {
+    decisions: Option<Mutex<LruCache<AuthCacheKey, AuthDecision, ahash::RandomState>>>,
+}
+
+impl AuthResultCache {
+    pub(super) fn new(capacity: usize) -> Self {
+        let decisions = NonZeroUsize::new(capacity)
+            .map(|cap| Mutex::new(LruCache::with_hasher(cap, ahash::RandomState::new())));
+        AuthResultCache { decisions }
+    }
+
+    /// get the cached result, the inner value will be None if it's a deny result
+    pub(super) fn get(&self, key: &AuthCacheKey) -> Option<Option<(ArcStr, Arc<User>)>> {
+        let decisions = self.decisions.as_ref()?;
+        let mut cache = decisions.lock().unwrap();
+        let decision = cache.get(key)?;
+        if decision.expire > Instant::now() {
+            return Some(decision.user.clone());
+        }
+        cache.pop(key);
+        None
+    }
+
+    pub(super) fn put(&self, key: AuthCacheKey, user: Option<(ArcStr, Arc<User>)>, ttl: Duration) {
+        let Some(decisions) = &self.decisions else {
+            return;
+        };
+        if ttl.is_zero() {
+            return;
+        }
+        let decision = AuthDecision {
+            expire: Instant::now() + ttl,
+            user,
+        };
+        decisions.lock().unwrap().put(key, decision);
+    }
+}
+
+struct RemoteUser<S> {
+    source: S,
+    user: Arc<User>,
+}
+
+/// the users created for remote auth backends, with the source of their config
+pub(super) struct RemoteUsers<S> {
+    group: NodeName,
+    users: Mutex<LruCache<ArcStr, RemoteUser<S>, ahash::RandomState>>,
+}
+
+impl<S: PartialEq> RemoteUsers<S> {
+    pub(super) fn new(group: &NodeName, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        RemoteUsers {
+            group: group.clone(),
+            users: Mutex::new(LruCache::with_hasher(capacity, ahash::RandomState::new())),
+        }
+    }
+
+    pub(super) fn get_or_build<F>(
+        &self,
+        username: &str,
+        source: S,
+        build_config: F,
+    ) -> anyhow::Result<Arc<User>>
+    where
+        F: FnOnce(&S) -> anyhow::Result<UserConfig>,
+    {
+        let mut users = self.users.lock().unwrap();
+        if let Some(old) = users.get(username)
+            && old.source == source
+        {
+            return Ok(old.user.clone());
+        }
+
+        let config = Arc::new(build_config(&source)?);
+        let datetime_now = Utc::now();
+        // always keep the stats and limiters of the same user
+        let user = match users.get(username) {
+            Some(old) => old.user.new_for_reload(&config, &datetime_now)?,
+            None => User::new(&self.group, &config, &datetime_now)?,
+        };
+        let user = Arc::new(user);
+        users.put(
+            ArcStr::from(username),
+            RemoteUser {
+                source,
+                user: user.clone(),
+            },
+        );
+        Ok(user)
+    }
+}
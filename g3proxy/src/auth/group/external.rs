@@ -0,0 +1,377 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use arcstr::ArcStr;
+use http::Method;
+use log::warn;
+use serde_json::{Map, Value};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use url::Position;
+
+use g3_http::HttpBodyReader;
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_openssl::SslConnector;
+use g3_types::auth::UserAuthError;
+use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
+
+use super::User;
+use super::cache::{AuthCacheKey, AuthResultCache, RemoteUsers, auth_cache_key};
+use crate::auth::connect::connect_upstream;
+use crate::config::auth::{UserConfig, UserExternalAuthConfig};
+
+const BODY_LINE_MAX_LEN: usize = 4096;
+
+pub(super) enum ExternalAuthCredential<'a> {
+    Password {
+        username: &'a str,
+        password: &'a str,
+    },
+    Token(&'a str),
+}
+
+impl ExternalAuthCredential<'_> {
+    fn cache_key(&self, client_addr: SocketAddr, target: Option<&UpstreamAddr>) -> AuthCacheKey {
+        let client_ip = client_addr.ip().to_string();
+        let target = target.map(|t| t.to_string()).unwrap_or_default();
+        match self {
+            ExternalAuthCredential::Password { username, password } => auth_cache_key(&[
+                b"password",
+                username.as_bytes(),
+                password.as_bytes(),
+                client_ip.as_bytes(),
+                target.as_bytes(),
+            ]),
+            ExternalAuthCredential::Token(token) => auth_cache_key(&[
+                b"token",
+                token.as_bytes(),
+                client_ip.as_bytes(),
+                target.as_bytes(),
+            ]),
+        }
+    }
+
+    fn username(&self) -> Option<&str> {
+        match self {
+            ExternalAuthCredential::Password { username, .. } => Some(username),
+            ExternalAuthCredential::Token(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ExternalAuthResponse {
+    allow: bool,
+    username: Option<String>,
+    overrides: Map<String, Value>,
+    ttl: Option<Duration>,
+}
+
+impl ExternalAuthResponse {
+    fn deny() -> Self {
+        ExternalAuthResponse {
+            allow: false,
+            username: None,
+            overrides: Map::new(),
+            ttl: None,
+        }
+    }
+
+    /// parse the json response body, which should be in the following format:
+    ///
+    ///   {"allow": true, "username": "name", "user": {...}, "ttl": "30s"}
+    fn parse_json(doc: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = doc else {
+            return Err(anyhow!("the response should be a json map"));
+        };
+
+        let mut rsp = ExternalAuthResponse::deny();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "allow" => {
+                    rsp.allow =
+                        g3_json::value::as_bool(v).context(format!("invalid value for key {k}"))?;
+                }
+                "username" => {
+                    let name = g3_json::value::as_string(v)
+                        .context(format!("invalid username value for key {k}"))?;
+                    rsp.username = Some(name);
+                }
+                "user" => {
+                    let Value::Object(map) = v else {
+                        return Err(anyhow!("invalid map value for key {k}"));
+                    };
+                    rsp.overrides = map.clone();
+                }
+                "ttl" => {
+                    let ttl = g3_json::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    rsp.ttl = Some(ttl);
+                }
+                _ => {}
+            }
+        }
+        Ok(rsp)
+    }
+}
+
+pub(super) struct ExternalUserAuth {
+    config: UserExternalAuthConfig,
+    group: NodeName,
+    cache: AuthResultCache,
+    users: RemoteUsers<Map<String, Value>>,
+}
+
+impl ExternalUserAuth {
+    pub(super) fn new(config: &UserExternalAuthConfig, group: &NodeName) -> Self {
+        ExternalUserAuth {
+            config: config.clone(),
+            group: group.clone(),
+            cache: AuthResultCache::new(config.cache_capacity),
+            users: RemoteUsers::new(group, config.cache_capacity),
+        }
+    }
+
+    pub(super) async fn check(
+        &self,
+        credential: ExternalAuthCredential<'_>,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
+        server: &NodeName,
+    ) -> Result<(ArcStr, Arc<User>), UserAuthError> {
+        let key = credential.cache_key(client_addr, target);
+        if let Some(user) = self.cache.get(&key) {
+            return user.ok_or(UserAuthError::TokenNotMatch);
+        }
+
+        let rsp = match tokio::time::timeout(
+            self.config.timeout,
+            self.call(&credential, client_addr, target, server),
+        )
+        .await
+        {
+            Ok(Ok(rsp)) => rsp,
+            Ok(Err(e)) => {
+                warn!(
+                    "user-group {}: external auth via {} failed: {e:?}",
+                    self.group, self.config.url
+                );
+                return Err(UserAuthError::NoSuchUser);
+            }
+            Err(_) => {
+                warn!(
+                    "user-group {}: external auth via {} timed out",
+                    self.group, self.config.url
+                );
+                return Err(UserAuthError::NoSuchUser);
+            }
+        };
+
+        let user = if rsp.allow {
+            let Some(username) = rsp.username.as_deref().or(credential.username()) else {
+                warn!(
+                    "user-group {}: no username returned from external auth via {}",
+                    self.group, self.config.url
+                );
+                return Err(UserAuthError::NoSuchUser);
+            };
+            let user = self
+                .users
+                .get_or_build(username, rsp.overrides, |overrides| {
+                    UserConfig::parse_json_external(username, overrides)
+                });
+            match user {
+                Ok(user) => Some((ArcStr::from(username), user)),
+                Err(e) => {
+                    warn!(
+                        "user-group {}: invalid user {username} returned from external auth: {e:?}",
+                        self.group
+                    );
+                    return Err(UserAuthError::NoSuchUser);
+                }
+            }
+        } else {
+            None
+        };
+
+        let ttl = rsp.ttl.unwrap_or(if user.is_some() {
+            self.config.cache_ttl
+        } else {
+            self.config.deny_cache_ttl
+        });
+        self.cache.put(key, user.clone(), ttl);
+
+        user.ok_or(UserAuthError::TokenNotMatch)
+    }
+
+    async fn call(
+        &self,
+        credential: &ExternalAuthCredential<'_>,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
+        server: &NodeName,
+    ) -> anyhow::Result<ExternalAuthResponse> {
+        let mut body = Map::new();
+        body.insert("user_group".to_string(), Value::from(self.group.as_str()));
+        body.insert("server".to_string(), Value::from(server.as_str()));
+        match credential {
+            ExternalAuthCredential::Password { username, password } => {
+                body.insert("username".to_string(), Value::from(*username));
+                body.insert("password".to_string(), Value::from(*password));
+            }
+            ExternalAuthCredential::Token(token) => {
+                body.insert("token".to_string(), Value::from(*token));
+            }
+        }
+        body.insert(
+            "client_ip".to_string(),
+            Value::from(client_addr.ip().to_string()),
+        );
+        if let Some(target) = target {
+            body.insert("target".to_string(), Value::from(target.to_string()));
+        }
+        let body = Value::Object(body).to_string();
+
+        let port = self.config.upstream.port();
+        let stream = connect_upstream(&self.config.upstream, &self.config.resolver).await?;
+
+        if let Some(tls_client) = &self.config.tls_client {
+            let ssl = tls_client.build_ssl(&self.config.tls_name, port)?;
+            let connector = SslConnector::new(ssl, stream)
+                .map_err(|e| anyhow!("failed to create tls connector: {e}"))?;
+            let stream = connector
+                .connect()
+                .await
+                .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+            self.http_post(stream, &body).await
+        } else {
+            self.http_post(stream, &body).await
+        }
+    }
+
+    async fn http_post<S>(&self, mut stream: S, body: &str) -> anyhow::Result<ExternalAuthResponse>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let url = &self.config.url;
+        let path = &url[Position::BeforePath..Position::AfterQuery];
+        let host = &url[Position::BeforeHost..Position::AfterPort];
+        let req = format!(
+            "POST {path} HTTP/1.1\r\nHost: {host}\r\nAccept: application/json\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\nUser-Agent: g3proxy\r\n\r\n",
+            body.len()
+        );
+
+        stream
+            .write_all(req.as_bytes())
+            .await
+            .map_err(|e| anyhow!("failed to send request: {e}"))?;
+        stream
+            .write_all(body.as_bytes())
+            .await
+            .map_err(|e| anyhow!("failed to send request: {e}"))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+        let mut reader = BufReader::new(stream);
+        let rsp = HttpForwardRemoteResponse::parse(
+            &mut reader,
+            &Method::POST,
+            false,
+            self.config.rsp_header_max_size,
+        )
+        .await
+        .map_err(|e| anyhow!("failed to recv response header: {e}"))?;
+        match rsp.code {
+            200 => {}
+            401 | 403 => return Ok(ExternalAuthResponse::deny()),
+            code => return Err(anyhow!("unexpected response status code {code}")),
+        }
+
+        let Some(body_type) = rsp.body_type(&Method::POST) else {
+            return Err(anyhow!("no response body found"));
+        };
+        let mut body = Vec::new();
+        let mut body_reader = HttpBodyReader::new(&mut reader, body_type, BODY_LINE_MAX_LEN);
+        let max_size = self.config.body_max_size as u64;
+        (&mut body_reader)
+            .take(max_size + 1)
+            .read_to_end(&mut body)
+            .await
+            .map_err(|e| anyhow!("failed to read response body: {e}"))?;
+        if body.len() as u64 > max_size {
+            return Err(anyhow!("response body is larger than {max_size}"));
+        }
+
+        let doc: Value = serde_json::from_slice(&body)
+            .map_err(|e| anyhow!("response body is not valid json: {e}"))?;
+        ExternalAuthResponse::parse_json(&doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_response() {
+        let doc = serde_json::json!({"allow": false});
+        let rsp = ExternalAuthResponse::parse_json(&doc).unwrap();
+        assert_eq!(rsp, ExternalAuthResponse::deny());
+
+        let doc = serde_json::json!({
+            "allow": true,
+            "username": "alice",
+            "user": {"tcp_sock_speed_limit": "10M"},
+            "ttl": "30s",
+        });
+        let rsp = ExternalAuthResponse::parse_json(&doc).unwrap();
+        assert!(rsp.allow);
+        assert_eq!(rsp.username.as_deref(), Some("alice"));
+        assert!(rsp.overrides.contains_key("tcp_sock_speed_limit"));
+        assert_eq!(rsp.ttl, Some(Duration::from_secs(30)));
+
+        let doc = serde_json::json!({"allow": true, "user": "alice"});
+        assert!(ExternalAuthResponse::parse_json(&doc).is_err());
+
+        let doc = serde_json::json!([true]);
+        assert!(ExternalAuthResponse::parse_json(&doc).is_err());
+    }
+
+    #[test]
+    fn cache_key() {
+        let addr1 = SocketAddr::from(([127, 0, 0, 1], 1000));
+        let addr2 = SocketAddr::from(([127, 0, 0, 1], 2000));
+        let addr3 = SocketAddr::from(([127, 0, 0, 2], 1000));
+
+        let c1 = ExternalAuthCredential::Password {
+            username: "a",
+            password: "b",
+        };
+        let c2 = ExternalAuthCredential::Password {
+            username: "a",
+            password: "c",
+        };
+        let c3 = ExternalAuthCredential::Token("a\0b");
+        assert_eq!(c1.cache_key(addr1, None), c1.cache_key(addr2, None));
+        assert_ne!(c1.cache_key(addr1, None), c1.cache_key(addr3, None));
+        assert_ne!(c1.cache_key(addr1, None), c2.cache_key(addr1, None));
+        assert_ne!(c1.cache_key(addr1, None), c3.cache_key(addr1, None));
+
+        let target = UpstreamAddr::from_host_str_and_port("example.net", 443).unwrap();
+        assert_ne!(
+            c1.cache_key(addr1, None),
+            c1.cache_key(addr1, Some(&target))
+        );
+    }
+}
@@ -0,0 +1,316 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::anyhow;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use openssl::symm::{Cipher, Crypter, Mode};
+
+pub(super) const ETYPE_AES128_CTS_HMAC_SHA1_96: i32 = 17;
+pub(super) const ETYPE_AES256_CTS_HMAC_SHA1_96: i32 = 18;
+
+pub(super) const KEY_USAGE_AP_REQ_TICKET: u32 = 2;
+pub(super) const KEY_USAGE_AP_REQ_AUTHENTICATOR: u32 = 11;
+
+const AES_BLOCK_SIZE: usize = 16;
+const HMAC_SIZE: usize = 12;
+
+pub(super) fn is_supported_etype(etype: i32) -> bool {
+    key_size(etype).is_some()
+}
+
+fn key_size(etype: i32) -> Option<usize> {
+    match etype {
+        ETYPE_AES128_CTS_HMAC_SHA1_96 => Some(16),
+        ETYPE_AES256_CTS_HMAC_SHA1_96 => Some(32),
+        _ => None,
+    }
+}
+
+fn aes_block(key: &[u8], mode: Mode, block: &[u8]) -> anyhow::Result<[u8; AES_BLOCK_SIZE]> {
+    let cipher = match key.len() {
+        16 => Cipher::aes_128_ecb(),
+        32 => Cipher::aes_256_ecb(),
+        n => return Err(anyhow!("invalid aes key size {n}")),
+    };
+    let mut crypter = Crypter::new(cipher, mode, key, None)
+        .map_err(|e| anyhow!("failed to create aes crypter: {e}"))?;
+    crypter.pad(false);
+    let mut buf = [0u8; AES_BLOCK_SIZE * 2];
+    let mut len = crypter
+        .update(block, &mut buf)
+        .map_err(|e| anyhow!("aes operation failed: {e}"))?;
+    len += crypter
+        .finalize(&mut buf[len..])
+        .map_err(|e| anyhow!("aes operation failed: {e}"))?;
+    if len != AES_BLOCK_SIZE {
+        return Err(anyhow!("invalid aes output size {len}"));
+    }
+    let mut out = [0u8; AES_BLOCK_SIZE];
+    out.copy_from_slice(&buf[..AES_BLOCK_SIZE]);
+    Ok(out)
+}
+
+fn xor_into(dst: &mut [u8], src: &[u8]) {
+    dst.iter_mut().zip(src).for_each(|(d, s)| *d ^= s);
+}
+
+/// the n-fold operation, see rfc3961 Section 5.1
+fn n_fold(input: &[u8], out_len: usize) -> Vec<u8> {
+    let in_len = input.len();
+    let mut a = out_len;
+    let mut b = in_len;
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let lcm = out_len * in_len / a;
+
+    let in_bits = in_len << 3;
+    let mut out = vec![0u8; out_len];
+    let mut carry = 0u32;
+    for i in (0..lcm).rev() {
+        // the msb in input which is added into this byte
+        let msbit =
+            ((in_bits - 1) + (in_bits + 13) * (i / in_len) + ((in_len - (i % in_len)) << 3))
+                % in_bits;
+        let hi = input[((in_len - 1) - (msbit >> 3)) % in_len] as u32;
+        let lo = input[(in_len - (msbit >> 3)) % in_len] as u32;
+        carry += (((hi << 8) | lo) >> ((msbit & 7) + 1)) & 0xff;
+        carry += out[i % out_len] as u32;
+        out[i % out_len] = (carry & 0xff) as u8;
+        carry >>= 8;
+    }
+    if carry != 0 {
+        for b in out.iter_mut().rev() {
+            carry += *b as u32;
+            *b = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+    }
+    out
+}
+
+/// DK(Key, Constant), the random-to-key function is identity for aes
+fn derive_key(key: &[u8], constant: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut block = n_fold(constant, AES_BLOCK_SIZE);
+    let mut out = Vec::with_capacity(key.len() + AES_BLOCK_SIZE);
+    while out.len() < key.len() {
+        let encrypted = aes_block(key, Mode::Encrypt, &block)?;
+        out.extend_from_slice(&encrypted);
+        block = encrypted.to_vec();
+    }
+    out.truncate(key.len());
+    Ok(out)
+}
+
+fn usage_key(key: &[u8], usage: u32, kind: u8) -> anyhow::Result<Vec<u8>> {
+    let mut constant = [0u8; 5];
+    constant[..4].copy_from_slice(&usage.to_be_bytes());
+    constant[4] = kind;
+    derive_key(key, &constant)
+}
+
+/// aes cbc mode with ciphertext stealing and zero iv, see rfc3962 Section 5
+fn cts_decrypt(key: &[u8], data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let len = data.len();
+    if len < AES_BLOCK_SIZE {
+        return Err(anyhow!("cipher text too short"));
+    }
+    if len == AES_BLOCK_SIZE {
+        return Ok(aes_block(key, Mode::Decrypt, data)?.to_vec());
+    }
+
+    let block_count = len.div_ceil(AES_BLOCK_SIZE);
+    let last_len = len - (block_count - 1) * AES_BLOCK_SIZE;
+    let mut out = Vec::with_capacity(len);
+    let mut prev = [0u8; AES_BLOCK_SIZE];
+    for block in data[..(block_count - 2) * AES_BLOCK_SIZE].chunks_exact(AES_BLOCK_SIZE) {
+        let mut plain = aes_block(key, Mode::Decrypt, block)?;
+        xor_into(&mut plain, &prev);
+        out.extend_from_slice(&plain);
+        prev.copy_from_slice(block);
+    }
+
+    let offset = (block_count - 2) * AES_BLOCK_SIZE;
+    let second_last = &data[offset..offset + AES_BLOCK_SIZE];
+    let last = &data[offset + AES_BLOCK_SIZE..];
+    let d = aes_block(key, Mode::Decrypt, second_last)?;
+    let mut last_plain = d;
+    xor_into(&mut last_plain[..last_len], last);
+
+    let mut padded = d;
+    padded[..last_len].copy_from_slice(last);
+    let mut second_last_plain = aes_block(key, Mode::Decrypt, &padded)?;
+    xor_into(&mut second_last_plain, &prev);
+
+    out.extend_from_slice(&second_last_plain);
+    out.extend_from_slice(&last_plain[..last_len]);
+    Ok(out)
+}
+
+fn hmac_sha1(key: &[u8], data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let pkey = PKey::hmac(key).map_err(|e| anyhow!("invalid hmac key: {e}"))?;
+    let mut signer = Signer::new(MessageDigest::sha1(), &pkey)
+        .map_err(|e| anyhow!("failed to create hmac signer: {e}"))?;
+    signer
+        .update(data)
+        .map_err(|e| anyhow!("hmac operation failed: {e}"))?;
+    signer
+        .sign_to_vec()
+        .map_err(|e| anyhow!("hmac operation failed: {e}"))
+}
+
+/// decrypt the cipher of EncryptedData and verify its integrity, see rfc3961 Section 5.3
+pub(super) fn decrypt(
+    key: &[u8],
+    etype: i32,
+    usage: u32,
+    cipher: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    let Some(size) = key_size(etype) else {
+        return Err(anyhow!("unsupported encryption type {etype}"));
+    };
+    if key.len() != size {
+        return Err(anyhow!("invalid key size {} for etype {etype}", key.len()));
+    }
+    if cipher.len() < AES_BLOCK_SIZE + HMAC_SIZE {
+        return Err(anyhow!("cipher text too short"));
+    }
+
+    let ke = usage_key(key, usage, 0xaa)?;
+    let ki = usage_key(key, usage, 0x55)?;
+    let (data, checksum) = cipher.split_at(cipher.len() - HMAC_SIZE);
+    let plain = cts_decrypt(&ke, data)?;
+    let hmac = hmac_sha1(&ki, &plain)?;
+    if !openssl::memcmp::eq(&hmac[..HMAC_SIZE], checksum) {
+        return Err(anyhow!("integrity check failed"));
+    }
+    // strip the confounder
+    Ok(plain[AES_BLOCK_SIZE..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    fn cts_encrypt(key: &[u8], data: &[u8]) -> Vec<u8> {
+        if data.len() == AES_BLOCK_SIZE {
+            return aes_block(key, Mode::Encrypt, data).unwrap().to_vec();
+        }
+        let mut blocks = Vec::new();
+        let mut prev = [0u8; AES_BLOCK_SIZE];
+        for chunk in data.chunks(AES_BLOCK_SIZE) {
+            let mut block = [0u8; AES_BLOCK_SIZE];
+            block[..chunk.len()].copy_from_slice(chunk);
+            xor_into(&mut block, &prev);
+            prev = aes_block(key, Mode::Encrypt, &block).unwrap();
+            blocks.push(prev);
+        }
+        let last_len = data.len() - (blocks.len() - 1) * AES_BLOCK_SIZE;
+        let n = blocks.len();
+        let mut out = Vec::new();
+        blocks[..n - 2]
+            .iter()
+            .for_each(|b| out.extend_from_slice(b));
+        out.extend_from_slice(&blocks[n - 1]);
+        out.extend_from_slice(&blocks[n - 2][..last_len]);
+        out
+    }
+
+    fn encrypt(key: &[u8], usage: u32, confounder: &[u8], data: &[u8]) -> Vec<u8> {
+        let ke = usage_key(key, usage, 0xaa).unwrap();
+        let ki = usage_key(key, usage, 0x55).unwrap();
+        let mut plain = confounder.to_vec();
+        plain.extend_from_slice(data);
+        let mut out = cts_encrypt(&ke, &plain);
+        out.extend_from_slice(&hmac_sha1(&ki, &plain).unwrap()[..HMAC_SIZE]);
+        out
+    }
+
+    #[test]
+    fn fold() {
+        // test vectors in rfc3961 Appendix A.1
+        assert_eq!(n_fold(b"012345", 8), hex!("be072631276b1955"));
+        assert_eq!(n_fold(b"password", 7), hex!("78a07b6caf85fa"));
+        assert_eq!(n_fold(b"kerberos", 8), hex!("6b65726265726f73"));
+        assert_eq!(
+            n_fold(b"kerberos", 16),
+            hex!("6b65726265726f737b9b5b2b93132b93")
+        );
+    }
+
+    #[test]
+    fn string_to_key() {
+        // test vectors in rfc3962 Appendix B
+        let mut tkey = [0u8; 16];
+        openssl::pkcs5::pbkdf2_hmac(
+            b"password",
+            b"ATHENA.MIT.EDUraeburn",
+            1,
+            MessageDigest::sha1(),
+            &mut tkey,
+        )
+        .unwrap();
+        assert_eq!(
+            derive_key(&tkey, b"kerberos").unwrap(),
+            hex!("42263c6e89f4fc28b8df68ee09799f15")
+        );
+
+        let mut tkey = [0u8; 32];
+        openssl::pkcs5::pbkdf2_hmac(
+            b"password",
+            b"ATHENA.MIT.EDUraeburn",
+            1,
+            MessageDigest::sha1(),
+            &mut tkey,
+        )
+        .unwrap();
+        assert_eq!(
+            derive_key(&tkey, b"kerberos").unwrap(),
+            hex!("fe697b52bc0d3ce14432ba036a92e65bbb52280990a2fa27883998d72af30161")
+        );
+    }
+
+    #[test]
+    fn cts() {
+        // test vectors in rfc3962 Appendix B
+        let key = b"chicken teriyaki";
+        let plain = hex!("4920776f756c64206c696b652074686520");
+        let cipher = hex!("c6353568f2bf8cb4d8a580362da7ff7f97");
+        assert_eq!(cts_decrypt(key, &cipher).unwrap(), plain);
+        assert_eq!(cts_encrypt(key, &plain), cipher);
+
+        let plain = hex!("4920776f756c64206c696b65207468652047656e6572616c20476175277320");
+        let cipher = hex!("fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5");
+        assert_eq!(cts_decrypt(key, &cipher).unwrap(), plain);
+    }
+
+    #[test]
+    fn decrypt_data() {
+        let key = hex!("fe697b52bc0d3ce14432ba036a92e65bbb52280990a2fa27883998d72af30161");
+        let data = b"some kerberos encrypted part";
+        let cipher = encrypt(&key, KEY_USAGE_AP_REQ_TICKET, &[0x5a; 16], data);
+        let plain = decrypt(
+            &key,
+            ETYPE_AES256_CTS_HMAC_SHA1_96,
+            KEY_USAGE_AP_REQ_TICKET,
+            &cipher,
+        )
+        .unwrap();
+        assert_eq!(plain, data);
+
+        assert!(
+            decrypt(
+                &key,
+                ETYPE_AES256_CTS_HMAC_SHA1_96,
+                KEY_USAGE_AP_REQ_AUTHENTICATOR,
+                &cipher
+            )
+            .is_err()
+        );
+    }
+}
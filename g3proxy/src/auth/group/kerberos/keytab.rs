@@ -0,0 +1,153 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::anyhow;
+
+const KEYTAB_VERSION_2: u16 = 0x0502;
+
+pub(super) struct KeytabEntry {
+    pub(super) principal: String,
+    pub(super) kvno: u32,
+    pub(super) etype: i32,
+    pub(super) key: Vec<u8>,
+}
+
+struct KeytabReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> KeytabReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow!("no enough data"));
+        }
+        let (v, left) = self.data.split_at(len);
+        self.data = left;
+        Ok(v)
+    }
+
+    fn read_u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> anyhow::Result<u16> {
+        let b = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        let b = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_string(&mut self) -> anyhow::Result<&'a str> {
+        let len = self.read_u16()? as usize;
+        let b = self.read_bytes(len)?;
+        std::str::from_utf8(b).map_err(|_| anyhow!("invalid utf-8 string"))
+    }
+}
+
+/// parse the MIT keytab file, only version 0x0502 is supported
+pub(super) fn parse(data: &[u8]) -> anyhow::Result<Vec<KeytabEntry>> {
+    let mut reader = KeytabReader { data };
+    let version = reader
+        .read_u16()
+        .map_err(|_| anyhow!("no keytab version found"))?;
+    if version != KEYTAB_VERSION_2 {
+        return Err(anyhow!("unsupported keytab version {version:#06x}"));
+    }
+
+    let mut entries = Vec::new();
+    while !reader.data.is_empty() {
+        let size = reader.read_u32()? as i32;
+        if size == 0 {
+            break;
+        }
+        let data = reader.read_bytes(size.unsigned_abs() as usize)?;
+        if size < 0 {
+            // a hole left by deleted entry
+            continue;
+        }
+        let entry = parse_entry(data)
+            .map_err(|e| anyhow!("invalid keytab entry #{}: {e}", entries.len()))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_entry(data: &[u8]) -> anyhow::Result<KeytabEntry> {
+    let mut reader = KeytabReader { data };
+    let count = reader.read_u16()?;
+    let realm = reader.read_string()?;
+    let mut components = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        components.push(reader.read_string()?);
+    }
+    let _name_type = reader.read_u32()?;
+    let _timestamp = reader.read_u32()?;
+    let vno8 = reader.read_u8()?;
+    let etype = reader.read_u16()? as i16 as i32;
+    let key_len = reader.read_u16()? as usize;
+    let key = reader.read_bytes(key_len)?.to_vec();
+    // the 32bit kvno is optional, and should be used if present and non-zero
+    let kvno = match reader.read_u32() {
+        Ok(0) | Err(_) => vno8 as u32,
+        Ok(n) => n,
+    };
+    Ok(KeytabEntry {
+        principal: format!("{}@{realm}", components.join("/")),
+        kvno,
+        etype,
+        key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counted(s: &[u8]) -> Vec<u8> {
+        let mut v = (s.len() as u16).to_be_bytes().to_vec();
+        v.extend_from_slice(s);
+        v
+    }
+
+    fn entry(kvno8: u8, kvno: Option<u32>) -> Vec<u8> {
+        let mut e = 2u16.to_be_bytes().to_vec();
+        e.extend(counted(b"EXAMPLE.NET"));
+        e.extend(counted(b"HTTP"));
+        e.extend(counted(b"proxy.example.net"));
+        e.extend(1u32.to_be_bytes());
+        e.extend(0x6a000000u32.to_be_bytes());
+        e.push(kvno8);
+        e.extend(18u16.to_be_bytes());
+        e.extend(counted(&[0x11; 32]));
+        if let Some(kvno) = kvno {
+            e.extend(kvno.to_be_bytes());
+        }
+        let mut data = (e.len() as u32).to_be_bytes().to_vec();
+        data.extend(e);
+        data
+    }
+
+    #[test]
+    fn parse_keytab() {
+        let mut data = KEYTAB_VERSION_2.to_be_bytes().to_vec();
+        data.extend(entry(3, None));
+        data.extend((-4i32).to_be_bytes());
+        data.extend([0; 4]);
+        data.extend(entry(1, Some(257)));
+
+        let entries = parse(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].principal, "HTTP/proxy.example.net@EXAMPLE.NET");
+        assert_eq!(entries[0].kvno, 3);
+        assert_eq!(entries[0].etype, 18);
+        assert_eq!(entries[0].key, [0x11; 32]);
+        assert_eq!(entries[1].kvno, 257);
+
+        assert!(parse(&[0x05, 0x01]).is_err());
+    }
+}
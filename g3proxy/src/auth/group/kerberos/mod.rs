@@ -0,0 +1,157 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Mutex;
+
+use anyhow::{Context, anyhow};
+use chrono::Utc;
+use log::debug;
+
+use g3_types::auth::UserAuthError;
+use g3_types::metrics::NodeName;
+
+use super::cache::auth_cache_key;
+use crate::config::auth::UserKerberosAuthConfig;
+
+mod crypto;
+mod keytab;
+mod proto;
+mod replay;
+
+use keytab::KeytabEntry;
+use proto::{ApReq, Authenticator, EncTicketPart};
+use replay::ReplayCache;
+
+pub(super) struct KerberosUserAuth {
+    config: UserKerberosAuthConfig,
+    group: NodeName,
+    keys: Vec<KeytabEntry>,
+    replay_cache: Option<Mutex<ReplayCache>>,
+}
+
+impl KerberosUserAuth {
+    pub(super) fn new(config: &UserKerberosAuthConfig, group: &NodeName) -> anyhow::Result<Self> {
+        let data = std::fs::read(&config.keytab).context(format!(
+            "failed to read keytab file {}",
+            config.keytab.display()
+        ))?;
+        let keys: Vec<KeytabEntry> = keytab::parse(&data)
+            .context(format!("invalid keytab file {}", config.keytab.display()))?
+            .into_iter()
+            .filter(|k| crypto::is_supported_etype(k.etype))
+            .filter(|k| {
+                config
+                    .service_principal
+                    .as_ref()
+                    .map(|p| k.principal.eq(p))
+                    .unwrap_or(true)
+            })
+            .collect();
+        if keys.is_empty() {
+            return Err(anyhow!(
+                "no usable aes keys found in keytab file {}",
+                config.keytab.display()
+            ));
+        }
+
+        let replay_cache = (config.replay_cache_capacity > 0)
+            .then(|| Mutex::new(ReplayCache::new(config.replay_cache_capacity)));
+        Ok(KerberosUserAuth {
+            config: config.clone(),
+            group: group.clone(),
+            keys,
+            replay_cache,
+        })
+    }
+
+    /// verify the SPNEGO token and return the mapped username
+    pub(super) fn check(&self, token: &[u8]) -> Result<String, UserAuthError> {
+        let (name, realm, service_realm) = self.verify(token).map_err(|e| {
+            debug!("user-group {}: kerberos auth failed: {e:?}", self.group);
+            UserAuthError::TokenNotMatch
+        })?;
+        if !self.config.allow_realm(&realm, &service_realm) {
+            debug!(
+                "user-group {}: kerberos realm {realm} of client {name} is not allowed",
+                self.group
+            );
+            return Err(UserAuthError::NoSuchUser);
+        }
+        if self.config.strip_realm {
+            Ok(name)
+        } else {
+            Ok(format!("{name}@{realm}"))
+        }
+    }
+
+    /// verify the token and return the client name, the client realm and the service realm
+    fn verify(&self, token: &[u8]) -> anyhow::Result<(String, String, String)> {
+        let data = proto::extract_ap_req(token)?;
+        let ap_req = ApReq::parse(&data)?;
+
+        let enc_part = &ap_req.ticket.enc_part;
+        let service = ap_req.ticket.service_principal();
+        let mut keys = self
+            .keys
+            .iter()
+            .filter(|k| k.principal == service && k.etype == enc_part.etype)
+            .filter(|k| enc_part.kvno.map(|v| v == k.kvno).unwrap_or(true))
+            .peekable();
+        if keys.peek().is_none() {
+            return Err(anyhow!(
+                "no key found for {service} with etype {} kvno {:?}",
+                enc_part.etype,
+                enc_part.kvno
+            ));
+        }
+        let plain = keys
+            .find_map(|k| {
+                crypto::decrypt(
+                    &k.key,
+                    enc_part.etype,
+                    crypto::KEY_USAGE_AP_REQ_TICKET,
+                    &enc_part.cipher,
+                )
+                .ok()
+            })
+            .ok_or_else(|| anyhow!("failed to decrypt the ticket for {service}"))?;
+        let ticket = EncTicketPart::parse(&plain).context("invalid ticket enc part")?;
+
+        let now = Utc::now();
+        let skew = chrono::Duration::from_std(self.config.clock_skew).unwrap_or_default();
+        if ticket.starttime - skew > now {
+            return Err(anyhow!("the ticket is not yet valid"));
+        }
+        if ticket.endtime + skew < now {
+            return Err(anyhow!("the ticket has expired"));
+        }
+
+        let authenticator = &ap_req.authenticator;
+        if authenticator.etype != ticket.key.etype {
+            return Err(anyhow!("mismatched authenticator encryption type"));
+        }
+        let plain = crypto::decrypt(
+            &ticket.key.value,
+            ticket.key.etype,
+            crypto::KEY_USAGE_AP_REQ_AUTHENTICATOR,
+            &authenticator.cipher,
+        )
+        .context("failed to decrypt the authenticator")?;
+        let auth = Authenticator::parse(&plain).context("invalid authenticator")?;
+        if auth.cname != ticket.cname || auth.crealm != ticket.crealm {
+            return Err(anyhow!("mismatched client principal in authenticator"));
+        }
+        if (auth.ctime - now).abs() > skew {
+            return Err(anyhow!("the authenticator time is out of the allowed skew"));
+        }
+
+        if let Some(cache) = &self.replay_cache {
+            let key = auth_cache_key(&[b"kerberos", &authenticator.cipher]);
+            cache.lock().unwrap().add(key, auth.ctime + skew, now)?;
+        }
+
+        Ok((ticket.cname.name(), ticket.crealm, ap_req.ticket.realm))
+    }
+}
@@ -0,0 +1,450 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::anyhow;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use yasna::models::ObjectIdentifier;
+use yasna::tags::{TAG_GENERALIZEDTIME, TAG_GENERALSTRING, TAG_OID};
+use yasna::{ASN1Error, ASN1ErrorKind, ASN1Result, BERReader, BERReaderSeq, PCBit, Tag};
+
+const TAG_GSS_INITIAL_CONTEXT_TOKEN: u64 = 0;
+const TAG_TICKET: u64 = 1;
+const TAG_AUTHENTICATOR: u64 = 2;
+const TAG_ENC_TICKET_PART: u64 = 3;
+const TAG_AP_REQ: u64 = 14;
+
+/// 1.3.6.1.5.5.2
+const OID_SPNEGO: &[u64] = &[1, 3, 6, 1, 5, 5, 2];
+/// 1.2.840.113554.1.2.2
+const OID_KRB5: &[u64] = &[1, 2, 840, 113554, 1, 2, 2];
+/// 1.2.840.48018.1.2.2, the one used by old windows clients
+const OID_KRB5_MS: &[u64] = &[1, 2, 840, 48018, 1, 2, 2];
+
+const KRB5_TOK_ID_AP_REQ: &[u8] = &[0x01, 0x00];
+
+const KRB5_PVNO: i64 = 5;
+const KRB5_MSG_TYPE_AP_REQ: i64 = 14;
+
+fn invalid<T>() -> ASN1Result<T> {
+    Err(ASN1Error::new(ASN1ErrorKind::Invalid))
+}
+
+/// read the explicitly tagged field
+fn read_field<T, F>(seq: &mut BERReaderSeq<'_, '_>, n: u64, f: F) -> ASN1Result<T>
+where
+    F: for<'a, 'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T>,
+{
+    seq.next().read_tagged(Tag::context(n), f)
+}
+
+/// read the explicitly tagged optional field
+fn read_optional_field<T, F>(seq: &mut BERReaderSeq<'_, '_>, n: u64, f: F) -> ASN1Result<Option<T>>
+where
+    F: for<'a, 'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T>,
+{
+    seq.read_optional(|r| r.read_tagged(Tag::context(n), f))
+}
+
+/// skip the fields that are not used, including the ones added by extensions
+fn skip_left_fields(seq: &mut BERReaderSeq<'_, '_>) -> ASN1Result<()> {
+    while seq.read_optional(|r| r.read_der())?.is_some() {}
+    Ok(())
+}
+
+fn read_string(r: BERReader<'_, '_>) -> ASN1Result<String> {
+    let value = r.read_tagged_implicit(TAG_GENERALSTRING, |r| r.read_bytes())?;
+    String::from_utf8(value).or_else(|_| invalid())
+}
+
+/// read KerberosTime, which is a GeneralizedTime value without fractional seconds
+fn read_time(r: BERReader<'_, '_>) -> ASN1Result<DateTime<Utc>> {
+    let value = r.read_tagged_implicit(TAG_GENERALIZEDTIME, |r| r.read_bytes())?;
+    let Ok(s) = std::str::from_utf8(&value) else {
+        return invalid();
+    };
+    match NaiveDateTime::parse_from_str(s, "%Y%m%d%H%M%SZ") {
+        Ok(datetime) => Ok(datetime.and_utc()),
+        Err(_) => invalid(),
+    }
+}
+
+/// split the mech oid and the inner token in the GSS-API InitialContextToken, see rfc2743 Section 3.1
+fn split_initial_context_token(token: &[u8]) -> anyhow::Result<(ObjectIdentifier, Vec<u8>)> {
+    let value = yasna::parse_ber(token, |r| r.read_tagged_der())
+        .map_err(|e| anyhow!("invalid gss token: {e}"))?;
+    if value.tag() != Tag::application(TAG_GSS_INITIAL_CONTEXT_TOKEN)
+        || value.pcbit() != PCBit::Constructed
+    {
+        return Err(anyhow!("the token is not a gss initial context token"));
+    }
+    // the inner token is mechanism specific and may be not a ber value
+    let data = value.value();
+    let oid_len = match data {
+        [tag, len, ..] if *tag == TAG_OID.tag_number as u8 && *len < 0x80 => 2 + *len as usize,
+        _ => return Err(anyhow!("no valid mech oid found in gss token")),
+    };
+    if data.len() < oid_len {
+        return Err(anyhow!("no enough data for mech oid in gss token"));
+    }
+    let (oid, inner) = data.split_at(oid_len);
+    let oid = yasna::parse_der(oid, |r| r.read_oid())
+        .map_err(|e| anyhow!("invalid mech oid in gss token: {e}"))?;
+    Ok((oid, inner.to_vec()))
+}
+
+/// get the AP-REQ message in the SPNEGO or raw kerberos GSS-API token
+pub(super) fn extract_ap_req(token: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let (oid, inner) = split_initial_context_token(token)?;
+    if oid.components().as_slice() == OID_SPNEGO {
+        // NegotiationToken CHOICE, only negTokenInit is expected, see rfc4178 Section 4.2
+        let mech_token = yasna::parse_ber(&inner, |r| {
+            r.read_tagged(Tag::context(0), |r| {
+                r.read_sequence(|seq| {
+                    let _mech_types = read_optional_field(seq, 0, |r| r.read_der())?;
+                    let _req_flags = read_optional_field(seq, 1, |r| r.read_der())?;
+                    let mech_token = read_optional_field(seq, 2, |r| r.read_bytes())?;
+                    skip_left_fields(seq)?;
+                    Ok(mech_token)
+                })
+            })
+        })
+        .map_err(|e| anyhow!("invalid spnego negTokenInit: {e}"))?
+        .ok_or_else(|| anyhow!("no mech token found in spnego negTokenInit"))?;
+        let (oid, inner) = split_initial_context_token(&mech_token)?;
+        extract_krb5_ap_req(&oid, inner)
+    } else {
+        extract_krb5_ap_req(&oid, inner)
+    }
+}
+
+fn extract_krb5_ap_req(oid: &ObjectIdentifier, mut inner: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    let oid = oid.components().as_slice();
+    if oid != OID_KRB5 && oid != OID_KRB5_MS {
+        return Err(anyhow!("unsupported gss mechanism"));
+    }
+    if !inner.starts_with(KRB5_TOK_ID_AP_REQ) {
+        return Err(anyhow!("the kerberos token is not AP-REQ"));
+    }
+    inner.drain(..KRB5_TOK_ID_AP_REQ.len());
+    Ok(inner)
+}
+
+pub(super) struct EncryptedData {
+    pub(super) etype: i32,
+    pub(super) kvno: Option<u32>,
+    pub(super) cipher: Vec<u8>,
+}
+
+impl EncryptedData {
+    fn read(r: BERReader<'_, '_>) -> ASN1Result<Self> {
+        r.read_sequence(|seq| {
+            let etype = read_field(seq, 0, |r| r.read_i32())?;
+            // some implementations encode it as a negative Int32
+            let kvno = read_optional_field(seq, 1, |r| r.read_i64())?.map(|v| v as u32);
+            let cipher = read_field(seq, 2, |r| r.read_bytes())?;
+            Ok(EncryptedData {
+                etype,
+                kvno,
+                cipher,
+            })
+        })
+    }
+}
+
+#[derive(PartialEq, Eq)]
+pub(super) struct PrincipalName {
+    components: Vec<String>,
+}
+
+impl PrincipalName {
+    fn read(r: BERReader<'_, '_>) -> ASN1Result<Self> {
+        let components = r.read_sequence(|seq| {
+            let _name_type = read_field(seq, 0, |r| r.read_i32())?;
+            read_field(seq, 1, |r| r.collect_sequence_of(read_string))
+        })?;
+        if components.is_empty() {
+            return invalid();
+        }
+        Ok(PrincipalName { components })
+    }
+
+    pub(super) fn name(&self) -> String {
+        self.components.join("/")
+    }
+}
+
+pub(super) struct Ticket {
+    pub(super) realm: String,
+    pub(super) sname: PrincipalName,
+    pub(super) enc_part: EncryptedData,
+}
+
+impl Ticket {
+    fn read(r: BERReader<'_, '_>) -> ASN1Result<Self> {
+        r.read_tagged(Tag::application(TAG_TICKET), |r| {
+            r.read_sequence(|seq| {
+                let _tkt_vno = read_field(seq, 0, |r| r.read_i64())?;
+                let ticket = Ticket {
+                    realm: read_field(seq, 1, read_string)?,
+                    sname: read_field(seq, 2, PrincipalName::read)?,
+                    enc_part: read_field(seq, 3, EncryptedData::read)?,
+                };
+                skip_left_fields(seq)?;
+                Ok(ticket)
+            })
+        })
+    }
+
+    pub(super) fn service_principal(&self) -> String {
+        format!("{}@{}", self.sname.name(), self.realm)
+    }
+}
+
+/// KRB_AP_REQ, see rfc4120 Section 5.5.1
+pub(super) struct ApReq {
+    pub(super) ticket: Ticket,
+    pub(super) authenticator: EncryptedData,
+}
+
+impl ApReq {
+    pub(super) fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        let (pvno, msg_type, ap_req) = yasna::parse_ber(data, |r| {
+            r.read_tagged(Tag::application(TAG_AP_REQ), |r| {
+                r.read_sequence(|seq| {
+                    let pvno = read_field(seq, 0, |r| r.read_i64())?;
+                    let msg_type = read_field(seq, 1, |r| r.read_i64())?;
+                    let _ap_options = read_field(seq, 2, |r| r.read_der())?;
+                    let ap_req = ApReq {
+                        ticket: read_field(seq, 3, Ticket::read)?,
+                        authenticator: read_field(seq, 4, EncryptedData::read)?,
+                    };
+                    skip_left_fields(seq)?;
+                    Ok((pvno, msg_type, ap_req))
+                })
+            })
+        })
+        .map_err(|e| anyhow!("invalid AP-REQ message: {e}"))?;
+        if pvno != KRB5_PVNO {
+            return Err(anyhow!("unsupported kerberos protocol version {pvno}"));
+        }
+        if msg_type != KRB5_MSG_TYPE_AP_REQ {
+            return Err(anyhow!("unexpected kerberos message type {msg_type}"));
+        }
+        Ok(ap_req)
+    }
+}
+
+pub(super) struct EncryptionKey {
+    pub(super) etype: i32,
+    pub(super) value: Vec<u8>,
+}
+
+impl EncryptionKey {
+    fn read(r: BERReader<'_, '_>) -> ASN1Result<Self> {
+        r.read_sequence(|seq| {
+            Ok(EncryptionKey {
+                etype: read_field(seq, 0, |r| r.read_i32())?,
+                value: read_field(seq, 1, |r| r.read_bytes())?,
+            })
+        })
+    }
+}
+
+pub(super) struct EncTicketPart {
+    pub(super) key: EncryptionKey,
+    pub(super) crealm: String,
+    pub(super) cname: PrincipalName,
+    pub(super) starttime: DateTime<Utc>,
+    pub(super) endtime: DateTime<Utc>,
+}
+
+impl EncTicketPart {
+    pub(super) fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        yasna::parse_ber(data, |r| {
+            r.read_tagged(Tag::application(TAG_ENC_TICKET_PART), |r| {
+                r.read_sequence(|seq| {
+                    let _flags = read_field(seq, 0, |r| r.read_der())?;
+                    let key = read_field(seq, 1, EncryptionKey::read)?;
+                    let crealm = read_field(seq, 2, read_string)?;
+                    let cname = read_field(seq, 3, PrincipalName::read)?;
+                    let _transited = read_field(seq, 4, |r| r.read_der())?;
+                    let authtime = read_field(seq, 5, read_time)?;
+                    let starttime = read_optional_field(seq, 6, read_time)?.unwrap_or(authtime);
+                    let endtime = read_field(seq, 7, read_time)?;
+                    skip_left_fields(seq)?;
+                    Ok(EncTicketPart {
+                        key,
+                        crealm,
+                        cname,
+                        starttime,
+                        endtime,
+                    })
+                })
+            })
+        })
+        .map_err(|e| anyhow!("invalid EncTicketPart: {e}"))
+    }
+}
+
+pub(super) struct Authenticator {
+    pub(super) crealm: String,
+    pub(super) cname: PrincipalName,
+    pub(super) ctime: DateTime<Utc>,
+}
+
+impl Authenticator {
+    pub(super) fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        yasna::parse_ber(data, |r| {
+            r.read_tagged(Tag::application(TAG_AUTHENTICATOR), |r| {
+                r.read_sequence(|seq| {
+                    let _vno = read_field(seq, 0, |r| r.read_i64())?;
+                    let crealm = read_field(seq, 1, read_string)?;
+                    let cname = read_field(seq, 2, PrincipalName::read)?;
+                    let _cksum = read_optional_field(seq, 3, |r| r.read_der())?;
+                    let _cusec = read_field(seq, 4, |r| r.read_i64())?;
+                    let ctime = read_field(seq, 5, read_time)?;
+                    skip_left_fields(seq)?;
+                    Ok(Authenticator {
+                        crealm,
+                        cname,
+                        ctime,
+                    })
+                })
+            })
+        })
+        .map_err(|e| anyhow!("invalid Authenticator: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yasna::models::TaggedDerValue;
+    use yasna::{DERWriter, DERWriterSeq};
+
+    fn write_string(w: DERWriter<'_>, s: &str) {
+        w.write_tagged_implicit(TAG_GENERALSTRING, |w| w.write_bytes(s.as_bytes()));
+    }
+
+    fn write_principal(w: DERWriter<'_>, components: &[&str]) {
+        w.write_sequence(|w| {
+            w.next().write_tagged(Tag::context(0), |w| w.write_i32(1));
+            w.next().write_tagged(Tag::context(1), |w| {
+                w.write_sequence(|w| {
+                    for c in components {
+                        write_string(w.next(), c);
+                    }
+                })
+            });
+        });
+    }
+
+    fn write_encrypted_data(w: DERWriter<'_>, etype: i32, kvno: u32, cipher: &[u8]) {
+        w.write_sequence(|w| {
+            w.next()
+                .write_tagged(Tag::context(0), |w| w.write_i32(etype));
+            w.next()
+                .write_tagged(Tag::context(1), |w| w.write_u32(kvno));
+            w.next()
+                .write_tagged(Tag::context(2), |w| w.write_bytes(cipher));
+        });
+    }
+
+    fn write_field<F>(w: &mut DERWriterSeq<'_>, n: u64, f: F)
+    where
+        F: FnOnce(DERWriter<'_>),
+    {
+        w.next().write_tagged(Tag::context(n), f);
+    }
+
+    fn ap_req() -> Vec<u8> {
+        yasna::construct_der(|w| {
+            w.write_tagged(Tag::application(TAG_AP_REQ), |w| {
+                w.write_sequence(|w| {
+                    write_field(w, 0, |w| w.write_i64(5));
+                    write_field(w, 1, |w| w.write_i64(14));
+                    write_field(w, 2, |w| w.write_bitvec_bytes(&[0, 0, 0, 0], 32));
+                    write_field(w, 3, |w| {
+                        w.write_tagged(Tag::application(TAG_TICKET), |w| {
+                            w.write_sequence(|w| {
+                                write_field(w, 0, |w| w.write_i64(5));
+                                write_field(w, 1, |w| write_string(w, "EXAMPLE.NET"));
+                                write_field(w, 2, |w| {
+                                    write_principal(w, &["HTTP", "proxy.example.net"])
+                                });
+                                write_field(w, 3, |w| write_encrypted_data(w, 18, 2, &[0xab; 40]));
+                            })
+                        })
+                    });
+                    write_field(w, 4, |w| write_encrypted_data(w, 18, 0, &[0xcd; 60]));
+                })
+            })
+        })
+    }
+
+    fn initial_context_token(oid: &[u64], inner: &[u8]) -> Vec<u8> {
+        let mut value = yasna::construct_der(|w| w.write_oid(&ObjectIdentifier::from_slice(oid)));
+        value.extend_from_slice(inner);
+        yasna::construct_der(|w| {
+            w.write_tagged_der(&TaggedDerValue::from_tag_pc_and_bytes(
+                Tag::application(TAG_GSS_INITIAL_CONTEXT_TOKEN),
+                PCBit::Constructed,
+                value,
+            ))
+        })
+    }
+
+    #[test]
+    fn spnego_token() {
+        let ap_req = ap_req();
+        let mut inner = KRB5_TOK_ID_AP_REQ.to_vec();
+        inner.extend_from_slice(&ap_req);
+        let krb5_token = initial_context_token(OID_KRB5, &inner);
+        assert_eq!(extract_ap_req(&krb5_token).unwrap(), ap_req);
+
+        let neg_token_init = yasna::construct_der(|w| {
+            w.write_tagged(Tag::context(0), |w| {
+                w.write_sequence(|w| {
+                    write_field(w, 0, |w| {
+                        w.write_sequence(|w| {
+                            w.next()
+                                .write_oid(&ObjectIdentifier::from_slice(OID_KRB5_MS));
+                            w.next().write_oid(&ObjectIdentifier::from_slice(OID_KRB5));
+                        })
+                    });
+                    write_field(w, 2, |w| w.write_bytes(&krb5_token));
+                })
+            })
+        });
+        let spnego_token = initial_context_token(OID_SPNEGO, &neg_token_init);
+        assert_eq!(extract_ap_req(&spnego_token).unwrap(), ap_req);
+
+        // NTLMSSP
+        assert!(extract_ap_req(b"NTLMSSP\0\x01\0\0\0").is_err());
+    }
+
+    #[test]
+    fn parse_ap_req() {
+        let data = ap_req();
+        let ap_req = ApReq::parse(&data).unwrap();
+        assert_eq!(
+            ap_req.ticket.service_principal(),
+            "HTTP/proxy.example.net@EXAMPLE.NET"
+        );
+        assert_eq!(ap_req.ticket.enc_part.etype, 18);
+        assert_eq!(ap_req.ticket.enc_part.kvno, Some(2));
+        assert_eq!(ap_req.ticket.enc_part.cipher, [0xab; 40]);
+        assert_eq!(ap_req.authenticator.cipher, [0xcd; 60]);
+    }
+
+    #[test]
+    fn time() {
+        let data = yasna::construct_der(|w| {
+            w.write_tagged_implicit(TAG_GENERALIZEDTIME, |w| w.write_bytes(b"20260102030405Z"))
+        });
+        let time = yasna::parse_der(&data, read_time).unwrap();
+        assert_eq!(time.timestamp(), 1767323045);
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeSet;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+
+use super::super::cache::AuthCacheKey;
+
+/// the authenticators seen within the clock skew window, see rfc4120 Section 3.2.3
+///
+/// Entries are only removed after they expire, so new authenticators will be rejected if the
+/// cache is full, or a replayed one may not be detected.
+pub(super) struct ReplayCache {
+    capacity: usize,
+    entries: AHashMap<AuthCacheKey, DateTime<Utc>>,
+    expire_queue: BTreeSet<(DateTime<Utc>, AuthCacheKey)>,
+}
+
+impl ReplayCache {
+    pub(super) fn new(capacity: usize) -> Self {
+        ReplayCache {
+            capacity,
+            entries: AHashMap::new(),
+            expire_queue: BTreeSet::new(),
+        }
+    }
+
+    fn purge_expired(&mut self, now: DateTime<Utc>) {
+        while let Some((expire, key)) = self.expire_queue.first() {
+            if *expire > now {
+                break;
+            }
+            self.entries.remove(key);
+            self.expire_queue.pop_first();
+        }
+    }
+
+    /// add the authenticator, which should not be seen before
+    pub(super) fn add(
+        &mut self,
+        key: AuthCacheKey,
+        expire: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        self.purge_expired(now);
+        if self.entries.contains_key(&key) {
+            return Err(anyhow!("replayed authenticator"));
+        }
+        if self.entries.len() >= self.capacity {
+            return Err(anyhow!("the replay cache is full"));
+        }
+        self.entries.insert(key, expire);
+        self.expire_queue.insert((expire, key));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn add() {
+        let mut cache = ReplayCache::new(2);
+        let now = Utc::now();
+        let expire = now + TimeDelta::seconds(300);
+
+        cache.add([1; 32], expire, now).unwrap();
+        assert!(cache.add([1; 32], expire, now).is_err());
+        cache.add([2; 32], expire, now).unwrap();
+        // no entry will be evicted before it expires
+        assert!(cache.add([3; 32], expire, now).is_err());
+        assert!(cache.add([1; 32], expire, now).is_err());
+
+        let now = expire;
+        cache
+            .add([3; 32], now + TimeDelta::seconds(300), now)
+            .unwrap();
+        cache
+            .add([1; 32], now + TimeDelta::seconds(300), now)
+            .unwrap();
+        assert!(
+            cache
+                .add([2; 32], now + TimeDelta::seconds(300), now)
+                .is_err()
+        );
+    }
+}
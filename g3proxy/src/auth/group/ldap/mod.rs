@@ -0,0 +1,405 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arcstr::ArcStr;
+use log::warn;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+use g3_openssl::SslConnector;
+use g3_types::auth::UserAuthError;
+use g3_types::metrics::NodeName;
+
+use super::User;
+use super::cache::{AuthResultCache, RemoteUsers, auth_cache_key};
+use crate::auth::connect::connect_upstream;
+use crate::config::auth::UserLdapAuthConfig;
+
+mod proto;
+use proto::{LdapMessage, LdapMessageReader, LdapResponse};
+
+const BIND_MESSAGE_ID: i32 = 1;
+const SEARCH_MESSAGE_ID: i32 = 2;
+const UNBIND_MESSAGE_ID: i32 = 3;
+
+enum LdapBindResult {
+    Success(Vec<String>),
+    InvalidCredentials,
+}
+
+pub(super) struct LdapUserAuth {
+    config: UserLdapAuthConfig,
+    group: NodeName,
+    cache: AuthResultCache,
+    users: RemoteUsers<ArcStr>,
+}
+
+impl LdapUserAuth {
+    pub(super) fn new(config: &UserLdapAuthConfig, group: &NodeName) -> Self {
+        LdapUserAuth {
+            config: config.clone(),
+            group: group.clone(),
+            cache: AuthResultCache::new(config.cache_capacity),
+            users: RemoteUsers::new(group, config.cache_capacity),
+        }
+    }
+
+    pub(super) async fn check(
+        &self,
+        username: &str,
+        password: &str,
+        static_users: &AHashMap<ArcStr, Arc<User>>,
+    ) -> Result<Arc<User>, UserAuthError> {
+        // an empty password will lead to an unauthenticated bind, see rfc4513 Section 5.1.2
+        if username.is_empty() || password.is_empty() {
+            return Err(UserAuthError::TokenNotMatch);
+        }
+
+        let key = auth_cache_key(&[b"ldap", username.as_bytes(), password.as_bytes()]);
+        if let Some(user) = self.cache.get(&key) {
+            return user
+                .map(|(_, user)| user)
+                .ok_or(UserAuthError::TokenNotMatch);
+        }
+
+        let bind_result =
+            match tokio::time::timeout(self.config.timeout, self.bind(username, password)).await {
+                Ok(Ok(r)) => r,
+                Ok(Err(e)) => {
+                    warn!(
+                        "user-group {}: ldap auth via {} failed: {e:?}",
+                        self.group, self.config.url
+                    );
+                    return Err(UserAuthError::NoSuchUser);
+                }
+                Err(_) => {
+                    warn!(
+                        "user-group {}: ldap auth via {} timed out",
+                        self.group, self.config.url
+                    );
+                    return Err(UserAuthError::NoSuchUser);
+                }
+            };
+
+        let user = match bind_result {
+            LdapBindResult::Success(groups) => match self.select_user(&groups) {
+                Some(name) => match self.get_user(username, name, static_users) {
+                    Ok(user) => Some((ArcStr::from(username), user)),
+                    Err(e) => {
+                        warn!(
+                            "user-group {}: failed to create user {username} from {name}: {e:?}",
+                            self.group
+                        );
+                        return Err(UserAuthError::NoSuchUser);
+                    }
+                },
+                None => None,
+            },
+            LdapBindResult::InvalidCredentials => None,
+        };
+
+        let ttl = if user.is_some() {
+            self.config.cache_ttl
+        } else {
+            self.config.deny_cache_ttl
+        };
+        self.cache.put(key, user.clone(), ttl);
+
+        user.map(|(_, user)| user)
+            .ok_or(UserAuthError::TokenNotMatch)
+    }
+
+    /// select the mapped user or user template by the groups
+    fn select_user(&self, groups: &[String]) -> Option<&ArcStr> {
+        for (group, user) in &self.config.group_map {
+            let matched = groups.iter().any(|dn| {
+                dn.eq_ignore_ascii_case(group)
+                    || proto::first_rdn_value(dn).is_some_and(|v| v.eq_ignore_ascii_case(group))
+            });
+            if matched {
+                return Some(user);
+            }
+        }
+        self.config.default_user.as_ref()
+    }
+
+    fn get_user(
+        &self,
+        username: &str,
+        name: &ArcStr,
+        static_users: &AHashMap<ArcStr, Arc<User>>,
+    ) -> anyhow::Result<Arc<User>> {
+        if let Some(template) = self.config.user_templates.get(name) {
+            self.users.get_or_build(username, name.clone(), |_| {
+                Ok(template.new_from_template(username))
+            })
+        } else if let Some(user) = static_users.get(name) {
+            Ok(user.clone())
+        } else {
+            Err(anyhow!("no user or user template found"))
+        }
+    }
+
+    async fn bind(&self, username: &str, password: &str) -> anyhow::Result<LdapBindResult> {
+        let port = self.config.upstream.port();
+        let stream = connect_upstream(&self.config.upstream, &self.config.resolver).await?;
+
+        if let Some(tls_client) = &self.config.tls_client {
+            let ssl = tls_client.build_ssl(&self.config.tls_name, port)?;
+            let connector = SslConnector::new(ssl, stream)
+                .map_err(|e| anyhow!("failed to create tls connector: {e}"))?;
+            let stream = connector
+                .connect()
+                .await
+                .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+            self.bind_on_stream(stream, username, password).await
+        } else {
+            self.bind_on_stream(stream, username, password).await
+        }
+    }
+
+    async fn bind_on_stream<S>(
+        &self,
+        mut stream: S,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<LdapBindResult>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut reader = LdapMessageReader::new(self.config.rsp_max_size);
+        let dn = self.config.bind_dn(&proto::escape_dn_value(username));
+
+        let req = proto::bind_request(BIND_MESSAGE_ID, &dn, password);
+        stream
+            .write_all(&req)
+            .await
+            .map_err(|e| anyhow!("failed to send bind request: {e}"))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| anyhow!("failed to send bind request: {e}"))?;
+        let msg = self
+            .recv_message(&mut reader, &mut stream, BIND_MESSAGE_ID)
+            .await?;
+        match msg.response {
+            LdapResponse::Bind(r) => match r.code {
+                proto::RESULT_SUCCESS => {}
+                proto::RESULT_INVALID_CREDENTIALS => {
+                    return Ok(LdapBindResult::InvalidCredentials);
+                }
+                code => {
+                    return Err(anyhow!(
+                        "bind failed with result code {code}: {}",
+                        r.diagnostic
+                    ));
+                }
+            },
+            _ => return Err(anyhow!("unexpected response for bind request")),
+        }
+
+        let mut groups = Vec::new();
+        if !self.config.group_map.is_empty() {
+            let req =
+                proto::search_base_request(SEARCH_MESSAGE_ID, &dn, &self.config.group_attribute);
+            stream
+                .write_all(&req)
+                .await
+                .map_err(|e| anyhow!("failed to send search request: {e}"))?;
+            stream
+                .flush()
+                .await
+                .map_err(|e| anyhow!("failed to send search request: {e}"))?;
+            loop {
+                let msg = self
+                    .recv_message(&mut reader, &mut stream, SEARCH_MESSAGE_ID)
+                    .await?;
+                match msg.response {
+                    LdapResponse::SearchEntry(attributes) => {
+                        for (name, values) in attributes {
+                            if !name.eq_ignore_ascii_case(&self.config.group_attribute) {
+                                continue;
+                            }
+                            for v in values {
+                                groups.push(String::from_utf8_lossy(&v).to_string());
+                            }
+                        }
+                    }
+                    LdapResponse::SearchDone(r) => match r.code {
+                        proto::RESULT_SUCCESS | proto::RESULT_NO_SUCH_OBJECT => break,
+                        code => {
+                            return Err(anyhow!(
+                                "search failed with result code {code}: {}",
+                                r.diagnostic
+                            ));
+                        }
+                    },
+                    // search result references are ignored
+                    _ => {}
+                }
+            }
+        }
+
+        let req = proto::unbind_request(UNBIND_MESSAGE_ID);
+        let _ = stream.write_all(&req).await;
+        let _ = stream.shutdown().await;
+        Ok(LdapBindResult::Success(groups))
+    }
+
+    async fn recv_message<R>(
+        &self,
+        reader: &mut LdapMessageReader,
+        stream: &mut R,
+        id: i32,
+    ) -> anyhow::Result<LdapMessage>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            let msg = reader
+                .read_message(stream)
+                .await
+                .map_err(|e| anyhow!("failed to read response: {e}"))?;
+            // unsolicited notifications use message id 0, see rfc4511 Section 4.4
+            if msg.id == id as u64 {
+                return Ok(msg);
+            }
+            if let LdapResponse::Other(tag) = msg.response
+                && msg.id == 0
+            {
+                return Err(anyhow!("unsolicited notification {tag:#x} received"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use arc_swap::ArcSwapOption;
+    use tokio::net::TcpListener;
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use g3_types::auth::Password;
+    use g3_yaml::YamlDocPosition;
+
+    use crate::auth::UserGroup;
+    use crate::config::auth::{AnyUserGroupConfig, BasicUserGroupConfig};
+
+    const BIND_SUCCESS: [u8; 14] = [
+        0x30, 0x0c, 0x02, 0x01, 0x01, 0x61, 0x07, 0x0a, 0x01, 0x00, 0x04, 0x00, 0x04, 0x00,
+    ];
+    const BIND_INVALID_CREDENTIALS: [u8; 14] = [
+        0x30, 0x0c, 0x02, 0x01, 0x01, 0x61, 0x07, 0x0a, 0x01, 0x31, 0x04, 0x00, 0x04, 0x00,
+    ];
+    const SEARCH_ENTRY: [u8; 36] = [
+        0x30, 0x22, 0x02, 0x01, 0x02, 0x64, 0x1d, 0x04, 0x04, b'c', b'n', b'=', b'a', 0x30, 0x15,
+        0x30, 0x13, 0x04, 0x08, b'm', b'e', b'm', b'b', b'e', b'r', b'O', b'f', 0x31, 0x07, 0x04,
+        0x05, b'c', b'n', b'=', b'g', b'1',
+    ];
+    const SEARCH_DONE: [u8; 14] = [
+        0x30, 0x0c, 0x02, 0x01, 0x02, 0x65, 0x07, 0x0a, 0x01, 0x00, 0x04, 0x00, 0x04, 0x00,
+    ];
+
+    /// A fake ldap server which only accepts user bob with password ldap-pass, in group g1
+    async fn run_fake_server(listener: TcpListener) {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut reader = LdapMessageReader::new(4096);
+                let Ok(req) = reader.read_tag(&mut stream).await else {
+                    return;
+                };
+                let expected =
+                    proto::bind_request(BIND_MESSAGE_ID, "uid=bob,ou=people", "ldap-pass");
+                let (_, expected) = lber::parse::parse_tag(&expected).unwrap();
+                if req != expected {
+                    let _ = stream.write_all(&BIND_INVALID_CREDENTIALS).await;
+                    return;
+                }
+                let _ = stream.write_all(&BIND_SUCCESS).await;
+                if reader.read_tag(&mut stream).await.is_err() {
+                    return;
+                }
+                let _ = stream.write_all(&SEARCH_ENTRY).await;
+                let _ = stream.write_all(&SEARCH_DONE).await;
+                let _ = reader.read_tag(&mut stream).await;
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn mapped_static_user() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(run_fake_server(listener));
+
+        // the token of root is generated from password toor
+        let conf = format!(
+            r#"
+            name: ldap
+            static_users:
+              - name: root
+                token:
+                  salt: 113323bdab6fd2cc
+                  md5: 5c81f2becadde7fa5fde9026652ccc84
+                  sha1: ff9d5c1a14328dd85ee95d4e574bd0558a1dfa96
+            ldap_auth:
+              url: ldap://127.0.0.1:{port}
+              bind_dn: "uid={{username}},ou=people"
+              group_map:
+                g1: root
+            "#
+        );
+        let doc = YamlLoader::load_from_str(&conf).unwrap().pop().unwrap();
+        let Yaml::Hash(map) = doc else {
+            panic!("not a map");
+        };
+        let position = YamlDocPosition {
+            path: PathBuf::from("/etc/g3proxy/user_group.yaml"),
+            index: 0,
+        };
+        let config = BasicUserGroupConfig::parse(&map, Some(position)).unwrap();
+        let group = UserGroup::new_with_config(AnyUserGroupConfig::Basic(config))
+            .await
+            .unwrap();
+
+        let client_addr = SocketAddr::from(([127, 0, 0, 1], 1000));
+        let server_name = NodeName::from_str("test").unwrap();
+        let server_extra_tags = Arc::new(ArcSwapOption::empty());
+        let check = |username: &'static str, password: &'static str| {
+            let group = group.clone();
+            let server_name = server_name.clone();
+            let server_extra_tags = server_extra_tags.clone();
+            async move {
+                let password = Password::from_original(password).unwrap();
+                group
+                    .check_user_with_password(
+                        username,
+                        &password,
+                        client_addr,
+                        None,
+                        &server_name,
+                        &server_extra_tags,
+                    )
+                    .await
+            }
+        };
+
+        let user_ctx = check("bob", "ldap-pass").await.unwrap();
+        assert_eq!(user_ctx.user_name().as_str(), "root");
+        assert!(check("bob", "toor").await.is_err());
+        assert!(check("root", "toor").await.is_ok());
+        assert!(check("root", "ldap-pass").await.is_err());
+
+        group.stop_fetch_job();
+    }
+}
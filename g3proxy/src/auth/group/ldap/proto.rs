@@ -0,0 +1,419 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::anyhow;
+use bytes::BytesMut;
+use lber::common::TagClass;
+use lber::structure::StructureTag;
+use lber::structures::{ASNTag, Boolean, Enumerated, Integer, OctetString, Sequence, Tag};
+use lber::universal::Types;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const OP_BIND_REQUEST: u64 = 0;
+const OP_BIND_RESPONSE: u64 = 1;
+const OP_UNBIND_REQUEST: u64 = 2;
+const OP_SEARCH_REQUEST: u64 = 3;
+const OP_SEARCH_RESULT_ENTRY: u64 = 4;
+const OP_SEARCH_RESULT_DONE: u64 = 5;
+
+const AUTH_SIMPLE: u64 = 0;
+const FILTER_PRESENT: u64 = 7;
+
+pub(super) const RESULT_SUCCESS: u32 = 0;
+pub(super) const RESULT_NO_SUCH_OBJECT: u32 = 32;
+pub(super) const RESULT_INVALID_CREDENTIALS: u32 = 49;
+
+fn octet_string(id: u64, class: TagClass, value: &str) -> Tag {
+    Tag::OctetString(OctetString {
+        id,
+        class,
+        inner: value.as_bytes().to_vec(),
+    })
+}
+
+fn encode_message(id: i32, op: Tag) -> Vec<u8> {
+    let msg = Tag::Sequence(Sequence {
+        inner: vec![
+            Tag::Integer(Integer {
+                inner: id as i64,
+                ..Default::default()
+            }),
+            op,
+        ],
+        ..Default::default()
+    });
+    let mut buf = BytesMut::new();
+    // encoding into memory never fails
+    let _ = lber::write::encode_into(&mut buf, msg.into_structure());
+    buf.to_vec()
+}
+
+/// encode a simple BindRequest, see rfc4511 Section 4.2
+pub(super) fn bind_request(id: i32, dn: &str, password: &str) -> Vec<u8> {
+    let op = Tag::Sequence(Sequence {
+        id: OP_BIND_REQUEST,
+        class: TagClass::Application,
+        inner: vec![
+            Tag::Integer(Integer {
+                inner: 3,
+                ..Default::default()
+            }),
+            octet_string(Types::OctetString as u64, TagClass::Universal, dn),
+            octet_string(AUTH_SIMPLE, TagClass::Context, password),
+        ],
+    });
+    encode_message(id, op)
+}
+
+/// encode a SearchRequest to read one attribute of the base object, see rfc4511 Section 4.5.1
+pub(super) fn search_base_request(id: i32, dn: &str, attribute: &str) -> Vec<u8> {
+    let op = Tag::Sequence(Sequence {
+        id: OP_SEARCH_REQUEST,
+        class: TagClass::Application,
+        inner: vec![
+            octet_string(Types::OctetString as u64, TagClass::Universal, dn),
+            // baseObject
+            Tag::Enumerated(Enumerated {
+                inner: 0,
+                ..Default::default()
+            }),
+            // neverDerefAliases
+            Tag::Enumerated(Enumerated {
+                inner: 0,
+                ..Default::default()
+            }),
+            // sizeLimit
+            Tag::Integer(Integer {
+                inner: 1,
+                ..Default::default()
+            }),
+            // timeLimit
+            Tag::Integer(Integer {
+                inner: 0,
+                ..Default::default()
+            }),
+            // typesOnly
+            Tag::Boolean(Boolean {
+                inner: false,
+                ..Default::default()
+            }),
+            octet_string(FILTER_PRESENT, TagClass::Context, "objectClass"),
+            Tag::Sequence(Sequence {
+                inner: vec![octet_string(
+                    Types::OctetString as u64,
+                    TagClass::Universal,
+                    attribute,
+                )],
+                ..Default::default()
+            }),
+        ],
+    });
+    encode_message(id, op)
+}
+
+pub(super) fn unbind_request(id: i32) -> Vec<u8> {
+    let op = Tag::OctetString(OctetString {
+        id: OP_UNBIND_REQUEST,
+        class: TagClass::Application,
+        inner: Vec::new(),
+    });
+    encode_message(id, op)
+}
+
+fn expect_universal(tag: StructureTag, ty: Types) -> anyhow::Result<StructureTag> {
+    let id = tag.id;
+    tag.match_class(TagClass::Universal)
+        .and_then(|t| t.match_id(ty as u64))
+        .ok_or_else(|| anyhow!("unexpected ber tag {id}"))
+}
+
+fn expect_sequence(tag: StructureTag) -> anyhow::Result<Vec<StructureTag>> {
+    expect_universal(tag, Types::Sequence)?
+        .expect_constructed()
+        .ok_or_else(|| anyhow!("ber sequence is not constructed"))
+}
+
+fn expect_octet_string(tag: StructureTag) -> anyhow::Result<Vec<u8>> {
+    expect_universal(tag, Types::OctetString)?
+        .expect_primitive()
+        .ok_or_else(|| anyhow!("ber octet string is not primitive"))
+}
+
+fn expect_uint(tag: StructureTag, ty: Types) -> anyhow::Result<u64> {
+    let value = expect_universal(tag, ty)?
+        .expect_primitive()
+        .ok_or_else(|| anyhow!("ber integer is not primitive"))?;
+    match value.first() {
+        Some(b) if *b & 0x80 == 0 && value.len() <= 8 => {}
+        _ => return Err(anyhow!("invalid ber unsigned integer")),
+    }
+    let (_, v) =
+        lber::parse::parse_uint(&value).map_err(|e| anyhow!("invalid ber integer: {e}"))?;
+    Ok(v)
+}
+
+fn next_tag<I>(iter: &mut I) -> anyhow::Result<StructureTag>
+where
+    I: Iterator<Item = StructureTag>,
+{
+    iter.next().ok_or_else(|| anyhow!("no enough ber values"))
+}
+
+pub(super) struct LdapResult {
+    pub(super) code: u32,
+    pub(super) diagnostic: String,
+}
+
+impl LdapResult {
+    fn parse(tags: Vec<StructureTag>) -> anyhow::Result<Self> {
+        let mut iter = tags.into_iter();
+        let code = expect_uint(next_tag(&mut iter)?, Types::Enumerated)?;
+        let code = u32::try_from(code).map_err(|_| anyhow!("invalid result code {code}"))?;
+        let _matched_dn = expect_octet_string(next_tag(&mut iter)?)?;
+        let diagnostic = expect_octet_string(next_tag(&mut iter)?)?;
+        Ok(LdapResult {
+            code,
+            diagnostic: String::from_utf8_lossy(&diagnostic).to_string(),
+        })
+    }
+}
+
+pub(super) enum LdapResponse {
+    Bind(LdapResult),
+    SearchEntry(Vec<(String, Vec<Vec<u8>>)>),
+    SearchDone(LdapResult),
+    Other(u64),
+}
+
+pub(super) struct LdapMessage {
+    pub(super) id: u64,
+    pub(super) response: LdapResponse,
+}
+
+impl LdapMessage {
+    pub(super) fn parse(tag: StructureTag) -> anyhow::Result<Self> {
+        let mut iter = expect_sequence(tag)?.into_iter();
+        let id = expect_uint(next_tag(&mut iter)?, Types::Integer)?;
+        let op = next_tag(&mut iter)?;
+        if op.class != TagClass::Application {
+            return Err(anyhow!("unexpected ldap protocol op class {:?}", op.class));
+        }
+        let op_id = op.id;
+        let Some(content) = op.expect_constructed() else {
+            return Ok(LdapMessage {
+                id,
+                response: LdapResponse::Other(op_id),
+            });
+        };
+        let response = match op_id {
+            OP_BIND_RESPONSE => LdapResponse::Bind(LdapResult::parse(content)?),
+            OP_SEARCH_RESULT_DONE => LdapResponse::SearchDone(LdapResult::parse(content)?),
+            OP_SEARCH_RESULT_ENTRY => {
+                let mut iter = content.into_iter();
+                let _object_name = expect_octet_string(next_tag(&mut iter)?)?;
+                let mut attributes = Vec::new();
+                for attr in expect_sequence(next_tag(&mut iter)?)? {
+                    let mut attr = expect_sequence(attr)?.into_iter();
+                    let name = expect_octet_string(next_tag(&mut attr)?)?;
+                    let vals = expect_universal(next_tag(&mut attr)?, Types::Set)?
+                        .expect_constructed()
+                        .ok_or_else(|| anyhow!("ber set is not constructed"))?;
+                    let mut values = Vec::with_capacity(vals.len());
+                    for v in vals {
+                        values.push(expect_octet_string(v)?);
+                    }
+                    attributes.push((String::from_utf8_lossy(&name).to_string(), values));
+                }
+                LdapResponse::SearchEntry(attributes)
+            }
+            _ => LdapResponse::Other(op_id),
+        };
+        Ok(LdapMessage { id, response })
+    }
+}
+
+/// read ber encoded ldap messages from the stream
+pub(super) struct LdapMessageReader {
+    buf: BytesMut,
+    max_size: usize,
+}
+
+impl LdapMessageReader {
+    pub(super) fn new(max_size: usize) -> Self {
+        LdapMessageReader {
+            buf: BytesMut::with_capacity(4096),
+            max_size,
+        }
+    }
+
+    /// read the full ber value of the next message
+    pub(super) async fn read_tag<R>(&mut self, reader: &mut R) -> anyhow::Result<StructureTag>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            if !self.buf.is_empty() {
+                match lber::parse::parse_tag(&self.buf) {
+                    Ok((left, tag)) => {
+                        let consumed = self.buf.len() - left.len();
+                        let _ = self.buf.split_to(consumed);
+                        return Ok(tag);
+                    }
+                    Err(e) if e.is_incomplete() => {}
+                    Err(e) => return Err(anyhow!("invalid ber data: {e}")),
+                }
+            }
+            if self.buf.len() >= self.max_size {
+                return Err(anyhow!(
+                    "ldap message size is larger than {}",
+                    self.max_size
+                ));
+            }
+            let nr = reader.read_buf(&mut self.buf).await?;
+            if nr == 0 {
+                return Err(anyhow!("connection closed"));
+            }
+        }
+    }
+
+    pub(super) async fn read_message<R>(&mut self, reader: &mut R) -> anyhow::Result<LdapMessage>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let tag = self.read_tag(reader).await?;
+        LdapMessage::parse(tag)
+    }
+}
+
+/// escape the attribute value for use in a dn, see rfc4514 Section 2.4
+pub(super) fn escape_dn_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let last = value.chars().count().saturating_sub(1);
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' if i == 0 => escaped.push_str("\\#"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\ "),
+            '\0' => escaped.push_str("\\00"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// get the value of the first rdn in the dn
+pub(super) fn first_rdn_value(dn: &str) -> Option<&str> {
+    let mut escaped = false;
+    let mut end = dn.len();
+    for (i, c) in dn.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' | '+' => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    let rdn = &dn[..end];
+    rdn.split_once('=').map(|(_, v)| v.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(data: &[u8]) -> LdapMessage {
+        let (left, tag) = lber::parse::parse_tag(data).unwrap();
+        assert!(left.is_empty());
+        LdapMessage::parse(tag).unwrap()
+    }
+
+    #[test]
+    fn bind() {
+        let data = bind_request(1, "cn=a", "pw");
+        assert_eq!(
+            data,
+            [
+                0x30, 0x12, 0x02, 0x01, 0x01, 0x60, 0x0d, 0x02, 0x01, 0x03, 0x04, 0x04, b'c', b'n',
+                b'=', b'a', 0x80, 0x02, b'p', b'w'
+            ]
+        );
+
+        let rsp = [
+            0x30, 0x0c, 0x02, 0x01, 0x01, 0x61, 0x07, 0x0a, 0x01, 0x31, 0x04, 0x00, 0x04, 0x00,
+        ];
+        let msg = parse(&rsp);
+        assert_eq!(msg.id, 1);
+        let LdapResponse::Bind(result) = msg.response else {
+            panic!("not bind response");
+        };
+        assert_eq!(result.code, RESULT_INVALID_CREDENTIALS);
+    }
+
+    #[test]
+    fn search() {
+        let data = search_base_request(2, "cn=a", "memberOf");
+        let (_, tag) = lber::parse::parse_tag(&data).unwrap();
+        let mut iter = expect_sequence(tag).unwrap().into_iter();
+        assert_eq!(
+            expect_uint(iter.next().unwrap(), Types::Integer).unwrap(),
+            2
+        );
+        let op = iter.next().unwrap();
+        assert_eq!(op.class, TagClass::Application);
+        assert_eq!(op.id, OP_SEARCH_REQUEST);
+        assert_eq!(op.expect_constructed().unwrap().len(), 8);
+
+        let rsp = [
+            0x30, 0x22, 0x02, 0x01, 0x02, 0x64, 0x1d, 0x04, 0x04, b'c', b'n', b'=', b'a', 0x30,
+            0x15, 0x30, 0x13, 0x04, 0x08, b'm', b'e', b'm', b'b', b'e', b'r', b'O', b'f', 0x31,
+            0x07, 0x04, 0x05, b'c', b'n', b'=', b'g', b'1',
+        ];
+        let msg = parse(&rsp);
+        assert_eq!(msg.id, 2);
+        let LdapResponse::SearchEntry(attributes) = msg.response else {
+            panic!("not search entry");
+        };
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0].0, "memberOf");
+        assert_eq!(attributes[0].1, [b"cn=g1".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn read_message() {
+        let rsp = [
+            0x30, 0x0c, 0x02, 0x01, 0x01, 0x61, 0x07, 0x0a, 0x01, 0x00, 0x04, 0x00, 0x04, 0x00,
+        ];
+        let mut data = rsp.to_vec();
+        data.extend_from_slice(&rsp);
+        let mut stream = &data[..];
+        let mut reader = LdapMessageReader::new(64);
+        for _ in 0..2 {
+            let msg = reader.read_message(&mut stream).await.unwrap();
+            assert_eq!(msg.id, 1);
+        }
+        assert!(reader.read_message(&mut stream).await.is_err());
+
+        let mut stream = &data[..10];
+        let mut reader = LdapMessageReader::new(8);
+        assert!(reader.read_message(&mut stream).await.is_err());
+    }
+
+    #[test]
+    fn dn() {
+        assert_eq!(escape_dn_value("a,b=c"), "a\\,b\\=c");
+        assert_eq!(escape_dn_value(" #a "), "\\ #a\\ ");
+        assert_eq!(escape_dn_value("#a"), "\\#a");
+
+        assert_eq!(first_rdn_value("cn=admins,ou=groups"), Some("admins"));
+        assert_eq!(first_rdn_value("cn=a\\,b,ou=groups"), Some("a\\,b"));
+        assert_eq!(first_rdn_value("admins"), None);
+    }
+}
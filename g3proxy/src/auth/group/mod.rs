@@ -5,22 +5,26 @@
 
 use std::net::SocketAddr;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use ahash::AHashMap;
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use arc_swap::{ArcSwap, ArcSwapOption};
 use arcstr::ArcStr;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use log::{info, warn};
+use serde_json::{Map, Value};
 use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 use g3_types::auth::{Password, UserAuthError};
 use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::UpstreamAddr;
 
 use super::{User, UserContext, UserType, source};
 use crate::config::auth::{AnyUserGroupConfig, UserConfig, UserGroupConfig};
+use crate::serve::LiveTask;
 
 mod basic;
 pub(crate) use basic::BasicUserGroup;
@@ -28,6 +32,20 @@ pub(crate) use basic::BasicUserGroup;
 mod facts;
 pub(crate) use facts::FactsUserGroup;
 
+mod cache;
+
+mod external;
+use external::{ExternalAuthCredential, ExternalUserAuth};
+
+mod ldap;
+use ldap::LdapUserAuth;
+
+mod kerberos;
+use kerberos::KerberosUserAuth;
+
+mod overrides;
+use overrides::UserOverride;
+
 #[derive(Clone)]
 pub(crate) enum UserGroup {
     Basic(Arc<BasicUserGroup>),
@@ -123,7 +141,7 @@ impl UserGroup {
         }
     }
 
-    pub(crate) fn all_static_users(&self) -> Vec<&str> {
+    pub(crate) fn all_static_users(&self) -> Vec<String> {
         match self {
             UserGroup::Basic(v) => v.base().all_static_users(),
             UserGroup::Facts(v) => v.base().all_static_users(),
@@ -144,6 +162,75 @@ impl UserGroup {
         }
     }
 
+    pub(crate) fn get_user_info(&self, username: &str) -> anyhow::Result<String> {
+        match self {
+            UserGroup::Basic(v) => v.base().get_user_info(username),
+            UserGroup::Facts(v) => v.base().get_user_info(username),
+        }
+    }
+
+    pub(crate) fn block_user(&self, username: &str, delay: Duration) -> anyhow::Result<()> {
+        match self {
+            UserGroup::Basic(v) => v.base().block_user(username, delay),
+            UserGroup::Facts(v) => {
+                v.base().block_user(username, delay)?;
+                v.rebuild_match_table();
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn unblock_user(&self, username: &str) -> anyhow::Result<()> {
+        match self {
+            UserGroup::Basic(v) => v.base().unblock_user(username),
+            UserGroup::Facts(v) => {
+                v.base().unblock_user(username)?;
+                v.rebuild_match_table();
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn set_user_limit(&self, username: &str, limits: &str) -> anyhow::Result<()> {
+        match self {
+            UserGroup::Basic(v) => v.base().set_user_limit(username, limits),
+            UserGroup::Facts(v) => {
+                v.base().set_user_limit(username, limits)?;
+                v.rebuild_match_table();
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn reset_user(&self, username: &str) -> anyhow::Result<()> {
+        match self {
+            UserGroup::Basic(v) => v.base().reset_user(username),
+            UserGroup::Facts(v) => {
+                v.base().reset_user(username)?;
+                v.rebuild_match_table();
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn list_user_tasks(&self, username: &str) -> anyhow::Result<Vec<Arc<LiveTask>>> {
+        match self {
+            UserGroup::Basic(v) => v.base().list_user_tasks(username),
+            UserGroup::Facts(v) => v.base().list_user_tasks(username),
+        }
+    }
+
+    pub(crate) fn kill_user_task(
+        &self,
+        username: &str,
+        task_id: Option<Uuid>,
+    ) -> anyhow::Result<usize> {
+        match self {
+            UserGroup::Basic(v) => v.base().kill_user_task(username, task_id),
+            UserGroup::Facts(v) => v.base().kill_user_task(username, task_id),
+        }
+    }
+
     pub(super) async fn save_dynamic_users(
         &self,
         contents: &str,
@@ -166,35 +253,126 @@ impl UserGroup {
         }
     }
 
-    pub(crate) fn check_user_with_password(
+    pub(crate) async fn check_user_with_password(
         &self,
         username: &str,
         password: &Password,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
         server_name: &NodeName,
         server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
     ) -> Result<UserContext, UserAuthError> {
         match self {
-            UserGroup::Basic(v) => v.base().check_user_with_password(
-                username,
-                password,
-                server_name,
-                server_extra_tags,
-            ),
+            UserGroup::Basic(v) => {
+                v.base()
+                    .check_user_with_password(
+                        username,
+                        password,
+                        client_addr,
+                        target,
+                        server_name,
+                        server_extra_tags,
+                    )
+                    .await
+            }
             UserGroup::Facts(_) => Err(UserAuthError::NoSuchUser),
         }
     }
+
+    pub(crate) async fn check_user_with_token(
+        &self,
+        token: &str,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        match self {
+            UserGroup::Basic(v) => {
+                v.base()
+                    .check_user_with_token(
+                        token,
+                        client_addr,
+                        target,
+                        server_name,
+                        server_extra_tags,
+                    )
+                    .await
+            }
+            UserGroup::Facts(v) => {
+                v.base()
+                    .check_user_with_token(
+                        token,
+                        client_addr,
+                        target,
+                        server_name,
+                        server_extra_tags,
+                    )
+                    .await
+            }
+        }
+    }
+
+    pub(crate) fn allow_negotiate(&self) -> bool {
+        match self {
+            UserGroup::Basic(v) => v.base().kerberos_auth.is_some(),
+            UserGroup::Facts(_) => false,
+        }
+    }
+
+    pub(crate) fn check_user_with_negotiate(
+        &self,
+        token: &[u8],
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        match self {
+            UserGroup::Basic(v) => {
+                v.base()
+                    .check_user_with_negotiate(token, server_name, server_extra_tags)
+            }
+            UserGroup::Facts(v) => {
+                v.base()
+                    .check_user_with_negotiate(token, server_name, server_extra_tags)
+            }
+        }
+    }
+
+    /// get the user that has already been authenticated by negotiate auth on the connection
+    pub(crate) fn get_negotiated_user(
+        &self,
+        username: &str,
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        match self {
+            UserGroup::Basic(v) => {
+                v.base()
+                    .get_negotiated_user(username, server_name, server_extra_tags)
+            }
+            UserGroup::Facts(v) => {
+                v.base()
+                    .get_negotiated_user(username, server_name, server_extra_tags)
+            }
+        }
+    }
 }
 
 struct BaseUserGroup<T: UserGroupConfig> {
     config: Arc<T>,
-    static_users: Arc<AHashMap<ArcStr, Arc<User>>>,
+    static_users: Arc<ArcSwap<AHashMap<ArcStr, Arc<User>>>>,
     dynamic_key: Uuid,
     dynamic_users: Arc<ArcSwap<AHashMap<ArcStr, Arc<User>>>>,
+    /// the runtime changes made via the control socket, which will be kept after reload
+    user_overrides: Mutex<AHashMap<ArcStr, UserOverride>>,
     /// the job for dynamic fetch
     fetch_quit_sender: Option<mpsc::Sender<()>>,
     // the job for user expire check
     check_quit_sender: Option<oneshot::Sender<()>>,
     anonymous_user: Option<Arc<User>>,
+    external_auth: Option<ExternalUserAuth>,
+    ldap_auth: Option<LdapUserAuth>,
+    kerberos_auth: Option<KerberosUserAuth>,
 }
 
 impl<T: UserGroupConfig> Drop for BaseUserGroup<T> {
@@ -216,12 +394,16 @@ where
     fn new_without_users(config: T) -> Self {
         BaseUserGroup {
             config: Arc::new(config),
-            static_users: Arc::new(AHashMap::new()),
+            static_users: Arc::new(ArcSwap::from_pointee(AHashMap::new())),
             dynamic_key: Uuid::new_v4(),
             dynamic_users: Arc::new(ArcSwap::from_pointee(AHashMap::new())),
+            user_overrides: Mutex::new(AHashMap::new()),
             fetch_quit_sender: None,
             check_quit_sender: None,
             anonymous_user: None,
+            external_auth: None,
+            ldap_auth: None,
+            kerberos_auth: None,
         }
     }
 
@@ -246,7 +428,7 @@ where
         let mut group = Self::new_without_users(config);
         let basic_config = group.config.basic_config();
 
-        group.static_users = Arc::new(users);
+        group.static_users.store(Arc::new(users));
         if let Some(source) = &basic_config.dynamic_source {
             match source::load_initial_users(basic_config, source).await {
                 Ok(cached_users) => {
@@ -267,6 +449,18 @@ where
         }
 
         group.anonymous_user = anonymous_user;
+        group.external_auth = basic_config
+            .external_auth
+            .as_ref()
+            .map(|c| ExternalUserAuth::new(c, basic_config.name()));
+        group.ldap_auth = basic_config
+            .ldap_auth
+            .as_ref()
+            .map(|c| LdapUserAuth::new(c, basic_config.name()));
+        group.kerberos_auth = match &basic_config.kerberos_auth {
+            Some(c) => Some(KerberosUserAuth::new(c, basic_config.name())?),
+            None => None,
+        };
 
         group.fetch_quit_sender = Some(source::new_fetch_job(
             group.config.clone(),
@@ -285,13 +479,17 @@ where
         let basic_config = config.basic_config();
 
         let datetime_now = Utc::now();
+        let user_overrides = self.user_overrides.lock().unwrap().clone();
+        let old_static_users = self.static_users.load();
         let mut static_users = AHashMap::new();
         for (username, user_config) in &basic_config.static_users {
-            let user = if let Some(user) = self.static_users.get(username) {
-                user.new_for_reload(user_config, &datetime_now)?
-            } else {
-                User::new(basic_config.name(), user_config, &datetime_now)?
-            };
+            let user = Self::build_user(
+                basic_config.name(),
+                old_static_users.get(username),
+                user_config,
+                user_overrides.get(username),
+                &datetime_now,
+            )?;
             static_users.insert(username.clone(), Arc::new(user));
         }
 
@@ -321,12 +519,25 @@ where
         let mut group = Self::new_without_users(config);
         let basic_config = group.config.basic_config();
 
-        group.static_users = Arc::new(static_users);
+        group.static_users.store(Arc::new(static_users));
         if !dynamic_users.is_empty() {
             group.dynamic_users.store(Arc::new(dynamic_users));
         }
+        group.user_overrides = Mutex::new(user_overrides);
 
         group.anonymous_user = anonymous_user;
+        group.external_auth = basic_config
+            .external_auth
+            .as_ref()
+            .map(|c| ExternalUserAuth::new(c, basic_config.name()));
+        group.ldap_auth = basic_config
+            .ldap_auth
+            .as_ref()
+            .map(|c| LdapUserAuth::new(c, basic_config.name()));
+        group.kerberos_auth = match &basic_config.kerberos_auth {
+            Some(c) => Some(KerberosUserAuth::new(c, basic_config.name())?),
+            None => None,
+        };
 
         group.fetch_quit_sender = Some(source::new_fetch_job(
             group.config.clone(),
@@ -376,16 +587,20 @@ where
         }
 
         let datetime_now = Utc::now();
+        // hold the lock so the runtime overrides won't be lost
+        let user_overrides = self.user_overrides.lock().unwrap();
         let old_dynamic_users = self.dynamic_users.load();
         let mut new_dynamic_users = AHashMap::new();
         for user_config in dynamic_config {
             let user_config = Arc::new(user_config);
             let username = user_config.name();
-            let user = if let Some(old_user) = old_dynamic_users.get(username) {
-                old_user.new_for_reload(&user_config, &datetime_now)?
-            } else {
-                User::new(basic_config.name(), &user_config, &datetime_now)?
-            };
+            let user = Self::build_user(
+                basic_config.name(),
+                old_dynamic_users.get(username),
+                &user_config,
+                user_overrides.get(username),
+                &datetime_now,
+            )?;
             new_dynamic_users.insert(username.clone(), Arc::new(user));
         }
 
@@ -393,6 +608,158 @@ where
         Ok(())
     }
 
+    fn build_user(
+        group: &NodeName,
+        old_user: Option<&Arc<User>>,
+        config: &Arc<UserConfig>,
+        user_override: Option<&UserOverride>,
+        datetime_now: &DateTime<Utc>,
+    ) -> anyhow::Result<User> {
+        let Some(user_override) = user_override else {
+            return match old_user {
+                Some(old_user) => old_user.new_for_reload(config, datetime_now),
+                None => User::new(group, config, datetime_now),
+            };
+        };
+
+        let applied_config = user_override.apply(config).context(format!(
+            "failed to apply runtime overrides to user {}",
+            config.name()
+        ))?;
+        let applied_config = Arc::new(applied_config);
+        let mut user = match old_user {
+            Some(old_user) => old_user.new_for_reload(&applied_config, datetime_now)?,
+            None => User::new(group, &applied_config, datetime_now)?,
+        };
+        user.set_origin_config(config);
+        Ok(user)
+    }
+
+    fn update_user_override<F>(&self, username: &str, update: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(Option<UserOverride>) -> anyhow::Result<Option<UserOverride>>,
+    {
+        let mut user_overrides = self.user_overrides.lock().unwrap();
+        let Some((old_user, user_type)) = self.get_local_user(username) else {
+            return Err(anyhow!("no user {username} found"));
+        };
+
+        let user_override = update(user_overrides.get(username).cloned())?;
+        let origin_config = Arc::clone(old_user.origin_config());
+        let user = Self::build_user(
+            self.config.basic_config().name(),
+            Some(&old_user),
+            &origin_config,
+            user_override.as_ref(),
+            &Utc::now(),
+        )?;
+        let user = Arc::new(user);
+
+        let users_container = match user_type {
+            UserType::Static => &self.static_users,
+            _ => &self.dynamic_users,
+        };
+        let username = origin_config.name();
+        users_container.rcu(|users| {
+            let mut users = AHashMap::clone(users);
+            users.insert(username.clone(), Arc::clone(&user));
+            users
+        });
+
+        match user_override {
+            Some(v) => {
+                user_overrides.insert(username.clone(), v);
+            }
+            None => {
+                user_overrides.remove(username);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_user_info(&self, username: &str) -> anyhow::Result<String> {
+        let Some((user, user_type)) = self.get_local_user(username) else {
+            return Err(anyhow!("no user {username} found"));
+        };
+
+        let mut map = Map::new();
+        map.insert("name".to_string(), Value::String(username.to_string()));
+        let user_type = match user_type {
+            UserType::Static => "static",
+            _ => "dynamic",
+        };
+        map.insert("type".to_string(), Value::String(user_type.to_string()));
+        map.insert("blocked".to_string(), Value::Bool(user.is_blocked()));
+        map.insert("expired".to_string(), Value::Bool(user.is_expired()));
+        map.insert("tasks".to_string(), Value::from(user.tasks().count()));
+        map.insert(
+            "limits".to_string(),
+            Value::Object(user.config().dump_runtime_limits()),
+        );
+        let user_overrides = self.user_overrides.lock().unwrap();
+        if let Some(v) = user_overrides.get(username) {
+            map.insert("overrides".to_string(), Value::Object(v.dump()));
+        }
+        serde_json::to_string_pretty(&Value::Object(map))
+            .map_err(|e| anyhow!("failed to encode user info: {e}"))
+    }
+
+    fn block_user(&self, username: &str, delay: Duration) -> anyhow::Result<()> {
+        self.update_user_override(username, |v| {
+            let mut v = v.unwrap_or_default();
+            v.set_block(delay);
+            Ok(Some(v))
+        })
+    }
+
+    fn unblock_user(&self, username: &str) -> anyhow::Result<()> {
+        self.update_user_override(username, |v| {
+            let mut v = v.unwrap_or_default();
+            v.set_unblock();
+            Ok(Some(v))
+        })
+    }
+
+    fn set_user_limit(&self, username: &str, limits: &str) -> anyhow::Result<()> {
+        let limits = match Value::from_str(limits) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => return Err(anyhow!("the limits should be a json object")),
+            Err(e) => return Err(anyhow!("the limits is not valid json: {e}")),
+        };
+        self.update_user_override(username, |v| {
+            let mut v = v.unwrap_or_default();
+            v.merge_limits(limits);
+            Ok(Some(v))
+        })
+    }
+
+    fn reset_user(&self, username: &str) -> anyhow::Result<()> {
+        self.update_user_override(username, |_| Ok(None))
+    }
+
+    fn list_user_tasks(&self, username: &str) -> anyhow::Result<Vec<Arc<LiveTask>>> {
+        let Some((user, _)) = self.get_local_user(username) else {
+            return Err(anyhow!("no user {username} found"));
+        };
+        Ok(user.tasks().all())
+    }
+
+    fn kill_user_task(&self, username: &str, task_id: Option<Uuid>) -> anyhow::Result<usize> {
+        let Some((user, _)) = self.get_local_user(username) else {
+            return Err(anyhow!("no user {username} found"));
+        };
+        match task_id {
+            Some(id) => {
+                if user.tasks().kill(&id) {
+                    Ok(1)
+                } else {
+                    Err(anyhow!("no task {id} found for user {username}"))
+                }
+            }
+            None => Ok(user.tasks().kill_all()),
+        }
+    }
+
     fn allow_anonymous(&self, client_addr: SocketAddr) -> bool {
         let Some(user) = &self.anonymous_user else {
             return false;
@@ -406,8 +773,21 @@ where
             .map(|user| (user.clone(), UserType::Anonymous))
     }
 
+    fn get_local_user(&self, username: &str) -> Option<(Arc<User>, UserType)> {
+        let static_users = self.static_users.load();
+        if let Some(user) = static_users.get(username) {
+            return Some((Arc::clone(user), UserType::Static));
+        }
+
+        let dynamic_users = self.dynamic_users.load();
+        dynamic_users
+            .get(username)
+            .map(|user| (Arc::clone(user), UserType::Dynamic))
+    }
+
     fn get_user(&self, username: &str) -> Option<(Arc<User>, UserType)> {
-        if let Some(user) = self.static_users.get(username) {
+        let static_users = self.static_users.load();
+        if let Some(user) = static_users.get(username) {
             return Some((Arc::clone(user), UserType::Static));
         }
 
@@ -437,7 +817,8 @@ where
     where
         F: FnMut(&str, &Arc<User>),
     {
-        for (name, user) in self.static_users.iter() {
+        let static_users = self.static_users.load();
+        for (name, user) in static_users.iter() {
             f(name, user);
         }
     }
@@ -452,8 +833,9 @@ where
         }
     }
 
-    fn all_static_users(&self) -> Vec<&str> {
-        self.static_users.keys().map(|k| k.as_ref()).collect()
+    fn all_static_users(&self) -> Vec<String> {
+        let static_users = self.static_users.load();
+        static_users.keys().map(|k| k.to_string()).collect()
     }
 
     fn all_dynamic_users(&self) -> Vec<String> {
@@ -461,13 +843,59 @@ where
         dynamic_users.keys().map(|k| k.to_string()).collect()
     }
 
-    fn check_user_with_password(
+    async fn check_user_with_password(
         &self,
         username: &str,
         password: &Password,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
         server_name: &NodeName,
         server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
     ) -> Result<UserContext, UserAuthError> {
+        if let Some(ldap_auth) = &self.ldap_auth
+            && self.get_local_user(username).is_none()
+        {
+            let user = ldap_auth
+                .check(
+                    username,
+                    password.as_original(),
+                    &self.static_users.load_full(),
+                )
+                .await?;
+            let user_ctx = UserContext::new(
+                Some(username.into()),
+                user,
+                UserType::External,
+                server_name,
+                server_extra_tags,
+            );
+            // the password has been verified by the ldap server, and the mapped static user
+            // may have a token of its own, so only check the user state here
+            user_ctx.check_state()?;
+            return Ok(user_ctx);
+        }
+
+        if let Some(external_auth) = &self.external_auth
+            && self.get_local_user(username).is_none()
+        {
+            let credential = ExternalAuthCredential::Password {
+                username,
+                password: password.as_original(),
+            };
+            let (_, user) = external_auth
+                .check(credential, client_addr, target, server_name)
+                .await?;
+            let user_ctx = UserContext::new(
+                Some(username.into()),
+                user,
+                UserType::External,
+                server_name,
+                server_extra_tags,
+            );
+            user_ctx.check_password(password.as_original())?;
+            return Ok(user_ctx);
+        }
+
         let Some((user, user_type)) = self.get_user(username) else {
             return Err(UserAuthError::NoSuchUser);
         };
@@ -481,4 +909,74 @@ where
         user_ctx.check_password(password.as_original())?;
         Ok(user_ctx)
     }
+
+    async fn check_user_with_token(
+        &self,
+        token: &str,
+        client_addr: SocketAddr,
+        target: Option<&UpstreamAddr>,
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        let Some(external_auth) = &self.external_auth else {
+            // tokens can only be verified by the external auth service
+            return self
+                .get_anonymous_user()
+                .map(|(user, user_type)| {
+                    UserContext::new(None, user, user_type, server_name, server_extra_tags)
+                })
+                .ok_or(UserAuthError::NoUserSupplied);
+        };
+
+        let (username, user) = external_auth
+            .check(
+                ExternalAuthCredential::Token(token),
+                client_addr,
+                target,
+                server_name,
+            )
+            .await?;
+        let user_ctx = UserContext::new(
+            Some(username),
+            user,
+            UserType::External,
+            server_name,
+            server_extra_tags,
+        );
+        user_ctx.check_password(token)?;
+        Ok(user_ctx)
+    }
+
+    fn check_user_with_negotiate(
+        &self,
+        token: &[u8],
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        let Some(kerberos_auth) = &self.kerberos_auth else {
+            return Err(UserAuthError::NoUserSupplied);
+        };
+        let username = kerberos_auth.check(token)?;
+        self.get_negotiated_user(&username, server_name, server_extra_tags)
+    }
+
+    fn get_negotiated_user(
+        &self,
+        username: &str,
+        server_name: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<MetricTagMap>>,
+    ) -> Result<UserContext, UserAuthError> {
+        let Some((user, user_type)) = self.get_local_user(username) else {
+            return Err(UserAuthError::NoSuchUser);
+        };
+        let user_ctx = UserContext::new(
+            Some(username.into()),
+            user,
+            user_type,
+            server_name,
+            server_extra_tags,
+        );
+        user_ctx.check_state()?;
+        Ok(user_ctx)
+    }
 }
@@ -0,0 +1,52 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+
+use crate::config::auth::UserConfig;
+
+/// the user config changes made at runtime via the control socket
+#[derive(Clone, Default)]
+pub(super) struct UserOverride {
+    /// Some(None) means the user is unblocked even if it's blocked in config
+    block_and_delay: Option<Option<Duration>>,
+    limits: Map<String, Value>,
+}
+
+impl UserOverride {
+    pub(super) fn set_block(&mut self, delay: Duration) {
+        self.block_and_delay = Some(Some(delay));
+    }
+
+    pub(super) fn set_unblock(&mut self) {
+        self.block_and_delay = Some(None);
+    }
+
+    pub(super) fn merge_limits(&mut self, limits: Map<String, Value>) {
+        self.limits.extend(limits);
+    }
+
+    pub(super) fn apply(&self, config: &UserConfig) -> anyhow::Result<UserConfig> {
+        let mut config = config.new_with_runtime_limits(&self.limits)?;
+        if let Some(block_and_delay) = self.block_and_delay {
+            config.block_and_delay = block_and_delay;
+        }
+        Ok(config)
+    }
+
+    pub(super) fn dump(&self) -> Map<String, Value> {
+        let mut map = self.limits.clone();
+        if let Some(block_and_delay) = self.block_and_delay {
+            let v = match block_and_delay {
+                Some(delay) => Value::String(format!("{delay:?}")),
+                None => Value::Null,
+            };
+            map.insert("block_and_delay".to_string(), v);
+        }
+        map
+    }
+}
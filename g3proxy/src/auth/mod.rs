@@ -27,12 +27,14 @@ pub(crate) use stats::{
     UserTrafficStats, UserUpstreamTrafficSnapshot, UserUpstreamTrafficStats,
 };
 
+mod connect;
 mod source;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub(crate) enum UserType {
     Static,
     Dynamic,
+    External,
     Anonymous,
 }
 
@@ -41,6 +43,7 @@ impl UserType {
         match self {
             UserType::Static => "Static",
             UserType::Dynamic => "Dynamic",
+            UserType::External => "External",
             UserType::Anonymous => "Anonymous",
         }
     }
@@ -0,0 +1,399 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use http::{Method, header};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use url::Position;
+
+use g3_http::HttpBodyReader;
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_openssl::SslConnector;
+
+use crate::auth::connect::connect_upstream;
+use crate::config::auth::{UserConfig, UserDynamicHttpSource};
+
+const BODY_LINE_MAX_LEN: usize = 4096;
+
+/// the last good state of the http source, which will be kept across fetches
+#[derive(Default)]
+pub(super) struct HttpFetchState {
+    loaded: bool,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    records: IndexMap<String, Map<String, Value>>,
+}
+
+impl HttpFetchState {
+    fn dump(&self) -> Value {
+        Value::Array(
+            self.records
+                .values()
+                .map(|map| Value::Object(map.clone()))
+                .collect(),
+        )
+    }
+}
+
+struct HttpFetchResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+pub(super) async fn fetch_records(
+    source: &Arc<UserDynamicHttpSource>,
+    state: &mut HttpFetchState,
+) -> anyhow::Result<(String, Vec<UserConfig>)> {
+    let rsp = tokio::time::timeout(source.fetch_timeout, fetch(source, state))
+        .await
+        .map_err(|_| anyhow!("timed out to fetch users from {}", source.url))??;
+
+    if let Some(rsp) = rsp {
+        let doc: Value = serde_json::from_slice(&rsp.body)
+            .map_err(|e| anyhow!("response from {} is not valid json: {e}", source.url))?;
+        let records = apply_update(state, &doc)?;
+        let new_state = HttpFetchState {
+            loaded: true,
+            etag: rsp.etag,
+            last_modified: rsp.last_modified,
+            records,
+        };
+
+        // make sure all records are valid before we replace the last good state
+        let all = new_state.dump();
+        let all_config = UserConfig::parse_json_many(&all)?;
+        *state = new_state;
+        Ok((all.to_string(), all_config))
+    } else {
+        let all = state.dump();
+        let all_config = UserConfig::parse_json_many(&all)?;
+        Ok((all.to_string(), all_config))
+    }
+}
+
+/// apply the full or delta update to the last good state
+///
+/// The response should be either a seq of all users, or a map in the following format:
+///
+///   {"delta": true, "users": [...], "removed": ["name", ...]}
+fn apply_update(
+    state: &HttpFetchState,
+    doc: &Value,
+) -> anyhow::Result<IndexMap<String, Map<String, Value>>> {
+    match doc {
+        Value::Array(seq) => {
+            let mut records = IndexMap::new();
+            add_records(&mut records, seq)?;
+            Ok(records)
+        }
+        Value::Object(map) => {
+            let delta = match map.get("delta") {
+                Some(v) => g3_json::value::as_bool(v).context("invalid value for key delta")?,
+                None => false,
+            };
+            let mut records = if delta {
+                if !state.loaded {
+                    return Err(anyhow!("delta update received without a full user set"));
+                }
+                state.records.clone()
+            } else {
+                IndexMap::new()
+            };
+
+            if let Some(v) = map.get("removed") {
+                if !delta {
+                    return Err(anyhow!("key removed is only allowed in delta update"));
+                }
+                let Value::Array(seq) = v else {
+                    return Err(anyhow!("invalid seq value for key removed"));
+                };
+                for (i, v) in seq.iter().enumerate() {
+                    let name = g3_json::value::as_string(v)
+                        .context(format!("invalid username value for removed #{i}"))?;
+                    records.shift_remove(&name);
+                }
+            }
+            if let Some(v) = map.get("users") {
+                let Value::Array(seq) = v else {
+                    return Err(anyhow!("invalid seq value for key users"));
+                };
+                add_records(&mut records, seq)?;
+            }
+            Ok(records)
+        }
+        _ => Err(anyhow!("invalid json value type for users")),
+    }
+}
+
+fn add_records(
+    records: &mut IndexMap<String, Map<String, Value>>,
+    seq: &[Value],
+) -> anyhow::Result<()> {
+    for (i, v) in seq.iter().enumerate() {
+        let Value::Object(map) = v else {
+            return Err(anyhow!("invalid value type for record #{i}"));
+        };
+        let Some(Value::String(name)) = map.get("name") else {
+            return Err(anyhow!("no valid name found in record #{i}"));
+        };
+        records.insert(name.to_string(), map.clone());
+    }
+    Ok(())
+}
+
+async fn fetch(
+    source: &UserDynamicHttpSource,
+    state: &HttpFetchState,
+) -> anyhow::Result<Option<HttpFetchResponse>> {
+    let stream = connect_upstream(&source.upstream, &source.resolver).await?;
+
+    if let Some(tls_client) = &source.tls_client {
+        let ssl = tls_client.build_ssl(&source.tls_name, source.upstream.port())?;
+        let connector = SslConnector::new(ssl, stream)
+            .map_err(|e| anyhow!("failed to create tls connector: {e}"))?;
+        let stream = connector
+            .connect()
+            .await
+            .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+        http_get(stream, source, state).await
+    } else {
+        http_get(stream, source, state).await
+    }
+}
+
+async fn http_get<S>(
+    mut stream: S,
+    source: &UserDynamicHttpSource,
+    state: &HttpFetchState,
+) -> anyhow::Result<Option<HttpFetchResponse>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let path = &source.url[Position::BeforePath..Position::AfterQuery];
+    let host = &source.url[Position::BeforeHost..Position::AfterPort];
+    let mut req = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}\r\nAccept: application/json\r\nConnection: close\r\nUser-Agent: g3proxy\r\n"
+    );
+    // only do conditional request if we have a full user set
+    if state.loaded {
+        if let Some(etag) = &state.etag {
+            req.push_str(&format!("If-None-Match: {etag}\r\n"));
+        }
+        if let Some(last_modified) = &state.last_modified {
+            req.push_str(&format!("If-Modified-Since: {last_modified}\r\n"));
+        }
+    }
+    req.push_str("\r\n");
+
+    stream
+        .write_all(req.as_bytes())
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+    let mut reader = BufReader::new(stream);
+    let rsp = HttpForwardRemoteResponse::parse(
+        &mut reader,
+        &Method::GET,
+        false,
+        source.rsp_header_max_size,
+    )
+    .await
+    .map_err(|e| anyhow!("failed to recv response header: {e}"))?;
+    match rsp.code {
+        200 => {}
+        304 if state.loaded => return Ok(None),
+        code => return Err(anyhow!("unexpected response status code {code}")),
+    }
+
+    let mut body = Vec::new();
+    if let Some(body_type) = rsp.body_type(&Method::GET) {
+        let mut body_reader = HttpBodyReader::new(&mut reader, body_type, BODY_LINE_MAX_LEN);
+        let max_size = source.body_max_size as u64;
+        (&mut body_reader)
+            .take(max_size + 1)
+            .read_to_end(&mut body)
+            .await
+            .map_err(|e| anyhow!("failed to read response body: {e}"))?;
+        if body.len() as u64 > max_size {
+            return Err(anyhow!("response body is larger than {max_size}"));
+        }
+    }
+
+    let get_header = |name| {
+        rsp.end_to_end_headers
+            .get(name)
+            .map(|v| v.to_str().to_string())
+    };
+    Ok(Some(HttpFetchResponse {
+        etag: get_header(header::ETAG),
+        last_modified: get_header(header::LAST_MODIFIED),
+        body,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use tokio::io::AsyncBufReadExt;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use url::Url;
+
+    use g3_types::metrics::NodeName;
+    use g3_types::net::UpstreamAddr;
+
+    fn names(records: &IndexMap<String, Map<String, Value>>) -> Vec<&str> {
+        records.keys().map(|s| s.as_str()).collect()
+    }
+
+    fn user_names(users: &[UserConfig]) -> Vec<&str> {
+        users.iter().map(|u| u.name().as_str()).collect()
+    }
+
+    /// reply the responses in order, and send the request headers back
+    async fn spawn_server(
+        responses: Vec<String>,
+    ) -> (Arc<UserDynamicHttpSource>, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (req_sender, req_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for rsp in responses {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                req_sender.send(head).unwrap();
+                let mut stream = reader.into_inner();
+                stream.write_all(rsp.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        let url = Url::parse(&format!("http://{addr}/users")).unwrap();
+        let source = UserDynamicHttpSource {
+            upstream: UpstreamAddr::try_from(&url).unwrap(),
+            tls_client: None,
+            tls_name: g3_types::net::Host::Ip(addr.ip()),
+            resolver: NodeName::default(),
+            fetch_timeout: Duration::from_secs(5),
+            rsp_header_max_size: 4096,
+            body_max_size: 4096,
+            url,
+        };
+        (Arc::new(source), req_receiver)
+    }
+
+    fn json_response(etag: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nETag: {etag}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[tokio::test]
+    async fn fetch() {
+        let responses = vec![
+            json_response("\"v1\"", r#"[{"name":"a"},{"name":"b"}]"#),
+            "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\n\r\n".to_string(),
+            json_response(
+                "\"v2\"",
+                r#"{"delta":true,"users":[{"name":"c"}],"removed":["a"]}"#,
+            ),
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n".to_string(),
+            json_response("\"v3\"", r#"[{"name":"d"},"#),
+            json_response("\"v4\"", r#"[{"name":"e","invalid_key":1}]"#),
+            "HTTP/1.1 304 Not Modified\r\n\r\n".to_string(),
+        ];
+        let (source, mut requests) = spawn_server(responses).await;
+        let mut state = HttpFetchState::default();
+
+        // the first request should not be a conditional one
+        let (_, users) = fetch_records(&source, &mut state).await.unwrap();
+        assert_eq!(user_names(&users), ["a", "b"]);
+        let req = requests.recv().await.unwrap();
+        assert!(req.starts_with("GET /users HTTP/1.1\r\n"));
+        assert!(!req.contains("If-None-Match"));
+
+        // 304 should keep the current users
+        let (_, users) = fetch_records(&source, &mut state).await.unwrap();
+        assert_eq!(user_names(&users), ["a", "b"]);
+        let req = requests.recv().await.unwrap();
+        assert!(req.contains("If-None-Match: \"v1\"\r\n"));
+
+        // delta update
+        let (_, users) = fetch_records(&source, &mut state).await.unwrap();
+        assert_eq!(user_names(&users), ["b", "c"]);
+        let req = requests.recv().await.unwrap();
+        assert!(req.contains("If-None-Match: \"v1\"\r\n"));
+
+        // the last good state should be kept on errors
+        assert!(fetch_records(&source, &mut state).await.is_err());
+        assert!(fetch_records(&source, &mut state).await.is_err());
+        assert!(fetch_records(&source, &mut state).await.is_err());
+        assert_eq!(names(&state.records), ["b", "c"]);
+        assert_eq!(state.etag.as_deref(), Some("\"v2\""));
+        for _ in 0..3 {
+            let req = requests.recv().await.unwrap();
+            assert!(req.contains("If-None-Match: \"v2\"\r\n"));
+        }
+
+        let (_, users) = fetch_records(&source, &mut state).await.unwrap();
+        assert_eq!(user_names(&users), ["b", "c"]);
+        let req = requests.recv().await.unwrap();
+        assert!(req.contains("If-None-Match: \"v2\"\r\n"));
+    }
+
+    #[test]
+    fn update() {
+        let mut state = HttpFetchState::default();
+
+        let doc = serde_json::json!({"delta": true, "users": [{"name": "a"}]});
+        assert!(apply_update(&state, &doc).is_err());
+
+        let doc = serde_json::json!([{"name": "a"}, {"name": "b"}]);
+        state.records = apply_update(&state, &doc).unwrap();
+        state.loaded = true;
+        assert_eq!(names(&state.records), ["a", "b"]);
+
+        let doc = serde_json::json!({
+            "delta": true,
+            "users": [{"name": "c"}, {"name": "a", "block_and_delay": "1s"}],
+            "removed": ["b"],
+        });
+        let records = apply_update(&state, &doc).unwrap();
+        assert_eq!(names(&records), ["a", "c"]);
+        assert!(records.get("a").unwrap().contains_key("block_and_delay"));
+
+        let doc = serde_json::json!({"users": [{"name": "d"}]});
+        let records = apply_update(&state, &doc).unwrap();
+        assert_eq!(names(&records), ["d"]);
+
+        let doc = serde_json::json!({"users": [], "removed": ["a"]});
+        assert!(apply_update(&state, &doc).is_err());
+
+        let doc = serde_json::json!([{"password": "x"}]);
+        assert!(apply_update(&state, &doc).is_err());
+    }
+}
@@ -17,6 +17,8 @@ use uuid::Uuid;
 use super::User;
 use crate::config::auth::{BasicUserGroupConfig, UserDynamicSource, UserGroupConfig};
 
+mod http;
+
 #[cfg(feature = "lua")]
 mod lua;
 
@@ -29,6 +31,12 @@ pub(super) async fn load_initial_users(
 ) -> anyhow::Result<AHashMap<ArcStr, Arc<User>>> {
     let (_, all_config) = match source {
         UserDynamicSource::File(config) => config.fetch_records().await?,
+        UserDynamicSource::Http(config) => {
+            config
+                .cache(&group_config.dynamic_cache)
+                .fetch_records()
+                .await?
+        }
         #[cfg(feature = "lua")]
         UserDynamicSource::Lua(config) => {
             config
@@ -69,6 +77,7 @@ where
         let basic_config = group_config.basic_config();
         let mut interval = tokio::time::interval(basic_config.refresh_interval);
         interval.tick().await; // will tick immediately
+        let mut http_state = http::HttpFetchState::default();
         loop {
             match quit_receiver.try_recv() {
                 Ok(_) => break,
@@ -82,6 +91,9 @@ where
 
             let r = match source {
                 UserDynamicSource::File(config) => config.fetch_records().await,
+                UserDynamicSource::Http(config) => {
+                    http::fetch_records(config, &mut http_state).await
+                }
                 #[cfg(feature = "lua")]
                 UserDynamicSource::Lua(config) => lua::fetch_records(config).await,
                 #[cfg(feature = "python")]
@@ -117,7 +129,7 @@ where
 
 pub(super) fn new_check_job(
     check_interval: Duration,
-    static_users: Arc<AHashMap<ArcStr, Arc<User>>>,
+    static_users_container: Arc<ArcSwap<AHashMap<ArcStr, Arc<User>>>>,
     dynamic_users_container: Arc<ArcSwap<AHashMap<ArcStr, Arc<User>>>>,
 ) -> oneshot::Sender<()> {
     use oneshot::error::TryRecvError;
@@ -136,7 +148,7 @@ pub(super) fn new_check_job(
 
             let datetime_now = Utc::now();
             check_dynamic_users(&datetime_now, &dynamic_users_container);
-            check_static_users(&datetime_now, &static_users);
+            check_static_users(&datetime_now, &static_users_container);
 
             interval.tick().await;
         }
@@ -157,8 +169,9 @@ fn check_dynamic_users(
 
 fn check_static_users(
     datetime_now: &DateTime<Utc>,
-    static_users: &Arc<AHashMap<ArcStr, Arc<User>>>,
+    static_users_container: &Arc<ArcSwap<AHashMap<ArcStr, Arc<User>>>>,
 ) {
+    let static_users = static_users_container.load();
     for (_, user) in static_users.iter() {
         user.check_expired(datetime_now);
     }
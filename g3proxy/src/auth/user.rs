@@ -29,9 +29,12 @@ use super::{
     UserSites, UserTrafficStats, UserType, UserUpstreamTrafficStats,
 };
 use crate::config::auth::{UserAuditConfig, UserConfig};
+use crate::serve::LiveTaskRegistry;
 
 pub(crate) struct User {
     config: Arc<UserConfig>,
+    /// the config before applying the runtime overrides
+    origin_config: Arc<UserConfig>,
     group: NodeName,
     started: Instant,
     is_expired: AtomicBool,
@@ -52,6 +55,7 @@ pub(crate) struct User {
     upstream_io_stats: Arc<Mutex<HashMap<NodeName, Arc<UserUpstreamTrafficStats>>>>,
     req_alive_sem: GaugeSemaphore,
     explicit_sites: UserSites,
+    tasks: Arc<LiveTaskRegistry>,
 }
 
 impl User {
@@ -138,6 +142,7 @@ impl User {
 
         let mut user = User {
             config: Arc::clone(config),
+            origin_config: Arc::clone(config),
             group: group.clone(),
             started: Instant::now(),
             is_expired,
@@ -158,6 +163,7 @@ impl User {
             upstream_io_stats: Arc::new(Mutex::new(HashMap::default())),
             req_alive_sem: GaugeSemaphore::new(config.request_alive_max),
             explicit_sites,
+            tasks: Arc::new(LiveTaskRegistry::default()),
         };
         user.update_ingress_net_filter();
         user.update_dst_host_filter();
@@ -296,6 +302,7 @@ impl User {
 
         let mut user = User {
             config: Arc::clone(config),
+            origin_config: Arc::clone(config),
             group: self.group.clone(),
             started: self.started,
             is_expired,
@@ -316,6 +323,7 @@ impl User {
             upstream_io_stats: Arc::clone(&self.upstream_io_stats),
             req_alive_sem: self.req_alive_sem.new_updated(config.request_alive_max),
             explicit_sites,
+            tasks: Arc::clone(&self.tasks),
         };
         if self
             .config
@@ -341,10 +349,29 @@ impl User {
     }
 
     #[inline]
-    fn is_expired(&self) -> bool {
+    pub(super) fn is_expired(&self) -> bool {
         self.is_expired.load(Ordering::Relaxed)
     }
 
+    #[inline]
+    pub(super) fn config(&self) -> &Arc<UserConfig> {
+        &self.config
+    }
+
+    #[inline]
+    pub(super) fn origin_config(&self) -> &Arc<UserConfig> {
+        &self.origin_config
+    }
+
+    pub(super) fn set_origin_config(&mut self, config: &Arc<UserConfig>) {
+        self.origin_config = Arc::clone(config);
+    }
+
+    #[inline]
+    pub(super) fn tasks(&self) -> &Arc<LiveTaskRegistry> {
+        &self.tasks
+    }
+
     pub(super) fn check_expired(&self, datetime_now: &DateTime<Utc>) -> bool {
         if self.config.is_expired(datetime_now) {
             // TODO log user expire ?
@@ -397,6 +424,14 @@ impl User {
             forbid_stats.add_auth_failed();
             return Err(UserAuthError::TokenNotMatch);
         }
+        self.check_state(forbid_stats)
+    }
+
+    /// check the user state after it has been authenticated by other means
+    pub(super) fn check_state(
+        &self,
+        forbid_stats: &Arc<UserForbiddenStats>,
+    ) -> Result<(), UserAuthError> {
         if self.is_expired() {
             forbid_stats.add_user_expired();
             return Err(UserAuthError::ExpiredUser);
@@ -842,6 +877,15 @@ impl UserContext {
         all_stats
     }
 
+    /// the registry for the live tasks, anonymous users won't have one
+    pub(crate) fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        if self.user_type.is_anonymous() {
+            None
+        } else {
+            Some(&self.user.tasks)
+        }
+    }
+
     pub(crate) fn record_task_ready(&self, dur: Duration) {
         if let Some(r) = &self.site_duration_recorder {
             r.record_task_ready(dur);
@@ -863,6 +907,11 @@ impl UserContext {
         self.user.check_password(password, &self.forbid_stats)
     }
 
+    #[inline]
+    pub(crate) fn check_state(&self) -> Result<(), UserAuthError> {
+        self.user.check_state(&self.forbid_stats)
+    }
+
     #[inline]
     pub(crate) fn skip_log(&self) -> bool {
         self.user.skip_log(&self.forbid_stats)
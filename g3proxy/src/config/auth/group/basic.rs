@@ -18,7 +18,10 @@ use g3_yaml::YamlDocPosition;
 
 use super::UserGroupConfig;
 use crate::config::auth::{CONFIG_KEY_USER_GROUP_NAME, CONFIG_KEY_USER_GROUP_TYPE};
-use crate::config::auth::{UserConfig, UserDynamicSource};
+use crate::config::auth::{
+    UserConfig, UserDynamicSource, UserExternalAuthConfig, UserKerberosAuthConfig,
+    UserLdapAuthConfig,
+};
 
 const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 
@@ -33,6 +36,9 @@ pub(crate) struct BasicUserGroupConfig {
     pub(crate) dynamic_cache: PathBuf,
     pub(crate) refresh_interval: Duration,
     pub(crate) anonymous_user: Option<Arc<UserConfig>>,
+    pub(crate) external_auth: Option<UserExternalAuthConfig>,
+    pub(crate) ldap_auth: Option<UserLdapAuthConfig>,
+    pub(crate) kerberos_auth: Option<UserKerberosAuthConfig>,
 }
 
 impl BasicUserGroupConfig {
@@ -53,6 +59,9 @@ impl BasicUserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            external_auth: None,
+            ldap_auth: None,
+            kerberos_auth: None,
         }
     }
 
@@ -65,6 +74,9 @@ impl BasicUserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            external_auth: None,
+            ldap_auth: None,
+            kerberos_auth: None,
         }
     }
 
@@ -82,6 +94,11 @@ impl BasicUserGroupConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        if let Some(ldap_auth) = &self.ldap_auth {
+            ldap_auth
+                .check(|name| self.static_users.contains_key(name))
+                .context("invalid ldap auth config")?;
+        }
 
         Ok(())
     }
@@ -144,6 +161,27 @@ impl BasicUserGroupConfig {
                     Err(anyhow!("invalid hash value for key {k}"))
                 }
             }
+            "external_auth" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = UserExternalAuthConfig::parse(v, lookup_dir)
+                    .context(format!("invalid external auth config value for key {k}"))?;
+                self.external_auth = Some(config);
+                Ok(())
+            }
+            "ldap_auth" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = UserLdapAuthConfig::parse(v, self.position.as_ref(), lookup_dir)
+                    .context(format!("invalid ldap auth config value for key {k}"))?;
+                self.ldap_auth = Some(config);
+                Ok(())
+            }
+            "kerberos_auth" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = UserKerberosAuthConfig::parse(v, lookup_dir)
+                    .context(format!("invalid kerberos auth config value for key {k}"))?;
+                self.kerberos_auth = Some(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
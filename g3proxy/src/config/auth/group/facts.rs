@@ -3,6 +3,7 @@
  * Copyright 2026 G3-OSS developers.
  */
 
+use anyhow::anyhow;
 use yaml_rust::{Yaml, yaml};
 
 use g3_yaml::YamlDocPosition;
@@ -34,6 +35,15 @@ impl FactsUserGroupConfig {
     }
 
     fn check(&self) -> anyhow::Result<()> {
+        if self.basic.external_auth.is_some() {
+            return Err(anyhow!("external auth is not supported in this user group"));
+        }
+        if self.basic.ldap_auth.is_some() {
+            return Err(anyhow!("ldap auth is not supported in this user group"));
+        }
+        if self.basic.kerberos_auth.is_some() {
+            return Err(anyhow!("kerberos auth is not supported in this user group"));
+        }
         self.basic.check()
     }
 
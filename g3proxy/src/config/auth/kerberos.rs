@@ -0,0 +1,106 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+const CONFIG_KEY_KEYTAB: &str = "keytab";
+
+#[derive(Clone)]
+pub(crate) struct UserKerberosAuthConfig {
+    pub(crate) keytab: PathBuf,
+    pub(crate) service_principal: Option<String>,
+    pub(crate) realms: Vec<String>,
+    pub(crate) strip_realm: bool,
+    pub(crate) clock_skew: Duration,
+    pub(crate) replay_cache_capacity: usize,
+}
+
+impl UserKerberosAuthConfig {
+    fn new(keytab: PathBuf) -> Self {
+        UserKerberosAuthConfig {
+            keytab,
+            service_principal: None,
+            realms: Vec::new(),
+            strip_realm: true,
+            clock_skew: Duration::from_secs(300),
+            replay_cache_capacity: 65536,
+        }
+    }
+
+    pub(crate) fn parse(v: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        match v {
+            Yaml::Hash(map) => Self::parse_map(map, lookup_dir),
+            Yaml::String(_) => {
+                let keytab = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context("invalid keytab file path value")?;
+                Ok(UserKerberosAuthConfig::new(keytab))
+            }
+            _ => Err(anyhow!("invalid yaml value type")),
+        }
+    }
+
+    fn parse_map(map: &yaml::Hash, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let v = g3_yaml::hash_get_required(map, CONFIG_KEY_KEYTAB)?;
+        let keytab = g3_yaml::value::as_file_path(v, lookup_dir, false).context(format!(
+            "invalid file path value for key {CONFIG_KEY_KEYTAB}"
+        ))?;
+        let mut config = UserKerberosAuthConfig::new(keytab);
+
+        g3_yaml::foreach_kv(map, |k, v| {
+            config.set(k, v).context(format!("failed to parse key {k}"))
+        })?;
+
+        Ok(config)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            CONFIG_KEY_KEYTAB => Ok(()),
+            "service_principal" => {
+                let principal = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                if !principal.contains('@') {
+                    return Err(anyhow!("no realm found in service principal {principal}"));
+                }
+                self.service_principal = Some(principal);
+                Ok(())
+            }
+            "realms" | "realm" => {
+                self.realms = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid string list value for key {k}"))?;
+                Ok(())
+            }
+            "strip_realm" => {
+                self.strip_realm = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "clock_skew" => {
+                self.clock_skew = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "replay_cache_capacity" => {
+                self.replay_cache_capacity = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    /// check if the client realm is allowed, only the realm of the service is allowed by default
+    pub(crate) fn allow_realm(&self, realm: &str, service_realm: &str) -> bool {
+        if self.realms.is_empty() {
+            realm.eq_ignore_ascii_case(service_realm)
+        } else {
+            self.realms.iter().any(|r| r.eq_ignore_ascii_case(realm))
+        }
+    }
+}
@@ -0,0 +1,249 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use arcstr::ArcStr;
+use url::Url;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+use g3_types::net::{Host, OpensslClientConfig, OpensslClientConfigBuilder, UpstreamAddr};
+use g3_yaml::YamlDocPosition;
+
+use super::UserConfig;
+
+const CONFIG_KEY_URL: &str = "url";
+const CONFIG_KEY_BIND_DN: &str = "bind_dn";
+
+const USERNAME_PLACEHOLDER: &str = "{username}";
+
+#[derive(Clone)]
+pub(crate) struct UserLdapAuthConfig {
+    pub(crate) url: Url,
+    pub(crate) upstream: UpstreamAddr,
+    pub(crate) tls_client: Option<OpensslClientConfig>,
+    pub(crate) tls_name: Host,
+    pub(crate) resolver: NodeName,
+    bind_dn: String,
+    pub(crate) group_attribute: String,
+    pub(crate) group_map: Vec<(String, ArcStr)>,
+    pub(crate) default_user: Option<ArcStr>,
+    pub(crate) user_templates: HashMap<ArcStr, Arc<UserConfig>>,
+    pub(crate) timeout: Duration,
+    pub(crate) rsp_max_size: usize,
+    pub(crate) cache_ttl: Duration,
+    pub(crate) deny_cache_ttl: Duration,
+    pub(crate) cache_capacity: usize,
+}
+
+impl UserLdapAuthConfig {
+    fn new(url: Url, bind_dn: String) -> anyhow::Result<Self> {
+        let (default_port, tls_client) = match url.scheme() {
+            "ldap" => (389, None),
+            "ldaps" => {
+                let builder = OpensslClientConfigBuilder::with_cache_for_one_site();
+                let config = builder
+                    .build()
+                    .context("failed to build default tls client config")?;
+                (636, Some(config))
+            }
+            s => return Err(anyhow!("unsupported url scheme {s}")),
+        };
+        // the host in non-special urls is opaque, so parse the ip address here
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("no host found in url"))?;
+        let host = Host::from_str(host).context(format!("invalid host {host} in url"))?;
+        let upstream = UpstreamAddr::new(host, url.port().unwrap_or(default_port));
+        let tls_name = upstream.host().clone();
+        Ok(UserLdapAuthConfig {
+            url,
+            upstream,
+            tls_client,
+            tls_name,
+            resolver: NodeName::default(),
+            bind_dn,
+            group_attribute: "memberOf".to_string(),
+            group_map: Vec::new(),
+            default_user: None,
+            user_templates: HashMap::new(),
+            timeout: Duration::from_secs(5),
+            rsp_max_size: 64 * 1024,
+            cache_ttl: Duration::from_secs(60),
+            deny_cache_ttl: Duration::from_secs(10),
+            cache_capacity: 4096,
+        })
+    }
+
+    pub(crate) fn parse(
+        v: &Yaml,
+        position: Option<&YamlDocPosition>,
+        lookup_dir: &Path,
+    ) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("invalid yaml value type"));
+        };
+
+        let v = g3_yaml::hash_get_required(map, CONFIG_KEY_URL)?;
+        let url = g3_yaml::value::as_url(v)
+            .context(format!("invalid url value for key {CONFIG_KEY_URL}"))?;
+        let v = g3_yaml::hash_get_required(map, CONFIG_KEY_BIND_DN)?;
+        let bind_dn = g3_yaml::value::as_string(v)
+            .context(format!("invalid string value for key {CONFIG_KEY_BIND_DN}"))?;
+        if !bind_dn.contains(USERNAME_PLACEHOLDER) {
+            return Err(anyhow!(
+                "no {USERNAME_PLACEHOLDER} placeholder found in {CONFIG_KEY_BIND_DN}"
+            ));
+        }
+        let mut config = UserLdapAuthConfig::new(url, bind_dn)?;
+
+        g3_yaml::foreach_kv(map, |k, v| {
+            config
+                .set(k, v, position, lookup_dir)
+                .context(format!("failed to parse key {k}"))
+        })?;
+
+        Ok(config)
+    }
+
+    fn set(
+        &mut self,
+        k: &str,
+        v: &Yaml,
+        position: Option<&YamlDocPosition>,
+        lookup_dir: &Path,
+    ) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            CONFIG_KEY_URL | CONFIG_KEY_BIND_DN => Ok(()),
+            "tls_client" => {
+                if self.tls_client.is_none() {
+                    return Err(anyhow!("tls client config is set for non ldaps url"));
+                }
+                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                    v,
+                    Some(lookup_dir),
+                )
+                .context(format!(
+                    "invalid openssl tls client config value for key {k}"
+                ))?;
+                let config = builder
+                    .build()
+                    .context("failed to build tls client config")?;
+                self.tls_client = Some(config);
+                Ok(())
+            }
+            "tls_name" => {
+                self.tls_name = g3_yaml::value::as_host(v)
+                    .context(format!("invalid tls name value for key {k}"))?;
+                Ok(())
+            }
+            "resolver" => {
+                self.resolver = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "group_attribute" => {
+                self.group_attribute = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            "group_map" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.group_map.clear();
+                for (group, user) in map.iter() {
+                    let group = g3_yaml::value::as_string(group)
+                        .context(format!("invalid group name in key {k}"))?;
+                    let user = g3_yaml::value::as_string(user)
+                        .context(format!("invalid user name value for group {group}"))?;
+                    self.group_map.push((group, user.into()));
+                }
+                Ok(())
+            }
+            "default_user" => {
+                let user = g3_yaml::value::as_string(v)
+                    .context(format!("invalid user name value for key {k}"))?;
+                self.default_user = Some(user.into());
+                Ok(())
+            }
+            "user_templates" => {
+                let Yaml::Array(seq) = v else {
+                    return Err(anyhow!("invalid sequence value for key {k}"));
+                };
+                for (i, obj) in seq.iter().enumerate() {
+                    let Yaml::Hash(map) = obj else {
+                        return Err(anyhow!("invalid hash value for key {k}#{i}"));
+                    };
+                    let user = Arc::new(UserConfig::parse_yaml(map, position)?);
+                    let name = user.name().clone();
+                    if let Some(old) = self.user_templates.insert(name, user) {
+                        return Err(anyhow!(
+                            "found duplicate entry for user template {}",
+                            old.name()
+                        ));
+                    }
+                }
+                Ok(())
+            }
+            "timeout" => {
+                self.timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "rsp_max_size" => {
+                self.rsp_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "cache_ttl" => {
+                self.cache_ttl = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "deny_cache_ttl" => {
+                self.deny_cache_ttl = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "cache_capacity" => {
+                self.cache_capacity = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    /// check that all mapped users exist in templates or the given static users
+    pub(crate) fn check<F>(&self, has_static_user: F) -> anyhow::Result<()>
+    where
+        F: Fn(&str) -> bool,
+    {
+        if matches!(self.upstream.host(), Host::Domain(_)) && self.resolver.is_empty() {
+            return Err(anyhow!("resolver is not set for domain in url"));
+        }
+        if self.group_map.is_empty() && self.default_user.is_none() {
+            return Err(anyhow!("neither group_map nor default_user is set"));
+        }
+        let mapped = self.group_map.iter().map(|(_, user)| user);
+        for user in mapped.chain(self.default_user.iter()) {
+            if !self.user_templates.contains_key(user) && !has_static_user(user) {
+                return Err(anyhow!("no user or user template found for {user}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// get the dn to bind, the username should have been escaped
+    pub(crate) fn bind_dn(&self, escaped_username: &str) -> String {
+        self.bind_dn.replace(USERNAME_PLACEHOLDER, escaped_username)
+    }
+}
@@ -19,6 +19,15 @@ pub(crate) use user::{UserAuditConfig, UserConfig, UserSiteConfig, UsernameParam
 mod source;
 pub(crate) use source::*;
 
+mod external;
+pub(crate) use external::UserExternalAuthConfig;
+
+mod ldap;
+pub(crate) use ldap::UserLdapAuthConfig;
+
+mod kerberos;
+pub(crate) use kerberos::UserKerberosAuthConfig;
+
 pub(crate) mod group;
 pub(crate) use group::{
     AnyUserGroupConfig, BasicUserGroupConfig, FactsUserGroupConfig, UserGroupConfig,
@@ -0,0 +1,144 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use url::Url;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::fs::ConfigFileFormat;
+use g3_types::metrics::NodeName;
+use g3_types::net::{Host, OpensslClientConfig, OpensslClientConfigBuilder, UpstreamAddr};
+
+use super::UserDynamicFileSource;
+
+const CONFIG_KEY_SOURCE_URL: &str = "url";
+
+#[derive(Clone)]
+pub(crate) struct UserDynamicHttpSource {
+    pub(crate) url: Url,
+    pub(crate) upstream: UpstreamAddr,
+    pub(crate) tls_client: Option<OpensslClientConfig>,
+    pub(crate) tls_name: Host,
+    pub(crate) resolver: NodeName,
+    pub(crate) fetch_timeout: Duration,
+    pub(crate) rsp_header_max_size: usize,
+    pub(crate) body_max_size: usize,
+}
+
+impl UserDynamicHttpSource {
+    fn new(url: Url) -> anyhow::Result<Self> {
+        let upstream = UpstreamAddr::try_from(&url).context("invalid upstream address in url")?;
+        let tls_name = upstream.host().clone();
+        let tls_client = match url.scheme() {
+            "http" => None,
+            "https" => {
+                let builder = OpensslClientConfigBuilder::with_cache_for_one_site();
+                let config = builder
+                    .build()
+                    .context("failed to build default tls client config")?;
+                Some(config)
+            }
+            s => return Err(anyhow!("unsupported url scheme {s}")),
+        };
+        Ok(UserDynamicHttpSource {
+            url,
+            upstream,
+            tls_client,
+            tls_name,
+            resolver: NodeName::default(),
+            fetch_timeout: Duration::from_secs(30),
+            rsp_header_max_size: 64 * 1024,
+            body_max_size: 64 * 1024 * 1024,
+        })
+    }
+
+    pub(super) fn parse_map(map: &yaml::Hash, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let v = g3_yaml::hash_get_required(map, CONFIG_KEY_SOURCE_URL)?;
+        let url = g3_yaml::value::as_url(v)
+            .context(format!("invalid url value for key {CONFIG_KEY_SOURCE_URL}"))?;
+        let mut config = UserDynamicHttpSource::new(url)?;
+
+        g3_yaml::foreach_kv(map, |k, v| {
+            config
+                .set(k, v, lookup_dir)
+                .context(format!("failed to parse key {k}"))
+        })?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    pub(super) fn parse_url(url: &Url) -> anyhow::Result<Self> {
+        let config = UserDynamicHttpSource::new(url.clone())?;
+        config.check()?;
+        Ok(config)
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if matches!(self.upstream.host(), Host::Domain(_)) && self.resolver.is_empty() {
+            return Err(anyhow!("resolver is not set for domain in url"));
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml, lookup_dir: &Path) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SOURCE_TYPE => Ok(()),
+            CONFIG_KEY_SOURCE_URL => Ok(()),
+            "tls_client" => {
+                if self.tls_client.is_none() {
+                    return Err(anyhow!("tls client config is set for non https url"));
+                }
+                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                    v,
+                    Some(lookup_dir),
+                )
+                .context(format!(
+                    "invalid openssl tls client config value for key {k}"
+                ))?;
+                let config = builder
+                    .build()
+                    .context("failed to build tls client config")?;
+                self.tls_client = Some(config);
+                Ok(())
+            }
+            "tls_name" => {
+                self.tls_name = g3_yaml::value::as_host(v)
+                    .context(format!("invalid tls name value for key {k}"))?;
+                Ok(())
+            }
+            "resolver" => {
+                self.resolver = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "fetch_timeout" | "timeout" => {
+                self.fetch_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "rsp_header_max_size" => {
+                self.rsp_header_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "body_max_size" => {
+                self.body_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    pub(crate) fn cache(&self, cache: &Path) -> UserDynamicFileSource {
+        UserDynamicFileSource {
+            path: cache.to_path_buf(),
+            format: ConfigFileFormat::Json,
+        }
+    }
+}
@@ -13,6 +13,9 @@ use yaml_rust::Yaml;
 pub(crate) mod file;
 use file::UserDynamicFileSource;
 
+pub(crate) mod http;
+pub(crate) use http::UserDynamicHttpSource;
+
 #[cfg(feature = "lua")]
 pub(crate) mod lua;
 #[cfg(feature = "lua")]
@@ -28,6 +31,7 @@ const CONFIG_KEY_SOURCE_TYPE: &str = "type";
 #[derive(Clone)]
 pub(crate) enum UserDynamicSource {
     File(Arc<UserDynamicFileSource>),
+    Http(Arc<UserDynamicHttpSource>),
     #[cfg(feature = "lua")]
     Lua(Arc<UserDynamicLuaSource>),
     #[cfg(feature = "python")]
@@ -45,6 +49,10 @@ impl UserDynamicSource {
                         let source = UserDynamicFileSource::parse_map(map, lookup_dir)?;
                         Ok(UserDynamicSource::File(Arc::new(source)))
                     }
+                    "http" => {
+                        let source = UserDynamicHttpSource::parse_map(map, lookup_dir)?;
+                        Ok(UserDynamicSource::Http(Arc::new(source)))
+                    }
                     #[cfg(feature = "lua")]
                     "lua" => {
                         let source = UserDynamicLuaSource::parse_map(map, lookup_dir)?;
@@ -67,6 +75,10 @@ impl UserDynamicSource {
                         let source = UserDynamicFileSource::parse_url(&url)?;
                         Ok(UserDynamicSource::File(Arc::new(source)))
                     }
+                    "http" | "https" => {
+                        let source = UserDynamicHttpSource::parse_url(&url)?;
+                        Ok(UserDynamicSource::Http(Arc::new(source)))
+                    }
                     _ => Err(anyhow!("unsupported url scheme: {scheme}")),
                 }
             }
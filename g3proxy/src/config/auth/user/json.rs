@@ -7,8 +7,11 @@ use std::str::FromStr;
 
 use anyhow::{Context, anyhow};
 use log::warn;
-use serde_json::{Map, Value};
+use serde_json::{Map, Value, json};
 
+use g3_types::limit::{
+    GlobalDatagramSpeedLimitConfig, GlobalStreamSpeedLimitConfig, RateLimitQuota,
+};
 use g3_types::metrics::NodeName;
 
 use super::{PasswordToken, UserConfig, UserSiteConfig};
@@ -23,6 +26,155 @@ impl UserConfig {
         Ok(config)
     }
 
+    /// parse the per-user overrides returned by the external auth service
+    pub(crate) fn parse_json_external(
+        name: &str,
+        map: &Map<String, Value>,
+    ) -> anyhow::Result<Self> {
+        let mut config = UserConfig::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                // the name and token are always decided by the external auth service
+                "name" | "token" => {}
+                _ => config.set_json(k, v)?,
+            }
+        }
+        config.name = name.into();
+        config.set_no_password();
+        config.check()?;
+        Ok(config)
+    }
+
+    /// apply the limits changed at runtime, a null value will remove the limit
+    pub(crate) fn new_with_runtime_limits(&self, map: &Map<String, Value>) -> anyhow::Result<Self> {
+        let mut config = self.clone();
+        for (k, v) in map {
+            config.set_runtime_limit(k, v)?;
+        }
+        Ok(config)
+    }
+
+    fn set_runtime_limit(&mut self, k: &str, v: &Value) -> anyhow::Result<()> {
+        let key = g3_json::key::normalize(k);
+        let key = match key.as_str() {
+            "request_max_alive" => "request_alive_max",
+            s => s,
+        };
+        if !v.is_null() {
+            return match key {
+                "request_rate_limit"
+                | "connection_rate_limit"
+                | "request_alive_max"
+                | "tcp_sock_speed_limit"
+                | "udp_sock_speed_limit"
+                | "tcp_all_upload_speed_limit"
+                | "tcp_all_download_speed_limit"
+                | "udp_all_upload_speed_limit"
+                | "udp_all_download_speed_limit" => self.set_json(key, v),
+                _ => Err(anyhow!("key {k} can not be changed at runtime")),
+            };
+        }
+
+        match key {
+            "request_rate_limit" => self.request_rate_limit = None,
+            "connection_rate_limit" => self.connection_rate_limit = None,
+            "request_alive_max" => self.request_alive_max = 0,
+            "tcp_sock_speed_limit" => self.tcp_sock_speed_limit = Default::default(),
+            "udp_sock_speed_limit" => self.udp_sock_speed_limit = Default::default(),
+            "tcp_all_upload_speed_limit" => self.tcp_all_upload_speed_limit = None,
+            "tcp_all_download_speed_limit" => self.tcp_all_download_speed_limit = None,
+            "udp_all_upload_speed_limit" => self.udp_all_upload_speed_limit = None,
+            "udp_all_download_speed_limit" => self.udp_all_download_speed_limit = None,
+            _ => return Err(anyhow!("key {k} can not be changed at runtime")),
+        }
+        Ok(())
+    }
+
+    /// dump the limits that can be changed at runtime
+    pub(crate) fn dump_runtime_limits(&self) -> Map<String, Value> {
+        fn rate_limit(quota: &RateLimitQuota) -> Value {
+            json!({
+                "replenish_interval": format!("{:?}", quota.replenish_interval()),
+                "max_burst": quota.max_burst().get(),
+            })
+        }
+
+        fn stream_speed_limit(config: &GlobalStreamSpeedLimitConfig) -> Value {
+            json!({
+                "replenish_interval": format!("{:?}", config.replenish_interval()),
+                "replenish_bytes": config.replenish_bytes(),
+                "max_burst_bytes": config.max_burst_bytes(),
+            })
+        }
+
+        fn datagram_speed_limit(config: &GlobalDatagramSpeedLimitConfig) -> Value {
+            json!({
+                "replenish_interval": format!("{:?}", config.replenish_interval()),
+                "replenish_bytes": config.replenish_bytes(),
+                "replenish_packets": config.replenish_packets(),
+                "max_burst_bytes": config.max_burst_bytes(),
+                "max_burst_packets": config.max_burst_packets(),
+            })
+        }
+
+        let mut map = Map::new();
+        let mut set = |k: &str, v: Option<Value>| {
+            map.insert(k.to_string(), v.unwrap_or(Value::Null));
+        };
+        set(
+            "request_rate_limit",
+            self.request_rate_limit.as_ref().map(rate_limit),
+        );
+        set(
+            "connection_rate_limit",
+            self.connection_rate_limit.as_ref().map(rate_limit),
+        );
+        set("request_alive_max", Some(json!(self.request_alive_max)));
+        set(
+            "tcp_sock_speed_limit",
+            Some(json!({
+                "shift_millis": self.tcp_sock_speed_limit.shift_millis,
+                "upload": self.tcp_sock_speed_limit.max_north,
+                "download": self.tcp_sock_speed_limit.max_south,
+            })),
+        );
+        set(
+            "udp_sock_speed_limit",
+            Some(json!({
+                "shift_millis": self.udp_sock_speed_limit.shift_millis,
+                "upload_bytes": self.udp_sock_speed_limit.max_north_bytes,
+                "download_bytes": self.udp_sock_speed_limit.max_south_bytes,
+                "upload_packets": self.udp_sock_speed_limit.max_north_packets,
+                "download_packets": self.udp_sock_speed_limit.max_south_packets,
+            })),
+        );
+        set(
+            "tcp_all_upload_speed_limit",
+            self.tcp_all_upload_speed_limit
+                .as_ref()
+                .map(stream_speed_limit),
+        );
+        set(
+            "tcp_all_download_speed_limit",
+            self.tcp_all_download_speed_limit
+                .as_ref()
+                .map(stream_speed_limit),
+        );
+        set(
+            "udp_all_upload_speed_limit",
+            self.udp_all_upload_speed_limit
+                .as_ref()
+                .map(datagram_speed_limit),
+        );
+        set(
+            "udp_all_download_speed_limit",
+            self.udp_all_download_speed_limit
+                .as_ref()
+                .map(datagram_speed_limit),
+        );
+        map
+    }
+
     fn set_json(&mut self, k: &str, v: &Value) -> anyhow::Result<()> {
         match g3_json::key::normalize(k).as_str() {
             "name" => {
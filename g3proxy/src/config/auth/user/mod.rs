@@ -153,6 +153,14 @@ impl UserConfig {
         self.password_token = PasswordToken::SkipVerify;
     }
 
+    /// create a config for the named user which has been authenticated elsewhere
+    pub(crate) fn new_from_template(&self, name: &str) -> Self {
+        let mut config = self.clone();
+        config.name = name.into();
+        config.set_no_password();
+        config
+    }
+
     fn add_site_group(&mut self, sg: UserSiteConfig) -> anyhow::Result<()> {
         let name = sg.id.clone();
         if let Some(old_sg) = self.explicit_sites.insert(name, Arc::new(sg)) {
@@ -23,6 +23,9 @@ pub(crate) mod direct_float;
 pub(crate) mod divert_tcp;
 pub(crate) mod dummy_deny;
 pub(crate) mod proxy_float;
+pub(crate) mod proxy_h2;
+#[cfg(feature = "quic")]
+pub(crate) mod proxy_h3;
 pub(crate) mod proxy_http;
 pub(crate) mod proxy_https;
 pub(crate) mod proxy_socks5;
@@ -38,7 +41,7 @@ pub(crate) mod route_upstream;
 pub(crate) mod trick_float;
 
 mod registry;
-pub(crate) use registry::clear;
+pub(crate) use registry::{clear, get};
 
 mod verify;
 use verify::EscaperConfigVerifier;
@@ -95,6 +98,9 @@ pub(crate) enum AnyEscaperConfig {
     DivertTcp(divert_tcp::DivertTcpEscaperConfig),
     DummyDeny(dummy_deny::DummyDenyEscaperConfig),
     ProxyFloat(proxy_float::ProxyFloatEscaperConfig),
+    ProxyH2(proxy_h2::ProxyH2EscaperConfig),
+    #[cfg(feature = "quic")]
+    ProxyH3(proxy_h3::ProxyH3EscaperConfig),
     ProxyHttp(proxy_http::ProxyHttpEscaperConfig),
     ProxyHttps(proxy_https::ProxyHttpsEscaperConfig),
     ProxySocks5(proxy_socks5::ProxySocks5EscaperConfig),
@@ -132,7 +138,7 @@ pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<Any
     if let Yaml::Hash(map) = doc {
         let escaper = load_escaper(&map, Some(position.clone()))?;
         let old_escaper = registry::add(escaper.clone());
-        if let Err(e) = build_topology_map() {
+        if let Err(e) = build_topology_map().and_then(|_| super::resolver::check_tunnel_escaper()) {
             // rollback
             match old_escaper {
                 Some(escaper) => {
@@ -149,7 +155,7 @@ pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<Any
     }
 }
 
-fn load_escaper(
+pub(super) fn load_escaper(
     map: &yaml::Hash,
     position: Option<YamlDocPosition>,
 ) -> anyhow::Result<AnyEscaperConfig> {
@@ -183,6 +189,15 @@ fn load_escaper(
             let config = proxy_https::ProxyHttpsEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxyHttps(config))
         }
+        "proxy_h2" | "proxyh2" => {
+            let config = proxy_h2::ProxyH2EscaperConfig::parse(map, position)?;
+            Ok(AnyEscaperConfig::ProxyH2(config))
+        }
+        #[cfg(feature = "quic")]
+        "proxy_h3" | "proxyh3" => {
+            let config = proxy_h3::ProxyH3EscaperConfig::parse(map, position)?;
+            Ok(AnyEscaperConfig::ProxyH3(config))
+        }
         "proxy_socks5" | "proxysocks5" => {
             let config = proxy_socks5::ProxySocks5EscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxySocks5(config))
@@ -33,7 +33,7 @@ pub(super) fn del(name: &NodeName) {
     ht.remove(name);
 }
 
-pub(super) fn get(name: &NodeName) -> Option<Arc<AnyEscaperConfig>> {
+pub(crate) fn get(name: &NodeName) -> Option<Arc<AnyEscaperConfig>> {
     let ht = INITIAL_ESCAPER_CONFIG_REGISTRY.lock().unwrap();
     ht.get(name).cloned()
 }
@@ -33,6 +33,7 @@ pub fn load() -> anyhow::Result<&'static Path> {
         Yaml::Hash(map) => load_doc(map),
         _ => Err(anyhow!("yaml doc root should be hash")),
     })?;
+    resolver::check_tunnel_escaper()?;
 
     Ok(config_file)
 }
@@ -59,6 +60,7 @@ fn reload_blocking() -> anyhow::Result<()> {
             Yaml::Hash(map) => reload_doc(map),
             _ => Err(anyhow!("yaml doc root should be hash")),
         })?;
+        resolver::check_tunnel_escaper()?;
     }
     Ok(())
 }
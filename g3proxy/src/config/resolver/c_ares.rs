@@ -6,11 +6,11 @@
 use std::collections::BTreeSet;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
 use g3_resolver::driver::c_ares::CAresDriverConfig;
-use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
+use g3_resolver::{AnyResolveDriverConfig, ResolverCacheSnapshotConfig, ResolverRuntimeConfig};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
@@ -85,6 +85,13 @@ impl CAresResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "cache_snapshot" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let snapshot = ResolverCacheSnapshotConfig::parse_yaml(v, lookup_dir)
+                    .context(format!("invalid cache snapshot config value for key {k}"))?;
+                self.runtime.cache_snapshot = Some(snapshot);
+                Ok(())
+            }
             _ => self.driver.set_by_yaml_kv(k, v),
         }
     }
@@ -0,0 +1,244 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use g3_types::metrics::NodeName;
+
+use super::AnyResolverConfig;
+use crate::config::escaper::AnyEscaperConfig;
+
+fn tunnel_escaper(conf: &AnyResolverConfig) -> Option<&NodeName> {
+    match conf {
+        AnyResolverConfig::Hickory(c) => c.escaper(),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct VisitedNodes {
+    resolvers: BTreeSet<NodeName>,
+    escapers: BTreeSet<NodeName>,
+}
+
+/// Check the escaper that a resolver connects to its dns servers through,
+/// which should exist and should not depend on the resolver itself
+pub(super) struct TunnelEscaperChecker<R, E> {
+    get_resolver: R,
+    get_escaper: E,
+}
+
+impl<R, E> TunnelEscaperChecker<R, E>
+where
+    R: Fn(&NodeName) -> Option<Arc<AnyResolverConfig>>,
+    E: Fn(&NodeName) -> Option<Arc<AnyEscaperConfig>>,
+{
+    pub(super) fn new(get_resolver: R, get_escaper: E) -> Self {
+        TunnelEscaperChecker {
+            get_resolver,
+            get_escaper,
+        }
+    }
+
+    pub(super) fn check(&self, resolver: &NodeName) -> anyhow::Result<()> {
+        let Some(conf) = (self.get_resolver)(resolver) else {
+            return Ok(());
+        };
+        let Some(escaper) = tunnel_escaper(&conf) else {
+            return Ok(());
+        };
+        if (self.get_escaper)(escaper).is_none() {
+            return Err(anyhow!(
+                "escaper {escaper} used by resolver {resolver} is not found"
+            ));
+        }
+
+        let mut visited = VisitedNodes::default();
+        if self.escaper_depend_on(escaper, resolver, &mut visited) {
+            return Err(anyhow!(
+                "escaper {escaper} used by resolver {resolver} depends on the resolver itself"
+            ));
+        }
+        Ok(())
+    }
+
+    fn escaper_depend_on(
+        &self,
+        escaper: &NodeName,
+        target: &NodeName,
+        visited: &mut VisitedNodes,
+    ) -> bool {
+        if !visited.escapers.insert(escaper.clone()) {
+            return false;
+        }
+        let Some(conf) = (self.get_escaper)(escaper) else {
+            return false;
+        };
+
+        let resolver = conf.resolver();
+        if !resolver.is_empty() && self.resolver_depend_on(resolver, target, visited) {
+            return true;
+        }
+        conf.dependent_escaper()
+            .unwrap_or_default()
+            .iter()
+            .any(|next| self.escaper_depend_on(next, target, visited))
+    }
+
+    fn resolver_depend_on(
+        &self,
+        resolver: &NodeName,
+        target: &NodeName,
+        visited: &mut VisitedNodes,
+    ) -> bool {
+        if resolver == target {
+            return true;
+        }
+        if !visited.resolvers.insert(resolver.clone()) {
+            return false;
+        }
+        let Some(conf) = (self.get_resolver)(resolver) else {
+            return false;
+        };
+
+        if let Some(escaper) = tunnel_escaper(&conf)
+            && self.escaper_depend_on(escaper, target, visited)
+        {
+            return true;
+        }
+        conf.dependent_resolver()
+            .unwrap_or_default()
+            .iter()
+            .any(|next| self.resolver_depend_on(next, target, visited))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use g3_yaml::YamlDocPosition;
+
+    fn load_map(s: &str) -> yaml_rust::yaml::Hash {
+        let doc = YamlLoader::load_from_str(s).unwrap().pop().unwrap();
+        let Yaml::Hash(map) = doc else {
+            panic!("not a map");
+        };
+        map
+    }
+
+    fn position() -> Option<YamlDocPosition> {
+        Some(YamlDocPosition {
+            path: PathBuf::from("/etc/g3proxy/main.yaml"),
+            index: 0,
+        })
+    }
+
+    fn add_resolver(map: &mut BTreeMap<NodeName, Arc<AnyResolverConfig>>, s: &str) {
+        let conf = super::super::load_resolver(&load_map(s), position()).unwrap();
+        map.insert(conf.name().clone(), Arc::new(conf));
+    }
+
+    fn add_escaper(map: &mut BTreeMap<NodeName, Arc<AnyEscaperConfig>>, s: &str) {
+        let conf = crate::config::escaper::load_escaper(&load_map(s), position()).unwrap();
+        map.insert(conf.name().clone(), Arc::new(conf));
+    }
+
+    fn check(
+        resolvers: &BTreeMap<NodeName, Arc<AnyResolverConfig>>,
+        escapers: &BTreeMap<NodeName, Arc<AnyEscaperConfig>>,
+        name: &str,
+    ) -> anyhow::Result<()> {
+        let checker =
+            TunnelEscaperChecker::new(|n| resolvers.get(n).cloned(), |n| escapers.get(n).cloned());
+        checker.check(&NodeName::from_str(name).unwrap())
+    }
+
+    #[test]
+    fn escaper_not_found() {
+        let mut resolvers = BTreeMap::new();
+        add_resolver(
+            &mut resolvers,
+            "{name: r1, type: hickory, server: 127.0.0.1, escaper: e1}",
+        );
+        let escapers = BTreeMap::new();
+        assert!(check(&resolvers, &escapers, "r1").is_err());
+    }
+
+    #[test]
+    fn no_cycle() {
+        let mut resolvers = BTreeMap::new();
+        add_resolver(
+            &mut resolvers,
+            "{name: r0, type: hickory, server: 127.0.0.1}",
+        );
+        add_resolver(
+            &mut resolvers,
+            "{name: r1, type: hickory, server: 127.0.0.1, escaper: e1}",
+        );
+        let mut escapers = BTreeMap::new();
+        add_escaper(
+            &mut escapers,
+            "{name: e1, type: direct_fixed, resolver: r0}",
+        );
+        assert!(check(&resolvers, &escapers, "r0").is_ok());
+        assert!(check(&resolvers, &escapers, "r1").is_ok());
+    }
+
+    #[test]
+    fn direct_cycle() {
+        let mut resolvers = BTreeMap::new();
+        add_resolver(
+            &mut resolvers,
+            "{name: r1, type: hickory, server: 127.0.0.1, escaper: e1}",
+        );
+        let mut escapers = BTreeMap::new();
+        add_escaper(
+            &mut escapers,
+            "{name: e1, type: direct_fixed, resolver: r1}",
+        );
+        assert!(check(&resolvers, &escapers, "r1").is_err());
+    }
+
+    #[test]
+    fn indirect_cycle() {
+        let mut resolvers = BTreeMap::new();
+        add_resolver(
+            &mut resolvers,
+            "{name: r0, type: hickory, server: 127.0.0.1}",
+        );
+        add_resolver(
+            &mut resolvers,
+            "{name: r1, type: hickory, server: 127.0.0.1, escaper: e1}",
+        );
+        add_resolver(
+            &mut resolvers,
+            "{name: r2, type: fail_over, primary: r0, standby: r1}",
+        );
+        let mut escapers = BTreeMap::new();
+        add_escaper(
+            &mut escapers,
+            "{name: e0, type: direct_fixed, resolver: r0}",
+        );
+        add_escaper(
+            &mut escapers,
+            "{name: e2, type: direct_fixed, resolver: r2}",
+        );
+        add_escaper(
+            &mut escapers,
+            "{name: e1, type: route_failover, primary: e0, standby: e2}",
+        );
+        assert!(check(&resolvers, &escapers, "r1").is_err());
+        assert!(check(&resolvers, &escapers, "r2").is_ok());
+    }
+}
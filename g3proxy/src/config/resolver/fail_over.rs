@@ -5,11 +5,11 @@
 
 use std::collections::BTreeSet;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
-use g3_resolver::ResolverRuntimeConfig;
 use g3_resolver::driver::fail_over::FailOverDriverStaticConfig;
+use g3_resolver::{ResolverCacheSnapshotConfig, ResolverRuntimeConfig};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
@@ -89,6 +89,13 @@ impl FailOverResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "cache_snapshot" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let snapshot = ResolverCacheSnapshotConfig::parse_yaml(v, lookup_dir)
+                    .context(format!("invalid cache snapshot config value for key {k}"))?;
+                self.runtime.cache_snapshot = Some(snapshot);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
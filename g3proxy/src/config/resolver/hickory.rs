@@ -6,11 +6,11 @@
 use std::collections::BTreeSet;
 use std::net::IpAddr;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
 use g3_resolver::driver::hickory::HickoryDriverConfig;
-use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
+use g3_resolver::{AnyResolveDriverConfig, ResolverCacheSnapshotConfig, ResolverRuntimeConfig};
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
@@ -25,6 +25,7 @@ pub(crate) struct HickoryResolverConfig {
     position: Option<YamlDocPosition>,
     runtime: ResolverRuntimeConfig,
     driver: HickoryDriverConfig,
+    escaper: Option<NodeName>,
 }
 
 impl From<&HickoryResolverConfig> for g3_resolver::ResolverConfig {
@@ -44,6 +45,7 @@ impl HickoryResolverConfig {
             position,
             runtime: Default::default(),
             driver: Default::default(),
+            escaper: None,
         }
     }
 
@@ -62,6 +64,11 @@ impl HickoryResolverConfig {
         self.driver.get_server_port()
     }
 
+    #[inline]
+    pub(crate) fn escaper(&self) -> Option<&NodeName> {
+        self.escaper.as_ref()
+    }
+
     pub(crate) fn get_encryption_summary(&self) -> Option<String> {
         self.driver.get_encryption().map(|c| c.summary())
     }
@@ -93,6 +100,18 @@ impl HickoryResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "cache_snapshot" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let snapshot = ResolverCacheSnapshotConfig::parse_yaml(v, lookup_dir)
+                    .context(format!("invalid cache snapshot config value for key {k}"))?;
+                self.runtime.cache_snapshot = Some(snapshot);
+                Ok(())
+            }
+            "escaper" => {
+                let name = g3_yaml::value::as_metric_node_name(v)?;
+                self.escaper = Some(name);
+                Ok(())
+            }
             _ => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 self.driver.set_by_yaml_kv(k, v, Some(lookup_dir))
@@ -104,6 +123,9 @@ impl HickoryResolverConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        if self.escaper.is_some() {
+            self.driver.check_tunnel_support()?;
+        }
         self.driver.check()
     }
 }
@@ -21,10 +21,14 @@ pub(crate) mod hickory;
 
 pub(crate) mod deny_all;
 pub(crate) mod fail_over;
+pub(crate) mod static_hosts;
 
 mod registry;
 pub(crate) use registry::clear;
 
+mod dependency;
+use dependency::TunnelEscaperChecker;
+
 const CONFIG_KEY_RESOLVER_TYPE: &str = "type";
 const CONFIG_KEY_RESOLVER_NAME: &str = "name";
 
@@ -54,6 +58,7 @@ pub(crate) enum AnyResolverConfig {
     Hickory(Box<hickory::HickoryResolverConfig>),
     DenyAll(deny_all::DenyAllResolverConfig),
     FailOver(fail_over::FailOverResolverConfig),
+    StaticHosts(static_hosts::StaticHostsResolverConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -78,7 +83,7 @@ pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<Any
     if let Yaml::Hash(map) = doc {
         let resolver = load_resolver(&map, Some(position.clone()))?;
         let old_resolver = registry::add(resolver.clone());
-        if let Err(e) = build_topology_map() {
+        if let Err(e) = build_topology_map().and_then(|_| check_tunnel_escaper()) {
             // rollback
             match old_resolver {
                 Some(resolver) => {
@@ -121,6 +126,11 @@ fn load_resolver(
                 .context("failed to load this FailOver resolver")?;
             Ok(AnyResolverConfig::FailOver(resolver))
         }
+        "static" | "static_hosts" | "hosts" => {
+            let resolver = static_hosts::StaticHostsResolverConfig::parse(map, position)
+                .context("failed to load this static resolver")?;
+            Ok(AnyResolverConfig::StaticHosts(resolver))
+        }
         _ => Err(anyhow!("unsupported resolver type {resolver_type}")),
     }
 }
@@ -138,6 +148,18 @@ fn build_topology_map() -> anyhow::Result<TopoMap> {
     Ok(topo_map)
 }
 
+/// Check the escapers used by resolvers to reach their dns servers,
+/// should be called after all escapers and resolvers have been loaded
+pub(crate) fn check_tunnel_escaper() -> anyhow::Result<()> {
+    let checker = TunnelEscaperChecker::new(registry::get, super::escaper::get);
+    for name in registry::get_all_names() {
+        checker
+            .check(&name)
+            .context(format!("invalid escaper for resolver {name}"))?;
+    }
+    Ok(())
+}
+
 pub(crate) fn get_all_sorted() -> anyhow::Result<Vec<Arc<AnyResolverConfig>>> {
     let topo_map = build_topology_map()?;
     let sorted_nodes = topo_map.sorted_nodes();
@@ -0,0 +1,122 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::ResolverRuntimeConfig;
+use g3_resolver::driver::static_hosts::StaticHostsDriverConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "static";
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct StaticHostsResolverConfig {
+    position: Option<YamlDocPosition>,
+    name: NodeName,
+    pub(crate) runtime: ResolverRuntimeConfig,
+    pub(crate) driver: StaticHostsDriverConfig,
+    pub(crate) next: Option<NodeName>,
+}
+
+impl StaticHostsResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        StaticHostsResolverConfig {
+            position,
+            name: NodeName::default(),
+            runtime: Default::default(),
+            driver: StaticHostsDriverConfig::default(),
+            next: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "next" => {
+                self.next = Some(g3_yaml::value::as_metric_node_name(v)?);
+                Ok(())
+            }
+            "graceful_stop_wait" => {
+                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "protective_query_timeout" => {
+                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            _ => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                self.driver.set_by_yaml_kv(k, v, Some(lookup_dir))
+            }
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if let Some(next) = &self.next
+            && next.eq(&self.name)
+        {
+            return Err(anyhow!("the next resolver should not be itself"));
+        }
+        self.driver.check()
+    }
+}
+
+impl ResolverConfig for StaticHostsResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::StaticHosts(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        let next = self.next.as_ref()?;
+        let mut set = BTreeSet::new();
+        set.insert(next.clone());
+        Some(set)
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_yaml::YamlDocPosition;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpCacheDiskConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) size: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpCacheConfig {
+    pub(crate) memory_size: usize,
+    pub(crate) disk: Option<HttpCacheDiskConfig>,
+    pub(crate) max_object_size: usize,
+    pub(crate) max_ttl: Duration,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        HttpCacheConfig {
+            memory_size: 64 * 1024 * 1024,
+            disk: None,
+            max_object_size: 8 * 1024 * 1024,
+            max_ttl: Duration::from_secs(86400),
+        }
+    }
+}
+
+impl HttpCacheConfig {
+    pub(crate) fn parse_yaml(value: &Yaml, doc: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = HttpCacheConfig::default();
+                let mut disk_path: Option<PathBuf> = None;
+                let mut disk_size: u64 = 1024 * 1024 * 1024;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "memory_size" | "memory" => {
+                        config.memory_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "disk_path" | "disk_dir" => {
+                        let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                        let path = g3_yaml::value::as_dir_path(v, lookup_dir, true)
+                            .context(format!("invalid directory path value for key {k}"))?;
+                        disk_path = Some(path);
+                        Ok(())
+                    }
+                    "disk_size" => {
+                        disk_size = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_object_size" => {
+                        config.max_object_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_ttl" => {
+                        config.max_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                if let Some(path) = disk_path {
+                    config.disk = Some(HttpCacheDiskConfig {
+                        path,
+                        size: disk_size,
+                    });
+                }
+                config.check()?;
+                Ok(config)
+            }
+            Yaml::Boolean(true) => Ok(HttpCacheConfig::default()),
+            _ => Err(anyhow!(
+                "yaml value type for 'HttpCacheConfig' should be 'map' or 'boolean'"
+            )),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.max_object_size == 0 {
+            return Err(anyhow!("max object size should not be zero"));
+        }
+        if self.memory_size < self.max_object_size {
+            return Err(anyhow!(
+                "memory size should not be less than the max object size"
+            ));
+        }
+        if let Some(disk) = &self.disk
+            && disk.size < self.max_object_size as u64
+        {
+            return Err(anyhow!(
+                "disk size should not be less than the max object size"
+            ));
+        }
+        if self.max_ttl.is_zero() {
+            return Err(anyhow!("max ttl should not be zero"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+/// settings for the h2 / h3 client connections
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpMultiplexConfig {
+    pub(crate) max_header_list_size: u32,
+    pub(crate) max_concurrent_streams: u32,
+    stream_window_size: u32,
+    connection_window_size: u32,
+    max_frame_size: u32,
+    pub(crate) max_send_buffer_size: usize,
+    pub(crate) handshake_timeout: Duration,
+    pub(crate) preface_timeout: Duration,
+}
+
+impl Default for HttpMultiplexConfig {
+    fn default() -> Self {
+        HttpMultiplexConfig {
+            max_header_list_size: 64 * 1024, // 64KiB
+            max_concurrent_streams: 128,
+            stream_window_size: 1024 * 1024,         // 1MiB
+            connection_window_size: 2 * 1024 * 1024, // 2MiB
+            max_frame_size: 16 * 1024,               // 16KiB
+            max_send_buffer_size: 1024 * 1024,       // 1MiB
+            handshake_timeout: Duration::from_secs(4),
+            preface_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl HttpMultiplexConfig {
+    #[inline]
+    pub(crate) fn max_frame_size(&self) -> u32 {
+        self.max_frame_size
+    }
+
+    fn set_max_frame_size(&mut self, size: u32) {
+        self.max_frame_size = size.clamp(1 << 14, (1 << 24) - 1);
+    }
+
+    #[inline]
+    pub(crate) fn stream_window_size(&self) -> u32 {
+        self.stream_window_size
+    }
+
+    fn set_stream_window_size(&mut self, size: u32) {
+        self.stream_window_size = size.max(65536);
+    }
+
+    #[inline]
+    pub(crate) fn connection_window_size(&self) -> u32 {
+        self.connection_window_size
+    }
+
+    fn set_connection_window_size(&mut self, size: u32) {
+        self.connection_window_size = size.max(65536);
+    }
+
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for 'HttpMultiplexConfig' should be 'map'"
+            ));
+        };
+
+        let mut config = HttpMultiplexConfig::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "max_header_list_size" | "max_header_size" => {
+                config.max_header_list_size = g3_yaml::humanize::as_u32(v)
+                    .context(format!("invalid humanize u32 value for key {k}"))?;
+                Ok(())
+            }
+            "max_concurrent_streams" => {
+                config.max_concurrent_streams =
+                    g3_yaml::value::as_u32(v).context(format!("invalid u32 value for key {k}"))?;
+                Ok(())
+            }
+            "max_frame_size" => {
+                let max_frame_size = g3_yaml::humanize::as_u32(v)
+                    .context(format!("invalid humanize u32 value for key {k}"))?;
+                config.set_max_frame_size(max_frame_size);
+                Ok(())
+            }
+            "stream_window_size" => {
+                let window_size = g3_yaml::humanize::as_u32(v)
+                    .context(format!("invalid humanize u32 value for key {k}"))?;
+                config.set_stream_window_size(window_size);
+                Ok(())
+            }
+            "connection_window_size" => {
+                let window_size = g3_yaml::humanize::as_u32(v)
+                    .context(format!("invalid humanize u32 value for key {k}"))?;
+                config.set_connection_window_size(window_size);
+                Ok(())
+            }
+            "max_send_buffer_size" => {
+                config.max_send_buffer_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "handshake_timeout" => {
+                config.handshake_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "preface_timeout" => {
+                config.preface_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if config.max_concurrent_streams == 0 {
+            return Err(anyhow!("max concurrent streams should not be zero"));
+        }
+        Ok(config)
+    }
+}
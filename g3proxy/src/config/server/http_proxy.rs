@@ -16,20 +16,22 @@ use log::warn;
 use yaml_rust::{Yaml, yaml};
 
 use g3_ftp_client::FtpClientConfig;
-use g3_io_ext::StreamCopyConfig;
+use g3_io_ext::{LimitedUdpRelayConfig, StreamCopyConfig};
 use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::{AclExactPortRule, AclNetworkRuleBuilder};
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{
-    Host, HttpKeepAliveConfig, HttpServerId, OpensslClientConfigBuilder, RustlsServerConfigBuilder,
-    TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
+    Host, HttpHeaderRewriteRule, HttpKeepAliveConfig, HttpServerId, OpensslClientConfigBuilder,
+    RustlsServerConfigBuilder, SocketBufferConfig, TcpListenConfig, TcpMiscSockOpts,
+    TcpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
 
 use super::{
-    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
-    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
+    AnyServerConfig, HttpCacheConfig, HttpMultiplexConfig, IDLE_CHECK_DEFAULT_DURATION,
+    IDLE_CHECK_DEFAULT_MAX_COUNT, IDLE_CHECK_MAXIMUM_DURATION, ServerConfig,
+    ServerConfigDiffAction,
 };
 use crate::config::auth::UsernameParamsConfig;
 
@@ -82,6 +84,12 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) task_log_flush_interval: Option<Duration>,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
+    pub(crate) enable_h2: bool,
+    pub(crate) enable_h3: bool,
+    pub(crate) multiplex: HttpMultiplexConfig,
+    pub(crate) enable_connect_udp: bool,
+    pub(crate) udp_socket_buffer: SocketBufferConfig,
+    pub(crate) udp_relay: LimitedUdpRelayConfig,
     pub(crate) req_hdr_max_size: usize,
     pub(crate) rsp_hdr_max_size: usize,
     pub(crate) log_uri_max_chars: usize,
@@ -97,6 +105,8 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) egress_path_selection_header: Option<HeaderName>,
     pub(crate) steal_forwarded_for: bool,
+    pub(crate) header_rewrite: Vec<HttpHeaderRewriteRule>,
+    pub(crate) http_cache: Option<HttpCacheConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
     // Optional: derive next-hop escaper addr from username params
     pub(crate) username_params: Option<UsernameParamsConfig>,
@@ -132,6 +142,12 @@ impl HttpProxyServerConfig {
             task_log_flush_interval: None,
             tcp_copy: Default::default(),
             tcp_misc_opts: Default::default(),
+            enable_h2: false,
+            enable_h3: false,
+            multiplex: HttpMultiplexConfig::default(),
+            enable_connect_udp: false,
+            udp_socket_buffer: SocketBufferConfig::default(),
+            udp_relay: Default::default(),
             req_hdr_max_size: 65536, // 64KiB
             rsp_hdr_max_size: 65536, // 64KiB
             log_uri_max_chars: 1024,
@@ -147,6 +163,8 @@ impl HttpProxyServerConfig {
             untrusted_read_limit: None,
             egress_path_selection_header: None,
             steal_forwarded_for: false,
+            header_rewrite: Vec::new(),
+            http_cache: None,
             extra_metrics_tags: None,
             username_params: None,
         }
@@ -305,6 +323,48 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid tcp misc sock opts value for key {k}"))?;
                 Ok(())
             }
+            "enable_h2" | "h2_enabled" => {
+                self.enable_h2 = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "enable_h3" | "h3_enabled" => {
+                self.enable_h3 = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "multiplex" => {
+                self.multiplex = HttpMultiplexConfig::parse_yaml(v)
+                    .context(format!("invalid http multiplex config value for key {k}"))?;
+                Ok(())
+            }
+            "enable_connect_udp" | "connect_udp_enabled" => {
+                self.enable_connect_udp = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "udp_socket_buffer" => {
+                self.udp_socket_buffer = g3_yaml::value::as_socket_buffer_config(v)
+                    .context(format!("invalid socket buffer config value for key {k}"))?;
+                Ok(())
+            }
+            "udp_relay_packet_size" => {
+                let packet_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_packet_size(packet_size);
+                Ok(())
+            }
+            "udp_relay_yield_size" => {
+                let yield_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_yield_size(yield_size);
+                Ok(())
+            }
+            "udp_relay_batch_size" => {
+                let batch_size = g3_yaml::value::as_usize(v)?;
+                self.udp_relay.set_batch_size(batch_size);
+                Ok(())
+            }
             "task_idle_check_duration" => {
                 warn!("deprecated config key '{k}', please use 'task_idle_check_interval' instead");
                 self.set("task_idle_check_interval", v)
@@ -426,6 +486,20 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid boolean value for key {k}"))?;
                 Ok(())
             }
+            "header_rewrite" => {
+                self.header_rewrite =
+                    g3_yaml::value::as_list(v, g3_yaml::value::as_http_header_rewrite_rule)
+                        .context(format!(
+                            "invalid http header rewrite rule list value for key {k}"
+                        ))?;
+                Ok(())
+            }
+            "http_cache" => {
+                let cache = HttpCacheConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid http cache config value for key {k}"))?;
+                self.http_cache = Some(cache);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
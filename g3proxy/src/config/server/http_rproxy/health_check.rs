@@ -0,0 +1,144 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use http::uri::PathAndQuery;
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum HttpHealthCheckProtocol {
+    Tcp,
+    Http {
+        path: PathAndQuery,
+        expect_status: Vec<u16>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpHealthCheckConfig {
+    pub(crate) protocol: HttpHealthCheckProtocol,
+    pub(crate) interval: Duration,
+    pub(crate) timeout: Duration,
+    pub(crate) rise: u32,
+    pub(crate) fall: u32,
+}
+
+fn as_is_http_protocol(value: &Yaml) -> anyhow::Result<bool> {
+    let protocol = g3_yaml::value::as_string(value)?;
+    match protocol.to_lowercase().as_str() {
+        "tcp" => Ok(false),
+        "http" => Ok(true),
+        _ => Err(anyhow!("unsupported health check protocol {protocol}")),
+    }
+}
+
+impl Default for HttpHealthCheckConfig {
+    fn default() -> Self {
+        HttpHealthCheckConfig {
+            protocol: HttpHealthCheckProtocol::Tcp,
+            interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(2),
+            rise: 2,
+            fall: 3,
+        }
+    }
+}
+
+impl HttpHealthCheckConfig {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = HttpHealthCheckConfig::default();
+                let mut is_http = false;
+                let mut path = PathAndQuery::from_static("/");
+                let mut expect_status = Vec::new();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "protocol" | "type" => {
+                        is_http = as_is_http_protocol(v)
+                            .context(format!("invalid health check protocol value for key {k}"))?;
+                        Ok(())
+                    }
+                    "interval" => {
+                        config.interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "timeout" => {
+                        config.timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "rise" | "healthy_threshold" => {
+                        config.rise = g3_yaml::value::as_nonzero_u32(v)
+                            .context(format!("invalid nonzero u32 value for key {k}"))?
+                            .get();
+                        Ok(())
+                    }
+                    "fall" | "unhealthy_threshold" => {
+                        config.fall = g3_yaml::value::as_nonzero_u32(v)
+                            .context(format!("invalid nonzero u32 value for key {k}"))?
+                            .get();
+                        Ok(())
+                    }
+                    "path" | "uri" => {
+                        path = g3_yaml::value::as_http_path_and_query(v)
+                            .context(format!("invalid http path value for key {k}"))?;
+                        Ok(())
+                    }
+                    "expect_status" | "status" => {
+                        expect_status = g3_yaml::value::as_list(v, |v| {
+                            let code = g3_yaml::value::as_u16(v)?;
+                            if !(100..600).contains(&code) {
+                                return Err(anyhow!("invalid http status code {code}"));
+                            }
+                            Ok(code)
+                        })
+                        .context(format!("invalid http status code list value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                if is_http {
+                    config.protocol = HttpHealthCheckProtocol::Http {
+                        path,
+                        expect_status,
+                    };
+                }
+                config.check()?;
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let mut config = HttpHealthCheckConfig::default();
+                if as_is_http_protocol(value)? {
+                    config.protocol = HttpHealthCheckProtocol::Http {
+                        path: PathAndQuery::from_static("/"),
+                        expect_status: Vec::new(),
+                    };
+                }
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'HttpHealthCheckConfig' should be 'map' or 'string'"
+            )),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.interval.is_zero() {
+            return Err(anyhow!("health check interval should not be zero"));
+        }
+        if self.timeout.is_zero() {
+            return Err(anyhow!("health check timeout should not be zero"));
+        }
+        if self.timeout > self.interval {
+            return Err(anyhow!(
+                "health check timeout should not be greater than the interval"
+            ));
+        }
+        Ok(())
+    }
+}
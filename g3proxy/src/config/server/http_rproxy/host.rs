@@ -6,32 +6,16 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
-use g3_types::net::{Host, OpensslClientConfigBuilder, RustlsServerConfigBuilder, UpstreamAddr};
+use g3_types::net::RustlsServerConfigBuilder;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
-#[derive(Debug, PartialEq)]
+use super::{HttpRouteConfig, HttpUpstreamConfig};
+
+#[derive(Debug, Default, PartialEq)]
 pub(crate) struct HttpHostConfig {
-    upstream: UpstreamAddr,
     pub(crate) tls_server_builder: Option<RustlsServerConfigBuilder>,
-    pub(crate) tls_client_builder: Option<OpensslClientConfigBuilder>,
-    pub(crate) tls_name: Host,
-}
-
-impl Default for HttpHostConfig {
-    fn default() -> Self {
-        HttpHostConfig {
-            upstream: UpstreamAddr::empty(),
-            tls_server_builder: None,
-            tls_client_builder: None,
-            tls_name: Host::empty(),
-        }
-    }
-}
-
-impl HttpHostConfig {
-    pub(crate) fn upstream(&self) -> &UpstreamAddr {
-        &self.upstream
-    }
+    pub(crate) upstream: HttpUpstreamConfig,
+    pub(crate) routes: Vec<HttpRouteConfig>,
 }
 
 impl YamlMapCallback for HttpHostConfig {
@@ -46,11 +30,6 @@ impl YamlMapCallback for HttpHostConfig {
         doc: Option<&YamlDocPosition>,
     ) -> anyhow::Result<()> {
         match key {
-            "upstream" => {
-                self.upstream = g3_yaml::value::as_upstream_addr(value, 80)
-                    .context(format!("invalid upstream addr value for key {key}"))?;
-                Ok(())
-            }
             "tls_server" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
                 let builder =
@@ -61,34 +40,30 @@ impl YamlMapCallback for HttpHostConfig {
                 self.tls_server_builder = Some(builder);
                 Ok(())
             }
-            "tls_client" => {
-                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
-                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
-                    value,
-                    Some(lookup_dir),
-                )
-                .context(format!(
-                    "invalid openssl tls client config value for key {key}"
-                ))?;
-                self.tls_client_builder = Some(builder);
-                Ok(())
-            }
-            "tls_name" => {
-                self.tls_name = g3_yaml::value::as_host(value)
-                    .context(format!("invalid tls name value for key {key}"))?;
+            "routes" => {
+                self.routes =
+                    g3_yaml::value::as_list(value, |v| HttpRouteConfig::parse_yaml(v, doc))
+                        .context(format!("invalid http route list value for key {key}"))?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {key}")),
+            _ => self.upstream.parse_kv(key, value, doc),
         }
     }
 
     fn check(&mut self) -> anyhow::Result<()> {
         if self.upstream.is_empty() {
-            return Err(anyhow!("upstream is empty"));
-        }
-        if self.tls_name.is_empty() {
-            self.upstream.host().clone_into(&mut self.tls_name);
+            if self.routes.is_empty() {
+                return Err(anyhow!("upstream is empty"));
+            }
+            if self.upstream.tls_client_builder.is_some() {
+                return Err(anyhow!("tls_client is set but no default upstream"));
+            }
+            if self.upstream.health_check.is_some() {
+                return Err(anyhow!("health_check is set but no default upstream"));
+            }
+            Ok(())
+        } else {
+            self.upstream.check()
         }
-        Ok(())
     }
 }
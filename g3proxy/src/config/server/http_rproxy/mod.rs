@@ -17,20 +17,29 @@ use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{
-    HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId, RustlsServerConfigBuilder,
-    TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
+    HttpForwardedHeaderType, HttpHeaderRewriteRule, HttpKeepAliveConfig, HttpServerId,
+    RustlsServerConfigBuilder, TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
 };
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
 
 use super::{
-    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
+    AnyServerConfig, HttpCacheConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
 };
 
+mod health_check;
+pub(crate) use health_check::{HttpHealthCheckConfig, HttpHealthCheckProtocol};
+
 mod host;
 pub(crate) use host::HttpHostConfig;
 
+mod route;
+pub(crate) use route::HttpRouteConfig;
+
+mod upstream;
+pub(crate) use upstream::HttpUpstreamConfig;
+
 const SERVER_CONFIG_TYPE: &str = "HttpRProxy";
 
 /// collection of timeout config
@@ -82,6 +91,8 @@ pub(crate) struct HttpRProxyServerConfig {
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) append_forwarded_for: HttpForwardedHeaderType,
+    pub(crate) header_rewrite: Vec<HttpHeaderRewriteRule>,
+    pub(crate) http_cache: Option<HttpCacheConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
     pub(crate) hosts: HostMatch<Arc<HttpHostConfig>>,
     pub(crate) enable_tls_server: bool,
@@ -122,6 +133,8 @@ impl HttpRProxyServerConfig {
             http_forward_upstream_keepalive: Default::default(),
             untrusted_read_limit: None,
             append_forwarded_for: HttpForwardedHeaderType::default(),
+            header_rewrite: Vec::new(),
+            http_cache: None,
             extra_metrics_tags: None,
             hosts: Default::default(),
             enable_tls_server: false,
@@ -320,6 +333,20 @@ impl HttpRProxyServerConfig {
                     ))?;
                 Ok(())
             }
+            "header_rewrite" => {
+                self.header_rewrite =
+                    g3_yaml::value::as_list(v, g3_yaml::value::as_http_header_rewrite_rule)
+                        .context(format!(
+                            "invalid http header rewrite rule list value for key {k}"
+                        ))?;
+                Ok(())
+            }
+            "http_cache" => {
+                let cache = HttpCacheConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid http cache config value for key {k}"))?;
+                self.http_cache = Some(cache);
+                Ok(())
+            }
             "hosts" | "sites" => {
                 self.hosts = g3_yaml::value::as_host_matched_obj(v, self.position.as_ref())
                     .context(format!(
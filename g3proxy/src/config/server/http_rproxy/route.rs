@@ -0,0 +1,107 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, Method};
+use yaml_rust::Yaml;
+
+use g3_yaml::YamlDocPosition;
+
+use super::HttpUpstreamConfig;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct HttpRouteHeaderMatch {
+    pub(crate) name: HeaderName,
+    pub(crate) value_regex: Option<String>,
+}
+
+impl HttpRouteHeaderMatch {
+    fn parse_yaml(k: &Yaml, v: &Yaml) -> anyhow::Result<Self> {
+        let name = g3_yaml::value::as_http_header_name(k)?;
+        let value_regex = match v {
+            Yaml::Null => None,
+            _ => {
+                let regex = g3_yaml::value::as_regex(v)
+                    .context(format!("invalid regex value for header {name}"))?;
+                Some(regex.as_str().to_string())
+            }
+        };
+        Ok(HttpRouteHeaderMatch { name, value_regex })
+    }
+}
+
+/// A route rule inside a local http host.
+///
+/// All set conditions should match, and for each condition, matching any of the
+/// values is enough.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct HttpRouteConfig {
+    pub(crate) path_prefix: Vec<String>,
+    pub(crate) path_regex: Vec<String>,
+    pub(crate) methods: Vec<Method>,
+    pub(crate) headers: Vec<HttpRouteHeaderMatch>,
+    pub(crate) upstream: HttpUpstreamConfig,
+}
+
+impl HttpRouteConfig {
+    pub(crate) fn parse_yaml(value: &Yaml, doc: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = value {
+            let mut route = HttpRouteConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| route.set(k, v, doc))?;
+            route.check()?;
+            Ok(route)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'HttpRouteConfig' should be 'map'"
+            ))
+        }
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml, doc: Option<&YamlDocPosition>) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "path_prefix" | "prefix_match" => {
+                self.path_prefix = g3_yaml::value::as_list(v, |v| {
+                    let prefix = g3_yaml::value::as_string(v)?;
+                    if !prefix.starts_with('/') {
+                        return Err(anyhow!("path prefix {prefix} should start with '/'"));
+                    }
+                    Ok(prefix)
+                })
+                .context(format!("invalid path prefix string list value for key {k}"))?;
+                Ok(())
+            }
+            "path_regex" | "regex_match" => {
+                self.path_regex = g3_yaml::value::as_list(v, |v| {
+                    g3_yaml::value::as_regex(v).map(|r| r.to_string())
+                })
+                .context(format!("invalid regex string list value for key {k}"))?;
+                Ok(())
+            }
+            "method" | "methods" => {
+                self.methods = g3_yaml::value::as_list(v, g3_yaml::value::as_http_method)
+                    .context(format!("invalid http method list value for key {k}"))?;
+                Ok(())
+            }
+            "header" | "headers" => {
+                if let Yaml::Hash(map) = v {
+                    let mut headers = Vec::with_capacity(map.len());
+                    for (k, v) in map.iter() {
+                        let header = HttpRouteHeaderMatch::parse_yaml(k, v)?;
+                        headers.push(header);
+                    }
+                    self.headers = headers;
+                    Ok(())
+                } else {
+                    Err(anyhow!("yaml value type for key {k} should be 'map'"))
+                }
+            }
+            normalized_key => self.upstream.parse_kv(normalized_key, v, doc),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        self.upstream.check()
+    }
+}
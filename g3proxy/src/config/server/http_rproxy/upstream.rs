@@ -0,0 +1,104 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::net::{Host, OpensslClientConfigBuilder, WeightedUpstreamAddr};
+use g3_yaml::YamlDocPosition;
+
+use super::HttpHealthCheckConfig;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct HttpUpstreamConfig {
+    upstream: Vec<WeightedUpstreamAddr>,
+    pub(crate) upstream_pick_policy: SelectivePickPolicy,
+    pub(crate) health_check: Option<HttpHealthCheckConfig>,
+    pub(crate) tls_client_builder: Option<OpensslClientConfigBuilder>,
+    pub(crate) tls_name: Option<Host>,
+}
+
+impl Default for HttpUpstreamConfig {
+    fn default() -> Self {
+        HttpUpstreamConfig {
+            upstream: Vec::new(),
+            upstream_pick_policy: SelectivePickPolicy::Random,
+            health_check: None,
+            tls_client_builder: None,
+            tls_name: None,
+        }
+    }
+}
+
+impl HttpUpstreamConfig {
+    pub(crate) fn upstream(&self) -> &[WeightedUpstreamAddr] {
+        &self.upstream
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.upstream.is_empty()
+    }
+
+    pub(super) fn parse_kv(
+        &mut self,
+        key: &str,
+        value: &Yaml,
+        doc: Option<&YamlDocPosition>,
+    ) -> anyhow::Result<()> {
+        match key {
+            "upstream" => {
+                self.upstream = g3_yaml::value::as_list(value, |v| {
+                    g3_yaml::value::as_weighted_upstream_addr(v, 80)
+                })
+                .context(format!(
+                    "invalid weighted upstream address value for key {key}"
+                ))?;
+                Ok(())
+            }
+            "upstream_pick_policy" => {
+                self.upstream_pick_policy = g3_yaml::value::as_selective_pick_policy(value)
+                    .context(format!("invalid selective pick policy value for key {key}"))?;
+                Ok(())
+            }
+            "health_check" => {
+                let config = HttpHealthCheckConfig::parse_yaml(value)
+                    .context(format!("invalid health check config value for key {key}"))?;
+                self.health_check = Some(config);
+                Ok(())
+            }
+            "tls_client" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                    value,
+                    Some(lookup_dir),
+                )
+                .context(format!(
+                    "invalid openssl tls client config value for key {key}"
+                ))?;
+                self.tls_client_builder = Some(builder);
+                Ok(())
+            }
+            "tls_name" => {
+                let tls_name = g3_yaml::value::as_host(value)
+                    .context(format!("invalid tls name value for key {key}"))?;
+                self.tls_name = Some(tls_name);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {key}")),
+        }
+    }
+
+    pub(super) fn check(&mut self) -> anyhow::Result<()> {
+        if self.upstream.is_empty() {
+            return Err(anyhow!("upstream is empty"));
+        }
+        if self.upstream.iter().all(|v| v.weight() <= 0.0) {
+            return Err(anyhow!("no upstream with positive weight"));
+        }
+        Ok(())
+    }
+}
@@ -42,10 +42,18 @@ pub(crate) mod tcp_stream;
 ))]
 pub(crate) mod tcp_tproxy;
 pub(crate) mod tls_stream;
+#[cfg(target_os = "linux")]
+pub(crate) mod udp_tproxy;
 
 mod registry;
 pub(crate) use registry::clear;
 
+mod http_cache;
+pub(crate) use http_cache::{HttpCacheConfig, HttpCacheDiskConfig};
+
+mod http_multiplex;
+pub(crate) use http_multiplex::HttpMultiplexConfig;
+
 const CONFIG_KEY_SERVER_TYPE: &str = "type";
 const CONFIG_KEY_SERVER_NAME: &str = "name";
 
@@ -146,6 +154,8 @@ pub(crate) enum AnyServerConfig {
     ))]
     TcpTProxy(tcp_tproxy::TcpTProxyServerConfig),
     TlsStream(tls_stream::TlsStreamServerConfig),
+    #[cfg(target_os = "linux")]
+    UdpTProxy(udp_tproxy::UdpTProxyServerConfig),
     SniProxy(sni_proxy::SniProxyServerConfig),
     SocksProxy(socks_proxy::SocksProxyServerConfig),
     HttpProxy(http_proxy::HttpProxyServerConfig),
@@ -250,6 +260,12 @@ fn load_server(
                 .context("failed to load this TLsStream server")?;
             Ok(AnyServerConfig::TlsStream(server))
         }
+        #[cfg(target_os = "linux")]
+        "udp_tproxy" | "udptproxy" => {
+            let server = udp_tproxy::UdpTProxyServerConfig::parse(map, position)
+                .context("failed to load this UdpTProxy server")?;
+            Ok(AnyServerConfig::UdpTProxy(server))
+        }
         "sni_proxy" | "sniproxy" => {
             let server = sni_proxy::SniProxyServerConfig::parse(map, position)
                 .context("failed to load this SniProxy server")?;
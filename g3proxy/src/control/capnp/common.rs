@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use g3proxy_proto::types_capnp::operation_result;
+use g3proxy_proto::types_capnp::{fetch_result, operation_result};
 
 pub(super) fn set_operation_result(
     mut builder: operation_result::Builder<'_>,
@@ -18,3 +18,12 @@ pub(super) fn set_operation_result(
         }
     }
 }
+
+pub(super) fn set_fetch_error<T>(builder: fetch_result::Builder<'_, T>, e: anyhow::Error)
+where
+    T: capnp::traits::Owned,
+{
+    let mut ev = builder.init_err();
+    ev.set_code(-1);
+    ev.set_reason(format!("{e:?}").as_str());
+}
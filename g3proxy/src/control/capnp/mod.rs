@@ -6,7 +6,7 @@
 use g3proxy_proto::proc_capnp::proc_control;
 
 mod common;
-use common::set_operation_result;
+use common::{set_fetch_error, set_operation_result};
 mod proc;
 
 mod escaper;
@@ -4,12 +4,20 @@
  */
 
 use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use uuid::Uuid;
 
 use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
 
 use g3proxy_proto::server_capnp::server_control;
 
-use crate::serve::ArcServer;
+use super::{set_fetch_error, set_operation_result};
+use crate::serve::{ArcServer, LiveTask, LiveTaskRegistry};
 
 pub(super) struct ServerControlImpl {
     server: ArcServer,
@@ -21,6 +29,30 @@ impl ServerControlImpl {
         let server = crate::serve::get_server(&name)?;
         Ok(capnp_rpc::new_client(ServerControlImpl { server }))
     }
+
+    fn live_tasks(&self) -> anyhow::Result<Arc<LiveTaskRegistry>> {
+        self.server
+            .get_server_stats()
+            .and_then(|stats| stats.live_tasks().cloned())
+            .ok_or_else(|| anyhow!("task management is not supported on this server"))
+    }
+}
+
+fn upstream_match(upstream: Option<&UpstreamAddr>, filter: &str) -> bool {
+    let Some(upstream) = upstream else {
+        return false;
+    };
+    upstream.to_string() == filter || upstream.host().to_string() == filter
+}
+
+fn task_match(task: &LiveTask, user: &str, upstream: &str) -> bool {
+    if !user.is_empty() && task.user().map(|u| u.as_str()) != Some(user) {
+        return false;
+    }
+    if !upstream.is_empty() && !upstream_match(task.upstream(), upstream) {
+        return false;
+    }
+    true
 }
 
 impl server_control::Server for ServerControlImpl {
@@ -42,4 +74,66 @@ impl server_control::Server for ServerControlImpl {
             ))
         }
     }
+
+    async fn list_task(
+        self: Rc<Self>,
+        params: server_control::ListTaskParams,
+        mut results: server_control::ListTaskResults,
+    ) -> capnp::Result<()> {
+        let params = params.get()?;
+        let user = params.get_user()?.to_str()?;
+        let upstream = params.get_upstream()?.to_str()?;
+        let builder = results.get().init_result();
+        let registry = match self.live_tasks() {
+            Ok(registry) => registry,
+            Err(e) => {
+                set_fetch_error(builder, e);
+                return Ok(());
+            }
+        };
+
+        let tasks: Vec<Arc<LiveTask>> = registry
+            .all()
+            .into_iter()
+            .filter(|task| task_match(task, user, upstream))
+            .collect();
+        let now = Utc::now();
+        let mut builder = builder.initn_data(tasks.len() as u32);
+        for (i, task) in tasks.iter().enumerate() {
+            let mut task_builder = builder.reborrow().get(i as u32);
+            task_builder.set_id(task.id().to_string().as_str());
+            if let Some(user) = task.user() {
+                task_builder.set_user(user.as_str());
+            }
+            task_builder.set_client_addr(task.client_addr().to_string().as_str());
+            if let Some(upstream) = task.upstream() {
+                task_builder.set_upstream(upstream.to_string().as_str());
+            }
+            task_builder.set_escaper(task.escaper().as_str());
+            task_builder.set_start_time(task.start_at().timestamp());
+            let alive_time = now.signed_duration_since(task.start_at()).num_seconds();
+            task_builder.set_alive_time(alive_time.max(0) as u64);
+            task_builder.set_client_read_bytes(task.traffic().client_read_bytes());
+            task_builder.set_client_write_bytes(task.traffic().client_write_bytes());
+        }
+        Ok(())
+    }
+
+    async fn kill_task(
+        self: Rc<Self>,
+        params: server_control::KillTaskParams,
+        mut results: server_control::KillTaskResults,
+    ) -> capnp::Result<()> {
+        let task = params.get()?.get_task()?.to_str()?;
+        let r = self.live_tasks().and_then(|registry| {
+            let id = Uuid::from_str(task).map_err(|e| anyhow!("invalid task id {task}: {e}"))?;
+            if registry.kill(&id) {
+                Ok(())
+            } else {
+                Err(anyhow!("no task {id} found"))
+            }
+        });
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
 }
@@ -4,12 +4,18 @@
  */
 
 use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use uuid::Uuid;
 
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::user_group_capnp::user_group_control;
 
-use super::set_operation_result;
+use super::{set_fetch_error, set_operation_result};
 use crate::auth::UserGroup;
 
 pub(super) struct UserGroupControlImpl {
@@ -32,7 +38,7 @@ impl user_group_control::Server for UserGroupControlImpl {
     ) -> capnp::Result<()> {
         let v = self.user_group.all_static_users();
         let mut builder = results.get().init_result(v.len() as u32);
-        for (i, name) in v.into_iter().enumerate() {
+        for (i, name) in v.iter().enumerate() {
             builder.set(i as u32, name);
         }
         Ok(())
@@ -62,4 +68,117 @@ impl user_group_control::Server for UserGroupControlImpl {
         set_operation_result(results.get().init_result(), r);
         Ok(())
     }
+
+    async fn get_user(
+        self: Rc<Self>,
+        params: user_group_control::GetUserParams,
+        mut results: user_group_control::GetUserResults,
+    ) -> capnp::Result<()> {
+        let user = params.get()?.get_user()?.to_str()?;
+        let mut builder = results.get().init_result();
+        match self.user_group.get_user_info(user) {
+            Ok(info) => builder.set_data(info.as_str())?,
+            Err(e) => set_fetch_error(builder, e),
+        }
+        Ok(())
+    }
+
+    async fn block_user(
+        self: Rc<Self>,
+        params: user_group_control::BlockUserParams,
+        mut results: user_group_control::BlockUserResults,
+    ) -> capnp::Result<()> {
+        let params = params.get()?;
+        let user = params.get_user()?.to_str()?;
+        let delay = Duration::from_millis(params.get_delay() as u64);
+        let r = self.user_group.block_user(user, delay);
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
+
+    async fn unblock_user(
+        self: Rc<Self>,
+        params: user_group_control::UnblockUserParams,
+        mut results: user_group_control::UnblockUserResults,
+    ) -> capnp::Result<()> {
+        let user = params.get()?.get_user()?.to_str()?;
+        let r = self.user_group.unblock_user(user);
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
+
+    async fn set_user_limit(
+        self: Rc<Self>,
+        params: user_group_control::SetUserLimitParams,
+        mut results: user_group_control::SetUserLimitResults,
+    ) -> capnp::Result<()> {
+        let params = params.get()?;
+        let user = params.get_user()?.to_str()?;
+        let limits = params.get_limits()?.to_str()?;
+        let r = self.user_group.set_user_limit(user, limits);
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
+
+    async fn reset_user(
+        self: Rc<Self>,
+        params: user_group_control::ResetUserParams,
+        mut results: user_group_control::ResetUserResults,
+    ) -> capnp::Result<()> {
+        let user = params.get()?.get_user()?.to_str()?;
+        let r = self.user_group.reset_user(user);
+        set_operation_result(results.get().init_result(), r);
+        Ok(())
+    }
+
+    async fn list_user_task(
+        self: Rc<Self>,
+        params: user_group_control::ListUserTaskParams,
+        mut results: user_group_control::ListUserTaskResults,
+    ) -> capnp::Result<()> {
+        let user = params.get()?.get_user()?.to_str()?;
+        let builder = results.get().init_result();
+        let tasks = match self.user_group.list_user_tasks(user) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                set_fetch_error(builder, e);
+                return Ok(());
+            }
+        };
+        let now = Utc::now();
+        let mut builder = builder.initn_data(tasks.len() as u32);
+        for (i, task) in tasks.iter().enumerate() {
+            let mut task_builder = builder.reborrow().get(i as u32);
+            task_builder.set_id(task.id().to_string().as_str());
+            task_builder.set_server(task.server().as_str());
+            task_builder.set_client_addr(task.client_addr().to_string().as_str());
+            task_builder.set_start_time(task.start_at().timestamp());
+            let alive_time = now.signed_duration_since(task.start_at()).num_seconds();
+            task_builder.set_alive_time(alive_time.max(0) as u64);
+        }
+        Ok(())
+    }
+
+    async fn kill_user_task(
+        self: Rc<Self>,
+        params: user_group_control::KillUserTaskParams,
+        mut results: user_group_control::KillUserTaskResults,
+    ) -> capnp::Result<()> {
+        let params = params.get()?;
+        let user = params.get_user()?.to_str()?;
+        let task = params.get_task()?.to_str()?;
+        let r = if task.is_empty() {
+            self.user_group.kill_user_task(user, None)
+        } else {
+            Uuid::from_str(task)
+                .map_err(|e| anyhow!("invalid task id {task}: {e}"))
+                .and_then(|id| self.user_group.kill_user_task(user, Some(id)))
+        };
+        let mut builder = results.get().init_result();
+        match r {
+            Ok(n) => builder.set_ok(format!("{n} tasks killed").as_str()),
+            Err(e) => set_operation_result(builder, Err(e)),
+        }
+        Ok(())
+    }
 }
@@ -12,6 +12,8 @@ pub struct QuitActor {}
 
 impl QuitAction for QuitActor {
     async fn do_release_controller(&self) {
+        // the new process will load the resolver cache after the controller is released
+        crate::resolve::save_cache_snapshots().await;
         DaemonController::abort().await;
     }
 
@@ -24,6 +26,7 @@ impl QuitAction for QuitActor {
     }
 
     async fn do_graceful_shutdown(&self) {
+        crate::resolve::save_cache_snapshots().await;
         UniqueController::abort_gracefully().await
     }
 
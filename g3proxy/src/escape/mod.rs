@@ -48,12 +48,17 @@ pub(crate) use stats::{
 mod egress_path;
 pub(crate) use egress_path::{EgressPathSelection, EgressUpstream};
 
+mod multiplex_pool;
+
 mod comply_audit;
 mod direct_fixed;
 mod direct_float;
 mod divert_tcp;
 mod dummy_deny;
 mod proxy_float;
+mod proxy_h2;
+#[cfg(feature = "quic")]
+mod proxy_h3;
 mod proxy_http;
 mod proxy_https;
 mod proxy_socks5;
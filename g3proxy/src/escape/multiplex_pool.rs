@@ -0,0 +1,195 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use g3_types::net::UpstreamAddr;
+
+/// the pool key is the next proxy address and the resolve sticky key
+pub(super) type MultiplexConnectionKey = (UpstreamAddr, String);
+
+/// the protocol specific part of a multiplexed connection to the next proxy
+pub(super) trait MultiplexConnection {
+    /// the max concurrent streams allowed by the peer
+    fn peer_max_streams(&self) -> Option<usize> {
+        None
+    }
+
+    /// check if the underlying connection has been closed
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+pub(super) struct MultiplexConnectionHandle<C> {
+    inner: C,
+    max_streams: usize,
+    active_streams: AtomicUsize,
+    closed: AtomicBool,
+    pub(super) peer_addr: Option<SocketAddr>,
+    pub(super) local_addr: Option<SocketAddr>,
+}
+
+impl<C: MultiplexConnection> MultiplexConnectionHandle<C> {
+    pub(super) fn new(
+        inner: C,
+        max_streams: usize,
+        peer_addr: Option<SocketAddr>,
+        local_addr: Option<SocketAddr>,
+    ) -> Self {
+        MultiplexConnectionHandle {
+            inner,
+            max_streams,
+            active_streams: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+            peer_addr,
+            local_addr,
+        }
+    }
+
+    #[inline]
+    pub(super) fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    #[inline]
+    pub(super) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire) || self.inner.is_closed()
+    }
+
+    #[inline]
+    pub(super) fn set_closed(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+
+    #[inline]
+    pub(super) fn active_streams(&self) -> usize {
+        self.active_streams.load(Ordering::Acquire)
+    }
+
+    /// the stream limit is the smaller one of the local config and the peer settings
+    fn has_capacity(&self) -> bool {
+        let max_streams = match self.inner.peer_max_streams() {
+            Some(n) => self.max_streams.min(n),
+            None => self.max_streams,
+        };
+        self.active_streams() < max_streams
+    }
+
+    pub(super) fn lease(self: &Arc<Self>) -> MultiplexStreamLease<C> {
+        self.active_streams.fetch_add(1, Ordering::AcqRel);
+        MultiplexStreamLease(self.clone())
+    }
+}
+
+/// hold a stream slot of the multiplexed connection until dropped
+pub(super) struct MultiplexStreamLease<C>(Arc<MultiplexConnectionHandle<C>>);
+
+impl<C> Drop for MultiplexStreamLease<C> {
+    fn drop(&mut self) {
+        self.0.active_streams.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+struct MultiplexConnectionSlot<C> {
+    conns: Vec<Arc<MultiplexConnectionHandle<C>>>,
+    /// held while creating a new connection, so concurrent callers will share the same handshake
+    connect_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<C> Default for MultiplexConnectionSlot<C> {
+    fn default() -> Self {
+        MultiplexConnectionSlot {
+            conns: Vec::new(),
+            connect_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+pub(super) struct MultiplexConnectionPool<C> {
+    inner: Mutex<HashMap<MultiplexConnectionKey, MultiplexConnectionSlot<C>>>,
+}
+
+impl<C> Default for MultiplexConnectionPool<C> {
+    fn default() -> Self {
+        MultiplexConnectionPool {
+            inner: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<C: MultiplexConnection> MultiplexConnectionPool<C> {
+    /// select the least loaded connection which still has free stream slots,
+    /// or the least loaded one if no more connections are allowed
+    fn select(
+        &self,
+        key: &MultiplexConnectionKey,
+        max_connections: usize,
+    ) -> Option<Arc<MultiplexConnectionHandle<C>>> {
+        let mut map = self.inner.lock().unwrap();
+        let slot = map.get_mut(key)?;
+        slot.conns.retain(|c| !c.is_closed());
+        if slot.conns.is_empty() {
+            // keep the slot if there are callers waiting for the connect lock
+            if Arc::strong_count(&slot.connect_lock) == 1 {
+                map.remove(key);
+            }
+            return None;
+        }
+
+        if let Some(c) = slot
+            .conns
+            .iter()
+            .filter(|c| c.has_capacity())
+            .min_by_key(|c| c.active_streams())
+        {
+            return Some(c.clone());
+        }
+        if slot.conns.len() >= max_connections {
+            slot.conns
+                .iter()
+                .min_by_key(|c| c.active_streams())
+                .cloned()
+        } else {
+            None
+        }
+    }
+
+    /// select an existing connection, or create a new one by `connect`.
+    /// Only one connection will be created at a time for the same key,
+    /// and the others will retry selection after it
+    pub(super) async fn get_or_connect<F, Fut, E>(
+        &self,
+        key: &MultiplexConnectionKey,
+        max_connections: usize,
+        connect: F,
+    ) -> Result<Arc<MultiplexConnectionHandle<C>>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Arc<MultiplexConnectionHandle<C>>, E>>,
+    {
+        if let Some(conn) = self.select(key, max_connections) {
+            return Ok(conn);
+        }
+
+        let connect_lock = {
+            let mut map = self.inner.lock().unwrap();
+            map.entry(key.clone()).or_default().connect_lock.clone()
+        };
+        let _guard = connect_lock.lock().await;
+        if let Some(conn) = self.select(key, max_connections) {
+            return Ok(conn);
+        }
+
+        let conn = connect().await?;
+        let mut map = self.inner.lock().unwrap();
+        map.entry(key.clone()).or_default().conns.push(conn.clone());
+        Ok(conn)
+    }
+}
@@ -23,6 +23,9 @@ use super::direct_float::DirectFloatEscaper;
 use super::divert_tcp::DivertTcpEscaper;
 use super::dummy_deny::DummyDenyEscaper;
 use super::proxy_float::ProxyFloatEscaper;
+use super::proxy_h2::ProxyH2Escaper;
+#[cfg(feature = "quic")]
+use super::proxy_h3::ProxyH3Escaper;
 use super::proxy_http::ProxyHttpEscaper;
 use super::proxy_https::ProxyHttpsEscaper;
 use super::proxy_socks5::ProxySocks5Escaper;
@@ -247,6 +250,9 @@ async fn spawn_new_unlocked(config: AnyEscaperConfig) -> anyhow::Result<()> {
         AnyEscaperConfig::DivertTcp(c) => DivertTcpEscaper::prepare_initial(c)?,
         AnyEscaperConfig::DummyDeny(c) => DummyDenyEscaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxyFloat(c) => ProxyFloatEscaper::prepare_initial(c).await?,
+        AnyEscaperConfig::ProxyH2(c) => ProxyH2Escaper::prepare_initial(c)?,
+        #[cfg(feature = "quic")]
+        AnyEscaperConfig::ProxyH3(c) => ProxyH3Escaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxyHttp(c) => ProxyHttpEscaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxyHttps(c) => ProxyHttpsEscaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxySocks5(c) => ProxySocks5Escaper::prepare_initial(c)?,
@@ -0,0 +1,375 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use h2::RecvStream;
+use http::{HeaderValue, Method, Request, Uri, Version, header};
+use tokio::io::{AsyncRead, AsyncWrite, Join};
+
+use g3_daemon::stat::remote::{
+    ArcTcpConnectionTaskRemoteStats, TcpConnectionTaskRemoteStatsWrapper,
+};
+use g3_h2::{H2StreamReader, H2StreamWriter};
+use g3_io_ext::{AsyncStream, LimitedReader, LimitedWriter};
+use g3_openssl::{SslConnector, SslStream};
+use g3_types::net::UpstreamAddr;
+
+use super::ProxyH2Escaper;
+use super::pool::{
+    H2ConnectionDriver, H2ConnectionHandle, H2ConnectionKey, H2LeasedIo, H2StreamLease,
+};
+use super::udp_connect::masque_udp_path;
+use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+pub(super) type H2TunnelReader = H2LeasedIo<H2StreamReader>;
+pub(super) type H2TunnelWriter = H2LeasedIo<H2StreamWriter>;
+
+fn h2_to_io_error(e: h2::Error) -> io::Error {
+    if e.is_io() {
+        e.into_io().unwrap()
+    } else {
+        io::Error::other(e)
+    }
+}
+
+impl ProxyH2Escaper {
+    fn select_connection_key(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> H2ConnectionKey {
+        match task_notes.egress_path_upstream(&self.config.name) {
+            Some(ups) => {
+                tcp_notes.override_peer = Some(ups.addr.clone());
+                (ups.addr.clone(), ups.resolve_sticky_key.clone())
+            }
+            None => (
+                self.get_next_proxy(task_notes, task_conf.upstream.host())
+                    .clone(),
+                String::new(),
+            ),
+        }
+    }
+
+    async fn spawn_h2_connection<S>(
+        &self,
+        stream: S,
+        tcp_notes: &TcpConnectTaskNotes,
+    ) -> Result<Arc<H2ConnectionHandle>, TcpConnectError>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let mut builder = h2::client::Builder::new();
+        builder.enable_push(false);
+        let (send_request, connection) = builder
+            .handshake::<_, Bytes>(stream)
+            .await
+            .map_err(|e| TcpConnectError::NegotiationWriteFailed(h2_to_io_error(e)))?;
+
+        let handle = Arc::new(H2ConnectionHandle::new(
+            send_request,
+            self.config.max_streams_per_connection,
+            tcp_notes.next,
+            tcp_notes.local,
+        ));
+        let driver = H2ConnectionDriver {
+            idle_timeout: self.config.connection_idle_timeout,
+            check_interval: self.config.health_check_interval,
+            check_timeout: self.config.health_check_timeout,
+        };
+        tokio::spawn(driver.run(connection, handle.clone()));
+        Ok(handle)
+    }
+
+    async fn new_h2_connection(
+        &self,
+        key: &H2ConnectionKey,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Arc<H2ConnectionHandle>, TcpConnectError> {
+        let (peer, resolve_sticky_key) = key;
+        let stream = self
+            .tcp_new_connection(peer, resolve_sticky_key, task_conf, tcp_notes, task_notes)
+            .await?;
+
+        if let Some(tls_config) = &self.tls_config {
+            let tls_stream = self
+                .tls_handshake_to_remote(tls_config, peer, stream, task_conf, tcp_notes, task_notes)
+                .await?;
+            if tls_stream.ssl().selected_alpn_protocol() != Some(b"h2") {
+                return Err(TcpConnectError::NegotiationRejected(
+                    "h2 is not selected by remote proxy in tls alpn".to_string(),
+                ));
+            }
+            self.spawn_h2_connection(tls_stream, tcp_notes).await
+        } else {
+            self.spawn_h2_connection(stream, tcp_notes).await
+        }
+    }
+
+    async fn get_h2_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(UpstreamAddr, Arc<H2ConnectionHandle>), TcpConnectError> {
+        let key = self.select_connection_key(task_conf, tcp_notes, task_notes);
+        let conn = self
+            .pool
+            .get_or_connect(&key, self.config.max_connections_per_proxy, || {
+                self.new_h2_connection(&key, task_conf, tcp_notes, task_notes)
+            })
+            .await?;
+        // the connection may be created by another task
+        tcp_notes.next = conn.peer_addr;
+        tcp_notes.local = conn.local_addr;
+        Ok((key.0, conn))
+    }
+
+    fn set_proxy_auth_header(&self, req: &mut Request<()>, task_notes: &ServerTaskNotes) {
+        if let Some(value) = &self.config.proxy_authorization {
+            req.headers_mut()
+                .insert(header::PROXY_AUTHORIZATION, value.clone());
+        } else if self.config.pass_proxy_userid
+            && let Some(name) = task_notes.raw_user_name()
+        {
+            let value = crate::module::http_header::proxy_authorization_basic_pass_value(name);
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                req.headers_mut().insert(header::PROXY_AUTHORIZATION, value);
+            }
+        }
+    }
+
+    /// open a new CONNECT stream and return the response body stream if succeeded
+    async fn h2_send_connect(
+        &self,
+        conn: &Arc<H2ConnectionHandle>,
+        req: Request<()>,
+    ) -> Result<(RecvStream, H2StreamWriter, H2StreamLease), TcpConnectError> {
+        let lease = conn.lease();
+        let mut send_request = conn.inner().clone().ready().await.map_err(|e| {
+            // the connection is not usable any more
+            conn.set_closed();
+            TcpConnectError::NegotiationWriteFailed(h2_to_io_error(e))
+        })?;
+        let (rsp_fut, send_stream) = send_request
+            .send_request(req, false)
+            .map_err(|e| TcpConnectError::NegotiationWriteFailed(h2_to_io_error(e)))?;
+        let rsp = rsp_fut.await.map_err(|e| {
+            if e.is_go_away() || e.is_io() {
+                conn.set_closed();
+            }
+            TcpConnectError::NegotiationReadFailed(h2_to_io_error(e))
+        })?;
+
+        let status = rsp.status();
+        if !status.is_success() {
+            return Err(TcpConnectError::NegotiationRejected(format!(
+                "rejected by remote proxy with response {status}"
+            )));
+        }
+
+        Ok((rsp.into_body(), H2StreamWriter::new(send_stream), lease))
+    }
+
+    async fn h2_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H2TunnelReader, H2TunnelWriter), TcpConnectError> {
+        let (_, conn) = self
+            .get_h2_connection(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let uri = Uri::try_from(task_conf.upstream.to_string())
+            .map_err(|_| TcpConnectError::InternalServerError("invalid upstream address"))?;
+        let mut req = Request::new(());
+        *req.method_mut() = Method::CONNECT;
+        *req.uri_mut() = uri;
+        *req.version_mut() = Version::HTTP_2;
+        self.set_proxy_auth_header(&mut req, task_notes);
+
+        let (recv_stream, writer, lease) = self.h2_send_connect(&conn, req).await?;
+        let lease = Arc::new(lease);
+        Ok((
+            H2LeasedIo::new(H2StreamReader::new(recv_stream), lease.clone()),
+            H2LeasedIo::new(writer, lease),
+        ))
+    }
+
+    pub(super) async fn timed_h2_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H2TunnelReader, H2TunnelWriter), TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.h2_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    async fn h2_connect_udp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H2TunnelReader, H2TunnelWriter), TcpConnectError> {
+        let (peer, conn) = self
+            .get_h2_connection(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let scheme = if self.tls_config.is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        let uri = Uri::builder()
+            .scheme(scheme)
+            .authority(peer.to_string())
+            .path_and_query(masque_udp_path(task_conf.upstream))
+            .build()
+            .map_err(|_| TcpConnectError::InternalServerError("invalid connect-udp uri"))?;
+        let mut req = Request::new(());
+        *req.method_mut() = Method::CONNECT;
+        *req.uri_mut() = uri;
+        *req.version_mut() = Version::HTTP_2;
+        req.extensions_mut()
+            .insert(h2::ext::Protocol::from_static("connect-udp"));
+        req.headers_mut()
+            .insert("capsule-protocol", HeaderValue::from_static("?1"));
+        self.set_proxy_auth_header(&mut req, task_notes);
+
+        let (recv_stream, writer, lease) = self.h2_send_connect(&conn, req).await?;
+        let lease = Arc::new(lease);
+        Ok((
+            H2LeasedIo::new(H2StreamReader::new(recv_stream), lease.clone()),
+            H2LeasedIo::new(writer, lease),
+        ))
+    }
+
+    pub(super) async fn timed_h2_connect_udp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H2TunnelReader, H2TunnelWriter), TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.h2_connect_udp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    pub(super) async fn h2_connect_new_tcp_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let (r, w) = self
+            .timed_h2_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        // add task and user stats
+        let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+        wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let r = LimitedReader::new(r, wrapper_stats.clone());
+        let w = LimitedWriter::new(w, wrapper_stats);
+
+        Ok((Box::new(r), Box::new(w)))
+    }
+
+    pub(super) async fn h2_connect_tls_connect_to(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        tls_application: TlsApplication,
+    ) -> Result<SslStream<Join<H2TunnelReader, H2TunnelWriter>>, TcpConnectError> {
+        let (r, w) = self
+            .timed_h2_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+            .await?;
+
+        let ssl = task_conf.build_ssl()?;
+        let connector = SslConnector::new(ssl, tokio::io::join(r, w))
+            .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+
+        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => {
+                let e = anyhow::Error::new(e);
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeFailed(e))
+            }
+            Err(_) => {
+                let e = anyhow!("upstream tls handshake timed out");
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeTimeout)
+            }
+        }
+    }
+
+    pub(super) async fn h2_connect_new_tls_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let tls_stream = self
+            .h2_connect_tls_connect_to(task_conf, tcp_notes, task_notes, TlsApplication::TcpStream)
+            .await?;
+
+        let (ups_r, ups_w) = tls_stream.into_split();
+
+        // add task and user stats
+        let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+        wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        Ok((Box::new(ups_r), Box::new(ups_w)))
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use h2::client::{Connection, SendRequest};
+use h2::{Ping, PingPong};
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::Instant;
+
+use crate::escape::multiplex_pool::{
+    MultiplexConnection, MultiplexConnectionHandle, MultiplexConnectionKey,
+    MultiplexConnectionPool, MultiplexStreamLease,
+};
+
+pub(super) type H2ConnectionKey = MultiplexConnectionKey;
+pub(super) type H2ConnectionHandle = MultiplexConnectionHandle<SendRequest<Bytes>>;
+pub(super) type H2StreamLease = MultiplexStreamLease<SendRequest<Bytes>>;
+pub(super) type H2ConnectionPool = MultiplexConnectionPool<SendRequest<Bytes>>;
+
+impl MultiplexConnection for SendRequest<Bytes> {
+    fn peer_max_streams(&self) -> Option<usize> {
+        Some(self.current_max_send_streams())
+    }
+}
+
+/// io wrapper which holds the stream lease
+pub(super) struct H2LeasedIo<T> {
+    inner: T,
+    _lease: Arc<H2StreamLease>,
+}
+
+impl<T> H2LeasedIo<T> {
+    pub(super) fn new(inner: T, lease: Arc<H2StreamLease>) -> Self {
+        H2LeasedIo {
+            inner,
+            _lease: lease,
+        }
+    }
+}
+
+impl<T> AsyncRead for H2LeasedIo<T>
+where
+    T: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T> AsyncWrite for H2LeasedIo<T>
+where
+    T: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+pub(super) struct H2ConnectionDriver {
+    pub(super) idle_timeout: Duration,
+    pub(super) check_interval: Duration,
+    pub(super) check_timeout: Duration,
+}
+
+impl H2ConnectionDriver {
+    async fn ping(ping_pong: &mut Option<PingPong>, timeout: Duration) -> bool {
+        let Some(ping_pong) = ping_pong else {
+            return true;
+        };
+        matches!(
+            tokio::time::timeout(timeout, ping_pong.ping(Ping::opaque())).await,
+            Ok(Ok(_))
+        )
+    }
+
+    /// drive the h2 connection and check its health, the handle will be marked as closed
+    /// if the connection is broken, unhealthy or idle for too long
+    pub(super) async fn run<T>(
+        self,
+        mut connection: Connection<T, Bytes>,
+        handle: Arc<H2ConnectionHandle>,
+    ) where
+        T: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut ping_pong = connection.ping_pong();
+        let mut check_interval =
+            tokio::time::interval_at(Instant::now() + self.check_interval, self.check_interval);
+        let mut idle_since: Option<Instant> = None;
+
+        loop {
+            tokio::select! {
+                r = &mut connection => {
+                    if let Err(e) = r {
+                        debug!("h2 connection to next proxy closed with error: {e}");
+                    }
+                    break;
+                }
+                _ = check_interval.tick() => {
+                    if handle.active_streams() == 0 {
+                        let since = idle_since.get_or_insert_with(Instant::now);
+                        if since.elapsed() >= self.idle_timeout {
+                            handle.set_closed();
+                            // new streams may be leased before the handle is closed
+                            if handle.active_streams() == 0 {
+                                break;
+                            }
+                        }
+                    } else {
+                        idle_since = None;
+                    }
+
+                    tokio::select! {
+                        r = &mut connection => {
+                            if let Err(e) = r {
+                                debug!("h2 connection to next proxy closed with error: {e}");
+                            }
+                            break;
+                        }
+                        healthy = Self::ping(&mut ping_pong, self.check_timeout) => {
+                            if !healthy {
+                                debug!("h2 connection to next proxy failed the health check");
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        handle.set_closed();
+    }
+}
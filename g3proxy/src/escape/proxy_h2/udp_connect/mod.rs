@@ -0,0 +1,98 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use tokio::io::BufReader;
+
+use g3_types::net::{Host, UpstreamAddr};
+
+use super::ProxyH2Escaper;
+use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
+use crate::module::udp_connect::{
+    ArcUdpConnectTaskRemoteStats, UdpConnectError, UdpConnectRemoteWrapperStats, UdpConnectResult,
+    UdpConnectTaskConf, UdpConnectTaskNotes,
+};
+use crate::serve::ServerTaskNotes;
+
+mod recv;
+mod send;
+
+use recv::ProxyH2UdpConnectRemoteRecv;
+use send::ProxyH2UdpConnectRemoteSend;
+
+const MAX_UDP_PAYLOAD_SIZE: usize = 65535;
+
+/// build the path of the connect-udp URI template defined in RFC 9298
+pub(crate) fn masque_udp_path(upstream: &UpstreamAddr) -> String {
+    let host = match upstream.host() {
+        Host::Ip(ip) => ip.to_string().replace(':', "%3A"),
+        Host::Domain(domain) => domain.to_string(),
+    };
+    format!("/.well-known/masque/udp/{host}/{}/", upstream.port())
+}
+
+impl ProxyH2Escaper {
+    pub(super) async fn udp_connect_to(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        let tcp_task_conf = TcpConnectTaskConf {
+            upstream: task_conf.upstream,
+        };
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let (ups_r, ups_w) = self
+            .timed_h2_connect_udp_connect_to(&tcp_task_conf, &mut tcp_notes, task_notes)
+            .await
+            .map_err(|e| UdpConnectError::SetupSocketFailed(io::Error::other(e)))?;
+
+        udp_notes.local = tcp_notes.local;
+        udp_notes.next = tcp_notes.next;
+
+        let mut wrapper_stats = UdpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let recv = ProxyH2UdpConnectRemoteRecv::new(
+            BufReader::new(ups_r),
+            wrapper_stats.clone(),
+            MAX_UDP_PAYLOAD_SIZE,
+            self.escape_logger.clone(),
+        );
+        let send = ProxyH2UdpConnectRemoteSend::new(
+            ups_w,
+            wrapper_stats,
+            MAX_UDP_PAYLOAD_SIZE,
+            self.escape_logger.clone(),
+        );
+
+        Ok((Box::new(recv), Box::new(send)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv6Addr};
+
+    #[test]
+    fn masque_path() {
+        let ups = UpstreamAddr::from_host_str_and_port("example.net", 53).unwrap();
+        assert_eq!(
+            masque_udp_path(&ups),
+            "/.well-known/masque/udp/example.net/53/"
+        );
+
+        let ups = UpstreamAddr::from_ip_and_port(IpAddr::V6(Ipv6Addr::LOCALHOST), 443);
+        assert_eq!(
+            masque_udp_path(&ups),
+            "/.well-known/masque/udp/%3A%3A1/443/"
+        );
+    }
+}
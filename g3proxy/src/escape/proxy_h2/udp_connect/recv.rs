@@ -0,0 +1,129 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::task::{Context, Poll, ready};
+
+use slog::Logger;
+use tokio::io::AsyncBufRead;
+
+use g3_http::capsule::UdpDatagramCapsuleReader;
+use g3_io_ext::{ArcLimitedRecvStats, UdpCopyRemoteError, UdpCopyRemoteRecv};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::{UdpCopyPacket, UdpCopyPacketMeta};
+
+/// receive UDP payloads from the DATAGRAM capsules sent by the next proxy
+pub(super) struct ProxyH2UdpConnectRemoteRecv<R> {
+    reader: UdpDatagramCapsuleReader<R>,
+    stats: ArcLimitedRecvStats,
+    logger: Option<Logger>,
+}
+
+impl<R> ProxyH2UdpConnectRemoteRecv<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub(super) fn new(
+        reader: R,
+        stats: ArcLimitedRecvStats,
+        max_payload_size: usize,
+        logger: Option<Logger>,
+    ) -> Self {
+        ProxyH2UdpConnectRemoteRecv {
+            reader: UdpDatagramCapsuleReader::new(reader, max_payload_size),
+            stats,
+            logger,
+        }
+    }
+
+    /// return the payload length, or None if the next proxy has closed the stream
+    fn poll_recv_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<Option<usize>, UdpCopyRemoteError>> {
+        let r = ready!(self.reader.poll_recv_payload(cx, buf))
+            .map_err(UdpCopyRemoteError::RecvFailed)?;
+        if let Some(nr) = r {
+            self.stats.add_recv_bytes(nr);
+            self.stats.add_recv_packet();
+        }
+        Poll::Ready(Ok(r))
+    }
+}
+
+impl<R> UdpCopyRemoteRecv for ProxyH2UdpConnectRemoteRecv<R>
+where
+    R: AsyncBufRead + Unpin + Send,
+{
+    fn error_logger(&self) -> Option<&Logger> {
+        self.logger.as_ref()
+    }
+
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyRemoteError>> {
+        match ready!(self.poll_recv_payload(cx, buf))? {
+            Some(nr) => Poll::Ready(Ok((0, nr))),
+            None => Poll::Ready(Err(UdpCopyRemoteError::RemoteSessionClosed)),
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        let mut count = 0;
+        for p in packets.iter_mut() {
+            match self.poll_recv_payload(cx, p.buf_mut()) {
+                Poll::Pending => {
+                    if count == 0 {
+                        return Poll::Pending;
+                    }
+                    break;
+                }
+                Poll::Ready(Ok(Some(nr))) => {
+                    let meta = {
+                        let iov = std::io::IoSliceMut::new(p.buf_mut());
+                        UdpCopyPacketMeta::new(&iov, 0, nr)
+                    };
+                    meta.set_packet(p);
+                    count += 1;
+                }
+                Poll::Ready(Ok(None)) => {
+                    if count == 0 {
+                        return Poll::Ready(Err(UdpCopyRemoteError::RemoteSessionClosed));
+                    }
+                    break;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            }
+        }
+        Poll::Ready(Ok(count))
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use h3::client::RequestStream;
+use h3_quinn::BidiStream;
+use log::debug;
+use quinn::SendDatagramError;
+use tokio::sync::mpsc;
+
+use g3_http::capsule::{CONNECT_UDP_CONTEXT_ID_PAYLOAD, decode_varint, encode_varint, varint_len};
+
+use super::pool::{H3ConnectionHandle, H3StreamLease};
+
+const STREAM_DATAGRAM_QUEUE_SIZE: usize = 256;
+
+/// dispatch the HTTP datagrams received on the QUIC connection to the request streams,
+/// by the quarter stream id prefix defined in RFC 9297
+#[derive(Default)]
+pub(super) struct H3DatagramDispatcher {
+    streams: Mutex<HashMap<u64, mpsc::Sender<Bytes>>>,
+}
+
+impl H3DatagramDispatcher {
+    /// the datagram will be dropped if the stream is not found or its queue is full
+    pub(super) fn dispatch(&self, mut data: Bytes) {
+        let Ok((quarter_stream_id, len)) = decode_varint(&data) else {
+            return;
+        };
+        data.advance(len);
+
+        let streams = self.streams.lock().unwrap();
+        if let Some(sender) = streams.get(&quarter_stream_id) {
+            let _ = sender.try_send(data);
+        }
+    }
+
+    fn register(&self, quarter_stream_id: u64, sender: mpsc::Sender<Bytes>) {
+        let mut streams = self.streams.lock().unwrap();
+        streams.insert(quarter_stream_id, sender);
+    }
+
+    fn unregister(&self, quarter_stream_id: u64) {
+        let mut streams = self.streams.lock().unwrap();
+        streams.remove(&quarter_stream_id);
+    }
+}
+
+/// receive the UDP payloads in the HTTP datagrams of a CONNECT-UDP stream
+pub(super) struct H3DatagramReceiver {
+    receiver: mpsc::Receiver<Bytes>,
+}
+
+impl H3DatagramReceiver {
+    /// return the UDP payload, or None if the request stream has been closed
+    pub(super) fn poll_recv_payload(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        loop {
+            let Some(mut data) = ready!(self.receiver.poll_recv(cx)) else {
+                return Poll::Ready(None);
+            };
+            // datagrams with unknown context id should be dropped, see RFC 9298 Section 4
+            match decode_varint(&data) {
+                Ok((CONNECT_UDP_CONTEXT_ID_PAYLOAD, len)) => {
+                    data.advance(len);
+                    return Poll::Ready(Some(data));
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// send UDP payloads as HTTP datagrams of a CONNECT-UDP stream
+pub(super) struct H3DatagramSender {
+    quic_connection: quinn::Connection,
+    header: Bytes,
+}
+
+impl H3DatagramSender {
+    /// return false if the payload is dropped as it's too large for a QUIC datagram
+    pub(super) fn send_payload(&self, payload: &[u8]) -> io::Result<bool> {
+        let mut buf = BytesMut::with_capacity(self.header.len() + payload.len());
+        buf.put_slice(&self.header);
+        buf.put_slice(payload);
+        match self.quic_connection.send_datagram(buf.freeze()) {
+            Ok(_) => Ok(true),
+            Err(SendDatagramError::TooLarge) => Ok(false),
+            Err(e) => Err(io::Error::other(e)),
+        }
+    }
+}
+
+/// set up the datagram channels for an established CONNECT-UDP stream,
+/// the stream and its slot will be held until either side closed the stream
+pub(super) fn spawn_udp_stream(
+    conn: &Arc<H3ConnectionHandle>,
+    stream: RequestStream<BidiStream<Bytes>, Bytes>,
+    lease: H3StreamLease,
+) -> (H3DatagramReceiver, H3DatagramSender) {
+    let quarter_stream_id = stream.id().into_inner() / 4;
+
+    let mut header = BytesMut::with_capacity(
+        varint_len(quarter_stream_id) + varint_len(CONNECT_UDP_CONTEXT_ID_PAYLOAD),
+    );
+    header.resize(header.capacity(), 0);
+    let id_len = encode_varint(quarter_stream_id, &mut header);
+    encode_varint(CONNECT_UDP_CONTEXT_ID_PAYLOAD, &mut header[id_len..]);
+    let sender = H3DatagramSender {
+        quic_connection: conn.inner().quic_connection.clone(),
+        header: header.freeze(),
+    };
+
+    let (data_sender, data_receiver) = mpsc::channel(STREAM_DATAGRAM_QUEUE_SIZE);
+    let dispatcher = conn.inner().datagram_dispatcher.clone();
+    dispatcher.register(quarter_stream_id, data_sender.clone());
+
+    tokio::spawn(async move {
+        let _lease = lease;
+        let (mut send_stream, mut recv_stream) = stream.split();
+
+        tokio::select! {
+            _ = data_sender.closed() => {
+                // closed by the local side
+                let _ = send_stream.finish().await;
+            }
+            r = async {
+                // capsules sent on the stream are not used, and will be ignored
+                while recv_stream.recv_data().await?.is_some() {}
+                Ok::<(), h3::error::StreamError>(())
+            } => {
+                if let Err(e) = r {
+                    debug!("h3 connect-udp stream to next proxy closed with error: {e}");
+                }
+            }
+        }
+
+        // the receiver will get None after all senders dropped
+        dispatcher.unregister(quarter_stream_id);
+    });
+
+    (
+        H3DatagramReceiver {
+            receiver: data_receiver,
+        },
+        sender,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+
+    #[tokio::test]
+    async fn dispatch() {
+        let dispatcher = H3DatagramDispatcher::default();
+        let (sender, receiver) = mpsc::channel(4);
+        dispatcher.register(1, sender);
+        let mut receiver = H3DatagramReceiver { receiver };
+
+        // quarter stream id 2 is not registered
+        dispatcher.dispatch(Bytes::from_static(b"\x02\x00abc"));
+        // unknown context id
+        dispatcher.dispatch(Bytes::from_static(b"\x01\x02abc"));
+        dispatcher.dispatch(Bytes::from_static(b"\x01\x00def"));
+        let payload = poll_fn(|cx| receiver.poll_recv_payload(cx)).await;
+        assert_eq!(payload.unwrap().as_ref(), b"def");
+
+        dispatcher.unregister(1);
+        let payload = poll_fn(|cx| receiver.poll_recv_payload(cx)).await;
+        assert!(payload.is_none());
+    }
+}
@@ -0,0 +1,466 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::borrow::Cow;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use h3::client::RequestStream;
+use h3::error::StreamError;
+use h3_quinn::BidiStream;
+use http::{HeaderValue, Method, Request, Uri, Version, header};
+use quinn::{ClientConfig, Endpoint, TokioRuntime};
+use tokio::io::DuplexStream;
+use tokio::time::Instant;
+
+use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
+use g3_io_ext::{AsyncStream, LimitedReader, LimitedWriter};
+use g3_openssl::{SslConnector, SslStream};
+use g3_socket::BindAddr;
+use g3_types::net::{ConnectError, Host, UpstreamAddr};
+
+use super::ProxyH3Escaper;
+use super::datagram::{H3DatagramDispatcher, H3DatagramReceiver, H3DatagramSender};
+use super::pool::{
+    H3Connection, H3ConnectionDriver, H3ConnectionHandle, H3ConnectionKey, H3StreamLease,
+};
+use crate::escape::proxy_h2::udp_connect::masque_udp_path;
+use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
+use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectRemoteWrapperStats, TcpConnectResult, TcpConnectTaskConf,
+    TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+impl ProxyH3Escaper {
+    fn select_connection_key(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> H3ConnectionKey {
+        match task_notes.egress_path_upstream(&self.config.name) {
+            Some(ups) => {
+                tcp_notes.override_peer = Some(ups.addr.clone());
+                (ups.addr.clone(), ups.resolve_sticky_key.clone())
+            }
+            None => (
+                self.get_next_proxy(task_notes, task_conf.upstream.host())
+                    .clone(),
+                String::new(),
+            ),
+        }
+    }
+
+    fn prepare_quic_socket(
+        &self,
+        peer: SocketAddr,
+    ) -> Result<(std::net::UdpSocket, BindAddr), TcpConnectError> {
+        let bind_ip = match peer.ip() {
+            IpAddr::V4(_) => {
+                if self.config.no_ipv4 {
+                    return Err(TcpConnectError::ForbiddenAddressFamily);
+                }
+                self.config.bind_v4.map(IpAddr::V4)
+            }
+            IpAddr::V6(_) => {
+                if self.config.no_ipv6 {
+                    return Err(TcpConnectError::ForbiddenAddressFamily);
+                }
+                self.config.bind_v6.map(IpAddr::V6)
+            }
+        };
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        let bind = bind_ip.map(BindAddr::Ip).unwrap_or_else(|| {
+            self.config
+                .bind_interface
+                .map(BindAddr::Interface)
+                .unwrap_or_default()
+        });
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        )))]
+        let bind = bind_ip.map(BindAddr::Ip).unwrap_or_default();
+        let socket = g3_socket::udp::new_std_socket_to(
+            peer,
+            &bind,
+            self.config.udp_sock_buffer,
+            self.config.udp_misc_opts,
+        )
+        .map_err(TcpConnectError::SetupSocketFailed)?;
+        socket
+            .connect(peer)
+            .map_err(TcpConnectError::SetupSocketFailed)?;
+        Ok((socket, bind))
+    }
+
+    async fn quic_connect_to(
+        &self,
+        key: &H3ConnectionKey,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<quinn::Connection, TcpConnectError> {
+        let (peer_proxy, resolve_sticky_key) = key;
+        let peer_ip = match peer_proxy.host() {
+            Host::Ip(ip) => *ip,
+            Host::Domain(domain) => {
+                self.resolve_peer_ip(domain.clone(), resolve_sticky_key)
+                    .await?
+            }
+        };
+        let peer = SocketAddr::new(peer_ip, peer_proxy.port());
+
+        let (socket, bind) = self.prepare_quic_socket(peer)?;
+        tcp_notes.next = Some(peer);
+        tcp_notes.bind = bind;
+        tcp_notes.local = socket.local_addr().ok();
+
+        let endpoint = Endpoint::new(Default::default(), None, socket, Arc::new(TokioRuntime))
+            .map_err(TcpConnectError::SetupSocketFailed)?;
+        let mut client_config = ClientConfig::new(self.tls_config.driver.clone());
+        client_config.transport_config(self.quic_transport.clone());
+        let tls_name = match self
+            .config
+            .tls_name
+            .as_ref()
+            .unwrap_or_else(|| peer_proxy.host())
+        {
+            Host::Ip(ip) => Cow::Owned(ip.to_string()),
+            Host::Domain(domain) => Cow::Borrowed(domain.as_str()),
+        };
+        let connecting = endpoint
+            .connect_with(client_config, peer, &tls_name)
+            .map_err(|e| TcpConnectError::SetupSocketFailed(io::Error::other(e)))?;
+
+        let instant_now = Instant::now();
+        self.stats.add_connection_attempted();
+        tcp_notes.tries = 1;
+        let e = match tokio::time::timeout(self.tls_config.handshake_timeout, connecting).await {
+            Ok(Ok(connection)) => {
+                tcp_notes.duration = instant_now.elapsed();
+                self.stats.add_connection_established();
+                return Ok(connection);
+            }
+            Ok(Err(e)) => TcpConnectError::ConnectFailed(ConnectError::from(io::Error::from(e))),
+            Err(_) => TcpConnectError::TimeoutByRule,
+        };
+        tcp_notes.duration = instant_now.elapsed();
+        if let Some(logger) = &self.escape_logger {
+            EscapeLogForTcpConnect {
+                upstream: task_conf.upstream,
+                tcp_notes,
+                task_id: &task_notes.id,
+            }
+            .log(logger, &e);
+        }
+        Err(e)
+    }
+
+    async fn new_h3_connection(
+        &self,
+        key: &H3ConnectionKey,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Arc<H3ConnectionHandle>, TcpConnectError> {
+        let quic_connection = self
+            .quic_connect_to(key, task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let (driver, send_request) = h3::client::builder()
+            .enable_datagram(true)
+            .build::<_, _, Bytes>(h3_quinn::Connection::new(quic_connection.clone()))
+            .await
+            .map_err(|e| TcpConnectError::NegotiationWriteFailed(io::Error::other(e)))?;
+
+        let peer_addr = quic_connection.remote_address();
+        let handle = Arc::new(H3ConnectionHandle::new(
+            H3Connection {
+                send_request,
+                quic_connection,
+                datagram_dispatcher: Arc::new(H3DatagramDispatcher::default()),
+            },
+            self.config.max_streams_per_connection,
+            Some(peer_addr),
+            tcp_notes.local,
+        ));
+        let driver_task = H3ConnectionDriver {
+            idle_timeout: self.config.connection_idle_timeout,
+        };
+        tokio::spawn(driver_task.run(driver, handle.clone()));
+        Ok(handle)
+    }
+
+    async fn get_h3_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(UpstreamAddr, Arc<H3ConnectionHandle>), TcpConnectError> {
+        let key = self.select_connection_key(task_conf, tcp_notes, task_notes);
+        let conn = self
+            .pool
+            .get_or_connect(&key, self.config.max_connections_per_proxy, || {
+                self.new_h3_connection(&key, task_conf, tcp_notes, task_notes)
+            })
+            .await?;
+        // the connection may be created by another task
+        tcp_notes.next = conn.peer_addr;
+        tcp_notes.local = conn.local_addr;
+        Ok((key.0, conn))
+    }
+
+    fn set_proxy_auth_header(&self, req: &mut Request<()>, task_notes: &ServerTaskNotes) {
+        if let Some(value) = &self.config.proxy_authorization {
+            req.headers_mut()
+                .insert(header::PROXY_AUTHORIZATION, value.clone());
+        } else if self.config.pass_proxy_userid
+            && let Some(name) = task_notes.raw_user_name()
+        {
+            let value = crate::module::http_header::proxy_authorization_basic_pass_value(name);
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                req.headers_mut().insert(header::PROXY_AUTHORIZATION, value);
+            }
+        }
+    }
+
+    /// open a new CONNECT stream and return it if succeeded
+    async fn h3_send_connect(
+        &self,
+        conn: &Arc<H3ConnectionHandle>,
+        req: Request<()>,
+    ) -> Result<(RequestStream<BidiStream<Bytes>, Bytes>, H3StreamLease), TcpConnectError> {
+        let mark_closed = |e: &StreamError| {
+            if matches!(
+                e,
+                StreamError::ConnectionError { .. } | StreamError::RemoteClosing { .. }
+            ) {
+                // the connection is not usable any more
+                conn.set_closed();
+            }
+        };
+
+        let lease = conn.lease();
+        let mut send_request = conn.inner().send_request.clone();
+        let mut stream: RequestStream<BidiStream<Bytes>, Bytes> =
+            send_request.send_request(req).await.map_err(|e| {
+                mark_closed(&e);
+                TcpConnectError::NegotiationWriteFailed(io::Error::other(e))
+            })?;
+        let rsp = stream.recv_response().await.map_err(|e| {
+            mark_closed(&e);
+            TcpConnectError::NegotiationReadFailed(io::Error::other(e))
+        })?;
+
+        let status = rsp.status();
+        if !status.is_success() {
+            return Err(TcpConnectError::NegotiationRejected(format!(
+                "rejected by remote proxy with response {status}"
+            )));
+        }
+
+        Ok((stream, lease))
+    }
+
+    async fn h3_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<DuplexStream, TcpConnectError> {
+        let (_, conn) = self
+            .get_h3_connection(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let uri = Uri::try_from(task_conf.upstream.to_string())
+            .map_err(|_| TcpConnectError::InternalServerError("invalid upstream address"))?;
+        let mut req = Request::new(());
+        *req.method_mut() = Method::CONNECT;
+        *req.uri_mut() = uri;
+        *req.version_mut() = Version::HTTP_3;
+        self.set_proxy_auth_header(&mut req, task_notes);
+
+        let (stream, lease) = self.h3_send_connect(&conn, req).await?;
+        Ok(super::stream::spawn_stream_relay(stream, lease))
+    }
+
+    pub(super) async fn timed_h3_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<DuplexStream, TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.h3_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    async fn h3_connect_udp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H3DatagramReceiver, H3DatagramSender), TcpConnectError> {
+        let (peer, conn) = self
+            .get_h3_connection(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let uri = Uri::builder()
+            .scheme("https")
+            .authority(peer.to_string())
+            .path_and_query(masque_udp_path(task_conf.upstream))
+            .build()
+            .map_err(|_| TcpConnectError::InternalServerError("invalid connect-udp uri"))?;
+        let mut req = Request::new(());
+        *req.method_mut() = Method::CONNECT;
+        *req.uri_mut() = uri;
+        *req.version_mut() = Version::HTTP_3;
+        req.extensions_mut().insert(h3::ext::Protocol::CONNECT_UDP);
+        req.headers_mut()
+            .insert("capsule-protocol", HeaderValue::from_static("?1"));
+        self.set_proxy_auth_header(&mut req, task_notes);
+
+        let (stream, lease) = self.h3_send_connect(&conn, req).await?;
+        // the UDP payloads should be sent in HTTP datagrams, see RFC 9298 Section 5.
+        // The H3_DATAGRAM setting of the peer is not exposed by h3, so only check the
+        // QUIC DATAGRAM support here
+        if conn.inner().quic_connection.max_datagram_size().is_none() {
+            return Err(TcpConnectError::NegotiationRejected(
+                "h3 datagram is not supported by remote proxy".to_string(),
+            ));
+        }
+        Ok(super::datagram::spawn_udp_stream(&conn, stream, lease))
+    }
+
+    pub(super) async fn timed_h3_connect_udp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(H3DatagramReceiver, H3DatagramSender), TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.h3_connect_udp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    pub(super) async fn h3_connect_new_tcp_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let stream = self
+            .timed_h3_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+        let (r, w) = tokio::io::split(stream);
+
+        // add task, escaper and user stats
+        let mut wrapper_stats = TcpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let r = LimitedReader::new(r, wrapper_stats.clone());
+        let w = LimitedWriter::new(w, wrapper_stats);
+
+        Ok((Box::new(r), Box::new(w)))
+    }
+
+    pub(super) async fn h3_connect_tls_connect_to(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        tls_application: TlsApplication,
+    ) -> Result<SslStream<DuplexStream>, TcpConnectError> {
+        let stream = self
+            .timed_h3_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+            .await?;
+
+        let ssl = task_conf.build_ssl()?;
+        let connector = SslConnector::new(ssl, stream)
+            .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+
+        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => {
+                let e = anyhow::Error::new(e);
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeFailed(e))
+            }
+            Err(_) => {
+                let e = anyhow!("upstream tls handshake timed out");
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeTimeout)
+            }
+        }
+    }
+
+    pub(super) async fn h3_connect_new_tls_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let tls_stream = self
+            .h3_connect_tls_connect_to(task_conf, tcp_notes, task_notes, TlsApplication::TcpStream)
+            .await?;
+
+        let (ups_r, ups_w) = tls_stream.into_split();
+
+        // add task, escaper and user stats
+        let mut wrapper_stats = TcpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        Ok((Box::new(ups_r), Box::new(ups_w)))
+    }
+}
@@ -0,0 +1,86 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use g3_io_ext::{AsyncStream, LimitedBufReader, LimitedWriter};
+
+use super::ProxyH3Escaper;
+use crate::escape::direct_fixed::http_forward::{DirectHttpForwardReader, DirectHttpForwardWriter};
+use crate::log::escape::tls_handshake::TlsApplication;
+use crate::module::http_forward::{
+    ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpForwardRemoteWrapperStats,
+    HttpForwardTaskRemoteWrapperStats,
+};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+impl ProxyH3Escaper {
+    fn wrap_http_forward_connection<R, W>(
+        &self,
+        ups_r: R,
+        ups_w: W,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> BoxHttpForwardConnection
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+        W: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        // add task, escaper and user stats
+        let mut w_wrapper_stats =
+            HttpForwardRemoteWrapperStats::new(self.stats.clone(), &task_stats);
+        let mut r_wrapper_stats = HttpForwardTaskRemoteWrapperStats::new(task_stats);
+        let user_stats = self.fetch_user_upstream_io_stats(task_notes);
+        w_wrapper_stats.push_user_io_stats_by_ref(&user_stats);
+        r_wrapper_stats.push_user_io_stats(user_stats);
+
+        let ups_r =
+            LimitedBufReader::new_unlimited(ups_r, self.stats.clone(), Arc::new(r_wrapper_stats));
+        let ups_w = LimitedWriter::new(ups_w, Arc::new(w_wrapper_stats));
+
+        let writer = DirectHttpForwardWriter::new(ups_w, Some(Arc::clone(&self.stats)));
+        let reader = DirectHttpForwardReader::new(ups_r);
+        (Box::new(writer), Box::new(reader))
+    }
+
+    pub(super) async fn http_forward_new_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        let stream = self
+            .timed_h3_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+        let (ups_r, ups_w) = tokio::io::split(stream);
+        Ok(self.wrap_http_forward_connection(ups_r, ups_w, task_notes, task_stats))
+    }
+
+    pub(super) async fn https_forward_new_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        let tls_stream = self
+            .h3_connect_tls_connect_to(
+                task_conf,
+                tcp_notes,
+                task_notes,
+                TlsApplication::HttpForward,
+            )
+            .await?;
+
+        let (ups_r, ups_w) = tls_stream.into_split();
+        Ok(self.wrap_http_forward_connection(ups_r, ups_w, task_notes, task_stats))
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use h3::client::{Connection, SendRequest};
+use h3_quinn::{OpenStreams, VarInt};
+use log::debug;
+use tokio::time::Instant;
+
+use super::datagram::H3DatagramDispatcher;
+use crate::escape::multiplex_pool::{
+    MultiplexConnection, MultiplexConnectionHandle, MultiplexConnectionKey,
+    MultiplexConnectionPool, MultiplexStreamLease,
+};
+
+const H3_NO_ERROR: u32 = 0x100;
+
+pub(super) struct H3Connection {
+    pub(super) send_request: SendRequest<OpenStreams, Bytes>,
+    pub(super) quic_connection: quinn::Connection,
+    pub(super) datagram_dispatcher: Arc<H3DatagramDispatcher>,
+}
+
+impl MultiplexConnection for H3Connection {
+    fn is_closed(&self) -> bool {
+        self.quic_connection.close_reason().is_some()
+    }
+}
+
+pub(super) type H3ConnectionKey = MultiplexConnectionKey;
+pub(super) type H3ConnectionHandle = MultiplexConnectionHandle<H3Connection>;
+pub(super) type H3StreamLease = MultiplexStreamLease<H3Connection>;
+pub(super) type H3ConnectionPool = MultiplexConnectionPool<H3Connection>;
+
+pub(super) struct H3ConnectionDriver {
+    pub(super) idle_timeout: Duration,
+}
+
+impl H3ConnectionDriver {
+    /// drive the h3 connection and close it if idle for too long,
+    /// the liveness of the connection is checked by the QUIC keep-alive.
+    /// The received HTTP datagrams will also be dispatched to their request streams
+    pub(super) async fn run(
+        self,
+        mut connection: Connection<h3_quinn::Connection, Bytes>,
+        handle: Arc<H3ConnectionHandle>,
+    ) {
+        let mut check_interval =
+            tokio::time::interval_at(Instant::now() + self.idle_timeout, self.idle_timeout);
+        let mut idle_since: Option<Instant> = None;
+        let quic_connection = handle.inner().quic_connection.clone();
+
+        loop {
+            tokio::select! {
+                e = connection.wait_idle() => {
+                    if !e.is_h3_no_error() {
+                        debug!("h3 connection to next proxy closed with error: {e}");
+                    }
+                    break;
+                }
+                r = quic_connection.read_datagram() => {
+                    match r {
+                        Ok(data) => handle.inner().datagram_dispatcher.dispatch(data),
+                        Err(e) => {
+                            debug!("h3 connection to next proxy closed with error: {e}");
+                            break;
+                        }
+                    }
+                }
+                _ = check_interval.tick() => {
+                    if handle.active_streams() > 0 {
+                        idle_since = None;
+                        continue;
+                    }
+                    let since = idle_since.get_or_insert_with(Instant::now);
+                    if since.elapsed() >= self.idle_timeout {
+                        handle.set_closed();
+                        // new streams may be leased before the handle is closed
+                        if handle.active_streams() == 0 {
+                            quic_connection.close(VarInt::from_u32(H3_NO_ERROR), b"idle");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        handle.set_closed();
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use arc_swap::ArcSwapOption;
+
+use g3_daemon::stat::remote::TcpConnectionTaskRemoteStats;
+use g3_io_ext::LimitedReaderStats;
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats, UdpIoSnapshot};
+
+use crate::escape::{EscaperInterfaceStats, EscaperInternalStats, EscaperStats, EscaperUdpStats};
+use crate::module::http_forward::HttpForwardTaskRemoteStats;
+use crate::module::udp_connect::UdpConnectTaskRemoteStats;
+
+/// the io stats are counted on the tunneled payload, not on the quic connection
+pub(crate) struct ProxyH3EscaperStats {
+    name: NodeName,
+    id: StatId,
+    extra_metrics_tags: Arc<ArcSwapOption<MetricTagMap>>,
+    pub(crate) interface: EscaperInterfaceStats,
+    connection_attempted: AtomicU64,
+    connection_established: AtomicU64,
+    pub(crate) tcp_io: TcpIoStats,
+    pub(crate) udp: EscaperUdpStats,
+}
+
+impl ProxyH3EscaperStats {
+    pub(crate) fn new(name: &NodeName) -> Self {
+        ProxyH3EscaperStats {
+            name: name.clone(),
+            id: StatId::new_unique(),
+            extra_metrics_tags: Arc::new(ArcSwapOption::new(None)),
+            interface: EscaperInterfaceStats::default(),
+            connection_attempted: AtomicU64::new(0),
+            connection_established: AtomicU64::new(0),
+            tcp_io: TcpIoStats::default(),
+            udp: EscaperUdpStats::default(),
+        }
+    }
+
+    pub(crate) fn set_extra_tags(&self, tags: Option<Arc<MetricTagMap>>) {
+        self.extra_metrics_tags.store(tags);
+    }
+
+    pub(crate) fn add_connection_attempted(&self) {
+        self.connection_attempted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_connection_established(&self) {
+        self.connection_established.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl EscaperInternalStats for ProxyH3EscaperStats {
+    #[inline]
+    fn add_http_forward_request_attempted(&self) {
+        self.interface.add_http_forward_request_attempted();
+    }
+
+    #[inline]
+    fn add_https_forward_request_attempted(&self) {
+        self.interface.add_https_forward_request_attempted();
+    }
+}
+
+impl EscaperStats for ProxyH3EscaperStats {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    fn load_extra_tags(&self) -> Option<Arc<MetricTagMap>> {
+        self.extra_metrics_tags.load_full()
+    }
+
+    fn share_extra_tags(&self) -> &Arc<ArcSwapOption<MetricTagMap>> {
+        &self.extra_metrics_tags
+    }
+
+    fn get_task_total(&self) -> u64 {
+        self.interface.get_task_total()
+    }
+
+    fn connection_attempted(&self) -> u64 {
+        self.connection_attempted.load(Ordering::Relaxed)
+    }
+
+    fn connection_established(&self) -> u64 {
+        self.connection_established.load(Ordering::Relaxed)
+    }
+
+    fn tcp_io_snapshot(&self) -> Option<TcpIoSnapshot> {
+        Some(self.tcp_io.snapshot())
+    }
+
+    fn udp_io_snapshot(&self) -> Option<UdpIoSnapshot> {
+        Some(self.udp.io.snapshot())
+    }
+}
+
+impl LimitedReaderStats for ProxyH3EscaperStats {
+    fn add_read_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.tcp_io.add_in_bytes(size);
+    }
+}
+
+impl TcpConnectionTaskRemoteStats for ProxyH3EscaperStats {
+    fn add_read_bytes(&self, size: u64) {
+        self.tcp_io.add_in_bytes(size);
+    }
+
+    fn add_write_bytes(&self, size: u64) {
+        self.tcp_io.add_out_bytes(size);
+    }
+}
+
+impl HttpForwardTaskRemoteStats for ProxyH3EscaperStats {
+    fn add_read_bytes(&self, size: u64) {
+        self.tcp_io.add_in_bytes(size);
+    }
+
+    fn add_write_bytes(&self, size: u64) {
+        self.tcp_io.add_out_bytes(size);
+    }
+}
+
+impl UdpConnectTaskRemoteStats for ProxyH3EscaperStats {
+    fn add_recv_bytes(&self, size: u64) {
+        self.udp.io.add_in_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.udp.io.add_in_packets(n);
+    }
+
+    fn add_send_bytes(&self, size: u64) {
+        self.udp.io.add_out_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.udp.io.add_out_packets(n);
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+
+use bytes::{Buf, Bytes, BytesMut};
+use h3::client::RequestStream;
+use h3_quinn::BidiStream;
+use log::debug;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+use super::pool::H3StreamLease;
+
+const RELAY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// relay the data between the h3 request stream and a local duplex stream,
+/// the stream slot will be held until both directions are finished
+pub(super) fn spawn_stream_relay(
+    stream: RequestStream<BidiStream<Bytes>, Bytes>,
+    lease: H3StreamLease,
+) -> DuplexStream {
+    let (local, remote) = tokio::io::duplex(RELAY_BUFFER_SIZE);
+
+    tokio::spawn(async move {
+        let _lease = lease;
+        let (mut send_stream, mut recv_stream) = stream.split();
+        let (mut local_r, mut local_w) = tokio::io::split(remote);
+
+        let upload = async {
+            let mut buf = BytesMut::with_capacity(RELAY_BUFFER_SIZE);
+            loop {
+                buf.reserve(RELAY_BUFFER_SIZE);
+                let nr = local_r.read_buf(&mut buf).await?;
+                if nr == 0 {
+                    return send_stream.finish().await.map_err(io::Error::other);
+                }
+                send_stream
+                    .send_data(buf.split().freeze())
+                    .await
+                    .map_err(io::Error::other)?;
+            }
+        };
+        let download = async {
+            while let Some(mut data) = recv_stream.recv_data().await.map_err(io::Error::other)? {
+                while data.has_remaining() {
+                    let chunk = data.chunk();
+                    let len = chunk.len();
+                    local_w.write_all(chunk).await?;
+                    data.advance(len);
+                }
+            }
+            local_w.shutdown().await
+        };
+
+        if let Err(e) = tokio::try_join!(upload, download) {
+            debug!("h3 stream relay to next proxy finished with error: {e}");
+        }
+    });
+
+    local
+}
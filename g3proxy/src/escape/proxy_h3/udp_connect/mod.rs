@@ -0,0 +1,57 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use super::ProxyH3Escaper;
+use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
+use crate::module::udp_connect::{
+    ArcUdpConnectTaskRemoteStats, UdpConnectError, UdpConnectRemoteWrapperStats, UdpConnectResult,
+    UdpConnectTaskConf, UdpConnectTaskNotes,
+};
+use crate::serve::ServerTaskNotes;
+
+mod recv;
+mod send;
+
+use recv::ProxyH3UdpConnectRemoteRecv;
+use send::ProxyH3UdpConnectRemoteSend;
+
+impl ProxyH3Escaper {
+    pub(super) async fn udp_connect_to(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        let tcp_task_conf = TcpConnectTaskConf {
+            upstream: task_conf.upstream,
+        };
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let (receiver, sender) = self
+            .timed_h3_connect_udp_connect_to(&tcp_task_conf, &mut tcp_notes, task_notes)
+            .await
+            .map_err(|e| UdpConnectError::SetupSocketFailed(io::Error::other(e)))?;
+
+        udp_notes.local = tcp_notes.local;
+        udp_notes.next = tcp_notes.next;
+
+        let mut wrapper_stats = UdpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let recv = ProxyH3UdpConnectRemoteRecv::new(
+            receiver,
+            wrapper_stats.clone(),
+            self.escape_logger.clone(),
+        );
+        let send =
+            ProxyH3UdpConnectRemoteSend::new(sender, wrapper_stats, self.escape_logger.clone());
+
+        Ok((Box::new(recv), Box::new(send)))
+    }
+}
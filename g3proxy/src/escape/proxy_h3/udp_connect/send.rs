@@ -0,0 +1,91 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::task::{Context, Poll};
+
+use slog::Logger;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::UdpCopyPacket;
+use g3_io_ext::{ArcLimitedSendStats, UdpCopyRemoteError, UdpCopyRemoteSend};
+
+use super::super::datagram::H3DatagramSender;
+
+/// send UDP payloads to the next proxy as HTTP datagrams,
+/// payloads too large for a QUIC datagram will be dropped
+pub(super) struct ProxyH3UdpConnectRemoteSend {
+    sender: H3DatagramSender,
+    stats: ArcLimitedSendStats,
+    logger: Option<Logger>,
+}
+
+impl ProxyH3UdpConnectRemoteSend {
+    pub(super) fn new(
+        sender: H3DatagramSender,
+        stats: ArcLimitedSendStats,
+        logger: Option<Logger>,
+    ) -> Self {
+        ProxyH3UdpConnectRemoteSend {
+            sender,
+            stats,
+            logger,
+        }
+    }
+
+    fn send_payload(&self, payload: &[u8]) -> Result<(), UdpCopyRemoteError> {
+        let sent = self
+            .sender
+            .send_payload(payload)
+            .map_err(UdpCopyRemoteError::SendFailed)?;
+        if sent {
+            self.stats.add_send_bytes(payload.len());
+            self.stats.add_send_packet();
+        }
+        Ok(())
+    }
+}
+
+impl UdpCopyRemoteSend for ProxyH3UdpConnectRemoteSend {
+    fn error_logger(&self) -> Option<&Logger> {
+        self.logger.as_ref()
+    }
+
+    fn poll_send_packet(
+        &mut self,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        self.send_payload(buf)?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_send_packets(
+        &mut self,
+        _cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        for p in packets {
+            self.send_payload(p.payload())?;
+        }
+        Poll::Ready(Ok(packets.len()))
+    }
+}
@@ -556,6 +556,10 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                         return Err(ServerTaskError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -855,6 +859,11 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                         return Err(ServerTaskError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = ups_to_clt.write_flush().await;
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = ups_to_clt.write_flush().await;
                         return Err(ServerTaskError::CanceledAsServerQuit)
@@ -539,6 +539,11 @@ where
                         return Err(ServerTaskError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = ups_to_clt.write_flush().await;
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = ups_to_clt.write_flush().await;
                         return Err(ServerTaskError::CanceledAsServerQuit)
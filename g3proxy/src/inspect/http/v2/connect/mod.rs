@@ -430,6 +430,10 @@ impl<'a, SC: ServerConfig> ExchangeHead<'a, SC> {
                             return Err(H2StreamTransferError::CanceledAsUserBlocked);
                         }
 
+                        if self.ctx.task_killed() {
+                            return Err(H2StreamTransferError::CanceledAsTaskKilled);
+                        }
+
                         if self.ctx.server_force_quit() {
                             return Err(H2StreamTransferError::CanceledAsServerQuit)
                         }
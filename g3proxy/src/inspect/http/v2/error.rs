@@ -35,6 +35,8 @@ pub(crate) enum H2InterceptionError {
     ClientConnectionClosed(h2::Error),
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as task killed")]
+    CanceledAsTaskKilled,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
     #[error("idle after {0:?} x {1}")]
@@ -91,6 +93,8 @@ pub(crate) enum H2StreamTransferError {
     ResponseBodyTransferFailed(H2StreamBodyTransferError),
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as task killed")]
+    CanceledAsTaskKilled,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
     #[error("read from http client idle")]
@@ -163,6 +167,7 @@ impl From<H2ReqmodAdaptationError> for H2StreamTransferError {
             }
             H2ReqmodAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => H2StreamTransferError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => H2StreamTransferError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => H2StreamTransferError::CanceledAsServerQuit,
             },
             H2ReqmodAdaptationError::HttpUpstreamRecvResponseFailed(e) => {
@@ -213,6 +218,7 @@ impl From<H2RespmodAdaptationError> for H2StreamTransferError {
             }
             H2RespmodAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => H2StreamTransferError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => H2StreamTransferError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => H2StreamTransferError::CanceledAsServerQuit,
             },
             e => H2StreamTransferError::InternalAdapterError(anyhow!("respmod: {e}")),
@@ -490,6 +490,10 @@ where
                         return Err(H2StreamTransferError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        return Err(H2StreamTransferError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         return Err(H2StreamTransferError::CanceledAsServerQuit)
                     }
@@ -663,6 +667,10 @@ where
                             return Err(H2StreamTransferError::CanceledAsUserBlocked);
                         }
 
+                        if self.ctx.task_killed() {
+                            return Err(H2StreamTransferError::CanceledAsTaskKilled);
+                        }
+
                         if self.ctx.server_force_quit() {
                             return Err(H2StreamTransferError::CanceledAsServerQuit)
                         }
@@ -152,6 +152,10 @@ impl<SC: ServerConfig> StreamTransitTask for H2InterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn task_killed(&self) -> bool {
+        self.ctx.task_killed()
+    }
 }
 
 impl<SC> H2InterceptObject<SC>
@@ -482,6 +486,13 @@ where
                         return Err(H2InterceptionError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = ping_quit_sender.send(());
+                        server_abrupt_shutdown(h2c_connection, Reason::ENHANCE_YOUR_CALM).await;
+
+                        return Err(H2InterceptionError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = ping_quit_sender.send(());
                         server_graceful_shutdown(h2c_connection).await;
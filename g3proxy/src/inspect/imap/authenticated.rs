@@ -134,6 +134,11 @@ where
                         return Ok(CloseReason::Local(ServerTaskError::CanceledAsUserBlocked));
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = ByeResponse::reply_blocked(clt_w).await;
+                        return Ok(CloseReason::Local(ServerTaskError::CanceledAsTaskKilled));
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = ByeResponse::reply_server_quit(clt_w).await;
                         return Ok(CloseReason::Local(ServerTaskError::CanceledAsServerQuit));
@@ -456,6 +461,12 @@ where
                         return Ok(Some(CloseReason::Local(ServerTaskError::CanceledAsUserBlocked)));
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = ByeResponse::reply_blocked(clt_w).await;
+                        let _ = ups_w.write_all_flush(DONE_MSG).await;
+                        return Ok(Some(CloseReason::Local(ServerTaskError::CanceledAsTaskKilled)));
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = ByeResponse::reply_server_quit(clt_w).await;
                         let _ = ups_w.write_all_flush(DONE_MSG).await;
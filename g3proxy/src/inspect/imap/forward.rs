@@ -162,6 +162,11 @@ where
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                        if self.ctx.task_killed() {
+                            let _ = clt_to_ups.write_flush().await;
+                            return Err(ServerTaskError::CanceledAsTaskKilled);
+                        }
+
                         if self.ctx.server_force_quit() {
                             let _ = clt_to_ups.write_flush().await;
                             return Err(ServerTaskError::CanceledAsServerQuit)
@@ -317,6 +322,11 @@ where
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                        if self.ctx.task_killed() {
+                            let _ = ups_to_clt.write_flush().await;
+                            return Err(ServerTaskError::CanceledAsTaskKilled);
+                        }
+
                         if self.ctx.server_force_quit() {
                             let _ = ups_to_clt.write_flush().await;
                             return Err(ServerTaskError::CanceledAsServerQuit)
@@ -172,6 +172,10 @@ impl<SC: ServerConfig> StreamTransitTask for ImapInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn task_killed(&self) -> bool {
+        self.ctx.task_killed()
+    }
 }
 
 impl<SC> ImapInterceptObject<SC>
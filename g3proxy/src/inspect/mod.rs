@@ -24,7 +24,7 @@ use crate::audit::AuditHandle;
 use crate::auth::{User, UserForbiddenStats, UserSite};
 use crate::config::server::ServerConfig;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
-use crate::serve::{ArcServerStats, ServerIdleChecker, ServerTaskNotes};
+use crate::serve::{ArcServerStats, LiveTask, ServerIdleChecker, ServerTaskNotes};
 
 mod error;
 pub(crate) use error::InterceptionError;
@@ -68,6 +68,7 @@ pub(crate) struct StreamInspectTaskNotes {
     pub(crate) server_addr: SocketAddr,
     worker_id: Option<usize>,
     user_ctx: Option<StreamInspectUserContext>,
+    live_task: Option<Arc<LiveTask>>,
 }
 
 impl StreamInspectTaskNotes {
@@ -85,6 +86,13 @@ impl StreamInspectTaskNotes {
     pub(crate) fn task_id(&self) -> &Uuid {
         &self.task_id
     }
+
+    pub(crate) fn is_killed(&self) -> bool {
+        self.live_task
+            .as_ref()
+            .map(|t| t.is_killed())
+            .unwrap_or(false)
+    }
 }
 
 impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
@@ -100,6 +108,7 @@ impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
                 user_site: ctx.user_site().cloned(),
                 forbidden_stats: ctx.forbidden_stats().clone(),
             }),
+            live_task: task_notes.live_task().cloned(),
         }
     }
 }
@@ -219,6 +228,7 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         ServerIdleChecker::new(
             self.idle_wheel.clone(),
             self.user_cloned(),
+            self.task_notes.live_task.clone(),
             self.max_idle_count,
             self.server_quit_policy.clone(),
         )
@@ -337,6 +347,11 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.imap_interception()
     }
 
+    #[inline]
+    fn task_killed(&self) -> bool {
+        self.task_notes.is_killed()
+    }
+
     fn belongs_to_blocked_user(&self) -> bool {
         self.task_notes
             .user_ctx
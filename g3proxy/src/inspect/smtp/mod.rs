@@ -162,6 +162,10 @@ impl<SC: ServerConfig> StreamTransitTask for SmtpInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn task_killed(&self) -> bool {
+        self.ctx.task_killed()
+    }
 }
 
 impl<SC> SmtpInterceptObject<SC>
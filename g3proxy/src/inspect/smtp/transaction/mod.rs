@@ -456,6 +456,11 @@ impl<'a, SC: ServerConfig> Transaction<'a, SC> {
                         return Err(ServerTaskError::CanceledAsUserBlocked);
                     }
 
+                    if self.ctx.task_killed() {
+                        let _ = clt_to_ups.write_flush().await;
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_force_quit() {
                         let _ = clt_to_ups.write_flush().await;
                         return Err(ServerTaskError::CanceledAsServerQuit)
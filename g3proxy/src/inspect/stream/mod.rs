@@ -33,6 +33,7 @@ pub(crate) trait StreamTransitTask {
     fn log_flush_interval(&self) -> Option<Duration>;
     fn quit_policy(&self) -> &ServerQuitPolicy;
     fn user(&self) -> Option<&User>;
+    fn task_killed(&self) -> bool;
 
     async fn transit_transparent<CR, CW, UR, UW>(
         &self,
@@ -136,6 +137,10 @@ pub(crate) trait StreamTransitTask {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.quit_policy().force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -194,6 +199,10 @@ pub(crate) trait StreamTransitTask {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.quit_policy().force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -252,6 +261,10 @@ pub(crate) trait StreamTransitTask {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.quit_policy().force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -362,6 +375,10 @@ impl<SC: ServerConfig> StreamTransitTask for UnknownStreamTransitTask<'_, SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn task_killed(&self) -> bool {
+        self.ctx.task_killed()
+    }
 }
 
 impl<SC> StreamInspectContext<SC>
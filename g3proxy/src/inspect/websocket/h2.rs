@@ -114,6 +114,10 @@ impl<SC: ServerConfig> StreamTransitTask for H2WebsocketInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn task_killed(&self) -> bool {
+        self.ctx.task_killed()
+    }
 }
 
 impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
@@ -7,8 +7,8 @@ use std::net::SocketAddr;
 
 use slog::Logger;
 
-use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtUpstreamAddr, LtUserName, LtUuid};
-use g3_types::net::UpstreamAddr;
+use g3_slog_types::{LtDateTime, LtDuration, LtHost, LtIpAddr, LtUpstreamAddr, LtUserName, LtUuid};
+use g3_types::net::{Host, UpstreamAddr};
 
 use super::TaskEvent;
 use crate::module::udp_connect::UdpConnectTaskNotes;
@@ -17,11 +17,12 @@ use crate::serve::{ServerTaskError, ServerTaskNotes};
 pub(crate) struct TaskLogForUdpConnect<'a> {
     pub(crate) logger: &'a Logger,
     pub(crate) task_notes: &'a ServerTaskNotes,
-    pub(crate) tcp_server_addr: SocketAddr,
-    pub(crate) tcp_client_addr: SocketAddr,
+    pub(crate) tcp_server_addr: Option<SocketAddr>,
+    pub(crate) tcp_client_addr: Option<SocketAddr>,
     pub(crate) udp_listen_addr: Option<SocketAddr>,
     pub(crate) udp_client_addr: Option<SocketAddr>,
     pub(crate) upstream: Option<&'a UpstreamAddr>,
+    pub(crate) sni: Option<&'a Host>,
     pub(crate) udp_notes: &'a UdpConnectTaskNotes,
     pub(crate) client_rd_bytes: u64,
     pub(crate) client_rd_packets: u64,
@@ -73,6 +74,7 @@ impl TaskLogForUdpConnect<'_> {
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
+            "sni" => self.sni.map(LtHost),
             "escaper" => self.udp_notes.escaper.as_str(),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
@@ -104,6 +106,7 @@ impl TaskLogForUdpConnect<'_> {
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
+            "sni" => self.sni.map(LtHost),
             "escaper" => self.udp_notes.escaper.as_str(),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
@@ -142,6 +145,7 @@ impl TaskLogForUdpConnect<'_> {
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
+            "sni" => self.sni.map(LtHost),
             "escaper" => self.udp_notes.escaper.as_str(),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
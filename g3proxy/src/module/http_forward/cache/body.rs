@@ -0,0 +1,150 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use super::{HttpCache, HttpCacheEntry};
+
+/// the response to store, with the body collected while it's sent to the client
+pub(crate) struct HttpCacheStore {
+    cache: Arc<HttpCache>,
+    entry: HttpCacheEntry,
+    body: Vec<u8>,
+    body_size: usize,
+}
+
+impl HttpCacheStore {
+    pub(super) fn new(cache: Arc<HttpCache>, entry: HttpCacheEntry, body_size: usize) -> Self {
+        HttpCacheStore {
+            cache,
+            entry,
+            body: Vec::with_capacity(body_size),
+            body_size,
+        }
+    }
+
+    /// return false if the body exceeds the expected size
+    fn push(&mut self, data: &[u8]) -> bool {
+        if self.body.len() + data.len() > self.body_size {
+            return false;
+        }
+        self.body.extend_from_slice(data);
+        true
+    }
+
+    pub(crate) fn finish(mut self) {
+        if self.body.len() != self.body_size {
+            return;
+        }
+        self.entry.set_body(Bytes::from(self.body));
+        self.cache.stats.add_stored();
+        self.cache.put(self.entry);
+    }
+}
+
+/// the response body reader, which will give up caching if the body exceeds the expected size
+pub(crate) struct HttpCacheBodyReader<R> {
+    inner: R,
+    store: Option<HttpCacheStore>,
+}
+
+impl<R> HttpCacheBodyReader<R> {
+    pub(crate) fn new(inner: R, store: HttpCacheStore) -> Self {
+        HttpCacheBodyReader {
+            inner,
+            store: Some(store),
+        }
+    }
+
+    /// store the response, should be called after all the body has been read
+    pub(crate) fn finish(self) {
+        if let Some(store) = self.store {
+            store.finish();
+        }
+    }
+}
+
+impl<R> AsyncRead for HttpCacheBodyReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let offset = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        if let Some(store) = &mut self.store
+            && !store.push(&buf.filled()[offset..])
+        {
+            self.store = None;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use tokio::io::{AsyncReadExt, BufReader};
+
+    use g3_http::client::HttpForwardRemoteResponse;
+    use g3_types::metrics::NodeName;
+    use g3_types::net::HttpHeaderMap;
+
+    use super::super::HttpCacheResponseTime;
+    use crate::config::server::HttpCacheConfig;
+
+    async fn read_and_store(cache: &Arc<HttpCache>, key: &str, body: &[u8]) {
+        let header = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nCache-Control: max-age=60\r\n\r\n";
+        let mut header_reader = BufReader::new(&header[..]);
+        let rsp = HttpForwardRemoteResponse::parse(&mut header_reader, &Method::GET, true, 4096)
+            .await
+            .unwrap();
+        let time = HttpCacheResponseTime {
+            request_time: 0,
+            response_time: 0,
+        };
+        let entry = HttpCacheEntry::new(
+            key.to_string(),
+            &HttpHeaderMap::default(),
+            &rsp,
+            time,
+            Bytes::new(),
+            60,
+        );
+        let store = HttpCacheStore::new(cache.clone(), entry, 5);
+
+        let mut reader = HttpCacheBodyReader::new(body, store);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, body);
+        reader.finish();
+    }
+
+    #[tokio::test]
+    async fn collect_body() {
+        let cache =
+            HttpCache::build(&HttpCacheConfig::default(), &NodeName::new_static("test")).unwrap();
+
+        read_and_store(&cache, "http://a.com/", b"hello").await;
+        let entry = cache.get("http://a.com/").await.unwrap();
+        assert_eq!(entry.body().as_ref(), b"hello");
+
+        read_and_store(&cache, "http://b.com/", b"hello world").await;
+        assert!(cache.get("http://b.com/").await.is_none());
+
+        read_and_store(&cache, "http://c.com/", b"hell").await;
+        assert!(cache.get("http://c.com/").await.is_none());
+    }
+}
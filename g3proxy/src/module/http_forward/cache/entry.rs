@@ -0,0 +1,362 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io::Write;
+use std::str::FromStr;
+
+use bytes::Bytes;
+use http::{HeaderName, HeaderValue, Version, header};
+use serde_json::{Map, Value};
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+use super::policy::{self, CacheControl};
+
+/// the time values of a response, in unix seconds
+#[derive(Clone, Copy)]
+pub(crate) struct HttpCacheResponseTime {
+    pub(crate) request_time: u64,
+    pub(crate) response_time: u64,
+}
+
+pub(crate) struct HttpCacheEntry {
+    key: String,
+    code: u16,
+    reason: String,
+    headers: HttpHeaderMap,
+    /// the lowercase field names in the Vary header
+    vary: Vec<String>,
+    /// the secondary key built from the request header fields nominated by `vary`
+    secondary_key: String,
+    response_time: u64,
+    initial_age: u64,
+    lifetime: u64,
+    must_revalidate: bool,
+    no_cache: bool,
+    body: Bytes,
+}
+
+impl HttpCacheEntry {
+    pub(super) fn new(
+        key: String,
+        req_headers: &HttpHeaderMap,
+        rsp: &HttpForwardRemoteResponse,
+        time: HttpCacheResponseTime,
+        body: Bytes,
+        max_ttl: u64,
+    ) -> Self {
+        let vary = policy::vary_fields(&rsp.end_to_end_headers).unwrap_or_default();
+        let secondary_key = policy::vary_secondary_key(&vary, req_headers);
+        let mut entry = HttpCacheEntry {
+            key,
+            code: rsp.code,
+            reason: rsp.reason.clone(),
+            headers: rsp.end_to_end_headers.clone(),
+            vary,
+            secondary_key,
+            response_time: time.response_time,
+            initial_age: 0,
+            lifetime: 0,
+            must_revalidate: false,
+            no_cache: false,
+            body,
+        };
+        entry.update_freshness(time, max_ttl);
+        entry
+    }
+
+    /// see rfc9111 Section 4.2.3
+    fn update_freshness(&mut self, time: HttpCacheResponseTime, max_ttl: u64) {
+        let cc = CacheControl::parse(&self.headers);
+        let date = self
+            .headers
+            .get(header::DATE)
+            .and_then(|v| policy::parse_http_date(v.to_str()))
+            .unwrap_or(time.response_time);
+        let age_value = self
+            .headers
+            .get(header::AGE)
+            .and_then(|v| u64::from_str(v.to_str().trim()).ok())
+            .unwrap_or_default();
+
+        let apparent_age = time.response_time.saturating_sub(date);
+        let response_delay = time.response_time.saturating_sub(time.request_time);
+        let corrected_age_value = age_value.saturating_add(response_delay);
+
+        self.response_time = time.response_time;
+        self.initial_age = apparent_age.max(corrected_age_value);
+        self.lifetime = policy::freshness_lifetime(self.code, &cc, &self.headers, date, max_ttl);
+        self.must_revalidate = cc.must_revalidate;
+        self.no_cache = cc.no_cache;
+    }
+
+    /// update the stored response with a 304 response, see rfc9111 Section 4.3.4
+    pub(super) fn refresh(
+        &self,
+        rsp: &HttpForwardRemoteResponse,
+        time: HttpCacheResponseTime,
+        max_ttl: u64,
+    ) -> Self {
+        let mut headers = self.headers.clone();
+        rsp.end_to_end_headers.for_each(|name, _| {
+            if name != header::CONTENT_LENGTH {
+                headers.remove(name);
+            }
+        });
+        rsp.end_to_end_headers.for_each(|name, value| {
+            if name != header::CONTENT_LENGTH {
+                headers.append(name.clone(), value.clone());
+            }
+        });
+
+        let mut entry = HttpCacheEntry {
+            key: self.key.clone(),
+            code: self.code,
+            reason: self.reason.clone(),
+            headers,
+            vary: self.vary.clone(),
+            secondary_key: self.secondary_key.clone(),
+            response_time: time.response_time,
+            initial_age: 0,
+            lifetime: 0,
+            must_revalidate: false,
+            no_cache: false,
+            body: self.body.clone(),
+        };
+        entry.update_freshness(time, max_ttl);
+        entry
+    }
+
+    #[inline]
+    pub(super) fn key(&self) -> &str {
+        &self.key
+    }
+
+    #[inline]
+    pub(crate) fn code(&self) -> u16 {
+        self.code
+    }
+
+    #[inline]
+    pub(crate) fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    #[inline]
+    pub(crate) fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    #[inline]
+    pub(super) fn set_body(&mut self, body: Bytes) {
+        self.body = body;
+    }
+
+    pub(super) fn size(&self) -> usize {
+        let mut size = self.key.len() + self.reason.len() + self.body.len();
+        size += self.vary.iter().map(|name| name.len()).sum::<usize>();
+        size += self.secondary_key.len();
+        self.headers.for_each(|name, value| {
+            size += name.as_str().len() + value.as_bytes().len() + 4;
+        });
+        size
+    }
+
+    /// check if the stored response is selected for the request, see rfc9111 Section 4.1
+    pub(super) fn is_variant_of(&self, req_headers: &HttpHeaderMap) -> bool {
+        self.vary.is_empty()
+            || policy::vary_secondary_key(&self.vary, req_headers) == self.secondary_key
+    }
+
+    pub(super) fn current_age(&self, now: u64) -> u64 {
+        self.initial_age
+            .saturating_add(now.saturating_sub(self.response_time))
+    }
+
+    /// check if the stored response can be used without validation, see rfc9111 Section 4.2
+    pub(super) fn is_fresh_for(&self, req_cc: &CacheControl, now: u64) -> bool {
+        if self.no_cache || req_cc.no_cache {
+            return false;
+        }
+        let age = self.current_age(now);
+        if let Some(max_age) = req_cc.max_age
+            && age > max_age
+        {
+            return false;
+        }
+        let min_fresh = req_cc.min_fresh.unwrap_or_default();
+        if age.saturating_add(min_fresh) < self.lifetime {
+            return true;
+        }
+        if self.must_revalidate {
+            return false;
+        }
+        match req_cc.max_stale {
+            Some(max_stale) => age.saturating_sub(self.lifetime) <= max_stale,
+            None => false,
+        }
+    }
+
+    pub(super) fn etag(&self) -> Option<&str> {
+        self.headers.get(header::ETAG).map(|v| v.to_str())
+    }
+
+    pub(super) fn last_modified(&self) -> Option<&str> {
+        self.headers.get(header::LAST_MODIFIED).map(|v| v.to_str())
+    }
+
+    #[inline]
+    pub(super) fn has_validator(&self) -> bool {
+        self.headers.contains_key(header::ETAG) || self.headers.contains_key(header::LAST_MODIFIED)
+    }
+
+    pub(crate) fn is_not_modified_for(&self, req_headers: &HttpHeaderMap) -> bool {
+        policy::is_not_modified(req_headers, self.etag(), self.last_modified())
+    }
+
+    /// get the headers to send to client, with the Age header updated
+    pub(crate) fn response_headers(&self) -> HttpHeaderMap {
+        let mut headers = self.headers.clone();
+        let age = self.current_age(super::unix_now());
+        headers.insert(header::AGE, HttpHeaderValue::from(HeaderValue::from(age)));
+        headers
+    }
+
+    pub(super) fn encode(&self) -> Vec<u8> {
+        fn encode_pairs<'a>(iter: impl Iterator<Item = (&'a str, &'a [u8])>) -> Value {
+            Value::Array(
+                iter.map(|(name, value)| {
+                    Value::Array(vec![
+                        Value::String(name.to_string()),
+                        Value::String(String::from_utf8_lossy(value).to_string()),
+                    ])
+                })
+                .collect(),
+            )
+        }
+
+        let mut headers = Vec::new();
+        self.headers.for_each(|name, value| {
+            let name = value.original_name().unwrap_or(name.as_str());
+            headers.push((name.to_string(), value.as_bytes().to_vec()));
+        });
+
+        let mut meta = Map::new();
+        meta.insert("key".to_string(), Value::String(self.key.clone()));
+        meta.insert("code".to_string(), Value::from(self.code));
+        meta.insert("reason".to_string(), Value::String(self.reason.clone()));
+        meta.insert(
+            "headers".to_string(),
+            encode_pairs(
+                headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_slice())),
+            ),
+        );
+        if !self.vary.is_empty() {
+            meta.insert(
+                "vary".to_string(),
+                Value::Array(self.vary.iter().map(|v| Value::String(v.clone())).collect()),
+            );
+            meta.insert(
+                "secondary_key".to_string(),
+                Value::String(self.secondary_key.clone()),
+            );
+        }
+        meta.insert("response_time".to_string(), Value::from(self.response_time));
+        meta.insert("initial_age".to_string(), Value::from(self.initial_age));
+        meta.insert("lifetime".to_string(), Value::from(self.lifetime));
+        meta.insert(
+            "must_revalidate".to_string(),
+            Value::Bool(self.must_revalidate),
+        );
+        meta.insert("no_cache".to_string(), Value::Bool(self.no_cache));
+
+        let mut buf = serde_json::to_vec(&Value::Object(meta)).unwrap_or_default();
+        buf.push(b'\n');
+        buf.extend_from_slice(&self.body);
+        buf
+    }
+
+    pub(super) fn decode(buf: Bytes) -> Option<Self> {
+        fn decode_pairs(value: &Value) -> Option<Vec<(&str, &str)>> {
+            value
+                .as_array()?
+                .iter()
+                .map(|v| {
+                    let pair = v.as_array()?;
+                    Some((pair.first()?.as_str()?, pair.get(1)?.as_str()?))
+                })
+                .collect()
+        }
+
+        let p = memchr::memchr(b'\n', &buf)?;
+        let meta: Value = serde_json::from_slice(&buf[..p]).ok()?;
+        let meta = meta.as_object()?;
+
+        let mut headers = HttpHeaderMap::default();
+        for (name, value) in decode_pairs(meta.get("headers")?)? {
+            let mut value = HttpHeaderValue::from_str(value).ok()?;
+            value.set_original_name(name);
+            headers.append(HeaderName::from_str(name).ok()?, value);
+        }
+
+        let vary = match meta.get("vary") {
+            Some(v) => v
+                .as_array()?
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_string()))
+                .collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        let secondary_key = match meta.get("secondary_key") {
+            Some(v) => v.as_str()?.to_string(),
+            None => String::new(),
+        };
+
+        Some(HttpCacheEntry {
+            key: meta.get("key")?.as_str()?.to_string(),
+            code: u16::try_from(meta.get("code")?.as_u64()?).ok()?,
+            reason: meta.get("reason")?.as_str()?.to_string(),
+            headers,
+            vary,
+            secondary_key,
+            response_time: meta.get("response_time")?.as_u64()?,
+            initial_age: meta.get("initial_age")?.as_u64()?,
+            lifetime: meta.get("lifetime")?.as_u64()?,
+            must_revalidate: meta.get("must_revalidate")?.as_bool()?,
+            no_cache: meta.get("no_cache")?.as_bool()?,
+            body: buf.slice(p + 1..),
+        })
+    }
+}
+
+/// serialize the response header to send to client
+pub(crate) fn serialize_response_header(
+    version: Version,
+    code: u16,
+    reason: &str,
+    headers: &HttpHeaderMap,
+    keep_alive: bool,
+) -> Vec<u8> {
+    let mut buf = Vec::<u8>::with_capacity(1024);
+    let version = if version == Version::HTTP_10 {
+        Version::HTTP_10
+    } else {
+        Version::HTTP_11
+    };
+    let _ = write!(buf, "{version:?} {code} {reason}\r\n");
+    headers.for_each(|name, value| value.write_to_buf(name, &mut buf));
+    if keep_alive {
+        if version == Version::HTTP_10 {
+            buf.extend_from_slice(b"Connection: keep-alive\r\n");
+        }
+    } else {
+        buf.extend_from_slice(b"Connection: close\r\n");
+    }
+    buf.extend_from_slice(b"\r\n");
+    buf
+}
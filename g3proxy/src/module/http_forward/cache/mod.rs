@@ -0,0 +1,420 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use bytes::Bytes;
+use http::{Method, header};
+
+use g3_http::HttpBodyType;
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::server::{HttpAdaptedRequest, HttpProxyClientRequest};
+use g3_types::metrics::NodeName;
+use g3_types::net::HttpHeaderValue;
+
+use crate::config::server::HttpCacheConfig;
+
+mod body;
+pub(crate) use body::{HttpCacheBodyReader, HttpCacheStore};
+
+mod entry;
+pub(crate) use entry::{HttpCacheEntry, HttpCacheResponseTime, serialize_response_header};
+
+mod policy;
+use policy::CacheControl;
+
+mod stats;
+pub(crate) use stats::{HttpCacheSnapshot, HttpCacheStats};
+
+mod storage;
+use storage::{DiskStorage, MemoryStorage};
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+pub(crate) enum HttpCacheLookup {
+    /// the cache should not be used for this request
+    Bypass,
+    /// no usable stored response, the response may be stored
+    Miss(HttpCacheTask),
+    /// a fresh stored response is found
+    Hit(Arc<HttpCacheEntry>),
+    /// no usable stored response but the client requires one
+    Unavailable,
+}
+
+pub(crate) struct HttpCache {
+    config: HttpCacheConfig,
+    memory: Mutex<MemoryStorage>,
+    disk: Option<DiskStorage>,
+    stats: Arc<HttpCacheStats>,
+}
+
+impl HttpCache {
+    pub(crate) fn build(config: &HttpCacheConfig, server: &NodeName) -> anyhow::Result<Arc<Self>> {
+        let stats = Arc::new(HttpCacheStats::new(server));
+        let disk = match &config.disk {
+            Some(disk) => {
+                let storage = DiskStorage::open(disk, stats.clone())
+                    .context("failed to open disk cache storage")?;
+                Some(storage)
+            }
+            None => None,
+        };
+        crate::stat::http_cache::push_cache_stats(stats.clone());
+        Ok(Arc::new(HttpCache {
+            config: config.clone(),
+            memory: Mutex::new(MemoryStorage::new(config.memory_size)),
+            disk,
+            stats,
+        }))
+    }
+
+    async fn get(&self, key: &str) -> Option<Arc<HttpCacheEntry>> {
+        if let Some(entry) = self.memory.lock().unwrap().get(key) {
+            return Some(entry);
+        }
+        let disk = self.disk.as_ref()?;
+        let entry = Arc::new(disk.get(key).await?);
+        self.put_memory(entry.clone());
+        Some(entry)
+    }
+
+    fn put_memory(&self, entry: Arc<HttpCacheEntry>) {
+        let mut memory = self.memory.lock().unwrap();
+        let evicted = memory.put(entry);
+        self.stats.set_memory_size(memory.size());
+        drop(memory);
+        if evicted > 0 && self.disk.is_none() {
+            for _ in 0..evicted {
+                self.stats.add_evicted();
+            }
+        }
+    }
+
+    fn put(&self, entry: HttpCacheEntry) -> Arc<HttpCacheEntry> {
+        if let Some(disk) = &self.disk {
+            disk.put(&entry);
+        }
+        let entry = Arc::new(entry);
+        self.put_memory(entry.clone());
+        entry
+    }
+
+    pub(crate) fn remove(&self, key: &str) {
+        let mut memory = self.memory.lock().unwrap();
+        memory.remove(key);
+        self.stats.set_memory_size(memory.size());
+        drop(memory);
+        if let Some(disk) = &self.disk {
+            disk.remove(key);
+        }
+    }
+
+    pub(crate) async fn lookup(
+        self: &Arc<Self>,
+        key: String,
+        req: &HttpProxyClientRequest,
+    ) -> HttpCacheLookup {
+        if policy::is_method_invalidating(&req.method) {
+            self.remove(&key);
+            return HttpCacheLookup::Bypass;
+        }
+        if !policy::is_method_cacheable(&req.method) || req.body_type().is_some() {
+            return HttpCacheLookup::Bypass;
+        }
+        let cc = CacheControl::parse_request(&req.end_to_end_headers);
+        if cc.no_store {
+            return HttpCacheLookup::Bypass;
+        }
+
+        let now = unix_now();
+        // only a single variant is kept for each key, the others are treated as not stored
+        let entry = self
+            .get(&key)
+            .await
+            .filter(|entry| entry.is_variant_of(&req.end_to_end_headers));
+        if let Some(entry) = &entry
+            && entry.is_fresh_for(&cc, now)
+        {
+            self.stats.add_hit();
+            return HttpCacheLookup::Hit(entry.clone());
+        }
+
+        self.stats.add_miss();
+        if cc.only_if_cached {
+            return HttpCacheLookup::Unavailable;
+        }
+        if req.method == Method::HEAD {
+            return HttpCacheLookup::Bypass;
+        }
+        let stale = entry.filter(|entry| {
+            entry.has_validator() && !policy::is_conditional_request(&req.end_to_end_headers)
+        });
+        HttpCacheLookup::Miss(HttpCacheTask {
+            cache: self.clone(),
+            key,
+            stale,
+            request_time: now,
+        })
+    }
+}
+
+pub(crate) enum HttpCacheResponseAction {
+    /// the stored response is validated, and should be sent to client
+    Refresh,
+    /// the response should be stored, with the body size to read
+    Store(usize),
+    /// the response should be sent to client directly
+    Pass,
+}
+
+pub(crate) struct HttpCacheTask {
+    cache: Arc<HttpCache>,
+    key: String,
+    stale: Option<Arc<HttpCacheEntry>>,
+    request_time: u64,
+}
+
+impl HttpCacheTask {
+    /// get the conditional request to validate the stored response, see rfc9111 Section 4.3.1
+    pub(crate) fn revalidate_request(
+        &self,
+        req: &HttpProxyClientRequest,
+    ) -> Option<HttpProxyClientRequest> {
+        let entry = self.stale.as_ref()?;
+        let mut headers = req.end_to_end_headers.clone();
+        if let Some(etag) = entry.etag()
+            && let Ok(value) = HttpHeaderValue::from_str(etag)
+        {
+            headers.insert(header::IF_NONE_MATCH, value);
+        }
+        if let Some(last_modified) = entry.last_modified()
+            && let Ok(value) = HttpHeaderValue::from_str(last_modified)
+        {
+            headers.insert(header::IF_MODIFIED_SINCE, value);
+        }
+        let adapted = HttpAdaptedRequest {
+            method: req.method.clone(),
+            uri: req.uri.clone(),
+            version: req.version,
+            headers,
+            content_length: None,
+        };
+        Some(req.adapt_without_body(adapted))
+    }
+
+    pub(crate) fn check_response(
+        &self,
+        req: &HttpProxyClientRequest,
+        rsp: &HttpForwardRemoteResponse,
+    ) -> HttpCacheResponseAction {
+        if rsp.code == 304 && self.stale.is_some() {
+            return HttpCacheResponseAction::Refresh;
+        }
+        if rsp.code >= 500 {
+            // keep the stored response
+            return HttpCacheResponseAction::Pass;
+        }
+
+        let cc = CacheControl::parse(&rsp.end_to_end_headers);
+        let storable = policy::is_response_storable(
+            rsp.code,
+            &cc,
+            &rsp.end_to_end_headers,
+            req.end_to_end_headers.contains_key(header::AUTHORIZATION),
+        );
+        if storable {
+            match rsp.body_type(&req.method) {
+                None => return HttpCacheResponseAction::Store(0),
+                Some(HttpBodyType::ContentLength(size))
+                    if size <= self.cache.config.max_object_size as u64 =>
+                {
+                    return HttpCacheResponseAction::Store(size as usize);
+                }
+                _ => {}
+            }
+        }
+        if self.stale.is_some() {
+            self.cache.remove(&self.key);
+        }
+        HttpCacheResponseAction::Pass
+    }
+
+    fn response_time(&self) -> HttpCacheResponseTime {
+        HttpCacheResponseTime {
+            request_time: self.request_time,
+            response_time: unix_now(),
+        }
+    }
+
+    /// update the stored response with the 304 response
+    pub(crate) fn refresh(self, rsp: &HttpForwardRemoteResponse) -> Option<Arc<HttpCacheEntry>> {
+        let stale = self.stale.as_ref()?;
+        let entry = stale.refresh(
+            rsp,
+            self.response_time(),
+            self.cache.config.max_ttl.as_secs(),
+        );
+        self.cache.stats.add_revalidated();
+        Some(self.cache.put(entry))
+    }
+
+    /// prepare to store the response, the body should be collected by [HttpCacheBodyReader]
+    pub(crate) fn store(
+        self,
+        req: &HttpProxyClientRequest,
+        rsp: &HttpForwardRemoteResponse,
+        body_size: usize,
+    ) -> HttpCacheStore {
+        let time = self.response_time();
+        let max_ttl = self.cache.config.max_ttl.as_secs();
+        let entry = HttpCacheEntry::new(
+            self.key,
+            &req.end_to_end_headers,
+            rsp,
+            time,
+            Bytes::new(),
+            max_ttl,
+        );
+        HttpCacheStore::new(self.cache, entry, body_size)
+    }
+}
+
+/// get the cache key of the request.
+/// `rewrite_user` should be set if the request headers have been rewritten for the user,
+/// so the stored response won't be shared with other users
+pub(crate) fn http_cache_key(
+    is_https: bool,
+    host: &impl std::fmt::Display,
+    req: &HttpProxyClientRequest,
+    rewrite_user: Option<&str>,
+) -> String {
+    let scheme = if is_https { "https" } else { "http" };
+    let path = req.uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    match rewrite_user {
+        // the fragment is never sent in requests, so it won't conflict with other keys
+        Some(user) => format!("{scheme}://{host}{path}#user={user}"),
+        None => format!("{scheme}://{host}{path}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Version;
+    use tokio::io::{AsyncReadExt, BufReader};
+
+    async fn parse_request(data: &[u8]) -> HttpProxyClientRequest {
+        let mut reader = BufReader::new(data);
+        let mut version = Version::HTTP_11;
+        HttpProxyClientRequest::parse_basic(&mut reader, 4096, &mut version)
+            .await
+            .unwrap()
+    }
+
+    async fn store_response(
+        cache: &Arc<HttpCache>,
+        key: &str,
+        req: &HttpProxyClientRequest,
+        header: &[u8],
+        body: &[u8],
+    ) -> bool {
+        let HttpCacheLookup::Miss(cache_task) = cache.lookup(key.to_string(), req).await else {
+            panic!("cache miss is expected");
+        };
+        let mut header_reader = BufReader::new(header);
+        let rsp = HttpForwardRemoteResponse::parse(&mut header_reader, &req.method, true, 4096)
+            .await
+            .unwrap();
+        let HttpCacheResponseAction::Store(size) = cache_task.check_response(req, &rsp) else {
+            return false;
+        };
+        let store = cache_task.store(req, &rsp, size);
+        let mut reader = HttpCacheBodyReader::new(body, store);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        reader.finish();
+        true
+    }
+
+    #[tokio::test]
+    async fn vary() {
+        let cache =
+            HttpCache::build(&HttpCacheConfig::default(), &NodeName::new_static("test")).unwrap();
+        let key = "http://a.com/";
+
+        let gzip_req = parse_request(
+            b"GET http://a.com/ HTTP/1.1\r\nHost: a.com\r\nAccept-Encoding: gzip, br\r\n\r\n",
+        )
+        .await;
+        assert!(
+            store_response(
+                &cache,
+                key,
+                &gzip_req,
+                b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nCache-Control: max-age=60\r\n\
+                  Vary: Accept-Encoding\r\n\r\n",
+                b"hello",
+            )
+            .await
+        );
+
+        let req = parse_request(
+            b"GET http://a.com/ HTTP/1.1\r\nHost: a.com\r\nAccept-Encoding: gzip,br\r\n\r\n",
+        )
+        .await;
+        let HttpCacheLookup::Hit(entry) = cache.lookup(key.to_string(), &req).await else {
+            panic!("cache hit is expected");
+        };
+        assert_eq!(entry.body().as_ref(), b"hello");
+
+        let req = parse_request(
+            b"GET http://a.com/ HTTP/1.1\r\nHost: a.com\r\nAccept-Encoding: identity\r\n\r\n",
+        )
+        .await;
+        assert!(matches!(
+            cache.lookup(key.to_string(), &req).await,
+            HttpCacheLookup::Miss(_)
+        ));
+
+        let req = parse_request(b"GET http://a.com/ HTTP/1.1\r\nHost: a.com\r\n\r\n").await;
+        assert!(matches!(
+            cache.lookup(key.to_string(), &req).await,
+            HttpCacheLookup::Miss(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn vary_any() {
+        let cache =
+            HttpCache::build(&HttpCacheConfig::default(), &NodeName::new_static("test")).unwrap();
+        let key = "http://b.com/";
+
+        let req = parse_request(b"GET http://b.com/ HTTP/1.1\r\nHost: b.com\r\n\r\n").await;
+        assert!(
+            !store_response(
+                &cache,
+                key,
+                &req,
+                b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nCache-Control: max-age=60\r\n\
+                  Vary: *\r\n\r\n",
+                b"hello",
+            )
+            .await
+        );
+        assert!(matches!(
+            cache.lookup(key.to_string(), &req).await,
+            HttpCacheLookup::Miss(_)
+        ));
+    }
+}
@@ -0,0 +1,393 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+
+use chrono::DateTime;
+use http::{HeaderName, Method, header};
+
+use g3_types::net::HttpHeaderMap;
+
+/// Cache-Control directives, see rfc9111 Section 5.2
+#[derive(Default)]
+pub(super) struct CacheControl {
+    pub(super) no_store: bool,
+    pub(super) no_cache: bool,
+    pub(super) private: bool,
+    pub(super) public: bool,
+    pub(super) must_revalidate: bool,
+    pub(super) only_if_cached: bool,
+    pub(super) max_age: Option<u64>,
+    pub(super) s_maxage: Option<u64>,
+    pub(super) max_stale: Option<u64>,
+    pub(super) min_fresh: Option<u64>,
+}
+
+impl CacheControl {
+    pub(super) fn parse_request(headers: &HttpHeaderMap) -> Self {
+        let mut cc = CacheControl::parse(headers);
+        if !headers.contains_key(header::CACHE_CONTROL) {
+            // see rfc9111 Section 5.4
+            cc.no_cache = headers
+                .get_all(header::PRAGMA)
+                .iter()
+                .any(|v| v.to_str().eq_ignore_ascii_case("no-cache"));
+        }
+        cc
+    }
+
+    pub(super) fn parse(headers: &HttpHeaderMap) -> Self {
+        let mut cc = CacheControl::default();
+        for value in headers.get_all(header::CACHE_CONTROL).iter() {
+            for directive in value.to_str().split(',') {
+                let directive = directive.trim();
+                if !directive.is_empty() {
+                    cc.set_directive(directive);
+                }
+            }
+        }
+        cc
+    }
+
+    fn set_directive(&mut self, directive: &str) {
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+            None => (directive, None),
+        };
+        let seconds = value.and_then(|v| u64::from_str(v).ok());
+        match name.to_ascii_lowercase().as_str() {
+            "no-store" => self.no_store = true,
+            // the field name list form is treated as the unqualified form
+            "no-cache" => self.no_cache = true,
+            "private" => self.private = true,
+            "public" => self.public = true,
+            "must-revalidate" | "proxy-revalidate" => self.must_revalidate = true,
+            "only-if-cached" => self.only_if_cached = true,
+            // invalid values should be treated as stale, see rfc9111 Section 1.2.2
+            "max-age" => self.max_age = Some(seconds.unwrap_or_default()),
+            "s-maxage" => self.s_maxage = Some(seconds.unwrap_or_default()),
+            "max-stale" => self.max_stale = Some(seconds.unwrap_or(u64::MAX)),
+            "min-fresh" => self.min_fresh = seconds,
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn parse_http_date(value: &str) -> Option<u64> {
+    let datetime = DateTime::parse_from_rfc2822(value).ok()?;
+    u64::try_from(datetime.timestamp()).ok()
+}
+
+fn header_date(headers: &HttpHeaderMap, name: HeaderName) -> Option<u64> {
+    headers.get(name).and_then(|v| parse_http_date(v.to_str()))
+}
+
+#[inline]
+pub(super) fn is_method_cacheable(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD)
+}
+
+/// see rfc9111 Section 4.4
+#[inline]
+pub(super) fn is_method_invalidating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::DELETE | Method::PATCH
+    )
+}
+
+/// status codes that are defined as heuristically cacheable, see rfc9110 Section 15.1
+fn is_heuristically_cacheable(code: u16) -> bool {
+    matches!(
+        code,
+        200 | 203 | 204 | 300 | 301 | 308 | 404 | 405 | 410 | 414 | 501
+    )
+}
+
+/// check if the response can be stored in a shared cache, see rfc9111 Section 3
+pub(super) fn is_response_storable(
+    code: u16,
+    cc: &CacheControl,
+    headers: &HttpHeaderMap,
+    req_has_authorization: bool,
+) -> bool {
+    if cc.no_store || cc.private {
+        return false;
+    }
+    // see rfc9111 Section 3.5
+    if req_has_authorization && !(cc.public || cc.must_revalidate || cc.s_maxage.is_some()) {
+        return false;
+    }
+    // responses with per-client state should never be shared
+    if headers.contains_key(header::SET_COOKIE) {
+        return false;
+    }
+    // a Vary value of "*" always fails to match, see rfc9111 Section 4.1
+    if vary_fields(headers).is_none() {
+        return false;
+    }
+
+    if is_heuristically_cacheable(code) {
+        return true;
+    }
+    if !matches!(code, 302 | 307) {
+        return false;
+    }
+    cc.public
+        || cc.max_age.is_some()
+        || cc.s_maxage.is_some()
+        || headers.contains_key(header::EXPIRES)
+}
+
+/// get the lowercase field names in the Vary header, or None if it contains "*"
+pub(super) fn vary_fields(headers: &HttpHeaderMap) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    for value in headers.get_all(header::VARY).iter() {
+        for name in value.to_str().split(',') {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            if name == "*" {
+                return None;
+            }
+            let name = name.to_ascii_lowercase();
+            if !fields.contains(&name) {
+                fields.push(name);
+            }
+        }
+    }
+    Some(fields)
+}
+
+/// build the secondary key from the request header fields nominated by Vary,
+/// the absent fields only match absent ones, see rfc9111 Section 4.1
+pub(super) fn vary_secondary_key(fields: &[String], req_headers: &HttpHeaderMap) -> String {
+    let mut key = String::new();
+    for name in fields {
+        key.push_str(name);
+        let mut values = req_headers.get_all(name.as_str()).iter().peekable();
+        if values.peek().is_some() {
+            key.push('=');
+            let mut first = true;
+            for value in values {
+                for v in value.to_str().split(',') {
+                    if !first {
+                        key.push(',');
+                    }
+                    first = false;
+                    key.push_str(v.trim());
+                }
+            }
+        }
+        key.push('\n');
+    }
+    key
+}
+
+/// get the freshness lifetime in seconds, see rfc9111 Section 4.2.1
+pub(super) fn freshness_lifetime(
+    code: u16,
+    cc: &CacheControl,
+    headers: &HttpHeaderMap,
+    date: u64,
+    max_ttl: u64,
+) -> u64 {
+    let lifetime = if let Some(s_maxage) = cc.s_maxage {
+        s_maxage
+    } else if let Some(max_age) = cc.max_age {
+        max_age
+    } else if let Some(expires) = headers.get(header::EXPIRES) {
+        // invalid date should be treated as in the past
+        parse_http_date(expires.to_str())
+            .map(|expires| expires.saturating_sub(date))
+            .unwrap_or_default()
+    } else if is_heuristically_cacheable(code)
+        && let Some(last_modified) = header_date(headers, header::LAST_MODIFIED)
+    {
+        // see rfc9111 Section 4.2.2
+        date.saturating_sub(last_modified) / 10
+    } else {
+        0
+    };
+    lifetime.min(max_ttl)
+}
+
+#[inline]
+pub(super) fn is_conditional_request(headers: &HttpHeaderMap) -> bool {
+    headers.contains_key(header::IF_NONE_MATCH)
+        || headers.contains_key(header::IF_MODIFIED_SINCE)
+        || headers.contains_key(header::IF_MATCH)
+        || headers.contains_key(header::IF_UNMODIFIED_SINCE)
+        || headers.contains_key(header::IF_RANGE)
+}
+
+fn weak_etag_eq(a: &str, b: &str) -> bool {
+    a.trim().trim_start_matches("W/") == b.trim().trim_start_matches("W/")
+}
+
+/// evaluate the client side conditional request against a cached response,
+/// see rfc9110 Section 13.2.2
+pub(super) fn is_not_modified(
+    req_headers: &HttpHeaderMap,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> bool {
+    if req_headers.contains_key(header::IF_NONE_MATCH) {
+        let Some(etag) = etag else {
+            return false;
+        };
+        return req_headers.get_all(header::IF_NONE_MATCH).iter().any(|v| {
+            v.to_str()
+                .split(',')
+                .any(|tag| tag.trim() == "*" || weak_etag_eq(tag, etag))
+        });
+    }
+
+    if let Some(since) = header_date(req_headers, header::IF_MODIFIED_SINCE)
+        && let Some(last_modified) = last_modified.and_then(parse_http_date)
+    {
+        return last_modified <= since;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+
+    fn headers(list: &[(&'static str, &'static str)]) -> HttpHeaderMap {
+        let mut map = HttpHeaderMap::default();
+        for (name, value) in list {
+            map.append(
+                HeaderName::from_static(name),
+                HttpHeaderValue::from_static(value),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn cache_control() {
+        let map = headers(&[
+            ("cache-control", "public, max-age=60"),
+            ("cache-control", "s-maxage=\"120\", must-revalidate"),
+        ]);
+        let cc = CacheControl::parse(&map);
+        assert!(cc.public);
+        assert!(cc.must_revalidate);
+        assert_eq!(cc.max_age, Some(60));
+        assert_eq!(cc.s_maxage, Some(120));
+
+        let map = headers(&[("cache-control", "max-stale, max-age=abc")]);
+        let cc = CacheControl::parse(&map);
+        assert_eq!(cc.max_stale, Some(u64::MAX));
+        assert_eq!(cc.max_age, Some(0));
+
+        let map = headers(&[("pragma", "no-cache")]);
+        assert!(CacheControl::parse_request(&map).no_cache);
+    }
+
+    #[test]
+    fn storable() {
+        let map = headers(&[("cache-control", "max-age=60")]);
+        let cc = CacheControl::parse(&map);
+        assert!(is_response_storable(200, &cc, &map, false));
+        assert!(!is_response_storable(200, &cc, &map, true));
+        assert!(!is_response_storable(206, &cc, &map, false));
+        assert!(is_response_storable(302, &cc, &map, false));
+
+        let map = headers(&[("cache-control", "private, max-age=60")]);
+        let cc = CacheControl::parse(&map);
+        assert!(!is_response_storable(200, &cc, &map, false));
+
+        let map = headers(&[("cache-control", "max-age=60"), ("vary", "accept-encoding")]);
+        let cc = CacheControl::parse(&map);
+        assert!(is_response_storable(200, &cc, &map, false));
+
+        let map = headers(&[("cache-control", "max-age=60"), ("vary", "accept, *")]);
+        let cc = CacheControl::parse(&map);
+        assert!(!is_response_storable(200, &cc, &map, false));
+
+        let map = headers(&[]);
+        let cc = CacheControl::parse(&map);
+        assert!(!is_response_storable(302, &cc, &map, false));
+    }
+
+    #[test]
+    fn vary() {
+        let map = headers(&[
+            ("vary", "Accept-Encoding, accept"),
+            ("vary", "accept-encoding"),
+        ]);
+        let fields = vary_fields(&map).unwrap();
+        assert_eq!(fields, vec!["accept-encoding", "accept"]);
+
+        let req1 = headers(&[("accept-encoding", "gzip,  br"), ("accept", "*/*")]);
+        let req2 = headers(&[("accept-encoding", "gzip"), ("accept-encoding", "br")]);
+        let req3 = headers(&[("accept-encoding", "gzip, br"), ("accept", "*/*")]);
+        let req4 = headers(&[("accept-encoding", "gzip, br"), ("accept", "")]);
+        let key1 = vary_secondary_key(&fields, &req1);
+        assert_eq!(key1, vary_secondary_key(&fields, &req3));
+        assert_ne!(key1, vary_secondary_key(&fields, &req2));
+        // absent is different from empty
+        assert_ne!(
+            vary_secondary_key(&fields, &req2),
+            vary_secondary_key(&fields, &req4)
+        );
+
+        let map = headers(&[]);
+        let fields = vary_fields(&map).unwrap();
+        assert!(fields.is_empty());
+        assert!(vary_secondary_key(&fields, &req1).is_empty());
+
+        let map = headers(&[("vary", "*")]);
+        assert!(vary_fields(&map).is_none());
+    }
+
+    #[test]
+    fn lifetime() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+
+        let map = headers(&[("cache-control", "max-age=60, s-maxage=30")]);
+        let cc = CacheControl::parse(&map);
+        assert_eq!(freshness_lifetime(200, &cc, &map, date, 3600), 30);
+        assert_eq!(freshness_lifetime(200, &cc, &map, date, 10), 10);
+
+        let map = headers(&[("expires", "Sun, 06 Nov 1994 08:59:37 GMT")]);
+        let cc = CacheControl::parse(&map);
+        assert_eq!(freshness_lifetime(200, &cc, &map, date, 3600), 600);
+
+        let map = headers(&[("expires", "0")]);
+        let cc = CacheControl::parse(&map);
+        assert_eq!(freshness_lifetime(200, &cc, &map, date, 3600), 0);
+
+        let map = headers(&[("last-modified", "Sun, 06 Nov 1994 06:49:37 GMT")]);
+        let cc = CacheControl::parse(&map);
+        assert_eq!(freshness_lifetime(200, &cc, &map, date, 3600), 720);
+        assert_eq!(freshness_lifetime(302, &cc, &map, date, 3600), 0);
+    }
+
+    #[test]
+    fn conditional() {
+        let map = headers(&[("if-none-match", "\"a\", W/\"b\"")]);
+        assert!(is_not_modified(&map, Some("\"b\""), None));
+        assert!(!is_not_modified(&map, Some("\"c\""), None));
+        assert!(!is_not_modified(&map, None, None));
+
+        let map = headers(&[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")]);
+        assert!(is_not_modified(
+            &map,
+            None,
+            Some("Sun, 06 Nov 1994 08:00:00 GMT")
+        ));
+        assert!(!is_not_modified(
+            &map,
+            None,
+            Some("Sun, 06 Nov 1994 09:00:00 GMT")
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+#[derive(Default)]
+pub(crate) struct HttpCacheSnapshot {
+    pub(crate) hit: u64,
+    pub(crate) miss: u64,
+    pub(crate) revalidated: u64,
+    pub(crate) stored: u64,
+    pub(crate) evicted: u64,
+}
+
+pub(crate) struct HttpCacheStats {
+    server: NodeName,
+    id: StatId,
+
+    hit: AtomicU64,
+    miss: AtomicU64,
+    revalidated: AtomicU64,
+    stored: AtomicU64,
+    evicted: AtomicU64,
+    memory_size: AtomicU64,
+    disk_size: AtomicU64,
+}
+
+impl HttpCacheStats {
+    pub(super) fn new(server: &NodeName) -> Self {
+        HttpCacheStats {
+            server: server.clone(),
+            id: StatId::new_unique(),
+            hit: AtomicU64::new(0),
+            miss: AtomicU64::new(0),
+            revalidated: AtomicU64::new(0),
+            stored: AtomicU64::new(0),
+            evicted: AtomicU64::new(0),
+            memory_size: AtomicU64::new(0),
+            disk_size: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn server(&self) -> &NodeName {
+        &self.server
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    pub(super) fn add_hit(&self) {
+        self.hit.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_miss(&self) {
+        self.miss.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_revalidated(&self) {
+        self.revalidated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_stored(&self) {
+        self.stored.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_evicted(&self) {
+        self.evicted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn set_memory_size(&self, size: usize) {
+        self.memory_size.store(size as u64, Ordering::Relaxed);
+    }
+
+    pub(super) fn set_disk_size(&self, size: u64) {
+        self.disk_size.store(size, Ordering::Relaxed);
+    }
+
+    pub(crate) fn memory_size(&self) -> u64 {
+        self.memory_size.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn disk_size(&self) -> u64 {
+        self.disk_size.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn snapshot(&self) -> HttpCacheSnapshot {
+        HttpCacheSnapshot {
+            hit: self.hit.load(Ordering::Relaxed),
+            miss: self.miss.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+            stored: self.stored.load(Ordering::Relaxed),
+            evicted: self.evicted.load(Ordering::Relaxed),
+        }
+    }
+}
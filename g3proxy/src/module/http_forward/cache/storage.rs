@@ -0,0 +1,334 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use log::warn;
+use lru::LruCache;
+use tokio::sync::{mpsc, oneshot};
+
+use super::{HttpCacheEntry, HttpCacheStats};
+use crate::config::server::HttpCacheDiskConfig;
+
+const TEMP_FILE_SUFFIX: &str = ".tmp";
+const DISK_WORKER_QUEUE_COUNT: usize = 4;
+const DISK_WORKER_QUEUE_SIZE: usize = 256;
+
+pub(super) struct MemoryStorage {
+    lru: LruCache<String, Arc<HttpCacheEntry>, ahash::RandomState>,
+    size: usize,
+    max_size: usize,
+}
+
+impl MemoryStorage {
+    pub(super) fn new(max_size: usize) -> Self {
+        MemoryStorage {
+            lru: LruCache::unbounded_with_hasher(ahash::RandomState::new()),
+            size: 0,
+            max_size,
+        }
+    }
+
+    #[inline]
+    pub(super) fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(super) fn get(&mut self, key: &str) -> Option<Arc<HttpCacheEntry>> {
+        self.lru.get(key).cloned()
+    }
+
+    /// insert the entry and return the count of evicted entries
+    pub(super) fn put(&mut self, entry: Arc<HttpCacheEntry>) -> u64 {
+        let entry_size = entry.size();
+        if let Some(old) = self.lru.put(entry.key().to_string(), entry) {
+            self.size -= old.size();
+        }
+        self.size += entry_size;
+
+        let mut evicted = 0;
+        while self.size > self.max_size {
+            let Some((_, old)) = self.lru.pop_lru() else {
+                break;
+            };
+            self.size -= old.size();
+            evicted += 1;
+        }
+        evicted
+    }
+
+    pub(super) fn remove(&mut self, key: &str) {
+        if let Some(old) = self.lru.pop(key) {
+            self.size -= old.size();
+        }
+    }
+}
+
+struct DiskIndex {
+    lru: LruCache<String, u64, ahash::RandomState>,
+    size: u64,
+}
+
+enum DiskOperation {
+    Read(PathBuf, oneshot::Sender<io::Result<Vec<u8>>>),
+    Write(PathBuf, Vec<u8>),
+    Remove(PathBuf),
+}
+
+impl DiskOperation {
+    fn run(self) {
+        match self {
+            DiskOperation::Read(path, sender) => {
+                let _ = sender.send(std::fs::read(path));
+            }
+            DiskOperation::Write(path, data) => {
+                let mut temp_path = path.clone().into_os_string();
+                temp_path.push(TEMP_FILE_SUFFIX);
+                if let Err(e) = std::fs::write(&temp_path, data) {
+                    warn!(
+                        "failed to write http cache file {}: {e}",
+                        Path::new(&temp_path).display()
+                    );
+                    let _ = std::fs::remove_file(&temp_path);
+                    return;
+                }
+                if let Err(e) = std::fs::rename(&temp_path, &path) {
+                    warn!("failed to save http cache file {}: {e}", path.display());
+                    let _ = std::fs::remove_file(&temp_path);
+                }
+            }
+            DiskOperation::Remove(path) => {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// the file operations will be run in blocking threads, and the ones for the same key
+/// will always be sent to the same queue, so they will be run in order
+struct DiskWorker {
+    queues: Vec<mpsc::Sender<DiskOperation>>,
+}
+
+impl DiskWorker {
+    fn spawn(count: usize) -> Self {
+        let mut queues = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (sender, mut receiver) = mpsc::channel::<DiskOperation>(DISK_WORKER_QUEUE_SIZE);
+            tokio::spawn(async move {
+                while let Some(op) = receiver.recv().await {
+                    let _ = tokio::task::spawn_blocking(move || op.run()).await;
+                }
+            });
+            queues.push(sender);
+        }
+        DiskWorker { queues }
+    }
+
+    fn queue(&self, file: &DiskFile) -> &mpsc::Sender<DiskOperation> {
+        &self.queues[file.queue_id % self.queues.len()]
+    }
+
+    /// return false if the queue is full
+    fn try_send(&self, file: DiskFile, op: impl FnOnce(PathBuf) -> DiskOperation) -> bool {
+        match self.queue(&file).try_send(op(file.path)) {
+            Ok(_) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => false,
+            Err(mpsc::error::TrySendError::Closed(_)) => true,
+        }
+    }
+
+    async fn send(&self, file: DiskFile, op: impl FnOnce(PathBuf) -> DiskOperation) {
+        let _ = self.queue(&file).send(op(file.path)).await;
+    }
+
+    /// the remove operation should never be dropped, or the stale file will be loaded again
+    /// after restart, so it will be sent in a new task if the queue is full
+    fn send_remove(&self, file: DiskFile) {
+        let queue = self.queue(&file);
+        if let Err(mpsc::error::TrySendError::Full(op)) =
+            queue.try_send(DiskOperation::Remove(file.path))
+        {
+            let queue = queue.clone();
+            tokio::spawn(async move {
+                let _ = queue.send(op).await;
+            });
+        }
+    }
+}
+
+struct DiskFile {
+    path: PathBuf,
+    queue_id: usize,
+}
+
+pub(super) struct DiskStorage {
+    dir: PathBuf,
+    max_size: u64,
+    index: Mutex<DiskIndex>,
+    worker: DiskWorker,
+    stats: Arc<HttpCacheStats>,
+}
+
+impl DiskStorage {
+    pub(super) fn open(
+        config: &HttpCacheDiskConfig,
+        stats: Arc<HttpCacheStats>,
+    ) -> anyhow::Result<Self> {
+        let mut index = DiskIndex {
+            lru: LruCache::unbounded_with_hasher(ahash::RandomState::new()),
+            size: 0,
+        };
+
+        let dir = std::fs::read_dir(&config.path).map_err(|e| {
+            anyhow!(
+                "failed to read cache directory {}: {e}",
+                config.path.display()
+            )
+        })?;
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.to_string_lossy().ends_with(TEMP_FILE_SUFFIX) {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            match read_file_key(&path) {
+                Some(key) => {
+                    index.size += meta.len();
+                    index.lru.put(key, meta.len());
+                }
+                None => {
+                    let _ = std::fs::remove_file(&path);
+                }
+            }
+        }
+        stats.set_disk_size(index.size);
+
+        let storage = DiskStorage {
+            dir: config.path.clone(),
+            max_size: config.size,
+            index: Mutex::new(index),
+            worker: DiskWorker::spawn(DISK_WORKER_QUEUE_COUNT),
+            stats,
+        };
+        storage.evict();
+        Ok(storage)
+    }
+
+    fn file(&self, key: &str) -> DiskFile {
+        use std::fmt::Write;
+
+        let digest = openssl::sha::sha256(key.as_bytes());
+        let mut name = String::with_capacity(digest.len() * 2);
+        for b in digest {
+            let _ = write!(name, "{b:02x}");
+        }
+        DiskFile {
+            path: self.dir.join(name),
+            queue_id: digest[0] as usize,
+        }
+    }
+
+    pub(super) async fn get(&self, key: &str) -> Option<HttpCacheEntry> {
+        {
+            let mut index = self.index.lock().unwrap();
+            index.lru.get(key)?;
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        self.worker
+            .send(self.file(key), |path| DiskOperation::Read(path, sender))
+            .await;
+        match receiver.await {
+            Ok(Ok(data)) => {
+                if let Some(entry) = HttpCacheEntry::decode(Bytes::from(data))
+                    && entry.key() == key
+                {
+                    return Some(entry);
+                }
+                self.remove(key);
+                None
+            }
+            _ => {
+                self.remove_index(key);
+                None
+            }
+        }
+    }
+
+    /// the entry will be skipped if the disk worker is busy, and the old one will be removed
+    pub(super) fn put(&self, entry: &HttpCacheEntry) {
+        let data = entry.encode();
+        let size = data.len() as u64;
+        if !self.worker.try_send(self.file(entry.key()), |path| {
+            DiskOperation::Write(path, data)
+        }) {
+            self.remove(entry.key());
+            return;
+        }
+
+        {
+            let mut index = self.index.lock().unwrap();
+            if let Some(old) = index.lru.put(entry.key().to_string(), size) {
+                index.size -= old;
+            }
+            index.size += size;
+        }
+        self.evict();
+    }
+
+    fn remove_index(&self, key: &str) {
+        let mut index = self.index.lock().unwrap();
+        if let Some(old) = index.lru.pop(key) {
+            index.size -= old;
+        }
+        self.stats.set_disk_size(index.size);
+    }
+
+    pub(super) fn remove(&self, key: &str) {
+        self.remove_index(key);
+        self.worker.send_remove(self.file(key));
+    }
+
+    /// the evicted files will be removed in the disk worker
+    fn evict(&self) {
+        let mut evicted = Vec::new();
+        {
+            let mut index = self.index.lock().unwrap();
+            while index.size > self.max_size {
+                let Some((key, size)) = index.lru.pop_lru() else {
+                    break;
+                };
+                index.size -= size;
+                evicted.push(key);
+            }
+            self.stats.set_disk_size(index.size);
+        }
+        for key in evicted {
+            self.stats.add_evicted();
+            self.worker.send_remove(self.file(&key));
+        }
+    }
+}
+
+fn read_file_key(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line).ok()?;
+    let meta: serde_json::Value = serde_json::from_slice(&line).ok()?;
+    meta.get("key")?.as_str().map(|s| s.to_string())
+}
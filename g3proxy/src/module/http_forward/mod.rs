@@ -3,12 +3,18 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+mod cache;
 mod connection;
 mod context;
 mod response;
 mod stats;
 mod task;
 
+pub(crate) use cache::{
+    HttpCache, HttpCacheBodyReader, HttpCacheEntry, HttpCacheLookup, HttpCacheResponseAction,
+    HttpCacheSnapshot, HttpCacheStats, HttpCacheStore, HttpCacheTask, http_cache_key,
+    serialize_response_header,
+};
 pub(crate) use connection::{
     BoxHttpForwardConnection, BoxHttpForwardReader, BoxHttpForwardWriter, HttpConnectionEofPoller,
     HttpForwardRead, HttpForwardWrite, HttpForwardWriterForAdaptation, send_req_header_to_origin,
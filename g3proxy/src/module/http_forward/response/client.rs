@@ -5,9 +5,11 @@
 
 use std::io::{self, Write};
 use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
 
 use ascii::AsciiStr;
-use http::{StatusCode, Version};
+use bytes::Bytes;
+use http::{HeaderName, HeaderValue, Response, StatusCode, Version, header};
 use mime::Mime;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
@@ -83,6 +85,23 @@ impl HttpProxyClientResponse {
         self.custom_error_message = Some(msg);
     }
 
+    pub(crate) fn switching_to_connect_udp(version: Version) -> Self {
+        let mut rsp =
+            HttpProxyClientResponse::from_standard(StatusCode::SWITCHING_PROTOCOLS, version, false);
+        rsp.add_extra_header("Connection: Upgrade\r\n".to_string());
+        rsp.add_extra_header("Upgrade: connect-udp\r\n".to_string());
+        rsp.add_extra_header("Capsule-Protocol: ?1\r\n".to_string());
+        rsp
+    }
+
+    /// the successful response to an extended CONNECT connect-udp request in h2 / h3,
+    /// see rfc9298 Section 3.3
+    pub(crate) fn connect_udp_established(version: Version) -> Self {
+        let mut rsp = HttpProxyClientResponse::from_standard(StatusCode::OK, version, false);
+        rsp.add_extra_header("Capsule-Protocol: ?1\r\n".to_string());
+        rsp
+    }
+
     #[inline]
     pub(crate) fn too_many_requests(version: Version) -> Self {
         HttpProxyClientResponse::from_standard(StatusCode::TOO_MANY_REQUESTS, version, true)
@@ -98,7 +117,6 @@ impl HttpProxyClientResponse {
         HttpProxyClientResponse::from_standard(StatusCode::METHOD_NOT_ALLOWED, version, true)
     }
 
-    #[allow(unused)]
     #[inline]
     pub(crate) fn unimplemented(version: Version) -> Self {
         HttpProxyClientResponse::from_standard(StatusCode::NOT_IMPLEMENTED, version, true)
@@ -132,6 +150,26 @@ impl HttpProxyClientResponse {
         response
     }
 
+    pub(crate) fn proxy_auth_required(
+        version: Version,
+        realm: &AsciiStr,
+        negotiate: bool,
+        close: bool,
+    ) -> Self {
+        let mut response = HttpProxyClientResponse::from_standard(
+            StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+            version,
+            close,
+        );
+        if negotiate {
+            let auth_header = g3_http::header::proxy_authenticate_negotiate();
+            response.add_extra_header(auth_header.to_string());
+        }
+        let auth_header = g3_http::header::proxy_authenticate_basic(realm.as_str());
+        response.add_extra_header(auth_header);
+        response
+    }
+
     pub(crate) fn auto_chunked_ok(
         version: Version,
         close: bool,
@@ -398,7 +436,7 @@ impl HttpProxyClientResponse {
             ServerTaskError::ClientAppTimeout(_) => {
                 HttpProxyClientResponse::from_standard(StatusCode::REQUEST_TIMEOUT, version, true)
             }
-            ServerTaskError::CanceledAsUserBlocked => {
+            ServerTaskError::CanceledAsUserBlocked | ServerTaskError::CanceledAsTaskKilled => {
                 HttpProxyClientResponse::from_standard(StatusCode::FORBIDDEN, version, true)
             }
             ServerTaskError::CanceledAsServerQuit => HttpProxyClientResponse::from_standard(
@@ -509,13 +547,10 @@ impl HttpProxyClientResponse {
         Ok(())
     }
 
-    async fn reply_err<W>(&self, writer: &mut W) -> io::Result<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
+    fn error_body(&self) -> String {
         let code = self.status.as_str();
         let reason = self.canonical_reason();
-        let body = if let Some(msg) = &self.custom_error_message {
+        if let Some(msg) = &self.custom_error_message {
             format!(
                 "<html>\n\
                  <head><title>{code} {reason}</title></head>\n\
@@ -533,7 +568,54 @@ impl HttpProxyClientResponse {
                  </body>\n\
                  </html>\n"
             )
-        };
+        }
+    }
+
+    /// convert to the response head of a h2 / h3 stream,
+    /// the connection specific headers will be skipped
+    pub(crate) fn to_stream_response(&self) -> Response<()> {
+        let mut rsp = Response::new(());
+        *rsp.status_mut() = self.status;
+        let headers = rsp.headers_mut();
+        for line in &self.extra_headers {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let Ok(name) = HeaderName::from_str(name.trim()) else {
+                continue;
+            };
+            if matches!(
+                name,
+                header::CONNECTION | header::UPGRADE | header::TRANSFER_ENCODING
+            ) {
+                continue;
+            }
+            if let Ok(value) = HeaderValue::from_str(value.trim()) {
+                headers.append(name, value);
+            }
+        }
+        rsp
+    }
+
+    /// convert to the error response of a h2 / h3 stream, with the html body
+    pub(crate) fn to_stream_error_response(&self) -> (Response<()>, Bytes) {
+        let body = self.error_body();
+        let mut rsp = self.to_stream_response();
+        let headers = rsp.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(mime::TEXT_HTML.as_ref()),
+        );
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        (rsp, Bytes::from(body))
+    }
+
+    async fn reply_err<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let reason = self.canonical_reason();
+        let body = self.error_body();
 
         let mut header = Vec::<u8>::with_capacity(Self::RESPONSE_BUFFER_SIZE);
         write!(
@@ -567,18 +649,14 @@ impl HttpProxyClientResponse {
         version: Version,
         writer: &mut W,
         realm: &AsciiStr,
+        negotiate: bool,
         close: bool,
     ) -> io::Result<()>
     where
         W: AsyncWrite + Unpin,
     {
-        let mut response = HttpProxyClientResponse::from_standard(
-            StatusCode::PROXY_AUTHENTICATION_REQUIRED,
-            version,
-            close,
-        );
-        let auth_header = g3_http::header::proxy_authenticate_basic(realm.as_str());
-        response.add_extra_header(auth_header);
+        let response =
+            HttpProxyClientResponse::proxy_auth_required(version, realm, negotiate, close);
         response.reply_err(writer).await
     }
 
@@ -0,0 +1,52 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use g3_types::net::{Host, HttpHeaderMap, HttpHeaderRewriteRule};
+
+pub(crate) fn rewrite_request(
+    rules: &[HttpHeaderRewriteRule],
+    host: &Host,
+    path: &str,
+    user: Option<&str>,
+    headers: &mut HttpHeaderMap,
+) {
+    for rule in rules {
+        if rule.is_match(host, path, user) {
+            rule.rewrite_request(headers);
+        }
+    }
+}
+
+/// get the user name if any matched rule will rewrite the request headers only for some users
+pub(crate) fn request_rewrite_user<'a>(
+    rules: &[HttpHeaderRewriteRule],
+    host: &Host,
+    path: &str,
+    user: Option<&'a str>,
+) -> Option<&'a str> {
+    let user = user?;
+    rules
+        .iter()
+        .any(|rule| {
+            !rule.users.is_empty()
+                && !rule.request.is_empty()
+                && rule.is_match(host, path, Some(user))
+        })
+        .then_some(user)
+}
+
+pub(crate) fn rewrite_response(
+    rules: &[HttpHeaderRewriteRule],
+    host: &Host,
+    path: &str,
+    user: Option<&str>,
+    headers: &mut HttpHeaderMap,
+) {
+    for rule in rules {
+        if rule.is_match(host, path, user) {
+            rule.rewrite_response(headers);
+        }
+    }
+}
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 use slog::Logger;
 
 use g3_types::metrics::NodeName;
@@ -77,6 +78,10 @@ impl ResolverInternal for CAresResolver {
         Ok(())
     }
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        Some(Box::pin(self.inner.save_cache_snapshot()))
+    }
+
     async fn _shutdown(&mut self) {
         self.inner.shutdown().await;
     }
@@ -104,6 +104,7 @@ impl LoggedResolveJob for HickoryResolverJob {
             "server" => servers,
             "server_port" => self.config.get_server_port(),
             "encryption" => self.config.get_encryption_summary(),
+            "escaper" => self.config.escaper().map(|v| v.as_str()),
             "query_type" => self.query_type.as_str(),
             "duration" => LtDuration(self.create_ins.elapsed()),
             "rr_source" => source.as_str(),
@@ -5,6 +5,7 @@
 
 mod handle;
 mod resolver;
+mod tunnel;
 
 use handle::HickoryResolverHandle;
 pub(super) use resolver::HickoryResolver;
@@ -8,8 +8,10 @@ use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 use slog::Logger;
 
+use g3_resolver::AnyResolveDriverConfig;
 use g3_types::metrics::NodeName;
 
 use crate::config::resolver::hickory::HickoryResolverConfig;
@@ -25,9 +27,19 @@ pub(crate) struct HickoryResolver {
     logger: Option<Logger>,
 }
 
+fn build_inner_config(config: &HickoryResolverConfig) -> g3_resolver::ResolverConfig {
+    let mut inner_config = g3_resolver::ResolverConfig::from(config);
+    if let Some(escaper) = config.escaper()
+        && let AnyResolveDriverConfig::Hickory(driver) = &mut inner_config.driver
+    {
+        driver.set_tunnel(Arc::new(super::tunnel::EscaperTunnel::new(escaper.clone())));
+    }
+    inner_config
+}
+
 impl HickoryResolver {
     pub(crate) fn new_obj(config: HickoryResolverConfig) -> anyhow::Result<BoxResolverInternal> {
-        let mut builder = g3_resolver::ResolverBuilder::new((&config).into());
+        let mut builder = g3_resolver::ResolverBuilder::new(build_inner_config(&config));
         builder.thread_name(format!("res-{}", config.name()));
         let resolver = builder.build()?;
 
@@ -60,7 +72,7 @@ impl ResolverInternal for HickoryResolver {
     ) -> anyhow::Result<()> {
         if let AnyResolverConfig::Hickory(config) = config {
             self.inner
-                .update_config(config.as_ref().into())
+                .update_config(build_inner_config(&config))
                 .context("failed to update inner hickory resolver config")?;
             self.config = Arc::new(*config);
             Ok(())
@@ -77,6 +89,10 @@ impl ResolverInternal for HickoryResolver {
         Ok(())
     }
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        Some(Box::pin(self.inner.save_cache_snapshot()))
+    }
+
     async fn _shutdown(&mut self) {
         self.inner.shutdown().await;
     }
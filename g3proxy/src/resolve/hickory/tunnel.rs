@@ -0,0 +1,73 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_daemon::stat::remote::TcpConnectionTaskRemoteStats;
+use g3_resolver::driver::hickory::{BoxHickoryTunnelStream, HickoryTunnelConnect};
+use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
+
+use crate::audit::AuditContext;
+use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
+use crate::serve::ServerTaskNotes;
+
+struct NullStats {}
+
+impl TcpConnectionTaskRemoteStats for NullStats {
+    fn add_read_bytes(&self, _size: u64) {}
+
+    fn add_write_bytes(&self, _size: u64) {}
+}
+
+/// Connect to the dns servers through the escaper, which is looked up for each new connection.
+/// The existence of the escaper is checked when loading the resolver config.
+pub(super) struct EscaperTunnel {
+    escaper: NodeName,
+}
+
+impl EscaperTunnel {
+    pub(super) fn new(escaper: NodeName) -> Self {
+        EscaperTunnel { escaper }
+    }
+}
+
+#[async_trait]
+impl HickoryTunnelConnect for EscaperTunnel {
+    async fn connect(&self, server: SocketAddr) -> io::Result<BoxHickoryTunnelStream> {
+        let escaper = crate::escape::get_escaper(&self.escaper).map_err(io::Error::other)?;
+
+        let upstream = UpstreamAddr::from(server);
+        let task_conf = TcpConnectTaskConf {
+            upstream: &upstream,
+        };
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let local_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+        let task_notes = ServerTaskNotes::new(
+            ClientConnectionInfo::new(local_addr, local_addr),
+            None,
+            Duration::ZERO,
+        );
+        let mut audit_ctx = AuditContext::new(None);
+
+        let (ups_r, ups_w) = escaper
+            .tcp_setup_connection(
+                &task_conf,
+                &mut tcp_notes,
+                &task_notes,
+                Arc::new(NullStats {}),
+                &mut audit_ctx,
+            )
+            .await
+            .map_err(io::Error::other)?;
+        Ok(Box::new(tokio::io::join(ups_r, ups_w)))
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 
 use g3_types::metrics::NodeName;
 
@@ -32,10 +33,11 @@ mod hickory;
 
 mod deny_all;
 mod fail_over;
+mod static_hosts;
 
 mod ops;
 pub use ops::spawn_all;
-pub(crate) use ops::{foreach_resolver, reload};
+pub(crate) use ops::{foreach_resolver, reload, save_cache_snapshots};
 
 pub(crate) trait Resolver {
     fn get_handle(&self) -> ArcIntegratedResolverHandle;
@@ -58,6 +60,10 @@ trait ResolverInternal: Resolver {
         handle: ArcIntegratedResolverHandle,
     ) -> anyhow::Result<()>;
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        None
+    }
+
     async fn _shutdown(&mut self);
 }
 
@@ -21,6 +21,7 @@ use super::hickory::HickoryResolver;
 
 use super::deny_all::DenyAllResolver;
 use super::fail_over::FailOverResolver;
+use super::static_hosts::StaticHostsResolver;
 
 use super::{Resolver, registry};
 
@@ -109,6 +110,22 @@ where
     registry::foreach(|name, resolver| f(name, resolver.as_ref()));
 }
 
+/// Save the cache snapshot of all resolvers, and wait for the results
+pub(crate) async fn save_cache_snapshots() {
+    let mut tasks = Vec::new();
+    registry::foreach(|name, resolver| {
+        if let Some(fut) = resolver._save_cache_snapshot() {
+            tasks.push((name.clone(), fut));
+        }
+    });
+
+    for (name, fut) in tasks {
+        if let Err(e) = fut.await {
+            warn!("failed to save cache snapshot for resolver {name}: {e:?}");
+        }
+    }
+}
+
 #[async_recursion]
 async fn update_dependency_to_resolver_unlocked(target: &NodeName, status: &str) {
     let mut names = Vec::<NodeName>::new();
@@ -178,6 +195,7 @@ async fn spawn_new_unlocked(config: AnyResolverConfig) -> anyhow::Result<()> {
         AnyResolverConfig::Hickory(c) => HickoryResolver::new_obj(*c)?,
         AnyResolverConfig::DenyAll(c) => DenyAllResolver::new_obj(c)?,
         AnyResolverConfig::FailOver(c) => FailOverResolver::new_obj(c)?,
+        AnyResolverConfig::StaticHosts(c) => StaticHostsResolver::new_obj(c)?,
     };
     let old_resolver = registry::add(name.clone(), resolver);
     update_dependency_to_resolver_unlocked(&name, STATUS).await;
@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+mod handle;
+mod resolver;
+
+use handle::StaticHostsResolverHandle;
+pub(super) use resolver::StaticHostsResolver;
@@ -0,0 +1,148 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use slog::Logger;
+
+use g3_resolver::ResolverRuntimeConfig;
+use g3_resolver::driver::static_hosts::StaticHostsDriverConfig;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::static_hosts::StaticHostsResolverConfig;
+use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
+use crate::resolve::{
+    ArcIntegratedResolverHandle, BoxResolverInternal, Resolver, ResolverInternal, ResolverStats,
+};
+
+pub(crate) struct StaticHostsResolver {
+    config: Arc<StaticHostsResolverConfig>,
+    driver_config: StaticHostsDriverConfig,
+    inner: g3_resolver::Resolver,
+    stats: Arc<ResolverStats>,
+    logger: Option<Logger>,
+}
+
+impl StaticHostsResolver {
+    pub(crate) fn new_obj(
+        config: StaticHostsResolverConfig,
+    ) -> anyhow::Result<BoxResolverInternal> {
+        let mut driver_config = config.driver.clone();
+        if let Some(next) = &config.next {
+            let next_handle =
+                crate::resolve::get_handle(next).context("failed to get next resolver handle")?;
+            driver_config.set_next_handle(next_handle.clone_inner());
+        }
+
+        let inner_config = g3_resolver::ResolverConfig {
+            name: config.name().to_string(),
+            runtime: config.runtime.clone(),
+            driver: g3_resolver::AnyResolveDriverConfig::StaticHosts(driver_config.clone()),
+        };
+        let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
+        builder.thread_name(format!("res-{}", config.name()));
+        let resolver = builder.build()?;
+
+        let logger = crate::log::resolve::get_logger(config.r#type(), config.name());
+        let stats = ResolverStats::new(config.name(), resolver.get_stats());
+
+        Ok(Box::new(StaticHostsResolver {
+            config: Arc::new(config),
+            driver_config,
+            inner: resolver,
+            stats: Arc::new(stats),
+            logger,
+        }))
+    }
+
+    fn update_driver_config(
+        &mut self,
+        runtime: ResolverRuntimeConfig,
+        driver_config: StaticHostsDriverConfig,
+    ) -> anyhow::Result<()> {
+        let inner_config = g3_resolver::ResolverConfig {
+            name: self.config.name().to_string(),
+            runtime,
+            driver: g3_resolver::AnyResolveDriverConfig::StaticHosts(driver_config.clone()),
+        };
+
+        self.inner
+            .update_config(inner_config)
+            .context("failed to update inner static resolver config")?;
+        self.driver_config = driver_config;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ResolverInternal for StaticHostsResolver {
+    fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        self.config.dependent_resolver()
+    }
+
+    fn _clone_config(&self) -> AnyResolverConfig {
+        AnyResolverConfig::StaticHosts(self.config.as_ref().clone())
+    }
+
+    fn _update_config(
+        &mut self,
+        config: AnyResolverConfig,
+        dep_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    ) -> anyhow::Result<()> {
+        if let AnyResolverConfig::StaticHosts(config) = config {
+            let mut driver_config = config.driver.clone();
+            if let Some(next) = &config.next {
+                let next_handle = dep_table.get(next).unwrap();
+                driver_config.set_next_handle(next_handle.clone_inner());
+            }
+
+            self.update_driver_config(config.runtime.clone(), driver_config)?;
+            self.config = Arc::new(config);
+            Ok(())
+        } else {
+            Err(anyhow!("invalid config type for StaticHostsResolver"))
+        }
+    }
+
+    fn _update_dependent_handle(
+        &mut self,
+        target: &NodeName,
+        handle: ArcIntegratedResolverHandle,
+    ) -> anyhow::Result<()> {
+        if self.config.next.as_ref() != Some(target) {
+            return Err(anyhow!(
+                "resolver {} doesn't depend on resolver {}",
+                self.config.name(),
+                target
+            ));
+        }
+
+        let mut driver_config = self.driver_config.clone();
+        driver_config.set_next_handle(handle.clone_inner());
+        self.update_driver_config(self.config.runtime.clone(), driver_config)
+    }
+
+    async fn _shutdown(&mut self) {
+        self.inner.shutdown().await;
+    }
+}
+
+impl Resolver for StaticHostsResolver {
+    fn get_handle(&self) -> ArcIntegratedResolverHandle {
+        let inner_context = self.inner.get_handle();
+        Arc::new(super::StaticHostsResolverHandle::new(
+            &self.config,
+            inner_context,
+            self.logger.clone(),
+        ))
+    }
+
+    fn get_stats(&self) -> Arc<ResolverStats> {
+        Arc::clone(&self.stats)
+    }
+}
@@ -114,6 +114,8 @@ pub(crate) enum ServerTaskError {
     ClosedEarlyByClient,
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as task killed")]
+    CanceledAsTaskKilled,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
     #[error("idle after {0:?} x {1}")]
@@ -160,6 +162,7 @@ impl ServerTaskError {
             ServerTaskError::ClosedByClient => "ClosedByClient",
             ServerTaskError::ClosedEarlyByClient => "ClosedEarlyByClient",
             ServerTaskError::CanceledAsUserBlocked => "CanceledAsUserBlocked",
+            ServerTaskError::CanceledAsTaskKilled => "CanceledAsTaskKilled",
             ServerTaskError::CanceledAsServerQuit => "CanceledAsServerQuit",
             ServerTaskError::Idle(_, _) => "Idle",
             ServerTaskError::InterceptionError(_, _) => "InterceptionError",
@@ -371,6 +374,7 @@ impl From<H1ReqmodAdaptationError> for ServerTaskError {
             }
             H1ReqmodAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => ServerTaskError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
             },
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
@@ -401,6 +405,7 @@ impl From<H1RespmodAdaptationError> for ServerTaskError {
             }
             H1RespmodAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => ServerTaskError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
             },
             e => ServerTaskError::InternalAdapterError(anyhow!("respmod: {e}")),
@@ -427,6 +432,7 @@ impl From<SmtpAdaptationError> for ServerTaskError {
             }
             SmtpAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => ServerTaskError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
             },
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
@@ -450,6 +456,7 @@ impl From<ImapAdaptationError> for ServerTaskError {
             }
             ImapAdaptationError::IdleForceQuit(reason) => match reason {
                 IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
+                IdleForceQuitReason::TaskKilled => ServerTaskError::CanceledAsTaskKilled,
                 IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
             },
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
@@ -19,7 +19,9 @@ use tokio::net::TcpStream;
 use tokio::sync::{broadcast, mpsc};
 use tokio_rustls::{TlsAcceptor, server::TlsStream};
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
+use g3_daemon::listen::{
+    AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime, ReceiveUdpServer,
+};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::{AsyncStream, IdleWheel};
 use g3_openssl::SslStream;
@@ -31,15 +33,18 @@ use g3_types::net::{
 };
 
 use super::HttpProxyServerStats;
+#[cfg(feature = "quic")]
+use super::task::HttpProxyH3ConnectionTask;
 use super::task::{
-    CommonTaskContext, HttpProxyPipelineReaderTask, HttpProxyPipelineStats,
-    HttpProxyPipelineWriterTask,
+    CommonTaskContext, H2_PREFACE, HttpProxyH2ConnectionTask, HttpProxyPipelineReaderTask,
+    HttpProxyPipelineStats, HttpProxyPipelineWriterTask, HttpProxyStreamContext,
 };
 use crate::audit::{AuditContext, AuditHandle};
 use crate::auth::UserGroup;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_forward::HttpCache;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
     ServerRegistry, ServerStats, WrapArcServer,
@@ -50,6 +55,7 @@ pub(crate) struct HttpProxyServer {
     server_stats: Arc<HttpProxyServerStats>,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    http_cache: Option<Arc<HttpCache>>,
     tls_acceptor: Option<TlsAcceptor>,
     tls_accept_timeout: Duration,
     tls_client_config: Arc<OpensslClientConfig>,
@@ -72,17 +78,21 @@ impl HttpProxyServer {
         server_stats: Arc<HttpProxyServerStats>,
         listen_stats: Arc<ListenStats>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        http_cache: Option<Arc<HttpCache>>,
         version: usize,
     ) -> anyhow::Result<HttpProxyServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
 
         let mut tls_accept_timeout = Duration::from_secs(10);
         let tls_acceptor = if let Some(tls_config_builder) = &config.server_tls_config {
+            let mut alpn_protocols = Vec::with_capacity(3);
+            if config.enable_h2 {
+                alpn_protocols.push(AlpnProtocol::Http2);
+            }
+            alpn_protocols.push(AlpnProtocol::Http11);
+            alpn_protocols.push(AlpnProtocol::Http10);
             let tls_server_config = tls_config_builder
-                .build_with_alpn_protocols(
-                    Some(vec![AlpnProtocol::Http11, AlpnProtocol::Http10]),
-                    tls_rolling_ticketer.clone(),
-                )
+                .build_with_alpn_protocols(Some(alpn_protocols), tls_rolling_ticketer.clone())
                 .context("failed to build tls server config")?;
             tls_accept_timeout = tls_server_config.accept_timeout;
             Some(TlsAcceptor::from(tls_server_config.driver))
@@ -113,13 +123,14 @@ impl HttpProxyServer {
 
         let escaper = Arc::new(crate::escape::get_or_insert_default(config.escaper()));
         let user_group = config.get_user_group().map(Arc::new);
-        let audit_handle = config.get_audit_handle()?;
+        let audit_handle = HttpProxyServer::get_audit_handle(&config)?;
 
         let server = HttpProxyServer {
             config,
             server_stats,
             listen_stats,
             tls_rolling_ticketer,
+            http_cache,
             tls_acceptor,
             tls_accept_timeout,
             tls_client_config: Arc::new(tls_client_config),
@@ -138,6 +149,24 @@ impl HttpProxyServer {
         Ok(server)
     }
 
+    /// the h2 / h3 client streams can't be adapted by ICAP services, so refuse to bypass them
+    fn get_audit_handle(
+        config: &HttpProxyServerConfig,
+    ) -> anyhow::Result<Option<Arc<AuditHandle>>> {
+        let audit_handle = config.get_audit_handle()?;
+        if (config.enable_h2 || config.enable_h3)
+            && let Some(handle) = &audit_handle
+            && (handle.icap_reqmod_client().is_some() || handle.icap_respmod_client().is_some())
+        {
+            return Err(anyhow!(
+                "ICAP adaptation is not supported for h2 / h3 clients, \
+                 which are enabled in server {}",
+                config.name()
+            ));
+        }
+        Ok(audit_handle)
+    }
+
     pub(crate) fn prepare_initial(
         config: HttpProxyServerConfig,
     ) -> anyhow::Result<ArcServerInternal> {
@@ -154,8 +183,22 @@ impl HttpProxyServer {
             None
         };
 
-        let server =
-            HttpProxyServer::new(config, server_stats, listen_stats, tls_rolling_ticketer, 1)?;
+        let http_cache = if let Some(c) = &config.http_cache {
+            let cache =
+                HttpCache::build(c, config.name()).context("failed to create http cache")?;
+            Some(cache)
+        } else {
+            None
+        };
+
+        let server = HttpProxyServer::new(
+            config,
+            server_stats,
+            listen_stats,
+            tls_rolling_ticketer,
+            http_cache,
+            1,
+        )?;
         Ok(Arc::new(server))
     }
 
@@ -176,11 +219,22 @@ impl HttpProxyServer {
                 None
             };
 
+            let http_cache = if self.config.http_cache.eq(&config.http_cache) {
+                self.http_cache.clone()
+            } else if let Some(c) = &config.http_cache {
+                let cache =
+                    HttpCache::build(c, config.name()).context("failed to create http cache")?;
+                Some(cache)
+            } else {
+                None
+            };
+
             let server = HttpProxyServer::new(
                 config,
                 server_stats,
                 listen_stats,
                 tls_rolling_ticketer,
+                http_cache,
                 self.reload_version + 1,
             )?;
             Ok(server)
@@ -204,6 +258,7 @@ impl HttpProxyServer {
             tls_client_config: self.tls_client_config.clone(),
             task_logger: self.task_logger.clone(),
             dst_host_filter: self.dst_host_filter.clone(),
+            http_cache: self.http_cache.clone(),
         })
     }
 
@@ -255,6 +310,43 @@ impl HttpProxyServer {
         w_task.into_running().await
     }
 
+    fn stream_context(&self, cc_info: ClientConnectionInfo) -> Arc<HttpProxyStreamContext> {
+        Arc::new(HttpProxyStreamContext::new(
+            self.get_common_task_context(cc_info),
+            self.audit_context(),
+            self.user_group.load_full(),
+        ))
+    }
+
+    async fn spawn_h2_task<T>(&self, stream: T, cc_info: ClientConnectionInfo)
+    where
+        T: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let task = HttpProxyH2ConnectionTask::new(self.stream_context(cc_info));
+        task.run(stream).await
+    }
+
+    /// detect h2 prior knowledge connections by peeking the full connection preface
+    async fn is_h2_prior_knowledge(&self, stream: &TcpStream) -> bool {
+        let mut buf = [0u8; H2_PREFACE.len()];
+        let peek_preface = async {
+            loop {
+                match stream.peek(&mut buf).await {
+                    Ok(0) | Err(_) => return false,
+                    Ok(n) if buf[..n] != H2_PREFACE[..n] => return false,
+                    Ok(n) if n == H2_PREFACE.len() => return true,
+                    Ok(_) => {
+                        // peek returns at once if there is any data, so wait for more
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(self.config.multiplex.preface_timeout, peek_preface)
+            .await
+            .unwrap_or(false)
+    }
+
     #[cfg(feature = "quic")]
     fn spawn_quic_stream_task(
         &self,
@@ -309,7 +401,7 @@ impl ServerInternal for HttpProxyServer {
     }
 
     fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
-        let audit_handle = self.config.get_audit_handle()?;
+        let audit_handle = HttpProxyServer::get_audit_handle(&self.config)?;
         self.audit_handle.store(audit_handle);
         Ok(())
     }
@@ -387,7 +479,11 @@ impl AcceptTcpServer for HttpProxyServer {
                         // Quick ACK is needed with session resumption
                         cc_info.tcp_sock_try_quick_ack();
                     }
-                    self.spawn_stream_task(tls_stream, cc_info).await
+                    if tls_stream.get_ref().1.alpn_protocol() == Some(b"h2") {
+                        self.spawn_h2_task(tls_stream, cc_info).await
+                    } else {
+                        self.spawn_stream_task(tls_stream, cc_info).await
+                    }
                 }
                 Ok(Err(e)) => {
                     self.listen_stats.add_failed();
@@ -408,6 +504,8 @@ impl AcceptTcpServer for HttpProxyServer {
                     // TODO record tls failure and add some sec policy
                 }
             }
+        } else if self.config.enable_h2 && self.is_h2_prior_knowledge(&stream).await {
+            self.spawn_h2_task(stream, cc_info).await;
         } else {
             self.spawn_stream_task(stream, cc_info).await;
         }
@@ -424,6 +522,18 @@ impl AcceptQuicServer for HttpProxyServer {
             return;
         }
 
+        if self.config.enable_h3 {
+            let alpn_protocol = connection
+                .handshake_data()
+                .and_then(|v| v.downcast::<quinn::crypto::rustls::HandshakeData>().ok())
+                .and_then(|v| v.protocol);
+            if alpn_protocol.as_deref() == Some(b"h3") {
+                let task = HttpProxyH3ConnectionTask::new(self.stream_context(cc_info));
+                task.run(connection).await;
+                return;
+            }
+        }
+
         loop {
             // TODO update ctx and quit gracefully
             match connection.accept_bi().await {
@@ -443,6 +553,17 @@ impl AcceptQuicServer for HttpProxyServer {
     }
 }
 
+impl ReceiveUdpServer for HttpProxyServer {
+    fn receive_udp_packet(
+        &self,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
+}
+
 #[async_trait]
 impl Server for HttpProxyServer {
     fn escaper(&self) -> &NodeName {
@@ -481,7 +602,11 @@ impl Server for HttpProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        if self.config.enable_h2 && stream.get_ref().1.alpn_protocol() == Some(b"h2") {
+            self.spawn_h2_task(stream, cc_info).await;
+        } else {
+            self.spawn_stream_task(stream, cc_info).await;
+        }
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
@@ -491,6 +616,10 @@ impl Server for HttpProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        if self.config.enable_h2 && stream.ssl().selected_alpn_protocol() == Some(b"h2") {
+            self.spawn_h2_task(stream, cc_info).await;
+        } else {
+            self.spawn_stream_task(stream, cc_info).await;
+        }
     }
 }
@@ -10,10 +10,11 @@ use std::sync::atomic::{AtomicIsize, AtomicU64, Ordering};
 use arc_swap::ArcSwapOption;
 
 use g3_types::metrics::{MetricTagMap, NodeName};
-use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
+use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats, UdpIoSnapshot, UdpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    LiveTaskRegistry, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats,
+    ServerStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -28,14 +29,18 @@ pub(crate) struct HttpProxyServerStats {
 
     pub forbidden: ServerForbiddenStats,
 
+    pub(crate) live_tasks: Arc<LiveTaskRegistry>,
+
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_connect: ServerPerTaskStats,
     pub task_http_forward: ServerPerTaskStats,
     pub task_ftp_over_http: ServerPerTaskStats,
+    pub task_udp_connect: ServerPerTaskStats,
 
     pub io_http: TcpIoStats,
     pub io_connect: TcpIoStats,
     pub io_untrusted: TcpIoStats,
+    pub io_udp: UdpIoStats,
 }
 
 impl HttpProxyServerStats {
@@ -47,13 +52,16 @@ impl HttpProxyServerStats {
             online: AtomicIsize::new(0),
             conn_total: AtomicU64::new(0),
             forbidden: Default::default(),
+            live_tasks: Arc::new(LiveTaskRegistry::default()),
             task_http_untrusted: Default::default(),
             task_http_connect: Default::default(),
             task_http_forward: Default::default(),
             task_ftp_over_http: Default::default(),
+            task_udp_connect: Default::default(),
             io_http: Default::default(),
             io_connect: Default::default(),
             io_untrusted: Default::default(),
+            io_udp: Default::default(),
         }
     }
 
@@ -108,6 +116,7 @@ impl ServerStats for HttpProxyServerStats {
         self.task_http_connect.get_task_total()
             + self.task_http_forward.get_task_total()
             + self.task_ftp_over_http.get_task_total()
+            + self.task_udp_connect.get_task_total()
     }
 
     fn get_alive_count(&self) -> i32 {
@@ -115,6 +124,7 @@ impl ServerStats for HttpProxyServerStats {
         self.task_http_connect.get_alive_count()
             + self.task_http_forward.get_alive_count()
             + self.task_ftp_over_http.get_alive_count()
+            + self.task_udp_connect.get_alive_count()
     }
 
     fn tcp_io_snapshot(&self) -> Option<TcpIoSnapshot> {
@@ -123,11 +133,19 @@ impl ServerStats for HttpProxyServerStats {
         Some(self.io_http.snapshot() + self.io_connect.snapshot())
     }
 
+    fn udp_io_snapshot(&self) -> Option<UdpIoSnapshot> {
+        Some(self.io_udp.snapshot())
+    }
+
     #[inline]
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot {
         self.forbidden.snapshot()
     }
 
+    fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        Some(&self.live_tasks)
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
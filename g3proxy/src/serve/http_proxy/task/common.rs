@@ -4,25 +4,31 @@
  */
 
 use std::net::SocketAddr;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use log::debug;
 use slog::Logger;
 use tokio::time::Instant;
 
 use g3_daemon::server::ClientConnectionInfo;
+use g3_http::server::HttpProxyClientRequest;
 use g3_icap_client::reqmod::h1::HttpAdapterErrorResponse;
 use g3_io_ext::{IdleWheel, OptionalInterval};
 use g3_types::acl::AclAction;
 use g3_types::acl_set::AclDstHostRuleSet;
-use g3_types::net::{OpensslClientConfig, UpstreamAddr};
+use g3_types::auth::UserAuthError;
+use g3_types::net::{HttpAuth, OpensslClientConfig, UpstreamAddr};
 
 use super::{HttpProxyServerConfig, HttpProxyServerStats};
-use crate::escape::ArcEscaper;
-use crate::module::http_forward::HttpProxyClientResponse;
+use crate::auth::{UserContext, UserGroup};
+use crate::config::server::ServerConfig;
+use crate::escape::{ArcEscaper, EgressPathSelection};
+use crate::module::http_forward::{HttpCache, HttpProxyClientResponse};
 use crate::module::http_header;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
-use crate::serve::{ServerIdleChecker, ServerQuitPolicy, ServerTaskNotes};
+use crate::serve::{ServerIdleChecker, ServerQuitPolicy, ServerStats, ServerTaskNotes};
 
 #[derive(Clone)]
 pub(crate) struct CommonTaskContext {
@@ -36,6 +42,7 @@ pub(crate) struct CommonTaskContext {
     pub(crate) task_logger: Option<Logger>,
 
     pub(crate) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    pub(crate) http_cache: Option<Arc<HttpCache>>,
 }
 
 impl CommonTaskContext {
@@ -48,6 +55,7 @@ impl CommonTaskContext {
         ServerIdleChecker::new(
             self.idle_wheel.clone(),
             task_notes.user_ctx().map(|c| c.user().clone()),
+            task_notes.live_task().cloned(),
             self.server_config.task_idle_max_count,
             self.server_quit_policy.clone(),
         )
@@ -80,6 +88,112 @@ impl CommonTaskContext {
         default_action
     }
 
+    /// check the user with the auth info in the request,
+    /// the negotiate token will always be checked as a new one
+    pub(crate) async fn check_user(
+        &self,
+        user_group: &UserGroup,
+        auth_info: &HttpAuth,
+        upstream: &UpstreamAddr,
+    ) -> Result<UserContext, UserAuthError> {
+        match auth_info {
+            HttpAuth::None => user_group
+                .get_anonymous_user()
+                .map(|(user, user_type)| {
+                    UserContext::new(
+                        None,
+                        user,
+                        user_type,
+                        self.server_config.name(),
+                        self.server_stats.share_extra_tags(),
+                    )
+                })
+                .ok_or(UserAuthError::NoUserSupplied),
+            HttpAuth::Basic(v) => {
+                let username = v.username.as_original();
+                let username = self
+                    .server_config
+                    .username_params
+                    .as_ref()
+                    .map(|c| c.real_username(username))
+                    .unwrap_or(username);
+                user_group
+                    .check_user_with_password(
+                        username,
+                        &v.password,
+                        self.client_addr(),
+                        Some(upstream),
+                        self.server_config.name(),
+                        self.server_stats.share_extra_tags(),
+                    )
+                    .await
+            }
+            HttpAuth::Bearer(v) => {
+                user_group
+                    .check_user_with_token(
+                        &v.token,
+                        self.client_addr(),
+                        Some(upstream),
+                        self.server_config.name(),
+                        self.server_stats.share_extra_tags(),
+                    )
+                    .await
+            }
+            HttpAuth::Negotiate(v) => user_group.check_user_with_negotiate(
+                &v.token,
+                self.server_config.name(),
+                self.server_stats.share_extra_tags(),
+            ),
+        }
+    }
+
+    pub(crate) fn get_egress_path_selection(
+        &self,
+        req: &mut HttpProxyClientRequest,
+    ) -> Result<Option<EgressPathSelection>, ()> {
+        let mut egress_path = EgressPathSelection::default();
+
+        if let Some(header) = &self.server_config.egress_path_selection_header {
+            // check and remove the custom header
+            if let Some(value) = req.end_to_end_headers.remove(header) {
+                match usize::from_str(value.to_str()) {
+                    Ok(id) => egress_path.set_number_id(self.server_config.name().clone(), id),
+                    Err(e) => {
+                        debug!("invalid egress path number id value in header {header}: {e}");
+                        return Err(());
+                    }
+                }
+            }
+        }
+
+        // Optional: compute username-param-derived escaper address and store override
+        if let Some(name_params) = &self.server_config.username_params
+            && let HttpAuth::Basic(v) = &req.auth_info
+        {
+            match name_params.parse_egress_upstream_http(v.username.as_original()) {
+                Ok(Some(ups)) => {
+                    debug!(
+                        "[{}] http username params -> next proxy {}",
+                        self.server_config.name(),
+                        ups.addr
+                    );
+                    egress_path.set_upstream(self.escaper.name().clone(), ups);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    debug!("failed to get upstream addr from username: {e}");
+                    return Err(());
+                }
+            }
+        }
+
+        if egress_path.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(egress_path))
+        }
+    }
+
     pub(crate) fn set_custom_header_for_local_reply(
         &self,
         tcp_notes: &TcpConnectTaskNotes,
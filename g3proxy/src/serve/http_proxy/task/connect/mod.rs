@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use super::{CommonTaskContext, HttpProxyServerStats, protocol};
+use super::{CommonTaskContext, HttpProxyServerStats, HttpStreamResponder, protocol};
 
 mod task;
 pub(super) use task::HttpProxyConnectTask;
@@ -17,7 +17,7 @@ use g3_types::acl::AclAction;
 use g3_types::net::{ProxyRequestType, UpstreamAddr};
 
 use super::protocol::{HttpClientWriter, HttpProxyRequest};
-use super::{CommonTaskContext, TcpConnectTaskCltWrapperStats};
+use super::{CommonTaskContext, HttpStreamResponder, TcpConnectTaskCltWrapperStats};
 use crate::audit::AuditContext;
 use crate::auth::User;
 use crate::config::server::ServerConfig;
@@ -32,6 +32,9 @@ use crate::serve::{
     ServerTaskStage,
 };
 
+/// the connect error, with the local reply to the client
+type ConnectFailure = (ServerTaskError, HttpProxyClientResponse);
+
 pub(crate) struct HttpProxyConnectTask {
     ctx: Arc<CommonTaskContext>,
     upstream: UpstreamAddr,
@@ -60,68 +63,46 @@ impl HttpProxyConnectTask {
         audit_ctx: AuditContext,
         req: &HttpProxyRequest<impl AsyncRead>,
         task_notes: ServerTaskNotes,
+    ) -> Self {
+        HttpProxyConnectTask::with_upstream(
+            ctx,
+            audit_ctx,
+            req.upstream.clone(),
+            req.inner.version,
+            task_notes,
+        )
+    }
+
+    pub(crate) fn with_upstream(
+        ctx: &Arc<CommonTaskContext>,
+        audit_ctx: AuditContext,
+        upstream: UpstreamAddr,
+        http_version: Version,
+        task_notes: ServerTaskNotes,
     ) -> Self {
         HttpProxyConnectTask {
             ctx: Arc::clone(ctx),
-            upstream: req.upstream.clone(),
+            upstream,
             stream_ups: None,
             back_to_http: false,
             task_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(TcpStreamTaskStats::default()),
             audit_ctx,
-            http_version: req.inner.version,
+            http_version,
             started: false,
         }
     }
 
-    async fn reply_too_many_requests<W>(&mut self, clt_w: &mut W)
-    where
-        W: AsyncWrite + Unpin,
-    {
-        let rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
-        // no custom header is set
-        let _ = rsp.reply_err_to_request(clt_w).await;
-        self.back_to_http = false;
-    }
-
-    async fn reply_forbidden<W>(&mut self, clt_w: &mut W)
-    where
-        W: AsyncWrite + Unpin,
-    {
-        let rsp = HttpProxyClientResponse::forbidden(self.http_version);
-        // no custom header is set
-        let _ = rsp.reply_err_to_request(clt_w).await;
-        self.back_to_http = false;
-    }
-
-    async fn reply_banned_protocol<W>(&mut self, clt_w: &mut W)
-    where
-        W: AsyncWrite + Unpin,
-    {
-        let rsp = HttpProxyClientResponse::method_not_allowed(self.http_version);
-        // no custom header is set
-        let _ = rsp.reply_err_to_request(clt_w).await;
-        self.back_to_http = false;
-    }
-
-    async fn reply_ok<W>(&self, clt_w: &mut W) -> ServerTaskResult<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
+    fn reply_ok(&self) -> HttpProxyClientResponse {
         let mut rsp =
             HttpProxyClientResponse::from_standard(http::StatusCode::OK, self.http_version, false);
         self.ctx
             .set_custom_header_for_local_reply(&self.tcp_notes, &mut rsp);
-        rsp.reply_ok_to_connect(clt_w)
-            .await
-            .map_err(ServerTaskError::ClientTcpWriteFailed)
+        rsp
     }
 
-    async fn reply_connect_err<W>(&mut self, e: &TcpConnectError, clt_w: &mut W)
-    where
-        W: AsyncWrite + Unpin,
-    {
+    fn reply_connect_err(&self, e: &TcpConnectError) -> HttpProxyClientResponse {
         // If the next-hop was derived from username params and DNS failed,
         // treat it as a bad request (400) instead of origin DNS error.
         if self.tcp_notes.override_peer.is_some() && matches!(e, TcpConnectError::ResolveFailed(_))
@@ -129,20 +110,13 @@ impl HttpProxyConnectTask {
             let mut rsp = HttpProxyClientResponse::bad_request(self.http_version);
             rsp.set_error_message("Proxy targeting didn't find a match");
             // no custom header is set for 400
-            self.back_to_http = false;
-            let _ = rsp.reply_err_to_request(clt_w).await;
-            return;
+            return rsp;
         }
 
         let mut rsp = HttpProxyClientResponse::from_tcp_connect_error(e, Version::HTTP_11, false);
         self.ctx
             .set_custom_header_for_local_reply(&self.tcp_notes, &mut rsp);
-        let should_close = rsp.should_close();
-        self.back_to_http = !should_close;
-
-        if rsp.reply_err_to_request(clt_w).await.is_err() {
-            self.back_to_http = false;
-        }
+        rsp
     }
 
     pub(crate) async fn connect_to_upstream<W>(&mut self, clt_w: &mut W)
@@ -150,11 +124,13 @@ impl HttpProxyConnectTask {
         W: AsyncWrite + Unpin,
     {
         self.pre_start();
-        match self.run_connect(clt_w).await {
+        match self.run_connect().await {
             Ok(()) => {
                 self.back_to_http = false;
             }
-            Err(e) => {
+            Err((e, rsp)) => {
+                let replied = rsp.reply_err_to_request(clt_w).await.is_ok();
+                self.back_to_http = replied && !rsp.should_close();
                 if let Some(log_ctx) = self.get_log_context() {
                     log_ctx.log(e);
                 }
@@ -162,50 +138,28 @@ impl HttpProxyConnectTask {
         }
     }
 
-    async fn handle_server_upstream_acl_action<W>(
+    /// connect to upstream for a h2 / h3 stream,
+    /// the local reply will be returned if failed, and the stream should be closed after sending it
+    pub(crate) async fn connect_to_upstream_for_stream(
         &mut self,
-        action: AclAction,
-        clt_w: &mut W,
-    ) -> ServerTaskResult<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
-        let forbid = match action {
-            AclAction::Permit => false,
-            AclAction::PermitAndLog => {
-                // TODO log permit
-                false
-            }
-            AclAction::Forbid => true,
-            AclAction::ForbidAndLog => {
-                // TODO log forbid
-                true
-            }
-        };
-        if forbid {
-            self.ctx.server_stats.forbidden.add_dest_denied();
-            if let Some(user_ctx) = self.task_notes.user_ctx() {
-                // also add to user level forbidden stats
-                user_ctx.add_dest_denied();
+    ) -> Option<HttpProxyClientResponse> {
+        self.pre_start();
+        match self.run_connect().await {
+            Ok(()) => None,
+            Err((e, rsp)) => {
+                if let Some(log_ctx) = self.get_log_context() {
+                    log_ctx.log(e);
+                }
+                Some(rsp)
             }
-
-            self.reply_forbidden(clt_w).await;
-            Err(ServerTaskError::ForbiddenByRule(
-                ServerTaskForbiddenError::DestDenied,
-            ))
-        } else {
-            Ok(())
         }
     }
 
-    async fn handle_user_upstream_acl_action<W>(
-        &mut self,
+    fn handle_acl_action(
+        &self,
         action: AclAction,
-        clt_w: &mut W,
-    ) -> ServerTaskResult<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
+        forbidden_error: ServerTaskForbiddenError,
+    ) -> Result<(), ConnectFailure> {
         let forbid = match action {
             AclAction::Permit => false,
             AclAction::PermitAndLog => {
@@ -219,80 +173,62 @@ impl HttpProxyConnectTask {
             }
         };
         if forbid {
-            self.reply_forbidden(clt_w).await;
-            Err(ServerTaskError::ForbiddenByRule(
-                ServerTaskForbiddenError::DestDenied,
-            ))
+            let rsp = match forbidden_error {
+                ServerTaskForbiddenError::ProtoBanned => {
+                    HttpProxyClientResponse::method_not_allowed(self.http_version)
+                }
+                _ => HttpProxyClientResponse::forbidden(self.http_version),
+            };
+            // no custom header is set
+            Err((ServerTaskError::ForbiddenByRule(forbidden_error), rsp))
         } else {
             Ok(())
         }
     }
 
-    async fn handle_user_protocol_acl_action<W>(
-        &mut self,
-        action: AclAction,
-        clt_w: &mut W,
-    ) -> ServerTaskResult<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
-        let forbid = match action {
-            AclAction::Permit => false,
-            AclAction::PermitAndLog => {
-                // TODO log permit
-                false
-            }
-            AclAction::Forbid => true,
-            AclAction::ForbidAndLog => {
-                // TODO log forbid
-                true
+    fn handle_server_upstream_acl_action(&self, action: AclAction) -> Result<(), ConnectFailure> {
+        let r = self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied);
+        if r.is_err() {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                // also add to user level forbidden stats
+                user_ctx.add_dest_denied();
             }
-        };
-        if forbid {
-            self.reply_banned_protocol(clt_w).await;
-            Err(ServerTaskError::ForbiddenByRule(
-                ServerTaskForbiddenError::ProtoBanned,
-            ))
-        } else {
-            Ok(())
         }
+        r
     }
 
-    async fn run_connect<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<()>
-    where
-        W: AsyncWrite + Unpin,
-    {
+    async fn run_connect(&mut self) -> Result<(), ConnectFailure> {
         let tcp_client_misc_opts;
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             let user_ctx = user_ctx.clone();
 
             if user_ctx.check_rate_limit().is_err() {
-                self.reply_too_many_requests(clt_w).await;
-                return Err(ServerTaskError::ForbiddenByRule(
-                    ServerTaskForbiddenError::RateLimited,
+                return Err((
+                    ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::RateLimited),
+                    HttpProxyClientResponse::too_many_requests(self.http_version),
                 ));
             }
 
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
-                    self.reply_too_many_requests(clt_w).await;
-                    return Err(ServerTaskError::ForbiddenByRule(
-                        ServerTaskForbiddenError::FullyLoaded,
+                    return Err((
+                        ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::FullyLoaded),
+                        HttpProxyClientResponse::too_many_requests(self.http_version),
                     ));
                 }
             }
 
             let action = user_ctx.check_proxy_request(ProxyRequestType::HttpConnect);
-            self.handle_user_protocol_acl_action(action, clt_w).await?;
+            self.handle_acl_action(action, ServerTaskForbiddenError::ProtoBanned)?;
 
             let action = user_ctx.check_upstream(&self.upstream);
-            self.handle_user_upstream_acl_action(action, clt_w).await?;
+            self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied)?;
 
             // server level dst host/port acl rules
             let action = self.ctx.check_upstream(&self.upstream);
-            self.handle_server_upstream_acl_action(action, clt_w)
-                .await?;
+            self.handle_server_upstream_acl_action(action)?;
 
             tcp_client_misc_opts = user_ctx
                 .user_config()
@@ -300,19 +236,23 @@ impl HttpProxyConnectTask {
         } else {
             // server level dst host/port acl rules
             let action = self.ctx.check_upstream(&self.upstream);
-            self.handle_server_upstream_acl_action(action, clt_w)
-                .await?;
+            self.handle_server_upstream_acl_action(action)?;
 
             tcp_client_misc_opts = Cow::Borrowed(&self.ctx.server_config.tcp_misc_opts);
         }
 
         // set client side socket options
-        self.ctx
+        if self
+            .ctx
             .cc_info
             .tcp_sock_set_raw_opts(&tcp_client_misc_opts, true)
-            .map_err(|_| {
-                ServerTaskError::InternalServerError("failed to set client socket options")
-            })?;
+            .is_err()
+        {
+            let e = ServerTaskError::InternalServerError("failed to set client socket options");
+            let rsp = HttpProxyClientResponse::from_task_err(&e, self.http_version, true)
+                .unwrap_or_else(|| HttpProxyClientResponse::bad_gateway(self.http_version));
+            return Err((e, rsp));
+        }
 
         self.task_notes.stage = ServerTaskStage::Connecting;
 
@@ -337,8 +277,8 @@ impl HttpProxyConnectTask {
                 Ok(())
             }
             Err(e) => {
-                self.reply_connect_err(&e, clt_w).await;
-                Err(e.into())
+                let rsp = self.reply_connect_err(&e);
+                Err((e.into(), rsp))
             }
         }
     }
@@ -364,6 +304,14 @@ impl HttpProxyConnectTask {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -442,7 +390,10 @@ impl HttpProxyConnectTask {
         }
 
         self.task_notes.stage = ServerTaskStage::Replying;
-        self.reply_ok(&mut clt_w).await?;
+        self.reply_ok()
+            .reply_ok_to_connect(&mut clt_w)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
 
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -454,6 +405,62 @@ impl HttpProxyConnectTask {
         self.relay(clt_r, clt_w, ups_r, ups_w).await
     }
 
+    /// run the tunnel for a h2 / h3 stream after connected to upstream
+    pub(crate) async fn run_stream<CDR, S>(mut self, clt_r: CDR, mut responder: S)
+    where
+        CDR: AsyncRead + Send + Sync + Unpin + 'static,
+        S: HttpStreamResponder,
+    {
+        let Some((ups_r, ups_w)) = self.stream_ups.take() else {
+            return;
+        };
+
+        let e = match self
+            .run_stream_connected(clt_r, &mut responder, ups_r, ups_w)
+            .await
+        {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+    }
+
+    async fn run_stream_connected<CDR, S, UR, UW>(
+        &mut self,
+        clt_r: CDR,
+        responder: &mut S,
+        ups_r: UR,
+        ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Sync + Unpin + 'static,
+        S: HttpStreamResponder,
+        UR: AsyncRead + Send + Sync + Unpin + 'static,
+        UW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        self.task_notes.stage = ServerTaskStage::Replying;
+        let clt_w = responder
+            .send_header(self.reply_ok().to_stream_response())
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+
+        self.task_notes.mark_relaying();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_ready.add_http_connect();
+            });
+        }
+        self.relay(clt_r, clt_w, ups_r, ups_w).await
+    }
+
     async fn relay<CDR, CDW, UR, UW>(
         &mut self,
         clt_r: CDR,
@@ -604,4 +611,8 @@ impl StreamTransitTask for HttpProxyConnectTask {
     fn user(&self) -> Option<&User> {
         self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
+
+    fn task_killed(&self) -> bool {
+        self.task_notes.is_killed()
+    }
 }
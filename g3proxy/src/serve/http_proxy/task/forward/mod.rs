@@ -3,11 +3,17 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use super::{CommonTaskContext, HttpProxyServerStats, protocol};
+use super::{
+    CommonTaskContext, HttpProxyServerStats, HttpStreamResponder, StreamResetReason,
+    is_malformed_body_error, protocol,
+};
 
 mod task;
 pub(super) use task::HttpProxyForwardTask;
 
+mod stream;
+pub(super) use stream::HttpProxyStreamForwardTask;
+
 mod stats;
 use stats::{
     HttpForwardTaskCltWrapperStats, HttpForwardTaskStats, HttpsForwardTaskCltWrapperStats,
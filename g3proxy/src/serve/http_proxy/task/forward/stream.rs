@@ -0,0 +1,1092 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{HeaderMap, Method, Response, StatusCode, header};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::server::HttpProxyClientRequest;
+use g3_http::{HttpBodyDecodeReader, HttpBodyType, StreamToChunkedTransfer};
+use g3_io_ext::{
+    ArcLimitedReaderStats, ArcLimitedWriterStats, LimitedBufReadExt, LimitedReader, LimitedWriter,
+    StreamCopy, StreamCopyError,
+};
+use g3_types::acl::AclAction;
+use g3_types::net::{ProxyRequestType, TcpSockSpeedLimitConfig, UpstreamAddr};
+
+use super::protocol::HttpProxyStreamRequest;
+use super::{
+    CommonTaskContext, HttpForwardTaskCltWrapperStats, HttpForwardTaskStats, HttpStreamResponder,
+    HttpsForwardTaskCltWrapperStats, StreamResetReason, is_malformed_body_error,
+};
+use crate::audit::AuditContext;
+use crate::config::server::ServerConfig;
+use crate::log::task::http_forward::TaskLogForHttpForward;
+use crate::module::http_forward::{
+    BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
+    HttpCacheBodyReader, HttpCacheEntry, HttpCacheLookup, HttpCacheResponseAction, HttpCacheStore,
+    HttpCacheTask, HttpForwardTaskNotes, HttpProxyClientResponse,
+};
+use crate::module::http_header;
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::{
+    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
+    ServerTaskStage,
+};
+
+/// the task error, with the local reply to the client
+type StreamTaskFailure = (ServerTaskError, HttpProxyClientResponse);
+
+/// the request body transfer to upstream, in the framing of the HTTP/1.1 request header
+enum RequestBodyTransfer<'a, R, W> {
+    ContentLength(StreamCopy<'a, R, W>),
+    Chunked(StreamToChunkedTransfer<'a, BufReader<R>, W>),
+}
+
+impl<R, W> RequestBodyTransfer<'_, R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    fn finished(&self) -> bool {
+        match self {
+            RequestBodyTransfer::ContentLength(c) => c.finished(),
+            RequestBodyTransfer::Chunked(c) => c.finished(),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        match self {
+            RequestBodyTransfer::ContentLength(c) => c.is_idle(),
+            RequestBodyTransfer::Chunked(c) => c.is_idle(),
+        }
+    }
+
+    fn reset_active(&mut self) {
+        match self {
+            RequestBodyTransfer::ContentLength(c) => c.reset_active(),
+            RequestBodyTransfer::Chunked(c) => c.reset_active(),
+        }
+    }
+
+    fn no_cached_data(&self) -> bool {
+        match self {
+            RequestBodyTransfer::ContentLength(c) => c.no_cached_data(),
+            RequestBodyTransfer::Chunked(c) => c.no_cached_data(),
+        }
+    }
+}
+
+impl<R, W> Future for RequestBodyTransfer<'_, R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    type Output = Result<u64, StreamCopyError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            RequestBodyTransfer::ContentLength(c) => Pin::new(c).poll(cx),
+            RequestBodyTransfer::Chunked(c) => Pin::new(c).poll(cx),
+        }
+    }
+}
+
+/// The http forward task for a h2 / h3 client stream.
+///
+/// A new upstream connection will be used for each stream, and it will be closed after the
+/// response, so the upstream keep-alive settings are not used. ICAP adaptation is not supported,
+/// the server will refuse to enable h2 / h3 if any ICAP service is set.
+pub(crate) struct HttpProxyStreamForwardTask<'a> {
+    ctx: Arc<CommonTaskContext>,
+    audit_ctx: AuditContext,
+    upstream: UpstreamAddr,
+    req: &'a HttpProxyClientRequest,
+    is_https: bool,
+    send_error_response: bool,
+    task_notes: ServerTaskNotes,
+    http_notes: HttpForwardTaskNotes,
+    tcp_notes: TcpConnectTaskNotes,
+    task_stats: Arc<HttpForwardTaskStats>,
+    max_idle_count: usize,
+    cache_task: Option<HttpCacheTask>,
+    started: bool,
+}
+
+impl Drop for HttpProxyStreamForwardTask<'_> {
+    fn drop(&mut self) {
+        if self.started {
+            self.post_stop();
+            self.started = false;
+        }
+    }
+}
+
+impl<'a> HttpProxyStreamForwardTask<'a> {
+    pub(crate) fn new(
+        ctx: &Arc<CommonTaskContext>,
+        audit_ctx: AuditContext,
+        req: &'a HttpProxyStreamRequest,
+        is_https: bool,
+        task_notes: ServerTaskNotes,
+    ) -> Self {
+        let uri_log_max_chars = task_notes
+            .user_ctx()
+            .and_then(|c| c.user_config().log_uri_max_chars)
+            .unwrap_or(ctx.server_config.log_uri_max_chars);
+        let http_notes = HttpForwardTaskNotes::new(
+            req.time_received,
+            task_notes.task_created_instant(),
+            req.inner.method.clone(),
+            req.inner.uri.clone(),
+            uri_log_max_chars,
+        );
+        let max_idle_count = task_notes
+            .user_ctx()
+            .and_then(|c| c.user().task_max_idle_count())
+            .unwrap_or(ctx.server_config.task_idle_max_count);
+        HttpProxyStreamForwardTask {
+            ctx: Arc::clone(ctx),
+            audit_ctx,
+            upstream: req.upstream.clone(),
+            req: &req.inner,
+            is_https,
+            send_error_response: true,
+            task_notes,
+            http_notes,
+            tcp_notes: TcpConnectTaskNotes::default(),
+            task_stats: Arc::new(HttpForwardTaskStats::default()),
+            max_idle_count,
+            cache_task: None,
+            started: false,
+        }
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForHttpForward<'_>> {
+        let Some(logger) = &self.ctx.task_logger else {
+            return None;
+        };
+
+        let http_user_agent = self
+            .req
+            .end_to_end_headers
+            .get(header::USER_AGENT)
+            .map(|v| v.to_str());
+        Some(TaskLogForHttpForward {
+            logger,
+            upstream: &self.upstream,
+            task_notes: &self.task_notes,
+            http_notes: &self.http_notes,
+            http_user_agent,
+            tcp_notes: &self.tcp_notes,
+            client_rd_bytes: self.task_stats.clt.read.get_bytes(),
+            client_wr_bytes: self.task_stats.clt.write.get_bytes(),
+            remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
+            remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+        })
+    }
+
+    /// `clt_r` is the reader of the request body, which should be limited by the content-length
+    pub(crate) async fn run<CDR, S>(
+        &mut self,
+        clt_r: Option<CDR>,
+        responder: &mut S,
+        fwd_ctx: &mut BoxHttpForwardContext,
+    ) where
+        CDR: AsyncRead + Send + Unpin,
+        S: HttpStreamResponder,
+    {
+        self.pre_start();
+        let e = match self.run_forward(clt_r, responder, fwd_ctx).await {
+            Ok(()) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(&e);
+        }
+    }
+
+    fn pre_start(&mut self) {
+        self.ctx.server_stats.task_http_forward.add_task();
+        self.ctx.server_stats.task_http_forward.inc_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_total.add_http_forward(self.is_https);
+                s.req_alive.add_http_forward(self.is_https);
+            });
+        }
+
+        if self.ctx.server_config.flush_task_log_on_created
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_created();
+        }
+
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+
+        self.started = true;
+    }
+
+    fn post_stop(&mut self) {
+        self.ctx.server_stats.task_http_forward.dec_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| s.req_alive.del_http_forward(self.is_https));
+
+            if let Some(user_req_alive_permit) = self.task_notes.user_req_alive_permit.take() {
+                drop(user_req_alive_permit);
+            }
+        }
+    }
+
+    async fn reply_error<S>(&mut self, responder: &mut S, rsp: &HttpProxyClientResponse)
+    where
+        S: HttpStreamResponder,
+    {
+        if responder.send_error(rsp).await.is_ok() {
+            self.http_notes.rsp_status = rsp.status();
+        }
+    }
+
+    fn reply_connect_err(&self, e: &TcpConnectError) -> HttpProxyClientResponse {
+        // If the next-hop was derived from username params and DNS failed,
+        // treat it as a bad request (400) instead of origin DNS error.
+        if self.tcp_notes.override_peer.is_some() && matches!(e, TcpConnectError::ResolveFailed(_))
+        {
+            let mut rsp = HttpProxyClientResponse::bad_request(self.req.version);
+            rsp.set_error_message("Proxy targeting didn't find a match");
+            // no custom header is set for 400
+            return rsp;
+        }
+
+        let mut rsp = HttpProxyClientResponse::from_tcp_connect_error(e, self.req.version, true);
+        self.ctx
+            .set_custom_header_for_local_reply(&self.tcp_notes, &mut rsp);
+        rsp
+    }
+
+    async fn reply_task_err<S>(&mut self, e: &ServerTaskError, responder: &mut S)
+    where
+        S: HttpStreamResponder,
+    {
+        if let ServerTaskError::ClientTcpReadFailed(ee) = e
+            && is_malformed_body_error(ee)
+        {
+            responder.reset(StreamResetReason::MalformedMessage);
+            return;
+        }
+
+        match HttpProxyClientResponse::from_task_err(e, self.req.version, true) {
+            Some(mut rsp) => {
+                self.ctx
+                    .set_custom_header_for_local_reply(&self.tcp_notes, &mut rsp);
+                self.reply_error(responder, &rsp).await;
+            }
+            None => responder.reset(StreamResetReason::InternalError),
+        }
+    }
+
+    fn handle_acl_action(
+        &self,
+        action: AclAction,
+        forbidden_error: ServerTaskForbiddenError,
+    ) -> Result<(), StreamTaskFailure> {
+        let forbid = match action {
+            AclAction::Permit => false,
+            AclAction::PermitAndLog => {
+                // TODO log permit
+                false
+            }
+            AclAction::Forbid => true,
+            AclAction::ForbidAndLog => {
+                // TODO log forbid
+                true
+            }
+        };
+        if forbid {
+            let rsp = match forbidden_error {
+                ServerTaskForbiddenError::ProtoBanned => {
+                    HttpProxyClientResponse::method_not_allowed(self.req.version)
+                }
+                _ => HttpProxyClientResponse::forbidden(self.req.version),
+            };
+            // no custom header is set
+            Err((ServerTaskError::ForbiddenByRule(forbidden_error), rsp))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle_server_upstream_acl_action(
+        &self,
+        action: AclAction,
+    ) -> Result<(), StreamTaskFailure> {
+        let r = self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied);
+        if r.is_err() {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                // also add to user level forbidden stats
+                user_ctx.add_dest_denied();
+            }
+        }
+        r
+    }
+
+    /// check the acl rules and return whether the task should be audited
+    fn check_rules(&mut self) -> Result<bool, StreamTaskFailure> {
+        let tcp_client_misc_opts;
+        let mut audit_task = false;
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user_ctx = user_ctx.clone();
+
+            if user_ctx.check_rate_limit().is_err() {
+                return Err((
+                    ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::RateLimited),
+                    HttpProxyClientResponse::too_many_requests(self.req.version),
+                ));
+            }
+
+            match user_ctx.acquire_request_semaphore() {
+                Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
+                Err(_) => {
+                    return Err((
+                        ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::FullyLoaded),
+                        HttpProxyClientResponse::too_many_requests(self.req.version),
+                    ));
+                }
+            }
+
+            let request_type = if self.is_https {
+                ProxyRequestType::HttpsForward
+            } else {
+                ProxyRequestType::HttpForward
+            };
+            let action = user_ctx.check_proxy_request(request_type);
+            self.handle_acl_action(action, ServerTaskForbiddenError::ProtoBanned)?;
+
+            let action = user_ctx.check_upstream(&self.upstream);
+            self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied)?;
+
+            // server level dst host/port acl rules
+            let action = self.ctx.check_upstream(&self.upstream);
+            self.handle_server_upstream_acl_action(action)?;
+
+            if let Some(action) = user_ctx.check_http_user_agent(&self.req.end_to_end_headers) {
+                self.handle_acl_action(action, ServerTaskForbiddenError::UaBlocked)?;
+            }
+
+            let user_config = user_ctx.user_config();
+            tcp_client_misc_opts =
+                user_config.tcp_client_misc_opts(&self.ctx.server_config.tcp_misc_opts);
+
+            if let Some(audit_handle) = self.audit_ctx.handle() {
+                audit_task = user_config
+                    .audit
+                    .do_task_audit()
+                    .unwrap_or_else(|| audit_handle.do_task_audit());
+            }
+        } else {
+            // server level dst host/port acl rules
+            let action = self.ctx.check_upstream(&self.upstream);
+            self.handle_server_upstream_acl_action(action)?;
+
+            tcp_client_misc_opts = Cow::Borrowed(&self.ctx.server_config.tcp_misc_opts);
+
+            if let Some(audit_handle) = self.audit_ctx.handle() {
+                audit_task = audit_handle.do_task_audit();
+            }
+        }
+
+        // set client side socket options
+        if self
+            .ctx
+            .cc_info
+            .tcp_sock_set_raw_opts(&tcp_client_misc_opts, true)
+            .is_err()
+        {
+            let e = ServerTaskError::InternalServerError("failed to set client socket options");
+            let rsp = HttpProxyClientResponse::from_task_err(&e, self.req.version, true)
+                .unwrap_or_else(|| HttpProxyClientResponse::bad_gateway(self.req.version));
+            return Err((e, rsp));
+        }
+
+        Ok(audit_task)
+    }
+
+    fn setup_clt_stats(
+        &self,
+    ) -> (
+        ArcLimitedReaderStats,
+        ArcLimitedWriterStats,
+        TcpSockSpeedLimitConfig,
+    ) {
+        let origin_header_size = self.req.origin_header_size() as u64;
+        self.task_stats.clt.read.add_bytes(origin_header_size);
+
+        let user_io_stats = self.task_notes.user_ctx().map(|user_ctx| {
+            user_ctx.fetch_traffic_stats(
+                self.ctx.server_config.name(),
+                self.ctx.server_stats.share_extra_tags(),
+            )
+        });
+        let (clt_r_stats, clt_w_stats) = if self.is_https {
+            let mut wrapper_stats =
+                HttpsForwardTaskCltWrapperStats::new(&self.ctx.server_stats, &self.task_stats);
+            if let Some(user_io_stats) = user_io_stats {
+                for s in &user_io_stats {
+                    s.io.https_forward.add_in_bytes(origin_header_size);
+                }
+                wrapper_stats.push_user_io_stats(user_io_stats);
+            }
+            wrapper_stats.split()
+        } else {
+            let mut wrapper_stats =
+                HttpForwardTaskCltWrapperStats::new(&self.ctx.server_stats, &self.task_stats);
+            if let Some(user_io_stats) = user_io_stats {
+                for s in &user_io_stats {
+                    s.io.http_forward.add_in_bytes(origin_header_size);
+                }
+                wrapper_stats.push_user_io_stats(user_io_stats);
+            }
+            wrapper_stats.split()
+        };
+
+        let limit_config = match self.task_notes.user_ctx() {
+            Some(user_ctx) => user_ctx
+                .user_config()
+                .tcp_sock_speed_limit
+                .shrink_as_smaller(&self.ctx.server_config.tcp_sock_speed_limit),
+            None => self.ctx.server_config.tcp_sock_speed_limit,
+        };
+        if let Some(user_ctx) = self.task_notes.user_ctx()
+            && let Some(limiter) = user_ctx.user().tcp_all_upload_speed_limit()
+        {
+            limiter.try_consume(origin_header_size);
+        }
+        (clt_r_stats, clt_w_stats, limit_config)
+    }
+
+    async fn run_forward<CDR, S>(
+        &mut self,
+        clt_r: Option<CDR>,
+        responder: &mut S,
+        fwd_ctx: &mut BoxHttpForwardContext,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        S: HttpStreamResponder,
+    {
+        let audit_task = match self.check_rules() {
+            Ok(audit_task) => audit_task,
+            Err((e, rsp)) => {
+                self.reply_error(responder, &rsp).await;
+                return Err(e);
+            }
+        };
+
+        let (clt_r_stats, clt_w_stats, limit_config) = self.setup_clt_stats();
+        let clt_r = clt_r.map(|r| {
+            let mut r = LimitedReader::local_limited(
+                r,
+                limit_config.shift_millis,
+                limit_config.max_north,
+                clt_r_stats,
+            );
+            if let Some(user_ctx) = self.task_notes.user_ctx()
+                && let Some(limiter) = user_ctx.user().tcp_all_upload_speed_limit()
+            {
+                r.add_global_limiter(limiter.clone());
+            }
+            r
+        });
+
+        // the response may be modified by the audit service, so skip the cache
+        if !audit_task && let Some(cache) = self.ctx.http_cache.clone() {
+            let rewrite_user = http_header::request_rewrite_user(
+                &self.ctx.server_config.header_rewrite,
+                self.upstream.host(),
+                self.req.uri.path(),
+                self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            );
+            let key = crate::module::http_forward::http_cache_key(
+                self.is_https,
+                &self.upstream,
+                self.req,
+                rewrite_user,
+            );
+            match cache.lookup(key, self.req).await {
+                HttpCacheLookup::Bypass => {}
+                HttpCacheLookup::Miss(cache_task) => self.cache_task = Some(cache_task),
+                HttpCacheLookup::Hit(entry) => {
+                    return self.send_cached_response(responder, &entry).await;
+                }
+                HttpCacheLookup::Unavailable => {
+                    let rsp = HttpProxyClientResponse::from_standard(
+                        StatusCode::GATEWAY_TIMEOUT,
+                        self.req.version,
+                        true,
+                    );
+                    self.reply_error(responder, &rsp).await;
+                    return Ok(());
+                }
+            }
+        }
+
+        fwd_ctx.prepare_connection(&self.upstream, self.is_https);
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let mut connection = match self.make_new_connection(fwd_ctx).await {
+            Ok(mut connection) => {
+                self.task_notes.stage = ServerTaskStage::Connected;
+                fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+
+                if self.ctx.server_config.flush_task_log_on_connected
+                    && let Some(log_ctx) = self.get_log_context()
+                {
+                    log_ctx.log_connected();
+                }
+
+                connection.0.prepare_new(&self.task_notes, &self.upstream);
+                self.mark_relaying();
+                connection
+            }
+            Err(e) => {
+                fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+                let rsp = self.reply_connect_err(&e);
+                self.reply_error(responder, &rsp).await;
+                return Err(e.into());
+            }
+        };
+
+        let r = self
+            .run_with_connection(clt_r, responder, &mut connection, clt_w_stats, limit_config)
+            .await;
+        // the upstream connection won't be reused
+        let _ = connection.0.shutdown().await;
+        if let Err(e) = &r
+            && self.send_error_response
+        {
+            self.reply_task_err(e, responder).await;
+        }
+        r
+    }
+
+    async fn make_new_connection(
+        &self,
+        fwd_ctx: &mut BoxHttpForwardContext,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        if self.is_https {
+            let tls_name = self.req.host.as_ref().unwrap_or(&self.upstream).host();
+
+            let tls_client = self
+                .task_notes
+                .user_ctx()
+                .and_then(|ctx| ctx.user_site())
+                .and_then(|site| site.tls_client())
+                .unwrap_or(&self.ctx.tls_client_config);
+
+            let task_conf = TlsConnectTaskConf {
+                tcp: TcpConnectTaskConf {
+                    upstream: &self.upstream,
+                },
+                tls_config: tls_client,
+                tls_name,
+            };
+            fwd_ctx
+                .make_new_https_connection(&task_conf, &self.task_notes, self.task_stats.clone())
+                .await
+        } else {
+            let task_conf = TcpConnectTaskConf {
+                upstream: &self.upstream,
+            };
+            fwd_ctx
+                .make_new_http_connection(&task_conf, &self.task_notes, self.task_stats.clone())
+                .await
+        }
+    }
+
+    fn mark_relaying(&mut self) {
+        self.task_notes.mark_relaying();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| s.req_ready.add_http_forward(self.is_https));
+        }
+    }
+
+    fn rsp_hdr_recv_timeout(&self) -> Duration {
+        self.task_notes
+            .user_ctx()
+            .and_then(|ctx| ctx.http_rsp_header_recv_timeout())
+            .unwrap_or(self.ctx.server_config.timeout.recv_rsp_header)
+    }
+
+    async fn run_with_connection<CDR, S>(
+        &mut self,
+        clt_r: Option<CDR>,
+        responder: &mut S,
+        ups_c: &mut BoxHttpForwardConnection,
+        clt_w_stats: ArcLimitedWriterStats,
+        limit_config: TcpSockSpeedLimitConfig,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        S: HttpStreamResponder,
+    {
+        let ups_w = &mut ups_c.0;
+        let ups_r = &mut ups_c.1;
+
+        let revalidate_req = self
+            .cache_task
+            .as_ref()
+            .and_then(|t| t.revalidate_request(self.req));
+        ups_w
+            .send_request_header(revalidate_req.as_ref().unwrap_or(self.req), None)
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+        ups_w
+            .flush()
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+        self.http_notes.mark_req_send_hdr();
+
+        let rsp_header = match (clt_r, self.req.body_type()) {
+            (Some(clt_r), Some(body_type)) => {
+                self.send_request_body(clt_r, body_type, ups_w, ups_r)
+                    .await?
+            }
+            _ => {
+                self.http_notes.mark_req_no_body();
+                None
+            }
+        };
+        let mut rsp_header = match rsp_header {
+            Some(header) => header,
+            None => match tokio::time::timeout(
+                self.rsp_hdr_recv_timeout(),
+                self.recv_final_response_header(ups_r),
+            )
+            .await
+            {
+                Ok(Ok(rsp_header)) => rsp_header,
+                Ok(Err(e)) => return Err(e),
+                Err(_) => {
+                    return Err(ServerTaskError::UpstreamAppTimeout(
+                        "timeout to receive response header",
+                    ));
+                }
+            },
+        };
+        self.http_notes.mark_rsp_recv_hdr();
+
+        if let Some(cache_task) = self.cache_task.take() {
+            self.send_response_with_cache(
+                responder,
+                ups_r,
+                &mut rsp_header,
+                cache_task,
+                clt_w_stats,
+                limit_config,
+            )
+            .await?;
+        } else {
+            self.send_response(
+                responder,
+                ups_r,
+                &mut rsp_header,
+                None,
+                clt_w_stats,
+                limit_config,
+            )
+            .await?;
+        }
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
+    /// send the request body to upstream, the response header will be returned if received early
+    async fn send_request_body<CDR>(
+        &mut self,
+        mut clt_r: CDR,
+        body_type: HttpBodyType,
+        ups_w: &mut BoxHttpForwardWriter,
+        ups_r: &mut BoxHttpForwardReader,
+    ) -> ServerTaskResult<Option<HttpForwardRemoteResponse>>
+    where
+        CDR: AsyncRead + Send + Unpin,
+    {
+        let mut chunked_reader;
+        let mut clt_to_ups = match body_type {
+            HttpBodyType::Chunked => {
+                chunked_reader = BufReader::new(clt_r);
+                RequestBodyTransfer::Chunked(StreamToChunkedTransfer::new_with_no_trailer(
+                    &mut chunked_reader,
+                    ups_w,
+                    self.ctx.server_config.tcp_copy.yield_size(),
+                ))
+            }
+            _ => RequestBodyTransfer::ContentLength(StreamCopy::new(
+                &mut clt_r,
+                ups_w,
+                &self.ctx.server_config.tcp_copy,
+            )),
+        };
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = ups_r.fill_wait_data() => {
+                    match r {
+                        Ok(true) => {
+                            // the upstream may reply before all the body received
+                            let hdr = self.recv_response_header(ups_r).await?;
+                            if hdr.code >= 200 {
+                                return Ok(Some(hdr));
+                            }
+                            // the informational responses won't be forwarded
+                        }
+                        Ok(false) => return Err(ServerTaskError::ClosedByUpstream),
+                        Err(e) => return Err(ServerTaskError::UpstreamReadFailed(e)),
+                    }
+                }
+                r = &mut clt_to_ups => {
+                    r.map_err(|e| match e {
+                        StreamCopyError::ReadFailed(e) => ServerTaskError::ClientTcpReadFailed(e),
+                        StreamCopyError::WriteFailed(e) => ServerTaskError::UpstreamWriteFailed(e),
+                    })?;
+                    self.http_notes.mark_req_send_all();
+                    return Ok(None);
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if clt_to_ups.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return if clt_to_ups.no_cached_data() {
+                                Err(ServerTaskError::ClientAppTimeout("idle while reading request body"))
+                            } else {
+                                Err(ServerTaskError::UpstreamAppTimeout("idle while sending request body"))
+                            };
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        clt_to_ups.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+
+                    if clt_to_ups.finished() {
+                        // wait for the response header
+                        continue;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn recv_final_response_header(
+        &mut self,
+        ups_r: &mut BoxHttpForwardReader,
+    ) -> ServerTaskResult<HttpForwardRemoteResponse> {
+        loop {
+            let hdr = self.recv_response_header(ups_r).await?;
+            if hdr.code >= 200 {
+                return Ok(hdr);
+            }
+            // the informational responses won't be forwarded
+        }
+    }
+
+    async fn recv_response_header(
+        &mut self,
+        ups_r: &mut BoxHttpForwardReader,
+    ) -> ServerTaskResult<HttpForwardRemoteResponse> {
+        ups_r
+            .recv_response_header(
+                &self.req.method,
+                false,
+                self.ctx.server_config.rsp_hdr_max_size,
+                &mut self.http_notes,
+            )
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn send_response_with_cache<S>(
+        &mut self,
+        responder: &mut S,
+        ups_r: &mut BoxHttpForwardReader,
+        rsp_header: &mut HttpForwardRemoteResponse,
+        cache_task: HttpCacheTask,
+        clt_w_stats: ArcLimitedWriterStats,
+        limit_config: TcpSockSpeedLimitConfig,
+    ) -> ServerTaskResult<()>
+    where
+        S: HttpStreamResponder,
+    {
+        let cache_store = match cache_task.check_response(self.req, rsp_header) {
+            HttpCacheResponseAction::Refresh => {
+                if let Some(entry) = cache_task.refresh(rsp_header) {
+                    self.http_notes.origin_status = rsp_header.code;
+                    self.http_notes.mark_rsp_no_body();
+                    return self.send_cached_response(responder, &entry).await;
+                }
+                None
+            }
+            HttpCacheResponseAction::Store(size) => {
+                Some(cache_task.store(self.req, rsp_header, size))
+            }
+            HttpCacheResponseAction::Pass => None,
+        };
+
+        self.send_response(
+            responder,
+            ups_r,
+            rsp_header,
+            cache_store,
+            clt_w_stats,
+            limit_config,
+        )
+        .await
+    }
+
+    async fn send_cached_response<S>(
+        &mut self,
+        responder: &mut S,
+        entry: &HttpCacheEntry,
+    ) -> ServerTaskResult<()>
+    where
+        S: HttpStreamResponder,
+    {
+        let mut headers = entry.response_headers();
+        http_header::rewrite_response(
+            &self.ctx.server_config.header_rewrite,
+            self.upstream.host(),
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut headers,
+        );
+
+        let (code, body) = if entry.is_not_modified_for(&self.req.end_to_end_headers) {
+            (304, Bytes::new())
+        } else if self.req.method == Method::HEAD {
+            (entry.code(), Bytes::new())
+        } else {
+            (entry.code(), entry.body().clone())
+        };
+        let mut rsp = Response::new(());
+        *rsp.status_mut() = StatusCode::from_u16(code).unwrap_or(StatusCode::BAD_GATEWAY);
+        *rsp.headers_mut() = HeaderMap::from(&headers);
+
+        self.send_error_response = false;
+        responder
+            .send_full(rsp, body)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.http_notes.rsp_status = code;
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
+    async fn send_response<S>(
+        &mut self,
+        responder: &mut S,
+        ups_r: &mut BoxHttpForwardReader,
+        rsp_header: &mut HttpForwardRemoteResponse,
+        cache_store: Option<HttpCacheStore>,
+        clt_w_stats: ArcLimitedWriterStats,
+        limit_config: TcpSockSpeedLimitConfig,
+    ) -> ServerTaskResult<()>
+    where
+        S: HttpStreamResponder,
+    {
+        self.http_notes.origin_status = rsp_header.code;
+        self.http_notes.rsp_status = 0;
+        super::task::update_response_header(
+            &self.ctx,
+            &self.tcp_notes,
+            &self.upstream,
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            rsp_header,
+        );
+        let rsp = stream_response_head(rsp_header);
+
+        let Some(body_type) = rsp_header.body_type(&self.req.method) else {
+            if let Some(store) = cache_store {
+                store.finish();
+            }
+            self.send_error_response = false;
+            responder
+                .send_full(rsp, Bytes::new())
+                .await
+                .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+            self.http_notes.rsp_status = rsp_header.code;
+            self.http_notes.mark_rsp_no_body();
+            return Ok(());
+        };
+
+        let clt_w = responder
+            .send_header(rsp)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.send_error_response = false;
+        self.http_notes.rsp_status = rsp_header.code;
+
+        let mut clt_w = LimitedWriter::local_limited(
+            clt_w,
+            limit_config.shift_millis,
+            limit_config.max_south,
+            clt_w_stats,
+        );
+        if let Some(user_ctx) = self.task_notes.user_ctx()
+            && let Some(limiter) = user_ctx.user().tcp_all_download_speed_limit()
+        {
+            clt_w.add_global_limiter(limiter.clone());
+        }
+
+        // the body will be sent in the frames of the client stream
+        let mut body_reader =
+            HttpBodyDecodeReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        if let Some(store) = cache_store {
+            let mut cache_reader = HttpCacheBodyReader::new(body_reader, store);
+            self.relay_response_body(&mut clt_w, &mut cache_reader)
+                .await?;
+            cache_reader.finish();
+        } else {
+            self.relay_response_body(&mut clt_w, &mut body_reader)
+                .await?;
+        }
+        // the stream will be reset if dropped before shutdown
+        clt_w
+            .shutdown()
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)
+    }
+
+    async fn relay_response_body<R, W>(
+        &mut self,
+        clt_w: &mut W,
+        body_reader: &mut R,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut ups_to_clt = StreamCopy::new(body_reader, clt_w, &self.ctx.server_config.tcp_copy);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut ups_to_clt => {
+                    return match r {
+                        Ok(_) => {
+                            self.http_notes.mark_rsp_recv_all();
+                            Ok(())
+                        }
+                        Err(StreamCopyError::ReadFailed(e)) => Err(ServerTaskError::UpstreamReadFailed(e)),
+                        Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if ups_to_clt.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return if ups_to_clt.no_cached_data() {
+                                Err(ServerTaskError::UpstreamAppTimeout("idle while reading response body"))
+                            } else {
+                                Err(ServerTaskError::ClientAppTimeout("idle while sending response with body"))
+                            };
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        ups_to_clt.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// convert to the response head of a h2 / h3 stream, the connection specific headers are skipped
+fn stream_response_head(rsp: &HttpForwardRemoteResponse) -> Response<()> {
+    let mut headers = HeaderMap::from(&rsp.end_to_end_headers);
+    rsp.hop_by_hop_headers.for_each(|name, value| {
+        if matches!(
+            *name,
+            header::CONNECTION | header::UPGRADE | header::TRANSFER_ENCODING | header::TE
+        ) || name.as_str() == "keep-alive"
+            || name.as_str() == "proxy-connection"
+        {
+            return;
+        }
+        headers.append(name.clone(), value.inner().clone());
+    });
+
+    let mut response = Response::new(());
+    *response.status_mut() = StatusCode::from_u16(rsp.code).unwrap_or(StatusCode::BAD_GATEWAY);
+    *response.headers_mut() = headers;
+    response
+}
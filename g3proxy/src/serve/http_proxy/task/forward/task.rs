@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, StatusCode, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::HttpForwardRemoteResponse;
@@ -39,6 +39,7 @@ use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
+    HttpCacheBodyReader, HttpCacheEntry, HttpCacheLookup, HttpCacheResponseAction, HttpCacheTask,
     HttpForwardTaskNotes, HttpProxyClientResponse,
 };
 use crate::module::http_header;
@@ -63,6 +64,7 @@ pub(crate) struct HttpProxyForwardTask<'a> {
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
     max_idle_count: usize,
+    cache_task: Option<HttpCacheTask>,
     started: bool,
 }
 
@@ -111,6 +113,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
             max_idle_count,
+            cache_task: None,
             started: false,
         }
     }
@@ -156,6 +159,22 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.should_close = true;
     }
 
+    async fn reply_gateway_timeout<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let rsp = HttpProxyClientResponse::from_standard(
+            StatusCode::GATEWAY_TIMEOUT,
+            self.req.version,
+            self.should_close,
+        );
+        if rsp.reply_err_to_request(clt_w).await.is_ok() {
+            self.http_notes.rsp_status = rsp.status();
+        } else {
+            self.should_close = true;
+        }
+    }
+
     async fn reply_connect_err<W>(&mut self, e: &TcpConnectError, clt_w: &mut W)
     where
         W: AsyncWrite + Unpin,
@@ -286,6 +305,14 @@ impl<'a> HttpProxyForwardTask<'a> {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -628,6 +655,36 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
+        // the response may be modified by the audit service, so skip the cache
+        if !audit_task && let Some(cache) = self.ctx.http_cache.clone() {
+            let rewrite_user = http_header::request_rewrite_user(
+                &self.ctx.server_config.header_rewrite,
+                self.upstream.host(),
+                self.req.uri.path(),
+                self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            );
+            let key = crate::module::http_forward::http_cache_key(
+                self.is_https,
+                &self.upstream,
+                self.req,
+                rewrite_user,
+            );
+            match cache.lookup(key, self.req).await {
+                HttpCacheLookup::Bypass => {}
+                HttpCacheLookup::Miss(cache_task) => self.cache_task = Some(cache_task),
+                HttpCacheLookup::Hit(entry) => {
+                    self.send_cached_response(clt_w, &entry).await?;
+                    self.save_or_close(fwd_ctx, clt_w, None).await;
+                    return Ok(());
+                }
+                HttpCacheLookup::Unavailable => {
+                    self.reply_gateway_timeout(clt_w).await;
+                    self.save_or_close(fwd_ctx, clt_w, None).await;
+                    return Ok(());
+                }
+            }
+        }
+
         fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
@@ -1158,8 +1215,12 @@ impl<'a> HttpProxyForwardTask<'a> {
         let ups_r = &mut ups_c.1;
 
         self.http_notes.retry_new_connection = true;
+        let revalidate_req = self
+            .cache_task
+            .as_ref()
+            .and_then(|t| t.revalidate_request(self.req));
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(revalidate_req.as_ref().unwrap_or(self.req), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1199,13 +1260,122 @@ impl<'a> HttpProxyForwardTask<'a> {
         };
         self.http_notes.mark_rsp_recv_hdr();
 
-        self.send_response(clt_w, ups_r, &mut rsp_header, false, None)
-            .await?;
+        if let Some(cache_task) = self.cache_task.take() {
+            self.send_response_with_cache(clt_w, ups_r, &mut rsp_header, cache_task)
+                .await?;
+        } else {
+            self.send_response(clt_w, ups_r, &mut rsp_header, false, None)
+                .await?;
+        }
 
         self.task_notes.stage = ServerTaskStage::Finished;
         Ok(Some(ups_c))
     }
 
+    async fn send_response_with_cache<R, W>(
+        &mut self,
+        clt_w: &mut W,
+        ups_r: &mut R,
+        rsp_header: &mut HttpForwardRemoteResponse,
+        cache_task: HttpCacheTask,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncBufRead + Send + Unpin,
+        W: AsyncWrite + Send + Unpin,
+    {
+        match cache_task.check_response(self.req, rsp_header) {
+            HttpCacheResponseAction::Refresh => {
+                if let Some(entry) = cache_task.refresh(rsp_header) {
+                    if !rsp_header.keep_alive() {
+                        self.should_close = true;
+                    }
+                    self.http_notes.origin_status = rsp_header.code;
+                    self.http_notes.mark_rsp_no_body();
+                    return self.send_cached_response(clt_w, &entry).await;
+                }
+            }
+            HttpCacheResponseAction::Store(size) => {
+                let store = cache_task.store(self.req, rsp_header, size);
+
+                if self.should_close {
+                    rsp_header.set_no_keep_alive();
+                }
+                if !rsp_header.keep_alive() {
+                    self.should_close = true;
+                }
+                self.http_notes.origin_status = rsp_header.code;
+                self.update_response_header(rsp_header);
+                self.send_error_response = false;
+
+                let Some(body_type) = rsp_header.body_type(&self.req.method) else {
+                    store.finish();
+                    self.send_response_header(clt_w, rsp_header).await?;
+                    self.http_notes.rsp_status = rsp_header.code;
+                    self.http_notes.mark_rsp_no_body();
+                    return Ok(());
+                };
+                let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
+                rsp_header.serialize_to(&mut buf);
+                self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
+
+                let body_reader =
+                    HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+                let mut cache_reader = HttpCacheBodyReader::new(body_reader, store);
+                self.relay_response_body(buf, clt_w, &mut cache_reader)
+                    .await?;
+                cache_reader.finish();
+                return Ok(());
+            }
+            HttpCacheResponseAction::Pass => {}
+        }
+
+        self.send_response(clt_w, ups_r, rsp_header, false, None)
+            .await
+    }
+
+    async fn send_cached_response<W>(
+        &mut self,
+        clt_w: &mut W,
+        entry: &HttpCacheEntry,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut headers = entry.response_headers();
+        http_header::rewrite_response(
+            &self.ctx.server_config.header_rewrite,
+            self.upstream.host(),
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut headers,
+        );
+
+        let (code, reason) = if entry.is_not_modified_for(&self.req.end_to_end_headers) {
+            (304, "Not Modified")
+        } else {
+            (entry.code(), entry.reason())
+        };
+        let mut buf = crate::module::http_forward::serialize_response_header(
+            self.req.version,
+            code,
+            reason,
+            &headers,
+            !self.should_close,
+        );
+        if code != 304 && self.req.method != Method::HEAD {
+            buf.extend_from_slice(entry.body());
+        }
+
+        self.send_error_response = false;
+        clt_w
+            .write_all_flush(&buf)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.http_notes.rsp_status = code;
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
     async fn send_full_req_and_recv_rsp(
         &mut self,
         body: &[u8],
@@ -1416,6 +1586,10 @@ impl<'a> HttpProxyForwardTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -1664,16 +1838,25 @@ impl<'a> HttpProxyForwardTask<'a> {
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
-        let header_len = header.len() as u64;
         let mut body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        self.relay_response_body(header, clt_w, &mut body_reader)
+            .await
+    }
 
-        let mut ups_to_clt = StreamCopy::with_data(
-            &mut body_reader,
-            clt_w,
-            &self.ctx.server_config.tcp_copy,
-            header,
-        );
+    async fn relay_response_body<R, W>(
+        &mut self,
+        header: Vec<u8>,
+        clt_w: &mut W,
+        body_reader: &mut R,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let header_len = header.len() as u64;
+        let mut ups_to_clt =
+            StreamCopy::with_data(body_reader, clt_w, &self.ctx.server_config.tcp_copy, header);
 
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut log_interval = self.ctx.get_log_interval();
@@ -1739,6 +1922,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        if ups_to_clt.copied_size() < header_len {
+                            let _ = ups_to_clt.write_flush().await; // flush rsp header to client
+                        }
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         if ups_to_clt.copied_size() < header_len {
                             let _ = ups_to_clt.write_flush().await; // flush rsp header to client
@@ -1751,39 +1941,14 @@ impl<'a> HttpProxyForwardTask<'a> {
     }
 
     fn update_response_header(&self, rsp: &mut HttpForwardRemoteResponse) {
-        // append headers to hop-by-hop headers, so they will pass to client without adaptation
-        if let Some(server_id) = &self.ctx.server_config.server_id {
-            if self.ctx.server_config.http_forward_mark_upstream {
-                http_header::set_upstream_id(&mut rsp.hop_by_hop_headers, server_id);
-            }
-
-            http_header::set_remote_connection_info(
-                &mut rsp.hop_by_hop_headers,
-                server_id,
-                self.tcp_notes.bind.ip(),
-                self.tcp_notes.local,
-                self.tcp_notes.next,
-                &self.tcp_notes.expire,
-            );
-
-            if let Some(egress_info) = &self.tcp_notes.egress {
-                http_header::set_dynamic_egress_info(
-                    &mut rsp.hop_by_hop_headers,
-                    server_id,
-                    egress_info,
-                );
-            }
-        }
-
-        if self.ctx.server_config.echo_chained_info {
-            if let Some(addr) = self.tcp_notes.chained.target_addr {
-                http_header::set_upstream_addr(&mut rsp.hop_by_hop_headers, addr);
-            }
-
-            if let Some(addr) = self.tcp_notes.chained.outgoing_addr {
-                http_header::set_outgoing_ip(&mut rsp.hop_by_hop_headers, addr);
-            }
-        }
+        update_response_header(
+            &self.ctx,
+            &self.tcp_notes,
+            &self.upstream,
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            rsp,
+        );
     }
 
     async fn send_response_header<W>(
@@ -1801,3 +1966,55 @@ impl<'a> HttpProxyForwardTask<'a> {
             .map_err(ServerTaskError::ClientTcpWriteFailed)
     }
 }
+
+/// update the response header from upstream before sending to the client
+pub(super) fn update_response_header(
+    ctx: &CommonTaskContext,
+    tcp_notes: &TcpConnectTaskNotes,
+    upstream: &UpstreamAddr,
+    path: &str,
+    username: Option<&str>,
+    rsp: &mut HttpForwardRemoteResponse,
+) {
+    // append headers to hop-by-hop headers, so they will pass to client without adaptation
+    if let Some(server_id) = &ctx.server_config.server_id {
+        if ctx.server_config.http_forward_mark_upstream {
+            http_header::set_upstream_id(&mut rsp.hop_by_hop_headers, server_id);
+        }
+
+        http_header::set_remote_connection_info(
+            &mut rsp.hop_by_hop_headers,
+            server_id,
+            tcp_notes.bind.ip(),
+            tcp_notes.local,
+            tcp_notes.next,
+            &tcp_notes.expire,
+        );
+
+        if let Some(egress_info) = &tcp_notes.egress {
+            http_header::set_dynamic_egress_info(
+                &mut rsp.hop_by_hop_headers,
+                server_id,
+                egress_info,
+            );
+        }
+    }
+
+    if ctx.server_config.echo_chained_info {
+        if let Some(addr) = tcp_notes.chained.target_addr {
+            http_header::set_upstream_addr(&mut rsp.hop_by_hop_headers, addr);
+        }
+
+        if let Some(addr) = tcp_notes.chained.outgoing_addr {
+            http_header::set_outgoing_ip(&mut rsp.hop_by_hop_headers, addr);
+        }
+    }
+
+    http_header::rewrite_response(
+        &ctx.server_config.header_rewrite,
+        upstream.host(),
+        path,
+        username,
+        &mut rsp.end_to_end_headers,
+    );
+}
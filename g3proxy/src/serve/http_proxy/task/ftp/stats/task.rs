@@ -6,6 +6,7 @@
 use g3_daemon::stat::task::{TcpStreamConnectionStats, TcpStreamHalfConnectionStats};
 
 use crate::module::ftp_over_http::{FtpTaskRemoteControlStats, FtpTaskRemoteTransferStats};
+use crate::serve::LiveTaskTraffic;
 
 #[derive(Default)]
 pub(crate) struct FtpOverHttpServerStats {
@@ -40,3 +41,13 @@ impl FtpTaskRemoteTransferStats for FtpOverHttpTaskStats {
         self.ftp_server.transfer_write.add_bytes(size);
     }
 }
+
+impl LiveTaskTraffic for FtpOverHttpTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.http_client.read.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.http_client.write.get_bytes()
+    }
+}
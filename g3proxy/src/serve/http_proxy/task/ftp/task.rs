@@ -154,6 +154,14 @@ impl<'a> FtpOverHttpTask<'a> {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(self.ftp_notes.upstream()),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -1300,6 +1308,10 @@ impl<'a> FtpOverHttpTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -1498,6 +1510,10 @@ impl<'a> FtpOverHttpTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
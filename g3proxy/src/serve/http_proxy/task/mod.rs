@@ -14,13 +14,20 @@ mod protocol;
 mod connect;
 mod forward;
 mod ftp;
+mod multiplex;
 mod pipeline;
+mod udp_connect;
 mod untrusted;
 
 use connect::HttpProxyConnectTask;
-use forward::HttpProxyForwardTask;
+use forward::{HttpProxyForwardTask, HttpProxyStreamForwardTask};
 use ftp::FtpOverHttpTask;
+#[cfg(feature = "quic")]
+pub(super) use multiplex::HttpProxyH3ConnectionTask;
+pub(super) use multiplex::{H2_PREFACE, HttpProxyH2ConnectionTask, HttpProxyStreamContext};
+use multiplex::{HttpStreamResponder, StreamResetReason, is_malformed_body_error};
 pub(super) use pipeline::{
     HttpProxyPipelineReaderTask, HttpProxyPipelineStats, HttpProxyPipelineWriterTask,
 };
+use udp_connect::HttpProxyUdpConnectTask;
 use untrusted::HttpProxyUntrustedTask;
@@ -0,0 +1,129 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Limit the request body of a h2 / h3 stream to the size in the content-length header.
+///
+/// A longer body will be returned as an `InvalidData` error, and a shorter body will be
+/// returned as an `UnexpectedEof` error, the stream should be reset in both cases.
+pub(crate) struct StreamBodyReader<R> {
+    inner: R,
+    content_length: Option<u64>,
+    received: u64,
+}
+
+impl<R> StreamBodyReader<R> {
+    pub(crate) fn new(inner: R, content_length: Option<u64>) -> Self {
+        StreamBodyReader {
+            inner,
+            content_length,
+            received: 0,
+        }
+    }
+}
+
+pub(crate) fn is_malformed_body_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+    )
+}
+
+impl<R> AsyncRead for StreamBodyReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        let Some(content_length) = self.content_length else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let nr = buf.filled().len() - filled;
+        if nr == 0 {
+            return if self.received < content_length {
+                Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "request body is shorter than the content-length",
+                )))
+            } else {
+                Poll::Ready(Ok(()))
+            };
+        }
+
+        self.received += nr as u64;
+        if self.received > content_length {
+            buf.set_filled(filled);
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request body is longer than the content-length",
+            )));
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn exact_length() {
+        let stream = tokio_test::io::Builder::new()
+            .read(b"abc")
+            .read(b"de")
+            .build();
+        let mut reader = StreamBodyReader::new(stream, Some(5));
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), b"abcde");
+    }
+
+    #[tokio::test]
+    async fn too_long() {
+        let stream = tokio_test::io::Builder::new()
+            .read(b"abc")
+            .read(b"def")
+            .build();
+        let mut reader = StreamBodyReader::new(stream, Some(5));
+        let mut buf = Vec::new();
+        let e = reader.read_to_end(&mut buf).await.unwrap_err();
+        assert!(is_malformed_body_error(&e));
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(buf.as_slice(), b"abc");
+    }
+
+    #[tokio::test]
+    async fn too_short() {
+        let stream = tokio_test::io::Builder::new().read(b"abc").build();
+        let mut reader = StreamBodyReader::new(stream, Some(5));
+        let mut buf = Vec::new();
+        let e = reader.read_to_end(&mut buf).await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn no_length() {
+        let stream = tokio_test::io::Builder::new()
+            .read(b"abc")
+            .read(b"def")
+            .build();
+        let mut reader = StreamBodyReader::new(stream, None);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), b"abcdef");
+    }
+}
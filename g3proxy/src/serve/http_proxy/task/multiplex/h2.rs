@@ -0,0 +1,282 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use h2::server::{Connection, SendResponse};
+use h2::{Reason, SendStream};
+use http::{Response, Version};
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_h2::{H2StreamReader, H2StreamWriter};
+
+use super::{
+    HttpProxyStreamContext, HttpStreamResponder, StreamConcurrencyStats, StreamResetReason,
+};
+use crate::serve::ServerStats;
+
+/// the connection preface sent by h2 clients with prior knowledge
+pub(crate) const H2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+fn h2_to_io_error(e: h2::Error) -> io::Error {
+    if e.is_io() {
+        e.into_io().unwrap()
+    } else {
+        io::Error::other(e)
+    }
+}
+
+/// the response body writer, the stream will be reset if dropped before shutdown
+struct H2ResponseBodyWriter {
+    inner: H2StreamWriter,
+    finished: bool,
+}
+
+impl H2ResponseBodyWriter {
+    fn new(send_stream: SendStream<Bytes>) -> Self {
+        H2ResponseBodyWriter {
+            inner: H2StreamWriter::new(send_stream),
+            finished: false,
+        }
+    }
+}
+
+impl Drop for H2ResponseBodyWriter {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.inner.send_reset(Reason::INTERNAL_ERROR);
+        }
+    }
+}
+
+impl AsyncWrite for H2ResponseBodyWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(Pin::new(&mut self.inner).poll_shutdown(cx))?;
+        self.finished = true;
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct H2StreamResponder {
+    send_rsp: SendResponse<Bytes>,
+}
+
+impl H2StreamResponder {
+    fn send_response(
+        &mut self,
+        mut rsp: Response<()>,
+        end_of_stream: bool,
+    ) -> io::Result<SendStream<Bytes>> {
+        *rsp.version_mut() = Version::HTTP_2;
+        self.send_rsp
+            .send_response(rsp, end_of_stream)
+            .map_err(h2_to_io_error)
+    }
+}
+
+#[async_trait]
+impl HttpStreamResponder for H2StreamResponder {
+    type BodyWriter = H2ResponseBodyWriter;
+
+    async fn send_header(&mut self, rsp: Response<()>) -> io::Result<Self::BodyWriter> {
+        let send_stream = self.send_response(rsp, false)?;
+        Ok(H2ResponseBodyWriter::new(send_stream))
+    }
+
+    async fn send_full(&mut self, rsp: Response<()>, body: Bytes) -> io::Result<()> {
+        if body.is_empty() {
+            self.send_response(rsp, true)?;
+            return Ok(());
+        }
+
+        let send_stream = self.send_response(rsp, false)?;
+        let mut writer = H2ResponseBodyWriter::new(send_stream);
+        writer.write_all(&body).await?;
+        writer.shutdown().await
+    }
+
+    fn reset(&mut self, reason: StreamResetReason) {
+        let reason = match reason {
+            StreamResetReason::InternalError => Reason::INTERNAL_ERROR,
+            StreamResetReason::MalformedMessage => Reason::PROTOCOL_ERROR,
+            StreamResetReason::RequestRejected => Reason::REFUSED_STREAM,
+        };
+        self.send_rsp.send_reset(reason);
+    }
+}
+
+pub(crate) struct HttpProxyH2ConnectionTask {
+    stream_ctx: Arc<HttpProxyStreamContext>,
+    stats: Arc<StreamConcurrencyStats>,
+}
+
+impl HttpProxyH2ConnectionTask {
+    pub(crate) fn new(stream_ctx: Arc<HttpProxyStreamContext>) -> Self {
+        HttpProxyH2ConnectionTask {
+            stream_ctx,
+            stats: Arc::new(StreamConcurrencyStats::default()),
+        }
+    }
+
+    pub(crate) async fn run<IO>(self, io: IO)
+    where
+        IO: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let ctx = self.stream_ctx.ctx().clone();
+        let multiplex_config = &ctx.server_config.multiplex;
+
+        let mut builder = h2::server::Builder::new();
+        builder
+            .max_header_list_size(multiplex_config.max_header_list_size)
+            .max_concurrent_streams(multiplex_config.max_concurrent_streams)
+            .initial_window_size(multiplex_config.stream_window_size())
+            .initial_connection_window_size(multiplex_config.connection_window_size())
+            .max_frame_size(multiplex_config.max_frame_size())
+            .max_send_buffer_size(multiplex_config.max_send_buffer_size);
+        if ctx.server_config.enable_connect_udp {
+            builder.enable_connect_protocol();
+        }
+
+        let mut connection = match tokio::time::timeout(
+            multiplex_config.handshake_timeout,
+            builder.handshake::<_, Bytes>(io),
+        )
+        .await
+        {
+            Ok(Ok(c)) => c,
+            Ok(Err(e)) => {
+                debug!(
+                    "{} - {} h2 handshake error: {e}",
+                    ctx.cc_info.sock_local_addr(),
+                    ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+            Err(_) => {
+                debug!(
+                    "{} - {} h2 handshake timeout",
+                    ctx.cc_info.sock_local_addr(),
+                    ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+        };
+
+        let mut idle_interval = ctx.idle_wheel.register();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = connection.accept() => {
+                    match r {
+                        Some(Ok((req, send_rsp))) => {
+                            idle_count = 0;
+                            self.spawn_stream(req, send_rsp);
+                        }
+                        Some(Err(e)) => {
+                            debug!(
+                                "{} - {} h2 connection error: {e}",
+                                ctx.cc_info.sock_local_addr(),
+                                ctx.cc_info.sock_peer_addr()
+                            );
+                            return;
+                        }
+                        None => {
+                            let _ = poll_fn(|cx| connection.poll_closed(cx)).await;
+                            return;
+                        }
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if self.stats.get_alive_task() <= 0 {
+                        idle_count += n;
+
+                        if idle_count > ctx.server_config.task_idle_max_count {
+                            connection.abrupt_shutdown(Reason::NO_ERROR);
+                            refuse_until_closed(connection).await;
+                            return;
+                        }
+                    } else {
+                        idle_count = 0;
+                    }
+
+                    if ctx.server_quit_policy.force_quit() {
+                        // the running streams will quit by themselves
+                        connection.graceful_shutdown();
+                        refuse_until_closed(connection).await;
+                        return;
+                    }
+
+                    if !ctx.server_stats.is_online() {
+                        connection.graceful_shutdown();
+                    }
+                }
+            }
+        }
+    }
+
+    fn spawn_stream(&self, req: http::Request<h2::RecvStream>, send_rsp: SendResponse<Bytes>) {
+        let time_accepted = Instant::now();
+        let stream_ctx = self.stream_ctx.clone();
+        let stats = self.stats.clone();
+        stats.add_task();
+        tokio::spawn(async move {
+            let (parts, recv_stream) = req.into_parts();
+            let protocol = parts
+                .extensions
+                .get::<h2::ext::Protocol>()
+                .map(|p| p.as_str().to_string());
+            let has_body = !recv_stream.is_end_stream();
+            stream_ctx
+                .serve_stream(
+                    parts,
+                    protocol.as_deref(),
+                    has_body,
+                    H2StreamReader::new(recv_stream),
+                    H2StreamResponder { send_rsp },
+                    time_accepted,
+                )
+                .await;
+            stats.del_task();
+        });
+    }
+}
+
+async fn refuse_until_closed<T>(mut connection: Connection<T, Bytes>)
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    while let Some(r) = connection.accept().await {
+        match r {
+            Ok((_req, mut send_rsp)) => {
+                send_rsp.send_reset(Reason::REFUSED_STREAM);
+            }
+            Err(_) => return,
+        }
+    }
+
+    let _ = poll_fn(|cx| connection.poll_closed(cx)).await;
+}
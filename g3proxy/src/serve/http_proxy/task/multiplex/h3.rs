@@ -0,0 +1,448 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use h3::error::{Code, StreamError};
+use h3::server::{RequestResolver, RequestStream};
+use http::{Method, Response, header};
+use log::debug;
+use quinn::Connection;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::time::Instant;
+
+use super::{
+    HttpProxyStreamContext, HttpStreamResponder, StreamConcurrencyStats, StreamResetReason,
+};
+use crate::serve::ServerStats;
+
+type H3SendStream = RequestStream<h3_quinn::SendStream<Bytes>, Bytes>;
+
+struct H3StreamReader {
+    recv_stream: RequestStream<h3_quinn::RecvStream, Bytes>,
+    received_bytes: Option<Bytes>,
+}
+
+impl AsyncRead for H3StreamReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            if let Some(mut b) = self.received_bytes.take() {
+                let to_put = buf.remaining().min(b.len());
+                let split = b.split_to(to_put);
+                buf.put_slice(&split);
+                if !b.is_empty() {
+                    self.received_bytes = Some(b);
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(self.recv_stream.poll_recv_data(cx)) {
+                Ok(Some(mut data)) => {
+                    let b = data.copy_to_bytes(data.remaining());
+                    if !b.is_empty() {
+                        self.received_bytes = Some(b);
+                    }
+                }
+                Ok(None) => return Poll::Ready(Ok(())),
+                Err(e) => return Poll::Ready(Err(io::Error::other(e))),
+            }
+        }
+    }
+}
+
+impl H3StreamReader {
+    /// read the first non-empty data frame, return false if the stream has been finished
+    async fn peek_data(&mut self) -> io::Result<bool> {
+        loop {
+            match self.recv_stream.recv_data().await {
+                Ok(Some(mut data)) => {
+                    let b = data.copy_to_bytes(data.remaining());
+                    if !b.is_empty() {
+                        self.received_bytes = Some(b);
+                        return Ok(true);
+                    }
+                }
+                Ok(None) => return Ok(false),
+                Err(e) => return Err(io::Error::other(e)),
+            }
+        }
+    }
+}
+
+/// The send side of the request stream.
+///
+/// Dropping a quic send stream will finish it, so the stream should be stopped explicitly if the
+/// response is not completed, or the client may take a truncated body as a complete one.
+struct H3SendGuard {
+    stream: H3SendStream,
+    finished: bool,
+}
+
+impl H3SendGuard {
+    fn new(stream: H3SendStream) -> Self {
+        H3SendGuard {
+            stream,
+            finished: false,
+        }
+    }
+
+    fn stop(&mut self, code: Code) {
+        self.stream.stop_stream(code);
+        self.finished = true;
+    }
+}
+
+impl Drop for H3SendGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.stream.stop_stream(Code::H3_INTERNAL_ERROR);
+        }
+    }
+}
+
+type H3SendFuture =
+    Pin<Box<dyn Future<Output = (H3SendGuard, Result<(), StreamError>)> + Send + Sync>>;
+
+enum H3WriteState {
+    Idle(H3SendGuard),
+    Sending(H3SendFuture),
+    Closed,
+}
+
+/// the response body writer, each write will be sent as a data frame
+struct H3ResponseBodyWriter {
+    state: H3WriteState,
+}
+
+impl H3ResponseBodyWriter {
+    fn new(guard: H3SendGuard) -> Self {
+        H3ResponseBodyWriter {
+            state: H3WriteState::Idle(guard),
+        }
+    }
+
+    /// wait for the pending data frame to be sent
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.state {
+            H3WriteState::Idle(_) => Poll::Ready(Ok(())),
+            H3WriteState::Sending(fut) => {
+                let (guard, r) = ready!(fut.as_mut().poll(cx));
+                match r {
+                    Ok(_) => {
+                        self.state = H3WriteState::Idle(guard);
+                        Poll::Ready(Ok(()))
+                    }
+                    Err(e) => {
+                        self.state = H3WriteState::Closed;
+                        Poll::Ready(Err(io::Error::other(e)))
+                    }
+                }
+            }
+            H3WriteState::Closed => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "h3 response stream has been closed",
+            ))),
+        }
+    }
+}
+
+impl AsyncWrite for H3ResponseBodyWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        ready!(self.poll_idle(cx))?;
+        let H3WriteState::Idle(mut guard) =
+            std::mem::replace(&mut self.state, H3WriteState::Closed)
+        else {
+            unreachable!()
+        };
+        let data = Bytes::copy_from_slice(buf);
+        self.state = H3WriteState::Sending(Box::pin(async move {
+            let r = guard.stream.send_data(data).await;
+            (guard, r)
+        }));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_idle(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_idle(cx))?;
+        let H3WriteState::Idle(mut guard) =
+            std::mem::replace(&mut self.state, H3WriteState::Closed)
+        else {
+            unreachable!()
+        };
+        self.state = H3WriteState::Sending(Box::pin(async move {
+            let r = guard.stream.finish().await;
+            guard.finished = r.is_ok();
+            (guard, r)
+        }));
+        ready!(self.poll_idle(cx))?;
+        self.state = H3WriteState::Closed;
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct H3StreamResponder {
+    send_stream: Option<H3SendGuard>,
+}
+
+impl H3StreamResponder {
+    fn take_stream(&mut self) -> io::Result<H3SendGuard> {
+        self.send_stream
+            .take()
+            .ok_or_else(|| io::Error::other("h3 response has already been sent"))
+    }
+}
+
+#[async_trait]
+impl HttpStreamResponder for H3StreamResponder {
+    type BodyWriter = H3ResponseBodyWriter;
+
+    async fn send_header(&mut self, rsp: Response<()>) -> io::Result<Self::BodyWriter> {
+        let mut guard = self.take_stream()?;
+        guard
+            .stream
+            .send_response(rsp)
+            .await
+            .map_err(io::Error::other)?;
+        Ok(H3ResponseBodyWriter::new(guard))
+    }
+
+    async fn send_full(&mut self, rsp: Response<()>, body: Bytes) -> io::Result<()> {
+        let mut guard = self.take_stream()?;
+        guard
+            .stream
+            .send_response(rsp)
+            .await
+            .map_err(io::Error::other)?;
+        if !body.is_empty() {
+            guard
+                .stream
+                .send_data(body)
+                .await
+                .map_err(io::Error::other)?;
+        }
+        guard.stream.finish().await.map_err(io::Error::other)?;
+        guard.finished = true;
+        Ok(())
+    }
+
+    fn reset(&mut self, reason: StreamResetReason) {
+        if let Some(mut guard) = self.send_stream.take() {
+            let code = match reason {
+                StreamResetReason::InternalError => Code::H3_INTERNAL_ERROR,
+                StreamResetReason::MalformedMessage => Code::H3_MESSAGE_ERROR,
+                StreamResetReason::RequestRejected => Code::H3_REQUEST_REJECTED,
+            };
+            guard.stop(code);
+        }
+    }
+}
+
+/// the end of stream is only known after reading in h3, so check the content-length header first,
+/// and then try to read the first data frame, which won't block if the client has finished the stream
+async fn request_has_body(
+    method: &Method,
+    headers: &http::HeaderMap,
+    clt_r: &mut H3StreamReader,
+) -> io::Result<bool> {
+    if *method == Method::CONNECT {
+        // the tunnel data will be sent after the response
+        return Ok(true);
+    }
+    if let Some(v) = headers.get(header::CONTENT_LENGTH) {
+        return Ok(v.as_bytes() != b"0");
+    }
+    clt_r.peek_data().await
+}
+
+type H3RequestResolver = RequestResolver<h3_quinn::Connection, Bytes>;
+
+pub(crate) struct HttpProxyH3ConnectionTask {
+    stream_ctx: Arc<HttpProxyStreamContext>,
+    stats: Arc<StreamConcurrencyStats>,
+}
+
+impl HttpProxyH3ConnectionTask {
+    pub(crate) fn new(stream_ctx: Arc<HttpProxyStreamContext>) -> Self {
+        HttpProxyH3ConnectionTask {
+            stream_ctx,
+            stats: Arc::new(StreamConcurrencyStats::default()),
+        }
+    }
+
+    pub(crate) async fn run(self, connection: Connection) {
+        let ctx = self.stream_ctx.ctx().clone();
+        let multiplex_config = &ctx.server_config.multiplex;
+
+        let mut h3_conn = match tokio::time::timeout(
+            multiplex_config.handshake_timeout,
+            h3::server::builder()
+                .enable_extended_connect(ctx.server_config.enable_connect_udp)
+                .max_field_section_size(multiplex_config.max_header_list_size as u64)
+                .build::<_, Bytes>(h3_quinn::Connection::new(connection)),
+        )
+        .await
+        {
+            Ok(Ok(c)) => c,
+            Ok(Err(e)) => {
+                debug!(
+                    "{} - {} h3 handshake error: {e}",
+                    ctx.cc_info.sock_local_addr(),
+                    ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+            Err(_) => {
+                debug!(
+                    "{} - {} h3 handshake timeout",
+                    ctx.cc_info.sock_local_addr(),
+                    ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+        };
+
+        let mut idle_interval = ctx.idle_wheel.register();
+        let mut idle_count = 0;
+        let mut closing = false;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = h3_conn.accept() => {
+                    match r {
+                        Ok(Some(resolver)) => {
+                            idle_count = 0;
+                            let reject = self.stats.get_alive_task()
+                                >= multiplex_config.max_concurrent_streams as i32;
+                            self.spawn_stream(resolver, reject);
+                        }
+                        Ok(None) => return,
+                        Err(e) => {
+                            debug!(
+                                "{} - {} h3 connection error: {e}",
+                                ctx.cc_info.sock_local_addr(),
+                                ctx.cc_info.sock_peer_addr()
+                            );
+                            return;
+                        }
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if self.stats.get_alive_task() <= 0 {
+                        idle_count += n;
+
+                        if idle_count > ctx.server_config.task_idle_max_count {
+                            let _ = h3_conn.shutdown(0).await;
+                            return;
+                        }
+                    } else {
+                        idle_count = 0;
+                    }
+
+                    if !closing
+                        && (ctx.server_quit_policy.force_quit() || !ctx.server_stats.is_online())
+                    {
+                        // the connection will be closed after all running streams quit,
+                        // which will quit by themselves if force quit
+                        closing = true;
+                        if h3_conn.shutdown(0).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn spawn_stream(&self, resolver: H3RequestResolver, reject: bool) {
+        let time_accepted = Instant::now();
+        let stream_ctx = self.stream_ctx.clone();
+        let stats = self.stats.clone();
+        stats.add_task();
+        tokio::spawn(async move {
+            serve_stream(&stream_ctx, resolver, reject, time_accepted).await;
+            stats.del_task();
+        });
+    }
+}
+
+async fn serve_stream(
+    stream_ctx: &HttpProxyStreamContext,
+    resolver: H3RequestResolver,
+    reject: bool,
+    time_accepted: Instant,
+) {
+    let ctx = stream_ctx.ctx();
+    let (req, stream) = match resolver.resolve_request().await {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(
+                "{} - {} h3 request error: {e}",
+                ctx.cc_info.sock_local_addr(),
+                ctx.cc_info.sock_peer_addr()
+            );
+            return;
+        }
+    };
+    let (send_stream, recv_stream) = stream.split();
+    let mut responder = H3StreamResponder {
+        send_stream: Some(H3SendGuard::new(send_stream)),
+    };
+    let mut clt_r = H3StreamReader {
+        recv_stream,
+        received_bytes: None,
+    };
+    if reject {
+        clt_r.recv_stream.stop_sending(Code::H3_REQUEST_REJECTED);
+        responder.reset(StreamResetReason::RequestRejected);
+        return;
+    }
+
+    let (parts, _) = req.into_parts();
+    let protocol = parts
+        .extensions
+        .get::<h3::ext::Protocol>()
+        .map(|p| p.as_str().to_string());
+    let has_body = match request_has_body(&parts.method, &parts.headers, &mut clt_r).await {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(
+                "{} - {} h3 request body error: {e}",
+                ctx.cc_info.sock_local_addr(),
+                ctx.cc_info.sock_peer_addr()
+            );
+            responder.reset(StreamResetReason::MalformedMessage);
+            return;
+        }
+    };
+    stream_ctx
+        .serve_stream(
+            parts,
+            protocol.as_deref(),
+            has_body,
+            clt_r,
+            responder,
+            time_accepted,
+        )
+        .await;
+}
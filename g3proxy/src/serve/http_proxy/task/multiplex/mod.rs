@@ -0,0 +1,29 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use super::{
+    CommonTaskContext, HttpProxyConnectTask, HttpProxyStreamForwardTask, HttpProxyUdpConnectTask,
+    protocol,
+};
+
+mod body;
+pub(crate) use body::{StreamBodyReader, is_malformed_body_error};
+
+mod responder;
+pub(crate) use responder::{HttpStreamResponder, StreamResetReason};
+
+mod stats;
+use stats::StreamConcurrencyStats;
+
+mod stream;
+pub(crate) use stream::HttpProxyStreamContext;
+
+mod h2;
+pub(crate) use h2::{H2_PREFACE, HttpProxyH2ConnectionTask};
+
+#[cfg(feature = "quic")]
+mod h3;
+#[cfg(feature = "quic")]
+pub(crate) use h3::HttpProxyH3ConnectionTask;
@@ -0,0 +1,48 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::Response;
+use tokio::io::AsyncWrite;
+
+use crate::module::http_forward::HttpProxyClientResponse;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StreamResetReason {
+    /// INTERNAL_ERROR in h2 and H3_INTERNAL_ERROR in h3
+    InternalError,
+    /// PROTOCOL_ERROR in h2 and H3_MESSAGE_ERROR in h3
+    MalformedMessage,
+    /// REFUSED_STREAM in h2 and H3_REQUEST_REJECTED in h3
+    RequestRejected,
+}
+
+/// the response side of a h2 / h3 client stream
+#[async_trait]
+pub(crate) trait HttpStreamResponder: Send {
+    /// The body writer of the response.
+    ///
+    /// The body is complete only after a successful shutdown, and the stream will be reset if the
+    /// writer is dropped before that, so the client won't take a truncated body as a complete one.
+    type BodyWriter: AsyncWrite + Send + Sync + Unpin + 'static;
+
+    /// send the response header, the body should be sent by the returned writer
+    async fn send_header(&mut self, rsp: Response<()>) -> io::Result<Self::BodyWriter>;
+
+    /// send the response with all the body data and then finish the stream
+    async fn send_full(&mut self, rsp: Response<()>, body: Bytes) -> io::Result<()>;
+
+    /// reset the stream before any response header is sent
+    fn reset(&mut self, reason: StreamResetReason);
+
+    /// send the local error response
+    async fn send_error(&mut self, rsp: &HttpProxyClientResponse) -> io::Result<()> {
+        let (rsp, body) = rsp.to_stream_error_response();
+        self.send_full(rsp, body).await
+    }
+}
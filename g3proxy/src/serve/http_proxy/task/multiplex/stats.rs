@@ -0,0 +1,25 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+#[derive(Default)]
+pub(super) struct StreamConcurrencyStats {
+    alive_task: AtomicI32,
+}
+
+impl StreamConcurrencyStats {
+    pub(super) fn add_task(&self) {
+        self.alive_task.fetch_add(1, Ordering::Release);
+    }
+
+    pub(super) fn del_task(&self) {
+        self.alive_task.fetch_sub(1, Ordering::Release);
+    }
+
+    pub(super) fn get_alive_task(&self) -> i32 {
+        self.alive_task.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,331 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ahash::AHashMap;
+use arcstr::ArcStr;
+use http::request;
+use tokio::io::{AsyncRead, BufReader};
+use tokio::time::Instant;
+
+use g3_http::HttpBodyType;
+use g3_http::server::HttpRequestParseError;
+use g3_types::auth::UserAuthError;
+use g3_types::net::HttpProxySubProtocol;
+
+use super::protocol::HttpProxyStreamRequest;
+use super::{
+    CommonTaskContext, HttpProxyConnectTask, HttpProxyStreamForwardTask, HttpProxyUdpConnectTask,
+    HttpStreamResponder, StreamBodyReader, StreamResetReason,
+};
+use crate::audit::AuditContext;
+use crate::auth::{UserContext, UserGroup, UserRequestStats};
+use crate::config::server::ServerConfig;
+use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::http_header;
+use crate::serve::{ServerStats, ServerTaskNotes};
+
+struct PassedUser {
+    req_stats: Arc<UserRequestStats>,
+    site_req_stats: Option<Arc<UserRequestStats>>,
+}
+
+impl Drop for PassedUser {
+    fn drop(&mut self) {
+        self.req_stats.l7_conn_alive.dec_http();
+        if let Some(site_req_stats) = &self.site_req_stats {
+            site_req_stats.l7_conn_alive.dec_http();
+        }
+    }
+}
+
+/// Serve the request streams of a h2 / h3 client connection.
+///
+/// Each stream is served by the tasks of the http proxy server directly, with the same rules
+/// as the HTTP/1.1 requests. The user auth is done for each stream, and the user connection
+/// stats are counted once for each connection.
+pub(crate) struct HttpProxyStreamContext {
+    ctx: Arc<CommonTaskContext>,
+    audit_ctx: AuditContext,
+    user_group: Option<Arc<UserGroup>>,
+    passed_users: Mutex<AHashMap<ArcStr, PassedUser>>,
+}
+
+impl HttpProxyStreamContext {
+    pub(crate) fn new(
+        ctx: Arc<CommonTaskContext>,
+        audit_ctx: AuditContext,
+        user_group: Option<Arc<UserGroup>>,
+    ) -> Self {
+        HttpProxyStreamContext {
+            ctx,
+            audit_ctx,
+            user_group,
+            passed_users: Mutex::new(AHashMap::new()),
+        }
+    }
+
+    pub(super) fn ctx(&self) -> &Arc<CommonTaskContext> {
+        &self.ctx
+    }
+
+    fn allow_negotiate(&self) -> bool {
+        self.user_group
+            .as_ref()
+            .is_some_and(|g| g.allow_negotiate())
+    }
+
+    async fn do_auth(
+        &self,
+        req: &HttpProxyStreamRequest,
+    ) -> Result<Option<UserContext>, UserAuthError> {
+        let Some(user_group) = &self.user_group else {
+            return Ok(None);
+        };
+
+        let mut user_ctx = self
+            .ctx
+            .check_user(user_group, &req.inner.auth_info, &req.upstream)
+            .await?;
+        user_ctx.check_client_addr(self.ctx.client_addr())?;
+
+        user_ctx.check_in_site(
+            self.ctx.server_config.name(),
+            self.ctx.server_stats.share_extra_tags(),
+            &req.upstream,
+        );
+
+        let mut passed_users = self.passed_users.lock().unwrap();
+        if passed_users.contains_key(user_ctx.user_name()) {
+            user_ctx.mark_reused_client_connection();
+        } else {
+            let req_stats = user_ctx.req_stats().clone();
+            req_stats.conn_total.add_http();
+            req_stats.l7_conn_alive.inc_http();
+            let site_req_stats = if let Some(site_req_stats) = user_ctx.site_req_stats() {
+                site_req_stats.conn_total.add_http();
+                site_req_stats.l7_conn_alive.inc_http();
+                Some(Arc::clone(site_req_stats))
+            } else {
+                None
+            };
+            passed_users.insert(
+                user_ctx.user_name().clone(),
+                PassedUser {
+                    req_stats,
+                    site_req_stats,
+                },
+            );
+        }
+        Ok(Some(user_ctx))
+    }
+
+    async fn reply_parse_err<S>(&self, e: &HttpRequestParseError, responder: &mut S)
+    where
+        S: HttpStreamResponder,
+    {
+        if self.ctx.server_config.no_early_error_reply {
+            responder.reset(StreamResetReason::RequestRejected);
+            return;
+        }
+
+        match HttpProxyClientResponse::from_request_error(e, http::Version::HTTP_11) {
+            Some(rsp) => {
+                let _ = responder.send_error(&rsp).await;
+            }
+            None => responder.reset(StreamResetReason::MalformedMessage),
+        }
+    }
+
+    async fn reply_auth_err<S>(
+        &self,
+        req: &HttpProxyStreamRequest,
+        blocked_delay: Option<Duration>,
+        responder: &mut S,
+    ) where
+        S: HttpStreamResponder,
+    {
+        let rsp = if let Some(duration) = blocked_delay {
+            self.ctx.server_stats.forbidden.add_user_blocked();
+
+            // delay some time before reply
+            tokio::time::sleep(duration).await;
+
+            HttpProxyClientResponse::forbidden(req.inner.version)
+        } else {
+            self.ctx.server_stats.forbidden.add_auth_failed();
+
+            HttpProxyClientResponse::proxy_auth_required(
+                req.inner.version,
+                &self.ctx.server_config.auth_realm,
+                self.allow_negotiate(),
+                false,
+            )
+        };
+
+        if self.ctx.server_config.no_early_error_reply {
+            responder.reset(StreamResetReason::RequestRejected);
+        } else {
+            // no custom header is set
+            let _ = responder.send_error(&rsp).await;
+        }
+    }
+
+    /// `protocol` is the value of the :protocol pseudo header in extended CONNECT requests
+    pub(super) async fn serve_stream<CDR, S>(
+        &self,
+        parts: request::Parts,
+        protocol: Option<&str>,
+        has_body: bool,
+        clt_r: CDR,
+        mut responder: S,
+        time_accepted: Instant,
+    ) where
+        CDR: AsyncRead + Send + Sync + Unpin + 'static,
+        S: HttpStreamResponder,
+    {
+        let mut req = match HttpProxyStreamRequest::parse(
+            &self.ctx.server_config,
+            &parts,
+            protocol,
+            has_body,
+            time_accepted,
+        ) {
+            Ok(req) => req,
+            Err(e) => {
+                self.reply_parse_err(&e, &mut responder).await;
+                return;
+            }
+        };
+
+        let user_ctx = match self.do_auth(&req).await {
+            Ok(user_ctx) => user_ctx,
+            Err(e) => {
+                self.reply_auth_err(&req, e.blocked_delay(), &mut responder)
+                    .await;
+                return;
+            }
+        };
+
+        let Ok(path_selection) = self.ctx.get_egress_path_selection(&mut req.inner) else {
+            // Bad request: unsupported param combo or invalid params
+            let rsp = HttpProxyClientResponse::bad_request(req.inner.version);
+            let _ = responder.send_error(&rsp).await;
+            return;
+        };
+
+        let task_notes = ServerTaskNotes::with_path_selection(
+            self.ctx.cc_info.clone(),
+            user_ctx,
+            req.time_accepted.elapsed(),
+            path_selection,
+        );
+
+        let mut forward_context = self
+            .ctx
+            .escaper
+            .new_http_forward_context(Arc::clone(&self.ctx.escaper));
+        let mut audit_ctx = self.audit_ctx.clone();
+        let remote_protocol = match req.client_protocol {
+            HttpProxySubProtocol::TcpConnect => HttpProxySubProtocol::TcpConnect,
+            HttpProxySubProtocol::UdpConnect => HttpProxySubProtocol::UdpConnect,
+            HttpProxySubProtocol::HttpForward => {
+                let _ = forward_context
+                    .check_in_final_escaper(&task_notes, &req.upstream, &mut audit_ctx)
+                    .await;
+                HttpProxySubProtocol::HttpForward
+            }
+            HttpProxySubProtocol::HttpsForward => {
+                let forward_capability = forward_context
+                    .check_in_final_escaper(&task_notes, &req.upstream, &mut audit_ctx)
+                    .await;
+                if forward_capability.forward_https() {
+                    HttpProxySubProtocol::HttpForward
+                } else {
+                    HttpProxySubProtocol::HttpsForward
+                }
+            }
+            HttpProxySubProtocol::FtpOverHttp => {
+                let forward_capability = forward_context
+                    .check_in_final_escaper(&task_notes, &req.upstream, &mut audit_ctx)
+                    .await;
+                if forward_capability.forward_ftp(&req.inner.method) {
+                    HttpProxySubProtocol::HttpForward
+                } else {
+                    HttpProxySubProtocol::FtpOverHttp
+                }
+            }
+        };
+
+        match remote_protocol {
+            HttpProxySubProtocol::TcpConnect => {
+                let mut connect_task = HttpProxyConnectTask::with_upstream(
+                    &self.ctx,
+                    audit_ctx,
+                    req.upstream.clone(),
+                    req.inner.version,
+                    task_notes,
+                );
+                if let Some(rsp) = connect_task.connect_to_upstream_for_stream().await {
+                    let _ = responder.send_error(&rsp).await;
+                    return;
+                }
+                connect_task.run_stream(clt_r, responder).await;
+            }
+            HttpProxySubProtocol::UdpConnect => {
+                let mut udp_task = HttpProxyUdpConnectTask::with_upstream(
+                    &self.ctx,
+                    req.upstream.clone(),
+                    req.inner.version,
+                    task_notes,
+                );
+                match udp_task.connect_to_upstream_for_stream().await {
+                    Ok(()) => udp_task.run_stream(BufReader::new(clt_r), responder).await,
+                    Err(Some(rsp)) => {
+                        let _ = responder.send_error(&rsp).await;
+                    }
+                    Err(None) => responder.reset(StreamResetReason::InternalError),
+                }
+            }
+            HttpProxySubProtocol::HttpForward | HttpProxySubProtocol::HttpsForward => {
+                let is_https = remote_protocol == HttpProxySubProtocol::HttpsForward;
+                let default_port = if is_https { 443 } else { 80 };
+                if self.ctx.server_config.drop_default_port_in_host
+                    && req.upstream.port() == default_port
+                {
+                    req.drop_default_port_in_host();
+                }
+                http_header::rewrite_request(
+                    &self.ctx.server_config.header_rewrite,
+                    req.upstream.host(),
+                    req.inner.uri.path(),
+                    task_notes.user_ctx().map(|c| c.user_name().as_str()),
+                    &mut req.inner.end_to_end_headers,
+                );
+
+                let clt_r = match req.inner.body_type() {
+                    Some(HttpBodyType::ContentLength(size)) => {
+                        Some(StreamBodyReader::new(clt_r, Some(size)))
+                    }
+                    Some(_) => Some(StreamBodyReader::new(clt_r, None)),
+                    None => None,
+                };
+                let mut forward_task = HttpProxyStreamForwardTask::new(
+                    &self.ctx, audit_ctx, &req, is_https, task_notes,
+                );
+                forward_task
+                    .run(clt_r, &mut responder, &mut forward_context)
+                    .await;
+            }
+            HttpProxySubProtocol::FtpOverHttp => {
+                // the ftp task can only reply in HTTP/1.1
+                let rsp = HttpProxyClientResponse::unimplemented(req.inner.version);
+                let _ = responder.send_error(&rsp).await;
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@
 
 use super::{
     CommonTaskContext, FtpOverHttpTask, HttpProxyConnectTask, HttpProxyForwardTask,
-    HttpProxyServerStats, HttpProxyUntrustedTask, protocol,
+    HttpProxyServerStats, HttpProxyUdpConnectTask, HttpProxyUntrustedTask, protocol,
 };
 
 mod reader;
@@ -3,13 +3,11 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use ahash::AHashMap;
 use arcstr::ArcStr;
-use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::mpsc;
 
@@ -20,13 +18,13 @@ use g3_types::net::{HttpAuth, HttpProxySubProtocol};
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest};
 use super::{
     CommonTaskContext, FtpOverHttpTask, HttpProxyCltWrapperStats, HttpProxyConnectTask,
-    HttpProxyForwardTask, HttpProxyPipelineStats, HttpProxyUntrustedTask,
+    HttpProxyForwardTask, HttpProxyPipelineStats, HttpProxyUdpConnectTask, HttpProxyUntrustedTask,
 };
 use crate::audit::AuditContext;
 use crate::auth::{UserContext, UserGroup, UserRequestStats};
 use crate::config::server::ServerConfig;
-use crate::escape::EgressPathSelection;
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
+use crate::module::http_header;
 use crate::serve::{ServerStats, ServerTaskNotes};
 
 struct UserData {
@@ -74,6 +72,9 @@ pub(crate) struct HttpProxyPipelineWriterTask<CDR, CDW> {
     wrapper_stats: ArcLimitedWriterStats,
     pipeline_stats: Arc<HttpProxyPipelineStats>,
     req_count: RequestCount,
+    /// the token and username of the last successful negotiate auth on this connection,
+    /// the requests without a negotiate token will not be authenticated by it
+    negotiated_user: Option<(Vec<u8>, ArcStr)>,
 }
 
 enum LoopAction {
@@ -115,42 +116,46 @@ where
             wrapper_stats: clt_w_stats,
             pipeline_stats: Arc::clone(pipeline_stats),
             req_count: RequestCount::default(),
+            negotiated_user: None,
         }
     }
 
-    fn do_auth(
+    fn allow_negotiate(&self) -> bool {
+        self.user_group
+            .as_ref()
+            .is_some_and(|g| g.allow_negotiate())
+    }
+
+    async fn do_auth(
         &mut self,
         req: &HttpProxyRequest<CDR>,
     ) -> Result<Option<UserContext>, UserAuthError> {
         if let Some(user_group) = &self.user_group {
             let mut user_ctx = match &req.inner.auth_info {
-                HttpAuth::None => user_group
-                    .get_anonymous_user()
-                    .map(|(user, user_type)| {
-                        UserContext::new(
-                            None,
-                            user,
-                            user_type,
+                HttpAuth::Negotiate(v) => match &self.negotiated_user {
+                    // the client may resend the same token on this connection
+                    Some((token, username)) if v.token.eq(token) => user_group
+                        .get_negotiated_user(
+                            username,
                             self.ctx.server_config.name(),
                             self.ctx.server_stats.share_extra_tags(),
-                        )
-                    })
-                    .ok_or(UserAuthError::NoUserSupplied)?,
-                HttpAuth::Basic(v) => {
-                    let username = v.username.as_original();
-                    let username = self
-                        .ctx
-                        .server_config
-                        .username_params
-                        .as_ref()
-                        .map(|c| c.real_username(username))
-                        .unwrap_or(username);
-                    user_group.check_user_with_password(
-                        username,
-                        &v.password,
-                        self.ctx.server_config.name(),
-                        self.ctx.server_stats.share_extra_tags(),
-                    )?
+                        )?,
+                    _ => {
+                        self.negotiated_user = None;
+                        let user_ctx = self
+                            .ctx
+                            .check_user(user_group, &req.inner.auth_info, &req.upstream)
+                            .await?;
+                        if let Some(username) = user_ctx.raw_user_name() {
+                            self.negotiated_user = Some((v.token.clone(), username.clone()));
+                        }
+                        user_ctx
+                    }
+                },
+                auth_info => {
+                    self.ctx
+                        .check_user(user_group, auth_info, &req.upstream)
+                        .await?
                 }
             };
             user_ctx.check_client_addr(self.ctx.client_addr())?;
@@ -195,7 +200,7 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
+                    let res = match self.do_auth(&req).await {
                         Ok(user_ctx) => {
                             self.req_count.consequent_auth_failed = 0;
                             self.run(req, user_ctx).await
@@ -232,59 +237,12 @@ where
         }
     }
 
-    fn get_egress_path_selection(
-        &self,
-        req: &mut HttpProxyRequest<CDR>,
-    ) -> Result<Option<EgressPathSelection>, ()> {
-        let mut egress_path = EgressPathSelection::default();
-
-        if let Some(header) = &self.ctx.server_config.egress_path_selection_header {
-            // check and remove the custom header
-            if let Some(value) = req.inner.end_to_end_headers.remove(header) {
-                match usize::from_str(value.to_str()) {
-                    Ok(id) => egress_path.set_number_id(self.ctx.server_config.name().clone(), id),
-                    Err(e) => {
-                        debug!("invalid egress path number id value in header {header}: {e}");
-                        return Err(());
-                    }
-                }
-            }
-        }
-
-        // Optional: compute username-param-derived escaper address and store override
-        if let Some(name_params) = &self.ctx.server_config.username_params
-            && let HttpAuth::Basic(v) = &req.inner.auth_info
-        {
-            match name_params.parse_egress_upstream_http(v.username.as_original()) {
-                Ok(Some(ups)) => {
-                    debug!(
-                        "[{}] http username params -> next proxy {}",
-                        self.ctx.server_config.name(),
-                        ups.addr
-                    );
-                    egress_path.set_upstream(self.ctx.escaper.name().clone(), ups);
-                }
-                Ok(None) => {}
-                Err(e) => {
-                    debug!("failed to get upstream addr from username: {e}");
-                    return Err(());
-                }
-            }
-        }
-
-        if egress_path.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(egress_path))
-        }
-    }
-
     async fn run(
         &mut self,
         mut req: HttpProxyRequest<CDR>,
         user_ctx: Option<UserContext>,
     ) -> LoopAction {
-        let Ok(path_selection) = self.get_egress_path_selection(&mut req) else {
+        let Ok(path_selection) = self.ctx.get_egress_path_selection(&mut req.inner) else {
             self.req_count.invalid += 1;
             // Bad request: unsupported param combo or invalid params
             if let Some(stream_w) = &mut self.stream_writer {
@@ -305,6 +263,7 @@ where
         let mut audit_ctx = self.audit_ctx.clone();
         let remote_protocol = match req.client_protocol {
             HttpProxySubProtocol::TcpConnect => HttpProxySubProtocol::TcpConnect,
+            HttpProxySubProtocol::UdpConnect => HttpProxySubProtocol::UdpConnect,
             HttpProxySubProtocol::HttpForward => {
                 let _ = self
                     .forward_context
@@ -364,6 +323,20 @@ where
                     unreachable!()
                 }
             }
+            HttpProxySubProtocol::UdpConnect => {
+                if let (Some(mut stream_w), Some(stream_r)) =
+                    (self.stream_writer.take(), req.body_reader.take())
+                {
+                    let mut udp_task = HttpProxyUdpConnectTask::new(&self.ctx, &req, task_notes);
+                    udp_task.connect_to_upstream(&mut stream_w).await;
+                    // close read end, the connection won't be reused after connect-udp
+                    let _ = req.stream_sender.try_send(None);
+                    udp_task.into_running(stream_r, stream_w);
+                    LoopAction::Break
+                } else {
+                    unreachable!()
+                }
+            }
             HttpProxySubProtocol::HttpForward | HttpProxySubProtocol::HttpsForward => {
                 if let Some(mut stream_w) = self.stream_writer.take() {
                     match self
@@ -444,12 +417,14 @@ where
             // if the previous request has already failed, close the connection
             self.ctx.server_stats.forbidden.add_auth_failed();
 
+            let negotiate = self.allow_negotiate();
             if let Some(clt_w) = &mut self.stream_writer {
                 // no custom header is set
                 let _ = HttpProxyClientResponse::reply_proxy_auth_err(
                     req.inner.version,
                     clt_w,
                     &self.ctx.server_config.auth_realm,
+                    negotiate,
                     true,
                 )
                 .await;
@@ -459,10 +434,15 @@ where
             LoopAction::Break
         } else if let Some(clt_w) = &mut self.stream_writer {
             self.ctx.server_stats.forbidden.add_auth_failed();
+            let negotiate = self
+                .user_group
+                .as_ref()
+                .is_some_and(|g| g.allow_negotiate());
 
             match req.body_reader.take() {
                 Some(stream_r) => {
-                    let mut untrusted_task = HttpProxyUntrustedTask::new(&self.ctx, &req);
+                    let mut untrusted_task =
+                        HttpProxyUntrustedTask::new(&self.ctx, &req, negotiate);
                     let mut clt_r = Some(stream_r);
                     untrusted_task.run(&mut clt_r, clt_w).await;
                     if untrusted_task.should_close() {
@@ -480,7 +460,8 @@ where
                     }
                 }
                 None => {
-                    let mut untrusted_task = HttpProxyUntrustedTask::new(&self.ctx, &req);
+                    let mut untrusted_task =
+                        HttpProxyUntrustedTask::new(&self.ctx, &req, negotiate);
                     let mut clt_r = None;
                     untrusted_task.run::<CDR, CDW>(&mut clt_r, clt_w).await;
                     if untrusted_task.should_close() {
@@ -524,6 +505,13 @@ where
             }
             _ => unreachable!(),
         };
+        http_header::rewrite_request(
+            &self.ctx.server_config.header_rewrite,
+            req.upstream.host(),
+            req.inner.uri.path(),
+            task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut req.inner.end_to_end_headers,
+        );
 
         match req.body_reader.take() {
             Some(stream_r) => {
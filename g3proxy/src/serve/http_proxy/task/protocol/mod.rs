@@ -8,5 +8,8 @@ use g3_io_ext::{LimitedBufReader, LimitedWriter};
 mod request;
 pub(super) use request::HttpProxyRequest;
 
+mod stream;
+pub(super) use stream::HttpProxyStreamRequest;
+
 pub(super) type HttpClientReader<CDR> = LimitedBufReader<CDR>;
 pub(super) type HttpClientWriter<CDW> = LimitedWriter<CDW>;
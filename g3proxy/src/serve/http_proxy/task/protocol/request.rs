@@ -10,7 +10,7 @@ use tokio::time::Instant;
 
 use g3_http::server::{HttpProxyClientRequest, HttpRequestParseError, UriExt};
 use g3_http::uri::{HttpMasque, WellKnownUri};
-use g3_types::net::{HttpProxySubProtocol, UpstreamAddr};
+use g3_types::net::{HttpProxySubProtocol, HttpUpgradeToken, UpstreamAddr};
 
 use super::HttpClientReader;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
@@ -62,46 +62,7 @@ where
         .await?;
         let time_received = Instant::now();
 
-        let (upstream, sub_protocol) = if matches!(&req.method, &Method::CONNECT) {
-            let addr = req.uri.get_upstream_with_default_port(443)?;
-            (addr, HttpProxySubProtocol::TcpConnect)
-        } else if req.is_local_request(&config.local_server_names) {
-            match WellKnownUri::parse(&req.uri).map_err(|e| {
-                HttpRequestParseError::UnsupportedRequest(format!("invalid well-known uri: {e}",))
-            })? {
-                Some(WellKnownUri::EasyProxy(protocol, addr, uri)) => {
-                    req.uri = uri;
-                    req.set_host(&addr);
-                    (addr, protocol)
-                }
-                Some(WellKnownUri::Masque(HttpMasque::Http(uri))) => {
-                    req.uri = uri;
-                    let (addr, protocol) = req.uri.get_upstream_and_protocol()?;
-                    req.set_host(&addr);
-                    (addr, protocol)
-                }
-                Some(v) => {
-                    return Err(HttpRequestParseError::UnsupportedRequest(format!(
-                        "unsupported well-known uri suffix: {}",
-                        v.suffix()
-                    )));
-                }
-                None => {
-                    return Err(HttpRequestParseError::UnsupportedRequest(
-                        "unsupported local request uri".to_string(),
-                    ));
-                }
-            }
-        } else {
-            req.uri.get_upstream_and_protocol()?
-        };
-
-        if !config.allow_custom_host
-            && let Some(host) = &req.host
-            && !host.host_eq(&upstream)
-        {
-            return Err(HttpRequestParseError::UnmatchedHostAndAuthority);
-        }
+        let (upstream, sub_protocol) = parse_upstream(config, &mut req)?;
 
         let req = HttpProxyRequest {
             client_protocol: sub_protocol,
@@ -114,7 +75,7 @@ where
         };
 
         match req.client_protocol {
-            HttpProxySubProtocol::TcpConnect => {
+            HttpProxySubProtocol::TcpConnect | HttpProxySubProtocol::UdpConnect => {
                 // just send to forward task, which will go into a connect task
                 // reader should be sent
                 return Ok((req, true));
@@ -133,12 +94,87 @@ where
     }
 
     pub(crate) fn drop_default_port_in_host(&mut self) {
-        if let Some(v) = self.inner.end_to_end_headers.get_mut(header::HOST) {
-            let b = v.inner().as_bytes();
-            if let Some(d) = memchr::memchr(b':', b) {
-                let new_v = HeaderValue::from_bytes(&b[..d]).unwrap();
-                v.set_inner(new_v);
+        drop_default_port_in_host(&mut self.inner);
+    }
+}
+
+/// get the upstream address and the sub protocol of the request,
+/// the uri and the host of the request may be changed for local requests
+pub(super) fn parse_upstream(
+    config: &HttpProxyServerConfig,
+    req: &mut HttpProxyClientRequest,
+) -> Result<(UpstreamAddr, HttpProxySubProtocol), HttpRequestParseError> {
+    let (upstream, sub_protocol) = if matches!(&req.method, &Method::CONNECT) {
+        let addr = req.uri.get_upstream_with_default_port(443)?;
+        (addr, HttpProxySubProtocol::TcpConnect)
+    } else if req.is_local_request(&config.local_server_names) {
+        match WellKnownUri::parse(&req.uri).map_err(|e| {
+            HttpRequestParseError::UnsupportedRequest(format!("invalid well-known uri: {e}",))
+        })? {
+            Some(WellKnownUri::EasyProxy(protocol, addr, uri)) => {
+                req.uri = uri;
+                req.set_host(&addr);
+                (addr, protocol)
             }
+            Some(WellKnownUri::Masque(HttpMasque::Udp(addr))) => {
+                if !config.enable_connect_udp {
+                    return Err(HttpRequestParseError::UnsupportedRequest(
+                        "connect-udp is not enabled".to_string(),
+                    ));
+                }
+                if !matches!(&req.method, &Method::GET)
+                    || !matches!(req.upgrade, Some(HttpUpgradeToken::ConnectUdp))
+                {
+                    return Err(HttpRequestParseError::UnsupportedRequest(
+                        "connect-udp should be requested by upgrade".to_string(),
+                    ));
+                }
+                (addr, HttpProxySubProtocol::UdpConnect)
+            }
+            Some(WellKnownUri::Masque(HttpMasque::Http(uri))) => {
+                req.uri = uri;
+                let (addr, protocol) = req.uri.get_upstream_and_protocol()?;
+                req.set_host(&addr);
+                (addr, protocol)
+            }
+            Some(v) => {
+                return Err(HttpRequestParseError::UnsupportedRequest(format!(
+                    "unsupported well-known uri suffix: {}",
+                    v.suffix()
+                )));
+            }
+            None => {
+                return Err(HttpRequestParseError::UnsupportedRequest(
+                    "unsupported local request uri".to_string(),
+                ));
+            }
+        }
+    } else {
+        req.uri.get_upstream_and_protocol()?
+    };
+
+    if req.upgrade.is_some() && sub_protocol != HttpProxySubProtocol::UdpConnect {
+        return Err(HttpRequestParseError::UpgradeIsNotSupported);
+    }
+
+    // the host header of connect-udp requests is the proxy itself
+    if !config.allow_custom_host
+        && sub_protocol != HttpProxySubProtocol::UdpConnect
+        && let Some(host) = &req.host
+        && !host.host_eq(&upstream)
+    {
+        return Err(HttpRequestParseError::UnmatchedHostAndAuthority);
+    }
+
+    Ok((upstream, sub_protocol))
+}
+
+pub(super) fn drop_default_port_in_host(req: &mut HttpProxyClientRequest) {
+    if let Some(v) = req.end_to_end_headers.get_mut(header::HOST) {
+        let b = v.inner().as_bytes();
+        if let Some(d) = memchr::memchr(b':', b) {
+            let new_v = HeaderValue::from_bytes(&b[..d]).unwrap();
+            v.set_inner(new_v);
         }
     }
 }
@@ -0,0 +1,81 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+
+use http::{Method, Uri, request};
+use tokio::time::Instant;
+
+use g3_http::server::{HttpProxyClientRequest, HttpRequestParseError};
+use g3_types::net::{HttpProxySubProtocol, HttpUpgradeToken, UpstreamAddr};
+
+use crate::config::server::http_proxy::HttpProxyServerConfig;
+
+/// the request of a h2 / h3 client stream
+pub(crate) struct HttpProxyStreamRequest {
+    pub(crate) client_protocol: HttpProxySubProtocol,
+    pub(crate) inner: HttpProxyClientRequest,
+    pub(crate) upstream: UpstreamAddr,
+    pub(crate) time_accepted: Instant,
+    pub(crate) time_received: Instant,
+}
+
+impl HttpProxyStreamRequest {
+    /// `protocol` is the value of the :protocol pseudo header in extended CONNECT requests
+    pub(crate) fn parse(
+        config: &HttpProxyServerConfig,
+        parts: &request::Parts,
+        protocol: Option<&str>,
+        has_body: bool,
+        time_accepted: Instant,
+    ) -> Result<Self, HttpRequestParseError> {
+        // the data of tunnels will be sent after the response, not as the request body
+        let has_body = has_body && parts.method != Method::CONNECT;
+        let mut req =
+            HttpProxyClientRequest::parse_stream_head(parts, has_body, |req, name, header| {
+                match name.as_str() {
+                    "proxy-authorization" => return req.parse_header_authorization(header.value),
+                    "forwarded" | "x-forwarded-for" => {
+                        if config.steal_forwarded_for {
+                            return Ok(());
+                        }
+                    }
+                    _ => {}
+                }
+                req.append_parsed_header(name, header)?;
+                Ok(())
+            })?;
+        let time_received = Instant::now();
+
+        if let Some(protocol) = protocol {
+            // extended CONNECT, see rfc8441 and rfc9220
+            match HttpUpgradeToken::from_str(protocol) {
+                Ok(HttpUpgradeToken::ConnectUdp) => {
+                    // convert to the HTTP/1.1 upgrade form, so it will be handled as a local
+                    // masque request, see rfc9298 Section 3.5
+                    req.method = Method::GET;
+                    req.upgrade = Some(HttpUpgradeToken::ConnectUdp);
+                    if let Some(path) = parts.uri.path_and_query() {
+                        req.uri = Uri::from(path.clone());
+                    }
+                }
+                _ => return Err(HttpRequestParseError::UpgradeIsNotSupported),
+            }
+        }
+
+        let (upstream, sub_protocol) = super::request::parse_upstream(config, &mut req)?;
+        Ok(HttpProxyStreamRequest {
+            client_protocol: sub_protocol,
+            inner: req,
+            upstream,
+            time_accepted,
+            time_received,
+        })
+    }
+
+    pub(crate) fn drop_default_port_in_host(&mut self) {
+        super::request::drop_default_port_in_host(&mut self.inner);
+    }
+}
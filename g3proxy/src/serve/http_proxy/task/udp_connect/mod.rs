@@ -0,0 +1,17 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use super::{CommonTaskContext, HttpProxyServerStats, HttpStreamResponder, protocol};
+
+mod task;
+pub(super) use task::HttpProxyUdpConnectTask;
+
+mod recv;
+mod send;
+mod stats;
+
+use recv::HttpUdpConnectClientRecv;
+use send::HttpUdpConnectClientSend;
+use stats::{UdpConnectTaskCltWrapperStats, UdpConnectTaskStats};
@@ -0,0 +1,116 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::AsyncBufRead;
+
+use g3_http::capsule::UdpDatagramCapsuleReader;
+use g3_io_ext::{ArcLimitedRecvStats, UdpCopyClientError, UdpCopyClientRecv};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::{UdpCopyPacket, UdpCopyPacketMeta};
+
+/// receive UDP payloads from the DATAGRAM capsules sent by the client
+pub(super) struct HttpUdpConnectClientRecv<R> {
+    reader: UdpDatagramCapsuleReader<R>,
+    stats: ArcLimitedRecvStats,
+}
+
+impl<R> HttpUdpConnectClientRecv<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub(super) fn new(reader: R, stats: ArcLimitedRecvStats, max_payload_size: usize) -> Self {
+        HttpUdpConnectClientRecv {
+            reader: UdpDatagramCapsuleReader::new(reader, max_payload_size),
+            stats,
+        }
+    }
+
+    /// return the payload length, or None if the client has closed the stream
+    fn poll_recv_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<Option<usize>, UdpCopyClientError>> {
+        let r = ready!(self.reader.poll_recv_payload(cx, buf))
+            .map_err(UdpCopyClientError::RecvFailed)?;
+        if let Some(nr) = r {
+            self.stats.add_recv_bytes(nr);
+            self.stats.add_recv_packet();
+        }
+        Poll::Ready(Ok(r))
+    }
+}
+
+impl<R> UdpCopyClientRecv for HttpUdpConnectClientRecv<R>
+where
+    R: AsyncBufRead + Unpin + Send,
+{
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        match ready!(self.poll_recv_payload(cx, buf))? {
+            Some(nr) => Poll::Ready(Ok((0, nr))),
+            None => Poll::Ready(Err(UdpCopyClientError::RecvFailed(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "client closed",
+            )))),
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut count = 0;
+        for p in packets.iter_mut() {
+            match self.poll_recv_payload(cx, p.buf_mut()) {
+                Poll::Pending => {
+                    if count == 0 {
+                        return Poll::Pending;
+                    }
+                    break;
+                }
+                Poll::Ready(Ok(Some(nr))) => {
+                    let meta = {
+                        let iov = io::IoSliceMut::new(p.buf_mut());
+                        UdpCopyPacketMeta::new(&iov, 0, nr)
+                    };
+                    meta.set_packet(p);
+                    count += 1;
+                }
+                Poll::Ready(Ok(None)) => break,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            }
+        }
+        Poll::Ready(Ok(count))
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::task::{Context, Poll, ready};
+
+use tokio::io::AsyncWrite;
+
+use g3_http::capsule::UdpDatagramCapsuleWriter;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::UdpCopyPacket;
+use g3_io_ext::{ArcLimitedSendStats, UdpCopyClientError, UdpCopyClientSend};
+
+/// send UDP payloads to the client as DATAGRAM capsules
+pub(super) struct HttpUdpConnectClientSend<W> {
+    writer: UdpDatagramCapsuleWriter<W>,
+    stats: ArcLimitedSendStats,
+    written: usize,
+}
+
+impl<W> HttpUdpConnectClientSend<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(super) fn new(writer: W, stats: ArcLimitedSendStats, max_payload_size: usize) -> Self {
+        HttpUdpConnectClientSend {
+            writer: UdpDatagramCapsuleWriter::new(writer, max_payload_size),
+            stats,
+            written: 0,
+        }
+    }
+
+    fn poll_write_capsule(
+        &mut self,
+        cx: &mut Context<'_>,
+        payload: &[u8],
+    ) -> Poll<Result<(), UdpCopyClientError>> {
+        ready!(self.writer.poll_write_payload(cx, payload))
+            .map_err(UdpCopyClientError::SendFailed)?;
+        self.stats.add_send_bytes(payload.len());
+        self.stats.add_send_packet();
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), UdpCopyClientError>> {
+        self.writer
+            .poll_flush(cx)
+            .map_err(UdpCopyClientError::SendFailed)
+    }
+}
+
+impl<W> UdpCopyClientSend for HttpUdpConnectClientSend<W>
+where
+    W: AsyncWrite + Unpin + Send,
+{
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        if self.written == 0 {
+            ready!(self.poll_write_capsule(cx, buf))?;
+            self.written = 1;
+        }
+        ready!(self.poll_flush(cx))?;
+        self.written = 0;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        while self.written < packets.len() {
+            match self.poll_write_capsule(cx, packets[self.written].payload()) {
+                Poll::Pending => {
+                    if self.written == 0 {
+                        return Poll::Pending;
+                    }
+                    break;
+                }
+                Poll::Ready(Ok(_)) => self.written += 1,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            }
+        }
+        ready!(self.poll_flush(cx))?;
+        let count = self.written;
+        self.written = 0;
+        Poll::Ready(Ok(count))
+    }
+}
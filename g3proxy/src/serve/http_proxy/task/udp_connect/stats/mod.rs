@@ -0,0 +1,12 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use super::HttpProxyServerStats;
+
+mod task;
+pub(super) use task::UdpConnectTaskStats;
+
+mod wrapper;
+pub(super) use wrapper::UdpConnectTaskCltWrapperStats;
@@ -0,0 +1,525 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use http::Version;
+use tokio::io::{AsyncBufRead, AsyncWrite};
+
+use g3_io_ext::{
+    UdpCopyClientRecv, UdpCopyClientSend, UdpCopyClientToRemote, UdpCopyError, UdpCopyRemoteRecv,
+    UdpCopyRemoteSend, UdpCopyRemoteToClient,
+};
+use g3_types::acl::AclAction;
+use g3_types::net::{ProxyRequestType, UpstreamAddr};
+
+use super::protocol::{HttpClientWriter, HttpProxyRequest};
+use super::{
+    CommonTaskContext, HttpStreamResponder, HttpUdpConnectClientRecv, HttpUdpConnectClientSend,
+    UdpConnectTaskCltWrapperStats, UdpConnectTaskStats,
+};
+use crate::config::server::ServerConfig;
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
+    ServerTaskStage,
+};
+
+type UdpConnectRemoteHalves = (
+    Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+    Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+);
+
+pub(crate) struct HttpProxyUdpConnectTask {
+    ctx: Arc<CommonTaskContext>,
+    upstream: UpstreamAddr,
+    udp_ups: Option<UdpConnectRemoteHalves>,
+    task_notes: ServerTaskNotes,
+    udp_notes: UdpConnectTaskNotes,
+    task_stats: Arc<UdpConnectTaskStats>,
+    http_version: Version,
+    max_idle_count: usize,
+    started: bool,
+}
+
+impl Drop for HttpProxyUdpConnectTask {
+    fn drop(&mut self) {
+        if self.started {
+            self.post_stop();
+            self.started = false;
+        }
+    }
+}
+
+impl HttpProxyUdpConnectTask {
+    pub(crate) fn new<CDR>(
+        ctx: &Arc<CommonTaskContext>,
+        req: &HttpProxyRequest<CDR>,
+        task_notes: ServerTaskNotes,
+    ) -> Self {
+        HttpProxyUdpConnectTask::with_upstream(
+            ctx,
+            req.upstream.clone(),
+            req.inner.version,
+            task_notes,
+        )
+    }
+
+    pub(crate) fn with_upstream(
+        ctx: &Arc<CommonTaskContext>,
+        upstream: UpstreamAddr,
+        http_version: Version,
+        task_notes: ServerTaskNotes,
+    ) -> Self {
+        let max_idle_count = task_notes
+            .user_ctx()
+            .and_then(|c| c.user().task_max_idle_count())
+            .unwrap_or(ctx.server_config.task_idle_max_count);
+        HttpProxyUdpConnectTask {
+            ctx: Arc::clone(ctx),
+            upstream,
+            udp_ups: None,
+            task_notes,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_stats: Arc::new(UdpConnectTaskStats::default()),
+            http_version,
+            max_idle_count,
+            started: false,
+        }
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: Some(self.ctx.cc_info.server_addr()),
+                tcp_client_addr: Some(self.ctx.client_addr()),
+                udp_listen_addr: None,
+                udp_client_addr: None,
+                upstream: Some(&self.upstream),
+                sni: None,
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    fn pre_start(&mut self) {
+        self.ctx.server_stats.task_udp_connect.add_task();
+        self.ctx.server_stats.task_udp_connect.inc_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_total.add_http_udp_connect();
+                s.req_alive.add_http_udp_connect();
+            });
+        }
+
+        if self.ctx.server_config.flush_task_log_on_created
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_created();
+        }
+
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+
+        self.started = true;
+    }
+
+    fn post_stop(&mut self) {
+        self.ctx.server_stats.task_udp_connect.dec_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_alive.del_http_udp_connect();
+            });
+
+            if let Some(user_req_alive_permit) = self.task_notes.user_req_alive_permit.take() {
+                drop(user_req_alive_permit);
+            }
+        }
+    }
+
+    fn task_err_response(&self, e: &ServerTaskError) -> Option<HttpProxyClientResponse> {
+        match e {
+            ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::RateLimited | ServerTaskForbiddenError::FullyLoaded,
+            ) => Some(HttpProxyClientResponse::too_many_requests(
+                self.http_version,
+            )),
+            ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::ProtoBanned) => Some(
+                HttpProxyClientResponse::method_not_allowed(self.http_version),
+            ),
+            _ => HttpProxyClientResponse::from_task_err(e, self.http_version, true),
+        }
+    }
+
+    async fn reply_task_err<W>(&self, e: &ServerTaskError, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(rsp) = self.task_err_response(e) {
+            let _ = rsp.reply_err_to_request(clt_w).await;
+        }
+    }
+
+    /// the client connection should always be closed if the task is not running
+    pub(crate) async fn connect_to_upstream<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.pre_start();
+        if let Err(e) = self.run_connect().await {
+            self.reply_task_err(&e, clt_w).await;
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log(e);
+            }
+        }
+    }
+
+    /// connect to upstream for a h2 / h3 stream,
+    /// the local reply will be returned if failed, or the stream should be reset if there is none
+    pub(crate) async fn connect_to_upstream_for_stream(
+        &mut self,
+    ) -> Result<(), Option<HttpProxyClientResponse>> {
+        self.pre_start();
+        if let Err(e) = self.run_connect().await {
+            let rsp = self.task_err_response(&e);
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log(e);
+            }
+            return Err(rsp);
+        }
+        Ok(())
+    }
+
+    fn handle_acl_action(
+        &self,
+        action: AclAction,
+        forbidden_error: ServerTaskForbiddenError,
+    ) -> ServerTaskResult<()> {
+        let forbid = match action {
+            AclAction::Permit => false,
+            AclAction::PermitAndLog => {
+                // TODO log permit
+                false
+            }
+            AclAction::Forbid => true,
+            AclAction::ForbidAndLog => {
+                // TODO log forbid
+                true
+            }
+        };
+        if forbid {
+            Err(ServerTaskError::ForbiddenByRule(forbidden_error))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle_server_upstream_acl_action(&self, action: AclAction) -> ServerTaskResult<()> {
+        let r = self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied);
+        if r.is_err() {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                // also add to user level forbidden stats
+                user_ctx.add_dest_denied();
+            }
+        }
+        r
+    }
+
+    async fn run_connect(&mut self) -> ServerTaskResult<()> {
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user_ctx = user_ctx.clone();
+
+            if user_ctx.check_rate_limit().is_err() {
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::RateLimited,
+                ));
+            }
+
+            match user_ctx.acquire_request_semaphore() {
+                Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
+                Err(_) => {
+                    return Err(ServerTaskError::ForbiddenByRule(
+                        ServerTaskForbiddenError::FullyLoaded,
+                    ));
+                }
+            }
+
+            let action = user_ctx.check_proxy_request(ProxyRequestType::HttpUdpConnect);
+            self.handle_acl_action(action, ServerTaskForbiddenError::ProtoBanned)?;
+
+            let action = user_ctx.check_upstream(&self.upstream);
+            self.handle_acl_action(action, ServerTaskForbiddenError::DestDenied)?;
+        }
+
+        // server level dst host/port acl rules
+        let action = self.ctx.check_upstream(&self.upstream);
+        self.handle_server_upstream_acl_action(action)?;
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let ups = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        self.task_notes.stage = ServerTaskStage::Connected;
+        self.udp_ups = Some(ups);
+        Ok(())
+    }
+
+    pub(crate) fn into_running<CDR, CDW>(mut self, clt_r: CDR, clt_w: HttpClientWriter<CDW>)
+    where
+        CDR: AsyncBufRead + Send + Sync + Unpin + 'static,
+        CDW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        let Some((ups_r, ups_w)) = self.udp_ups.take() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let e = match self.run_connected(clt_r, clt_w, ups_r, ups_w).await {
+                Ok(_) => ServerTaskError::ClosedByClient,
+                Err(e) => e,
+            };
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log(e);
+            }
+        });
+    }
+
+    /// run the relay for a h2 / h3 stream after connected to upstream,
+    /// the datagrams are sent in capsules on the stream, see rfc9298 Section 5
+    pub(crate) async fn run_stream<CDR, S>(mut self, clt_r: CDR, mut responder: S)
+    where
+        CDR: AsyncBufRead + Send + Sync + Unpin + 'static,
+        S: HttpStreamResponder,
+    {
+        let Some((ups_r, ups_w)) = self.udp_ups.take() else {
+            return;
+        };
+
+        let e = match self
+            .run_stream_connected(clt_r, &mut responder, ups_r, ups_w)
+            .await
+        {
+            Ok(_) => ServerTaskError::ClosedByClient,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+    }
+
+    async fn run_stream_connected<CDR, S>(
+        &mut self,
+        clt_r: CDR,
+        responder: &mut S,
+        ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncBufRead + Send + Sync + Unpin + 'static,
+        S: HttpStreamResponder,
+    {
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        self.task_notes.stage = ServerTaskStage::Replying;
+        let rsp = HttpProxyClientResponse::connect_udp_established(self.http_version);
+        let clt_w = responder
+            .send_header(rsp.to_stream_response())
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+
+        self.relay_client(clt_r, clt_w, ups_r, ups_w).await
+    }
+
+    async fn run_connected<CDR, CDW>(
+        &mut self,
+        clt_r: CDR,
+        mut clt_w: HttpClientWriter<CDW>,
+        ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncBufRead + Send + Sync + Unpin + 'static,
+        CDW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        self.task_notes.stage = ServerTaskStage::Replying;
+        let rsp = HttpProxyClientResponse::switching_to_connect_udp(self.http_version);
+        rsp.reply_ok_to_connect(&mut clt_w)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+
+        self.relay_client(clt_r, clt_w.into_inner(), ups_r, ups_w)
+            .await
+    }
+
+    async fn relay_client<CDR, CDW>(
+        &mut self,
+        clt_r: CDR,
+        clt_w: CDW,
+        ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncBufRead + Send + Sync + Unpin + 'static,
+        CDW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        self.task_notes.mark_relaying();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_ready.add_http_udp_connect();
+            });
+        }
+
+        let mut wrapper_stats =
+            UdpConnectTaskCltWrapperStats::new(&self.ctx.server_stats, &self.task_stats);
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            wrapper_stats.push_user_io_stats(user_ctx.fetch_traffic_stats(
+                self.ctx.server_config.name(),
+                self.ctx.server_stats.share_extra_tags(),
+            ));
+        }
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let packet_size = self.ctx.server_config.udp_relay.packet_size();
+        let clt_r = HttpUdpConnectClientRecv::new(clt_r, wrapper_stats.clone(), packet_size);
+        let clt_w = HttpUdpConnectClientSend::new(clt_w, wrapper_stats, packet_size);
+
+        self.run_relay(Box::new(clt_r), Box::new(clt_w), ups_r, ups_w)
+            .await
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: Box<dyn UdpCopyClientRecv + Unpin + Send>,
+        mut clt_w: Box<dyn UdpCopyClientSend + Unpin + Send>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut *clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut *clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = ups_w.error_logger() {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = ups_r.error_logger() {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -19,6 +19,7 @@ use crate::serve::{ServerTaskError, ServerTaskResult};
 pub(crate) struct HttpProxyUntrustedTask<'a> {
     ctx: Arc<CommonTaskContext>,
     req: &'a HttpProxyClientRequest,
+    negotiate: bool,
     should_close: bool,
     started: bool,
 }
@@ -36,10 +37,12 @@ impl<'a> HttpProxyUntrustedTask<'a> {
     pub(crate) fn new(
         ctx: &Arc<CommonTaskContext>,
         req: &'a HttpProxyRequest<impl AsyncRead>,
+        negotiate: bool,
     ) -> Self {
         HttpProxyUntrustedTask {
             ctx: Arc::clone(ctx),
             req: &req.inner,
+            negotiate,
             should_close: !req.inner.keep_alive(),
             started: false,
         }
@@ -105,6 +108,7 @@ impl<'a> HttpProxyUntrustedTask<'a> {
             self.req.version,
             clt_w,
             &self.ctx.server_config.auth_realm,
+            self.negotiate,
             self.should_close,
         )
         .await;
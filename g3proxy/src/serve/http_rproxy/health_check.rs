@@ -0,0 +1,231 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::uri::PathAndQuery;
+use log::{info, warn};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::time::MissedTickBehavior;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_daemon::stat::remote::TcpConnectionTaskRemoteStats;
+use g3_types::net::UpstreamAddr;
+
+use super::{HttpUpstream, HttpUpstreamPeer};
+use crate::audit::AuditContext;
+use crate::config::server::http_rproxy::{HttpHealthCheckConfig, HttpHealthCheckProtocol};
+use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
+use crate::serve::ServerTaskNotes;
+
+const STATUS_LINE_MAX_SIZE: u64 = 1024;
+
+pub(super) fn spawn(upstream: &Arc<HttpUpstream>, config: &HttpHealthCheckConfig) {
+    for index in 0..upstream.peers().len() {
+        let upstream = Arc::downgrade(upstream);
+        let config = config.clone();
+        tokio::spawn(async move {
+            run(upstream, index, config).await;
+        });
+    }
+}
+
+/// check the peer periodically, and quit when the upstream is dropped
+async fn run(upstream: Weak<HttpUpstream>, index: usize, config: HttpHealthCheckConfig) {
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut passed: u32 = 0;
+    let mut failed: u32 = 0;
+    loop {
+        interval.tick().await;
+
+        let Some(upstream) = upstream.upgrade() else {
+            break;
+        };
+        let peer = upstream.peers()[index].inner().clone();
+
+        let r = match tokio::time::timeout(config.timeout, probe(&upstream, &peer, &config)).await {
+            Ok(r) => r,
+            Err(_) => Err(anyhow!("timed out")),
+        };
+        match r {
+            Ok(_) => {
+                peer.stats.add_probe_passed();
+                failed = 0;
+                passed = passed.saturating_add(1);
+                if !peer.is_healthy() && passed >= config.rise {
+                    info!(
+                        "server {}: upstream {} restored after {passed} passed health checks",
+                        peer.stats.server(),
+                        peer.addr()
+                    );
+                    peer.stats.set_healthy(true);
+                    upstream.update_healthy_nodes();
+                }
+            }
+            Err(e) => {
+                peer.stats.add_probe_failed();
+                passed = 0;
+                failed = failed.saturating_add(1);
+                if peer.is_healthy() && failed >= config.fall {
+                    warn!(
+                        "server {}: upstream {} ejected after {failed} failed health checks: {e:?}",
+                        peer.stats.server(),
+                        peer.addr()
+                    );
+                    peer.stats.set_healthy(false);
+                    peer.stats.add_ejected();
+                    upstream.update_healthy_nodes();
+                }
+            }
+        }
+    }
+}
+
+struct NullStats {}
+
+impl TcpConnectionTaskRemoteStats for NullStats {
+    fn add_read_bytes(&self, _size: u64) {}
+
+    fn add_write_bytes(&self, _size: u64) {}
+}
+
+/// connect to the peer through the escaper of the server, just like the real tasks
+async fn probe(
+    upstream: &HttpUpstream,
+    peer: &HttpUpstreamPeer,
+    config: &HttpHealthCheckConfig,
+) -> anyhow::Result<()> {
+    let escaper = crate::escape::get_escaper(&upstream.escaper)?;
+
+    let tcp_conf = TcpConnectTaskConf {
+        upstream: peer.addr(),
+    };
+    let mut tcp_notes = TcpConnectTaskNotes::default();
+    let local_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+    let task_notes = ServerTaskNotes::new(
+        ClientConnectionInfo::new(local_addr, local_addr),
+        None,
+        Duration::ZERO,
+    );
+    let mut audit_ctx = AuditContext::new(None);
+
+    let path = match &config.protocol {
+        HttpHealthCheckProtocol::Tcp => None,
+        HttpHealthCheckProtocol::Http { path, .. } => Some(path),
+    };
+    let (ups_r, ups_w) = match (&upstream.tls_client, path) {
+        (Some(tls_client), Some(_)) => {
+            let task_conf = TlsConnectTaskConf {
+                tcp: tcp_conf,
+                tls_config: tls_client,
+                tls_name: &peer.tls_name,
+            };
+            escaper
+                .tls_setup_connection(
+                    &task_conf,
+                    &mut tcp_notes,
+                    &task_notes,
+                    Arc::new(NullStats {}),
+                    &mut audit_ctx,
+                )
+                .await?
+        }
+        _ => {
+            escaper
+                .tcp_setup_connection(
+                    &tcp_conf,
+                    &mut tcp_notes,
+                    &task_notes,
+                    Arc::new(NullStats {}),
+                    &mut audit_ctx,
+                )
+                .await?
+        }
+    };
+
+    match &config.protocol {
+        HttpHealthCheckProtocol::Tcp => Ok(()),
+        HttpHealthCheckProtocol::Http {
+            path,
+            expect_status,
+        } => {
+            let stream = tokio::io::join(ups_r, ups_w);
+            let status = http_get_status(stream, peer.addr(), path).await?;
+
+            let ok = if expect_status.is_empty() {
+                (200..400).contains(&status)
+            } else {
+                expect_status.contains(&status)
+            };
+            if ok {
+                Ok(())
+            } else {
+                Err(anyhow!("unexpected response status code {status}"))
+            }
+        }
+    }
+}
+
+async fn http_get_status<S>(
+    mut stream: S,
+    upstream: &UpstreamAddr,
+    path: &PathAndQuery,
+) -> anyhow::Result<u16>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let req = format!(
+        "GET {path} HTTP/1.1\r\nHost: {upstream}\r\nConnection: close\r\nUser-Agent: g3proxy-health-check\r\n\r\n"
+    );
+    stream
+        .write_all(req.as_bytes())
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+    let mut reader = BufReader::new(stream.take(STATUS_LINE_MAX_SIZE));
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .await
+        .map_err(|e| anyhow!("failed to read response: {e}"))?;
+    parse_status_line(&line)
+}
+
+fn parse_status_line(line: &str) -> anyhow::Result<u16> {
+    let mut parts = line.split_ascii_whitespace();
+    match parts.next() {
+        Some(version) if version.starts_with("HTTP/") => {}
+        _ => return Err(anyhow!("invalid http status line")),
+    }
+    let Some(code) = parts.next() else {
+        return Err(anyhow!("no status code found in http status line"));
+    };
+    code.parse::<u16>()
+        .map_err(|_| anyhow!("invalid status code {code}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_line() {
+        assert_eq!(parse_status_line("HTTP/1.1 200 OK\r\n").unwrap(), 200);
+        assert_eq!(parse_status_line("HTTP/1.0 503\r\n").unwrap(), 503);
+        assert!(parse_status_line("SSH-2.0-OpenSSH\r\n").is_err());
+        assert!(parse_status_line("HTTP/1.1\r\n").is_err());
+        assert!(parse_status_line("HTTP/1.1 abc\r\n").is_err());
+        assert!(parse_status_line("").is_err());
+    }
+}
@@ -7,19 +7,22 @@ use std::sync::Arc;
 
 use anyhow::Context;
 
-use g3_types::net::{OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConfig};
+use g3_http::server::HttpProxyClientRequest;
+use g3_types::net::{OpensslTicketKey, RollingTicketer, RustlsServerConfig};
 
+use super::{HttpRoute, HttpUpstream, HttpUpstreamBuildContext};
 use crate::config::server::http_rproxy::HttpHostConfig;
 
 pub(crate) struct HttpHost {
-    pub(super) config: Arc<HttpHostConfig>,
     pub(super) tls_server: Option<RustlsServerConfig>,
-    pub(super) tls_client: Option<OpensslClientConfig>,
+    upstream: Option<Arc<HttpUpstream>>,
+    routes: Vec<HttpRoute>,
 }
 
 impl HttpHost {
     pub(super) fn try_build(
-        config: &Arc<HttpHostConfig>,
+        config: &HttpHostConfig,
+        upstream_ctx: &HttpUpstreamBuildContext,
         ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
         let tls_server = if let Some(builder) = &config.tls_server_builder {
@@ -31,17 +34,43 @@ impl HttpHost {
             None
         };
 
-        let tls_client = if let Some(builder) = &config.tls_client_builder {
-            let client = builder.build().context("failed to build tls client")?;
-            Some(client)
-        } else {
+        let upstream = if config.upstream.is_empty() {
             None
+        } else {
+            let upstream = HttpUpstream::try_build(&config.upstream, upstream_ctx)
+                .context("failed to build default upstream")?;
+            Some(upstream)
         };
 
+        let mut routes = Vec::with_capacity(config.routes.len());
+        for (i, route_config) in config.routes.iter().enumerate() {
+            let route = HttpRoute::try_build(route_config, upstream_ctx)
+                .context(format!("failed to build route #{i}"))?;
+            routes.push(route);
+        }
+
         Ok(HttpHost {
-            config: Arc::clone(config),
             tls_server,
-            tls_client,
+            upstream,
+            routes,
         })
     }
+
+    /// get the upstream of the first matched route, or the default one if no route matches
+    pub(super) fn select_upstream(
+        &self,
+        req: &HttpProxyClientRequest,
+    ) -> Option<&Arc<HttpUpstream>> {
+        self.routes
+            .iter()
+            .find(|r| r.is_match(req))
+            .map(|r| &r.upstream)
+            .or(self.upstream.as_ref())
+    }
+
+    pub(super) fn upstreams(&self) -> impl Iterator<Item = &Arc<HttpUpstream>> {
+        self.upstream
+            .iter()
+            .chain(self.routes.iter().map(|r| &r.upstream))
+    }
 }
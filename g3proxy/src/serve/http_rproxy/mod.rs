@@ -5,6 +5,7 @@
 
 mod stats;
 use stats::HttpRProxyServerStats;
+pub(crate) use stats::{HttpUpstreamPeerSnapshot, HttpUpstreamPeerStats};
 
 mod task;
 
@@ -13,3 +14,11 @@ pub(super) use server::HttpRProxyServer;
 
 mod host;
 use host::HttpHost;
+
+mod route;
+use route::HttpRoute;
+
+mod upstream;
+use upstream::{HttpUpstream, HttpUpstreamBuildContext, HttpUpstreamPeer};
+
+mod health_check;
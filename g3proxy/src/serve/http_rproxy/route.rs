@@ -0,0 +1,206 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, Method};
+use regex::{Regex, RegexSet};
+
+use g3_http::server::HttpProxyClientRequest;
+use g3_types::net::HttpHeaderMap;
+use g3_types::route::UriPathMatch;
+
+use super::{HttpUpstream, HttpUpstreamBuildContext};
+use crate::config::server::http_rproxy::HttpRouteConfig;
+
+struct HttpRouteHeaderMatch {
+    name: HeaderName,
+    value_regex: Option<Regex>,
+}
+
+pub(super) struct HttpRoute {
+    path_prefix: Option<UriPathMatch<()>>,
+    path_regex: Option<RegexSet>,
+    methods: Vec<Method>,
+    headers: Vec<HttpRouteHeaderMatch>,
+    pub(super) upstream: Arc<HttpUpstream>,
+}
+
+impl HttpRoute {
+    pub(super) fn try_build(
+        config: &HttpRouteConfig,
+        ctx: &HttpUpstreamBuildContext,
+    ) -> anyhow::Result<Self> {
+        let path_prefix = if config.path_prefix.is_empty() {
+            None
+        } else {
+            let mut path_match = UriPathMatch::default();
+            for prefix in &config.path_prefix {
+                path_match.add_prefix(prefix.clone(), ());
+            }
+            Some(path_match)
+        };
+        let path_regex = if config.path_regex.is_empty() {
+            None
+        } else {
+            let set = RegexSet::new(&config.path_regex)
+                .map_err(|e| anyhow!("failed to build path regex set: {e}"))?;
+            Some(set)
+        };
+
+        let mut headers = Vec::with_capacity(config.headers.len());
+        for h in &config.headers {
+            let value_regex = match &h.value_regex {
+                Some(s) => {
+                    let regex = Regex::new(s)
+                        .map_err(|e| anyhow!("invalid value regex for header {}: {e}", h.name))?;
+                    Some(regex)
+                }
+                None => None,
+            };
+            headers.push(HttpRouteHeaderMatch {
+                name: h.name.clone(),
+                value_regex,
+            });
+        }
+
+        let upstream =
+            HttpUpstream::try_build(&config.upstream, ctx).context("failed to build upstream")?;
+
+        Ok(HttpRoute {
+            path_prefix,
+            path_regex,
+            methods: config.methods.clone(),
+            headers,
+            upstream,
+        })
+    }
+
+    pub(super) fn is_match(&self, req: &HttpProxyClientRequest) -> bool {
+        self.check(&req.method, req.uri.path(), &req.end_to_end_headers)
+    }
+
+    fn check(&self, method: &Method, path: &str, headers: &HttpHeaderMap) -> bool {
+        if !self.methods.is_empty() && !self.methods.contains(method) {
+            return false;
+        }
+
+        if let Some(path_match) = &self.path_prefix
+            && path_match.get_by_prefix(path).is_none()
+        {
+            return false;
+        }
+        if let Some(set) = &self.path_regex
+            && !set.is_match(path)
+        {
+            return false;
+        }
+
+        self.headers.iter().all(|h| {
+            let mut values = headers.get_all(&h.name).iter();
+            match &h.value_regex {
+                Some(regex) => values.any(|v| regex.is_match(v.to_str())),
+                None => values.next().is_some(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::metrics::NodeName;
+    use g3_types::net::HttpHeaderValue;
+    use yaml_rust::YamlLoader;
+
+    fn build_route(yaml: &str) -> HttpRoute {
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let config = HttpRouteConfig::parse_yaml(&docs[0], None).unwrap();
+        let name = NodeName::new_static("test");
+        HttpRoute::try_build(&config, &HttpUpstreamBuildContext::new(&name, &name)).unwrap()
+    }
+
+    #[test]
+    fn path_prefix() {
+        let route = build_route(
+            r#"
+              path_prefix:
+                - /api/
+                - /v2/
+              upstream: 127.0.0.1:8080
+            "#,
+        );
+        let headers = HttpHeaderMap::default();
+        assert!(route.check(&Method::GET, "/api/user", &headers));
+        assert!(route.check(&Method::POST, "/v2/", &headers));
+        assert!(!route.check(&Method::GET, "/api", &headers));
+        assert!(!route.check(&Method::GET, "/static/a.js", &headers));
+        assert_eq!(route.upstream.peers()[0].inner().addr().port(), 8080);
+    }
+
+    #[test]
+    fn path_regex_and_method() {
+        let route = build_route(
+            r#"
+              path_regex: "^/order/[0-9]+$"
+              methods: [get, DELETE]
+              upstream: 127.0.0.1
+            "#,
+        );
+        let headers = HttpHeaderMap::default();
+        assert!(route.check(&Method::GET, "/order/123", &headers));
+        assert!(route.check(&Method::DELETE, "/order/1", &headers));
+        assert!(!route.check(&Method::POST, "/order/123", &headers));
+        assert!(!route.check(&Method::GET, "/order/abc", &headers));
+    }
+
+    #[test]
+    fn header_match() {
+        let route = build_route(
+            r#"
+              headers:
+                x-canary: "^(1|true)$"
+                x-tenant: ~
+              upstream: 127.0.0.1
+            "#,
+        );
+        let mut headers = HttpHeaderMap::default();
+        assert!(!route.check(&Method::GET, "/", &headers));
+
+        headers.append(
+            HeaderName::from_static("x-tenant"),
+            HttpHeaderValue::from_static("a"),
+        );
+        assert!(!route.check(&Method::GET, "/", &headers));
+
+        headers.append(
+            HeaderName::from_static("x-canary"),
+            HttpHeaderValue::from_static("0"),
+        );
+        assert!(!route.check(&Method::GET, "/", &headers));
+
+        headers.append(
+            HeaderName::from_static("x-canary"),
+            HttpHeaderValue::from_static("true"),
+        );
+        assert!(route.check(&Method::GET, "/", &headers));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let docs = YamlLoader::load_from_str(
+            r#"
+              path_prefix: api
+              upstream: 127.0.0.1
+            "#,
+        )
+        .unwrap();
+        assert!(HttpRouteConfig::parse_yaml(&docs[0], None).is_err());
+
+        let docs = YamlLoader::load_from_str("path_prefix: /api").unwrap();
+        assert!(HttpRouteConfig::parse_yaml(&docs[0], None).is_err());
+    }
+}
@@ -20,7 +20,9 @@ use tokio::sync::{broadcast, mpsc};
 use tokio_rustls::LazyConfigAcceptor;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
+use g3_daemon::listen::{
+    AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime, ReceiveUdpServer,
+};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::{AsyncStream, IdleWheel};
 use g3_openssl::SslStream;
@@ -36,11 +38,12 @@ use super::task::{
     CommonTaskContext, HttpRProxyPipelineReaderTask, HttpRProxyPipelineStats,
     HttpRProxyPipelineWriterTask,
 };
-use super::{HttpHost, HttpRProxyServerStats};
+use super::{HttpHost, HttpRProxyServerStats, HttpUpstreamBuildContext};
 use crate::auth::UserGroup;
 use crate::config::server::http_rproxy::HttpRProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_forward::HttpCache;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
     ServerRegistry, ServerStats, WrapArcServer,
@@ -51,6 +54,7 @@ pub(crate) struct HttpRProxyServer {
     server_stats: Arc<HttpRProxyServerStats>,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    http_cache: Option<Arc<HttpCache>>,
     global_tls_server: Option<RustlsServerConfig>,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
@@ -71,6 +75,7 @@ impl HttpRProxyServer {
         listen_stats: Arc<ListenStats>,
         hosts: HostMatch<Arc<HttpHost>>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        http_cache: Option<Arc<HttpCache>>,
         version: usize,
     ) -> anyhow::Result<Self> {
         let reload_sender = crate::serve::new_reload_notify_channel();
@@ -107,6 +112,7 @@ impl HttpRProxyServer {
             server_stats,
             listen_stats,
             tls_rolling_ticketer,
+            http_cache,
             global_tls_server,
             ingress_net_filter,
             reload_sender,
@@ -137,9 +143,17 @@ impl HttpRProxyServer {
         } else {
             None
         };
-        let hosts = config
-            .hosts
-            .try_build_arc(|c| HttpHost::try_build(c, tls_rolling_ticketer.clone()))?;
+        let upstream_ctx = HttpUpstreamBuildContext::new(config.name(), config.escaper());
+        let hosts = config.hosts.try_build_arc(|c| {
+            HttpHost::try_build(c, &upstream_ctx, tls_rolling_ticketer.clone())
+        })?;
+        let http_cache = if let Some(c) = &config.http_cache {
+            let cache =
+                HttpCache::build(c, config.name()).context("failed to create http cache")?;
+            Some(cache)
+        } else {
+            None
+        };
 
         let server = HttpRProxyServer::new(
             config,
@@ -147,6 +161,7 @@ impl HttpRProxyServer {
             listen_stats,
             hosts,
             tls_rolling_ticketer,
+            http_cache,
             1,
         )?;
         Ok(Arc::new(server))
@@ -168,10 +183,23 @@ impl HttpRProxyServer {
             } else {
                 None
             };
-            // TODO do update if host has runtime state
-            let hosts = config
-                .hosts
-                .try_build_arc(|c| HttpHost::try_build(c, tls_rolling_ticketer.clone()))?;
+            let mut upstream_ctx = HttpUpstreamBuildContext::new(config.name(), config.escaper());
+            for host in self.hosts.values() {
+                host.upstreams()
+                    .for_each(|upstream| upstream_ctx.add_old_upstream(upstream));
+            }
+            let hosts = config.hosts.try_build_arc(|c| {
+                HttpHost::try_build(c, &upstream_ctx, tls_rolling_ticketer.clone())
+            })?;
+            let http_cache = if self.config.http_cache.eq(&config.http_cache) {
+                self.http_cache.clone()
+            } else if let Some(c) = &config.http_cache {
+                let cache =
+                    HttpCache::build(c, config.name()).context("failed to create http cache")?;
+                Some(cache)
+            } else {
+                None
+            };
 
             let server = HttpRProxyServer::new(
                 config,
@@ -179,6 +207,7 @@ impl HttpRProxyServer {
                 listen_stats,
                 hosts,
                 tls_rolling_ticketer,
+                http_cache,
                 self.reload_version + 1,
             )?;
             Ok(server)
@@ -200,6 +229,7 @@ impl HttpRProxyServer {
             escaper: self.escaper.load().as_ref().clone(),
             cc_info,
             task_logger: self.task_logger.clone(),
+            http_cache: self.http_cache.clone(),
         })
     }
 
@@ -436,6 +466,17 @@ impl AcceptQuicServer for HttpRProxyServer {
     async fn run_quic_task(&self, _connection: Connection, _cc_info: ClientConnectionInfo) {}
 }
 
+impl ReceiveUdpServer for HttpRProxyServer {
+    fn receive_udp_packet(
+        &self,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
+}
+
 #[async_trait]
 impl Server for HttpRProxyServer {
     fn escaper(&self) -> &NodeName {
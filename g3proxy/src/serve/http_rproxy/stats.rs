@@ -5,15 +5,17 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicIsize, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering};
 
 use arc_swap::ArcSwapOption;
 
 use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::UpstreamAddr;
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    LiveTaskRegistry, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats,
+    ServerStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -28,6 +30,8 @@ pub(crate) struct HttpRProxyServerStats {
 
     pub forbidden: ServerForbiddenStats,
 
+    pub(crate) live_tasks: Arc<LiveTaskRegistry>,
+
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_forward: ServerPerTaskStats,
 
@@ -44,6 +48,7 @@ impl HttpRProxyServerStats {
             online: AtomicIsize::new(0),
             conn_total: AtomicU64::new(0),
             forbidden: Default::default(),
+            live_tasks: Arc::new(LiveTaskRegistry::default()),
             task_http_untrusted: Default::default(),
             task_http_forward: Default::default(),
             io_http: Default::default(),
@@ -118,6 +123,10 @@ impl ServerStats for HttpRProxyServerStats {
         self.forbidden.snapshot()
     }
 
+    fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        Some(&self.live_tasks)
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
@@ -126,3 +135,86 @@ impl ServerStats for HttpRProxyServerStats {
         })
     }
 }
+
+#[derive(Default)]
+pub(crate) struct HttpUpstreamPeerSnapshot {
+    pub(crate) selected: u64,
+    pub(crate) probe_passed: u64,
+    pub(crate) probe_failed: u64,
+    pub(crate) ejected: u64,
+}
+
+pub(crate) struct HttpUpstreamPeerStats {
+    server: NodeName,
+    upstream: String,
+    id: StatId,
+
+    healthy: AtomicBool,
+    selected: AtomicU64,
+    probe_passed: AtomicU64,
+    probe_failed: AtomicU64,
+    ejected: AtomicU64,
+}
+
+impl HttpUpstreamPeerStats {
+    pub(super) fn new(server: &NodeName, upstream: &UpstreamAddr) -> Self {
+        HttpUpstreamPeerStats {
+            server: server.clone(),
+            upstream: upstream.to_string(),
+            id: StatId::new_unique(),
+            healthy: AtomicBool::new(true),
+            selected: AtomicU64::new(0),
+            probe_passed: AtomicU64::new(0),
+            probe_failed: AtomicU64::new(0),
+            ejected: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn server(&self) -> &NodeName {
+        &self.server
+    }
+
+    #[inline]
+    pub(crate) fn upstream(&self) -> &str {
+        &self.upstream
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn set_healthy(&self, healthy: bool) {
+        self.healthy.store(healthy, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_selected(&self) {
+        self.selected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_probe_passed(&self) {
+        self.probe_passed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_probe_failed(&self) {
+        self.probe_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_ejected(&self) {
+        self.ejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> HttpUpstreamPeerSnapshot {
+        HttpUpstreamPeerSnapshot {
+            selected: self.selected.load(Ordering::Relaxed),
+            probe_passed: self.probe_passed.load(Ordering::Relaxed),
+            probe_failed: self.probe_failed.load(Ordering::Relaxed),
+            ejected: self.ejected.load(Ordering::Relaxed),
+        }
+    }
+}
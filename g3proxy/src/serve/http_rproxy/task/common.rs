@@ -15,6 +15,7 @@ use g3_io_ext::{IdleWheel, OptionalInterval};
 
 use super::{HttpRProxyServerConfig, HttpRProxyServerStats};
 use crate::escape::ArcEscaper;
+use crate::module::http_forward::HttpCache;
 use crate::serve::ServerQuitPolicy;
 
 #[derive(Clone)]
@@ -26,6 +27,7 @@ pub(crate) struct CommonTaskContext {
     pub(crate) escaper: ArcEscaper,
     pub(crate) cc_info: ClientConnectionInfo,
     pub(crate) task_logger: Option<Logger>,
+    pub(crate) http_cache: Option<Arc<HttpCache>>,
 }
 
 impl CommonTaskContext {
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, StatusCode, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::HttpForwardRemoteResponse;
@@ -19,6 +19,7 @@ use g3_io_ext::{
     StreamCopyError,
 };
 use g3_types::acl::AclAction;
+use g3_types::net::Host;
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpRProxyRequest};
 use super::{
@@ -29,12 +30,14 @@ use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
+    HttpCacheBodyReader, HttpCacheEntry, HttpCacheLookup, HttpCacheResponseAction, HttpCacheTask,
     HttpForwardTaskNotes, HttpProxyClientResponse,
 };
+use crate::module::http_header;
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
-use crate::serve::http_rproxy::host::HttpHost;
+use crate::serve::http_rproxy::{HttpUpstream, HttpUpstreamPeer};
 use crate::serve::{
     ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
     ServerTaskStage,
@@ -42,8 +45,10 @@ use crate::serve::{
 
 pub(crate) struct HttpRProxyForwardTask<'a> {
     ctx: Arc<CommonTaskContext>,
-    host: Arc<HttpHost>,
+    upstream: Arc<HttpUpstream>,
+    peer: Arc<HttpUpstreamPeer>,
     req: &'a HttpProxyClientRequest,
+    req_host: &'a Host,
     is_https: bool,
     should_close: bool,
     send_error_response: bool,
@@ -52,6 +57,7 @@ pub(crate) struct HttpRProxyForwardTask<'a> {
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
     max_idle_count: usize,
+    cache_task: Option<HttpCacheTask>,
     started: bool,
 }
 
@@ -68,7 +74,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
     pub(crate) fn new(
         ctx: &Arc<CommonTaskContext>,
         req: &'a HttpRProxyRequest<impl AsyncRead>,
-        host: Arc<HttpHost>,
+        upstream: Arc<HttpUpstream>,
+        peer: Arc<HttpUpstreamPeer>,
         task_notes: ServerTaskNotes,
     ) -> Self {
         let uri_log_max_chars = task_notes
@@ -82,15 +89,17 @@ impl<'a> HttpRProxyForwardTask<'a> {
             req.inner.uri.clone(),
             uri_log_max_chars,
         );
-        let is_https = host.tls_client.is_some();
+        let is_https = upstream.tls_client.is_some();
         let max_idle_count = task_notes
             .user_ctx()
             .and_then(|c| c.user().task_max_idle_count())
             .unwrap_or(ctx.server_config.task_idle_max_count);
         HttpRProxyForwardTask {
             ctx: Arc::clone(ctx),
-            host,
+            upstream,
+            peer,
             req: &req.inner,
+            req_host: req.upstream.host(),
             is_https,
             should_close: !req.inner.keep_alive(),
             send_error_response: true,
@@ -99,6 +108,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
             max_idle_count,
+            cache_task: None,
             started: false,
         }
     }
@@ -138,6 +148,23 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.should_close = true;
     }
 
+    async fn reply_gateway_timeout<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut rsp = HttpProxyClientResponse::from_standard(
+            StatusCode::GATEWAY_TIMEOUT,
+            self.req.version,
+            self.should_close,
+        );
+        self.enable_custom_header_for_local_reply(&mut rsp);
+        if rsp.reply_err_to_request(clt_w).await.is_ok() {
+            self.http_notes.rsp_status = rsp.status();
+        } else {
+            self.should_close = true;
+        }
+    }
+
     async fn reply_connect_err<W>(&mut self, e: &TcpConnectError, clt_w: &mut W)
     where
         W: AsyncWrite + Unpin,
@@ -201,7 +228,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             .map(|v| v.to_str());
         Some(TaskLogForHttpForward {
             logger,
-            upstream: self.host.config.upstream(),
+            upstream: self.peer.addr(),
             task_notes: &self.task_notes,
             http_notes: &self.http_notes,
             http_user_agent,
@@ -249,6 +276,14 @@ impl<'a> HttpRProxyForwardTask<'a> {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(self.peer.addr()),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -469,7 +504,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 }
             }
 
-            let action = user_ctx.check_upstream(self.host.config.upstream());
+            let action = user_ctx.check_upstream(self.peer.addr());
             self.handle_user_upstream_acl_action(action, clt_w).await?;
 
             if let Some(action) = user_ctx.check_http_user_agent(&self.req.end_to_end_headers) {
@@ -495,7 +530,36 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
-        fwd_ctx.prepare_connection(self.host.config.upstream(), self.is_https);
+        if let Some(cache) = self.ctx.http_cache.clone() {
+            let rewrite_user = http_header::request_rewrite_user(
+                &self.ctx.server_config.header_rewrite,
+                self.req_host,
+                self.req.uri.path(),
+                self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            );
+            let key = crate::module::http_forward::http_cache_key(
+                self.is_https,
+                self.req_host,
+                self.req,
+                rewrite_user,
+            );
+            match cache.lookup(key, self.req).await {
+                HttpCacheLookup::Bypass => {}
+                HttpCacheLookup::Miss(cache_task) => self.cache_task = Some(cache_task),
+                HttpCacheLookup::Hit(entry) => {
+                    self.send_cached_response(clt_w, &entry).await?;
+                    self.save_or_close(fwd_ctx, clt_w, None).await;
+                    return Ok(());
+                }
+                HttpCacheLookup::Unavailable => {
+                    self.reply_gateway_timeout(clt_w).await;
+                    self.save_or_close(fwd_ctx, clt_w, None).await;
+                    return Ok(());
+                }
+            }
+        }
+
+        fwd_ctx.prepare_connection(self.peer.addr(), self.is_https);
 
         if let Some(mut connection) = fwd_ctx
             .get_alive_connection(
@@ -519,9 +583,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 log_ctx.log_connected();
             }
 
-            connection
-                .0
-                .prepare_new(&self.task_notes, self.host.config.upstream());
+            connection.0.prepare_new(&self.task_notes, self.peer.addr());
             self.mark_relaying();
 
             let r = self
@@ -613,9 +675,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                     log_ctx.log_connected();
                 }
 
-                connection
-                    .0
-                    .prepare_new(&self.task_notes, self.host.config.upstream());
+                connection.0.prepare_new(&self.task_notes, self.peer.addr());
                 self.mark_relaying();
                 Ok(connection)
             }
@@ -632,20 +692,20 @@ impl<'a> HttpRProxyForwardTask<'a> {
         &self,
         fwd_ctx: &mut BoxHttpForwardContext,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        if let Some(tls_client) = &self.host.tls_client {
+        if let Some(tls_client) = &self.upstream.tls_client {
             let task_conf = TlsConnectTaskConf {
                 tcp: TcpConnectTaskConf {
-                    upstream: self.host.config.upstream(),
+                    upstream: self.peer.addr(),
                 },
                 tls_config: tls_client,
-                tls_name: &self.host.config.tls_name,
+                tls_name: &self.peer.tls_name,
             };
             fwd_ctx
                 .make_new_https_connection(&task_conf, &self.task_notes, self.task_stats.clone())
                 .await
         } else {
             let task_conf = TcpConnectTaskConf {
-                upstream: self.host.config.upstream(),
+                upstream: self.peer.addr(),
             };
             fwd_ctx
                 .make_new_http_connection(&task_conf, &self.task_notes, self.task_stats.clone())
@@ -764,8 +824,12 @@ impl<'a> HttpRProxyForwardTask<'a> {
         let ups_r = &mut ups_c.1;
 
         self.http_notes.retry_new_connection = true;
+        let revalidate_req = self
+            .cache_task
+            .as_ref()
+            .and_then(|t| t.revalidate_request(self.req));
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(revalidate_req.as_ref().unwrap_or(self.req), None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -805,13 +869,119 @@ impl<'a> HttpRProxyForwardTask<'a> {
         };
         self.http_notes.mark_rsp_recv_hdr();
 
-        self.update_response_header(&mut rsp_header);
-        self.send_response(clt_w, ups_r, &rsp_header).await?;
+        if let Some(cache_task) = self.cache_task.take() {
+            self.send_response_with_cache(clt_w, ups_r, &mut rsp_header, cache_task)
+                .await?;
+        } else {
+            self.update_response_header(&mut rsp_header);
+            self.send_response(clt_w, ups_r, &rsp_header).await?;
+        }
 
         self.task_notes.stage = ServerTaskStage::Finished;
         Ok(Some(ups_c))
     }
 
+    async fn send_response_with_cache<R, W>(
+        &mut self,
+        clt_w: &mut W,
+        ups_r: &mut R,
+        rsp_header: &mut HttpForwardRemoteResponse,
+        cache_task: HttpCacheTask,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        match cache_task.check_response(self.req, rsp_header) {
+            HttpCacheResponseAction::Refresh => {
+                if let Some(entry) = cache_task.refresh(rsp_header) {
+                    if !rsp_header.keep_alive() {
+                        self.should_close = true;
+                    }
+                    self.http_notes.origin_status = rsp_header.code;
+                    self.http_notes.mark_rsp_no_body();
+                    return self.send_cached_response(clt_w, &entry).await;
+                }
+            }
+            HttpCacheResponseAction::Store(size) => {
+                let store = cache_task.store(self.req, rsp_header, size);
+
+                self.update_response_header(rsp_header);
+                if !rsp_header.keep_alive() {
+                    self.should_close = true;
+                }
+                self.send_error_response = false;
+                self.http_notes.origin_status = rsp_header.code;
+
+                let Some(body_type) = rsp_header.body_type(&self.req.method) else {
+                    store.finish();
+                    self.send_response_header(clt_w, rsp_header).await?;
+                    self.http_notes.rsp_status = rsp_header.code;
+                    self.http_notes.mark_rsp_no_body();
+                    return Ok(());
+                };
+                let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
+                rsp_header.serialize_to(&mut buf);
+                self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
+
+                let body_reader =
+                    HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+                let mut cache_reader = HttpCacheBodyReader::new(body_reader, store);
+                self.relay_response_body(buf, clt_w, &mut cache_reader)
+                    .await?;
+                cache_reader.finish();
+                return Ok(());
+            }
+            HttpCacheResponseAction::Pass => {}
+        }
+
+        self.update_response_header(rsp_header);
+        self.send_response(clt_w, ups_r, rsp_header).await
+    }
+
+    async fn send_cached_response<W>(
+        &mut self,
+        clt_w: &mut W,
+        entry: &HttpCacheEntry,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut headers = entry.response_headers();
+        http_header::rewrite_response(
+            &self.ctx.server_config.header_rewrite,
+            self.req_host,
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut headers,
+        );
+
+        let (code, reason) = if entry.is_not_modified_for(&self.req.end_to_end_headers) {
+            (304, "Not Modified")
+        } else {
+            (entry.code(), entry.reason())
+        };
+        let mut buf = crate::module::http_forward::serialize_response_header(
+            self.req.version,
+            code,
+            reason,
+            &headers,
+            !self.should_close,
+        );
+        if code != 304 && self.req.method != Method::HEAD {
+            buf.extend_from_slice(entry.body());
+        }
+
+        self.send_error_response = false;
+        clt_w
+            .write_all_flush(&buf)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.http_notes.rsp_status = code;
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
     async fn send_full_req_and_recv_rsp(
         &mut self,
         body: &[u8],
@@ -1022,6 +1192,10 @@ impl<'a> HttpRProxyForwardTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -1160,16 +1334,25 @@ impl<'a> HttpRProxyForwardTask<'a> {
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
-        let header_len = header.len() as u64;
         let mut body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        self.relay_response_body(header, clt_w, &mut body_reader)
+            .await
+    }
 
-        let mut ups_to_clt = StreamCopy::with_data(
-            &mut body_reader,
-            clt_w,
-            &self.ctx.server_config.tcp_copy,
-            header,
-        );
+    async fn relay_response_body<R, W>(
+        &mut self,
+        header: Vec<u8>,
+        clt_w: &mut W,
+        body_reader: &mut R,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let header_len = header.len() as u64;
+        let mut ups_to_clt =
+            StreamCopy::with_data(body_reader, clt_w, &self.ctx.server_config.tcp_copy, header);
 
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut log_interval = self.ctx.get_log_interval();
@@ -1234,6 +1417,13 @@ impl<'a> HttpRProxyForwardTask<'a> {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        if ups_to_clt.copied_size() < header_len {
+                            let _ = ups_to_clt.write_flush().await; // flush rsp header to client
+                        }
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         if ups_to_clt.copied_size() < header_len {
                             let _ = ups_to_clt.write_flush().await; // flush rsp header to client
@@ -1253,6 +1443,14 @@ impl<'a> HttpRProxyForwardTask<'a> {
         if let Some(_server_id) = &self.ctx.server_config.server_id {
             // TODO custom header
         }
+
+        http_header::rewrite_response(
+            &self.ctx.server_config.header_rewrite,
+            self.req_host,
+            self.req.uri.path(),
+            self.task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut rsp.end_to_end_headers,
+        );
     }
 
     async fn send_response_header<W>(
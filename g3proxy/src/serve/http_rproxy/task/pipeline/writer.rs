@@ -25,7 +25,8 @@ use crate::audit::AuditContext;
 use crate::auth::{UserContext, UserGroup, UserRequestStats};
 use crate::config::server::ServerConfig;
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
-use crate::serve::http_rproxy::host::HttpHost;
+use crate::module::http_header;
+use crate::serve::http_rproxy::{HttpHost, HttpUpstream, HttpUpstreamPeer};
 use crate::serve::{ServerStats, ServerTaskNotes};
 
 struct UserData {
@@ -114,7 +115,7 @@ where
         }
     }
 
-    fn do_auth(
+    async fn do_auth(
         &mut self,
         req: &HttpRProxyRequest<CDR>,
     ) -> Result<Option<UserContext>, UserAuthError> {
@@ -132,12 +133,31 @@ where
                         )
                     })
                     .ok_or(UserAuthError::NoUserSupplied)?,
-                HttpAuth::Basic(v) => user_group.check_user_with_password(
-                    v.username.as_original(),
-                    &v.password,
-                    self.ctx.server_config.name(),
-                    self.ctx.server_stats.share_extra_tags(),
-                )?,
+                HttpAuth::Basic(v) => {
+                    user_group
+                        .check_user_with_password(
+                            v.username.as_original(),
+                            &v.password,
+                            self.ctx.client_addr(),
+                            Some(&req.upstream),
+                            self.ctx.server_config.name(),
+                            self.ctx.server_stats.share_extra_tags(),
+                        )
+                        .await?
+                }
+                HttpAuth::Bearer(v) => {
+                    user_group
+                        .check_user_with_token(
+                            &v.token,
+                            self.ctx.client_addr(),
+                            Some(&req.upstream),
+                            self.ctx.server_config.name(),
+                            self.ctx.server_stats.share_extra_tags(),
+                        )
+                        .await?
+                }
+                // negotiate auth is only supported in http_proxy server
+                HttpAuth::Negotiate(_) => return Err(UserAuthError::NoUserSupplied),
             };
             user_ctx.check_client_addr(self.ctx.client_addr())?;
 
@@ -182,26 +202,27 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
+                    let res = match self.do_auth(&req).await {
                         Ok(user_ctx) => {
                             self.req_count.consequent_auth_failed = 0;
 
-                            match hosts.get(req.upstream.host()).cloned() {
-                                Some(host) => self.run(req, user_ctx, host).await,
+                            match hosts.get(req.upstream.host()) {
+                                Some(host) => match host.select_upstream(&req.inner).cloned() {
+                                    Some(upstream) => self.run(req, user_ctx, upstream).await,
+                                    None => {
+                                        // close the connection if no route matched
+                                        let rsp = HttpProxyClientResponse::resource_not_found(
+                                            req.inner.version,
+                                            true,
+                                        );
+                                        self.reply_invalid(rsp).await
+                                    }
+                                },
                                 None => {
                                     // close the connection if no host config found
-                                    self.req_count.invalid += 1;
-
-                                    if !self.ctx.server_config.no_early_error_reply
-                                        && let Some(stream_w) = &mut self.stream_writer
-                                    {
-                                        let rsp =
-                                            HttpProxyClientResponse::bad_request(req.inner.version);
-                                        let _ = rsp.reply_err_to_request(stream_w).await;
-                                    }
-
-                                    self.notify_reader_to_close();
-                                    LoopAction::Break
+                                    let rsp =
+                                        HttpProxyClientResponse::bad_request(req.inner.version);
+                                    self.reply_invalid(rsp).await
                                 }
                             }
                         }
@@ -237,27 +258,52 @@ where
         }
     }
 
+    async fn reply_invalid(&mut self, rsp: HttpProxyClientResponse) -> LoopAction {
+        self.req_count.invalid += 1;
+
+        if !self.ctx.server_config.no_early_error_reply
+            && let Some(stream_w) = &mut self.stream_writer
+        {
+            let _ = rsp.reply_err_to_request(stream_w).await;
+        }
+
+        self.notify_reader_to_close();
+        LoopAction::Break
+    }
+
     async fn run(
         &mut self,
-        req: HttpRProxyRequest<CDR>,
+        mut req: HttpRProxyRequest<CDR>,
         user_ctx: Option<UserContext>,
-        host: Arc<HttpHost>,
+        upstream: Arc<HttpUpstream>,
     ) -> LoopAction {
         let task_notes = ServerTaskNotes::new(
             self.ctx.cc_info.clone(),
             user_ctx,
             req.time_accepted.elapsed(),
         );
+        http_header::rewrite_request(
+            &self.ctx.server_config.header_rewrite,
+            req.upstream.host(),
+            req.inner.uri.path(),
+            task_notes.user_ctx().map(|c| c.user_name().as_str()),
+            &mut req.inner.end_to_end_headers,
+        );
 
         if let Some(mut stream_w) = self.stream_writer.take() {
+            let peer = upstream.select_peer(&self.ctx.cc_info);
+
             let mut audit_ctx = AuditContext::default();
             // check in final escaper so we can use route escapers
             let _ = self
                 .forward_context
-                .check_in_final_escaper(&task_notes, host.config.upstream(), &mut audit_ctx)
+                .check_in_final_escaper(&task_notes, peer.addr(), &mut audit_ctx)
                 .await;
 
-            match self.run_forward(&mut stream_w, req, host, task_notes).await {
+            match self
+                .run_forward(&mut stream_w, req, upstream, peer, task_notes)
+                .await
+            {
                 LoopAction::Continue => {
                     self.reset_client_writer(stream_w);
                     LoopAction::Continue
@@ -373,7 +419,8 @@ where
         &mut self,
         clt_w: &mut HttpClientWriter<CDW>,
         mut req: HttpRProxyRequest<CDR>,
-        host: Arc<HttpHost>,
+        upstream: Arc<HttpUpstream>,
+        peer: Arc<HttpUpstreamPeer>,
         task_notes: ServerTaskNotes,
     ) -> LoopAction {
         match req.body_reader.take() {
@@ -381,7 +428,7 @@ where
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, upstream, peer, task_notes);
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
@@ -403,7 +450,7 @@ where
             None => {
                 // no body, and the connection is expected to keep alive from the client side
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, upstream, peer, task_notes);
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
@@ -53,6 +53,9 @@ where
                 "CONNECT".to_string(),
             ));
         }
+        if req.upgrade.is_some() {
+            return Err(HttpRequestParseError::UpgradeIsNotSupported);
+        }
 
         let upstream = if let Some(mut host) = req.host.clone() {
             if let Some(u) = get_upstream_from_uri(&req.uri)? {
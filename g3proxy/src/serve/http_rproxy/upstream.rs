@@ -0,0 +1,298 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwapOption;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::collection::{SelectivePickPolicy, SelectiveVec, SelectiveVecBuilder, WeightedValue};
+use g3_types::metrics::NodeName;
+use g3_types::net::{Host, OpensslClientConfig, UpstreamAddr};
+
+use super::HttpUpstreamPeerStats;
+use crate::config::server::http_rproxy::HttpUpstreamConfig;
+
+pub(crate) struct HttpUpstreamPeer {
+    addr: UpstreamAddr,
+    pub(super) tls_name: Host,
+    pub(super) stats: Arc<HttpUpstreamPeerStats>,
+}
+
+impl Hash for HttpUpstreamPeer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr.hash(state);
+    }
+}
+
+impl HttpUpstreamPeer {
+    #[inline]
+    pub(super) fn addr(&self) -> &UpstreamAddr {
+        &self.addr
+    }
+
+    #[inline]
+    pub(super) fn is_healthy(&self) -> bool {
+        self.stats.is_healthy()
+    }
+}
+
+type HttpUpstreamPeerNode = WeightedValue<Arc<HttpUpstreamPeer>>;
+
+/// the server level context to build the upstreams
+pub(super) struct HttpUpstreamBuildContext {
+    server: NodeName,
+    escaper: NodeName,
+    /// upstreams of the old server on reload, keyed by the peer list
+    old_upstreams: AHashMap<Vec<UpstreamAddr>, Arc<HttpUpstream>>,
+}
+
+impl HttpUpstreamBuildContext {
+    pub(super) fn new(server: &NodeName, escaper: &NodeName) -> Self {
+        HttpUpstreamBuildContext {
+            server: server.clone(),
+            escaper: escaper.clone(),
+            old_upstreams: AHashMap::new(),
+        }
+    }
+
+    pub(super) fn add_old_upstream(&mut self, upstream: &Arc<HttpUpstream>) {
+        self.old_upstreams
+            .insert(upstream.peer_addrs(), upstream.clone());
+    }
+}
+
+pub(crate) struct HttpUpstream {
+    peers: Vec<HttpUpstreamPeerNode>,
+    pick_policy: SelectivePickPolicy,
+    all_nodes: SelectiveVec<HttpUpstreamPeerNode>,
+    healthy_nodes: ArcSwapOption<SelectiveVec<HttpUpstreamPeerNode>>,
+    pub(super) tls_client: Option<OpensslClientConfig>,
+    pub(super) escaper: NodeName,
+}
+
+impl HttpUpstream {
+    pub(super) fn try_build(
+        config: &HttpUpstreamConfig,
+        ctx: &HttpUpstreamBuildContext,
+    ) -> anyhow::Result<Arc<Self>> {
+        let tls_client = if let Some(builder) = &config.tls_client_builder {
+            let client = builder.build().context("failed to build tls client")?;
+            Some(client)
+        } else {
+            None
+        };
+
+        let mut peers = Vec::with_capacity(config.upstream().len());
+        for node in config.upstream() {
+            let addr = node.inner().clone();
+            let tls_name = config
+                .tls_name
+                .clone()
+                .unwrap_or_else(|| addr.host().clone());
+            let stats = Arc::new(HttpUpstreamPeerStats::new(&ctx.server, &addr));
+            crate::stat::http_upstream::push_peer_stats(stats.clone());
+            let peer = HttpUpstreamPeer {
+                addr,
+                tls_name,
+                stats,
+            };
+            peers.push(WeightedValue::with_weight(Arc::new(peer), node.weight()));
+        }
+
+        let all_nodes = SelectiveVecBuilder::with_inner(peers.clone())
+            .build()
+            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+        let healthy_nodes = SelectiveVecBuilder::with_inner(peers.clone())
+            .build()
+            .map(Arc::new);
+
+        let upstream = Arc::new(HttpUpstream {
+            peers,
+            pick_policy: config.upstream_pick_policy,
+            all_nodes,
+            healthy_nodes: ArcSwapOption::new(healthy_nodes),
+            tls_client,
+            escaper: ctx.escaper.clone(),
+        });
+        if let Some(health_check) = &config.health_check {
+            if let Some(old) = ctx.old_upstreams.get(&upstream.peer_addrs()) {
+                // keep the health state as the peers are not changed,
+                // it will be updated by the new health check tasks
+                for (node, old_node) in upstream.peers.iter().zip(old.peers.iter()) {
+                    node.inner()
+                        .stats
+                        .set_healthy(old_node.inner().is_healthy());
+                }
+                upstream.update_healthy_nodes();
+            }
+            super::health_check::spawn(&upstream, health_check);
+        }
+        Ok(upstream)
+    }
+
+    #[inline]
+    pub(super) fn peers(&self) -> &[HttpUpstreamPeerNode] {
+        &self.peers
+    }
+
+    fn peer_addrs(&self) -> Vec<UpstreamAddr> {
+        self.peers.iter().map(|n| n.inner().addr.clone()).collect()
+    }
+
+    /// select a peer from the healthy ones, or from all peers if none of them is healthy
+    pub(super) fn select_peer(&self, cc_info: &ClientConnectionInfo) -> Arc<HttpUpstreamPeer> {
+        #[derive(Hash)]
+        struct ConsistentKey {
+            client_ip: IpAddr,
+            server_ip: IpAddr,
+        }
+
+        let healthy_nodes = self.healthy_nodes.load();
+        let nodes = healthy_nodes.as_deref().unwrap_or(&self.all_nodes);
+        let key = ConsistentKey {
+            client_ip: cc_info.client_ip(),
+            server_ip: cc_info.server_ip(),
+        };
+        let node = match self.pick_policy {
+            SelectivePickPolicy::Random => nodes.pick_random(),
+            SelectivePickPolicy::Serial => nodes.pick_serial(),
+            SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
+            SelectivePickPolicy::Ketama => nodes.pick_ketama(&key),
+            SelectivePickPolicy::Rendezvous => nodes.pick_rendezvous(&key),
+            SelectivePickPolicy::JumpHash => nodes.pick_jump(&key),
+        };
+        let peer = node.inner().clone();
+        peer.stats.add_selected();
+        peer
+    }
+
+    pub(super) fn update_healthy_nodes(&self) {
+        let mut builder = SelectiveVecBuilder::with_capacity(self.peers.len());
+        for node in &self.peers {
+            if node.inner().is_healthy() {
+                builder.insert(node.clone());
+            }
+        }
+        self.healthy_nodes.store(builder.build().map(Arc::new));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use yaml_rust::YamlLoader;
+
+    use crate::config::server::http_rproxy::HttpRouteConfig;
+
+    #[test]
+    fn select_healthy() {
+        let docs = YamlLoader::load_from_str(
+            r#"
+              upstream:
+                - 127.0.0.1:8080
+                - addr: 127.0.0.1:8081
+                  weight: 2
+              upstream_pick_policy: rr
+            "#,
+        )
+        .unwrap();
+        let config = HttpRouteConfig::parse_yaml(&docs[0], None).unwrap();
+        let name = NodeName::new_static("test");
+        let ctx = HttpUpstreamBuildContext::new(&name, &name);
+        let upstream = HttpUpstream::try_build(&config.upstream, &ctx).unwrap();
+        assert_eq!(upstream.peers().len(), 2);
+
+        let cc_info = ClientConnectionInfo::new(
+            SocketAddr::from_str("192.168.1.1:10000").unwrap(),
+            SocketAddr::from_str("192.168.1.2:80").unwrap(),
+        );
+
+        let peer0 = upstream.peers()[0].inner();
+        peer0.stats.set_healthy(false);
+        upstream.update_healthy_nodes();
+        for _ in 0..4 {
+            assert_eq!(upstream.select_peer(&cc_info).addr().port(), 8081);
+        }
+
+        // use all peers if none is healthy
+        let peer1 = upstream.peers()[1].inner();
+        peer1.stats.set_healthy(false);
+        upstream.update_healthy_nodes();
+        let mut ports: Vec<u16> = (0..4)
+            .map(|_| upstream.select_peer(&cc_info).addr().port())
+            .collect();
+        ports.sort_unstable();
+        ports.dedup();
+        assert_eq!(ports, vec![8080, 8081]);
+
+        peer0.stats.set_healthy(true);
+        upstream.update_healthy_nodes();
+        for _ in 0..4 {
+            assert_eq!(upstream.select_peer(&cc_info).addr().port(), 8080);
+        }
+    }
+
+    fn build_with_old(yaml: &str, old: &Arc<HttpUpstream>) -> Arc<HttpUpstream> {
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let config = HttpRouteConfig::parse_yaml(&docs[0], None).unwrap();
+        let name = NodeName::new_static("test");
+        let mut ctx = HttpUpstreamBuildContext::new(&name, &name);
+        ctx.add_old_upstream(old);
+        HttpUpstream::try_build(&config.upstream, &ctx).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reload_health_state() {
+        const WITH_HEALTH_CHECK: &str = r#"
+          upstream:
+            - 127.0.0.1:8080
+            - 127.0.0.1:8081
+          health_check:
+            protocol: tcp
+            interval: 1h
+        "#;
+        const WITHOUT_HEALTH_CHECK: &str = r#"
+          upstream:
+            - 127.0.0.1:8080
+            - 127.0.0.1:8081
+          upstream_pick_policy: rr
+        "#;
+
+        let docs = YamlLoader::load_from_str(WITH_HEALTH_CHECK).unwrap();
+        let config = HttpRouteConfig::parse_yaml(&docs[0], None).unwrap();
+        let name = NodeName::new_static("test");
+        let ctx = HttpUpstreamBuildContext::new(&name, &name);
+        let old = HttpUpstream::try_build(&config.upstream, &ctx).unwrap();
+        old.peers()[0].inner().stats.set_healthy(false);
+        old.update_healthy_nodes();
+
+        // the health state is kept if health check is still enabled
+        let upstream = build_with_old(WITH_HEALTH_CHECK, &old);
+        assert!(!upstream.peers()[0].inner().is_healthy());
+        assert!(upstream.peers()[1].inner().is_healthy());
+
+        // all peers should be healthy if health check is removed
+        let upstream = build_with_old(WITHOUT_HEALTH_CHECK, &old);
+        assert!(upstream.peers()[0].inner().is_healthy());
+        assert!(upstream.peers()[1].inner().is_healthy());
+        let cc_info = ClientConnectionInfo::new(
+            SocketAddr::from_str("192.168.1.1:10000").unwrap(),
+            SocketAddr::from_str("192.168.1.2:80").unwrap(),
+        );
+        let mut ports: Vec<u16> = (0..4)
+            .map(|_| upstream.select_peer(&cc_info).addr().port())
+            .collect();
+        ports.sort_unstable();
+        ports.dedup();
+        assert_eq!(ports, vec![8080, 8081]);
+    }
+}
@@ -7,12 +7,14 @@ use std::sync::Arc;
 
 use g3_io_ext::{IdleCheck, IdleForceQuitReason, IdleInterval, IdleWheel};
 
+use super::LiveTask;
 use super::ServerQuitPolicy;
 use crate::auth::User;
 
 pub(crate) struct ServerIdleChecker {
     pub(crate) idle_wheel: Arc<IdleWheel>,
     pub(crate) user: Option<Arc<User>>,
+    pub(crate) live_task: Option<Arc<LiveTask>>,
     pub(crate) max_idle_count: usize,
     pub(crate) server_quit_policy: Arc<ServerQuitPolicy>,
 }
@@ -21,6 +23,7 @@ impl ServerIdleChecker {
     pub(crate) fn new(
         idle_wheel: Arc<IdleWheel>,
         user: Option<Arc<User>>,
+        live_task: Option<Arc<LiveTask>>,
         task_max_idle_count: usize,
         server_quit_policy: Arc<ServerQuitPolicy>,
     ) -> Self {
@@ -31,6 +34,7 @@ impl ServerIdleChecker {
         ServerIdleChecker {
             idle_wheel,
             user,
+            live_task,
             max_idle_count,
             server_quit_policy,
         }
@@ -53,6 +57,12 @@ impl IdleCheck for ServerIdleChecker {
             return Some(IdleForceQuitReason::UserBlocked);
         }
 
+        if let Some(task) = &self.live_task
+            && task.is_killed()
+        {
+            return Some(IdleForceQuitReason::TaskKilled);
+        }
+
         if self.server_quit_policy.force_quit() {
             return Some(IdleForceQuitReason::ServerQuit);
         }
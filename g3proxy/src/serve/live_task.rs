@@ -0,0 +1,259 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ahash::AHashMap;
+use arcstr::ArcStr;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use g3_daemon::stat::task::TcpStreamTaskStats;
+use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
+
+use super::ServerTaskNotes;
+
+/// the client side traffic of a live task
+pub(crate) trait LiveTaskTraffic {
+    fn client_read_bytes(&self) -> u64;
+    fn client_write_bytes(&self) -> u64;
+}
+
+pub(crate) type ArcLiveTaskTraffic = Arc<dyn LiveTaskTraffic + Send + Sync>;
+
+impl LiveTaskTraffic for TcpStreamTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.read.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.write.get_bytes()
+    }
+}
+
+/// a running task, which can be listed and killed via the control socket
+pub(crate) struct LiveTask {
+    id: Uuid,
+    server: NodeName,
+    user: Option<ArcStr>,
+    client_addr: SocketAddr,
+    start_at: DateTime<Utc>,
+    escaper: NodeName,
+    upstream: Option<UpstreamAddr>,
+    traffic: ArcLiveTaskTraffic,
+    killed: AtomicBool,
+}
+
+impl LiveTask {
+    pub(crate) fn new(
+        task_notes: &ServerTaskNotes,
+        server: &NodeName,
+        escaper: &NodeName,
+        upstream: Option<&UpstreamAddr>,
+        traffic: ArcLiveTaskTraffic,
+    ) -> Self {
+        LiveTask {
+            id: task_notes.id,
+            server: server.clone(),
+            user: task_notes.raw_user_name().cloned(),
+            client_addr: task_notes.client_addr(),
+            start_at: task_notes.start_at,
+            escaper: escaper.clone(),
+            upstream: upstream.cloned(),
+            traffic,
+            killed: AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    #[inline]
+    pub(crate) fn server(&self) -> &NodeName {
+        &self.server
+    }
+
+    #[inline]
+    pub(crate) fn user(&self) -> Option<&ArcStr> {
+        self.user.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn client_addr(&self) -> SocketAddr {
+        self.client_addr
+    }
+
+    #[inline]
+    pub(crate) fn start_at(&self) -> &DateTime<Utc> {
+        &self.start_at
+    }
+
+    #[inline]
+    pub(crate) fn escaper(&self) -> &NodeName {
+        &self.escaper
+    }
+
+    #[inline]
+    pub(crate) fn upstream(&self) -> Option<&UpstreamAddr> {
+        self.upstream.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn traffic(&self) -> &ArcLiveTaskTraffic {
+        &self.traffic
+    }
+
+    fn kill(&self) {
+        self.killed.store(true, Ordering::Relaxed);
+    }
+
+    /// the task should quit at the next idle check if it has been killed
+    #[inline]
+    pub(crate) fn is_killed(&self) -> bool {
+        self.killed.load(Ordering::Relaxed)
+    }
+}
+
+/// all live tasks of a server or a user
+#[derive(Default)]
+pub(crate) struct LiveTaskRegistry {
+    tasks: Mutex<AHashMap<Uuid, Arc<LiveTask>>>,
+}
+
+impl LiveTaskRegistry {
+    fn add(&self, task: &Arc<LiveTask>) {
+        self.tasks.lock().unwrap().insert(task.id, task.clone());
+    }
+
+    fn del(&self, id: &Uuid) {
+        self.tasks.lock().unwrap().remove(id);
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.tasks.lock().unwrap().len()
+    }
+
+    pub(crate) fn all(&self) -> Vec<Arc<LiveTask>> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut all_tasks: Vec<Arc<LiveTask>> = tasks.values().cloned().collect();
+        all_tasks.sort_by_key(|t| t.start_at);
+        all_tasks
+    }
+
+    pub(crate) fn kill(&self, id: &Uuid) -> bool {
+        let tasks = self.tasks.lock().unwrap();
+        match tasks.get(id) {
+            Some(task) => {
+                task.kill();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn kill_all(&self) -> usize {
+        let tasks = self.tasks.lock().unwrap();
+        for task in tasks.values() {
+            task.kill();
+        }
+        tasks.len()
+    }
+}
+
+/// the registered task, which will be removed from the registries when dropped
+pub(crate) struct LiveTaskHandle {
+    task: Arc<LiveTask>,
+    server_registry: Arc<LiveTaskRegistry>,
+    user_registry: Option<Arc<LiveTaskRegistry>>,
+}
+
+impl LiveTaskHandle {
+    pub(crate) fn register(
+        task: LiveTask,
+        server_registry: &Arc<LiveTaskRegistry>,
+        user_registry: Option<&Arc<LiveTaskRegistry>>,
+    ) -> Self {
+        let task = Arc::new(task);
+        server_registry.add(&task);
+        if let Some(registry) = user_registry {
+            registry.add(&task);
+        }
+        LiveTaskHandle {
+            task,
+            server_registry: server_registry.clone(),
+            user_registry: user_registry.cloned(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn task(&self) -> &Arc<LiveTask> {
+        &self.task
+    }
+}
+
+impl Drop for LiveTaskHandle {
+    fn drop(&mut self) {
+        self.server_registry.del(&self.task.id);
+        if let Some(registry) = &self.user_registry {
+            registry.del(&self.task.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use g3_daemon::server::ClientConnectionInfo;
+
+    use super::*;
+
+    fn new_task(server: &NodeName) -> LiveTask {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1080));
+        let task_notes =
+            ServerTaskNotes::new(ClientConnectionInfo::new(addr, addr), None, Duration::ZERO);
+        LiveTask::new(
+            &task_notes,
+            server,
+            server,
+            None,
+            Arc::new(TcpStreamTaskStats::default()),
+        )
+    }
+
+    #[test]
+    fn register_and_kill() {
+        let server_registry = Arc::new(LiveTaskRegistry::default());
+        let user_registry = Arc::new(LiveTaskRegistry::default());
+        let server = NodeName::new_static("test");
+
+        let h1 =
+            LiveTaskHandle::register(new_task(&server), &server_registry, Some(&user_registry));
+        let id1 = *h1.task().id();
+        let h2 = LiveTaskHandle::register(new_task(&server), &server_registry, None);
+        assert_eq!(server_registry.count(), 2);
+        assert_eq!(user_registry.count(), 1);
+
+        assert!(user_registry.kill(&id1));
+        assert!(h1.task().is_killed());
+        assert!(!h2.task().is_killed());
+        assert!(!server_registry.kill(&Uuid::new_v4()));
+
+        drop(h1);
+        assert_eq!(server_registry.count(), 1);
+        assert!(user_registry.all().is_empty());
+        assert!(!server_registry.kill(&id1));
+
+        assert_eq!(server_registry.kill_all(), 1);
+        assert!(h2.task().is_killed());
+        drop(h2);
+        assert!(server_registry.all().is_empty());
+    }
+}
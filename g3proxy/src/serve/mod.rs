@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -12,7 +13,7 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats};
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ReceiveUdpServer};
 use g3_daemon::server::{
     BaseServer, ClientConnectionInfo, ReloadServer, ServerQuitPolicy, ServerReloadCommand,
 };
@@ -49,11 +50,17 @@ mod tcp_stream;
 ))]
 mod tcp_tproxy;
 mod tls_stream;
+#[cfg(target_os = "linux")]
+mod udp_tproxy;
 
 mod error;
+mod live_task;
 mod task;
 
 pub(crate) use error::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+pub(crate) use live_task::{
+    ArcLiveTaskTraffic, LiveTask, LiveTaskHandle, LiveTaskRegistry, LiveTaskTraffic,
+};
 pub(crate) use task::{ServerTaskNotes, ServerTaskStage};
 
 mod ops;
@@ -65,12 +72,15 @@ pub(crate) use ops::{
 pub use ops::{spawn_all, spawn_offline_clean};
 
 mod stats;
+pub(crate) use http_rproxy::{HttpUpstreamPeerSnapshot, HttpUpstreamPeerStats};
 pub(crate) use stats::{
     ArcServerStats, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
 };
 
 #[async_trait]
-pub(crate) trait Server: BaseServer + AcceptTcpServer + AcceptQuicServer {
+pub(crate) trait Server:
+    BaseServer + AcceptTcpServer + AcceptQuicServer + ReceiveUdpServer
+{
     fn escaper(&self) -> &NodeName;
     fn user_group(&self) -> &NodeName;
     fn auditor(&self) -> &NodeName;
@@ -157,6 +167,19 @@ impl AcceptQuicServer for WrapArcServer {
     }
 }
 
+impl ReceiveUdpServer for WrapArcServer {
+    fn receive_udp_packet(
+        &self,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        self.0
+            .receive_udp_packet(packet, client_addr, server_addr, worker_id)
+    }
+}
+
 fn new_reload_notify_channel() -> broadcast::Sender<ServerReloadCommand> {
     broadcast::Sender::new(16)
 }
@@ -13,7 +13,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats, UdpIoSnapshot, UdpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    LiveTaskRegistry, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats,
+    ServerStats,
 };
 
 pub(crate) struct SocksProxyServerStats {
@@ -27,6 +28,8 @@ pub(crate) struct SocksProxyServerStats {
 
     pub(crate) forbidden: ServerForbiddenStats,
 
+    pub(crate) live_tasks: Arc<LiveTaskRegistry>,
+
     pub(crate) task_tcp_connect: ServerPerTaskStats,
     pub(crate) task_udp_associate: ServerPerTaskStats,
     pub(crate) task_udp_connect: ServerPerTaskStats,
@@ -44,6 +47,7 @@ impl SocksProxyServerStats {
             online: AtomicIsize::new(0),
             conn_total: AtomicU64::new(0),
             forbidden: Default::default(),
+            live_tasks: Arc::new(LiveTaskRegistry::default()),
             task_tcp_connect: Default::default(),
             task_udp_associate: Default::default(),
             task_udp_connect: Default::default(),
@@ -124,4 +128,8 @@ impl ServerStats for SocksProxyServerStats {
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot {
         self.forbidden.snapshot()
     }
+
+    fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        Some(&self.live_tasks)
+    }
 }
@@ -129,6 +129,14 @@ impl SocksProxyTcpConnectTask {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -517,4 +525,8 @@ impl StreamTransitTask for SocksProxyTcpConnectTask {
     fn user(&self) -> Option<&User> {
         self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
+
+    fn task_killed(&self) -> bool {
+        self.task_notes.is_killed()
+    }
 }
@@ -8,6 +8,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use g3_daemon::stat::task::UdpConnectHalfConnectionStats;
 
 use crate::module::udp_relay::UdpRelayTaskRemoteStats;
+use crate::serve::LiveTaskTraffic;
 
 #[derive(Default)]
 pub(crate) struct UdpAssociateClientSideStats {
@@ -68,3 +69,13 @@ impl UdpRelayTaskRemoteStats for UdpAssociateTaskStats {
         self.ups.send.add_packets(n);
     }
 }
+
+impl LiveTaskTraffic for UdpAssociateTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.recv.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.send.get_bytes()
+    }
+}
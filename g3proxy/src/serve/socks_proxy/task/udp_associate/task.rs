@@ -132,6 +132,14 @@ impl SocksProxyUdpAssociateTask {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            None,
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -358,6 +366,10 @@ impl SocksProxyUdpAssociateTask {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
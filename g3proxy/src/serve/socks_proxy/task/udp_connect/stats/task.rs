@@ -6,6 +6,7 @@
 use g3_daemon::stat::task::UdpConnectConnectionStats;
 
 use crate::module::udp_connect::UdpConnectTaskRemoteStats;
+use crate::serve::LiveTaskTraffic;
 
 #[derive(Default)]
 pub(crate) struct UdpConnectTaskStats {
@@ -30,3 +31,13 @@ impl UdpConnectTaskRemoteStats for UdpConnectTaskStats {
         self.ups.send.add_packets(n);
     }
 }
+
+impl LiveTaskTraffic for UdpConnectTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.recv.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.send.get_bytes()
+    }
+}
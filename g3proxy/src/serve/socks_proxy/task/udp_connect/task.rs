@@ -82,11 +82,12 @@ impl SocksProxyUdpConnectTask {
             .map(|logger| TaskLogForUdpConnect {
                 logger,
                 task_notes: &self.task_notes,
-                tcp_server_addr: self.ctx.server_addr(),
-                tcp_client_addr: self.ctx.client_addr(),
+                tcp_server_addr: Some(self.ctx.server_addr()),
+                tcp_client_addr: Some(self.ctx.client_addr()),
                 udp_listen_addr: self.udp_listen_addr,
                 udp_client_addr: self.udp_client_addr,
                 upstream: self.upstream.as_ref(),
+                sni: None,
                 udp_notes: &self.udp_notes,
                 client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
                 client_rd_packets: self.task_stats.clt.recv.get_packets(),
@@ -131,6 +132,14 @@ impl SocksProxyUdpConnectTask {
             log_ctx.log_created();
         }
 
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            self.upstream.as_ref(),
+            self.task_stats.clone(),
+        );
+
         self.started = true;
     }
 
@@ -407,6 +416,10 @@ impl SocksProxyUdpConnectTask {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
 
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
                     if self.ctx.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -11,6 +11,7 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
+use super::LiveTaskRegistry;
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
 pub(crate) trait ServerStats {
@@ -40,6 +41,11 @@ pub(crate) trait ServerStats {
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         None
     }
+
+    /// the running tasks that can be listed and killed via the control socket
+    fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        None
+    }
 }
 
 pub(crate) type ArcServerStats = Arc<dyn ServerStats + Send + Sync>;
@@ -4,6 +4,7 @@
  */
 
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 use std::time::Duration;
 
 use arcstr::ArcStr;
@@ -14,7 +15,9 @@ use uuid::Uuid;
 use g3_daemon::server::ClientConnectionInfo;
 use g3_types::limit::GaugeSemaphorePermit;
 use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
 
+use super::{ArcLiveTaskTraffic, LiveTask, LiveTaskHandle, LiveTaskRegistry};
 use crate::auth::UserContext;
 use crate::escape::{EgressPathSelection, EgressUpstream};
 
@@ -60,6 +63,7 @@ pub(crate) struct ServerTaskNotes {
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     /// the following fields should not be cloned
     pub(crate) user_req_alive_permit: Option<GaugeSemaphorePermit>,
+    live_task: Option<LiveTaskHandle>,
 }
 
 impl ServerTaskNotes {
@@ -90,6 +94,7 @@ impl ServerTaskNotes {
             ready_time: Duration::default(),
             egress_path_selection,
             user_req_alive_permit: None,
+            live_task: None,
         }
     }
 
@@ -127,6 +132,33 @@ impl ServerTaskNotes {
         self.user_ctx.as_ref().and_then(|c| c.raw_user_name())
     }
 
+    /// register the task so it can be listed and killed via the control socket
+    pub(crate) fn register_live_task(
+        &mut self,
+        server: &NodeName,
+        registry: &Arc<LiveTaskRegistry>,
+        escaper: &NodeName,
+        upstream: Option<&UpstreamAddr>,
+        traffic: ArcLiveTaskTraffic,
+    ) {
+        let task = LiveTask::new(self, server, escaper, upstream, traffic);
+        let user_registry = self.user_ctx.as_ref().and_then(|ctx| ctx.live_tasks());
+        self.live_task = Some(LiveTaskHandle::register(task, registry, user_registry));
+    }
+
+    #[inline]
+    pub(crate) fn live_task(&self) -> Option<&Arc<LiveTask>> {
+        self.live_task.as_ref().map(|h| h.task())
+    }
+
+    /// check if the task has been killed via the control socket
+    pub(crate) fn is_killed(&self) -> bool {
+        self.live_task
+            .as_ref()
+            .map(|h| h.task().is_killed())
+            .unwrap_or(false)
+    }
+
     pub(crate) fn egress_path_number_id(&self, escaper: &NodeName, length: usize) -> Option<usize> {
         if let Some(ctx) = &self.user_ctx
             && let Some(p) = ctx.user_config().egress_path_selection.as_ref()
@@ -12,7 +12,7 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
-use crate::serve::{ServerForbiddenSnapshot, ServerForbiddenStats, ServerStats};
+use crate::serve::{LiveTaskRegistry, ServerForbiddenSnapshot, ServerForbiddenStats, ServerStats};
 
 pub(crate) struct TcpStreamServerStats {
     name: NodeName,
@@ -28,6 +28,7 @@ pub(crate) struct TcpStreamServerStats {
 
     tcp: TcpIoStats,
     pub(crate) forbidden: ServerForbiddenStats,
+    pub(crate) live_tasks: Arc<LiveTaskRegistry>,
 }
 
 impl TcpStreamServerStats {
@@ -42,6 +43,7 @@ impl TcpStreamServerStats {
             task_alive_count: AtomicI32::new(0),
             tcp: Default::default(),
             forbidden: Default::default(),
+            live_tasks: Arc::new(LiveTaskRegistry::default()),
         }
     }
 
@@ -132,4 +134,8 @@ impl ServerStats for TcpStreamServerStats {
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot {
         self.forbidden.snapshot()
     }
+
+    fn live_tasks(&self) -> Option<&Arc<LiveTaskRegistry>> {
+        Some(&self.live_tasks)
+    }
 }
@@ -17,6 +17,7 @@ use super::common::CommonTaskContext;
 use super::stats::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::server::ServerConfig;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
@@ -90,6 +91,14 @@ impl TcpStreamTask {
         {
             log_ctx.log_created();
         }
+
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
     }
 
     async fn run<CR, CW>(&mut self, clt_r: CR, clt_w: CW) -> ServerTaskResult<()>
@@ -283,4 +292,68 @@ impl StreamTransitTask for TcpStreamTask {
     fn user(&self) -> Option<&User> {
         None
     }
+
+    fn task_killed(&self) -> bool {
+        self.task_notes.is_killed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use yaml_rust::YamlLoader;
+
+    use g3_daemon::server::ClientConnectionInfo;
+    use g3_io_ext::IdleWheel;
+
+    use super::*;
+    use crate::config::server::tcp_stream::TcpStreamServerConfig;
+    use crate::serve::tcp_stream::TcpStreamServerStats;
+
+    fn new_task() -> TcpStreamTask {
+        let conf = r#"
+            name: test
+            escaper: test
+            upstream: 127.0.0.1:80
+            task_idle_check_interval: 10ms
+        "#;
+        let doc = YamlLoader::load_from_str(conf).unwrap().pop().unwrap();
+        let server_config = TcpStreamServerConfig::parse(doc.as_hash().unwrap(), None).unwrap();
+        let server_config = Arc::new(server_config);
+        let upstream = server_config.upstream[0].inner().clone();
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1080));
+        let ctx = CommonTaskContext {
+            server_stats: Arc::new(TcpStreamServerStats::new(server_config.name())),
+            server_quit_policy: Arc::new(ServerQuitPolicy::default()),
+            idle_wheel: IdleWheel::spawn(server_config.task_idle_check_interval),
+            escaper: crate::escape::get_or_insert_default(&server_config.escaper),
+            cc_info: ClientConnectionInfo::new(addr, addr),
+            tls_client_config: None,
+            task_logger: None,
+            server_config,
+        };
+        TcpStreamTask::new(ctx, &upstream, AuditContext::new(None))
+    }
+
+    #[tokio::test]
+    async fn relay_killed() {
+        let mut task = new_task();
+        task.pre_start();
+        assert_eq!(task.ctx.server_stats.live_tasks.count(), 1);
+        assert_eq!(task.ctx.server_stats.live_tasks.kill_all(), 1);
+
+        let (clt, _clt_peer) = tokio::io::duplex(1024);
+        let (ups, _ups_peer) = tokio::io::duplex(1024);
+        let (clt_r, clt_w) = tokio::io::split(clt);
+        let (ups_r, ups_w) = tokio::io::split(ups);
+        let r = tokio::time::timeout(
+            Duration::from_secs(5),
+            task.relay(clt_r, clt_w, ups_r, ups_w),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(r, Err(ServerTaskError::CanceledAsTaskKilled)));
+    }
 }
@@ -18,6 +18,7 @@ use g3_types::net::UpstreamAddr;
 use super::common::CommonTaskContext;
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::server::ServerConfig;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
@@ -87,6 +88,14 @@ impl TlsStreamTask {
         {
             log_ctx.log_created();
         }
+
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
     }
 
     async fn run(&mut self, clt_stream: TlsStream<TcpStream>) -> ServerTaskResult<()> {
@@ -272,4 +281,8 @@ impl StreamTransitTask for TlsStreamTask {
     fn user(&self) -> Option<&User> {
         None
     }
+
+    fn task_killed(&self) -> bool {
+        self.task_notes.is_killed()
+    }
 }
@@ -0,0 +1,169 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+/// the client address and the original destination address
+pub(super) type FlowKey = (SocketAddr, SocketAddr);
+
+/// All the active flows of a server, which will be shared by the old and new server on reload
+#[derive(Default)]
+pub(super) struct FlowTable {
+    inner: Mutex<HashMap<FlowKey, mpsc::Sender<Bytes>>>,
+}
+
+pub(super) enum FlowDispatchResult {
+    /// the packet has been sent to the existing flow
+    Queued,
+    /// the packet is dropped as the queue of the existing flow is full
+    QueueFull,
+    /// the packet is dropped as the max number of flows has been reached
+    FlowLimited,
+    Created(FlowHandle, mpsc::Receiver<Bytes>),
+}
+
+impl FlowTable {
+    /// Send the packet to the existing flow, or create a new flow if there is none.
+    ///
+    /// No new flow will be created if there are already `max_flows` flows, 0 means no limit.
+    pub(super) fn dispatch(
+        self: &Arc<Self>,
+        key: FlowKey,
+        packet: &[u8],
+        queue_size: usize,
+        max_flows: usize,
+    ) -> FlowDispatchResult {
+        let mut map = self.inner.lock().unwrap();
+        if let Some(sender) = map.get(&key) {
+            match sender.try_send(Bytes::copy_from_slice(packet)) {
+                Ok(_) => return FlowDispatchResult::Queued,
+                Err(TrySendError::Full(_)) => return FlowDispatchResult::QueueFull,
+                Err(TrySendError::Closed(_)) => {}
+            }
+        } else if max_flows > 0 && map.len() >= max_flows {
+            return FlowDispatchResult::FlowLimited;
+        }
+
+        let (sender, receiver) = mpsc::channel(queue_size);
+        // the receiver is still alive, so this will not fail
+        let _ = sender.try_send(Bytes::copy_from_slice(packet));
+        map.insert(key, sender.clone());
+        let handle = FlowHandle {
+            table: Arc::clone(self),
+            key,
+            sender,
+        };
+        FlowDispatchResult::Created(handle, receiver)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.inner.lock().unwrap().len()
+    }
+}
+
+/// The registered entry will be removed from the flow table on drop
+pub(super) struct FlowHandle {
+    table: Arc<FlowTable>,
+    key: FlowKey,
+    sender: mpsc::Sender<Bytes>,
+}
+
+impl Drop for FlowHandle {
+    fn drop(&mut self) {
+        let mut map = self.table.inner.lock().unwrap();
+        if let Some(sender) = map.get(&self.key)
+            && sender.same_channel(&self.sender)
+        {
+            map.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dispatch() {
+        let table = Arc::new(FlowTable::default());
+        let key = (
+            "192.168.1.2:50000".parse().unwrap(),
+            "1.1.1.1:443".parse().unwrap(),
+        );
+
+        let FlowDispatchResult::Created(handle, mut receiver) = table.dispatch(key, b"1", 2, 0)
+        else {
+            panic!("no new flow created");
+        };
+        assert_eq!(receiver.try_recv().unwrap().as_ref(), b"1");
+        assert!(matches!(
+            table.dispatch(key, b"2", 2, 0),
+            FlowDispatchResult::Queued
+        ));
+        assert!(matches!(
+            table.dispatch(key, b"3", 2, 0),
+            FlowDispatchResult::Queued
+        ));
+        assert!(matches!(
+            table.dispatch(key, b"4", 2, 0),
+            FlowDispatchResult::QueueFull
+        ));
+        assert_eq!(receiver.try_recv().unwrap().as_ref(), b"2");
+        assert_eq!(receiver.try_recv().unwrap().as_ref(), b"3");
+        assert!(receiver.try_recv().is_err());
+
+        // a new flow will be created if the old one is closing
+        drop(receiver);
+        let FlowDispatchResult::Created(new_handle, mut receiver) = table.dispatch(key, b"5", 2, 0)
+        else {
+            panic!("no new flow created");
+        };
+        assert_eq!(receiver.try_recv().unwrap().as_ref(), b"5");
+        drop(handle);
+        assert_eq!(table.len(), 1);
+        drop(new_handle);
+        assert_eq!(table.len(), 0);
+    }
+
+    #[test]
+    fn max_flows() {
+        let table = Arc::new(FlowTable::default());
+        let key1 = (
+            "192.168.1.2:50000".parse().unwrap(),
+            "1.1.1.1:443".parse().unwrap(),
+        );
+        let key2 = (
+            "192.168.1.2:50001".parse().unwrap(),
+            "1.1.1.1:443".parse().unwrap(),
+        );
+
+        let FlowDispatchResult::Created(handle, receiver) = table.dispatch(key1, b"1", 2, 1) else {
+            panic!("no new flow created");
+        };
+        assert!(matches!(
+            table.dispatch(key2, b"1", 2, 1),
+            FlowDispatchResult::FlowLimited
+        ));
+
+        // the closing flow can be replaced even if the limit is reached
+        drop(receiver);
+        let FlowDispatchResult::Created(_new_handle, _receiver) = table.dispatch(key1, b"2", 2, 1)
+        else {
+            panic!("no new flow created");
+        };
+        drop(handle);
+        assert!(matches!(
+            table.dispatch(key2, b"1", 2, 1),
+            FlowDispatchResult::FlowLimited
+        ));
+    }
+}
@@ -0,0 +1,17 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+mod common;
+mod flow;
+#[cfg(feature = "quic")]
+mod quic;
+mod recv;
+mod send;
+mod server;
+mod stats;
+mod task;
+
+pub(crate) use server::UdpTProxyServer;
+use stats::UdpTProxyServerStats;
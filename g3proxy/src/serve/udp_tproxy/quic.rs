@@ -0,0 +1,47 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use g3_dpi::parser::quic::{HandshakeCoalescer, InitialPacket};
+use g3_dpi::parser::tls::ExtensionType;
+use g3_types::net::TlsServerName;
+
+/// Try to get the TLS server name from the QUIC Initial packets sent by the client.
+///
+/// All the received packets will be pushed to `packets`, and the detection will stop
+/// if any of them is not a valid client Initial packet.
+pub(super) async fn detect_server_name(
+    receiver: &mut mpsc::Receiver<Bytes>,
+    packets: &mut Vec<Bytes>,
+    wait_timeout: Duration,
+    max_client_hello_size: u32,
+) -> Option<TlsServerName> {
+    let deadline = Instant::now() + wait_timeout;
+    let mut handshake_coalescer = HandshakeCoalescer::new(max_client_hello_size);
+
+    let mut offset = 0;
+    loop {
+        for p in &packets[offset..] {
+            let initial = InitialPacket::parse_client(p).ok()?;
+            initial.consume_frames(&mut handshake_coalescer).ok()?;
+        }
+        offset = packets.len();
+
+        if let Some(ch) = handshake_coalescer.parse_client_hello().ok()? {
+            let sni = ch.get_ext(ExtensionType::ServerName).ok()??;
+            return TlsServerName::from_extension_value(sni).ok();
+        }
+
+        match tokio::time::timeout_at(deadline, receiver.recv()).await {
+            Ok(Some(p)) => packets.push(p),
+            Ok(None) | Err(_) => return None,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io::IoSliceMut;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+
+use g3_io_ext::{
+    ArcLimitedRecvStats, AsyncUdpRecv, UdpCopyClientError, UdpCopyClientRecv, UdpCopyPacket,
+    UdpCopyPacketMeta,
+};
+use g3_io_sys::udp::RecvMsgHdr;
+
+/// Receive client packets from both the flow queue and the connected reply socket.
+///
+/// The packets will be delivered to the reply socket after it's connected, but some of
+/// them may still be received by the listen socket, and then be sent to the flow queue.
+pub(super) struct UdpTProxyClientRecv<T> {
+    inner: T,
+    queue: mpsc::Receiver<Bytes>,
+    queue_closed: bool,
+    stats: ArcLimitedRecvStats,
+}
+
+impl<T> UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv,
+{
+    pub(super) fn new(inner: T, queue: mpsc::Receiver<Bytes>, stats: ArcLimitedRecvStats) -> Self {
+        UdpTProxyClientRecv {
+            inner,
+            queue,
+            queue_closed: false,
+            stats,
+        }
+    }
+
+    fn poll_recv_queued(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<usize> {
+        if self.queue_closed {
+            return Poll::Pending;
+        }
+        match ready!(self.queue.poll_recv(cx)) {
+            Some(p) => {
+                let len = p.len().min(buf.len());
+                buf[..len].copy_from_slice(&p[..len]);
+                self.stats.add_recv_bytes(len);
+                self.stats.add_recv_packet();
+                Poll::Ready(len)
+            }
+            None => {
+                self.queue_closed = true;
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> UdpCopyClientRecv for UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv + Send,
+{
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        if let Poll::Ready(nr) = self.poll_recv_queued(cx, buf) {
+            return Poll::Ready(Ok((0, nr)));
+        }
+
+        let nr = ready!(self.inner.poll_recv(cx, buf)).map_err(UdpCopyClientError::RecvFailed)?;
+        Poll::Ready(Ok((0, nr)))
+    }
+
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut count = 0;
+        for p in packets.iter_mut() {
+            let Poll::Ready(nr) = self.poll_recv_queued(cx, p.buf_mut()) else {
+                break;
+            };
+            let meta = {
+                let iov = IoSliceMut::new(p.buf_mut());
+                UdpCopyPacketMeta::new(&iov, 0, nr)
+            };
+            meta.set_packet(p);
+            count += 1;
+        }
+        if count > 0 {
+            return Poll::Ready(Ok(count));
+        }
+
+        let mut hdr_v: Vec<RecvMsgHdr<1>> = packets
+            .iter_mut()
+            .map(|p| RecvMsgHdr::new([IoSliceMut::new(p.buf_mut())]))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_recvmsg(cx, &mut hdr_v))
+            .map_err(UdpCopyClientError::RecvFailed)?;
+
+        let mut r = Vec::with_capacity(count);
+        for h in hdr_v.into_iter().take(count) {
+            r.push(UdpCopyPacketMeta::new(&h.iov[0], 0, h.n_recv));
+        }
+        for (m, p) in r.into_iter().zip(packets.iter_mut()) {
+            m.set_packet(p);
+        }
+
+        Poll::Ready(Ok(count))
+    }
+}
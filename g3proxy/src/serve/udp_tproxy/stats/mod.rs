@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+mod server;
+pub(crate) use server::{UdpTProxyServerAliveTaskGuard, UdpTProxyServerStats};
+
+mod task;
+pub(super) use task::UdpTProxyTaskStats;
+
+mod wrapper;
+pub(super) use wrapper::UdpTProxyTaskCltWrapperStats;
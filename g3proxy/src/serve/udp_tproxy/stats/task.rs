@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use g3_daemon::stat::task::UdpConnectConnectionStats;
+
+use crate::module::udp_connect::UdpConnectTaskRemoteStats;
+use crate::serve::LiveTaskTraffic;
+
+#[derive(Default)]
+pub(crate) struct UdpTProxyTaskStats {
+    pub(crate) clt: UdpConnectConnectionStats,
+    pub(crate) ups: UdpConnectConnectionStats,
+}
+
+impl UdpConnectTaskRemoteStats for UdpTProxyTaskStats {
+    fn add_recv_bytes(&self, size: u64) {
+        self.ups.recv.add_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.ups.recv.add_packets(n);
+    }
+
+    fn add_send_bytes(&self, size: u64) {
+        self.ups.send.add_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.ups.send.add_packets(n);
+    }
+}
+
+impl LiveTaskTraffic for UdpTProxyTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.recv.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.send.get_bytes()
+    }
+}
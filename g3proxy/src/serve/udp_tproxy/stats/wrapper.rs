@@ -0,0 +1,51 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use g3_io_ext::{LimitedRecvStats, LimitedSendStats};
+
+use super::{UdpTProxyServerStats, UdpTProxyTaskStats};
+
+#[derive(Clone)]
+pub(crate) struct UdpTProxyTaskCltWrapperStats {
+    server: Arc<UdpTProxyServerStats>,
+    task: Arc<UdpTProxyTaskStats>,
+}
+
+impl UdpTProxyTaskCltWrapperStats {
+    pub(crate) fn new(server: &Arc<UdpTProxyServerStats>, task: &Arc<UdpTProxyTaskStats>) -> Self {
+        UdpTProxyTaskCltWrapperStats {
+            server: Arc::clone(server),
+            task: Arc::clone(task),
+        }
+    }
+}
+
+impl LimitedRecvStats for UdpTProxyTaskCltWrapperStats {
+    fn add_recv_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.io_udp.add_in_bytes(size);
+        self.task.clt.recv.add_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.server.io_udp.add_in_packets(n);
+        self.task.clt.recv.add_packets(n);
+    }
+}
+
+impl LimitedSendStats for UdpTProxyTaskCltWrapperStats {
+    fn add_send_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.io_udp.add_out_bytes(size);
+        self.task.clt.send.add_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.server.io_udp.add_out_packets(n);
+        self.task.clt.send.add_packets(n);
+    }
+}
@@ -0,0 +1,515 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::future::poll_fn;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+
+use g3_io_ext::{
+    LimitedRecvStats, LimitedUdpRecv, LimitedUdpSend, UdpCopyClientRecv, UdpCopyClientSend,
+    UdpCopyClientToRemote, UdpCopyError, UdpCopyRemoteRecv, UdpCopyRemoteSend,
+    UdpCopyRemoteToClient,
+};
+use g3_socket::BindAddr;
+use g3_types::acl::AclAction;
+use g3_types::net::{Host, UpstreamAddr};
+
+use super::common::CommonTaskContext;
+use super::flow::FlowHandle;
+use super::recv::UdpTProxyClientRecv;
+use super::send::UdpTProxyClientSend;
+use super::stats::{
+    UdpTProxyServerAliveTaskGuard, UdpTProxyTaskCltWrapperStats, UdpTProxyTaskStats,
+};
+use crate::config::server::ServerConfig;
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
+
+pub(super) struct UdpTProxyTask {
+    ctx: CommonTaskContext,
+    upstream: UpstreamAddr,
+    sni: Option<Host>,
+    task_notes: ServerTaskNotes,
+    udp_notes: UdpConnectTaskNotes,
+    task_stats: Arc<UdpTProxyTaskStats>,
+    _flow: FlowHandle,
+    _alive_guard: Option<UdpTProxyServerAliveTaskGuard>,
+}
+
+impl UdpTProxyTask {
+    pub(super) fn new(
+        ctx: CommonTaskContext,
+        flow: FlowHandle,
+        task_notes: ServerTaskNotes,
+    ) -> Self {
+        let upstream = UpstreamAddr::from(ctx.server_addr());
+        UdpTProxyTask {
+            ctx,
+            upstream,
+            sni: None,
+            task_notes,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_stats: Arc::new(UdpTProxyTaskStats::default()),
+            _flow: flow,
+            _alive_guard: None,
+        }
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: None,
+                tcp_client_addr: None,
+                udp_listen_addr: Some(self.ctx.server_addr()),
+                udp_client_addr: Some(self.ctx.client_addr()),
+                upstream: Some(&self.upstream),
+                sni: self.sni.as_ref(),
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    pub(super) async fn into_running(mut self, mut queue: mpsc::Receiver<Bytes>) {
+        let mut packets = Vec::with_capacity(4);
+        // the first packet has already been pushed to the queue
+        if let Some(p) = queue.recv().await {
+            packets.push(p);
+        }
+        self.detect_server_name(&mut queue, &mut packets).await;
+
+        self.pre_start();
+        let e = match self.run(queue, packets).await {
+            Ok(_) => ServerTaskError::ClosedByClient,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+    }
+
+    /// the packets will still be sent to the original destination, the server name is only used
+    /// in the acl rules and logs
+    #[cfg(feature = "quic")]
+    async fn detect_server_name(
+        &mut self,
+        queue: &mut mpsc::Receiver<Bytes>,
+        packets: &mut Vec<Bytes>,
+    ) {
+        if !self.ctx.server_config.quic_sni_detection {
+            return;
+        }
+
+        if let Some(sni) = super::quic::detect_server_name(
+            queue,
+            packets,
+            self.ctx.server_config.quic_sni_wait_timeout,
+            self.ctx.server_config.quic_max_client_hello_size,
+        )
+        .await
+        {
+            self.sni = Some(Host::from(sni));
+        }
+    }
+
+    #[cfg(not(feature = "quic"))]
+    async fn detect_server_name(
+        &mut self,
+        _queue: &mut mpsc::Receiver<Bytes>,
+        _packets: &mut Vec<Bytes>,
+    ) {
+    }
+
+    fn pre_start(&mut self) {
+        self._alive_guard = Some(self.ctx.server_stats.add_task());
+
+        if self.ctx.server_config.flush_task_log_on_created
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_created();
+        }
+
+        self.task_notes.register_live_task(
+            self.ctx.server_config.name(),
+            &self.ctx.server_stats.live_tasks,
+            &self.ctx.server_config.escaper,
+            Some(&self.upstream),
+            self.task_stats.clone(),
+        );
+    }
+
+    fn handle_server_upstream_acl_action(&self, action: AclAction) -> ServerTaskResult<()> {
+        let forbid = match action {
+            AclAction::Permit => false,
+            AclAction::PermitAndLog => {
+                // TODO log permit
+                false
+            }
+            AclAction::Forbid => true,
+            AclAction::ForbidAndLog => {
+                // TODO log forbid
+                true
+            }
+        };
+        if forbid {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::DestDenied,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// check both the original destination and the server name, as the latter is set by the client
+    fn check_upstream_rules(&self) -> ServerTaskResult<()> {
+        let action = self.ctx.check_upstream(&self.upstream);
+        self.handle_server_upstream_acl_action(action)?;
+
+        if let Some(sni) = &self.sni {
+            let sni_upstream = UpstreamAddr::new(sni.clone(), self.upstream.port());
+            let action = self.ctx.check_upstream(&sni_upstream);
+            self.handle_server_upstream_acl_action(action)?;
+        }
+        Ok(())
+    }
+
+    fn setup_reply_socket(&self) -> ServerTaskResult<UdpSocket> {
+        let client_addr = self.ctx.client_addr();
+        let socket = g3_socket::udp::new_std_socket_to(
+            client_addr,
+            &BindAddr::Foreign(self.ctx.server_addr()),
+            self.ctx.server_config.udp_socket_buffer,
+            self.ctx.server_config.udp_misc_opts,
+        )
+        .map_err(|_| {
+            ServerTaskError::InternalServerError("failed to setup udp reply socket to client")
+        })?;
+        socket.connect(client_addr).map_err(|_| {
+            ServerTaskError::InternalServerError("unable to connect the client side udp socket")
+        })?;
+        UdpSocket::from_std(socket).map_err(|_| {
+            ServerTaskError::InternalServerError(
+                "failed to convert std udp socket to tokio udp socket",
+            )
+        })
+    }
+
+    async fn run(
+        &mut self,
+        queue: mpsc::Receiver<Bytes>,
+        packets: Vec<Bytes>,
+    ) -> ServerTaskResult<()> {
+        self.check_upstream_rules()?;
+
+        let clt_socket = self.setup_reply_socket()?;
+        let (clt_r, clt_w) = g3_io_ext::split_udp(clt_socket);
+
+        let limit_config = self.ctx.server_config.udp_sock_speed_limit;
+        let wrapper_stats = Arc::new(UdpTProxyTaskCltWrapperStats::new(
+            &self.ctx.server_stats,
+            &self.task_stats,
+        ));
+
+        let clt_r = LimitedUdpRecv::local_limited(
+            clt_r,
+            limit_config.shift_millis,
+            limit_config.max_north_packets,
+            limit_config.max_north_bytes,
+            wrapper_stats.clone(),
+        );
+        let clt_w = LimitedUdpSend::local_limited(
+            clt_w,
+            limit_config.shift_millis,
+            limit_config.max_south_packets,
+            limit_config.max_south_bytes,
+            wrapper_stats.clone(),
+        );
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let (ups_r, mut ups_w) = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        for p in packets {
+            wrapper_stats.add_recv_bytes(p.len());
+            wrapper_stats.add_recv_packet();
+            poll_fn(|cx| ups_w.poll_send_packet(cx, &p)).await?;
+        }
+
+        self.task_notes.mark_relaying();
+
+        let clt_r = UdpTProxyClientRecv::new(clt_r, queue, wrapper_stats);
+        let clt_w = UdpTProxyClientSend::new(clt_w);
+
+        self.run_relay(Box::new(clt_r), Box::new(clt_w), ups_r, ups_w)
+            .await
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: Box<dyn UdpCopyClientRecv + Unpin + Send>,
+        mut clt_w: Box<dyn UdpCopyClientSend + Unpin + Send>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut *clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut *clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        let max_idle_count = self.ctx.server_config.task_idle_max_count;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = ups_w.error_logger() {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = ups_r.error_logger() {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if self.task_notes.is_killed() {
+                        return Err(ServerTaskError::CanceledAsTaskKilled);
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "quic", feature = "rustls-ring"))]
+mod tests {
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use yaml_rust::YamlLoader;
+
+    use g3_daemon::server::{ClientConnectionInfo, ServerQuitPolicy};
+    use g3_io_ext::IdleWheel;
+
+    use super::*;
+    use crate::config::server::udp_tproxy::UdpTProxyServerConfig;
+    use crate::serve::udp_tproxy::flow::{FlowDispatchResult, FlowTable};
+    use crate::serve::udp_tproxy::stats::UdpTProxyServerStats;
+
+    fn new_task(
+        conf: &str,
+        server_addr: SocketAddr,
+        first_packet: &[u8],
+    ) -> (UdpTProxyTask, mpsc::Receiver<Bytes>) {
+        let doc = YamlLoader::load_from_str(conf).unwrap().pop().unwrap();
+        let server_config = UdpTProxyServerConfig::parse(doc.as_hash().unwrap(), None).unwrap();
+        let server_config = Arc::new(server_config);
+
+        let client_addr = SocketAddr::from(([127, 0, 0, 1], 50000));
+        let cc_info = ClientConnectionInfo::new(client_addr, server_addr);
+        let task_notes = ServerTaskNotes::new(cc_info.clone(), None, Duration::ZERO);
+
+        let table = Arc::new(FlowTable::default());
+        let FlowDispatchResult::Created(flow, queue) =
+            table.dispatch((client_addr, server_addr), first_packet, 64, 0)
+        else {
+            panic!("no new flow created");
+        };
+
+        let ctx = CommonTaskContext {
+            server_stats: Arc::new(UdpTProxyServerStats::new(server_config.name())),
+            server_quit_policy: Arc::new(ServerQuitPolicy::default()),
+            idle_wheel: IdleWheel::spawn(server_config.task_idle_check_interval),
+            escaper: crate::escape::get_or_insert_default(&server_config.escaper),
+            dst_host_filter: server_config
+                .dst_host_filter
+                .as_ref()
+                .map(|builder| Arc::new(builder.build())),
+            cc_info,
+            task_logger: None,
+            server_config,
+        };
+        (UdpTProxyTask::new(ctx, flow, task_notes), queue)
+    }
+
+    /// get the first QUIC Initial packet sent by a real client
+    async fn quic_client_initial(server_name: &str) -> (SocketAddr, Bytes) {
+        use quinn::crypto::rustls::QuicClientConfig;
+
+        let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let tls_config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .unwrap()
+            .with_root_certificates(rustls::RootCertStore::empty())
+            .with_no_client_auth();
+        let quic_config = QuicClientConfig::try_from(tls_config).unwrap();
+
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let endpoint = quinn::Endpoint::new(
+            Default::default(),
+            None,
+            socket,
+            Arc::new(quinn::TokioRuntime),
+        )
+        .unwrap();
+        let _connecting = endpoint
+            .connect_with(
+                quinn::ClientConfig::new(Arc::new(quic_config)),
+                server_addr,
+                server_name,
+            )
+            .unwrap();
+
+        let mut buf = [0u8; 2048];
+        let len = server.recv(&mut buf).await.unwrap();
+        (server_addr, Bytes::copy_from_slice(&buf[..len]))
+    }
+
+    #[tokio::test]
+    async fn sni_keep_upstream() {
+        let conf = r#"
+            name: test
+            escaper: test
+            listen: 127.0.0.1:10443
+        "#;
+        let (server_addr, packet) = quic_client_initial("example.com").await;
+        let (mut task, mut queue) = new_task(conf, server_addr, &packet);
+
+        let mut packets = vec![queue.recv().await.unwrap()];
+        task.detect_server_name(&mut queue, &mut packets).await;
+        assert_eq!(packets.len(), 1);
+        assert_eq!(task.sni, Some(Host::from_str("example.com").unwrap()));
+        // the packets should still be relayed to the original destination
+        assert_eq!(task.upstream, UpstreamAddr::from(server_addr));
+        assert!(task.check_upstream_rules().is_ok());
+    }
+
+    #[tokio::test]
+    async fn sni_acl() {
+        let (server_addr, packet) = quic_client_initial("example.com").await;
+
+        let conf = r#"
+            name: test
+            escaper: test
+            listen: 127.0.0.1:10443
+            dst_host_filter_set:
+              exact_match:
+                default: allow
+                forbid: example.com
+        "#;
+        let (mut task, mut queue) = new_task(conf, server_addr, &packet);
+        let mut packets = vec![queue.recv().await.unwrap()];
+        assert!(task.check_upstream_rules().is_ok());
+        task.detect_server_name(&mut queue, &mut packets).await;
+        assert!(task.check_upstream_rules().is_err());
+
+        let conf = r#"
+            name: test
+            escaper: test
+            listen: 127.0.0.1:10443
+            dst_host_filter_set:
+              exact_match:
+                default: allow
+                forbid: 127.0.0.1
+        "#;
+        let (mut task, mut queue) = new_task(conf, server_addr, &packet);
+        let mut packets = vec![queue.recv().await.unwrap()];
+        task.detect_server_name(&mut queue, &mut packets).await;
+        assert!(task.sni.is_some());
+        assert!(task.check_upstream_rules().is_err());
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::{TAG_KEY_SERVER, TAG_KEY_STAT_ID};
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::stats::GlobalStatsMap;
+
+use crate::module::http_forward::{HttpCacheSnapshot, HttpCacheStats};
+
+const METRIC_NAME_HTTP_CACHE_HIT: &str = "server.http_cache.hit";
+const METRIC_NAME_HTTP_CACHE_MISS: &str = "server.http_cache.miss";
+const METRIC_NAME_HTTP_CACHE_REVALIDATED: &str = "server.http_cache.revalidated";
+const METRIC_NAME_HTTP_CACHE_STORED: &str = "server.http_cache.stored";
+const METRIC_NAME_HTTP_CACHE_EVICTED: &str = "server.http_cache.evicted";
+const METRIC_NAME_HTTP_CACHE_MEMORY_SIZE: &str = "server.http_cache.memory_size";
+const METRIC_NAME_HTTP_CACHE_DISK_SIZE: &str = "server.http_cache.disk_size";
+
+type CacheStatsValue = (Arc<HttpCacheStats>, HttpCacheSnapshot);
+
+static CACHE_STATS_MAP: Mutex<GlobalStatsMap<CacheStatsValue>> = Mutex::new(GlobalStatsMap::new());
+
+pub(crate) fn push_cache_stats(stats: Arc<HttpCacheStats>) {
+    let k = stats.stat_id();
+    let v = (stats, HttpCacheSnapshot::default());
+    let mut ht = CACHE_STATS_MAP.lock().unwrap();
+    ht.insert(k, v);
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut cache_stats_map = CACHE_STATS_MAP.lock().unwrap();
+    cache_stats_map.retain(|(stats, snap)| {
+        emit_cache_stats(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_cache_stats(
+    client: &mut StatsdClient,
+    stats: &Arc<HttpCacheStats>,
+    snap: &mut HttpCacheSnapshot,
+) {
+    let mut buffer = itoa::Buffer::new();
+    let stat_id = buffer.format(stats.stat_id().as_u64());
+
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_SERVER, stats.server());
+    common_tags.add_tag(TAG_KEY_STAT_ID, stat_id);
+
+    client
+        .gauge_with_tags(
+            METRIC_NAME_HTTP_CACHE_MEMORY_SIZE,
+            stats.memory_size(),
+            &common_tags,
+        )
+        .send();
+    client
+        .gauge_with_tags(
+            METRIC_NAME_HTTP_CACHE_DISK_SIZE,
+            stats.disk_size(),
+            &common_tags,
+        )
+        .send();
+
+    let new_snap = stats.snapshot();
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = new_snap.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, &common_tags)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(hit, METRIC_NAME_HTTP_CACHE_HIT);
+    emit_field!(miss, METRIC_NAME_HTTP_CACHE_MISS);
+    emit_field!(revalidated, METRIC_NAME_HTTP_CACHE_REVALIDATED);
+    emit_field!(stored, METRIC_NAME_HTTP_CACHE_STORED);
+    emit_field!(evicted, METRIC_NAME_HTTP_CACHE_EVICTED);
+}
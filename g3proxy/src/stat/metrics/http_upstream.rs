@@ -0,0 +1,77 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::{TAG_KEY_SERVER, TAG_KEY_STAT_ID};
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::stats::GlobalStatsMap;
+
+use crate::serve::{HttpUpstreamPeerSnapshot, HttpUpstreamPeerStats};
+
+const TAG_KEY_UPSTREAM: &str = "upstream";
+
+const METRIC_NAME_UPSTREAM_PEER_HEALTHY: &str = "server.upstream.peer.healthy";
+const METRIC_NAME_UPSTREAM_PEER_SELECTED: &str = "server.upstream.peer.selected";
+const METRIC_NAME_UPSTREAM_PEER_PROBE_PASSED: &str = "server.upstream.peer.probe.passed";
+const METRIC_NAME_UPSTREAM_PEER_PROBE_FAILED: &str = "server.upstream.peer.probe.failed";
+const METRIC_NAME_UPSTREAM_PEER_EJECTED: &str = "server.upstream.peer.ejected";
+
+type PeerStatsValue = (Arc<HttpUpstreamPeerStats>, HttpUpstreamPeerSnapshot);
+
+static PEER_STATS_MAP: Mutex<GlobalStatsMap<PeerStatsValue>> = Mutex::new(GlobalStatsMap::new());
+
+pub(crate) fn push_peer_stats(stats: Arc<HttpUpstreamPeerStats>) {
+    let k = stats.stat_id();
+    let v = (stats, HttpUpstreamPeerSnapshot::default());
+    let mut ht = PEER_STATS_MAP.lock().unwrap();
+    ht.insert(k, v);
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut peer_stats_map = PEER_STATS_MAP.lock().unwrap();
+    peer_stats_map.retain(|(stats, snap)| {
+        emit_peer_stats(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_peer_stats(
+    client: &mut StatsdClient,
+    stats: &Arc<HttpUpstreamPeerStats>,
+    snap: &mut HttpUpstreamPeerSnapshot,
+) {
+    let mut buffer = itoa::Buffer::new();
+    let stat_id = buffer.format(stats.stat_id().as_u64());
+
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_SERVER, stats.server());
+    common_tags.add_tag(TAG_KEY_UPSTREAM, stats.upstream());
+    common_tags.add_tag(TAG_KEY_STAT_ID, stat_id);
+
+    let healthy = if stats.is_healthy() { 1 } else { 0 };
+    client
+        .gauge_with_tags(METRIC_NAME_UPSTREAM_PEER_HEALTHY, healthy, &common_tags)
+        .send();
+
+    let new_snap = stats.snapshot();
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = new_snap.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, &common_tags)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(selected, METRIC_NAME_UPSTREAM_PEER_SELECTED);
+    emit_field!(probe_passed, METRIC_NAME_UPSTREAM_PEER_PROBE_PASSED);
+    emit_field!(probe_failed, METRIC_NAME_UPSTREAM_PEER_PROBE_FAILED);
+    emit_field!(ejected, METRIC_NAME_UPSTREAM_PEER_EJECTED);
+}
@@ -4,6 +4,8 @@
  */
 
 pub(super) mod escaper;
+pub(crate) mod http_cache;
+pub(crate) mod http_upstream;
 pub(super) mod resolver;
 pub(super) mod server;
 
@@ -41,6 +43,7 @@ enum MetricUserRequestType {
     HttpForward,
     HttpsForward,
     HttpConnect,
+    HttpUdpConnect,
     FtpOverHttp,
     SocksTcpConnect,
     SocksUdpConnect,
@@ -54,6 +57,7 @@ impl MetricUserRequestType {
             MetricUserRequestType::HttpForward => "http_forward",
             MetricUserRequestType::HttpsForward => "https_forward",
             MetricUserRequestType::HttpConnect => "http_connect",
+            MetricUserRequestType::HttpUdpConnect => "http_udp_connect",
             MetricUserRequestType::FtpOverHttp => "ftp_over_http",
             MetricUserRequestType::SocksTcpConnect => "socks_tcp_connect",
             MetricUserRequestType::SocksUdpConnect => "socks_udp_connect",
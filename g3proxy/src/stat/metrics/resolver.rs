@@ -29,6 +29,9 @@ const METRIC_NAME_QUERY_SERVER_REFUSED: &str = "resolver.query.server.refused";
 const METRIC_NAME_QUERY_SERVER_MALFORMED: &str = "resolver.query.server.malformed";
 const METRIC_NAME_QUERY_SERVER_NOT_FOUND: &str = "resolver.query.server.not_found";
 const METRIC_NAME_QUERY_SERVER_SERV_FAIL: &str = "resolver.query.server.serv_fail";
+const METRIC_NAME_QUERY_DNSSEC_SECURE: &str = "resolver.query.dnssec.secure";
+const METRIC_NAME_QUERY_DNSSEC_INSECURE: &str = "resolver.query.dnssec.insecure";
+const METRIC_NAME_QUERY_DNSSEC_BOGUS: &str = "resolver.query.dnssec.bogus";
 const METRIC_NAME_MEMORY_CACHE_CAPACITY: &str = "resolver.memory.cache.capacity";
 const METRIC_NAME_MEMORY_CACHE_LENGTH: &str = "resolver.memory.cache.length";
 const METRIC_NAME_MEMORY_DOING_CAPACITY: &str = "resolver.memory.doing.capacity";
@@ -153,6 +156,9 @@ fn emit_query_stats_to_statsd(
     emit_query_stats_u64!(server_malformed, METRIC_NAME_QUERY_SERVER_MALFORMED);
     emit_query_stats_u64!(server_not_found, METRIC_NAME_QUERY_SERVER_NOT_FOUND);
     emit_query_stats_u64!(server_serv_fail, METRIC_NAME_QUERY_SERVER_SERV_FAIL);
+    emit_query_stats_u64!(dnssec_secure, METRIC_NAME_QUERY_DNSSEC_SECURE);
+    emit_query_stats_u64!(dnssec_insecure, METRIC_NAME_QUERY_DNSSEC_INSECURE);
+    emit_query_stats_u64!(dnssec_bogus, METRIC_NAME_QUERY_DNSSEC_BOGUS);
 }
 
 fn emit_memory_stats_to_statsd(
@@ -14,7 +14,7 @@ use g3_statsd_client::{StatsdClient, StatsdClientConfig};
 pub(crate) mod types;
 
 mod metrics;
-pub(crate) use metrics::user_site;
+pub(crate) use metrics::{http_cache, http_upstream, user_site};
 
 static QUIT_STAT_THREAD: AtomicBool = AtomicBool::new(false);
 
@@ -48,6 +48,8 @@ fn spawn_main_thread(config: &StatsdClientConfig) -> anyhow::Result<JoinHandle<(
                 metrics::escaper::emit_stats(&mut client);
                 metrics::resolver::emit_stats(&mut client);
                 metrics::user::emit_stats(&mut client);
+                metrics::http_upstream::emit_stats(&mut client);
+                metrics::http_cache::emit_stats(&mut client);
                 g3_daemon::runtime::metrics::emit_stats(&mut client);
                 g3_daemon::log::metrics::emit_stats(&mut client);
 
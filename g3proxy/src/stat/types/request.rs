@@ -11,6 +11,7 @@ pub(crate) struct RequestStats {
     http_forward: AtomicU64,
     https_forward: AtomicU64,
     http_connect: AtomicU64,
+    http_udp_connect: AtomicU64,
     ftp_over_http: AtomicU64,
     socks_tcp_connect: AtomicU64,
     socks_udp_connect: AtomicU64,
@@ -23,6 +24,7 @@ pub(crate) struct RequestSnapshot {
     pub(crate) http_forward: u64,
     pub(crate) https_forward: u64,
     pub(crate) http_connect: u64,
+    pub(crate) http_udp_connect: u64,
     pub(crate) ftp_over_http: u64,
     pub(crate) socks_tcp_connect: u64,
     pub(crate) socks_udp_connect: u64,
@@ -62,6 +64,14 @@ impl RequestStats {
         self.http_connect.load(Ordering::Relaxed)
     }
 
+    pub(crate) fn add_http_udp_connect(&self) {
+        self.http_udp_connect.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn http_udp_connect(&self) -> u64 {
+        self.http_udp_connect.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn add_ftp_over_http(&self) {
         self.ftp_over_http.fetch_add(1, Ordering::Relaxed);
     }
@@ -131,6 +141,7 @@ pub(crate) struct RequestAliveStats {
     http_forward: AtomicI32,
     https_forward: AtomicI32,
     http_connect: AtomicI32,
+    http_udp_connect: AtomicI32,
     ftp_over_http: AtomicI32,
     socks_tcp_connect: AtomicI32,
     socks_udp_connect: AtomicI32,
@@ -186,6 +197,18 @@ impl RequestAliveStats {
         self.http_connect.load(Ordering::Relaxed)
     }
 
+    pub(crate) fn add_http_udp_connect(&self) {
+        self.http_udp_connect.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn del_http_udp_connect(&self) {
+        self.http_udp_connect.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn http_udp_connect(&self) -> i32 {
+        self.http_udp_connect.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn add_ftp_over_http(&self) {
         self.ftp_over_http.fetch_add(1, Ordering::Relaxed);
     }
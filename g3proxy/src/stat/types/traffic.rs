@@ -13,6 +13,7 @@ pub(crate) struct TrafficStats {
     pub(crate) http_connect: TcpIoStats,
     pub(crate) ftp_over_http: TcpIoStats,
     pub(crate) socks_tcp_connect: TcpIoStats,
+    pub(crate) http_udp_connect: UdpIoStats,
     pub(crate) socks_udp_connect: UdpIoStats,
     pub(crate) socks_udp_associate: UdpIoStats,
 }
@@ -25,6 +26,7 @@ pub(crate) struct TrafficSnapshot {
     pub(crate) http_connect: TcpIoSnapshot,
     pub(crate) ftp_over_http: TcpIoSnapshot,
     pub(crate) socks_tcp_connect: TcpIoSnapshot,
+    pub(crate) http_udp_connect: UdpIoSnapshot,
     pub(crate) socks_udp_connect: UdpIoSnapshot,
     pub(crate) socks_udp_associate: UdpIoSnapshot,
 }
@@ -11,17 +11,50 @@ use g3_ctl::CommandResult;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::server_capnp::server_control;
 
+use super::common::{parse_fetch_result, parse_operation_result};
+
 pub const COMMAND: &str = "server";
 
 const COMMAND_ARG_NAME: &str = "name";
+const COMMAND_ARG_USER: &str = "user";
+const COMMAND_ARG_UPSTREAM: &str = "upstream";
+const COMMAND_ARG_TASK: &str = "task";
 
 const SUBCOMMAND_STATUS: &str = "status";
+const SUBCOMMAND_LIST_TASK: &str = "list-task";
+const SUBCOMMAND_KILL_TASK: &str = "kill-task";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
         .subcommand_required(true)
         .subcommand(Command::new(SUBCOMMAND_STATUS))
+        .subcommand(
+            Command::new(SUBCOMMAND_LIST_TASK)
+                .about("List live tasks")
+                .arg(
+                    Arg::new(COMMAND_ARG_USER)
+                        .help("Only show tasks of this user")
+                        .long(COMMAND_ARG_USER)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(COMMAND_ARG_UPSTREAM)
+                        .help("Only show tasks to this upstream host or host:port")
+                        .long(COMMAND_ARG_UPSTREAM)
+                        .num_args(1),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_KILL_TASK)
+                .about("Kill a live task")
+                .arg(
+                    Arg::new(COMMAND_ARG_TASK)
+                        .help("The task id")
+                        .required(true)
+                        .num_args(1),
+                ),
+        )
 }
 
 async fn status(client: &server_control::Client) -> CommandResult<()> {
@@ -35,16 +68,68 @@ async fn status(client: &server_control::Client) -> CommandResult<()> {
     Ok(())
 }
 
+async fn list_task(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.list_task_request();
+    if let Some(user) = args.get_one::<String>(COMMAND_ARG_USER) {
+        req.get().set_user(user.as_str());
+    }
+    if let Some(upstream) = args.get_one::<String>(COMMAND_ARG_UPSTREAM) {
+        req.get().set_upstream(upstream.as_str());
+    }
+    let rsp = req.send().promise.await?;
+    let tasks = parse_fetch_result(rsp.get()?.get_result()?)?;
+    for task in tasks.iter() {
+        println!("id: {}", task.get_id()?.to_str().unwrap_or_default());
+        println!("  user: {}", task.get_user()?.to_str().unwrap_or_default());
+        println!(
+            "  client: {}",
+            task.get_client_addr()?.to_str().unwrap_or_default()
+        );
+        println!(
+            "  upstream: {}",
+            task.get_upstream()?.to_str().unwrap_or_default()
+        );
+        println!(
+            "  escaper: {}",
+            task.get_escaper()?.to_str().unwrap_or_default()
+        );
+        println!("  start time: {}", task.get_start_time());
+        println!("  alive time: {}s", task.get_alive_time());
+        println!("  client read bytes: {}", task.get_client_read_bytes());
+        println!("  client write bytes: {}", task.get_client_write_bytes());
+    }
+    Ok(())
+}
+
+async fn kill_task(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let task = args.get_one::<String>(COMMAND_ARG_TASK).unwrap();
+
+    let mut req = client.kill_task_request();
+    req.get().set_task(task.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
-    let (subcommand, _) = args.subcommand().unwrap();
+    let (subcommand, args) = args.subcommand().unwrap();
     match subcommand {
         SUBCOMMAND_STATUS => {
             super::proc::get_server(client, name)
                 .and_then(|server| async move { status(&server).await })
                 .await
         }
+        SUBCOMMAND_LIST_TASK => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { list_task(&server, args).await })
+                .await
+        }
+        SUBCOMMAND_KILL_TASK => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { kill_task(&server, args).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
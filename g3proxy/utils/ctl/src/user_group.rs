@@ -7,23 +7,39 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use clap::{Arg, ArgMatches, Command, ValueHint, value_parser};
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint, value_parser};
 
 use g3_ctl::{CommandError, CommandResult};
 
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::user_group_capnp::user_group_control;
 
-use super::common::parse_operation_result;
+use super::common::{parse_fetch_result, parse_operation_result};
 
 pub const COMMAND: &str = "user-group";
 
 const COMMAND_ARG_NAME: &str = "name";
 const COMMAND_ARG_FILE: &str = "file";
+const COMMAND_ARG_USER: &str = "user";
+const COMMAND_ARG_DELAY: &str = "delay";
+const COMMAND_ARG_LIMITS: &str = "limits";
+const COMMAND_ARG_TASK: &str = "task";
+const COMMAND_ARG_ALL: &str = "all";
 
 const SUBCOMMAND_LIST_STATIC_USER: &str = "list-static-user";
 const SUBCOMMAND_LIST_DYNAMIC_USER: &str = "list-dynamic-user";
 const SUBCOMMAND_PUBLISH_USER: &str = "publish-user";
+const SUBCOMMAND_GET_USER: &str = "get-user";
+const SUBCOMMAND_BLOCK_USER: &str = "block-user";
+const SUBCOMMAND_UNBLOCK_USER: &str = "unblock-user";
+const SUBCOMMAND_SET_USER_LIMIT: &str = "set-user-limit";
+const SUBCOMMAND_RESET_USER: &str = "reset-user";
+const SUBCOMMAND_LIST_USER_TASK: &str = "list-user-task";
+const SUBCOMMAND_KILL_USER_TASK: &str = "kill-user-task";
+
+fn user_arg() -> Arg {
+    Arg::new(COMMAND_ARG_USER).required(true).num_args(1)
+}
 
 pub fn command() -> Command {
     Command::new(COMMAND)
@@ -43,6 +59,77 @@ pub fn command() -> Command {
                         .value_hint(ValueHint::FilePath),
                 ),
         )
+        .subcommand(
+            Command::new(SUBCOMMAND_GET_USER)
+                .about("Show the state and runtime limits of a user")
+                .arg(user_arg()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_BLOCK_USER)
+                .about("Block a user at runtime")
+                .arg(user_arg())
+                .arg(
+                    Arg::new(COMMAND_ARG_DELAY)
+                        .help("Delay in milliseconds before sending the error response")
+                        .long(COMMAND_ARG_DELAY)
+                        .num_args(1)
+                        .value_parser(value_parser!(u32))
+                        .default_value("0"),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_UNBLOCK_USER)
+                .about("Unblock a user at runtime")
+                .arg(user_arg()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_SET_USER_LIMIT)
+                .about("Change the limits of a user at runtime")
+                .arg(user_arg())
+                .arg(
+                    Arg::new(COMMAND_ARG_LIMITS)
+                        .help("The limits in json map format")
+                        .num_args(1)
+                        .required_unless_present(COMMAND_ARG_FILE)
+                        .conflicts_with(COMMAND_ARG_FILE),
+                )
+                .arg(
+                    Arg::new(COMMAND_ARG_FILE)
+                        .help("Read the limits from the json file")
+                        .long(COMMAND_ARG_FILE)
+                        .num_args(1)
+                        .value_parser(value_parser!(PathBuf))
+                        .value_hint(ValueHint::FilePath),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_RESET_USER)
+                .about("Drop all runtime changes of a user")
+                .arg(user_arg()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_LIST_USER_TASK)
+                .about("List the live tasks of a user")
+                .arg(user_arg()),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_KILL_USER_TASK)
+                .about("Kill live tasks of a user")
+                .arg(user_arg())
+                .arg(
+                    Arg::new(COMMAND_ARG_TASK)
+                        .help("The task id")
+                        .num_args(1)
+                        .required_unless_present(COMMAND_ARG_ALL)
+                        .conflicts_with(COMMAND_ARG_ALL),
+                )
+                .arg(
+                    Arg::new(COMMAND_ARG_ALL)
+                        .help("Kill all tasks of the user")
+                        .long(COMMAND_ARG_ALL)
+                        .action(ArgAction::SetTrue),
+                ),
+        )
 }
 
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
@@ -55,6 +142,13 @@ pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandRes
         SUBCOMMAND_LIST_STATIC_USER => list_static_user(&user_group).await,
         SUBCOMMAND_LIST_DYNAMIC_USER => list_dynamic_user(&user_group).await,
         SUBCOMMAND_PUBLISH_USER => publish_dynamic_user(&user_group, args).await,
+        SUBCOMMAND_GET_USER => get_user(&user_group, args).await,
+        SUBCOMMAND_BLOCK_USER => block_user(&user_group, args).await,
+        SUBCOMMAND_UNBLOCK_USER => unblock_user(&user_group, args).await,
+        SUBCOMMAND_SET_USER_LIMIT => set_user_limit(&user_group, args).await,
+        SUBCOMMAND_RESET_USER => reset_user(&user_group, args).await,
+        SUBCOMMAND_LIST_USER_TASK => list_user_task(&user_group, args).await,
+        SUBCOMMAND_KILL_USER_TASK => kill_user_task(&user_group, args).await,
         _ => unreachable!(),
     }
 }
@@ -97,3 +191,122 @@ async fn publish_dynamic_user(
     let rsp = req.send().promise.await?;
     parse_operation_result(rsp.get()?.get_result()?)
 }
+
+async fn get_user(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+
+    let mut req = client.get_user_request();
+    req.get().set_user(user.as_str());
+    let rsp = req.send().promise.await?;
+    let info = parse_fetch_result(rsp.get()?.get_result()?)?;
+    g3_ctl::print_text("user info", info)
+}
+
+async fn block_user(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+    let delay = args.get_one::<u32>(COMMAND_ARG_DELAY).copied().unwrap_or(0);
+
+    let mut req = client.block_user_request();
+    req.get().set_user(user.as_str());
+    req.get().set_delay(delay);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn unblock_user(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+
+    let mut req = client.unblock_user_request();
+    req.get().set_user(user.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn set_user_limit(
+    client: &user_group_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+    let limits = if let Some(file) = args.get_one::<PathBuf>(COMMAND_ARG_FILE) {
+        tokio::fs::read_to_string(file).await.map_err(|e| {
+            CommandError::Cli(anyhow!(
+                "failed to read contents of file {}: {e:?}",
+                file.display()
+            ))
+        })?
+    } else {
+        args.get_one::<String>(COMMAND_ARG_LIMITS).unwrap().clone()
+    };
+
+    match serde_json::Value::from_str(&limits) {
+        Ok(serde_json::Value::Object(_)) => {}
+        Ok(_) => {
+            return Err(CommandError::Cli(anyhow!(
+                "the limits should be a json map"
+            )));
+        }
+        Err(e) => {
+            return Err(CommandError::Cli(anyhow!(
+                "the limits is not valid json: {e:?}"
+            )));
+        }
+    }
+
+    let mut req = client.set_user_limit_request();
+    req.get().set_user(user.as_str());
+    req.get().set_limits(limits.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn reset_user(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+
+    let mut req = client.reset_user_request();
+    req.get().set_user(user.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn list_user_task(
+    client: &user_group_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+
+    let mut req = client.list_user_task_request();
+    req.get().set_user(user.as_str());
+    let rsp = req.send().promise.await?;
+    let tasks = parse_fetch_result(rsp.get()?.get_result()?)?;
+    for task in tasks.iter() {
+        println!("id: {}", task.get_id()?.to_str().unwrap_or_default());
+        println!(
+            "  server: {}",
+            task.get_server()?.to_str().unwrap_or_default()
+        );
+        println!(
+            "  client: {}",
+            task.get_client_addr()?.to_str().unwrap_or_default()
+        );
+        println!("  start time: {}", task.get_start_time());
+        println!("  alive time: {}s", task.get_alive_time());
+    }
+    Ok(())
+}
+
+async fn kill_user_task(
+    client: &user_group_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let user = args.get_one::<String>(COMMAND_ARG_USER).unwrap();
+
+    let mut req = client.kill_user_task_request();
+    req.get().set_user(user.as_str());
+    if let Some(task) = args.get_one::<String>(COMMAND_ARG_TASK) {
+        req.get().set_task(task.as_str());
+    } else {
+        req.get().set_task("");
+    }
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
@@ -3,10 +3,11 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
 use ahash::AHashMap;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::{broadcast, mpsc};
 
 use g3_types::metrics::MetricTagMap;
@@ -15,7 +16,7 @@ use super::Command;
 use crate::collect::ArcCollector;
 use crate::config::collector::aggregate::AggregateCollectorConfig;
 use crate::export::ArcExporter;
-use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+use crate::types::{MetricName, MetricRecord, MetricSet, MetricSummary, MetricType, MetricValue};
 
 const BATCH_SIZE: usize = 128;
 
@@ -29,6 +30,8 @@ pub(super) struct GlobalStore {
 
     counter: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
     gauge: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
+    summary: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricSummary>>,
+    set: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricSet>>,
 }
 
 impl GlobalStore {
@@ -55,6 +58,8 @@ impl GlobalStore {
             exporters,
             counter: Default::default(),
             gauge: Default::default(),
+            summary: Default::default(),
+            set: Default::default(),
         }
     }
 
@@ -124,6 +129,7 @@ impl GlobalStore {
                     name,
                     tag_map,
                     value,
+                    sample_rate: _,
                 } = record;
 
                 self.counter
@@ -139,6 +145,7 @@ impl GlobalStore {
                     name,
                     tag_map,
                     value,
+                    sample_rate: _,
                 } = record;
 
                 self.gauge
@@ -148,6 +155,98 @@ impl GlobalStore {
                     .and_modify(|v| *v = value)
                     .or_insert(value);
             }
+            MetricType::Timer | MetricType::Histogram | MetricType::Distribution => {
+                self.summary
+                    .entry(record.name)
+                    .or_default()
+                    .entry(record.tag_map)
+                    .or_default()
+                    .add(record.value, record.sample_rate);
+            }
+            MetricType::Set => {
+                self.set
+                    .entry(record.name)
+                    .or_default()
+                    .entry(record.tag_map)
+                    .or_default()
+                    .add(record.value);
+            }
+        }
+    }
+
+    fn send_record(&self, time: DateTime<Utc>, record: MetricRecord) {
+        for exporter in &self.exporters {
+            exporter.add_metric(time, &record);
+        }
+
+        if let Some(next) = &self.next {
+            next.add_metric(time, record, None);
+        }
+    }
+
+    fn join_tag_map(&self, mut tag_map: Arc<MetricTagMap>) -> Arc<MetricTagMap> {
+        if !self.config.join_tags.is_empty() {
+            let inner = Arc::make_mut(&mut tag_map);
+            for tag in &self.config.join_tags {
+                inner.drop(tag);
+            }
+        }
+        tag_map
+    }
+
+    /// Expand timer, histogram and distribution values to count, sum, min, max, mean and quantiles
+    fn emit_summary(&mut self, time: DateTime<Utc>) {
+        for (name, inner_map) in std::mem::take(&mut self.summary) {
+            let mut joined_map: AHashMap<Arc<MetricTagMap>, MetricSummary> = AHashMap::default();
+            for (tag_map, summary) in inner_map {
+                match joined_map.entry(self.join_tag_map(tag_map)) {
+                    Entry::Occupied(mut o) => o.get_mut().merge(&summary),
+                    Entry::Vacant(v) => {
+                        v.insert(summary);
+                    }
+                }
+            }
+
+            for (tag_map, summary) in joined_map {
+                summary.expand(&self.config.quantile_list, |node, r#type, value| {
+                    let mut name = name.as_ref().clone();
+                    name.add_suffix(node);
+                    let record = MetricRecord {
+                        r#type,
+                        name: Arc::new(name),
+                        tag_map: tag_map.clone(),
+                        value,
+                        sample_rate: 1.0,
+                    };
+                    self.send_record(time, record);
+                });
+            }
+        }
+    }
+
+    /// Emit the cardinality of set values as gauge
+    fn emit_set(&mut self, time: DateTime<Utc>) {
+        for (name, inner_map) in std::mem::take(&mut self.set) {
+            let mut joined_map: AHashMap<Arc<MetricTagMap>, MetricSet> = AHashMap::default();
+            for (tag_map, set) in inner_map {
+                match joined_map.entry(self.join_tag_map(tag_map)) {
+                    Entry::Occupied(mut o) => o.get_mut().merge(&set),
+                    Entry::Vacant(v) => {
+                        v.insert(set);
+                    }
+                }
+            }
+
+            for (tag_map, set) in joined_map {
+                let record = MetricRecord {
+                    r#type: MetricType::Gauge,
+                    name: name.clone(),
+                    tag_map,
+                    value: MetricValue::Unsigned(set.cardinality()),
+                    sample_rate: 1.0,
+                };
+                self.send_record(time, record);
+            }
         }
     }
 
@@ -163,6 +262,7 @@ impl GlobalStore {
                             name: name.clone(),
                             tag_map,
                             value,
+                            sample_rate: 1.0,
                         };
 
                         for exporter in &self.exporters {
@@ -206,6 +306,7 @@ impl GlobalStore {
                             name: name.clone(),
                             tag_map,
                             value,
+                            sample_rate: 1.0,
                         };
 
                         for exporter in &self.exporters {
@@ -227,5 +328,7 @@ impl GlobalStore {
             emit_join!(counter, MetricType::Counter);
             emit_join!(gauge, MetricType::Gauge);
         }
+        self.emit_summary(time);
+        self.emit_set(time);
     }
 }
@@ -77,7 +77,11 @@ impl AggregateHandle {
                     return;
                 }
             }
-            MetricType::Gauge => {}
+            MetricType::Gauge
+            | MetricType::Timer
+            | MetricType::Histogram
+            | MetricType::Set
+            | MetricType::Distribution => {}
         }
 
         if self.global.send(Command::Add(record)).is_err() {
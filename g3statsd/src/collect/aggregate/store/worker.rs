@@ -65,6 +65,7 @@ impl WorkerStore {
                     name,
                     tag_map,
                     value,
+                    sample_rate: _,
                 } = record;
 
                 self.counter
@@ -74,7 +75,11 @@ impl WorkerStore {
                     .and_modify(|v| *v += value)
                     .or_insert(value);
             }
-            MetricType::Gauge => {
+            MetricType::Gauge
+            | MetricType::Timer
+            | MetricType::Histogram
+            | MetricType::Set
+            | MetricType::Distribution => {
                 let _ = self.global_sender.send(Command::Add(record));
             }
         }
@@ -88,6 +93,7 @@ impl WorkerStore {
                     name: name.clone(),
                     tag_map,
                     value,
+                    sample_rate: 1.0,
                 };
                 let _ = self.global_sender.send(Command::Add(record));
             }
@@ -3,20 +3,93 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::Utc;
 use tokio::sync::broadcast;
 use tokio::time::Instant;
 
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue, NodeName};
+
 use crate::config::collector::internal::InternalCollectorConfig;
+use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+
+const METRIC_NAME_SPOOL_SIZE: &str = "exporter.spool.size";
+const METRIC_NAME_SPOOL_ENTRIES: &str = "exporter.spool.entries";
+const METRIC_NAME_SPOOL_DROPPED: &str = "exporter.spool.dropped";
+const TAG_KEY_EXPORTER: &str = "exporter";
 
 pub(super) struct InternalEmitter {
     reload_receiver: broadcast::Receiver<Arc<InternalCollectorConfig>>,
+    spool_dropped: HashMap<NodeName, u64>,
 }
 
 impl InternalEmitter {
     pub(super) fn new(reload_receiver: broadcast::Receiver<Arc<InternalCollectorConfig>>) -> Self {
-        InternalEmitter { reload_receiver }
+        InternalEmitter {
+            reload_receiver,
+            spool_dropped: HashMap::new(),
+        }
+    }
+
+    fn emit_spool_stats(&mut self, config: &InternalCollectorConfig) {
+        let mut records = Vec::new();
+        crate::runtime::export::foreach_spool(|spool| {
+            let snapshot = spool.snapshot();
+
+            let mut tag_map = MetricTagMap::default();
+            if let Ok(value) = MetricTagValue::from_str(spool.exporter().as_str()) {
+                tag_map.insert(MetricTagName::from_str(TAG_KEY_EXPORTER).unwrap(), value);
+            }
+            let tag_map = Arc::new(tag_map);
+
+            let mut add_record = |r#type, name: &str, value| {
+                records.push(MetricRecord {
+                    r#type,
+                    name: Arc::new(MetricName::parse(name).unwrap()),
+                    tag_map: tag_map.clone(),
+                    value: MetricValue::Unsigned(value),
+                    sample_rate: 1.0,
+                })
+            };
+            add_record(MetricType::Gauge, METRIC_NAME_SPOOL_SIZE, snapshot.size);
+            add_record(
+                MetricType::Gauge,
+                METRIC_NAME_SPOOL_ENTRIES,
+                snapshot.entries,
+            );
+
+            let last_dropped = self
+                .spool_dropped
+                .insert(spool.exporter().clone(), snapshot.dropped)
+                .unwrap_or_default();
+            if snapshot.dropped > last_dropped {
+                add_record(
+                    MetricType::Counter,
+                    METRIC_NAME_SPOOL_DROPPED,
+                    snapshot.dropped - last_dropped,
+                );
+            }
+        });
+        if records.is_empty() {
+            return;
+        }
+
+        let time = Utc::now();
+        for name in &config.exporters {
+            let exporter = crate::export::get_or_insert_default(name);
+            for record in &records {
+                exporter.add_metric(time, record);
+            }
+        }
+        if let Some(next) = &config.next {
+            let collector = crate::collect::get_or_insert_default(next);
+            for record in records {
+                collector.add_metric(time, record, None);
+            }
+        }
     }
 
     pub(super) async fn into_running(mut self, mut config: Arc<InternalCollectorConfig>) {
@@ -27,7 +100,7 @@ impl InternalEmitter {
             tokio::select! {
                 i = interval.tick() => {
                     last_instant = i;
-                    // TODO emit stats
+                    self.emit_spool_stats(&config);
                 }
                 r = self.reload_receiver.recv() => {
                     match r {
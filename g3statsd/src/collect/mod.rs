@@ -24,6 +24,7 @@ mod aggregate;
 mod discard;
 mod internal;
 mod regulate;
+mod relabel;
 
 pub(crate) trait Collector {
     fn name(&self) -> &NodeName;
@@ -203,6 +203,9 @@ async fn spawn_new_unlocked(config: AnyCollectorConfig) -> anyhow::Result<()> {
         AnyCollectorConfig::Regulate(config) => {
             super::regulate::RegulateCollector::prepare_initial(config)?
         }
+        AnyCollectorConfig::Relabel(config) => {
+            super::relabel::RelabelCollector::prepare_initial(config)?
+        }
     };
     let name = collector.name().clone();
     registry::add(collector);
@@ -0,0 +1,152 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+
+use g3_types::metrics::NodeName;
+
+use super::{ArcCollector, ArcCollectorInternal, Collector, CollectorInternal, CollectorRegistry};
+use crate::config::collector::relabel::RelabelCollectorConfig;
+use crate::config::collector::{AnyCollectorConfig, CollectorConfig};
+use crate::export::ArcExporter;
+use crate::types::MetricRecord;
+
+mod rule;
+use rule::RelabelRule;
+
+mod series;
+use series::SeriesLimiter;
+
+pub(crate) struct RelabelCollector {
+    config: RelabelCollectorConfig,
+    rules: Vec<RelabelRule>,
+    series_limiter: Option<SeriesLimiter>,
+    next: Option<ArcCollector>,
+    exporters: Vec<ArcExporter>,
+}
+
+impl RelabelCollector {
+    fn new<F>(config: RelabelCollectorConfig, fetch_collector: F) -> anyhow::Result<Self>
+    where
+        F: FnMut(&NodeName) -> ArcCollector,
+    {
+        let rules = config
+            .rules
+            .iter()
+            .map(RelabelRule::new)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let series_limiter = if config.max_series_per_metric > 0 {
+            Some(SeriesLimiter::new(
+                config.name().clone(),
+                config.max_series_per_metric,
+                config.series_reset_interval,
+            ))
+        } else {
+            None
+        };
+
+        let next = config.next.as_ref().map(fetch_collector);
+        let exporters = config
+            .exporters
+            .iter()
+            .map(crate::export::get_or_insert_default)
+            .collect();
+
+        Ok(RelabelCollector {
+            config,
+            rules,
+            series_limiter,
+            next,
+            exporters,
+        })
+    }
+
+    pub(crate) fn prepare_initial(
+        config: RelabelCollectorConfig,
+    ) -> anyhow::Result<ArcCollectorInternal> {
+        let server = RelabelCollector::new(config, crate::collect::get_or_insert_default)?;
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(
+        &self,
+        config: AnyCollectorConfig,
+        registry: &mut CollectorRegistry,
+    ) -> anyhow::Result<ArcCollectorInternal> {
+        if let AnyCollectorConfig::Relabel(config) = config {
+            let server =
+                RelabelCollector::new(config, |name| registry.get_or_insert_default(name))?;
+            Ok(Arc::new(server))
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.collector_type(),
+                config.collector_type()
+            ))
+        }
+    }
+}
+
+impl Collector for RelabelCollector {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.collector_type()
+    }
+
+    fn add_metric(&self, time: DateTime<Utc>, mut record: MetricRecord, worker_id: Option<usize>) {
+        for rule in &self.rules {
+            if !rule.apply(&mut record) {
+                return;
+            }
+        }
+        if let Some(limiter) = &self.series_limiter
+            && !limiter.check(&record)
+        {
+            return;
+        }
+
+        for exporter in &self.exporters {
+            exporter.add_metric(time, &record);
+        }
+
+        if let Some(next) = &self.next {
+            next.add_metric(time, record, worker_id);
+        }
+    }
+}
+
+impl CollectorInternal for RelabelCollector {
+    fn _clone_config(&self) -> AnyCollectorConfig {
+        AnyCollectorConfig::Relabel(self.config.clone())
+    }
+
+    fn _depend_on_collector(&self, name: &NodeName) -> bool {
+        self.config
+            .next
+            .as_ref()
+            .map(|n| n.eq(name))
+            .unwrap_or(false)
+    }
+
+    fn _depend_on_exporter(&self, name: &NodeName) -> bool {
+        self.config.exporters.contains(name)
+    }
+
+    fn _reload(
+        &self,
+        config: AnyCollectorConfig,
+        registry: &mut CollectorRegistry,
+    ) -> anyhow::Result<ArcCollectorInternal> {
+        self.prepare_reload(config, registry)
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use regex::Regex;
+
+use g3_types::metrics::{MetricTagName, MetricTagValue};
+
+use crate::config::collector::relabel::{RelabelAction, RelabelRuleConfig, RelabelTarget};
+use crate::types::{MetricName, MetricRecord};
+
+pub(super) struct RelabelRule {
+    config: RelabelRuleConfig,
+    regex: Regex,
+}
+
+impl RelabelRule {
+    pub(super) fn new(config: &RelabelRuleConfig) -> anyhow::Result<Self> {
+        let regex = config.build_regex()?;
+        Ok(RelabelRule {
+            config: config.clone(),
+            regex,
+        })
+    }
+
+    fn source_value(&self, record: &MetricRecord) -> String {
+        let mut value = String::new();
+        for (i, source) in self.config.source.iter().enumerate() {
+            if i > 0 {
+                value.push_str(&self.config.separator);
+            }
+            match source {
+                RelabelTarget::MetricName => value.push_str(&record.name.display('.').to_string()),
+                RelabelTarget::Tag(name) => {
+                    if let Some(v) = record.tag_map.get(name) {
+                        value.push_str(v.as_str());
+                    }
+                }
+            }
+        }
+        value
+    }
+
+    /// Apply this rule to the record, `false` will be returned if the record should be dropped
+    pub(super) fn apply(&self, record: &mut MetricRecord) -> bool {
+        match self.config.action {
+            RelabelAction::Keep => self.regex.is_match(&self.source_value(record)),
+            RelabelAction::Drop => !self.regex.is_match(&self.source_value(record)),
+            RelabelAction::Replace => {
+                self.replace(record);
+                true
+            }
+            RelabelAction::KeepTags => {
+                self.drop_tags(record, |name| !self.regex.is_match(name.as_str()));
+                true
+            }
+            RelabelAction::DropTags => {
+                self.drop_tags(record, |name| self.regex.is_match(name.as_str()));
+                true
+            }
+        }
+    }
+
+    fn replace(&self, record: &mut MetricRecord) {
+        let source = self.source_value(record);
+        let Some(captures) = self.regex.captures(&source) else {
+            return;
+        };
+        let mut value = String::new();
+        captures.expand(&self.config.replacement, &mut value);
+
+        match &self.config.target {
+            Some(RelabelTarget::MetricName) => {
+                // keep the name unchanged if the new one is invalid
+                if let Ok(name) = MetricName::parse(&value) {
+                    record.name = Arc::new(name);
+                }
+            }
+            Some(RelabelTarget::Tag(name)) => {
+                if value.is_empty() {
+                    if record.tag_map.contains(name) {
+                        Arc::make_mut(&mut record.tag_map).drop(name);
+                    }
+                } else if let Ok(value) = MetricTagValue::from_str(&value) {
+                    Arc::make_mut(&mut record.tag_map).insert(name.clone(), value);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn drop_tags<F>(&self, record: &mut MetricRecord, should_drop: F)
+    where
+        F: Fn(&MetricTagName) -> bool,
+    {
+        let names = record
+            .tag_map
+            .iter()
+            .filter(|(name, _)| should_drop(name))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return;
+        }
+        let tag_map = Arc::make_mut(&mut record.tag_map);
+        for name in &names {
+            tag_map.drop(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MetricType, MetricValue};
+    use g3_types::metrics::MetricTagMap;
+
+    fn rule(yaml: &str) -> RelabelRule {
+        let docs = yaml_rust::YamlLoader::load_from_str(yaml).unwrap();
+        let config = RelabelRuleConfig::parse_yaml(&docs[0]).unwrap();
+        RelabelRule::new(&config).unwrap()
+    }
+
+    fn record() -> MetricRecord {
+        let mut tag_map = MetricTagMap::default();
+        tag_map.insert(
+            MetricTagName::from_str("user").unwrap(),
+            MetricTagValue::from_str("alice").unwrap(),
+        );
+        tag_map.insert(
+            MetricTagName::from_str("server").unwrap(),
+            MetricTagValue::from_str("http-1").unwrap(),
+        );
+        MetricRecord {
+            r#type: MetricType::Counter,
+            name: Arc::new(MetricName::parse("g3proxy.user.forbidden").unwrap()),
+            tag_map: Arc::new(tag_map),
+            value: MetricValue::Unsigned(1),
+            sample_rate: 1.0,
+        }
+    }
+
+    #[test]
+    fn keep_drop() {
+        let mut r = record();
+        assert!(rule("{action: keep, regex: 'g3proxy\\..*'}").apply(&mut r));
+        assert!(!rule("{action: keep, regex: 'g3proxy'}").apply(&mut r));
+        assert!(!rule("{action: drop, source: [user], regex: 'alice|bob'}").apply(&mut r));
+        assert!(rule("{action: drop, source: [user, server], regex: 'bob;.*'}").apply(&mut r));
+    }
+
+    #[test]
+    fn replace() {
+        let mut r = record();
+        let user = MetricTagName::from_str("user").unwrap();
+        let site = MetricTagName::from_str("site").unwrap();
+
+        rule(
+            "{action: replace, regex: 'g3proxy\\.(.*)', target: __name__, replacement: 'proxy.$1'}",
+        )
+        .apply(&mut r);
+        assert_eq!(r.name.display('.').to_string(), "proxy.user.forbidden");
+
+        rule("{action: replace, source: [server], regex: '(.*)-\\d+', target: site}").apply(&mut r);
+        assert_eq!(r.tag_map.get(&site).unwrap().as_str(), "http");
+
+        rule("{action: replace, target: user, replacement: ''}").apply(&mut r);
+        assert!(!r.tag_map.contains(&user));
+    }
+
+    #[test]
+    fn tags() {
+        let mut r = record();
+        rule("{action: drop_tags, regex: user}").apply(&mut r);
+        assert_eq!(r.tag_map.len(), 1);
+
+        let mut r = record();
+        rule("{action: keep_tags, regex: user}").apply(&mut r);
+        assert_eq!(r.tag_map.len(), 1);
+        assert!(
+            r.tag_map
+                .contains(&MetricTagName::from_str("user").unwrap())
+        );
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::{AHashMap, AHashSet};
+use log::warn;
+
+use g3_types::metrics::{MetricTagMap, NodeName};
+
+use crate::types::{MetricName, MetricRecord};
+
+#[derive(Default)]
+struct MetricSeries {
+    tag_maps: AHashSet<Arc<MetricTagMap>>,
+    overflow_warned: bool,
+}
+
+struct SeriesState {
+    reset_at: Instant,
+    metrics: AHashMap<Arc<MetricName>, MetricSeries>,
+}
+
+/// Limit the number of series, which is the unique tag sets, for each metric name
+pub(super) struct SeriesLimiter {
+    collector: NodeName,
+    max_series: usize,
+    reset_interval: Duration,
+    state: Mutex<SeriesState>,
+}
+
+impl SeriesLimiter {
+    pub(super) fn new(collector: NodeName, max_series: usize, reset_interval: Duration) -> Self {
+        SeriesLimiter {
+            collector,
+            max_series,
+            reset_interval,
+            state: Mutex::new(SeriesState {
+                reset_at: Instant::now() + reset_interval,
+                metrics: AHashMap::new(),
+            }),
+        }
+    }
+
+    /// Check if the record is allowed, new series will be rejected if the limit is reached
+    pub(super) fn check(&self, record: &MetricRecord) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        let now = Instant::now();
+        if now >= state.reset_at {
+            state.metrics.clear();
+            state.reset_at = now + self.reset_interval;
+        }
+
+        let series = state.metrics.entry(record.name.clone()).or_default();
+        if series.tag_maps.contains(&record.tag_map) {
+            return true;
+        }
+        if series.tag_maps.len() < self.max_series {
+            series.tag_maps.insert(record.tag_map.clone());
+            return true;
+        }
+
+        if !series.overflow_warned {
+            series.overflow_warned = true;
+            warn!(
+                "collector {}: series limit {} reached for metric {}, new series will be dropped",
+                self.collector,
+                self.max_series,
+                record.name.display('.')
+            );
+        }
+        false
+    }
+}
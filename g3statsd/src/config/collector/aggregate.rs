@@ -9,10 +9,12 @@ use std::time::Duration;
 use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
+use g3_histogram::Quantile;
 use g3_types::metrics::{MetricTagName, NodeName};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyCollectorConfig, CollectorConfig, CollectorConfigDiffAction};
+use crate::types::default_quantile_list;
 
 const COLLECTOR_CONFIG_TYPE: &str = "Aggregate";
 
@@ -22,6 +24,7 @@ pub(crate) struct AggregateCollectorConfig {
     position: Option<YamlDocPosition>,
     pub(crate) emit_interval: Duration,
     pub(crate) join_tags: Vec<MetricTagName>,
+    pub(crate) quantile_list: BTreeSet<Quantile>,
     pub(crate) next: Option<NodeName>,
     pub(crate) exporters: Vec<NodeName>,
 }
@@ -33,6 +36,7 @@ impl AggregateCollectorConfig {
             position,
             emit_interval: Duration::from_secs(1),
             join_tags: Vec::new(),
+            quantile_list: default_quantile_list(),
             next: None,
             exporters: Vec::new(),
         }
@@ -67,6 +71,11 @@ impl AggregateCollectorConfig {
                     .context(format!("invalid list of metric tag names for key {k}"))?;
                 Ok(())
             }
+            "quantile" | "quantiles" => {
+                self.quantile_list = g3_yaml::value::as_quantile_list(v)
+                    .context(format!("invalid quantile list value for key {k}"))?;
+                Ok(())
+            }
             "next" => {
                 let next = g3_yaml::value::as_metric_node_name(v)?;
                 self.next = Some(next);
@@ -22,6 +22,7 @@ pub(crate) mod aggregate;
 pub(crate) mod discard;
 pub(crate) mod internal;
 pub(crate) mod regulate;
+pub(crate) mod relabel;
 
 const CONFIG_KEY_COLLECTOR_TYPE: &str = "type";
 const CONFIG_KEY_COLLECTOR_NAME: &str = "name";
@@ -56,6 +57,7 @@ pub(crate) enum AnyCollectorConfig {
     Discard(discard::DiscardCollectorConfig),
     Internal(internal::InternalCollectorConfig),
     Regulate(regulate::RegulateCollectorConfig),
+    Relabel(relabel::RelabelCollectorConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -118,6 +120,11 @@ fn load_collector(
                 .context("failed to load this Regulate collector")?;
             Ok(AnyCollectorConfig::Regulate(collector))
         }
+        "relabel" => {
+            let collector = relabel::RelabelCollectorConfig::parse(map, position)
+                .context("failed to load this Relabel collector")?;
+            Ok(AnyCollectorConfig::Relabel(collector))
+        }
         "aggregate" => {
             let collector = aggregate::AggregateCollectorConfig::parse(map, position)
                 .context("failed to load this Aggregate collector")?;
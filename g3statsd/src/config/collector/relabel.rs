@@ -0,0 +1,273 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use regex::Regex;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::{MetricTagName, NodeName};
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyCollectorConfig, CollectorConfig, CollectorConfigDiffAction};
+
+const COLLECTOR_CONFIG_TYPE: &str = "Relabel";
+
+/// The label name to use for the metric name in source and target
+pub(crate) const METRIC_NAME_LABEL: &str = "__name__";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum RelabelAction {
+    Keep,
+    Drop,
+    Replace,
+    KeepTags,
+    DropTags,
+}
+
+impl FromStr for RelabelAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "keep" => Ok(RelabelAction::Keep),
+            "drop" => Ok(RelabelAction::Drop),
+            "replace" => Ok(RelabelAction::Replace),
+            "keep_tags" | "labelkeep" => Ok(RelabelAction::KeepTags),
+            "drop_tags" | "labeldrop" => Ok(RelabelAction::DropTags),
+            _ => Err(anyhow!("invalid relabel action {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RelabelTarget {
+    MetricName,
+    Tag(MetricTagName),
+}
+
+impl RelabelTarget {
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        if s == METRIC_NAME_LABEL {
+            Ok(RelabelTarget::MetricName)
+        } else {
+            let name =
+                MetricTagName::from_str(s).map_err(|e| anyhow!("invalid metric tag name: {e}"))?;
+            Ok(RelabelTarget::Tag(name))
+        }
+    }
+
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = v {
+            RelabelTarget::parse(s)
+        } else {
+            Err(anyhow!(
+                "yaml value type for relabel target should be string"
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RelabelRuleConfig {
+    pub(crate) action: RelabelAction,
+    pub(crate) source: Vec<RelabelTarget>,
+    pub(crate) separator: String,
+    /// the regex string, which should be fully anchored when used
+    pub(crate) regex: String,
+    pub(crate) target: Option<RelabelTarget>,
+    pub(crate) replacement: String,
+}
+
+impl RelabelRuleConfig {
+    fn new(action: RelabelAction) -> Self {
+        RelabelRuleConfig {
+            action,
+            source: vec![RelabelTarget::MetricName],
+            separator: ";".to_string(),
+            regex: "(.*)".to_string(),
+            target: None,
+            replacement: "$1".to_string(),
+        }
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("yaml value type for relabel rule should be map"));
+        };
+
+        let v = g3_yaml::hash_get_required(map, "action")?;
+        let action = g3_yaml::value::as_string(v)?;
+        let action = RelabelAction::from_str(&action)?;
+        let mut rule = RelabelRuleConfig::new(action);
+        g3_yaml::foreach_kv(map, |k, v| rule.set(k, v))?;
+        rule.check()?;
+        Ok(rule)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "action" => Ok(()),
+            "source" | "source_tags" | "source_labels" => {
+                self.source = g3_yaml::value::as_list(v, RelabelTarget::parse_yaml)
+                    .context(format!("invalid list of source tag names for key {k}"))?;
+                Ok(())
+            }
+            "separator" => {
+                self.separator = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "regex" => {
+                let regex = g3_yaml::value::as_regex(v)?;
+                self.regex = regex.as_str().to_string();
+                Ok(())
+            }
+            "target" | "target_tag" | "target_label" => {
+                let target = RelabelTarget::parse_yaml(v)
+                    .context(format!("invalid relabel target value for key {k}"))?;
+                self.target = Some(target);
+                Ok(())
+            }
+            "replacement" => {
+                self.replacement = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.action == RelabelAction::Replace && self.target.is_none() {
+            return Err(anyhow!("target is required for replace action"));
+        }
+        // make sure the anchored one is also valid
+        self.build_regex()?;
+        Ok(())
+    }
+
+    /// Build the regex, which is anchored on both ends like in Prometheus
+    pub(crate) fn build_regex(&self) -> anyhow::Result<Regex> {
+        Regex::new(&format!("^(?:{})$", self.regex))
+            .map_err(|e| anyhow!("invalid regex {}: {e}", self.regex))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RelabelCollectorConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) rules: Vec<RelabelRuleConfig>,
+    pub(crate) max_series_per_metric: usize,
+    pub(crate) series_reset_interval: Duration,
+    pub(crate) next: Option<NodeName>,
+    pub(crate) exporters: Vec<NodeName>,
+}
+
+impl RelabelCollectorConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        RelabelCollectorConfig {
+            name: NodeName::default(),
+            position,
+            rules: Vec::new(),
+            max_series_per_metric: 0,
+            series_reset_interval: Duration::from_secs(3600),
+            next: None,
+            exporters: Vec::new(),
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut collector = RelabelCollectorConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| collector.set(k, v))?;
+
+        collector.check()?;
+        Ok(collector)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_COLLECTOR_TYPE => Ok(()),
+            super::CONFIG_KEY_COLLECTOR_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "rules" | "relabel_rules" => {
+                self.rules = g3_yaml::value::as_list(v, RelabelRuleConfig::parse_yaml)
+                    .context(format!("invalid list of relabel rules for key {k}"))?;
+                Ok(())
+            }
+            "max_series_per_metric" => {
+                self.max_series_per_metric = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            "series_reset_interval" => {
+                self.series_reset_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "next" => {
+                let next = g3_yaml::value::as_metric_node_name(v)?;
+                self.next = Some(next);
+                Ok(())
+            }
+            "exporter" => {
+                self.exporters = g3_yaml::value::as_list(v, g3_yaml::value::as_metric_node_name)
+                    .context(format!("invalid list of exporter names for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.series_reset_interval.is_zero() {
+            return Err(anyhow!("series reset interval should not be zero"));
+        }
+        Ok(())
+    }
+}
+
+impl CollectorConfig for RelabelCollectorConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn collector_type(&self) -> &'static str {
+        COLLECTOR_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyCollectorConfig) -> CollectorConfigDiffAction {
+        let AnyCollectorConfig::Relabel(new) = new else {
+            return CollectorConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return CollectorConfigDiffAction::NoAction;
+        }
+
+        CollectorConfigDiffAction::Reload
+    }
+
+    fn dependent_collector(&self) -> Option<BTreeSet<NodeName>> {
+        let next = self.next.as_ref()?;
+        let mut set = BTreeSet::new();
+        set.insert(next.clone());
+        Some(set)
+    }
+}
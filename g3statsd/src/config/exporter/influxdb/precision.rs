@@ -6,6 +6,7 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 use yaml_rust::Yaml;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -35,6 +36,15 @@ impl TimestampPrecision {
         }
     }
 
+    pub(crate) fn to_datetime(self, ts: i64) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Seconds => DateTime::from_timestamp(ts, 0),
+            Self::MilliSeconds => DateTime::from_timestamp_millis(ts),
+            Self::MicroSeconds => DateTime::from_timestamp_micros(ts),
+            Self::NanoSeconds => Some(DateTime::from_timestamp_nanos(ts)),
+        }
+    }
+
     pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
         if let Yaml::String(s) = value {
             TimestampPrecision::from_str(s)
@@ -53,8 +63,8 @@ impl FromStr for TimestampPrecision {
         match s.to_lowercase().as_str() {
             "s" | "second" | "seconds" => Ok(TimestampPrecision::Seconds),
             "ms" | "millisecond" | "milliseconds" => Ok(TimestampPrecision::MilliSeconds),
-            "us" | "microsecond" | "microseconds" => Ok(TimestampPrecision::MicroSeconds),
-            "ns" | "nanosecond" | "nanoseconds" => Ok(TimestampPrecision::NanoSeconds),
+            "u" | "us" | "microsecond" | "microseconds" => Ok(TimestampPrecision::MicroSeconds),
+            "n" | "ns" | "nanosecond" | "nanoseconds" => Ok(TimestampPrecision::NanoSeconds),
             _ => Err(anyhow!("invalid timestamp precision: {s}")),
         }
     }
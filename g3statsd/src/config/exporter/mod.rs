@@ -21,6 +21,8 @@ pub(crate) mod graphite;
 pub(crate) mod influxdb;
 pub(crate) mod memory;
 pub(crate) mod opentsdb;
+pub(crate) mod otlp;
+pub(crate) mod prometheus;
 
 const CONFIG_KEY_EXPORTER_TYPE: &str = "type";
 const CONFIG_KEY_EXPORTER_NAME: &str = "name";
@@ -52,6 +54,8 @@ pub(crate) enum AnyExporterConfig {
     Opentsdb(opentsdb::OpentsdbExporterConfig),
     InfluxdbV2(influxdb::InfluxdbV2ExporterConfig),
     InfluxdbV3(influxdb::InfluxdbV3ExporterConfig),
+    Prometheus(prometheus::PrometheusExporterConfig),
+    Otlp(otlp::OtlpExporterConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -122,6 +126,16 @@ fn load_exporter(
                 .context("failed to load this InfluxDB v3 exporter")?;
             Ok(AnyExporterConfig::InfluxdbV3(exporter))
         }
+        "prometheus" => {
+            let exporter = prometheus::PrometheusExporterConfig::parse(map, position)
+                .context("failed to load this Prometheus exporter")?;
+            Ok(AnyExporterConfig::Prometheus(exporter))
+        }
+        "otlp" | "opentelemetry" => {
+            let exporter = otlp::OtlpExporterConfig::parse(map, position)
+                .context("failed to load this OTLP exporter")?;
+            Ok(AnyExporterConfig::Otlp(exporter))
+        }
         _ => Err(anyhow!("unsupported exporter type {}", exporter_type)),
     }
 }
@@ -0,0 +1,216 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use http::uri::PathAndQuery;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, NodeName};
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction};
+use crate::runtime::export::HttpExportConfig;
+use crate::types::MetricName;
+
+const EXPORTER_CONFIG_TYPE: &str = "OTLP";
+
+const DEFAULT_API_PATH: &str = "/v1/metrics";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OtlpEncoding {
+    Protobuf,
+    Json,
+}
+
+impl OtlpEncoding {
+    pub(crate) fn content_type(&self) -> &'static str {
+        match self {
+            OtlpEncoding::Protobuf => "application/x-protobuf",
+            OtlpEncoding::Json => "application/json",
+        }
+    }
+
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = value {
+            OtlpEncoding::from_str(s)
+        } else {
+            Err(anyhow!(
+                "yaml value type for otlp encoding should be string"
+            ))
+        }
+    }
+}
+
+impl FromStr for OtlpEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "protobuf" | "proto" | "binary" => Ok(OtlpEncoding::Protobuf),
+            "json" => Ok(OtlpEncoding::Json),
+            _ => Err(anyhow!("unsupported otlp encoding {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct OtlpExporterConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) emit_interval: Duration,
+    pub(crate) http_export: HttpExportConfig,
+    pub(crate) api_path: PathAndQuery,
+    pub(crate) encoding: OtlpEncoding,
+    pub(crate) headers: HeaderMap,
+    pub(crate) max_batch_size: usize,
+    pub(crate) queue_size: usize,
+    pub(crate) prefix: Option<MetricName>,
+    pub(crate) global_tags: MetricTagMap,
+    pub(crate) resource_tags: Vec<MetricTagName>,
+}
+
+impl OtlpExporterConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        let mut http_export = HttpExportConfig::new(4318);
+        http_export.set_default_max_retry(3);
+        OtlpExporterConfig {
+            name: NodeName::default(),
+            position,
+            emit_interval: Duration::from_secs(10),
+            http_export,
+            api_path: PathAndQuery::from_static(DEFAULT_API_PATH),
+            encoding: OtlpEncoding::Protobuf,
+            headers: HeaderMap::new(),
+            max_batch_size: 1000,
+            queue_size: 100000,
+            prefix: None,
+            global_tags: MetricTagMap::default(),
+            resource_tags: Vec::new(),
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut collector = OtlpExporterConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| collector.set(k, v))?;
+
+        collector.check()?;
+        Ok(collector)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_EXPORTER_TYPE => Ok(()),
+            super::CONFIG_KEY_EXPORTER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "emit_interval" => {
+                self.emit_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "path" | "api_path" => {
+                let path = g3_yaml::value::as_string(v)?;
+                self.api_path = PathAndQuery::from_str(&path)
+                    .map_err(|e| anyhow!("invalid api path {path}: {e}"))?;
+                Ok(())
+            }
+            "encoding" => {
+                self.encoding = OtlpEncoding::parse_yaml(v)
+                    .context(format!("invalid otlp encoding value for key {k}"))?;
+                Ok(())
+            }
+            "headers" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.headers.clear();
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let name = HeaderName::from_str(k)
+                        .map_err(|e| anyhow!("invalid http header name {k}: {e}"))?;
+                    let value = g3_yaml::value::as_http_header_value_string(v)
+                        .context(format!("invalid http header value for header {k}"))?;
+                    let value = HeaderValue::from_str(&value)
+                        .map_err(|e| anyhow!("invalid http header value for header {k}: {e}"))?;
+                    self.headers.append(name, value);
+                    Ok(())
+                })
+                .context(format!("invalid http headers value for key {k}"))?;
+                Ok(())
+            }
+            "max_batch_size" => {
+                self.max_batch_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "queue_size" => {
+                self.queue_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "prefix" => {
+                let prefix = MetricName::parse_yaml(v)
+                    .context(format!("invalid metric name value for key {k}"))?;
+                self.prefix = Some(prefix);
+                Ok(())
+            }
+            "global_tags" => {
+                self.global_tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                Ok(())
+            }
+            "resource_tags" => {
+                self.resource_tags = g3_yaml::value::as_list(v, g3_yaml::value::as_metric_tag_name)
+                    .context(format!("invalid list of metric tag names for key {k}"))?;
+                Ok(())
+            }
+            _ => self.http_export.set_by_yaml_kv(k, v),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.max_batch_size == 0 {
+            return Err(anyhow!("max batch size should not be 0"));
+        }
+        if self.queue_size < self.max_batch_size {
+            return Err(anyhow!("queue size should not be less than max batch size"));
+        }
+        self.http_export.check(self.name.clone())?;
+        Ok(())
+    }
+}
+
+impl ExporterConfig for OtlpExporterConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn exporter_type(&self) -> &'static str {
+        EXPORTER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
+        let AnyExporterConfig::Otlp(_new) = new else {
+            return ExporterConfigDiffAction::SpawnNew;
+        };
+
+        ExporterConfigDiffAction::Reload
+    }
+}
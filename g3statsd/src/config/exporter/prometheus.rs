@@ -0,0 +1,130 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction};
+use crate::types::MetricName;
+
+const EXPORTER_CONFIG_TYPE: &str = "Prometheus";
+
+const DEFAULT_LISTEN_PORT: u16 = 9102;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PrometheusExporterConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) listen: SocketAddr,
+    pub(crate) expire: Duration,
+    pub(crate) max_header_size: usize,
+    pub(crate) prefix: Option<MetricName>,
+    pub(crate) global_tags: MetricTagMap,
+}
+
+impl PrometheusExporterConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        PrometheusExporterConfig {
+            name: NodeName::default(),
+            position,
+            listen: SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), DEFAULT_LISTEN_PORT),
+            expire: Duration::from_secs(300),
+            max_header_size: 4096,
+            prefix: None,
+            global_tags: MetricTagMap::default(),
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut collector = PrometheusExporterConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| collector.set(k, v))?;
+
+        collector.check()?;
+        Ok(collector)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_EXPORTER_TYPE => Ok(()),
+            super::CONFIG_KEY_EXPORTER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "listen" => {
+                self.listen = g3_yaml::value::as_env_sockaddr(v)
+                    .context(format!("invalid socket address value for key {k}"))?;
+                Ok(())
+            }
+            "expire" | "series_expire" => {
+                self.expire = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "max_header_size" => {
+                self.max_header_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "prefix" => {
+                let prefix = MetricName::parse_yaml(v)
+                    .context(format!("invalid metric name value for key {k}"))?;
+                self.prefix = Some(prefix);
+                Ok(())
+            }
+            "global_tags" => {
+                self.global_tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.listen.port() == 0 {
+            return Err(anyhow!("listen port is not set"));
+        }
+        Ok(())
+    }
+}
+
+impl ExporterConfig for PrometheusExporterConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn exporter_type(&self) -> &'static str {
+        EXPORTER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
+        let AnyExporterConfig::Prometheus(new) = new else {
+            return ExporterConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ExporterConfigDiffAction::NoAction;
+        }
+
+        ExporterConfigDiffAction::Reload
+    }
+}
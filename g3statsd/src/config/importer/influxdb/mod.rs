@@ -0,0 +1,31 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+use super::{AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction};
+use super::{CONFIG_KEY_IMPORTER_NAME, CONFIG_KEY_IMPORTER_TYPE};
+use crate::config::exporter::influxdb::TimestampPrecision;
+
+mod udp;
+pub(crate) use udp::InfluxdbUdpImporterConfig;
+
+mod tcp;
+pub(crate) use tcp::InfluxdbTcpImporterConfig;
+
+mod http;
+pub(crate) use http::InfluxdbHttpImporterConfig;
+
+/// Parse the timestamp precision, `None` will be returned for `auto`
+fn parse_precision(v: &Yaml) -> anyhow::Result<Option<TimestampPrecision>> {
+    match v {
+        Yaml::String(s) if s.eq_ignore_ascii_case("auto") => Ok(None),
+        Yaml::String(_) => TimestampPrecision::parse_yaml(v).map(Some),
+        _ => Err(anyhow!(
+            "yaml value type for timestamp precision should be string"
+        )),
+    }
+}
@@ -16,6 +16,8 @@ mod registry;
 pub(crate) use registry::{clear, get_all};
 
 pub(crate) mod dummy;
+pub(crate) mod graphite;
+pub(crate) mod influxdb;
 pub(crate) mod statsd;
 
 const CONFIG_KEY_IMPORTER_TYPE: &str = "type";
@@ -50,6 +52,10 @@ pub(crate) enum AnyImporterConfig {
     StatsDUdp(statsd::StatsdUdpImporterConfig),
     #[cfg(unix)]
     StatsDUnix(statsd::StatsdUnixImporterConfig),
+    InfluxdbUdp(influxdb::InfluxdbUdpImporterConfig),
+    InfluxdbTcp(influxdb::InfluxdbTcpImporterConfig),
+    InfluxdbHttp(influxdb::InfluxdbHttpImporterConfig),
+    GraphiteTcp(graphite::GraphiteTcpImporterConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -101,6 +107,26 @@ fn load_importer(
                 .context("failed to load this StatsD_UNIX importer")?;
             Ok(AnyImporterConfig::StatsDUnix(importer))
         }
+        "influxdb_udp" => {
+            let importer = influxdb::InfluxdbUdpImporterConfig::parse(map, position)
+                .context("failed to load this InfluxDB_UDP importer")?;
+            Ok(AnyImporterConfig::InfluxdbUdp(importer))
+        }
+        "influxdb_tcp" => {
+            let importer = influxdb::InfluxdbTcpImporterConfig::parse(map, position)
+                .context("failed to load this InfluxDB_TCP importer")?;
+            Ok(AnyImporterConfig::InfluxdbTcp(importer))
+        }
+        "influxdb" | "influxdb_http" => {
+            let importer = influxdb::InfluxdbHttpImporterConfig::parse(map, position)
+                .context("failed to load this InfluxDB_HTTP importer")?;
+            Ok(AnyImporterConfig::InfluxdbHttp(importer))
+        }
+        "graphite" | "graphite_tcp" => {
+            let importer = graphite::GraphiteTcpImporterConfig::parse(map, position)
+                .context("failed to load this Graphite_TCP importer")?;
+            Ok(AnyImporterConfig::GraphiteTcp(importer))
+        }
         _ => Err(anyhow!("unsupported importer type {}", importer_type)),
     }
 }
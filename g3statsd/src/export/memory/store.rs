@@ -72,6 +72,8 @@ type GaugeInnerMap = Arc<Mutex<InnerMap<GaugeStoreValue>>>;
 pub(super) struct MemoryStore {
     counter: Mutex<AHashMap<Arc<MetricName>, CounterInnerMap>>,
     gauge: Mutex<AHashMap<Arc<MetricName>, GaugeInnerMap>>,
+    /// raw values of timer, histogram, set and distribution metrics
+    sample: Mutex<AHashMap<Arc<MetricName>, GaugeInnerMap>>,
 }
 
 impl Default for MemoryStore {
@@ -79,6 +81,7 @@ impl Default for MemoryStore {
         MemoryStore {
             counter: Mutex::new(AHashMap::default()),
             gauge: Mutex::new(AHashMap::default()),
+            sample: Mutex::new(AHashMap::default()),
         }
     }
 }
@@ -104,6 +107,17 @@ impl MemoryStore {
                 let slot = map.entry(record.name.clone()).or_default().clone();
                 drop(map);
 
+                let mut inner = slot.lock().unwrap();
+                inner.add(time, store_count, record.tag_map.clone(), record.value);
+            }
+            MetricType::Timer
+            | MetricType::Histogram
+            | MetricType::Set
+            | MetricType::Distribution => {
+                let mut map = self.sample.lock().unwrap();
+                let slot = map.entry(record.name.clone()).or_default().clone();
+                drop(map);
+
                 let mut inner = slot.lock().unwrap();
                 inner.add(time, store_count, record.tag_map.clone(), record.value);
             }
@@ -25,6 +25,8 @@ mod graphite;
 mod influxdb;
 mod memory;
 mod opentsdb;
+mod otlp;
+mod prometheus;
 
 pub(crate) trait Exporter {
     fn name(&self) -> &NodeName;
@@ -153,6 +153,10 @@ async fn spawn_new_unlocked(config: AnyExporterConfig) -> anyhow::Result<()> {
         AnyExporterConfig::InfluxdbV3(config) => {
             super::influxdb::InfluxdbV3Exporter::prepare_initial(config)?
         }
+        AnyExporterConfig::Prometheus(config) => {
+            super::prometheus::PrometheusExporter::prepare_initial(config)?
+        }
+        AnyExporterConfig::Otlp(config) => super::otlp::OtlpExporter::prepare_initial(config)?,
     };
     let name = exporter.name().clone();
     registry::add(exporter);
@@ -0,0 +1,332 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde_json::{Map, Number, Value};
+
+use g3_types::metrics::MetricTagMap;
+
+use crate::types::MetricValue;
+
+// https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/metrics/v1/metrics.proto
+
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_FIXED64: u8 = 1;
+const WIRE_TYPE_LEN: u8 = 2;
+
+const AGGREGATION_TEMPORALITY_DELTA: u64 = 1;
+
+const SCOPE_NAME: &str = "g3statsd";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum OtlpMetricKind {
+    Gauge,
+    /// monotonic sum with delta aggregation temporality
+    DeltaSum,
+}
+
+pub(super) struct OtlpDataPoint {
+    pub(super) attributes: Arc<MetricTagMap>,
+    pub(super) start_time_unix_nano: u64,
+    pub(super) time_unix_nano: u64,
+    pub(super) value: MetricValue,
+}
+
+pub(super) struct OtlpMetric {
+    pub(super) name: String,
+    pub(super) kind: OtlpMetricKind,
+    pub(super) points: Vec<OtlpDataPoint>,
+}
+
+/// The metrics grouped by the resource attributes
+pub(super) type ResourceMetricsMap<'a> = BTreeMap<&'a MetricTagMap, Vec<&'a OtlpMetric>>;
+
+fn put_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn put_key(buf: &mut Vec<u8>, field: u32, wire_type: u8) {
+    put_varint(buf, ((field as u64) << 3) | wire_type as u64);
+}
+
+fn put_fixed64(buf: &mut Vec<u8>, field: u32, v: u64) {
+    put_key(buf, field, WIRE_TYPE_FIXED64);
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_string(buf: &mut Vec<u8>, field: u32, s: &str) {
+    put_key(buf, field, WIRE_TYPE_LEN);
+    put_varint(buf, s.len() as u64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Encode the embedded message in place, and insert the length prefix after it's done
+fn put_message<F>(buf: &mut Vec<u8>, field: u32, encode: F)
+where
+    F: FnOnce(&mut Vec<u8>),
+{
+    put_key(buf, field, WIRE_TYPE_LEN);
+    let start = buf.len();
+    encode(buf);
+    let mut len_buf = Vec::with_capacity(10);
+    put_varint(&mut len_buf, (buf.len() - start) as u64);
+    buf.splice(start..start, len_buf);
+}
+
+fn put_attributes(buf: &mut Vec<u8>, field: u32, tags: &MetricTagMap) {
+    for (k, v) in tags.iter() {
+        // KeyValue
+        put_message(buf, field, |buf| {
+            put_string(buf, 1, k.as_str());
+            // AnyValue
+            put_message(buf, 2, |buf| put_string(buf, 1, v.as_str()));
+        });
+    }
+}
+
+fn put_data_point(buf: &mut Vec<u8>, point: &OtlpDataPoint) {
+    // NumberDataPoint
+    put_message(buf, 1, |buf| {
+        if point.start_time_unix_nano > 0 {
+            put_fixed64(buf, 2, point.start_time_unix_nano);
+        }
+        put_fixed64(buf, 3, point.time_unix_nano);
+        match point.value {
+            MetricValue::Double(f) => put_fixed64(buf, 4, f.to_bits()),
+            MetricValue::Signed(i) => put_fixed64(buf, 6, i as u64),
+            MetricValue::Unsigned(u) => match i64::try_from(u) {
+                Ok(i) => put_fixed64(buf, 6, i as u64),
+                Err(_) => put_fixed64(buf, 4, (u as f64).to_bits()),
+            },
+        }
+        put_attributes(buf, 7, &point.attributes);
+    });
+}
+
+fn put_metric(buf: &mut Vec<u8>, metric: &OtlpMetric) {
+    put_string(buf, 1, &metric.name);
+    match metric.kind {
+        OtlpMetricKind::Gauge => put_message(buf, 5, |buf| {
+            metric.points.iter().for_each(|p| put_data_point(buf, p));
+        }),
+        OtlpMetricKind::DeltaSum => put_message(buf, 7, |buf| {
+            metric.points.iter().for_each(|p| put_data_point(buf, p));
+            put_key(buf, 2, WIRE_TYPE_VARINT);
+            put_varint(buf, AGGREGATION_TEMPORALITY_DELTA);
+            put_key(buf, 3, WIRE_TYPE_VARINT);
+            put_varint(buf, 1);
+        }),
+    }
+}
+
+/// Encode as a protobuf `ExportMetricsServiceRequest` message
+pub(super) fn encode_protobuf(map: &ResourceMetricsMap<'_>, buf: &mut Vec<u8>) {
+    for (resource, metrics) in map {
+        // ResourceMetrics
+        put_message(buf, 1, |buf| {
+            // Resource
+            put_message(buf, 1, |buf| put_attributes(buf, 1, resource));
+            // ScopeMetrics
+            put_message(buf, 2, |buf| {
+                // InstrumentationScope
+                put_message(buf, 1, |buf| {
+                    put_string(buf, 1, SCOPE_NAME);
+                    put_string(buf, 2, crate::build::VERSION);
+                });
+                for metric in metrics {
+                    put_message(buf, 2, |buf| put_metric(buf, metric));
+                }
+            });
+        });
+    }
+}
+
+fn json_attributes(tags: &MetricTagMap) -> Value {
+    let attributes = tags
+        .iter()
+        .map(|(k, v)| {
+            let mut value = Map::new();
+            value.insert(
+                "stringValue".to_string(),
+                Value::String(v.as_str().to_string()),
+            );
+            let mut kv = Map::new();
+            kv.insert("key".to_string(), Value::String(k.as_str().to_string()));
+            kv.insert("value".to_string(), Value::Object(value));
+            Value::Object(kv)
+        })
+        .collect();
+    Value::Array(attributes)
+}
+
+/// The 64 bit integers are encoded as decimal strings in OTLP/JSON
+fn json_int64<T: ToString>(v: T) -> Value {
+    Value::String(v.to_string())
+}
+
+fn json_double(f: f64) -> Value {
+    match Number::from_f64(f) {
+        Some(n) => Value::Number(n),
+        None if f.is_nan() => Value::String("NaN".to_string()),
+        None if f.is_sign_positive() => Value::String("Infinity".to_string()),
+        None => Value::String("-Infinity".to_string()),
+    }
+}
+
+fn json_data_point(point: &OtlpDataPoint) -> Value {
+    let mut map = Map::new();
+    if !point.attributes.is_empty() {
+        map.insert("attributes".to_string(), json_attributes(&point.attributes));
+    }
+    if point.start_time_unix_nano > 0 {
+        map.insert(
+            "startTimeUnixNano".to_string(),
+            json_int64(point.start_time_unix_nano),
+        );
+    }
+    map.insert("timeUnixNano".to_string(), json_int64(point.time_unix_nano));
+    match point.value {
+        MetricValue::Double(f) => map.insert("asDouble".to_string(), json_double(f)),
+        MetricValue::Signed(i) => map.insert("asInt".to_string(), json_int64(i)),
+        MetricValue::Unsigned(u) => map.insert("asInt".to_string(), json_int64(u)),
+    };
+    Value::Object(map)
+}
+
+fn json_metric(metric: &OtlpMetric) -> Value {
+    let points = metric.points.iter().map(json_data_point).collect();
+    let mut data = Map::new();
+    data.insert("dataPoints".to_string(), Value::Array(points));
+
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String(metric.name.clone()));
+    match metric.kind {
+        OtlpMetricKind::Gauge => {
+            map.insert("gauge".to_string(), Value::Object(data));
+        }
+        OtlpMetricKind::DeltaSum => {
+            data.insert(
+                "aggregationTemporality".to_string(),
+                Value::Number(Number::from(AGGREGATION_TEMPORALITY_DELTA)),
+            );
+            data.insert("isMonotonic".to_string(), Value::Bool(true));
+            map.insert("sum".to_string(), Value::Object(data));
+        }
+    }
+    Value::Object(map)
+}
+
+/// Encode as a `ExportMetricsServiceRequest` in OTLP/JSON format
+pub(super) fn encode_json(map: &ResourceMetricsMap<'_>, buf: &mut Vec<u8>) {
+    let resource_metrics = map
+        .iter()
+        .map(|(resource, metrics)| {
+            let mut scope = Map::new();
+            scope.insert("name".to_string(), Value::String(SCOPE_NAME.to_string()));
+            scope.insert(
+                "version".to_string(),
+                Value::String(crate::build::VERSION.to_string()),
+            );
+            let mut scope_metrics = Map::new();
+            scope_metrics.insert("scope".to_string(), Value::Object(scope));
+            scope_metrics.insert(
+                "metrics".to_string(),
+                Value::Array(metrics.iter().map(|m| json_metric(m)).collect()),
+            );
+
+            let mut resource_map = Map::new();
+            resource_map.insert("attributes".to_string(), json_attributes(resource));
+            let mut map = Map::new();
+            map.insert("resource".to_string(), Value::Object(resource_map));
+            map.insert(
+                "scopeMetrics".to_string(),
+                Value::Array(vec![Value::Object(scope_metrics)]),
+            );
+            Value::Object(map)
+        })
+        .collect();
+
+    let mut request = Map::new();
+    request.insert(
+        "resourceMetrics".to_string(),
+        Value::Array(resource_metrics),
+    );
+    let _ = serde_json::to_writer(buf, &Value::Object(request));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn varint() {
+        let mut buf = Vec::new();
+        put_varint(&mut buf, 1);
+        put_varint(&mut buf, 300);
+        assert_eq!(buf, [0x01, 0xac, 0x02]);
+
+        buf.clear();
+        put_message(&mut buf, 1, |buf| buf.extend_from_slice(&[0u8; 200]));
+        assert_eq!(&buf[..3], &[0x0a, 0xc8, 0x01]);
+        assert_eq!(buf.len(), 203);
+    }
+
+    #[test]
+    fn encode() {
+        let mut resource = MetricTagMap::default();
+        resource.insert(
+            FromStr::from_str("service.name").unwrap(),
+            FromStr::from_str("g3proxy").unwrap(),
+        );
+        let mut attributes = MetricTagMap::default();
+        attributes.insert(
+            FromStr::from_str("server").unwrap(),
+            FromStr::from_str("http").unwrap(),
+        );
+        let metric = OtlpMetric {
+            name: "conn.total".to_string(),
+            kind: OtlpMetricKind::DeltaSum,
+            points: vec![OtlpDataPoint {
+                attributes: Arc::new(attributes),
+                start_time_unix_nano: 1_000_000_000,
+                time_unix_nano: 11_000_000_000,
+                value: MetricValue::Unsigned(10),
+            }],
+        };
+        let mut map = ResourceMetricsMap::new();
+        map.entry(&resource).or_default().push(&metric);
+
+        let mut buf = Vec::new();
+        encode_json(&map, &mut buf);
+        let v: Value = serde_json::from_slice(&buf).unwrap();
+        let rm = &v["resourceMetrics"][0];
+        assert_eq!(
+            rm["resource"]["attributes"][0]["value"]["stringValue"],
+            "g3proxy"
+        );
+        let m = &rm["scopeMetrics"][0]["metrics"][0];
+        assert_eq!(m["name"], "conn.total");
+        assert_eq!(m["sum"]["aggregationTemporality"], 1);
+        assert_eq!(m["sum"]["dataPoints"][0]["asInt"], "10");
+        assert_eq!(m["sum"]["dataPoints"][0]["timeUnixNano"], "11000000000");
+        assert_eq!(m["sum"]["dataPoints"][0]["attributes"][0]["key"], "server");
+
+        buf.clear();
+        encode_protobuf(&map, &mut buf);
+        // field 1 (resource_metrics), wire type LEN
+        assert_eq!(buf[0], 0x0a);
+        assert_eq!(buf[1] as usize, buf.len() - 2);
+        // the as_int field of the data point, as sfixed64
+        let as_int = [0x31, 10, 0, 0, 0, 0, 0, 0, 0];
+        assert!(buf.windows(as_int.len()).any(|w| w == as_int));
+    }
+}
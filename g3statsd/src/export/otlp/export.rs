@@ -0,0 +1,252 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use http::uri::PathAndQuery;
+use http::{HeaderMap, HeaderValue, header};
+use log::warn;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc};
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_types::metrics::{MetricTagMap, MetricTagName, NodeName};
+
+use super::encode::{self, OtlpDataPoint, OtlpMetric, OtlpMetricKind, ResourceMetricsMap};
+use crate::config::exporter::ExporterConfig;
+use crate::config::exporter::otlp::{OtlpEncoding, OtlpExporterConfig};
+use crate::runtime::export::{AggregateExport, CounterStoreValue, GaugeStoreValue, HttpExport};
+use crate::types::MetricName;
+
+/// The metric data points to send, which will hold the queue permits until dropped
+pub(super) struct OtlpMetricPiece {
+    resource: Arc<MetricTagMap>,
+    metric: OtlpMetric,
+    _permit: OwnedSemaphorePermit,
+}
+
+pub(super) struct OtlpAggregateExport {
+    exporter: NodeName,
+    emit_interval: Duration,
+    max_batch_size: usize,
+    prefix: Option<MetricName>,
+    global_resource: Arc<MetricTagMap>,
+    resource_tags: Vec<MetricTagName>,
+    queue: Arc<Semaphore>,
+    piece_sender: mpsc::UnboundedSender<OtlpMetricPiece>,
+}
+
+impl OtlpAggregateExport {
+    pub(super) fn new(
+        config: &OtlpExporterConfig,
+        piece_sender: mpsc::UnboundedSender<OtlpMetricPiece>,
+    ) -> Self {
+        OtlpAggregateExport {
+            exporter: config.name().clone(),
+            emit_interval: config.emit_interval,
+            max_batch_size: config.max_batch_size,
+            prefix: config.prefix.clone(),
+            global_resource: Arc::new(config.global_tags.clone()),
+            resource_tags: config.resource_tags.clone(),
+            queue: Arc::new(Semaphore::new(config.queue_size)),
+            piece_sender,
+        }
+    }
+
+    fn metric_name(&self, name: &MetricName) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}.{}", prefix.display('.'), name.display('.')),
+            None => name.display('.').to_string(),
+        }
+    }
+
+    /// Split the tags to resource attributes and data point attributes
+    fn split_tags(&self, tag_map: &Arc<MetricTagMap>) -> (Arc<MetricTagMap>, Arc<MetricTagMap>) {
+        if !self.resource_tags.iter().any(|k| tag_map.contains(k)) {
+            return (self.global_resource.clone(), tag_map.clone());
+        }
+
+        let mut resource = self.global_resource.as_ref().clone();
+        let mut attributes = tag_map.as_ref().clone();
+        for k in &self.resource_tags {
+            if let Some(v) = tag_map.get(k) {
+                resource.insert(k.clone(), v.clone());
+                attributes.drop(k);
+            }
+        }
+        (Arc::new(resource), Arc::new(attributes))
+    }
+
+    fn send_points<I>(&mut self, name: &MetricName, kind: OtlpMetricKind, values: I)
+    where
+        I: Iterator<Item = (Arc<MetricTagMap>, OtlpDataPoint)>,
+    {
+        let mut resource_points: AHashMap<Arc<MetricTagMap>, Vec<OtlpDataPoint>> = AHashMap::new();
+        for (resource, point) in values {
+            resource_points.entry(resource).or_default().push(point);
+        }
+
+        let name = self.metric_name(name);
+        let mut dropped = 0;
+        for (resource, mut points) in resource_points {
+            while !points.is_empty() {
+                let left = points.split_off(points.len().min(self.max_batch_size));
+                let batch = std::mem::replace(&mut points, left);
+                let Ok(permit) = self
+                    .queue
+                    .clone()
+                    .try_acquire_many_owned(batch.len() as u32)
+                else {
+                    dropped += batch.len();
+                    continue;
+                };
+                let _ = self.piece_sender.send(OtlpMetricPiece {
+                    resource: resource.clone(),
+                    metric: OtlpMetric {
+                        name: name.clone(),
+                        kind,
+                        points: batch,
+                    },
+                    _permit: permit,
+                });
+            }
+        }
+        if dropped > 0 {
+            warn!(
+                "exporter {}: queue is full, dropped {dropped} data points of metric {name}",
+                self.exporter
+            );
+        }
+    }
+}
+
+fn unix_nano(time: &DateTime<Utc>) -> u64 {
+    time.timestamp_nanos_opt().unwrap_or_default() as u64
+}
+
+impl AggregateExport for OtlpAggregateExport {
+    fn emit_interval(&self) -> Duration {
+        self.emit_interval
+    }
+
+    fn emit_gauge(
+        &mut self,
+        name: &MetricName,
+        values: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>,
+    ) {
+        let points = values
+            .iter()
+            .map(|(tag_map, gauge)| {
+                let (resource, attributes) = self.split_tags(tag_map);
+                let point = OtlpDataPoint {
+                    attributes,
+                    start_time_unix_nano: 0,
+                    time_unix_nano: unix_nano(&gauge.time),
+                    value: gauge.value,
+                };
+                (resource, point)
+            })
+            .collect::<Vec<_>>();
+        self.send_points(name, OtlpMetricKind::Gauge, points.into_iter());
+    }
+
+    fn emit_counter(
+        &mut self,
+        name: &MetricName,
+        values: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
+    ) {
+        let points = values
+            .iter()
+            .map(|(tag_map, counter)| {
+                let (resource, attributes) = self.split_tags(tag_map);
+                let start_time = counter.time - self.emit_interval;
+                let point = OtlpDataPoint {
+                    attributes,
+                    start_time_unix_nano: unix_nano(&start_time),
+                    time_unix_nano: unix_nano(&counter.time),
+                    value: counter.diff,
+                };
+                (resource, point)
+            })
+            .collect::<Vec<_>>();
+        self.send_points(name, OtlpMetricKind::DeltaSum, points.into_iter());
+    }
+}
+
+pub(super) struct OtlpHttpExport {
+    api_path: PathAndQuery,
+    static_headers: HeaderMap,
+    encoding: OtlpEncoding,
+    max_batch_size: usize,
+}
+
+impl OtlpHttpExport {
+    pub(super) fn new(config: &OtlpExporterConfig) -> anyhow::Result<Self> {
+        let mut static_headers = config.headers.clone();
+        static_headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(config.encoding.content_type()),
+        );
+        Ok(OtlpHttpExport {
+            api_path: config.api_path.clone(),
+            static_headers,
+            encoding: config.encoding,
+            max_batch_size: config.max_batch_size,
+        })
+    }
+}
+
+// https://opentelemetry.io/docs/specs/otlp/#otlphttp
+impl HttpExport for OtlpHttpExport {
+    type BodyPiece = OtlpMetricPiece;
+
+    fn api_path(&self) -> &PathAndQuery {
+        &self.api_path
+    }
+
+    fn static_headers(&self) -> &HeaderMap {
+        &self.static_headers
+    }
+
+    fn fill_body(&mut self, pieces: &[OtlpMetricPiece], body_buf: &mut Vec<u8>) -> usize {
+        let mut added_points = 0;
+        let mut handled_pieces = 0;
+        let mut map = ResourceMetricsMap::new();
+        for piece in pieces {
+            if added_points + piece.metric.points.len() > self.max_batch_size {
+                break;
+            }
+
+            map.entry(piece.resource.as_ref())
+                .or_default()
+                .push(&piece.metric);
+            handled_pieces += 1;
+            added_points += piece.metric.points.len();
+        }
+
+        if handled_pieces > 0 {
+            match self.encoding {
+                OtlpEncoding::Protobuf => encode::encode_protobuf(&map, body_buf),
+                OtlpEncoding::Json => encode::encode_json(&map, body_buf),
+            }
+        }
+        handled_pieces
+    }
+
+    fn check_response(&self, rsp: HttpForwardRemoteResponse, body: &[u8]) -> anyhow::Result<()> {
+        if !(200..300).contains(&rsp.code) {
+            if let Ok(detail) = std::str::from_utf8(body) {
+                Err(anyhow!("error response: {} {detail}", rsp.code))
+            } else {
+                Err(anyhow!("error response: {}", rsp.code))
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc;
+
+use g3_types::metrics::NodeName;
+
+use super::{ArcExporterInternal, Exporter, ExporterInternal};
+use crate::config::exporter::otlp::OtlpExporterConfig;
+use crate::config::exporter::{AnyExporterConfig, ExporterConfig};
+use crate::runtime::export::{AggregateExportRuntime, HttpExportRuntime};
+use crate::types::MetricRecord;
+
+mod export;
+use export::{OtlpAggregateExport, OtlpHttpExport};
+
+mod encode;
+
+pub(crate) struct OtlpExporter {
+    config: OtlpExporterConfig,
+    sender: mpsc::UnboundedSender<(DateTime<Utc>, MetricRecord)>,
+}
+
+impl OtlpExporter {
+    fn new(config: OtlpExporterConfig) -> anyhow::Result<Self> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (agg_sender, agg_receiver) = mpsc::unbounded_channel();
+        let aggregate_export = OtlpAggregateExport::new(&config, agg_sender);
+        let aggregate_runtime = AggregateExportRuntime::new(aggregate_export, receiver);
+
+        let http_export = OtlpHttpExport::new(&config)?;
+        let http_runtime =
+            HttpExportRuntime::new(config.http_export.clone(), http_export, agg_receiver);
+
+        tokio::spawn(async move { aggregate_runtime.into_running().await });
+        tokio::spawn(http_runtime.into_running());
+        Ok(OtlpExporter { config, sender })
+    }
+
+    pub(crate) fn prepare_initial(
+        config: OtlpExporterConfig,
+    ) -> anyhow::Result<ArcExporterInternal> {
+        let server = OtlpExporter::new(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyExporterConfig) -> anyhow::Result<OtlpExporter> {
+        if let AnyExporterConfig::Otlp(config) = config {
+            OtlpExporter::new(config)
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.exporter_type(),
+                config.exporter_type()
+            ))
+        }
+    }
+}
+
+impl Exporter for OtlpExporter {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.exporter_type()
+    }
+
+    fn add_metric(&self, time: DateTime<Utc>, record: &MetricRecord) {
+        let _ = self.sender.send((time, record.clone())); // TODO record drop
+    }
+}
+
+impl ExporterInternal for OtlpExporter {
+    fn _clone_config(&self) -> AnyExporterConfig {
+        AnyExporterConfig::Otlp(self.config.clone())
+    }
+
+    fn _reload(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal> {
+        let exporter = self.prepare_reload(config)?;
+        Ok(Arc::new(exporter))
+    }
+}
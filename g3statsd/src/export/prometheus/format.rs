@@ -0,0 +1,284 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+use g3_histogram::Quantile;
+use g3_types::metrics::MetricTagMap;
+
+use crate::types::{MetricName, MetricSummary, MetricValue};
+
+pub(super) const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+const COUNTER_SUFFIX: &str = "_total";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum FamilyType {
+    Counter,
+    Gauge,
+    Summary,
+}
+
+impl FamilyType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FamilyType::Counter => "counter",
+            FamilyType::Gauge => "gauge",
+            FamilyType::Summary => "summary",
+        }
+    }
+}
+
+/// Convert the dotted metric name to a valid OpenMetrics metric name,
+/// all chars not in `[a-zA-Z0-9_:]` will be replaced by `_`
+pub(super) fn metric_name(prefix: Option<&MetricName>, name: &MetricName) -> String {
+    let raw = match prefix {
+        Some(prefix) => format!("{}.{}", prefix.display('.'), name.display('.')),
+        None => name.display('.').to_string(),
+    };
+
+    let mut s = String::with_capacity(raw.len() + 1);
+    if raw.starts_with(|c: char| c.is_ascii_digit()) {
+        s.push('_');
+    }
+    for c in raw.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | ':' => s.push(c),
+            _ => s.push('_'),
+        }
+    }
+    s
+}
+
+/// Convert the tag name to a valid label name, all chars not in `[a-zA-Z0-9_]` will be replaced by `_`,
+/// and the `__` prefix, which is reserved for internal use, will be reduced to a single `_`
+pub(super) fn label_name(tag: &str) -> String {
+    let mut s = String::with_capacity(tag.len() + 1);
+    if tag.starts_with(|c: char| c.is_ascii_digit()) {
+        s.push('_');
+    }
+    for c in tag.chars() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => s.push(c),
+            _ => s.push('_'),
+        }
+    }
+    while s.starts_with("__") {
+        s.remove(0);
+    }
+    s
+}
+
+fn write_label_value(buf: &mut Vec<u8>, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\\' => buf.extend_from_slice(b"\\\\"),
+            '"' => buf.extend_from_slice(b"\\\""),
+            '\n' => buf.extend_from_slice(b"\\n"),
+            _ => {
+                let mut b = [0u8; 4];
+                buf.extend_from_slice(c.encode_utf8(&mut b).as_bytes());
+            }
+        }
+    }
+}
+
+fn write_value(buf: &mut Vec<u8>, value: &MetricValue) {
+    match value {
+        MetricValue::Double(f) if f.is_nan() => buf.extend_from_slice(b"NaN"),
+        MetricValue::Double(f) if f.is_infinite() => {
+            if f.is_sign_positive() {
+                buf.extend_from_slice(b"+Inf");
+            } else {
+                buf.extend_from_slice(b"-Inf");
+            }
+        }
+        _ => {
+            let _ = write!(buf, "{value}");
+        }
+    }
+}
+
+pub(super) enum FamilySample<'a> {
+    Value(MetricValue),
+    Summary(&'a MetricSummary),
+}
+
+/// All samples in a metric family, which share the same sanitized name
+pub(super) struct MetricFamily<'a> {
+    r#type: FamilyType,
+    samples: Vec<(&'a MetricTagMap, FamilySample<'a>)>,
+}
+
+impl<'a> MetricFamily<'a> {
+    pub(super) fn new(r#type: FamilyType) -> Self {
+        MetricFamily {
+            r#type,
+            samples: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub(super) fn r#type(&self) -> FamilyType {
+        self.r#type
+    }
+
+    pub(super) fn add_sample(&mut self, tags: &'a MetricTagMap, sample: FamilySample<'a>) {
+        self.samples.push((tags, sample));
+    }
+
+    pub(super) fn serialize(
+        &self,
+        name: &str,
+        global_tags: &MetricTagMap,
+        quantile_list: &BTreeSet<Quantile>,
+        buf: &mut Vec<u8>,
+    ) {
+        let family_name = match self.r#type {
+            FamilyType::Counter => name.strip_suffix(COUNTER_SUFFIX).unwrap_or(name),
+            FamilyType::Gauge | FamilyType::Summary => name,
+        };
+        let _ = writeln!(buf, "# TYPE {family_name} {}", self.r#type.as_str());
+
+        let mut labels = BTreeMap::new();
+        for (tags, sample) in &self.samples {
+            labels.clear();
+            for (k, v) in global_tags.iter().chain(tags.iter()) {
+                let name = label_name(k.as_str());
+                if !name.is_empty() {
+                    labels.insert(name, v.as_str());
+                }
+            }
+
+            match sample {
+                FamilySample::Value(value) => {
+                    let suffix = match self.r#type {
+                        FamilyType::Counter => COUNTER_SUFFIX,
+                        _ => "",
+                    };
+                    write_sample(buf, family_name, suffix, &labels, None, value);
+                }
+                FamilySample::Summary(summary) => {
+                    for quantile in quantile_list {
+                        let value = MetricValue::Double(summary.quantile(quantile));
+                        let quantile_label = Some(quantile.as_str());
+                        write_sample(buf, family_name, "", &labels, quantile_label, &value);
+                    }
+                    let sum = MetricValue::Double(summary.sum());
+                    write_sample(buf, family_name, "_sum", &labels, None, &sum);
+                    let count = MetricValue::Unsigned(summary.count());
+                    write_sample(buf, family_name, "_count", &labels, None, &count);
+                }
+            }
+        }
+    }
+}
+
+fn write_sample(
+    buf: &mut Vec<u8>,
+    name: &str,
+    suffix: &str,
+    labels: &BTreeMap<String, &str>,
+    quantile: Option<&str>,
+    value: &MetricValue,
+) {
+    buf.extend_from_slice(name.as_bytes());
+    buf.extend_from_slice(suffix.as_bytes());
+    if !labels.is_empty() || quantile.is_some() {
+        buf.push(b'{');
+        let quantile = quantile.map(|q| ("quantile", q));
+        let all_labels = labels.iter().map(|(k, v)| (k.as_str(), *v)).chain(quantile);
+        for (i, (k, v)) in all_labels.enumerate() {
+            if i > 0 {
+                buf.push(b',');
+            }
+            buf.extend_from_slice(k.as_bytes());
+            buf.extend_from_slice(b"=\"");
+            write_label_value(buf, v);
+            buf.push(b'"');
+        }
+        buf.push(b'}');
+    }
+    buf.push(b' ');
+    write_value(buf, value);
+    buf.push(b'\n');
+}
+
+pub(super) fn finish(buf: &mut Vec<u8>) {
+    buf.extend_from_slice(b"# EOF\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn sanitize_name() {
+        let name = MetricName::parse("g3proxy.server-conn.total").unwrap();
+        assert_eq!(metric_name(None, &name), "g3proxy_server_conn_total");
+
+        let prefix = MetricName::parse("1st").unwrap();
+        assert_eq!(
+            metric_name(Some(&prefix), &name),
+            "_1st_g3proxy_server_conn_total"
+        );
+
+        assert_eq!(label_name("stat_id"), "stat_id");
+        assert_eq!(label_name("server.name"), "server_name");
+        assert_eq!(label_name("__name"), "_name");
+        assert_eq!(label_name("1a"), "_1a");
+    }
+
+    #[test]
+    fn serialize_family() {
+        let mut tags = MetricTagMap::default();
+        tags.insert(
+            FromStr::from_str("server").unwrap(),
+            FromStr::from_str("http").unwrap(),
+        );
+        let mut global_tags = MetricTagMap::default();
+        global_tags.insert(
+            FromStr::from_str("daemon-group").unwrap(),
+            FromStr::from_str("g3").unwrap(),
+        );
+        let empty = MetricTagMap::default();
+
+        let quantile_list = BTreeSet::from([Quantile::PCT50]);
+        let mut summary = MetricSummary::default();
+        summary.add(MetricValue::Unsigned(2), 1.0);
+
+        let mut buf = Vec::new();
+        let mut counter = MetricFamily::new(FamilyType::Counter);
+        counter.add_sample(&tags, FamilySample::Value(MetricValue::Unsigned(10)));
+        counter.serialize("conn_total", &global_tags, &quantile_list, &mut buf);
+
+        let mut gauge = MetricFamily::new(FamilyType::Gauge);
+        gauge.add_sample(
+            &empty,
+            FamilySample::Value(MetricValue::Double(f64::INFINITY)),
+        );
+        gauge.serialize("alive", &empty, &quantile_list, &mut buf);
+
+        let mut timer = MetricFamily::new(FamilyType::Summary);
+        timer.add_sample(&tags, FamilySample::Summary(&summary));
+        timer.serialize("latency", &empty, &quantile_list, &mut buf);
+        finish(&mut buf);
+
+        assert_eq!(
+            std::str::from_utf8(&buf).unwrap(),
+            "# TYPE conn counter\n\
+             conn_total{daemon_group=\"g3\",server=\"http\"} 10\n\
+             # TYPE alive gauge\n\
+             alive +Inf\n\
+             # TYPE latency summary\n\
+             latency{server=\"http\",quantile=\"0.50\"} 2.0\n\
+             latency_sum{server=\"http\"} 2.0\n\
+             latency_count{server=\"http\"} 1\n\
+             # EOF\n"
+        );
+    }
+}
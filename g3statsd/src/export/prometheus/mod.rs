@@ -0,0 +1,95 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+
+use g3_types::metrics::NodeName;
+
+use super::{ArcExporterInternal, Exporter, ExporterInternal};
+use crate::config::exporter::prometheus::PrometheusExporterConfig;
+use crate::config::exporter::{AnyExporterConfig, ExporterConfig};
+use crate::types::MetricRecord;
+
+mod format;
+
+mod store;
+use store::PrometheusStore;
+
+mod server;
+use server::PrometheusServer;
+
+pub(crate) struct PrometheusExporter {
+    config: PrometheusExporterConfig,
+    store: Arc<PrometheusStore>,
+    server: Arc<PrometheusServer>,
+}
+
+impl PrometheusExporter {
+    pub(crate) fn prepare_initial(
+        config: PrometheusExporterConfig,
+    ) -> anyhow::Result<ArcExporterInternal> {
+        let store = Arc::new(PrometheusStore::default());
+        let server = PrometheusServer::spawn(&config, store.clone())?;
+        Ok(Arc::new(PrometheusExporter {
+            config,
+            store,
+            server: Arc::new(server),
+        }))
+    }
+
+    fn prepare_reload(&self, config: AnyExporterConfig) -> anyhow::Result<PrometheusExporter> {
+        if let AnyExporterConfig::Prometheus(config) = config {
+            let store = self.store.clone();
+            let server = if self.server.listen() == config.listen {
+                self.server.update_config(&config);
+                self.server.clone()
+            } else {
+                Arc::new(PrometheusServer::spawn(&config, store.clone())?)
+            };
+            Ok(PrometheusExporter {
+                config,
+                store,
+                server,
+            })
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.exporter_type(),
+                config.exporter_type()
+            ))
+        }
+    }
+}
+
+impl Exporter for PrometheusExporter {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.exporter_type()
+    }
+
+    fn add_metric(&self, _time: DateTime<Utc>, record: &MetricRecord) {
+        self.store.add_record(record, Instant::now());
+    }
+}
+
+impl ExporterInternal for PrometheusExporter {
+    fn _clone_config(&self) -> AnyExporterConfig {
+        AnyExporterConfig::Prometheus(self.config.clone())
+    }
+
+    fn _reload(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal> {
+        let exporter = self.prepare_reload(config)?;
+        Ok(Arc::new(exporter))
+    }
+}
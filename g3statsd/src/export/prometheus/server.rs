@@ -0,0 +1,181 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use http::{Method, StatusCode, Version};
+use log::{debug, warn};
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use g3_http::server::HttpProxyClientRequest;
+use g3_types::metrics::NodeName;
+use g3_types::net::TcpListenConfig;
+
+use super::PrometheusStore;
+use super::format::CONTENT_TYPE;
+use crate::config::exporter::ExporterConfig;
+use crate::config::exporter::prometheus::PrometheusExporterConfig;
+
+const METRICS_PATH: &str = "/metrics";
+const REQUEST_HEADER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The HTTP server for Prometheus to scrape, which will be stopped when dropped
+pub(super) struct PrometheusServer {
+    listen: SocketAddr,
+    config: Arc<ArcSwap<PrometheusExporterConfig>>,
+    handle: JoinHandle<()>,
+}
+
+impl PrometheusServer {
+    pub(super) fn spawn(
+        config: &PrometheusExporterConfig,
+        store: Arc<PrometheusStore>,
+    ) -> anyhow::Result<Self> {
+        let listen_config = TcpListenConfig::new(config.listen);
+        let listener = g3_socket::tcp::new_listen_to(&listen_config)
+            .map_err(|e| anyhow!("failed to listen on {}: {e}", config.listen))?;
+
+        let shared_config = Arc::new(ArcSwap::from_pointee(config.clone()));
+        let handle = tokio::spawn(accept_loop(
+            config.name().clone(),
+            listener,
+            shared_config.clone(),
+            store,
+        ));
+        Ok(PrometheusServer {
+            listen: config.listen,
+            config: shared_config,
+            handle,
+        })
+    }
+
+    #[inline]
+    pub(super) fn listen(&self) -> SocketAddr {
+        self.listen
+    }
+
+    pub(super) fn update_config(&self, config: &PrometheusExporterConfig) {
+        self.config.store(Arc::new(config.clone()));
+    }
+}
+
+impl Drop for PrometheusServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn accept_loop(
+    exporter: NodeName,
+    listener: TcpListener,
+    config: Arc<ArcSwap<PrometheusExporterConfig>>,
+    store: Arc<PrometheusStore>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let config = config.clone();
+                let store = store.clone();
+                let exporter = exporter.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, config, store).await {
+                        debug!("exporter {exporter}: connection from {peer} closed: {e}");
+                    }
+                });
+            }
+            Err(e) => {
+                warn!("exporter {exporter}: failed to accept new connection: {e}");
+            }
+        }
+    }
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    config: Arc<ArcSwap<PrometheusExporterConfig>>,
+    store: Arc<PrometheusStore>,
+) -> io::Result<()> {
+    let (r, mut w) = stream.into_split();
+    let mut reader = BufReader::new(r);
+    let mut body = Vec::with_capacity(4096);
+
+    loop {
+        let config = config.load_full();
+        let mut version = Version::HTTP_11;
+        let req = match tokio::time::timeout(
+            REQUEST_HEADER_TIMEOUT,
+            HttpProxyClientRequest::parse_basic(&mut reader, config.max_header_size, &mut version),
+        )
+        .await
+        {
+            Ok(Ok(req)) => req,
+            Ok(Err(e)) => return Err(io::Error::other(e)),
+            Err(_) => return Ok(()),
+        };
+        // a scrape request should never have a body, close the connection if there is
+        let keep_alive = req.keep_alive() && req.body_type().is_none();
+
+        body.clear();
+        let status = if req.uri.path() != METRICS_PATH {
+            StatusCode::NOT_FOUND
+        } else if req.method == Method::GET || req.method == Method::HEAD {
+            store.serialize(&config, Instant::now(), &mut body);
+            StatusCode::OK
+        } else {
+            StatusCode::METHOD_NOT_ALLOWED
+        };
+        let send_body = req.method != Method::HEAD;
+        send_response(&mut w, status, &body, send_body, keep_alive).await?;
+
+        if !keep_alive {
+            return w.shutdown().await;
+        }
+    }
+}
+
+async fn send_response<W>(
+    writer: &mut W,
+    status: StatusCode,
+    body: &[u8],
+    send_body: bool,
+    keep_alive: bool,
+) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut header = Vec::with_capacity(256);
+    header.extend_from_slice(b"HTTP/1.1 ");
+    header.extend_from_slice(status.as_str().as_bytes());
+    header.push(b' ');
+    header.extend_from_slice(status.canonical_reason().unwrap_or_default().as_bytes());
+    header.extend_from_slice(b"\r\n");
+    if status == StatusCode::OK {
+        header.extend_from_slice(b"Content-Type: ");
+        header.extend_from_slice(CONTENT_TYPE.as_bytes());
+        header.extend_from_slice(b"\r\n");
+    } else if status == StatusCode::METHOD_NOT_ALLOWED {
+        header.extend_from_slice(b"Allow: GET, HEAD\r\n");
+    }
+    header.extend_from_slice(b"Content-Length: ");
+    header.extend_from_slice(itoa::Buffer::new().format(body.len()).as_bytes());
+    header.extend_from_slice(b"\r\n");
+    if !keep_alive {
+        header.extend_from_slice(b"Connection: close\r\n");
+    }
+    header.extend_from_slice(b"\r\n");
+
+    writer.write_all(&header).await?;
+    if send_body && !body.is_empty() {
+        writer.write_all(body).await?;
+    }
+    writer.flush().await
+}
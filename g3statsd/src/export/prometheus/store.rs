@@ -0,0 +1,217 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use log::debug;
+
+use g3_types::metrics::MetricTagMap;
+
+use super::format::{self, FamilySample, FamilyType, MetricFamily};
+use crate::config::exporter::prometheus::PrometheusExporterConfig;
+use crate::types::{MetricName, MetricRecord, MetricSet, MetricSummary, MetricType, MetricValue};
+
+trait UpdateTime {
+    fn update_time(&self) -> Instant;
+}
+
+struct SeriesValue {
+    value: MetricValue,
+    update_time: Instant,
+}
+
+impl UpdateTime for SeriesValue {
+    fn update_time(&self) -> Instant {
+        self.update_time
+    }
+}
+
+struct SummarySeries {
+    summary: MetricSummary,
+    update_time: Instant,
+}
+
+impl UpdateTime for SummarySeries {
+    fn update_time(&self) -> Instant {
+        self.update_time
+    }
+}
+
+/// The set members with their last seen time, only the members seen within the expire duration are counted
+struct SetSeries {
+    members: AHashMap<u64, Instant>,
+    update_time: Instant,
+}
+
+impl UpdateTime for SetSeries {
+    fn update_time(&self) -> Instant {
+        self.update_time
+    }
+}
+
+type SeriesMap<T> = AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, T>>;
+
+#[derive(Default)]
+pub(super) struct PrometheusStore {
+    counter: Mutex<SeriesMap<SeriesValue>>,
+    gauge: Mutex<SeriesMap<SeriesValue>>,
+    summary: Mutex<SeriesMap<SummarySeries>>,
+    set: Mutex<SeriesMap<SetSeries>>,
+}
+
+fn retain_series<T: UpdateTime>(map: &mut SeriesMap<T>, expire: Duration, now: Instant) {
+    if expire.is_zero() {
+        return;
+    }
+    map.retain(|_, series| {
+        series.retain(|_, v| now.duration_since(v.update_time()) < expire);
+        !series.is_empty()
+    });
+}
+
+impl PrometheusStore {
+    pub(super) fn add_record(&self, record: &MetricRecord, now: Instant) {
+        match record.r#type {
+            MetricType::Counter => {
+                let mut map = self.counter.lock().unwrap();
+                map.entry(record.name.clone())
+                    .or_default()
+                    .entry(record.tag_map.clone())
+                    .and_modify(|v| {
+                        v.value += record.value;
+                        v.update_time = now;
+                    })
+                    .or_insert(SeriesValue {
+                        value: record.value,
+                        update_time: now,
+                    });
+            }
+            MetricType::Gauge => {
+                let mut map = self.gauge.lock().unwrap();
+                map.entry(record.name.clone()).or_default().insert(
+                    record.tag_map.clone(),
+                    SeriesValue {
+                        value: record.value,
+                        update_time: now,
+                    },
+                );
+            }
+            MetricType::Timer | MetricType::Histogram | MetricType::Distribution => {
+                let mut map = self.summary.lock().unwrap();
+                let series = map
+                    .entry(record.name.clone())
+                    .or_default()
+                    .entry(record.tag_map.clone())
+                    .or_insert_with(|| SummarySeries {
+                        summary: MetricSummary::default(),
+                        update_time: now,
+                    });
+                series.summary.add(record.value, record.sample_rate);
+                series.update_time = now;
+            }
+            MetricType::Set => {
+                let mut map = self.set.lock().unwrap();
+                let series = map
+                    .entry(record.name.clone())
+                    .or_default()
+                    .entry(record.tag_map.clone())
+                    .or_insert_with(|| SetSeries {
+                        members: AHashMap::default(),
+                        update_time: now,
+                    });
+                series.members.insert(MetricSet::member(record.value), now);
+                series.update_time = now;
+            }
+        }
+    }
+
+    /// Drop the expired series and serialize all the left ones in OpenMetrics text format
+    pub(super) fn serialize(
+        &self,
+        config: &PrometheusExporterConfig,
+        now: Instant,
+        buf: &mut Vec<u8>,
+    ) {
+        let mut counter = self.counter.lock().unwrap();
+        retain_series(&mut counter, config.expire, now);
+        let mut gauge = self.gauge.lock().unwrap();
+        retain_series(&mut gauge, config.expire, now);
+        let mut summary = self.summary.lock().unwrap();
+        retain_series(&mut summary, config.expire, now);
+        let mut set = self.set.lock().unwrap();
+        retain_series(&mut set, config.expire, now);
+        if !config.expire.is_zero() {
+            for series in set.values_mut().flat_map(|m| m.values_mut()) {
+                series
+                    .members
+                    .retain(|_, seen| now.duration_since(*seen) < config.expire);
+            }
+        }
+
+        let prefix = config.prefix.as_ref();
+        let mut families = BTreeMap::new();
+        let mut add_family_sample = |name: &MetricName, r#type, tags, sample| {
+            let name = format::metric_name(prefix, name);
+            let family = match families.entry(name) {
+                Entry::Occupied(o) => o.into_mut(),
+                Entry::Vacant(v) => v.insert(MetricFamily::new(r#type)),
+            };
+            if family.r#type() != r#type {
+                debug!("metric family type conflict, drop the {:?} samples", r#type);
+                return;
+            }
+            family.add_sample(tags, sample);
+        };
+
+        for (name, series) in counter.iter() {
+            for (tags, v) in series {
+                add_family_sample(
+                    name,
+                    FamilyType::Counter,
+                    tags,
+                    FamilySample::Value(v.value),
+                );
+            }
+        }
+        for (name, series) in gauge.iter() {
+            for (tags, v) in series {
+                add_family_sample(name, FamilyType::Gauge, tags, FamilySample::Value(v.value));
+            }
+        }
+        for (name, series) in set.iter() {
+            for (tags, v) in series {
+                let cardinality = MetricValue::Unsigned(v.members.len() as u64);
+                add_family_sample(
+                    name,
+                    FamilyType::Gauge,
+                    tags,
+                    FamilySample::Value(cardinality),
+                );
+            }
+        }
+        for (name, series) in summary.iter() {
+            for (tags, v) in series {
+                let sample = FamilySample::Summary(&v.summary);
+                add_family_sample(name, FamilyType::Summary, tags, sample);
+            }
+        }
+
+        let quantile_list = crate::types::default_quantile_list();
+        for (name, family) in &families {
+            family.serialize(name, &config.global_tags, &quantile_list, buf);
+        }
+        format::finish(buf);
+        drop(families);
+
+        // the quantiles only cover the values added since the last scrape
+        for series in summary.values_mut().flat_map(|m| m.values_mut()) {
+            series.summary.reset_quantiles();
+        }
+    }
+}
@@ -7,14 +7,16 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use async_trait::async_trait;
+use tokio::net::TcpStream;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
 
-use g3_daemon::listen::ReceiveUdpServer;
 #[cfg(unix)]
 use g3_daemon::listen::ReceiveUnixDatagramServer;
-use g3_daemon::server::{BaseServer, ServerReloadCommand};
+use g3_daemon::listen::{AcceptTcpServer, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_types::metrics::NodeName;
 
 use super::{ArcImporter, ArcImporterInternal, Importer, ImporterInternal, ImporterRegistry};
@@ -132,6 +134,11 @@ impl ReceiveUnixDatagramServer for DummyImporter {
     fn receive_unix_packet(&self, _packet: &[u8], _peer_addr: UnixSocketAddr) {}
 }
 
+#[async_trait]
+impl AcceptTcpServer for DummyImporter {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
 impl Importer for DummyImporter {
     fn collector(&self) -> &NodeName {
         Default::default()
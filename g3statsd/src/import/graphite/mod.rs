@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+mod parser;
+use parser::parse_line;
+
+mod tcp;
+pub(super) use tcp::GraphiteTcpImporter;
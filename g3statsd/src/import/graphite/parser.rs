@@ -0,0 +1,123 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue};
+
+use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+
+// https://graphite.readthedocs.io/en/latest/feeding-carbon.html
+
+#[derive(Debug, Error)]
+pub(crate) enum GraphiteParseError {
+    #[error("no path")]
+    NoPath,
+    #[error("invalid path: {0}")]
+    InvalidPath(anyhow::Error),
+    #[error("invalid tag: {0}")]
+    InvalidTag(anyhow::Error),
+    #[error("no value")]
+    NoValue,
+    #[error("invalid value: {0}")]
+    InvalidValue(anyhow::Error),
+    #[error("invalid timestamp")]
+    InvalidTimestamp,
+}
+
+/// Parse a plaintext line in format `<path>[;<tag>=<value>...] <value> [<timestamp>]`
+pub(crate) fn parse_line(
+    line: &[u8],
+) -> Result<(Option<DateTime<Utc>>, MetricRecord), GraphiteParseError> {
+    let line = std::str::from_utf8(line)
+        .map_err(|e| GraphiteParseError::InvalidPath(anyhow!("invalid utf-8 string: {e}")))?;
+    let mut parts = line.split_ascii_whitespace();
+
+    let Some(path) = parts.next() else {
+        return Err(GraphiteParseError::NoPath);
+    };
+    let mut path_parts = path.split(';');
+    let name = path_parts.next().unwrap_or_default();
+    if name.is_empty() {
+        return Err(GraphiteParseError::NoPath);
+    }
+    let name = MetricName::parse(name)
+        .map_err(|e| GraphiteParseError::InvalidPath(anyhow!("invalid metric name: {e}")))?;
+
+    let mut tag_map = MetricTagMap::default();
+    for tag in path_parts {
+        let Some((k, v)) = tag.split_once('=') else {
+            return Err(GraphiteParseError::InvalidTag(anyhow!("no tag value")));
+        };
+        let k = MetricTagName::from_str(k)
+            .map_err(|e| GraphiteParseError::InvalidTag(anyhow!("invalid tag name {k}: {e}")))?;
+        let v = MetricTagValue::from_str(v)
+            .map_err(|e| GraphiteParseError::InvalidTag(anyhow!("invalid tag value {v}: {e}")))?;
+        tag_map.insert(k, v);
+    }
+
+    let Some(value) = parts.next() else {
+        return Err(GraphiteParseError::NoValue);
+    };
+    let value = MetricValue::from_str(value).map_err(GraphiteParseError::InvalidValue)?;
+
+    // the timestamp is optional, and -1 means now
+    let time = match parts.next() {
+        Some("-1") | None => None,
+        Some(s) => {
+            let ts = f64::from_str(s).map_err(|_| GraphiteParseError::InvalidTimestamp)?;
+            let time = DateTime::from_timestamp_millis((ts * 1000.0) as i64)
+                .ok_or(GraphiteParseError::InvalidTimestamp)?;
+            Some(time)
+        }
+    };
+
+    let record = MetricRecord {
+        r#type: MetricType::Gauge,
+        name: Arc::new(name),
+        tag_map: Arc::new(tag_map),
+        value,
+        sample_rate: 1.0,
+    };
+    Ok((time, record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plaintext() {
+        let (time, r) = parse_line(b"servers.host1.cpu 12.5 1556813561").unwrap();
+        assert_eq!(time.unwrap().timestamp(), 1556813561);
+        assert_eq!(r.name.display('.').to_string(), "servers.host1.cpu");
+        assert_eq!(r.value, MetricValue::Double(12.5));
+        assert!(r.tag_map.is_empty());
+
+        let (time, r) = parse_line(b"disk.used;dc=us-west;host=a  100  -1").unwrap();
+        assert!(time.is_none());
+        assert_eq!(r.value, MetricValue::Unsigned(100));
+        assert_eq!(r.tag_map.len(), 2);
+
+        assert!(matches!(parse_line(b""), Err(GraphiteParseError::NoPath)));
+        assert!(matches!(
+            parse_line(b"disk.used"),
+            Err(GraphiteParseError::NoValue)
+        ));
+        assert!(matches!(
+            parse_line(b"disk.used;dc 1"),
+            Err(GraphiteParseError::InvalidTag(_))
+        ));
+        assert!(matches!(
+            parse_line(b"disk.used 1 now"),
+            Err(GraphiteParseError::InvalidTimestamp)
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::debug;
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::unix::SocketAddr as UnixSocketAddr;
+use tokio::sync::broadcast;
+
+#[cfg(unix)]
+use g3_daemon::listen::ReceiveUnixDatagramServer;
+use g3_daemon::listen::{AcceptTcpServer, ListenStats, ListenTcpRuntime, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::metrics::NodeName;
+
+use super::parse_line;
+use crate::collect::ArcCollector;
+use crate::config::importer::graphite::GraphiteTcpImporterConfig;
+use crate::config::importer::{AnyImporterConfig, ImporterConfig};
+use crate::import::{
+    ArcImporter, ArcImporterInternal, Importer, ImporterInternal, ImporterRegistry, WrapArcImporter,
+};
+
+pub(crate) struct GraphiteTcpImporter {
+    config: GraphiteTcpImporterConfig,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    listen_stats: Arc<ListenStats>,
+
+    collector: ArcSwap<ArcCollector>,
+    reload_version: usize,
+}
+
+impl GraphiteTcpImporter {
+    fn new(
+        config: GraphiteTcpImporterConfig,
+        listen_stats: Arc<ListenStats>,
+        reload_version: usize,
+    ) -> Self {
+        let reload_sender = crate::import::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let collector = Arc::new(crate::collect::get_or_insert_default(config.collector()));
+
+        GraphiteTcpImporter {
+            config,
+            ingress_net_filter,
+            reload_sender,
+            listen_stats,
+            collector: ArcSwap::new(collector),
+            reload_version,
+        }
+    }
+
+    pub(crate) fn prepare_initial(
+        config: GraphiteTcpImporterConfig,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+        let server = GraphiteTcpImporter::new(config, listen_stats, 1);
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyImporterConfig) -> anyhow::Result<GraphiteTcpImporter> {
+        if let AnyImporterConfig::GraphiteTcp(config) = config {
+            let listen_stats = self.listen_stats.clone();
+            Ok(GraphiteTcpImporter::new(
+                config,
+                listen_stats,
+                self.reload_version + 1,
+            ))
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.importer_type(),
+                config.importer_type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl ImporterInternal for GraphiteTcpImporter {
+    fn _clone_config(&self) -> AnyImporterConfig {
+        AnyImporterConfig::GraphiteTcp(self.config.clone())
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_collector_in_place(&self) {
+        let collector = crate::collect::get_or_insert_default(self.config.collector());
+        self.collector.store(Arc::new(collector));
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, importer: ArcImporter) -> anyhow::Result<()> {
+        let runtime = ListenTcpRuntime::new(WrapArcImporter(importer), self.listen_stats.clone());
+        runtime.run_all_instances(
+            &self.config.listen,
+            self.config.listen_in_worker,
+            &self.reload_sender,
+        )
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+    }
+}
+
+impl BaseServer for GraphiteTcpImporter {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.importer_type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+impl ReceiveUdpServer for GraphiteTcpImporter {
+    fn receive_udp_packet(
+        &self,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
+}
+
+#[cfg(unix)]
+impl ReceiveUnixDatagramServer for GraphiteTcpImporter {
+    fn receive_unix_packet(&self, _packet: &[u8], _peer_addr: UnixSocketAddr) {}
+}
+
+#[async_trait]
+impl AcceptTcpServer for GraphiteTcpImporter {
+    async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        let worker_id = cc_info.worker_id();
+        let r = crate::import::recv_tcp_lines(stream, self.config.max_line_length, |line| {
+            if line.is_empty() {
+                return;
+            }
+            match parse_line(line) {
+                Ok((time, record)) => {
+                    let time = time.unwrap_or_else(Utc::now);
+                    self.collector.load().add_metric(time, record, worker_id);
+                }
+                Err(e) => debug!("invalid Graphite line from {client_addr}: {e}"),
+            }
+        })
+        .await;
+        if let Err(e) = r {
+            debug!("Graphite connection from {client_addr} closed: {e}");
+        }
+    }
+}
+
+impl Importer for GraphiteTcpImporter {
+    fn collector(&self) -> &NodeName {
+        self.config.collector()
+    }
+}
@@ -0,0 +1,471 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use http::{Method, StatusCode, Version, header};
+use log::debug;
+use tokio::io::{AsyncBufRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::unix::SocketAddr as UnixSocketAddr;
+use tokio::sync::broadcast;
+
+#[cfg(unix)]
+use g3_daemon::listen::ReceiveUnixDatagramServer;
+use g3_daemon::listen::{AcceptTcpServer, ListenStats, ListenTcpRuntime, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+use g3_http::server::{HttpProxyClientRequest, HttpRequestParseError};
+use g3_http::{HttpBodyDecodeReader, HttpBodyType};
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::metrics::NodeName;
+
+use crate::collect::ArcCollector;
+use crate::config::exporter::influxdb::TimestampPrecision;
+use crate::config::importer::influxdb::InfluxdbHttpImporterConfig;
+use crate::config::importer::{AnyImporterConfig, ImporterConfig};
+use crate::import::{
+    ArcImporter, ArcImporterInternal, Importer, ImporterInternal, ImporterRegistry, WrapArcImporter,
+};
+
+const PING_PATH: &str = "/ping";
+const REQUEST_HEADER_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) struct InfluxdbHttpImporter {
+    config: InfluxdbHttpImporterConfig,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    listen_stats: Arc<ListenStats>,
+
+    collector: ArcSwap<ArcCollector>,
+    reload_version: usize,
+}
+
+impl InfluxdbHttpImporter {
+    fn new(
+        config: InfluxdbHttpImporterConfig,
+        listen_stats: Arc<ListenStats>,
+        reload_version: usize,
+    ) -> Self {
+        let reload_sender = crate::import::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let collector = Arc::new(crate::collect::get_or_insert_default(config.collector()));
+
+        InfluxdbHttpImporter {
+            config,
+            ingress_net_filter,
+            reload_sender,
+            listen_stats,
+            collector: ArcSwap::new(collector),
+            reload_version,
+        }
+    }
+
+    pub(crate) fn prepare_initial(
+        config: InfluxdbHttpImporterConfig,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+        let server = InfluxdbHttpImporter::new(config, listen_stats, 1);
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyImporterConfig) -> anyhow::Result<InfluxdbHttpImporter> {
+        if let AnyImporterConfig::InfluxdbHttp(config) = config {
+            let listen_stats = self.listen_stats.clone();
+            Ok(InfluxdbHttpImporter::new(
+                config,
+                listen_stats,
+                self.reload_version + 1,
+            ))
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.importer_type(),
+                config.importer_type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    async fn serve_connection(
+        &self,
+        stream: TcpStream,
+        client_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) -> anyhow::Result<()> {
+        let (r, mut w) = stream.into_split();
+        let mut reader = BufReader::new(r);
+        let mut body = Vec::with_capacity(4096);
+
+        loop {
+            let mut version = Version::HTTP_11;
+            let req = match tokio::time::timeout(
+                REQUEST_HEADER_TIMEOUT,
+                HttpProxyClientRequest::parse_basic(
+                    &mut reader,
+                    self.config.max_header_size,
+                    &mut version,
+                ),
+            )
+            .await
+            {
+                Ok(Ok(req)) => req,
+                Ok(Err(HttpRequestParseError::ClientClosed)) => return Ok(()),
+                Ok(Err(e)) => return Err(anyhow!("invalid http request: {e}")),
+                Err(_) => return Ok(()),
+            };
+
+            let mut body_consumed = req.body_type().is_none();
+            let path = req.uri.path();
+            let (status, error) = if path == PING_PATH {
+                if req.method == Method::GET || req.method == Method::HEAD {
+                    (StatusCode::NO_CONTENT, None)
+                } else {
+                    (StatusCode::METHOD_NOT_ALLOWED, None)
+                }
+            } else if let Some(api) = WriteApi::from_path(path) {
+                if req.method != Method::POST {
+                    (StatusCode::METHOD_NOT_ALLOWED, None)
+                } else {
+                    match self.check_write_request(&req, api) {
+                        Ok(precision) => {
+                            body_consumed = self.read_body(&mut reader, &req, &mut body).await?;
+                            if body_consumed {
+                                self.write_lines(&body, precision, client_addr, worker_id)
+                            } else {
+                                (
+                                    StatusCode::PAYLOAD_TOO_LARGE,
+                                    Some(format!(
+                                        "body size exceeds the limit {}",
+                                        self.config.max_body_size
+                                    )),
+                                )
+                            }
+                        }
+                        Err(rsp) => rsp,
+                    }
+                }
+            } else {
+                (StatusCode::NOT_FOUND, None)
+            };
+
+            // close the connection if the request body is not totally consumed
+            let keep_alive = req.keep_alive() && body_consumed;
+            let allow = if path == PING_PATH {
+                "GET, HEAD"
+            } else {
+                "POST"
+            };
+            send_response(&mut w, status, error.as_deref(), allow, keep_alive).await?;
+
+            if !keep_alive {
+                w.shutdown().await?;
+                return Ok(());
+            }
+        }
+    }
+
+    /// Check the request headers and get the timestamp precision
+    fn check_write_request(
+        &self,
+        req: &HttpProxyClientRequest,
+        api: WriteApi,
+    ) -> Result<Option<TimestampPrecision>, (StatusCode, Option<String>)> {
+        if let Some(v) = req.end_to_end_headers.get(header::CONTENT_ENCODING)
+            && !v.to_str().eq_ignore_ascii_case("identity")
+        {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Some(format!("unsupported content encoding {}", v.to_str())),
+            ));
+        }
+        if let Some(HttpBodyType::ContentLength(size)) = req.body_type()
+            && size > self.config.max_body_size as u64
+        {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Some(format!(
+                    "body size exceeds the limit {}",
+                    self.config.max_body_size
+                )),
+            ));
+        }
+
+        let mut precision = api.default_precision();
+        let query_pairs = req.uri.query().unwrap_or_default().split('&');
+        for (k, v) in query_pairs.filter_map(|kv| kv.split_once('=')) {
+            if k != "precision" {
+                continue;
+            }
+            if v.eq_ignore_ascii_case("auto") {
+                precision = None;
+            } else {
+                let p = TimestampPrecision::from_str(v)
+                    .map_err(|e| (StatusCode::BAD_REQUEST, Some(e.to_string())))?;
+                precision = Some(p);
+            }
+        }
+        Ok(precision)
+    }
+
+    /// Read the whole request body, `false` will be returned if the size exceeds the limit
+    async fn read_body<R>(
+        &self,
+        reader: &mut R,
+        req: &HttpProxyClientRequest,
+        body: &mut Vec<u8>,
+    ) -> anyhow::Result<bool>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        body.clear();
+        let Some(body_type) = req.body_type() else {
+            return Ok(true);
+        };
+
+        let max_body_size = self.config.max_body_size;
+        let mut body_reader = HttpBodyDecodeReader::new(reader, body_type, 1024);
+        (&mut body_reader)
+            .take(max_body_size as u64 + 1)
+            .read_to_end(body)
+            .await
+            .map_err(|e| anyhow!("failed to read request body: {e}"))?;
+        if body.len() > max_body_size {
+            return Ok(false);
+        }
+        body_reader
+            .trailer(self.config.max_header_size)
+            .await
+            .map_err(|e| anyhow!("failed to read request trailer: {e}"))?;
+        Ok(true)
+    }
+
+    fn write_lines(
+        &self,
+        body: &[u8],
+        precision: Option<TimestampPrecision>,
+        client_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) -> (StatusCode, Option<String>) {
+        let collector = self.collector.load();
+        let mut first_error = None;
+        for (i, line) in super::visit_lines(body).enumerate() {
+            if let Err(e) = super::add_line(&collector, line, precision, worker_id) {
+                debug!("invalid InfluxDB line from {client_addr}: {e}");
+                if first_error.is_none() {
+                    first_error = Some(format!("unable to parse line {}: {e}", i + 1));
+                }
+            }
+        }
+        match first_error {
+            Some(e) => (StatusCode::BAD_REQUEST, Some(e)),
+            None => (StatusCode::NO_CONTENT, None),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum WriteApi {
+    V1,
+    V2,
+    V3,
+}
+
+impl WriteApi {
+    fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "/write" => Some(WriteApi::V1),
+            "/api/v2/write" => Some(WriteApi::V2),
+            "/api/v3/write_lp" => Some(WriteApi::V3),
+            _ => None,
+        }
+    }
+
+    fn default_precision(self) -> Option<TimestampPrecision> {
+        match self {
+            WriteApi::V1 | WriteApi::V2 => Some(TimestampPrecision::NanoSeconds),
+            WriteApi::V3 => None,
+        }
+    }
+}
+
+async fn send_response<W>(
+    writer: &mut W,
+    status: StatusCode,
+    error: Option<&str>,
+    allow: &str,
+    keep_alive: bool,
+) -> anyhow::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut body = Vec::new();
+    if let Some(e) = error {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "error".to_string(),
+            serde_json::Value::String(e.to_string()),
+        );
+        serde_json::to_writer(&mut body, &map)?;
+    }
+
+    let mut header = Vec::with_capacity(256);
+    header.extend_from_slice(b"HTTP/1.1 ");
+    header.extend_from_slice(status.as_str().as_bytes());
+    header.push(b' ');
+    header.extend_from_slice(status.canonical_reason().unwrap_or_default().as_bytes());
+    header.extend_from_slice(b"\r\n");
+    if status == StatusCode::METHOD_NOT_ALLOWED {
+        header.extend_from_slice(b"Allow: ");
+        header.extend_from_slice(allow.as_bytes());
+        header.extend_from_slice(b"\r\n");
+    }
+    if status != StatusCode::NO_CONTENT {
+        if !body.is_empty() {
+            header.extend_from_slice(b"Content-Type: application/json\r\n");
+        }
+        header.extend_from_slice(b"Content-Length: ");
+        header.extend_from_slice(itoa::Buffer::new().format(body.len()).as_bytes());
+        header.extend_from_slice(b"\r\n");
+    }
+    if !keep_alive {
+        header.extend_from_slice(b"Connection: close\r\n");
+    }
+    header.extend_from_slice(b"\r\n");
+
+    writer.write_all(&header).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+impl ImporterInternal for InfluxdbHttpImporter {
+    fn _clone_config(&self) -> AnyImporterConfig {
+        AnyImporterConfig::InfluxdbHttp(self.config.clone())
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_collector_in_place(&self) {
+        let collector = crate::collect::get_or_insert_default(self.config.collector());
+        self.collector.store(Arc::new(collector));
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, importer: ArcImporter) -> anyhow::Result<()> {
+        let runtime = ListenTcpRuntime::new(WrapArcImporter(importer), self.listen_stats.clone());
+        runtime.run_all_instances(
+            &self.config.listen,
+            self.config.listen_in_worker,
+            &self.reload_sender,
+        )
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+    }
+}
+
+impl BaseServer for InfluxdbHttpImporter {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.importer_type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+impl ReceiveUdpServer for InfluxdbHttpImporter {
+    fn receive_udp_packet(
+        &self,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
+}
+
+#[cfg(unix)]
+impl ReceiveUnixDatagramServer for InfluxdbHttpImporter {
+    fn receive_unix_packet(&self, _packet: &[u8], _peer_addr: UnixSocketAddr) {}
+}
+
+#[async_trait]
+impl AcceptTcpServer for InfluxdbHttpImporter {
+    async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        if let Err(e) = self
+            .serve_connection(stream, client_addr, cc_info.worker_id())
+            .await
+        {
+            debug!("InfluxDB HTTP connection from {client_addr} closed: {e}");
+        }
+    }
+}
+
+impl Importer for InfluxdbHttpImporter {
+    fn collector(&self) -> &NodeName {
+        self.config.collector()
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use chrono::Utc;
+
+use crate::collect::ArcCollector;
+use crate::config::exporter::influxdb::TimestampPrecision;
+
+mod parser;
+use parser::{InfluxdbLine, InfluxdbParseError};
+
+mod udp;
+pub(super) use udp::InfluxdbUdpImporter;
+
+mod tcp;
+pub(super) use tcp::InfluxdbTcpImporter;
+
+mod http;
+pub(super) use http::InfluxdbHttpImporter;
+
+/// Iterate over all lines, with empty lines and comment lines skipped
+fn visit_lines(buf: &[u8]) -> impl Iterator<Item = &[u8]> {
+    buf.split(|c| *c == b'\n')
+        .map(|line| line.trim_ascii())
+        .filter(|line| !line.is_empty() && !line.starts_with(b"#"))
+}
+
+fn add_line(
+    collector: &ArcCollector,
+    line: &[u8],
+    precision: Option<TimestampPrecision>,
+    worker_id: Option<usize>,
+) -> Result<(), InfluxdbParseError> {
+    let line = InfluxdbLine::parse(line, precision)?;
+    let time = line.time.unwrap_or_else(Utc::now);
+    for record in line.records {
+        collector.add_metric(time, record, worker_id);
+    }
+    Ok(())
+}
@@ -0,0 +1,281 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue, NodeName};
+
+use crate::config::exporter::influxdb::TimestampPrecision;
+use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+
+// https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/
+
+/// The field with this key will use the measurement as the metric name
+const DEFAULT_FIELD_KEY: &str = "value";
+
+#[derive(Debug, Error)]
+pub(crate) enum InfluxdbParseError {
+    #[error("no measurement")]
+    NoMeasurement,
+    #[error("invalid measurement: {0}")]
+    InvalidMeasurement(anyhow::Error),
+    #[error("invalid tag: {0}")]
+    InvalidTag(anyhow::Error),
+    #[error("no field set")]
+    NoField,
+    #[error("invalid field: {0}")]
+    InvalidField(anyhow::Error),
+    #[error("invalid timestamp")]
+    InvalidTimestamp,
+}
+
+/// The metric records parsed from a single line
+pub(crate) struct InfluxdbLine {
+    pub(crate) time: Option<DateTime<Utc>>,
+    pub(crate) records: Vec<MetricRecord>,
+}
+
+/// Split at the first unescaped delimiter outside of double quotes
+fn split_once(buf: &[u8], delimiter: u8, quote: bool) -> (&[u8], Option<&[u8]>) {
+    let mut escaped = false;
+    let mut in_quotes = false;
+    for (i, c) in buf.iter().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match *c {
+            b'\\' => escaped = true,
+            b'"' if quote => in_quotes = !in_quotes,
+            _ if *c == delimiter && !in_quotes => return (&buf[..i], Some(&buf[i + 1..])),
+            _ => {}
+        }
+    }
+    (buf, None)
+}
+
+fn split_all(mut buf: &[u8], delimiter: u8, quote: bool) -> impl Iterator<Item = &[u8]> {
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let (part, left) = split_once(buf, delimiter, quote);
+        match left {
+            Some(left) => buf = left,
+            None => done = true,
+        }
+        Some(part)
+    })
+}
+
+fn unescape(buf: &[u8]) -> anyhow::Result<String> {
+    let mut s = Vec::with_capacity(buf.len());
+    let mut iter = buf.iter().peekable();
+    while let Some(c) = iter.next() {
+        if *c == b'\\'
+            && let Some(next) = iter.next_if(|n| matches!(n, b',' | b'=' | b' ' | b'\\' | b'"'))
+        {
+            s.push(*next);
+        } else {
+            s.push(*c);
+        }
+    }
+    String::from_utf8(s).map_err(|e| anyhow!("invalid utf-8 string: {e}"))
+}
+
+fn parse_field_value(buf: &[u8]) -> anyhow::Result<Option<MetricValue>> {
+    let s = std::str::from_utf8(buf).map_err(|e| anyhow!("invalid utf-8 string: {e}"))?;
+    match s {
+        "" => Err(anyhow!("empty value")),
+        "t" | "T" | "true" | "True" | "TRUE" => Ok(Some(MetricValue::Unsigned(1))),
+        "f" | "F" | "false" | "False" | "FALSE" => Ok(Some(MetricValue::Unsigned(0))),
+        _ if s.starts_with('"') => Ok(None), // string values are not supported
+        _ => {
+            if let Some(i) = s.strip_suffix('i') {
+                let i = i64::from_str(i).map_err(|e| anyhow!("invalid integer value: {e}"))?;
+                Ok(Some(MetricValue::Signed(i)))
+            } else if let Some(u) = s.strip_suffix('u') {
+                let u = u64::from_str(u).map_err(|e| anyhow!("invalid uinteger value: {e}"))?;
+                Ok(Some(MetricValue::Unsigned(u)))
+            } else {
+                let f = f64::from_str(s).map_err(|e| anyhow!("invalid float value: {e}"))?;
+                Ok(Some(MetricValue::Double(f)))
+            }
+        }
+    }
+}
+
+/// Guess the timestamp precision by the value if it's not specified
+fn parse_timestamp(ts: i64, precision: Option<TimestampPrecision>) -> Option<DateTime<Utc>> {
+    let precision = precision.unwrap_or(match ts.unsigned_abs() {
+        0..10_000_000_000 => TimestampPrecision::Seconds,
+        10_000_000_000..10_000_000_000_000 => TimestampPrecision::MilliSeconds,
+        10_000_000_000_000..10_000_000_000_000_000 => TimestampPrecision::MicroSeconds,
+        _ => TimestampPrecision::NanoSeconds,
+    });
+    precision.to_datetime(ts)
+}
+
+impl InfluxdbLine {
+    pub(crate) fn parse(
+        line: &[u8],
+        precision: Option<TimestampPrecision>,
+    ) -> Result<Self, InfluxdbParseError> {
+        let (key, left) = split_once(line, b' ', false);
+        let Some(left) = left else {
+            return Err(InfluxdbParseError::NoField);
+        };
+        let left = left.trim_ascii_start();
+        let (fields, timestamp) = split_once(left, b' ', true);
+        if fields.is_empty() {
+            return Err(InfluxdbParseError::NoField);
+        }
+
+        let mut key_parts = split_all(key, b',', false);
+        let measurement = key_parts.next().unwrap_or_default();
+        if measurement.is_empty() {
+            return Err(InfluxdbParseError::NoMeasurement);
+        }
+        let measurement = unescape(measurement).map_err(InfluxdbParseError::InvalidMeasurement)?;
+        let name = MetricName::parse(&measurement).map_err(|e| {
+            InfluxdbParseError::InvalidMeasurement(anyhow!("invalid metric name: {e}"))
+        })?;
+
+        let mut tag_map = MetricTagMap::default();
+        for tag in key_parts {
+            let (k, v) = split_once(tag, b'=', false);
+            let Some(v) = v else {
+                return Err(InfluxdbParseError::InvalidTag(anyhow!("no tag value")));
+            };
+            let k = unescape(k).map_err(InfluxdbParseError::InvalidTag)?;
+            let v = unescape(v).map_err(InfluxdbParseError::InvalidTag)?;
+            let k = MetricTagName::from_str(&k).map_err(|e| {
+                InfluxdbParseError::InvalidTag(anyhow!("invalid tag name {k}: {e}"))
+            })?;
+            let v = MetricTagValue::from_str(&v).map_err(|e| {
+                InfluxdbParseError::InvalidTag(anyhow!("invalid tag value {v}: {e}"))
+            })?;
+            tag_map.insert(k, v);
+        }
+        let tag_map = Arc::new(tag_map);
+
+        let time = match timestamp.map(|s| s.trim_ascii()) {
+            Some(s) if !s.is_empty() => {
+                let ts = std::str::from_utf8(s)
+                    .ok()
+                    .and_then(|s| i64::from_str(s).ok())
+                    .ok_or(InfluxdbParseError::InvalidTimestamp)?;
+                let time =
+                    parse_timestamp(ts, precision).ok_or(InfluxdbParseError::InvalidTimestamp)?;
+                Some(time)
+            }
+            _ => None,
+        };
+
+        let name = Arc::new(name);
+        let mut records = Vec::new();
+        for field in split_all(fields, b',', true) {
+            let (k, v) = split_once(field, b'=', true);
+            let Some(v) = v else {
+                return Err(InfluxdbParseError::InvalidField(anyhow!("no field value")));
+            };
+            let k = unescape(k).map_err(InfluxdbParseError::InvalidField)?;
+            let Some(value) = parse_field_value(v).map_err(|e| {
+                InfluxdbParseError::InvalidField(anyhow!("invalid value for field {k}: {e}"))
+            })?
+            else {
+                continue;
+            };
+
+            let name = if k == DEFAULT_FIELD_KEY {
+                name.clone()
+            } else {
+                let node = NodeName::from_str(&k).map_err(|e| {
+                    InfluxdbParseError::InvalidField(anyhow!("invalid field key {k}: {e}"))
+                })?;
+                let mut name = name.as_ref().clone();
+                name.add_suffix(node);
+                Arc::new(name)
+            };
+            records.push(MetricRecord {
+                r#type: MetricType::Gauge,
+                name,
+                tag_map: tag_map.clone(),
+                value,
+                sample_rate: 1.0,
+            });
+        }
+        Ok(InfluxdbLine { time, records })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line() {
+        let line = br#"cpu,host=server01,region=us-west usage_idle=92.5,value=3i,up=t,state="ok, good" 1556813561098000000"#;
+        let r = InfluxdbLine::parse(line, None).unwrap();
+        assert_eq!(r.time.unwrap().timestamp(), 1556813561);
+        assert_eq!(r.records.len(), 3);
+
+        let r0 = &r.records[0];
+        assert_eq!(r0.name.display('.').to_string(), "cpu.usage_idle");
+        assert_eq!(r0.value, MetricValue::Double(92.5));
+        let host = MetricTagName::from_str("host").unwrap();
+        assert_eq!(r0.tag_map.get(&host).unwrap().as_str(), "server01");
+
+        let r1 = &r.records[1];
+        assert_eq!(r1.name.display('.').to_string(), "cpu");
+        assert_eq!(r1.value, MetricValue::Signed(3));
+
+        assert_eq!(r.records[2].value, MetricValue::Unsigned(1));
+    }
+
+    #[test]
+    fn parse_precision() {
+        let r = InfluxdbLine::parse(b"mem value=1 1556813561", None).unwrap();
+        assert_eq!(r.time.unwrap().timestamp(), 1556813561);
+
+        let r = InfluxdbLine::parse(b"mem value=1 1556813561", Some(TimestampPrecision::Seconds))
+            .unwrap();
+        assert_eq!(r.time.unwrap().timestamp(), 1556813561);
+
+        let r = InfluxdbLine::parse(b"mem value=1u", None).unwrap();
+        assert!(r.time.is_none());
+        assert_eq!(r.records[0].value, MetricValue::Unsigned(1));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(matches!(
+            InfluxdbLine::parse(b"mem", None),
+            Err(InfluxdbParseError::NoField)
+        ));
+        assert!(matches!(
+            InfluxdbLine::parse(b",host=a value=1", None),
+            Err(InfluxdbParseError::NoMeasurement)
+        ));
+        assert!(matches!(
+            InfluxdbLine::parse(b"mem,host value=1", None),
+            Err(InfluxdbParseError::InvalidTag(_))
+        ));
+        assert!(matches!(
+            InfluxdbLine::parse(b"mem value=abc", None),
+            Err(InfluxdbParseError::InvalidField(_))
+        ));
+        assert!(matches!(
+            InfluxdbLine::parse(b"mem value=1 abc", None),
+            Err(InfluxdbParseError::InvalidTimestamp)
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use log::debug;
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::unix::SocketAddr as UnixSocketAddr;
+use tokio::sync::broadcast;
+
+#[cfg(unix)]
+use g3_daemon::listen::ReceiveUnixDatagramServer;
+use g3_daemon::listen::{AcceptTcpServer, ReceiveUdpRuntime, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::metrics::NodeName;
+
+use crate::collect::ArcCollector;
+use crate::config::importer::influxdb::InfluxdbUdpImporterConfig;
+use crate::config::importer::{AnyImporterConfig, ImporterConfig};
+use crate::import::{
+    ArcImporter, ArcImporterInternal, Importer, ImporterInternal, ImporterRegistry, WrapArcImporter,
+};
+
+pub(crate) struct InfluxdbUdpImporter {
+    config: InfluxdbUdpImporterConfig,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+
+    collector: ArcSwap<ArcCollector>,
+    reload_version: usize,
+}
+
+impl InfluxdbUdpImporter {
+    fn new(config: InfluxdbUdpImporterConfig, reload_version: usize) -> Self {
+        let reload_sender = crate::import::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let collector = Arc::new(crate::collect::get_or_insert_default(config.collector()));
+
+        InfluxdbUdpImporter {
+            config,
+            ingress_net_filter,
+            reload_sender,
+            collector: ArcSwap::new(collector),
+            reload_version,
+        }
+    }
+
+    pub(crate) fn prepare_initial(
+        config: InfluxdbUdpImporterConfig,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let server = InfluxdbUdpImporter::new(config, 1);
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyImporterConfig) -> anyhow::Result<InfluxdbUdpImporter> {
+        if let AnyImporterConfig::InfluxdbUdp(config) = config {
+            Ok(InfluxdbUdpImporter::new(config, self.reload_version + 1))
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.importer_type(),
+                config.importer_type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    return true;
+                }
+            }
+        }
+
+        // TODO add cps limit
+
+        false
+    }
+}
+
+impl ImporterInternal for InfluxdbUdpImporter {
+    fn _clone_config(&self) -> AnyImporterConfig {
+        AnyImporterConfig::InfluxdbUdp(self.config.clone())
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_collector_in_place(&self) {
+        let collector = crate::collect::get_or_insert_default(self.config.collector());
+        self.collector.store(Arc::new(collector));
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyImporterConfig,
+        _registry: &mut ImporterRegistry,
+    ) -> anyhow::Result<ArcImporterInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, importer: ArcImporter) -> anyhow::Result<()> {
+        let runtime = ReceiveUdpRuntime::new(
+            WrapArcImporter(importer.clone()),
+            self.config.listen.clone(),
+        );
+        runtime.run_all_instances(self.config.listen_in_worker, &self.reload_sender)
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+    }
+}
+
+impl BaseServer for InfluxdbUdpImporter {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.importer_type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+impl ReceiveUdpServer for InfluxdbUdpImporter {
+    fn receive_udp_packet(
+        &self,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        let collector = self.collector.load();
+        for line in super::visit_lines(packet) {
+            if let Err(e) = super::add_line(&collector, line, self.config.precision, worker_id) {
+                debug!("invalid InfluxDB line from {client_addr}: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl ReceiveUnixDatagramServer for InfluxdbUdpImporter {
+    fn receive_unix_packet(&self, _packet: &[u8], _peer_addr: UnixSocketAddr) {}
+}
+
+#[async_trait]
+impl AcceptTcpServer for InfluxdbUdpImporter {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
+impl Importer for InfluxdbUdpImporter {
+    fn collector(&self) -> &NodeName {
+        self.config.collector()
+    }
+}
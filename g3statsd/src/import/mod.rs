@@ -3,17 +3,22 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::io;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use async_trait::async_trait;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
 
-use g3_daemon::listen::ReceiveUdpServer;
 #[cfg(unix)]
 use g3_daemon::listen::ReceiveUnixDatagramServer;
-use g3_daemon::server::{BaseServer, ReloadServer, ServerReloadCommand};
+use g3_daemon::listen::{AcceptTcpServer, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ReloadServer, ServerReloadCommand};
+use g3_io_ext::LimitedBufReadExt;
 use g3_types::metrics::NodeName;
 
 use crate::config::importer::AnyImporterConfig;
@@ -27,16 +32,18 @@ pub(crate) use ops::{reload, update_dependency_to_collector};
 pub use ops::{spawn_all, stop_all};
 
 mod dummy;
+mod graphite;
+mod influxdb;
 mod statsd;
 
 #[cfg(unix)]
 pub(crate) trait Importer:
-    ReceiveUdpServer + ReceiveUnixDatagramServer + BaseServer
+    ReceiveUdpServer + ReceiveUnixDatagramServer + AcceptTcpServer + BaseServer
 {
     fn collector(&self) -> &NodeName;
 }
 #[cfg(not(unix))]
-pub(crate) trait Importer: ReceiveUdpServer + BaseServer {
+pub(crate) trait Importer: ReceiveUdpServer + AcceptTcpServer + BaseServer {
     fn collector(&self) -> &NodeName;
 }
 
@@ -107,6 +114,41 @@ impl ReceiveUnixDatagramServer for WrapArcImporter {
     }
 }
 
+#[async_trait]
+impl AcceptTcpServer for WrapArcImporter {
+    async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        self.0.run_tcp_task(stream, cc_info).await
+    }
+}
+
 fn new_reload_notify_channel() -> broadcast::Sender<ServerReloadCommand> {
     broadcast::Sender::new(16)
 }
+
+/// Receive all lines from the tcp stream, the connection will be closed if any line is too long
+async fn recv_tcp_lines<F>(
+    stream: TcpStream,
+    max_line_length: usize,
+    mut recv_line: F,
+) -> io::Result<()>
+where
+    F: FnMut(&[u8]),
+{
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::with_capacity(1024);
+    loop {
+        buf.clear();
+        let (found, nr) = reader
+            .limited_read_until(b'\n', max_line_length, &mut buf)
+            .await?;
+        if nr == 0 {
+            return Ok(());
+        }
+        if !found && nr >= max_line_length {
+            return Err(io::Error::other(format!(
+                "line length exceeds the limit {max_line_length}"
+            )));
+        }
+        recv_line(buf.trim_ascii());
+    }
+}
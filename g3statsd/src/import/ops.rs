@@ -164,6 +164,18 @@ fn spawn_new_unlocked(config: AnyImporterConfig) -> anyhow::Result<()> {
         AnyImporterConfig::StatsDUnix(config) => {
             super::statsd::StatsdUnixImporter::prepare_initial(config)?
         }
+        AnyImporterConfig::InfluxdbUdp(config) => {
+            super::influxdb::InfluxdbUdpImporter::prepare_initial(config)?
+        }
+        AnyImporterConfig::InfluxdbTcp(config) => {
+            super::influxdb::InfluxdbTcpImporter::prepare_initial(config)?
+        }
+        AnyImporterConfig::InfluxdbHttp(config) => {
+            super::influxdb::InfluxdbHttpImporter::prepare_initial(config)?
+        }
+        AnyImporterConfig::GraphiteTcp(config) => {
+            super::graphite::GraphiteTcpImporter::prepare_initial(config)?
+        }
     };
     registry::add(importer)
 }
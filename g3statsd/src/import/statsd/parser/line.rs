@@ -3,10 +3,12 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::hash::BuildHasher;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use foldhash::fast::FixedState;
 
 use g3_types::metrics::MetricTagMap;
 
@@ -34,13 +36,14 @@ impl<'a> LineParser<'a> {
         let metric_type = parse_type(part2)?;
 
         let mut tag_map = MetricTagMap::default();
+        let mut sample_rate = 1.0;
         while let Some(part) = self.next_part() {
             if part.is_empty() {
                 continue;
             }
 
             match part[0] {
-                b'@' => {} // sample rate
+                b'@' => sample_rate = parse_sample_rate(&part[1..]),
                 b'#' => {
                     tag_map
                         .parse_statsd(&part[1..])
@@ -52,7 +55,7 @@ impl<'a> LineParser<'a> {
             }
         }
 
-        LineValueIter::new(part1, metric_type, tag_map)
+        LineValueIter::new(part1, metric_type, tag_map, sample_rate)
     }
 
     fn next_part(&mut self) -> Option<&'a [u8]> {
@@ -77,6 +80,7 @@ pub(super) struct LineValueIter<'a> {
     r#type: MetricType,
     name: Arc<MetricName>,
     tag_map: Arc<MetricTagMap>,
+    sample_rate: f64,
     value_buf: &'a [u8],
     offset: usize,
 }
@@ -86,6 +90,7 @@ impl<'a> LineValueIter<'a> {
         part: &'a [u8],
         r#type: MetricType,
         tag_map: MetricTagMap,
+        sample_rate: f64,
     ) -> Result<LineValueIter<'a>, StatsdParseError> {
         let Some(p) = memchr::memchr(b':', part) else {
             return Err(StatsdParseError::NoValue);
@@ -108,6 +113,7 @@ impl<'a> LineValueIter<'a> {
             r#type,
             name: Arc::new(name),
             tag_map: Arc::new(tag_map),
+            sample_rate,
             value_buf: &part[p + 1..],
             offset: 0,
         })
@@ -141,6 +147,18 @@ impl Iterator for LineValueIter<'_> {
                 continue;
             }
 
+            if self.r#type == MetricType::Set {
+                // only the cardinality is needed, so store the hash value of the member
+                let hash = FixedState::with_seed(0).hash_one(value);
+                return Some(Ok(MetricRecord {
+                    r#type: self.r#type,
+                    name: self.name.clone(),
+                    tag_map: self.tag_map.clone(),
+                    value: MetricValue::Unsigned(hash),
+                    sample_rate: self.sample_rate,
+                }));
+            }
+
             return match std::str::from_utf8(value) {
                 Ok(s) => match MetricValue::from_str(s) {
                     Ok(value) => Some(Ok(MetricRecord {
@@ -148,6 +166,7 @@ impl Iterator for LineValueIter<'_> {
                         name: self.name.clone(),
                         tag_map: self.tag_map.clone(),
                         value,
+                        sample_rate: self.sample_rate,
                     })),
                     Err(e) => Some(Err(StatsdParseError::InvalidValue(e))),
                 },
@@ -157,12 +176,28 @@ impl Iterator for LineValueIter<'_> {
     }
 }
 
+/// Invalid sample rates are treated as 1.0, which means no sampling
+fn parse_sample_rate(part: &[u8]) -> f64 {
+    std::str::from_utf8(part)
+        .ok()
+        .and_then(|s| f64::from_str(s).ok())
+        .filter(|r| *r > 0.0 && *r <= 1.0)
+        .unwrap_or(1.0)
+}
+
 fn parse_type(part: &[u8]) -> Result<MetricType, StatsdParseError> {
     match part.len() {
         0 => Err(StatsdParseError::NoType),
         1 => match part[0] {
             b'c' => Ok(MetricType::Counter),
             b'g' => Ok(MetricType::Gauge),
+            b'h' => Ok(MetricType::Histogram),
+            b's' => Ok(MetricType::Set),
+            b'd' => Ok(MetricType::Distribution),
+            _ => Err(StatsdParseError::UnsupportedType),
+        },
+        2 => match part {
+            b"ms" => Ok(MetricType::Timer),
             _ => Err(StatsdParseError::UnsupportedType),
         },
         _ => Err(StatsdParseError::UnsupportedType),
@@ -207,6 +242,40 @@ mod tests {
         assert_eq!(r.r#type, MetricType::Gauge);
         assert_eq!(r.value, MetricValue::Signed(-10));
         assert!(r.name.display('.').to_string().as_bytes().eq(b"gaugor"));
+
+        let timer = b"glork:320|ms|@0.1";
+        let parser = LineParser::new(timer);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Timer);
+        assert_eq!(r.value, MetricValue::Unsigned(320));
+        assert_eq!(r.sample_rate, 0.1);
+
+        let timer = b"glork:320|ms|@0";
+        let parser = LineParser::new(timer);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.sample_rate, 1.0);
+
+        let histogram = b"glork:0.5|h";
+        let parser = LineParser::new(histogram);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Histogram);
+        assert_eq!(r.value, MetricValue::Double(0.5));
+
+        let set = b"uniques:alice:bob:alice|s";
+        let parser = LineParser::new(set);
+        let iter = parser.parse().unwrap();
+        let values = iter.map(|r| r.unwrap()).collect::<Vec<_>>();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].r#type, MetricType::Set);
+        assert_eq!(values[0].value, values[2].value);
+        assert_ne!(values[0].value, values[1].value);
+
+        let timer = b"glork:320|mss";
+        let parser = LineParser::new(timer);
+        assert!(parser.parse().is_err());
     }
 
     #[test]
@@ -273,4 +342,14 @@ mod tests {
         assert_eq!(r3.r#type, MetricType::Counter);
         assert_eq!(r3.value, MetricValue::Unsigned(3));
     }
+
+    #[test]
+    fn dog_statsd_distribution() {
+        let distribution = b"request.latency:12.5|d|#host:a";
+        let parser = LineParser::new(distribution);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Distribution);
+        assert_eq!(r.value, MetricValue::Double(12.5));
+    }
 }
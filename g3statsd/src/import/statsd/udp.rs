@@ -8,16 +8,18 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
+use async_trait::async_trait;
 use chrono::Utc;
 use log::debug;
+use tokio::net::TcpStream;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
 
 #[cfg(unix)]
 use g3_daemon::listen::ReceiveUnixDatagramServer;
-use g3_daemon::listen::{ReceiveUdpRuntime, ReceiveUdpServer};
-use g3_daemon::server::{BaseServer, ServerReloadCommand};
+use g3_daemon::listen::{AcceptTcpServer, ReceiveUdpRuntime, ReceiveUdpServer};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
 
@@ -188,6 +190,11 @@ impl ReceiveUnixDatagramServer for StatsdUdpImporter {
     fn receive_unix_packet(&self, _packet: &[u8], _peer_addr: UnixSocketAddr) {}
 }
 
+#[async_trait]
+impl AcceptTcpServer for StatsdUdpImporter {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
 impl Importer for StatsdUdpImporter {
     fn collector(&self) -> &NodeName {
         self.config.collector()
@@ -7,13 +7,17 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
+use async_trait::async_trait;
 use chrono::Utc;
 use log::debug;
+use tokio::net::TcpStream;
 use tokio::net::unix::SocketAddr;
 use tokio::sync::broadcast;
 
-use g3_daemon::listen::{ReceiveUdpServer, ReceiveUnixDatagramRuntime, ReceiveUnixDatagramServer};
-use g3_daemon::server::{BaseServer, ServerReloadCommand};
+use g3_daemon::listen::{
+    AcceptTcpServer, ReceiveUdpServer, ReceiveUnixDatagramRuntime, ReceiveUnixDatagramServer,
+};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_types::metrics::NodeName;
 
 use super::StatsdRecordVisitor;
@@ -156,6 +160,11 @@ impl ReceiveUnixDatagramServer for StatsdUnixImporter {
     }
 }
 
+#[async_trait]
+impl AcceptTcpServer for StatsdUnixImporter {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
 impl Importer for StatsdUnixImporter {
     fn collector(&self) -> &NodeName {
         self.config.collector()
@@ -3,6 +3,7 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -10,9 +11,10 @@ use ahash::AHashMap;
 use chrono::{DateTime, Utc};
 use tokio::sync::mpsc;
 
+use g3_histogram::Quantile;
 use g3_types::metrics::MetricTagMap;
 
-use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+use crate::types::{MetricName, MetricRecord, MetricSet, MetricSummary, MetricType, MetricValue};
 
 struct InnerMap<T> {
     inner: AHashMap<Arc<MetricTagMap>, T>,
@@ -48,6 +50,9 @@ pub(crate) struct AggregateExportRuntime<T: AggregateExport> {
 
     counter: AHashMap<Arc<MetricName>, InnerMap<CounterStoreValue>>,
     gauge: AHashMap<Arc<MetricName>, InnerMap<GaugeStoreValue>>,
+    quantile_list: BTreeSet<Quantile>,
+    summary: AHashMap<Arc<MetricName>, InnerMap<MetricSummary>>,
+    set: AHashMap<Arc<MetricName>, InnerMap<MetricSet>>,
 }
 
 pub(crate) struct CounterStoreValue {
@@ -72,6 +77,9 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
             store_time: Utc::now(),
             counter: AHashMap::default(),
             gauge: AHashMap::default(),
+            quantile_list: crate::types::default_quantile_list(),
+            summary: AHashMap::default(),
+            set: AHashMap::default(),
         }
     }
 
@@ -90,11 +98,13 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
                 biased;
 
                 _ = emit_interval.tick() => {
+                    self.expand_samples();
                     self.retain();
                     self.emit();
                 }
                 n = self.receiver.recv_many(&mut buf, BATCH_SIZE) => {
                     if n == 0 {
+                        self.expand_samples();
                         self.emit();
                         break;
                     }
@@ -128,6 +138,40 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
         }
     }
 
+    /// Convert the samples collected in this interval to counter and gauge values
+    fn expand_samples(&mut self) {
+        let mut records = Vec::new();
+        for (name, inner) in self.summary.drain() {
+            for (tag_map, summary) in inner.inner {
+                summary.expand(&self.quantile_list, |node, r#type, value| {
+                    let mut name = name.as_ref().clone();
+                    name.add_suffix(node);
+                    records.push(MetricRecord {
+                        r#type,
+                        name: Arc::new(name),
+                        tag_map: tag_map.clone(),
+                        value,
+                        sample_rate: 1.0,
+                    });
+                });
+            }
+        }
+        for (name, inner) in self.set.drain() {
+            for (tag_map, set) in inner.inner {
+                records.push(MetricRecord {
+                    r#type: MetricType::Gauge,
+                    name: name.clone(),
+                    tag_map,
+                    value: MetricValue::Unsigned(set.cardinality()),
+                    sample_rate: 1.0,
+                });
+            }
+        }
+        for record in records {
+            self.add_record(record);
+        }
+    }
+
     fn add_record(&mut self, record: MetricRecord) {
         match record.r#type {
             MetricType::Counter => {
@@ -157,6 +201,24 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
                     },
                 );
             }
+            MetricType::Timer | MetricType::Histogram | MetricType::Distribution => {
+                self.summary
+                    .entry(record.name)
+                    .or_default()
+                    .inner
+                    .entry(record.tag_map)
+                    .or_default()
+                    .add(record.value, record.sample_rate);
+            }
+            MetricType::Set => {
+                self.set
+                    .entry(record.name)
+                    .or_default()
+                    .inner
+                    .entry(record.tag_map)
+                    .or_default()
+                    .add(record.value);
+            }
         }
     }
 }
@@ -18,6 +18,8 @@ use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
 
+use crate::runtime::export::ExportSpoolConfig;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct HttpExportConfig {
     pub(super) exporter: NodeName,
@@ -25,6 +27,10 @@ pub(crate) struct HttpExportConfig {
     port: u16,
     resolve_retry_wait: Duration,
     connect_retry_wait: Duration,
+    pub(super) spool: Option<ExportSpoolConfig>,
+    max_retry: usize,
+    retry_backoff: Duration,
+    max_retry_backoff: Duration,
     pub(super) rsp_head_max_size: usize,
     pub(super) body_line_max_len: usize,
 
@@ -44,6 +50,10 @@ impl HttpExportConfig {
             port,
             resolve_retry_wait: Duration::from_secs(30),
             connect_retry_wait: Duration::from_secs(10),
+            spool: None,
+            max_retry: 0,
+            retry_backoff: Duration::from_secs(1),
+            max_retry_backoff: Duration::from_secs(30),
             rsp_head_max_size: 8192,
             body_line_max_len: 512,
             peer_s: String::new(),
@@ -82,6 +92,27 @@ impl HttpExportConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "spool" => {
+                let spool = ExportSpoolConfig::parse_yaml(v)
+                    .context(format!("invalid export spool config value for key {k}"))?;
+                self.spool = Some(spool);
+                Ok(())
+            }
+            "max_retry" => {
+                self.max_retry = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "retry_backoff" => {
+                self.retry_backoff = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "max_retry_backoff" => {
+                self.max_retry_backoff = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
             "rsp_header_max_size" => {
                 self.rsp_head_max_size = g3_yaml::humanize::as_usize(v)
                     .context(format!("invalid humanize usize value for key {k}"))?;
@@ -96,6 +127,28 @@ impl HttpExportConfig {
         }
     }
 
+    pub(crate) fn set_default_max_retry(&mut self, max_retry: usize) {
+        self.max_retry = max_retry;
+    }
+
+    #[inline]
+    pub(super) fn connect_retry_wait(&self) -> Duration {
+        self.connect_retry_wait
+    }
+
+    /// Get the time to wait before the next retry, the backoff will be doubled for each retry.
+    /// `None` will be returned if no more retry is allowed
+    pub(super) fn retry_wait(&self, retry: usize) -> Option<Duration> {
+        if retry >= self.max_retry {
+            return None;
+        }
+        let wait = self
+            .retry_backoff
+            .saturating_mul(1u32 << retry.min(16))
+            .min(self.max_retry_backoff);
+        Some(wait)
+    }
+
     async fn select_peer(&mut self) -> Option<SocketAddr> {
         match tokio::net::lookup_host(&self.peer_s).await {
             Ok(peers) => {
@@ -4,6 +4,7 @@
  */
 
 use std::io::{self, IoSlice};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -18,6 +19,8 @@ use g3_http::HttpBodyDecodeReader;
 use g3_http::client::HttpForwardRemoteResponse;
 use g3_io_ext::{AsyncStream, LimitedWriteExt};
 
+use super::ExportSpool;
+
 mod config;
 pub(crate) use config::HttpExportConfig;
 
@@ -30,15 +33,23 @@ pub(crate) trait HttpExport {
     fn static_headers(&self) -> &HeaderMap;
     fn fill_body(&mut self, piece: &[Self::BodyPiece], body_buf: &mut Vec<u8>) -> usize;
     fn check_response(&self, rsp: HttpForwardRemoteResponse, body: &[u8]) -> anyhow::Result<()>;
+
+    /// Check if the request should be sent again for this error response
+    fn retryable_response(&self, rsp: &HttpForwardRemoteResponse) -> bool {
+        matches!(rsp.code, 429 | 502 | 503 | 504)
+    }
 }
 
 pub(crate) struct HttpExportRuntime<T: HttpExport> {
     config: HttpExportConfig,
     exporter: T,
     receiver: mpsc::UnboundedReceiver<T::BodyPiece>,
+    spool: Option<Arc<ExportSpool>>,
 
     recv_buf: Vec<T::BodyPiece>,
     recv_handled: usize,
+    send_start: usize,
+    retry_count: usize,
     header_buf: Vec<u8>,
     fixed_header_len: usize,
     req_body_buf: Vec<u8>,
@@ -60,12 +71,20 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             exporter.static_headers(),
         );
         let fixed_header_len = header_buf.len();
+        let spool = config.spool.as_ref().and_then(|spool_config| {
+            ExportSpool::get_or_open(&config.exporter, spool_config)
+                .inspect_err(|e| warn!("exporter {}: disk spool disabled: {e:?}", config.exporter))
+                .ok()
+        });
         HttpExportRuntime {
             config,
             exporter,
             receiver,
+            spool,
             recv_buf: Vec::with_capacity(BATCH_SIZE),
             recv_handled: 0,
+            send_start: 0,
+            retry_count: 0,
             header_buf,
             fixed_header_len,
             req_body_buf: Vec::with_capacity(2048),
@@ -88,9 +107,15 @@ impl<T: HttpExport> HttpExportRuntime<T> {
     }
 
     async fn drop_wait(&mut self, wait: Duration) {
+        self.spool_records().await;
         if tokio::time::timeout(wait, async {
-            while self.receiver.recv().await.is_some() {
-                // TODO add metrics
+            while self
+                .receiver
+                .recv_many(&mut self.recv_buf, BATCH_SIZE)
+                .await
+                > 0
+            {
+                self.spool_records().await;
             }
         })
         .await
@@ -100,6 +125,63 @@ impl<T: HttpExport> HttpExportRuntime<T> {
         }
     }
 
+    /// Save all the pending records to the disk spool, or drop them if no spool configured
+    async fn spool_records(&mut self) {
+        if let Some(spool) = &self.spool {
+            while self.recv_handled < self.recv_buf.len() {
+                self.req_body_buf.clear();
+                let records = &self.recv_buf[self.recv_handled..];
+                let handled = self.exporter.fill_body(records, &mut self.req_body_buf);
+                if handled == 0 {
+                    self.recv_handled += 1;
+                } else {
+                    self.recv_handled += handled;
+                    spool.push(&self.req_body_buf).await;
+                }
+            }
+        }
+        // TODO add drop metrics
+        self.recv_buf.clear();
+        self.recv_handled = 0;
+    }
+
+    /// Save the body of the last request to the disk spool if it won't be sent again
+    async fn spool_last_request(&self) {
+        if let Some(spool) = &self.spool {
+            spool.push(&self.req_body_buf).await;
+        }
+    }
+
+    /// Send all the spooled requests before sending new records
+    async fn replay_spool<R, W>(&mut self, reader: &mut R, writer: &mut W) -> anyhow::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let Some(spool) = self.spool.clone() else {
+            return Ok(());
+        };
+        while let Some(id) = spool.peek(&mut self.req_body_buf).await {
+            self.write_request(writer)
+                .await
+                .map_err(|e| anyhow!("failed to send request: {e}"))?;
+            let rsp = self.recv_response(reader).await?;
+            self.close_connection = !rsp.keep_alive();
+            let retryable = self.exporter.retryable_response(&rsp);
+            if let Err(e) = self.exporter.check_response(rsp, &self.rsp_body_buf) {
+                if retryable {
+                    return Err(e.context("retryable error response"));
+                }
+                warn!("exporter {}: error response: {e:?}", self.config.exporter);
+            }
+            spool.pop(id).await;
+            if self.close_connection {
+                break;
+            }
+        }
+        Ok(())
+    }
+
     async fn run_with_stream<S>(&mut self, stream: S)
     where
         S: AsyncStream + Unpin,
@@ -111,6 +193,19 @@ impl<T: HttpExport> HttpExportRuntime<T> {
 
         let mut read_buf = [0u8; BATCH_SIZE];
 
+        self.close_connection = false;
+        if let Err(e) = self.replay_spool(&mut buf_reader, &mut writer).await {
+            warn!(
+                "exporter {}: failed to send spooled data: {e:?}",
+                self.config.exporter
+            );
+            self.drop_wait(self.config.connect_retry_wait()).await;
+            return;
+        }
+        if self.close_connection {
+            return;
+        }
+
         loop {
             if self.recv_handled < self.recv_buf.len() {
                 if let Err(e) = self.send_records(&mut buf_reader, &mut writer).await {
@@ -118,6 +213,9 @@ impl<T: HttpExport> HttpExportRuntime<T> {
                         "exporter {}: failed to send records: {e:?}",
                         self.config.exporter
                     );
+                    if !self.retry_wait().await {
+                        self.spool_last_request().await;
+                    }
                     break;
                 }
                 if self.close_connection {
@@ -163,19 +261,44 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             .map_err(|e| anyhow!("failed to send request: {e}"))?;
         let rsp = self.recv_response(reader).await?;
         self.close_connection = !rsp.keep_alive();
+        let retryable = self.exporter.retryable_response(&rsp);
         if let Err(e) = self.exporter.check_response(rsp, &self.rsp_body_buf) {
             warn!("exporter {}: error response: {e:?}", self.config.exporter);
+            if retryable {
+                if self.retry_wait().await {
+                    return Ok(());
+                }
+                self.spool_last_request().await;
+            }
         }
+        self.retry_count = 0;
         Ok(())
     }
 
+    /// Rewind to the pieces in the last request and wait for the backoff time,
+    /// return false if no more retry is allowed
+    async fn retry_wait(&mut self) -> bool {
+        let Some(wait) = self.config.retry_wait(self.retry_count) else {
+            self.retry_count = 0;
+            return false;
+        };
+        self.retry_count += 1;
+        self.recv_handled = self.send_start;
+        debug!(
+            "exporter {}: retry #{} after {wait:?}",
+            self.config.exporter, self.retry_count
+        );
+        tokio::time::sleep(wait).await;
+        true
+    }
+
     async fn send_request<W>(&mut self, writer: &mut W) -> io::Result<()>
     where
         W: AsyncWrite + Unpin,
     {
-        self.header_buf.truncate(self.fixed_header_len);
         self.req_body_buf.clear();
 
+        self.send_start = self.recv_handled;
         let records = &self.recv_buf[self.recv_handled..];
         let handled = self.exporter.fill_body(records, &mut self.req_body_buf);
         if handled == 0 {
@@ -189,6 +312,15 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             self.recv_handled += handled;
         }
 
+        self.write_request(writer).await
+    }
+
+    async fn write_request<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.header_buf.truncate(self.fixed_header_len);
+
         // set content-length
         self.header_buf.extend_from_slice(b"Content-Length: ");
         let mut usize_buf = Buffer::new();
@@ -13,3 +13,6 @@ pub(crate) use stream::{StreamExport, StreamExportConfig, StreamExportRuntime};
 
 mod http;
 pub(crate) use http::{HttpExport, HttpExportConfig, HttpExportRuntime};
+
+mod spool;
+pub(crate) use spool::{ExportSpool, ExportSpoolConfig, foreach_spool};
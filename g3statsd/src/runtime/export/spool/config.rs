@@ -0,0 +1,78 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::PathBuf;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ExportSpoolConfig {
+    pub(super) path: PathBuf,
+    pub(super) max_size: u64,
+    pub(super) segment_size: u64,
+}
+
+impl ExportSpoolConfig {
+    fn new(path: PathBuf) -> Self {
+        ExportSpoolConfig {
+            path,
+            max_size: 256 * 1024 * 1024,
+            segment_size: 4 * 1024 * 1024,
+        }
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        match v {
+            Yaml::String(_) => {
+                let path = g3_yaml::value::as_absolute_path(v)?;
+                Ok(ExportSpoolConfig::new(path))
+            }
+            Yaml::Hash(map) => {
+                let mut config = ExportSpoolConfig::new(PathBuf::new());
+                g3_yaml::foreach_kv(map, |k, v| config.set(k, v))?;
+                config.check()?;
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for export spool config should be 'string' or 'map'"
+            )),
+        }
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "path" | "dir" | "directory" => {
+                self.path = g3_yaml::value::as_absolute_path(v)
+                    .context(format!("invalid absolute path value for key {k}"))?;
+                Ok(())
+            }
+            "max_size" => {
+                self.max_size = g3_yaml::humanize::as_u64(v)
+                    .context(format!("invalid humanize u64 value for key {k}"))?;
+                Ok(())
+            }
+            "segment_size" => {
+                self.segment_size = g3_yaml::humanize::as_u64(v)
+                    .context(format!("invalid humanize u64 value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.path.as_os_str().is_empty() {
+            return Err(anyhow!("path is not set"));
+        }
+        if self.segment_size == 0 {
+            return Err(anyhow!("segment size should not be zero"));
+        }
+        if self.max_size < self.segment_size {
+            return Err(anyhow!("max size should not be less than segment size"));
+        }
+        Ok(())
+    }
+}
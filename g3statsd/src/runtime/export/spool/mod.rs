@@ -0,0 +1,132 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
+
+use anyhow::anyhow;
+use foldhash::fast::FixedState;
+
+use g3_types::metrics::NodeName;
+
+mod config;
+pub(crate) use config::ExportSpoolConfig;
+
+mod store;
+pub(crate) use store::SpoolEntryId;
+use store::SpoolStore;
+
+static EXPORT_SPOOL_REGISTRY: Mutex<HashMap<NodeName, Weak<ExportSpool>, FixedState>> =
+    Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
+
+/// The disk backed spool for an exporter, which will be shared by the old and new export runtimes on reload
+pub(crate) struct ExportSpool {
+    exporter: NodeName,
+    dir: PathBuf,
+    store: Arc<Mutex<SpoolStore>>,
+}
+
+pub(crate) struct ExportSpoolSnapshot {
+    pub(crate) size: u64,
+    pub(crate) entries: u64,
+    pub(crate) dropped: u64,
+}
+
+impl ExportSpool {
+    /// Get the existing spool of this exporter, or open a new one
+    pub(crate) fn get_or_open(
+        exporter: &NodeName,
+        config: &ExportSpoolConfig,
+    ) -> anyhow::Result<Arc<Self>> {
+        let dir = config.path.join(exporter.as_str());
+
+        let mut registry = EXPORT_SPOOL_REGISTRY.lock().unwrap();
+        if let Some(spool) = registry.get(exporter).and_then(|s| s.upgrade())
+            && spool.dir == dir
+        {
+            let mut store = spool.store.lock().unwrap();
+            store.max_size = config.max_size;
+            store.segment_size = config.segment_size;
+            drop(store);
+            return Ok(spool);
+        }
+
+        let store = SpoolStore::open(dir.clone(), config.max_size, config.segment_size)
+            .map_err(|e| anyhow!("failed to open spool dir {}: {e}", dir.display()))?;
+        let spool = Arc::new(ExportSpool {
+            exporter: exporter.clone(),
+            dir,
+            store: Arc::new(Mutex::new(store)),
+        });
+        registry.insert(exporter.clone(), Arc::downgrade(&spool));
+        Ok(spool)
+    }
+
+    #[inline]
+    pub(crate) fn exporter(&self) -> &NodeName {
+        &self.exporter
+    }
+
+    /// Run the blocking file operations of the store in the blocking threads
+    async fn run_blocking<T, F>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut SpoolStore) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || f(&mut store.lock().unwrap()))
+            .await
+            .ok()
+    }
+
+    pub(crate) async fn push(&self, data: &[u8]) {
+        let data = data.to_vec();
+        self.run_blocking(move |store| store.push(&data)).await;
+    }
+
+    /// Copy the oldest entry to the buffer
+    pub(crate) async fn peek(&self, buf: &mut Vec<u8>) -> Option<SpoolEntryId> {
+        let mut data = std::mem::take(buf);
+        let (data, id) = self
+            .run_blocking(move |store| {
+                let id = store.peek(&mut data);
+                (data, id)
+            })
+            .await?;
+        *buf = data;
+        id
+    }
+
+    /// Remove the oldest entry after it has been sent
+    pub(crate) async fn pop(&self, id: SpoolEntryId) {
+        self.run_blocking(move |store| store.pop(id)).await;
+    }
+
+    pub(crate) fn snapshot(&self) -> ExportSpoolSnapshot {
+        let store = self.store.lock().unwrap();
+        ExportSpoolSnapshot {
+            size: store.size,
+            entries: store.entries,
+            dropped: store.dropped,
+        }
+    }
+}
+
+/// Visit all the active spools
+pub(crate) fn foreach_spool<F>(mut f: F)
+where
+    F: FnMut(&ExportSpool),
+{
+    let mut registry = EXPORT_SPOOL_REGISTRY.lock().unwrap();
+    registry.retain(|_, spool| {
+        if let Some(spool) = spool.upgrade() {
+            f(&spool);
+            true
+        } else {
+            false
+        }
+    });
+}
@@ -0,0 +1,469 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+const SEGMENT_FILE_EXTENSION: &str = "spool";
+const ENTRY_HEADER_LEN: usize = 4;
+/// the sidecar file which records the sequence and offset of the next entry to read
+const READ_OFFSET_FILE_NAME: &str = "read.offset";
+const READ_OFFSET_LEN: usize = 16;
+
+/// The position of an entry in the spool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SpoolEntryId {
+    seq: u64,
+    offset: usize,
+}
+
+struct Segment {
+    seq: u64,
+    size: u64,
+    entries: u64,
+}
+
+struct SegmentReader {
+    seq: u64,
+    data: Vec<u8>,
+    offset: usize,
+}
+
+impl SegmentReader {
+    /// Get the entry at the current offset, `None` will be returned if reach the end or the data is truncated
+    fn current(&self) -> Option<&[u8]> {
+        let left = &self.data[self.offset..];
+        if left.len() < ENTRY_HEADER_LEN {
+            return None;
+        }
+        let len = u32::from_be_bytes([left[0], left[1], left[2], left[3]]) as usize;
+        left.get(ENTRY_HEADER_LEN..ENTRY_HEADER_LEN + len)
+    }
+}
+
+/// The on disk store of the spool, which is a series of segment files with length prefixed entries
+pub(super) struct SpoolStore {
+    dir: PathBuf,
+    pub(super) max_size: u64,
+    pub(super) segment_size: u64,
+    segments: VecDeque<Segment>,
+    next_seq: u64,
+    writer: Option<File>,
+    reader: Option<SegmentReader>,
+    /// the read offset of the front segment, which is loaded from the sidecar file
+    front_offset: usize,
+    offset_file: Option<File>,
+    pub(super) size: u64,
+    pub(super) entries: u64,
+    pub(super) dropped: u64,
+}
+
+/// Get the valid size and entry count of the segment data
+fn count_entries(data: &[u8]) -> (u64, u64) {
+    let mut offset = 0;
+    let mut entries = 0;
+    while offset + ENTRY_HEADER_LEN <= data.len() {
+        let len = u32::from_be_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ]) as usize;
+        if offset + ENTRY_HEADER_LEN + len > data.len() {
+            break;
+        }
+        offset += ENTRY_HEADER_LEN + len;
+        entries += 1;
+    }
+    (offset as u64, entries)
+}
+
+impl SpoolStore {
+    /// Open the store in the directory, and load all the existing segments
+    pub(super) fn open(dir: PathBuf, max_size: u64, segment_size: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+
+        let mut seqs = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some(SEGMENT_FILE_EXTENSION) {
+                continue;
+            }
+            if let Some(seq) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                seqs.push(seq);
+            }
+        }
+        seqs.sort_unstable();
+
+        let read_offset = load_read_offset(&dir);
+        let mut next_seq = seqs.last().map(|v| v + 1).unwrap_or_default();
+        if let Some((seq, _)) = read_offset {
+            // keep the sequence increasing, or new segments will be taken as sent ones
+            next_seq = next_seq.max(seq + 1);
+        }
+
+        let mut store = SpoolStore {
+            dir,
+            max_size,
+            segment_size,
+            segments: VecDeque::with_capacity(seqs.len()),
+            next_seq,
+            writer: None,
+            reader: None,
+            front_offset: 0,
+            offset_file: None,
+            size: 0,
+            entries: 0,
+            dropped: 0,
+        };
+        for seq in seqs {
+            let path = store.segment_path(seq);
+            let mut skip = 0;
+            if let Some((read_seq, read_offset)) = read_offset {
+                if seq < read_seq {
+                    // all entries in this segment have been sent
+                    let _ = fs::remove_file(&path);
+                    continue;
+                }
+                if seq == read_seq {
+                    skip = read_offset;
+                }
+            }
+            let data = fs::read(&path)?;
+            let (size, entries) = count_entries(data.get(skip..).unwrap_or_default());
+            if entries == 0 {
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+            if store.segments.is_empty() {
+                store.front_offset = skip;
+            }
+            store.segments.push_back(Segment { seq, size, entries });
+            store.size += size;
+            store.entries += entries;
+        }
+        Ok(store)
+    }
+
+    fn segment_path(&self, seq: u64) -> PathBuf {
+        segment_path(&self.dir, seq)
+    }
+
+    fn drop_front_segment(&mut self) {
+        let Some(segment) = self.segments.pop_front() else {
+            return;
+        };
+        if self.segments.is_empty() {
+            self.writer = None;
+        }
+        self.front_offset = 0;
+        if self
+            .reader
+            .as_ref()
+            .map(|r| r.seq == segment.seq)
+            .unwrap_or(false)
+        {
+            self.reader = None;
+        }
+        self.size -= segment.size;
+        self.entries -= segment.entries;
+        let _ = fs::remove_file(self.segment_path(segment.seq));
+    }
+
+    fn new_segment(&mut self) -> io::Result<()> {
+        let seq = self.next_seq;
+        let file = OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(self.segment_path(seq))?;
+        self.next_seq += 1;
+        self.segments.push_back(Segment {
+            seq,
+            size: 0,
+            entries: 0,
+        });
+        self.writer = Some(file);
+        Ok(())
+    }
+
+    pub(super) fn push(&mut self, data: &[u8]) {
+        let entry_len = (ENTRY_HEADER_LEN + data.len()) as u64;
+        if entry_len > self.max_size || data.len() > u32::MAX as usize {
+            self.dropped += 1;
+            return;
+        }
+
+        // drop the oldest segments to make room for the new entry
+        while self.size + entry_len > self.max_size && !self.segments.is_empty() {
+            let entries = self.segments.front().map(|s| s.entries).unwrap_or_default();
+            self.dropped += entries;
+            self.drop_front_segment();
+        }
+
+        let need_new_segment = match (&self.writer, self.segments.back()) {
+            (Some(_), Some(segment)) => {
+                segment.size > 0 && segment.size + entry_len > self.segment_size
+            }
+            _ => true,
+        };
+        if need_new_segment && let Err(e) = self.new_segment() {
+            warn!(
+                "failed to create new spool segment in {}: {e}",
+                self.dir.display()
+            );
+            self.dropped += 1;
+            return;
+        }
+
+        let mut buf = Vec::with_capacity(entry_len as usize);
+        buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        buf.extend_from_slice(data);
+        let Some(writer) = &mut self.writer else {
+            return;
+        };
+        if let Err(e) = writer.write_all(&buf) {
+            warn!(
+                "failed to write to spool segment in {}: {e}",
+                self.dir.display()
+            );
+            // switch to a new segment for the next entry
+            self.writer = None;
+            self.dropped += 1;
+            return;
+        }
+        if let Some(segment) = self.segments.back_mut() {
+            segment.size += entry_len;
+            segment.entries += 1;
+        }
+        self.size += entry_len;
+        self.entries += 1;
+    }
+
+    fn open_reader(&mut self) -> Option<&SegmentReader> {
+        while self.reader.is_none() {
+            let segment = self.segments.front()?;
+            let seq = segment.seq;
+            if self.segments.len() == 1 {
+                // stop writing to the segment that is going to be read
+                self.writer = None;
+            }
+            match fs::read(self.segment_path(seq)) {
+                Ok(data) => {
+                    let reader = SegmentReader {
+                        seq,
+                        data,
+                        offset: self.front_offset,
+                    };
+                    if reader.current().is_some() {
+                        self.reader = Some(reader);
+                    } else {
+                        self.drop_front_segment();
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "failed to read spool segment {seq} in {}: {e}",
+                        self.dir.display()
+                    );
+                    self.drop_front_segment();
+                }
+            }
+        }
+        self.reader.as_ref()
+    }
+
+    /// Copy the first entry to the buffer
+    pub(super) fn peek(&mut self, buf: &mut Vec<u8>) -> Option<SpoolEntryId> {
+        let reader = self.open_reader()?;
+        let data = reader.current()?;
+        buf.clear();
+        buf.extend_from_slice(data);
+        Some(SpoolEntryId {
+            seq: reader.seq,
+            offset: reader.offset,
+        })
+    }
+
+    /// Remove the entry if it's still the first one
+    pub(super) fn pop(&mut self, id: SpoolEntryId) {
+        let Some(reader) = &mut self.reader else {
+            return;
+        };
+        if reader.seq != id.seq || reader.offset != id.offset {
+            return;
+        }
+        let Some(data) = reader.current() else {
+            return;
+        };
+        let entry_len = ENTRY_HEADER_LEN + data.len();
+        reader.offset += entry_len;
+        let finished = reader.current().is_none();
+        let (seq, offset) = (reader.seq, reader.offset);
+        self.save_read_offset(seq, offset);
+
+        if let Some(segment) = self.segments.front_mut() {
+            segment.size = segment.size.saturating_sub(entry_len as u64);
+            segment.entries = segment.entries.saturating_sub(1);
+        }
+        self.size = self.size.saturating_sub(entry_len as u64);
+        self.entries = self.entries.saturating_sub(1);
+        if finished {
+            self.drop_front_segment();
+        }
+    }
+}
+
+impl SpoolStore {
+    fn save_read_offset(&mut self, seq: u64, offset: usize) {
+        if self.offset_file.is_none() {
+            match OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(self.dir.join(READ_OFFSET_FILE_NAME))
+            {
+                Ok(file) => self.offset_file = Some(file),
+                Err(e) => {
+                    warn!(
+                        "failed to open spool read offset file in {}: {e}",
+                        self.dir.display()
+                    );
+                    return;
+                }
+            }
+        }
+
+        let mut buf = [0u8; READ_OFFSET_LEN];
+        buf[..8].copy_from_slice(&seq.to_be_bytes());
+        buf[8..].copy_from_slice(&(offset as u64).to_be_bytes());
+        let Some(file) = &mut self.offset_file else {
+            return;
+        };
+        if let Err(e) = file
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| file.write_all(&buf))
+        {
+            warn!(
+                "failed to save spool read offset in {}: {e}",
+                self.dir.display()
+            );
+            self.offset_file = None;
+        }
+    }
+}
+
+/// Load the sequence and offset of the next entry to read
+fn load_read_offset(dir: &Path) -> Option<(u64, usize)> {
+    let data = fs::read(dir.join(READ_OFFSET_FILE_NAME)).ok()?;
+    if data.len() != READ_OFFSET_LEN {
+        return None;
+    }
+    let seq = u64::from_be_bytes(data[..8].try_into().ok()?);
+    let offset = u64::from_be_bytes(data[8..].try_into().ok()?);
+    Some((seq, offset as usize))
+}
+
+fn segment_path(dir: &Path, seq: u64) -> PathBuf {
+    dir.join(format!("{seq:020}.{SEGMENT_FILE_EXTENSION}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let dir = std::env::temp_dir().join(format!("g3statsd-spool-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut store = SpoolStore::open(dir.clone(), 48, 24).unwrap();
+        store.push(b"0123456789");
+        store.push(b"abcdefghij");
+        store.push(b"ABCDEFGHIJ");
+        assert_eq!(store.entries, 3);
+        assert_eq!(store.size, 42);
+        assert_eq!(store.segments.len(), 3);
+
+        let mut buf = Vec::new();
+        let id = store.peek(&mut buf).unwrap();
+        assert_eq!(buf, b"0123456789");
+        store.pop(id);
+        // pop again with the same id should do nothing
+        store.pop(id);
+        assert_eq!(store.entries, 2);
+
+        // the oldest segment should be dropped if full
+        store.push(b"klmnopqrst");
+        store.push(b"KLMNOPQRST");
+        assert_eq!(store.dropped, 1);
+        assert_eq!(store.entries, 3);
+
+        // reopen and load the left entries
+        drop(store);
+        let mut store = SpoolStore::open(dir.clone(), 48, 24).unwrap();
+        assert_eq!(store.entries, 3);
+        let id = store.peek(&mut buf).unwrap();
+        assert_eq!(buf, b"ABCDEFGHIJ");
+        store.pop(id);
+        store.push(b"uvwxyz");
+        let mut left = Vec::new();
+        while let Some(id) = store.peek(&mut buf) {
+            left.push(buf.clone());
+            store.pop(id);
+        }
+        assert_eq!(left, [&b"klmnopqrst"[..], b"KLMNOPQRST", b"uvwxyz"]);
+        assert_eq!(store.size, 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reopen_after_pop() {
+        let dir =
+            std::env::temp_dir().join(format!("g3statsd-spool-reopen-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut store = SpoolStore::open(dir.clone(), 1024, 1024).unwrap();
+        store.push(b"0123456789");
+        store.push(b"abcdefghij");
+        store.push(b"ABCDEFGHIJ");
+        let mut buf = Vec::new();
+        let id = store.peek(&mut buf).unwrap();
+        store.pop(id);
+
+        // the sent entry should not be loaded again
+        drop(store);
+        let mut store = SpoolStore::open(dir.clone(), 1024, 1024).unwrap();
+        assert_eq!(store.entries, 2);
+        assert_eq!(store.size, 28);
+        let id = store.peek(&mut buf).unwrap();
+        assert_eq!(buf, b"abcdefghij");
+        store.pop(id);
+        let id = store.peek(&mut buf).unwrap();
+        store.pop(id);
+        assert!(store.peek(&mut buf).is_none());
+
+        // new segments should be read after the sent ones
+        drop(store);
+        let mut store = SpoolStore::open(dir.clone(), 1024, 1024).unwrap();
+        assert_eq!(store.entries, 0);
+        store.push(b"klmnopqrst");
+        drop(store);
+        let mut store = SpoolStore::open(dir.clone(), 1024, 1024).unwrap();
+        assert_eq!(store.entries, 1);
+        store.peek(&mut buf).unwrap();
+        assert_eq!(buf, b"klmnopqrst");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
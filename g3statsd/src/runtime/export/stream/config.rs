@@ -16,6 +16,8 @@ use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
 
+use crate::runtime::export::ExportSpoolConfig;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct StreamExportConfig {
     pub(super) exporter: NodeName,
@@ -23,6 +25,7 @@ pub(crate) struct StreamExportConfig {
     port: u16,
     resolve_retry_wait: Duration,
     connect_retry_wait: Duration,
+    pub(super) spool: Option<ExportSpoolConfig>,
 
     peer_s: String,
     peer_addrs: Vec<SocketAddr>,
@@ -36,6 +39,7 @@ impl StreamExportConfig {
             port: default_port,
             resolve_retry_wait: Duration::from_secs(30),
             connect_retry_wait: Duration::from_secs(10),
+            spool: None,
             peer_s: String::new(),
             peer_addrs: Vec::new(),
         }
@@ -72,6 +76,12 @@ impl StreamExportConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "spool" => {
+                let spool = ExportSpoolConfig::parse_yaml(v)
+                    .context(format!("invalid export spool config value for key {k}"))?;
+                self.spool = Some(spool);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
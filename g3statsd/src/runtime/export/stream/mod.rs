@@ -4,6 +4,7 @@
  */
 
 use std::io;
+use std::sync::Arc;
 use std::time::Duration;
 
 use log::{debug, warn};
@@ -12,6 +13,8 @@ use tokio::sync::mpsc;
 
 use g3_io_ext::LimitedWriteExt;
 
+use super::ExportSpool;
+
 mod config;
 pub(crate) use config::StreamExportConfig;
 
@@ -27,6 +30,7 @@ pub(crate) struct StreamExportRuntime<T: StreamExport> {
     config: StreamExportConfig,
     formatter: T,
     receiver: mpsc::UnboundedReceiver<T::Piece>,
+    spool: Option<Arc<ExportSpool>>,
 
     recv_buf: Vec<T::Piece>,
     recv_handled: usize,
@@ -43,10 +47,16 @@ where
         formatter: T,
         receiver: mpsc::UnboundedReceiver<T::Piece>,
     ) -> Self {
+        let spool = config.spool.as_ref().and_then(|spool_config| {
+            ExportSpool::get_or_open(&config.exporter, spool_config)
+                .inspect_err(|e| warn!("exporter {}: disk spool disabled: {e:?}", config.exporter))
+                .ok()
+        });
         StreamExportRuntime {
             config,
             formatter,
             receiver,
+            spool,
             recv_buf: Vec::with_capacity(BATCH_SIZE),
             recv_handled: 0,
             write_buf: Vec::with_capacity(2048),
@@ -67,9 +77,15 @@ where
     }
 
     async fn drop_wait(&mut self, wait: Duration) {
+        self.spool_records().await;
         if tokio::time::timeout(wait, async {
-            while self.receiver.recv().await.is_some() {
-                // TODO add metrics
+            while self
+                .receiver
+                .recv_many(&mut self.recv_buf, BATCH_SIZE)
+                .await
+                > 0
+            {
+                self.spool_records().await;
             }
         })
         .await
@@ -79,12 +95,55 @@ where
         }
     }
 
+    /// Save all the pending records to the disk spool, or drop them if no spool configured
+    async fn spool_records(&mut self) {
+        if let Some(spool) = &self.spool {
+            while self.recv_handled < self.recv_buf.len() {
+                self.write_buf.clear();
+                let records = &self.recv_buf[self.recv_handled..];
+                let handled = self.formatter.serialize(records, &mut self.write_buf);
+                if handled == 0 {
+                    self.recv_handled += 1;
+                } else {
+                    self.recv_handled += handled;
+                    spool.push(&self.write_buf).await;
+                }
+            }
+        }
+        // TODO add drop metrics
+        self.recv_buf.clear();
+        self.recv_handled = 0;
+    }
+
+    /// Send all the spooled data before sending new records
+    async fn replay_spool<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let Some(spool) = &self.spool else {
+            return Ok(());
+        };
+        while let Some(id) = spool.peek(&mut self.write_buf).await {
+            writer.write_all_flush(&self.write_buf).await?;
+            spool.pop(id).await;
+        }
+        Ok(())
+    }
+
     async fn run_with_stream<S>(&mut self, mut stream: S)
     where
         S: AsyncRead + AsyncWrite + Unpin,
     {
         let mut read_buf = [0u8; BATCH_SIZE];
 
+        if let Err(e) = self.replay_spool(&mut stream).await {
+            warn!(
+                "exporter {}: failed to send spooled data: {e:?}",
+                self.config.exporter
+            );
+            return;
+        }
+
         loop {
             if self.recv_handled < self.recv_buf.len() {
                 if let Err(e) = self.send_records(&mut stream).await {
@@ -92,6 +151,9 @@ where
                         "exporter {}: failed to send records: {e:?}",
                         self.config.exporter
                     );
+                    if let Some(spool) = &self.spool {
+                        spool.push(&self.write_buf).await;
+                    }
                     break;
                 }
                 continue;
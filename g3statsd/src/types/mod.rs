@@ -13,10 +13,17 @@ pub(crate) use name::MetricName;
 mod value;
 pub(crate) use value::MetricValue;
 
+mod summary;
+pub(crate) use summary::{MetricSet, MetricSummary, default_quantile_list};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum MetricType {
     Counter,
     Gauge,
+    Timer,
+    Histogram,
+    Set,
+    Distribution,
 }
 
 #[derive(Clone)]
@@ -25,4 +32,6 @@ pub(crate) struct MetricRecord {
     pub(crate) name: Arc<MetricName>,
     pub(crate) tag_map: Arc<MetricTagMap>,
     pub(crate) value: MetricValue,
+    /// The client side sample rate, only used by timer, histogram and distribution values
+    pub(crate) sample_rate: f64,
 }
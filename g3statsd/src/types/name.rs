@@ -38,6 +38,10 @@ impl MetricName {
         self.nodes = new_nodes;
     }
 
+    pub(crate) fn add_suffix(&mut self, node: NodeName) {
+        self.nodes.push_back(node);
+    }
+
     pub(crate) fn display(&self, delimiter: char) -> MetricNameDisplay<'_> {
         MetricNameDisplay {
             nodes: &self.nodes,
@@ -106,4 +110,11 @@ mod tests {
         name.add_prefix(&prefix);
         assert_eq!(name.display('.').to_string().as_str(), "g3.bar.foo.counter");
     }
+
+    #[test]
+    fn add_suffix() {
+        let mut name = MetricName::parse("foo.timer").unwrap();
+        name.add_suffix(NodeName::from_str("p99").unwrap());
+        assert_eq!(name.display('.').to_string().as_str(), "foo.timer.p99");
+    }
 }
@@ -0,0 +1,272 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use ahash::AHashSet;
+use hdrhistogram::Histogram;
+
+use g3_histogram::Quantile;
+use g3_types::metrics::NodeName;
+
+use super::{MetricType, MetricValue};
+
+/// The histogram only accepts unsigned integers, so values are scaled to keep 3 decimal places
+const HISTOGRAM_VALUE_SCALE: f64 = 1000.0;
+const HISTOGRAM_SIGFIG: u8 = 3;
+
+pub(crate) fn default_quantile_list() -> BTreeSet<Quantile> {
+    BTreeSet::from([Quantile::PCT50, Quantile::PCT90, Quantile::PCT99])
+}
+
+/// The statistics of all the values of a timer, histogram or distribution series
+pub(crate) struct MetricSummary {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    histogram: Histogram<u64>,
+}
+
+impl Default for MetricSummary {
+    fn default() -> Self {
+        MetricSummary {
+            count: 0,
+            sum: 0.0,
+            min: 0.0,
+            max: 0.0,
+            histogram: Histogram::new(HISTOGRAM_SIGFIG).unwrap(),
+        }
+    }
+}
+
+impl MetricSummary {
+    /// Add a value sampled at `sample_rate`, which stands for `1 / sample_rate` values
+    pub(crate) fn add(&mut self, value: MetricValue, sample_rate: f64) {
+        let weight = sample_weight(sample_rate);
+        let v = value.as_f64();
+        if self.count == 0 {
+            self.min = v;
+            self.max = v;
+        } else {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+        }
+        self.count += weight;
+        self.sum += v * weight as f64;
+
+        // negative values are counted as 0 in the histogram
+        let scaled = (v * HISTOGRAM_VALUE_SCALE).round();
+        let _ = self.histogram.record_n(scaled.max(0.0) as u64, weight);
+    }
+
+    /// Drop the values recorded in the histogram, the count and sum are kept as is
+    pub(crate) fn reset_quantiles(&mut self) {
+        self.histogram.reset();
+    }
+
+    pub(crate) fn merge(&mut self, other: &MetricSummary) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        let _ = self.histogram.add(&other.histogram);
+    }
+
+    #[inline]
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+
+    #[inline]
+    pub(crate) fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub(crate) fn quantile(&self, quantile: &Quantile) -> f64 {
+        if self.histogram.is_empty() {
+            return f64::NAN;
+        }
+        let v = self.histogram.value_at_quantile(quantile.value());
+        let v = self.histogram.median_equivalent(v) as f64 / HISTOGRAM_VALUE_SCALE;
+        v.clamp(self.min, self.max)
+    }
+
+    /// Expand to plain counter and gauge values, with the name suffix for each of them
+    pub(crate) fn expand<F>(&self, quantile_list: &BTreeSet<Quantile>, mut f: F)
+    where
+        F: FnMut(NodeName, MetricType, MetricValue),
+    {
+        if self.count == 0 {
+            return;
+        }
+        f(
+            NodeName::new_static("count"),
+            MetricType::Counter,
+            MetricValue::Unsigned(self.count),
+        );
+        f(
+            NodeName::new_static("sum"),
+            MetricType::Counter,
+            MetricValue::Double(self.sum),
+        );
+        f(
+            NodeName::new_static("min"),
+            MetricType::Gauge,
+            MetricValue::Double(self.min),
+        );
+        f(
+            NodeName::new_static("max"),
+            MetricType::Gauge,
+            MetricValue::Double(self.max),
+        );
+        f(
+            NodeName::new_static("mean"),
+            MetricType::Gauge,
+            MetricValue::Double(self.sum / self.count as f64),
+        );
+        for quantile in quantile_list {
+            f(
+                quantile_node_name(quantile),
+                MetricType::Gauge,
+                MetricValue::Double(self.quantile(quantile)),
+            );
+        }
+    }
+}
+
+/// Get the number of values that a sampled value stands for, invalid sample rates are ignored
+fn sample_weight(sample_rate: f64) -> u64 {
+    if sample_rate > 0.0 && sample_rate < 1.0 {
+        (1.0 / sample_rate).round() as u64
+    } else {
+        1
+    }
+}
+
+/// Get the name suffix for the quantile, e.g. `p50` for 0.5 and `p99_9` for 0.999
+fn quantile_node_name(quantile: &Quantile) -> NodeName {
+    let pct = format!("{:.6}", quantile.value() * 100.0);
+    let pct = pct.trim_end_matches('0').trim_end_matches('.');
+    NodeName::from_str(&format!("p{}", pct.replace('.', "_"))).unwrap()
+}
+
+/// The unique members of a set series, the members are hashed by the parser
+#[derive(Default)]
+pub(crate) struct MetricSet {
+    members: AHashSet<u64>,
+}
+
+impl MetricSet {
+    pub(crate) fn member(value: MetricValue) -> u64 {
+        match value {
+            MetricValue::Unsigned(u) => u,
+            MetricValue::Signed(i) => i as u64,
+            MetricValue::Double(f) => f.to_bits(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, value: MetricValue) {
+        self.members.insert(MetricSet::member(value));
+    }
+
+    pub(crate) fn merge(&mut self, other: &MetricSet) {
+        self.members.extend(other.members.iter());
+    }
+
+    #[inline]
+    pub(crate) fn cardinality(&self) -> u64 {
+        self.members.len() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantile_name() {
+        assert_eq!(quantile_node_name(&Quantile::PCT50).as_str(), "p50");
+        let q = Quantile::from_str("0.999").unwrap();
+        assert_eq!(quantile_node_name(&q).as_str(), "p99_9");
+        let q = Quantile::from_str("1.0").unwrap();
+        assert_eq!(quantile_node_name(&q).as_str(), "p100");
+    }
+
+    #[test]
+    fn summary() {
+        let mut s1 = MetricSummary::default();
+        for i in 1..=100u64 {
+            s1.add(MetricValue::Unsigned(i), 1.0);
+        }
+        let mut s2 = MetricSummary::default();
+        s2.add(MetricValue::Double(0.5), 1.0);
+        s1.merge(&s2);
+
+        let mut values = Vec::new();
+        s1.expand(&default_quantile_list(), |node, r#type, v| {
+            values.push((node.to_string(), r#type, v))
+        });
+        assert_eq!(values.len(), 8);
+        assert_eq!(
+            values[0],
+            (
+                "count".to_string(),
+                MetricType::Counter,
+                MetricValue::Unsigned(101)
+            )
+        );
+        assert_eq!(
+            values[1],
+            (
+                "sum".to_string(),
+                MetricType::Counter,
+                MetricValue::Double(5050.5)
+            )
+        );
+        assert_eq!(values[2].2, MetricValue::Double(0.5));
+        assert_eq!(values[3].2, MetricValue::Double(100.0));
+        assert_eq!(values[5].0, "p50");
+        // the quantile value is the median equivalent value in the histogram
+        assert!((values[5].2.as_f64() - 50.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn sampled() {
+        let mut s = MetricSummary::default();
+        s.add(MetricValue::Unsigned(320), 0.1);
+        s.add(MetricValue::Unsigned(100), 0.0);
+        assert_eq!(s.count(), 11);
+        assert_eq!(s.sum(), 3300.0);
+        assert!((s.quantile(&Quantile::PCT50) - 320.0).abs() < 0.5);
+
+        s.reset_quantiles();
+        assert_eq!(s.count(), 11);
+        s.add(MetricValue::Unsigned(100), 1.0);
+        assert_eq!(s.count(), 12);
+        assert!((s.quantile(&Quantile::PCT99) - 100.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn set() {
+        let mut s1 = MetricSet::default();
+        s1.add(MetricValue::Unsigned(1));
+        s1.add(MetricValue::Unsigned(2));
+        s1.add(MetricValue::Unsigned(1));
+        let mut s2 = MetricSet::default();
+        s2.add(MetricValue::Unsigned(3));
+        s1.merge(&s2);
+        assert_eq!(s1.cardinality(), 3);
+    }
+}
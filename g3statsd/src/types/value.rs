@@ -23,7 +23,6 @@ impl MetricValue {
         DisplayInfluxdbValue(self)
     }
 
-    #[allow(unused)]
     pub(crate) fn as_f64(&self) -> f64 {
         match self {
             MetricValue::Double(f) => *f,
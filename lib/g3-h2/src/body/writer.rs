@@ -8,7 +8,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll, ready};
 
 use bytes::Bytes;
-use h2::SendStream;
+use h2::{Reason, SendStream};
 use tokio::io::AsyncWrite;
 
 pub struct H2StreamWriter {
@@ -21,6 +21,11 @@ impl H2StreamWriter {
             send_stream: stream,
         }
     }
+
+    /// reset the stream if the body can not be sent completely
+    pub fn send_reset(&mut self, reason: Reason) {
+        self.send_stream.send_reset(reason);
+    }
 }
 
 impl AsyncWrite for H2StreamWriter {
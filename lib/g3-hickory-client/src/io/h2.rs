@@ -17,6 +17,7 @@ use hickory_proto::{ProtoError, ProtoErrorKind};
 use http::{Response, Version};
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
+use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_socket::TcpConnectInfo;
 
@@ -30,15 +31,7 @@ pub async fn connect(
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<HttpsClientStream, ProtoError> {
-    let server_name = match &tls_name {
-        ServerName::DnsName(domain) => domain.as_ref().to_string(),
-        ServerName::IpAddress(ip) => IpAddr::from(*ip).to_string(),
-        _ => {
-            return Err(ProtoError::from(format!(
-                "unsupported tls name: {tls_name:?}",
-            )));
-        }
-    };
+    let server_name = h2_server_name(&tls_name)?;
 
     let tls_stream = tokio::time::timeout(
         connect_timeout,
@@ -47,6 +40,50 @@ pub async fn connect(
     .await
     .map_err(|_| ProtoError::from("tls connect timed out"))??;
 
+    h2_handshake(tls_stream, &server_name, request_timeout).await
+}
+
+/// Do TLS and h2 handshake over an already connected stream, which may be a tunnel to the target server
+pub async fn connect_with_stream<S>(
+    stream: S,
+    tls_config: ClientConfig,
+    tls_name: ServerName<'static>,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> Result<HttpsClientStream, ProtoError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let server_name = h2_server_name(&tls_name)?;
+
+    let tls_stream = tokio::time::timeout(
+        connect_timeout,
+        crate::connect::rustls::tls_handshake(stream, tls_config, tls_name, b"h2"),
+    )
+    .await
+    .map_err(|_| ProtoError::from("tls handshake timed out"))??;
+
+    h2_handshake(tls_stream, &server_name, request_timeout).await
+}
+
+fn h2_server_name(tls_name: &ServerName<'static>) -> Result<String, ProtoError> {
+    match tls_name {
+        ServerName::DnsName(domain) => Ok(domain.as_ref().to_string()),
+        ServerName::IpAddress(ip) => Ok(IpAddr::from(*ip).to_string()),
+        _ => Err(ProtoError::from(format!(
+            "unsupported tls name: {tls_name:?}",
+        ))),
+    }
+}
+
+async fn h2_handshake<S>(
+    tls_stream: S,
+    server_name: &str,
+    request_timeout: Duration,
+) -> Result<HttpsClientStream, ProtoError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
     let mut client_builder = h2::client::Builder::new();
     client_builder.enable_push(false);
 
@@ -59,7 +96,7 @@ pub async fn connect(
         let _ = connection.await;
     });
 
-    HttpsClientStream::new(&server_name, send_request, request_timeout)
+    HttpsClientStream::new(server_name, send_request, request_timeout)
 }
 
 /// A DNS client connection for DNS-over-HTTPS
@@ -3,12 +3,14 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::time::Duration;
 
 use hickory_proto::ProtoError;
 use hickory_proto::runtime::iocompat::AsyncIoTokioAsStd;
 use hickory_proto::tcp::{DnsTcpStream, TcpClientStream, TcpStream};
 use hickory_proto::xfer::StreamReceiver;
+use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_socket::TcpConnectInfo;
 
@@ -28,3 +30,17 @@ pub async fn connect(
     );
     Ok(TcpClientStream::from_stream(stream))
 }
+
+/// Use an already connected stream, which may be a tunnel to the target server
+pub fn connect_with_stream<S>(
+    stream: S,
+    server: SocketAddr,
+    outbound_messages: StreamReceiver,
+) -> TcpClientStream<impl DnsTcpStream>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+{
+    let stream =
+        TcpStream::from_stream_with_receiver(AsyncIoTokioAsStd(stream), server, outbound_messages);
+    TcpClientStream::from_stream(stream)
+}
@@ -3,6 +3,7 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::time::Duration;
 
 use hickory_proto::ProtoError;
@@ -11,6 +12,7 @@ use hickory_proto::tcp::{DnsTcpStream, TcpClientStream, TcpStream};
 use hickory_proto::xfer::StreamReceiver;
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
+use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_socket::TcpConnectInfo;
 
@@ -35,3 +37,30 @@ pub async fn connect(
     );
     Ok(TcpClientStream::from_stream(stream))
 }
+
+/// Do TLS handshake over an already connected stream, which may be a tunnel to the target server
+pub async fn connect_with_stream<S>(
+    stream: S,
+    server: SocketAddr,
+    tls_config: ClientConfig,
+    tls_name: ServerName<'static>,
+    outbound_messages: StreamReceiver,
+    connect_timeout: Duration,
+) -> Result<TcpClientStream<impl DnsTcpStream>, ProtoError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+{
+    let tls_stream = tokio::time::timeout(
+        connect_timeout,
+        crate::connect::rustls::tls_handshake(stream, tls_config, tls_name, b"dot"),
+    )
+    .await
+    .map_err(|_| ProtoError::from("tls handshake timed out"))??;
+
+    let stream = TcpStream::from_stream_with_receiver(
+        AsyncIoTokioAsStd(tls_stream),
+        server,
+        outbound_messages,
+    );
+    Ok(TcpClientStream::from_stream(stream))
+}
@@ -0,0 +1,196 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+//! HTTP Capsule Protocol, see RFC 9297 and RFC 9298
+
+use thiserror::Error;
+
+mod udp;
+pub use udp::{UdpDatagramCapsuleReader, UdpDatagramCapsuleWriter};
+
+pub const CAPSULE_TYPE_DATAGRAM: u64 = 0x00;
+
+/// the max length of the capsule type and capsule length fields
+pub const CAPSULE_HEADER_MAX_LEN: usize = 16;
+
+/// the context id for UDP payloads in connect-udp datagrams
+pub const CONNECT_UDP_CONTEXT_ID_PAYLOAD: u64 = 0;
+
+const VARINT_MAX: u64 = (1 << 62) - 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HttpCapsuleParseError {
+    #[error("need more data")]
+    NeedMoreData,
+    #[error("invalid datagram capsule")]
+    InvalidDatagram,
+}
+
+/// get the encoded length of a QUIC variable-length integer
+pub fn varint_len(v: u64) -> usize {
+    if v < (1 << 6) {
+        1
+    } else if v < (1 << 14) {
+        2
+    } else if v < (1 << 30) {
+        4
+    } else {
+        8
+    }
+}
+
+/// encode a QUIC variable-length integer, the buf should be large enough
+pub fn encode_varint(v: u64, buf: &mut [u8]) -> usize {
+    assert!(v <= VARINT_MAX);
+    match varint_len(v) {
+        1 => {
+            buf[0] = v as u8;
+            1
+        }
+        2 => {
+            buf[..2].copy_from_slice(&(v as u16 | 0x4000).to_be_bytes());
+            2
+        }
+        4 => {
+            buf[..4].copy_from_slice(&(v as u32 | 0x8000_0000).to_be_bytes());
+            4
+        }
+        _ => {
+            buf[..8].copy_from_slice(&(v | 0xC000_0000_0000_0000).to_be_bytes());
+            8
+        }
+    }
+}
+
+/// decode a QUIC variable-length integer, return the value and the encoded length
+pub fn decode_varint(buf: &[u8]) -> Result<(u64, usize), HttpCapsuleParseError> {
+    let Some(first) = buf.first() else {
+        return Err(HttpCapsuleParseError::NeedMoreData);
+    };
+    let len = 1usize << (first >> 6);
+    if buf.len() < len {
+        return Err(HttpCapsuleParseError::NeedMoreData);
+    }
+
+    let mut v = u64::from(first & 0x3F);
+    for b in &buf[1..len] {
+        v = (v << 8) | u64::from(*b);
+    }
+    Ok((v, len))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HttpCapsuleHeader {
+    pub capsule_type: u64,
+    pub length: u64,
+}
+
+impl HttpCapsuleHeader {
+    pub fn new_datagram(length: u64) -> Self {
+        HttpCapsuleHeader {
+            capsule_type: CAPSULE_TYPE_DATAGRAM,
+            length,
+        }
+    }
+
+    #[inline]
+    pub fn is_datagram(&self) -> bool {
+        self.capsule_type == CAPSULE_TYPE_DATAGRAM
+    }
+
+    /// parse the capsule header, return the header and the header length
+    pub fn parse(buf: &[u8]) -> Result<(Self, usize), HttpCapsuleParseError> {
+        let (capsule_type, type_len) = decode_varint(buf)?;
+        let (length, len_len) = decode_varint(&buf[type_len..])?;
+        Ok((
+            HttpCapsuleHeader {
+                capsule_type,
+                length,
+            },
+            type_len + len_len,
+        ))
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        varint_len(self.capsule_type) + varint_len(self.length)
+    }
+
+    /// encode the capsule header, the buf should be at least `CAPSULE_HEADER_MAX_LEN` long
+    pub fn encode(&self, buf: &mut [u8]) -> usize {
+        let type_len = encode_varint(self.capsule_type, buf);
+        let len_len = encode_varint(self.length, &mut buf[type_len..]);
+        type_len + len_len
+    }
+}
+
+/// encode the header of a DATAGRAM capsule which carries a UDP payload,
+/// including the context id, and return the encoded length
+pub fn encode_udp_payload_header(payload_len: usize, buf: &mut [u8]) -> usize {
+    let context_len = varint_len(CONNECT_UDP_CONTEXT_ID_PAYLOAD);
+    let hdr = HttpCapsuleHeader::new_datagram((context_len + payload_len) as u64);
+    let hdr_len = hdr.encode(buf);
+    hdr_len + encode_varint(CONNECT_UDP_CONTEXT_ID_PAYLOAD, &mut buf[hdr_len..])
+}
+
+/// parse the context id of the HTTP datagram, return the context id and the encoded length
+pub fn parse_datagram_context_id(buf: &[u8]) -> Result<(u64, usize), HttpCapsuleParseError> {
+    decode_varint(buf).map_err(|_| HttpCapsuleParseError::InvalidDatagram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint() {
+        // examples from RFC 9000 Appendix A.1
+        let cases: &[(&[u8], u64)] = &[
+            (
+                &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c],
+                151288809941952652,
+            ),
+            (&[0x9d, 0x7f, 0x3e, 0x7d], 494878333),
+            (&[0x7b, 0xbd], 15293),
+            (&[0x25], 37),
+        ];
+        for (data, value) in cases {
+            let (v, len) = decode_varint(data).unwrap();
+            assert_eq!(v, *value);
+            assert_eq!(len, data.len());
+
+            let mut buf = [0u8; 8];
+            let len = encode_varint(*value, &mut buf);
+            assert_eq!(&buf[..len], *data);
+        }
+
+        assert_eq!(
+            decode_varint(&[0x7b]),
+            Err(HttpCapsuleParseError::NeedMoreData)
+        );
+        assert_eq!(decode_varint(&[]), Err(HttpCapsuleParseError::NeedMoreData));
+    }
+
+    #[test]
+    fn udp_payload() {
+        let mut buf = [0u8; CAPSULE_HEADER_MAX_LEN];
+        let len = encode_udp_payload_header(100, &mut buf);
+        assert_eq!(&buf[..len], &[0x00, 0x40, 0x65, 0x00]);
+
+        let (hdr, hdr_len) = HttpCapsuleHeader::parse(&buf[..len]).unwrap();
+        assert!(hdr.is_datagram());
+        assert_eq!(hdr.length, 101);
+        assert_eq!(hdr_len, 3);
+        assert_eq!(hdr.encoded_len(), 3);
+
+        let (context_id, id_len) = parse_datagram_context_id(&buf[hdr_len..len]).unwrap();
+        assert_eq!(context_id, CONNECT_UDP_CONTEXT_ID_PAYLOAD);
+        assert_eq!(id_len, 1);
+
+        assert_eq!(
+            HttpCapsuleHeader::parse(&buf[..2]),
+            Err(HttpCapsuleParseError::NeedMoreData)
+        );
+    }
+}
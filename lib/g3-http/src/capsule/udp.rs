@@ -0,0 +1,227 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::{AsyncBufRead, AsyncWrite};
+
+use super::{CAPSULE_HEADER_MAX_LEN, CONNECT_UDP_CONTEXT_ID_PAYLOAD, HttpCapsuleHeader};
+
+enum RecvState {
+    Header,
+    Datagram(usize),
+    Skip(u64),
+}
+
+/// read UDP payloads from the DATAGRAM capsules in a connect-udp stream
+pub struct UdpDatagramCapsuleReader<R> {
+    reader: R,
+    max_datagram_size: usize,
+    state: RecvState,
+    hdr_buf: [u8; CAPSULE_HEADER_MAX_LEN],
+    hdr_len: usize,
+    datagram: Vec<u8>,
+}
+
+impl<R> UdpDatagramCapsuleReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub fn new(reader: R, max_payload_size: usize) -> Self {
+        // reserve space for the context id
+        let max_datagram_size = max_payload_size + 8;
+        UdpDatagramCapsuleReader {
+            reader,
+            max_datagram_size,
+            state: RecvState::Header,
+            hdr_buf: [0u8; CAPSULE_HEADER_MAX_LEN],
+            hdr_len: 0,
+            datagram: Vec::with_capacity(max_datagram_size),
+        }
+    }
+
+    /// read the next UDP payload into `buf` and return its length,
+    /// or None if the peer has closed the stream.
+    ///
+    /// Unknown capsules, datagrams with unknown context id and too large datagrams will be dropped.
+    pub fn poll_recv_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<Option<usize>>> {
+        loop {
+            if let RecvState::Datagram(size) = self.state
+                && self.datagram.len() >= size
+            {
+                self.state = RecvState::Header;
+                if let Some(nr) = self.take_payload(buf)? {
+                    return Poll::Ready(Ok(Some(nr)));
+                }
+                continue;
+            }
+            if let RecvState::Skip(0) = self.state {
+                self.state = RecvState::Header;
+            }
+
+            let data = ready!(Pin::new(&mut self.reader).poll_fill_buf(cx))?;
+            if data.is_empty() {
+                return if matches!(self.state, RecvState::Header) && self.hdr_len == 0 {
+                    Poll::Ready(Ok(None))
+                } else {
+                    Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "stream closed in the middle of a capsule",
+                    )))
+                };
+            }
+
+            let consumed = match self.state {
+                RecvState::Header => {
+                    let to_copy = data.len().min(CAPSULE_HEADER_MAX_LEN - self.hdr_len);
+                    let total = self.hdr_len + to_copy;
+                    self.hdr_buf[self.hdr_len..total].copy_from_slice(&data[..to_copy]);
+                    match HttpCapsuleHeader::parse(&self.hdr_buf[..total]) {
+                        Ok((hdr, hdr_len)) => {
+                            let consumed = hdr_len - self.hdr_len;
+                            self.hdr_len = 0;
+                            if hdr.is_datagram() && hdr.length <= self.max_datagram_size as u64 {
+                                self.datagram.clear();
+                                self.state = RecvState::Datagram(hdr.length as usize);
+                            } else {
+                                self.state = RecvState::Skip(hdr.length);
+                            }
+                            consumed
+                        }
+                        Err(_) => {
+                            self.hdr_len = total;
+                            to_copy
+                        }
+                    }
+                }
+                RecvState::Datagram(size) => {
+                    let to_copy = data.len().min(size - self.datagram.len());
+                    self.datagram.extend_from_slice(&data[..to_copy]);
+                    to_copy
+                }
+                RecvState::Skip(left) => {
+                    let to_skip = (data.len() as u64).min(left);
+                    self.state = RecvState::Skip(left - to_skip);
+                    to_skip as usize
+                }
+            };
+            Pin::new(&mut self.reader).consume(consumed);
+        }
+    }
+
+    fn take_payload(&mut self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        let (context_id, id_len) = super::parse_datagram_context_id(&self.datagram)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if context_id != CONNECT_UDP_CONTEXT_ID_PAYLOAD {
+            return Ok(None);
+        }
+
+        let payload = &self.datagram[id_len..];
+        if payload.len() > buf.len() {
+            return Ok(None);
+        }
+        let nr = payload.len();
+        buf[..nr].copy_from_slice(payload);
+        Ok(Some(nr))
+    }
+}
+
+/// write UDP payloads as DATAGRAM capsules into a connect-udp stream
+pub struct UdpDatagramCapsuleWriter<W> {
+    writer: W,
+    buf: Vec<u8>,
+    offset: usize,
+}
+
+impl<W> UdpDatagramCapsuleWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub fn new(writer: W, max_payload_size: usize) -> Self {
+        UdpDatagramCapsuleWriter {
+            writer,
+            buf: Vec::with_capacity(max_payload_size + CAPSULE_HEADER_MAX_LEN),
+            offset: 0,
+        }
+    }
+
+    /// write the payload as a whole capsule, the same payload should be used if called
+    /// again after `Poll::Pending` is returned
+    pub fn poll_write_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+        payload: &[u8],
+    ) -> Poll<io::Result<()>> {
+        if self.buf.is_empty() {
+            let mut hdr = [0u8; CAPSULE_HEADER_MAX_LEN];
+            let hdr_len = super::encode_udp_payload_header(payload.len(), &mut hdr);
+            self.buf.extend_from_slice(&hdr[..hdr_len]);
+            self.buf.extend_from_slice(payload);
+            self.offset = 0;
+        }
+
+        while self.offset < self.buf.len() {
+            let nw = ready!(Pin::new(&mut self.writer).poll_write(cx, &self.buf[self.offset..]))?;
+            if nw == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write zero byte into stream",
+                )));
+            }
+            self.offset += nw;
+        }
+        self.buf.clear();
+        self.offset = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    pub fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn read_write() {
+        let mut data = Vec::new();
+        let mut writer = UdpDatagramCapsuleWriter::new(&mut data, 1024);
+        poll_fn(|cx| writer.poll_write_payload(cx, b"hello"))
+            .await
+            .unwrap();
+        poll_fn(|cx| writer.poll_write_payload(cx, b"world"))
+            .await
+            .unwrap();
+        // an unknown capsule which should be skipped
+        data.extend_from_slice(&[0x21, 0x02, 0x01, 0x02]);
+
+        let mut reader = UdpDatagramCapsuleReader::new(BufReader::new(data.as_slice()), 1024);
+        let mut buf = [0u8; 16];
+        let nr = poll_fn(|cx| reader.poll_recv_payload(cx, &mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..nr], b"hello");
+        let nr = poll_fn(|cx| reader.poll_recv_payload(cx, &mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..nr], b"world");
+        let r = poll_fn(|cx| reader.poll_recv_payload(cx, &mut buf))
+            .await
+            .unwrap();
+        assert!(r.is_none());
+    }
+}
@@ -25,6 +25,14 @@ where
             let line = crate::header::proxy_authorization_basic(&a.username, &a.password);
             req.append_dyn_header(line);
         }
+        HttpAuth::Bearer(a) => {
+            let line = crate::header::proxy_authorization_bearer(&a.token);
+            req.append_dyn_header(line);
+        }
+        HttpAuth::Negotiate(a) => {
+            let line = crate::header::proxy_authorization_negotiate(a.encoded_value());
+            req.append_dyn_header(line);
+        }
     }
 
     req.send(buf_stream)
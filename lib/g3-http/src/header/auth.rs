@@ -18,10 +18,26 @@ pub fn proxy_authorization_basic(username: &Username, password: &Password) -> St
     )
 }
 
+pub fn proxy_authorization_bearer(token: &str) -> String {
+    format!("Proxy-Authorization: Bearer {token}\r\n")
+}
+
+pub fn proxy_authorization_negotiate(encoded_token: &str) -> String {
+    format!("Proxy-Authorization: Negotiate {encoded_token}\r\n")
+}
+
 pub fn proxy_authenticate_basic(realm: &str) -> String {
     format!("Proxy-Authenticate: Basic realm=\"{realm}\"\r\n")
 }
 
+pub fn proxy_authenticate_negotiate() -> &'static str {
+    "Proxy-Authenticate: Negotiate\r\n"
+}
+
+pub fn www_authenticate_negotiate() -> &'static str {
+    "WWW-Authenticate: Negotiate\r\n"
+}
+
 pub fn www_authenticate_basic(realm: &str) -> String {
     format!("WWW-Authenticate: Basic realm=\"{realm}\"\r\n")
 }
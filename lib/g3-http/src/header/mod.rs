@@ -4,7 +4,11 @@
  */
 
 mod auth;
-pub use auth::{proxy_authenticate_basic, proxy_authorization_basic, www_authenticate_basic};
+pub use auth::{
+    proxy_authenticate_basic, proxy_authenticate_negotiate, proxy_authorization_basic,
+    proxy_authorization_bearer, proxy_authorization_negotiate, www_authenticate_basic,
+    www_authenticate_negotiate,
+};
 
 mod connection;
 pub use connection::{Connection, connection_as_bytes};
@@ -14,6 +14,7 @@ pub use body::{
     HttpBodyType, StreamToChunkedTransfer, TrailerReadError, TrailerReader,
 };
 
+pub mod capsule;
 pub mod client;
 pub mod connect;
 pub mod header;
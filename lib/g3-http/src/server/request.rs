@@ -8,11 +8,13 @@ use std::io::Write;
 use std::str::FromStr;
 
 use bytes::BufMut;
-use http::{HeaderName, Method, Uri, Version, header};
+use http::{HeaderName, Method, Uri, Version, header, request};
 use tokio::io::AsyncBufRead;
 
 use g3_io_ext::LimitedBufReadExt;
-use g3_types::net::{Host, HttpAuth, HttpHeaderMap, HttpHeaderValue, UpstreamAddr};
+use g3_types::net::{
+    Host, HttpAuth, HttpHeaderMap, HttpHeaderValue, HttpUpgradeToken, UpstreamAddr,
+};
 
 use super::{HttpAdaptedRequest, HttpRequestParseError};
 use crate::header::Connection;
@@ -27,6 +29,8 @@ pub struct HttpProxyClientRequest {
     pub auth_info: HttpAuth,
     /// the port may be 0
     pub host: Option<UpstreamAddr>,
+    /// only connect-udp is supported for now
+    pub upgrade: Option<HttpUpgradeToken>,
     original_connection_name: Connection,
     extra_connection_headers: Vec<HeaderName>,
     origin_header_size: usize,
@@ -47,6 +51,7 @@ impl HttpProxyClientRequest {
             hop_by_hop_headers: HttpHeaderMap::default(),
             auth_info: HttpAuth::None,
             host: None,
+            upgrade: None,
             original_connection_name: Connection::default(),
             extra_connection_headers: Vec::new(),
             origin_header_size: 0,
@@ -71,6 +76,7 @@ impl HttpProxyClientRequest {
                     hop_by_hop_headers,
                     auth_info: HttpAuth::None,
                     host: None,
+                    upgrade: None,
                     original_connection_name: self.original_connection_name.clone(),
                     extra_connection_headers: self.extra_connection_headers.clone(),
                     origin_header_size: self.origin_header_size,
@@ -101,6 +107,7 @@ impl HttpProxyClientRequest {
                     hop_by_hop_headers,
                     auth_info: HttpAuth::None,
                     host: None,
+                    upgrade: None,
                     original_connection_name: self.original_connection_name.clone(),
                     extra_connection_headers: self.extra_connection_headers.clone(),
                     origin_header_size: self.origin_header_size,
@@ -125,6 +132,7 @@ impl HttpProxyClientRequest {
             hop_by_hop_headers,
             auth_info: HttpAuth::None,
             host: None,
+            upgrade: None,
             original_connection_name: self.original_connection_name.clone(),
             extra_connection_headers: self.extra_connection_headers.clone(),
             origin_header_size: self.origin_header_size,
@@ -287,6 +295,57 @@ impl HttpProxyClientRequest {
         Ok(req)
     }
 
+    /// Parse the request head of a h2 / h3 request stream.
+    ///
+    /// The version will be set to HTTP/1.1, as the request will be sent to upstream in HTTP/1.1.
+    /// If the stream has a body but no content-length header, chunked transfer-encoding
+    /// will be used.
+    pub fn parse_stream_head<F>(
+        parts: &request::Parts,
+        has_body: bool,
+        parse_more_header: F,
+    ) -> Result<Self, HttpRequestParseError>
+    where
+        F: Fn(&mut Self, HeaderName, &HttpHeaderLine) -> Result<(), HttpRequestParseError>,
+    {
+        let mut req =
+            HttpProxyClientRequest::new(parts.method.clone(), parts.uri.clone(), Version::HTTP_11);
+
+        let mut header_size: usize = 0;
+        for (name, value) in &parts.headers {
+            let value = value.to_str().map_err(|_| {
+                HttpRequestParseError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderValue)
+            })?;
+            header_size += name.as_str().len() + value.len() + 4;
+            let header = HttpHeaderLine {
+                name: name.as_str(),
+                value,
+            };
+            req.handle_header(header, &parse_more_header)?;
+        }
+        req.origin_header_size = header_size;
+        // connection specific headers are not allowed in h2 / h3
+        req.keep_alive = true;
+
+        if req.host.is_none()
+            && let Some(authority) = parts.uri.authority()
+        {
+            let host = UpstreamAddr::from_str(authority.as_str())
+                .map_err(|_| HttpRequestParseError::InvalidHost)?;
+            req.set_host(&host);
+        }
+
+        if has_body && !req.has_content_length && !req.chunked_transfer {
+            req.chunked_transfer = true;
+            req.has_transfer_encoding = true;
+            req.hop_by_hop_headers.insert(
+                header::TRANSFER_ENCODING,
+                HttpHeaderValue::from_static("chunked"),
+            );
+        }
+        Ok(req)
+    }
+
     /// do some necessary check and fix
     fn post_check_and_fix(&mut self) {
         // Don't move non-standard connection headers to hop-by-hop headers, as we don't support them
@@ -419,8 +478,18 @@ impl HttpProxyClientRequest {
                 return self.insert_hop_by_hop_header(name, &header);
             }
             "upgrade" => {
-                // TODO we have no support for it right now
-                return Err(HttpRequestParseError::UpgradeIsNotSupported);
+                // only connect-udp is supported for now
+                let found = header.value.split(',').any(|v| {
+                    matches!(
+                        HttpUpgradeToken::from_str(v.trim()),
+                        Ok(HttpUpgradeToken::ConnectUdp)
+                    )
+                });
+                if !found {
+                    return Err(HttpRequestParseError::UpgradeIsNotSupported);
+                }
+                self.upgrade = Some(HttpUpgradeToken::ConnectUdp);
+                return self.insert_hop_by_hop_header(name, &header);
             }
             "transfer-encoding" => {
                 // it's a hop-by-hop option, but we just pass it
@@ -589,4 +658,45 @@ mod tests {
                 .unwrap();
         assert!(!request.keep_alive());
     }
+
+    #[test]
+    fn stream_head() {
+        let (parts, _) = http::Request::builder()
+            .method(Method::POST)
+            .uri("https://example.net/upload")
+            .header("proxy-authorization", "Basic dDp0")
+            .header("content-type", "text/plain")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let request =
+            HttpProxyClientRequest::parse_stream_head(&parts, true, |req, name, header| {
+                if name.as_str() == "proxy-authorization" {
+                    return req.parse_header_authorization(header.value);
+                }
+                req.append_parsed_header(name, header)
+            })
+            .unwrap();
+        assert_eq!(request.version, Version::HTTP_11);
+        assert!(request.keep_alive());
+        assert!(request.has_auth_info());
+        assert_eq!(request.body_type(), Some(HttpBodyType::Chunked));
+        assert_eq!(request.host.as_ref().unwrap().host_str(), "example.net");
+        let buf = request.serialize_for_origin();
+        assert_eq!(
+            buf.as_slice(),
+            b"POST /upload HTTP/1.1\r\ncontent-type: text/plain\r\nhost: example.net\r\ntransfer-encoding: chunked\r\nConnection: Keep-Alive\r\n\r\n"
+        );
+
+        let (parts, _) = http::Request::builder()
+            .method(Method::PUT)
+            .uri("http://example.net/a")
+            .header("content-length", "3")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let request =
+            HttpProxyClientRequest::parse_stream_head(&parts, true, parse_more_header).unwrap();
+        assert_eq!(request.body_type(), Some(HttpBodyType::ContentLength(3)));
+    }
 }
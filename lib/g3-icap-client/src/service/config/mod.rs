@@ -142,6 +142,16 @@ impl IcapServiceConfig {
                     basic_auth.encoded_value()
                 );
             }
+            HttpAuth::Negotiate(negotiate_auth) => {
+                let _ = write!(
+                    header,
+                    "Authorization: Negotiate {}\r\n",
+                    negotiate_auth.encoded_value()
+                );
+            }
+            HttpAuth::Bearer(bearer_auth) => {
+                let _ = write!(header, "Authorization: Bearer {}\r\n", bearer_auth.token);
+            }
         }
     }
 }
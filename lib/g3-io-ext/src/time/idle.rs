@@ -42,6 +42,7 @@ impl IdleInterval {
 #[derive(Clone, Copy, Debug)]
 pub enum IdleForceQuitReason {
     UserBlocked,
+    TaskKilled,
     ServerQuit,
 }
 
@@ -4,7 +4,7 @@
  */
 
 use std::io;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
 #[cfg(unix)]
@@ -17,6 +17,8 @@ const CMSG_RECV_BUFFER_SIZE: usize = 10240; // see rfc3542 20.1
 pub trait RecvAncillaryData {
     fn set_recv_interface(&mut self, id: u32);
     fn set_recv_dst_addr(&mut self, addr: IpAddr);
+    /// the original destination address of a transparent proxied packet
+    fn set_orig_dst_addr(&mut self, addr: SocketAddr);
     fn set_timestamp(&mut self, ts: Duration);
 }
 
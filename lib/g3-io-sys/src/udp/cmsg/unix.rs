@@ -4,7 +4,7 @@
  */
 
 use std::io;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use super::{RecvAncillaryBuffer, RecvAncillaryData};
 
@@ -136,6 +136,25 @@ impl RecvAncillaryBuffer {
                         let ip4 = Ipv4Addr::from(u32::from_be(ipaddr.s_addr));
                         data.set_recv_dst_addr(IpAddr::V4(ip4));
                     }
+                    #[cfg(any(target_os = "linux", target_os = "android"))]
+                    libc::IP_ORIGDSTADDR => {
+                        if payload.len() < size_of::<libc::sockaddr_in>() {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "no enough msg data for struct sockaddr_in",
+                            ));
+                        }
+                        let addr = unsafe {
+                            payload
+                                .as_ptr()
+                                .cast::<libc::sockaddr_in>()
+                                .as_ref()
+                                .unwrap()
+                        };
+                        let ip4 = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+                        let port = u16::from_be(addr.sin_port);
+                        data.set_orig_dst_addr(SocketAddr::new(IpAddr::V4(ip4), port));
+                    }
                     _ => {}
                 },
                 libc::IPPROTO_IPV6 => match hdr.cmsg_type {
@@ -158,6 +177,25 @@ impl RecvAncillaryBuffer {
                         let ip6 = Ipv6Addr::from(pktinfo.ipi6_addr.s6_addr);
                         data.set_recv_dst_addr(IpAddr::V6(ip6));
                     }
+                    #[cfg(any(target_os = "linux", target_os = "android"))]
+                    libc::IPV6_ORIGDSTADDR => {
+                        if payload.len() < size_of::<libc::sockaddr_in6>() {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "no enough msg data for struct sockaddr_in6",
+                            ));
+                        }
+                        let addr = unsafe {
+                            payload
+                                .as_ptr()
+                                .cast::<libc::sockaddr_in6>()
+                                .as_ref()
+                                .unwrap()
+                        };
+                        let ip6 = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+                        let port = u16::from_be(addr.sin6_port);
+                        data.set_orig_dst_addr(SocketAddr::new(IpAddr::V6(ip6), port));
+                    }
                     _ => {}
                 },
                 _ => {}
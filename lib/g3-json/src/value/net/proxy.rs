@@ -42,6 +42,9 @@ mod tests {
             ("httpconnect", ProxyRequestType::HttpConnect),
             ("HTTPConnect", ProxyRequestType::HttpConnect),
             ("http_connect", ProxyRequestType::HttpConnect),
+            ("httpudpconnect", ProxyRequestType::HttpUdpConnect),
+            ("HTTPUdpConnect", ProxyRequestType::HttpUdpConnect),
+            ("http_udp_connect", ProxyRequestType::HttpUdpConnect),
             ("sockstcpconnect", ProxyRequestType::SocksTcpConnect),
             ("SocksTCPConnect", ProxyRequestType::SocksTcpConnect),
             ("socks_tcp_connect", ProxyRequestType::SocksTcpConnect),
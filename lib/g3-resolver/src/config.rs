@@ -5,7 +5,7 @@
 
 use std::time::Duration;
 
-use super::AnyResolveDriverConfig;
+use super::{AnyResolveDriverConfig, ResolverCacheSnapshotConfig};
 
 pub(crate) const RESOLVER_MINIMUM_CACHE_TTL: u32 = 30;
 #[cfg(any(feature = "c-ares", feature = "hickory"))]
@@ -22,6 +22,7 @@ pub struct ResolverRuntimeConfig {
     pub batch_request_count: usize,
     pub protective_query_timeout: Duration,
     pub graceful_stop_wait: Duration,
+    pub cache_snapshot: Option<ResolverCacheSnapshotConfig>,
 }
 
 impl Default for ResolverRuntimeConfig {
@@ -31,6 +32,7 @@ impl Default for ResolverRuntimeConfig {
             batch_request_count: RESOLVER_BATCH_REQUEST_COUNT,
             protective_query_timeout: RESOLVER_PROTECTIVE_QUERY_TIMEOUT,
             graceful_stop_wait: RESOLVER_GRACEFUL_STOP_WAIT,
+            cache_snapshot: None,
         }
     }
 }
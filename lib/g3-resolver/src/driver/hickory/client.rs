@@ -11,16 +11,24 @@ use std::time::Duration;
 use anyhow::anyhow;
 use arcstr::ArcStr;
 use async_recursion::async_recursion;
+use hickory_client::ClientError;
 use hickory_client::client::{Client, ClientHandle};
 use hickory_proto::BufDnsStreamHandle;
 use hickory_proto::rr::{DNSClass, Name, RData, RecordType};
+use hickory_proto::xfer::DnsResponse;
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
 use tokio::sync::mpsc;
 
 use g3_socket::{BindAddr, TcpConnectInfo, UdpConnectInfo};
 use g3_types::net::{DnsEncryptionConfig, DnsEncryptionProtocol, TcpMiscSockOpts, UdpMiscSockOpts};
+#[cfg(feature = "dnssec")]
+use hickory_proto::dnssec::{DnssecDnsHandle, TrustAnchors};
 
+#[cfg(feature = "dnssec")]
+use super::dnssec::{DnssecProofTracker, is_bogus_error};
+
+use super::ArcHickoryTunnelConnect;
 use crate::{ResolveDriverError, ResolveError, ResolvedRecord};
 
 #[derive(Clone)]
@@ -60,20 +68,39 @@ impl HickoryClientState {
     }
 }
 
+/// The handle to send queries, which is built once for each underlying client
+#[derive(Clone)]
+enum HickoryQueryHandle {
+    Plain(Client),
+    #[cfg(feature = "dnssec")]
+    Dnssec(DnssecDnsHandle<Client>),
+}
+
+impl HickoryQueryHandle {
+    async fn query(&mut self, name: Name, rtype: RecordType) -> Result<DnsResponse, ClientError> {
+        match self {
+            HickoryQueryHandle::Plain(client) => client.query(name, DNSClass::IN, rtype).await,
+            #[cfg(feature = "dnssec")]
+            HickoryQueryHandle::Dnssec(handle) => handle.query(name, DNSClass::IN, rtype).await,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct HickoryClient {
     config: Arc<HickoryClientConfig>,
     state: Arc<HickoryClientState>,
-    client: Client,
+    handle: HickoryQueryHandle,
 }
 
 impl HickoryClient {
     pub(super) async fn new(config: HickoryClientConfig) -> anyhow::Result<Self> {
         let client = config.build_async_client().await?;
+        let handle = config.query_handle(client);
         Ok(HickoryClient {
             config: Arc::new(config),
             state: Arc::new(HickoryClientState::default()),
-            client,
+            handle,
         })
     }
 
@@ -97,9 +124,9 @@ impl HickoryClient {
                         try_failed: self.config.each_tries,
                         try_truncated: self.config.retry_tcp(),
                     };
-                    let async_client = self.client.clone();
+                    let handle = self.handle.clone();
                     tokio::spawn(async move {
-                        let r = client_job.run(async_client, req).await;
+                        let r = client_job.run(handle, req).await;
                         let _ = rsp_sender.send(r).await;
                     });
                 }
@@ -109,14 +136,15 @@ impl HickoryClient {
                         let client_config = self.config.clone();
                         tokio::spawn(async move {
                             if let Ok(client) = client_config.build_async_client().await {
-                                let _ = client_sender.try_send(client);
+                                let handle = client_config.query_handle(client);
+                                let _ = client_sender.try_send(handle);
                             }
                         });
                     }
                 }
                 r = client_receiver.recv() => {
-                    if let Some(client) = r {
-                        self.client = client;
+                    if let Some(handle) = r {
+                        self.handle = handle;
                     }
                 }
             }
@@ -134,7 +162,7 @@ pub(super) struct HickoryClientJob {
 
 impl HickoryClientJob {
     #[async_recursion]
-    async fn run(mut self, mut async_client: Client, req: DnsRequest) -> ResolvedRecord {
+    async fn run(mut self, mut handle: HickoryQueryHandle, req: DnsRequest) -> ResolvedRecord {
         let Ok(mut name) = Name::from_ascii(&req.domain) else {
             return ResolvedRecord::failed(
                 req.domain,
@@ -145,11 +173,12 @@ impl HickoryClientJob {
         // always use FQDN format such like "www.example.com."
         name.set_fqdn(true);
 
+        #[cfg(feature = "dnssec")]
+        let mut dnssec_tracker = DnssecProofTracker::new(self.config.dnssec_trust_anchor.is_some());
+
         loop {
-            match async_client
-                .query(name.clone(), DNSClass::IN, req.rtype)
-                .await
-            {
+            let rsp = handle.query(name.clone(), req.rtype).await;
+            match rsp {
                 Ok(rsp) => {
                     let (mut msg, _) = rsp.into_parts();
 
@@ -161,7 +190,8 @@ impl HickoryClientJob {
                     if msg.truncated() && self.try_truncated {
                         self.try_truncated = false;
                         if let Ok(client) = self.config.new_dns_over_tcp_client().await {
-                            return self.run(client, req).await;
+                            let handle = self.config.query_handle(client);
+                            return self.run(handle, req).await;
                         }
                     }
 
@@ -173,16 +203,22 @@ impl HickoryClientJob {
                         match r.data() {
                             RData::A(v) => {
                                 if req.rtype == RecordType::A {
+                                    #[cfg(feature = "dnssec")]
+                                    dnssec_tracker.add_record(&r);
                                     ips.push(IpAddr::V4(v.0));
                                 }
                             }
                             RData::AAAA(v) => {
                                 if req.rtype == RecordType::AAAA {
+                                    #[cfg(feature = "dnssec")]
+                                    dnssec_tracker.add_record(&r);
                                     ips.push(IpAddr::V6(v.0))
                                 }
                             }
                             RData::CNAME(v) => {
                                 if name.eq(r.name()) {
+                                    #[cfg(feature = "dnssec")]
+                                    dnssec_tracker.add_record(&r);
                                     has_cname = true;
                                     name = v.0.clone();
                                 }
@@ -190,11 +226,21 @@ impl HickoryClientJob {
                             _ => {}
                         }
                     }
-                    return if ips.is_empty() {
+                    #[cfg(feature = "dnssec")]
+                    if dnssec_tracker.is_bogus() {
+                        return ResolvedRecord::failed(
+                            req.domain,
+                            self.config.negative_ttl,
+                            ResolveError::DnssecBogus,
+                        );
+                    }
+                    let record = if ips.is_empty() {
                         if has_cname {
                             self.try_truncated = true;
                             continue;
                         }
+                        #[cfg(feature = "dnssec")]
+                        dnssec_tracker.add_negative(msg.name_servers());
                         ResolvedRecord::empty(req.domain, self.config.negative_ttl)
                     } else {
                         ResolvedRecord::resolved(
@@ -205,14 +251,26 @@ impl HickoryClientJob {
                             ips,
                         )
                     };
+                    #[cfg(feature = "dnssec")]
+                    let record = dnssec_tracker.mark_record(record);
+                    return record;
                 }
                 Err(e) => {
+                    #[cfg(feature = "dnssec")]
+                    if is_bogus_error(&e) {
+                        return ResolvedRecord::failed(
+                            req.domain,
+                            self.config.negative_ttl,
+                            ResolveError::DnssecBogus,
+                        );
+                    }
                     self.state.add_failed();
                     self.try_failed -= 1;
                     if self.try_failed > 0
                         && let Ok(client) = self.config.build_async_client().await
                     {
-                        return self.run(client, req).await;
+                        let handle = self.config.query_handle(client);
+                        return self.run(handle, req).await;
                     }
                     return ResolvedRecord::failed(req.domain, self.config.negative_ttl, e.into());
                 }
@@ -234,14 +292,32 @@ pub(super) struct HickoryClientConfig {
     pub(super) negative_ttl: u32,
     pub(super) tcp_misc_opts: TcpMiscSockOpts,
     pub(super) udp_misc_opts: UdpMiscSockOpts,
+    pub(super) tunnel: Option<ArcHickoryTunnelConnect>,
+    #[cfg(feature = "dnssec")]
+    pub(super) dnssec_trust_anchor: Option<Arc<TrustAnchors>>,
 }
 
 impl HickoryClientConfig {
     fn retry_tcp(&self) -> bool {
-        self.encryption.is_none()
+        self.encryption.is_none() && self.tunnel.is_none()
+    }
+
+    fn query_handle(&self, client: Client) -> HickoryQueryHandle {
+        #[cfg(feature = "dnssec")]
+        if let Some(trust_anchor) = &self.dnssec_trust_anchor {
+            return HickoryQueryHandle::Dnssec(DnssecDnsHandle::with_trust_anchor(
+                client,
+                trust_anchor.clone(),
+            ));
+        }
+        HickoryQueryHandle::Plain(client)
     }
 
     async fn build_async_client(&self) -> anyhow::Result<Client> {
+        if let Some(tunnel) = &self.tunnel {
+            return self.new_tunnel_client(tunnel).await;
+        }
+
         if let Some(ec) = &self.encryption {
             let tls_client = ec.tls_client().driver.as_ref().clone();
 
@@ -287,6 +363,76 @@ impl HickoryClientConfig {
         }
     }
 
+    async fn new_tunnel_client(&self, tunnel: &ArcHickoryTunnelConnect) -> anyhow::Result<Client> {
+        let stream = tokio::time::timeout(self.connect_timeout, tunnel.connect(self.target))
+            .await
+            .map_err(|_| anyhow!("tunnel connect timed out"))?
+            .map_err(|e| anyhow!("tunnel connect failed: {e}"))?;
+
+        let Some(ec) = &self.encryption else {
+            let (message_sender, outbound_messages) = BufDnsStreamHandle::new(self.target);
+            let tcp_stream = g3_hickory_client::io::tcp::connect_with_stream(
+                stream,
+                self.target,
+                outbound_messages,
+            );
+            let (client, bg) = Client::with_timeout(
+                Box::pin(std::future::ready(Ok(tcp_stream))),
+                message_sender,
+                self.request_timeout,
+                None,
+            )
+            .await
+            .map_err(|e| anyhow!("failed to create tcp async client over tunnel: {e}"))?;
+            tokio::spawn(bg);
+            return Ok(client);
+        };
+
+        let tls_client = ec.tls_client().driver.as_ref().clone();
+        match ec.protocol() {
+            DnsEncryptionProtocol::Tls => {
+                let (message_sender, outbound_messages) = BufDnsStreamHandle::new(self.target);
+                let tls_connect = g3_hickory_client::io::tls::connect_with_stream(
+                    stream,
+                    self.target,
+                    tls_client,
+                    ec.tls_name().clone(),
+                    outbound_messages,
+                    self.connect_timeout,
+                );
+                let (client, bg) = Client::with_timeout(
+                    Box::pin(tls_connect),
+                    message_sender,
+                    self.request_timeout,
+                    None,
+                )
+                .await
+                .map_err(|e| anyhow!("failed to create tls async client over tunnel: {e}"))?;
+                tokio::spawn(bg);
+                Ok(client)
+            }
+            DnsEncryptionProtocol::Https => {
+                let client_connect = g3_hickory_client::io::h2::connect_with_stream(
+                    stream,
+                    tls_client,
+                    ec.tls_name().clone(),
+                    self.connect_timeout,
+                    self.request_timeout,
+                );
+                let (client, bg) = Client::connect(Box::pin(client_connect))
+                    .await
+                    .map_err(|e| anyhow!("failed to create h2 async client over tunnel: {e}"))?;
+                tokio::spawn(bg);
+                Ok(client)
+            }
+            #[cfg(feature = "quic")]
+            DnsEncryptionProtocol::Quic | DnsEncryptionProtocol::H3 => Err(anyhow!(
+                "dns encryption protocol {} can not be used through tunnel",
+                ec.protocol().as_str()
+            )),
+        }
+    }
+
     async fn new_dns_over_udp_client(&self) -> anyhow::Result<Client> {
         // random port is used here
         let client_connect =
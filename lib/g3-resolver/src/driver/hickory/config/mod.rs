@@ -4,16 +4,27 @@
  */
 
 use std::net::{IpAddr, SocketAddr};
+#[cfg(feature = "dnssec")]
+use std::path::PathBuf;
 use std::str::FromStr;
+#[cfg(feature = "dnssec")]
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
+#[cfg(feature = "dnssec")]
+use hickory_proto::dnssec::TrustAnchors;
+use log::warn;
 use yaml_rust::Yaml;
 
 use g3_socket::BindAddr;
-use g3_types::net::{DnsEncryptionConfigBuilder, TcpMiscSockOpts, UdpMiscSockOpts};
+use g3_types::net::{
+    DnsEncryptionConfigBuilder, DnsEncryptionProtocol, TcpMiscSockOpts, UdpMiscSockOpts,
+};
 
-use super::{HickoryClient, HickoryClientConfig, HickoryResolver};
+use super::{
+    ArcHickoryTunnelConnect, HickoryClient, HickoryClientConfig, HickoryResolver, HickoryTunnel,
+};
 use crate::driver::BoxResolverDriver;
 
 #[cfg(feature = "yaml")]
@@ -35,6 +46,11 @@ pub struct HickoryDriverConfig {
     encryption: Option<DnsEncryptionConfigBuilder>,
     tcp_misc_opts: TcpMiscSockOpts,
     udp_misc_opts: UdpMiscSockOpts,
+    tunnel: Option<HickoryTunnel>,
+    #[cfg(feature = "dnssec")]
+    dnssec_validation: bool,
+    #[cfg(feature = "dnssec")]
+    dnssec_trust_anchor: Option<PathBuf>,
 }
 
 impl Default for HickoryDriverConfig {
@@ -54,6 +70,11 @@ impl Default for HickoryDriverConfig {
             encryption: None,
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
+            tunnel: None,
+            #[cfg(feature = "dnssec")]
+            dnssec_validation: false,
+            #[cfg(feature = "dnssec")]
+            dnssec_trust_anchor: None,
         }
     }
 }
@@ -66,10 +87,28 @@ impl HickoryDriverConfig {
         if self.positive_max_ttl < self.positive_min_ttl {
             self.positive_max_ttl = self.positive_min_ttl;
         }
+        #[cfg(feature = "dnssec")]
+        if self.dnssec_validation {
+            self.load_dnssec_trust_anchor()?;
+        }
 
         Ok(())
     }
 
+    #[cfg(feature = "dnssec")]
+    fn load_dnssec_trust_anchor(&self) -> anyhow::Result<Arc<TrustAnchors>> {
+        let trust_anchor = match &self.dnssec_trust_anchor {
+            Some(path) => TrustAnchors::from_file(path).map_err(|e| {
+                anyhow!(
+                    "failed to load dnssec trust anchor file {}: {e}",
+                    path.display()
+                )
+            })?,
+            None => TrustAnchors::default(),
+        };
+        Ok(Arc::new(trust_anchor))
+    }
+
     fn parse_server_str(&mut self, addrs: &str) -> anyhow::Result<()> {
         let addrs = addrs.split_whitespace();
         for (i, addr) in addrs.enumerate() {
@@ -117,6 +156,25 @@ impl HickoryDriverConfig {
         self.bind_addr
     }
 
+    /// Connect to the dns servers through the tunnel, only TCP based protocols can be used
+    pub fn set_tunnel(&mut self, connector: ArcHickoryTunnelConnect) {
+        self.tunnel = Some(HickoryTunnel(connector));
+    }
+
+    pub fn check_tunnel_support(&self) -> anyhow::Result<()> {
+        let Some(ec) = &self.encryption else {
+            return Ok(());
+        };
+        match ec.protocol() {
+            DnsEncryptionProtocol::Tls | DnsEncryptionProtocol::Https => Ok(()),
+            #[cfg(feature = "quic")]
+            DnsEncryptionProtocol::Quic | DnsEncryptionProtocol::H3 => Err(anyhow!(
+                "dns encryption protocol {} can not be used through tunnel",
+                ec.protocol().as_str()
+            )),
+        }
+    }
+
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
         let mut driver =
             HickoryResolver::new(self.each_timeout, self.retry_interval, self.negative_ttl);
@@ -131,6 +189,15 @@ impl HickoryDriverConfig {
         } else {
             None
         };
+        if self.tunnel.is_some() {
+            self.check_tunnel_support()?;
+        }
+        #[cfg(feature = "dnssec")]
+        let dnssec_trust_anchor = if self.dnssec_validation {
+            Some(self.load_dnssec_trust_anchor()?)
+        } else {
+            None
+        };
 
         for ip in &self.servers {
             let client_config = HickoryClientConfig {
@@ -145,11 +212,30 @@ impl HickoryDriverConfig {
                 negative_ttl: self.negative_ttl,
                 tcp_misc_opts: self.tcp_misc_opts.clone(),
                 udp_misc_opts: self.udp_misc_opts,
+                tunnel: self.tunnel.as_ref().map(|t| t.0.clone()),
+                #[cfg(feature = "dnssec")]
+                dnssec_trust_anchor: dnssec_trust_anchor.clone(),
             };
             let (req_sender, req_receiver) = kanal::unbounded_async();
             driver.push_client(req_sender);
+            let retry_interval = self.retry_interval;
             tokio::spawn(async move {
-                let client = HickoryClient::new(client_config).await.unwrap(); // TODO
+                // the tunnel or the encrypted connection may be not available at startup
+                let client = loop {
+                    match HickoryClient::new(client_config.clone()).await {
+                        Ok(client) => break client,
+                        Err(e) => {
+                            if req_receiver.is_disconnected() {
+                                return;
+                            }
+                            warn!(
+                                "failed to create hickory client to {}: {e:?}",
+                                client_config.target
+                            );
+                            tokio::time::sleep(retry_interval).await;
+                        }
+                    }
+                };
                 client.run(req_receiver).await;
             });
         }
@@ -96,6 +96,23 @@ impl HickoryDriverConfig {
                 Ok(())
             }
             "negative_max_ttl" => Ok(()),
+            #[cfg(feature = "dnssec")]
+            "dnssec_validation" | "dnssec" => {
+                self.dnssec_validation = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            #[cfg(feature = "dnssec")]
+            "dnssec_trust_anchor" => {
+                let lookup_dir = lookup_dir.ok_or_else(|| anyhow!("no lookup dir set"))?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.dnssec_trust_anchor = Some(path);
+                Ok(())
+            }
+            #[cfg(not(feature = "dnssec"))]
+            "dnssec_validation" | "dnssec" | "dnssec_trust_anchor" => {
+                Err(anyhow!("dnssec is not supported in this build"))
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -107,6 +124,8 @@ mod tests {
     use g3_types::net::DnsEncryptionProtocol;
     use g3_yaml::yaml_doc;
     use std::net::IpAddr;
+    #[cfg(feature = "dnssec")]
+    use std::path::PathBuf;
     use std::str::FromStr;
     use std::time::Duration;
     use yaml_rust::YamlLoader;
@@ -291,4 +310,30 @@ mod tests {
         assert_eq!(config.positive_min_ttl, 7200);
         assert_eq!(config.positive_max_ttl, 7200);
     }
+
+    #[cfg(feature = "dnssec")]
+    #[test]
+    fn set_by_yaml_kv_dnssec() {
+        use g3_yaml::yaml_str;
+
+        let mut config = HickoryDriverConfig::default();
+        config.add_server_str("8.8.8.8").unwrap();
+        config
+            .set_by_yaml_kv("dnssec", &Yaml::Boolean(true), None)
+            .unwrap();
+        assert!(config.dnssec_validation);
+        assert!(config.check().is_ok());
+
+        assert!(
+            config
+                .set_by_yaml_kv("dnssec_trust_anchor", &yaml_str!("root.key"), None)
+                .is_err()
+        );
+
+        let mut config = HickoryDriverConfig::default();
+        config.add_server_str("8.8.8.8").unwrap();
+        config.dnssec_validation = true;
+        config.dnssec_trust_anchor = Some(PathBuf::from("/nonexistent/root.key"));
+        assert!(config.check().is_err());
+    }
 }
@@ -0,0 +1,74 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use hickory_client::{ClientError, ClientErrorKind};
+use hickory_proto::ProtoErrorKind;
+use hickory_proto::dnssec::Proof;
+use hickory_proto::rr::Record;
+
+use crate::{DnssecStatus, ResolvedRecord};
+
+/// Collect the DNSSEC proofs of all the records used to build a resolved record
+pub(super) struct DnssecProofTracker {
+    enabled: bool,
+    insecure: bool,
+    bogus: bool,
+}
+
+impl DnssecProofTracker {
+    pub(super) fn new(enabled: bool) -> Self {
+        DnssecProofTracker {
+            enabled,
+            insecure: false,
+            bogus: false,
+        }
+    }
+
+    pub(super) fn add_record(&mut self, record: &Record) {
+        if !self.enabled {
+            return;
+        }
+        match record.proof() {
+            Proof::Secure => {}
+            Proof::Insecure => self.insecure = true,
+            Proof::Bogus | Proof::Indeterminate => self.bogus = true,
+        }
+    }
+
+    /// The authority records of a negative response have already been checked by the
+    /// validating handle, we only need to know if the zone is unsigned
+    pub(super) fn add_negative(&mut self, authorities: &[Record]) {
+        if !self.enabled {
+            return;
+        }
+        if authorities.is_empty() || authorities.iter().all(|r| r.proof().is_insecure()) {
+            self.insecure = true;
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_bogus(&self) -> bool {
+        self.bogus
+    }
+
+    pub(super) fn mark_record(&self, mut record: ResolvedRecord) -> ResolvedRecord {
+        if self.enabled {
+            record.dnssec = if self.insecure {
+                Some(DnssecStatus::Insecure)
+            } else {
+                Some(DnssecStatus::Secure)
+            };
+        }
+        record
+    }
+}
+
+/// Negative responses that can not be proven are returned as errors by the validating handle
+pub(super) fn is_bogus_error(e: &ClientError) -> bool {
+    match e.kind() {
+        ClientErrorKind::Proto(e) => matches!(e.kind(), ProtoErrorKind::Nsec { .. }),
+        _ => false,
+    }
+}
@@ -6,6 +6,12 @@
 mod config;
 pub use config::HickoryDriverConfig;
 
+mod tunnel;
+use tunnel::HickoryTunnel;
+pub use tunnel::{
+    ArcHickoryTunnelConnect, BoxHickoryTunnelStream, HickoryTunnelConnect, HickoryTunnelStream,
+};
+
 mod client;
 use client::{DnsRequest, HickoryClient, HickoryClientConfig};
 
@@ -13,3 +19,6 @@ mod driver;
 use driver::HickoryResolver;
 
 mod error;
+
+#[cfg(feature = "dnssec")]
+mod dnssec;
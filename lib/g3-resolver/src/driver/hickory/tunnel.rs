@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+pub trait HickoryTunnelStream: AsyncRead + AsyncWrite + Unpin + Send + Sync {}
+
+impl<T> HickoryTunnelStream for T where T: AsyncRead + AsyncWrite + Unpin + Send + Sync {}
+
+pub type BoxHickoryTunnelStream = Box<dyn HickoryTunnelStream>;
+
+/// Set up TCP connections to the DNS servers through an external tunnel
+#[async_trait]
+pub trait HickoryTunnelConnect {
+    async fn connect(&self, server: SocketAddr) -> io::Result<BoxHickoryTunnelStream>;
+}
+
+pub type ArcHickoryTunnelConnect = Arc<dyn HickoryTunnelConnect + Send + Sync>;
+
+#[derive(Clone)]
+pub(super) struct HickoryTunnel(pub(super) ArcHickoryTunnelConnect);
+
+impl fmt::Debug for HickoryTunnel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HickoryTunnel")
+    }
+}
+
+impl PartialEq for HickoryTunnel {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for HickoryTunnel {}
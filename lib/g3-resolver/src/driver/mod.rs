@@ -10,6 +10,7 @@ use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
 
 pub mod fail_over;
+pub mod static_hosts;
 
 #[cfg(feature = "c-ares")]
 pub mod c_ares;
@@ -20,6 +21,7 @@ pub mod hickory;
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyResolveDriverConfig {
     FailOver(fail_over::FailOverDriverConfig),
+    StaticHosts(static_hosts::StaticHostsDriverConfig),
     #[cfg(feature = "c-ares")]
     CAres(c_ares::CAresDriverConfig),
     #[cfg(feature = "hickory")]
@@ -30,6 +32,7 @@ impl AnyResolveDriverConfig {
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<Box<dyn ResolveDriver>> {
         match self {
             AnyResolveDriverConfig::FailOver(c) => Ok(c.spawn_resolver_driver()),
+            AnyResolveDriverConfig::StaticHosts(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "c-ares")]
             AnyResolveDriverConfig::CAres(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "hickory")]
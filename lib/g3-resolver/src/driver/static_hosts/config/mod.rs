@@ -0,0 +1,183 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
+use log::{debug, warn};
+
+use super::{StaticHostsResolver, StaticHostsTable};
+use crate::{BoxResolverDriver, ResolverHandle};
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaticHostsFileFormat {
+    Hosts,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl StaticHostsFileFormat {
+    fn parse(&self, content: &str) -> anyhow::Result<StaticHostsTable> {
+        match self {
+            StaticHostsFileFormat::Hosts => StaticHostsTable::parse_hosts(content),
+            #[cfg(feature = "yaml")]
+            StaticHostsFileFormat::Yaml => StaticHostsTable::parse_yaml_doc(content),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaticHostsDriverConfig {
+    records: StaticHostsTable,
+    file: Option<PathBuf>,
+    file_format: StaticHostsFileFormat,
+    refresh_interval: Duration,
+    ttl: u32,
+    next: Option<ResolverHandle>,
+}
+
+impl Default for StaticHostsDriverConfig {
+    fn default() -> Self {
+        StaticHostsDriverConfig {
+            records: StaticHostsTable::default(),
+            file: None,
+            file_format: StaticHostsFileFormat::Hosts,
+            refresh_interval: Duration::from_secs(10),
+            ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+            next: None,
+        }
+    }
+}
+
+fn load_file(path: &Path, format: StaticHostsFileFormat) -> anyhow::Result<StaticHostsTable> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+    format
+        .parse(&content)
+        .context(format!("invalid records in file {}", path.display()))
+}
+
+impl StaticHostsDriverConfig {
+    pub fn set_next_handle(&mut self, handle: Option<ResolverHandle>) {
+        self.next = handle;
+    }
+
+    pub fn set_records(&mut self, records: StaticHostsTable) {
+        self.records = records;
+    }
+
+    pub fn set_file(&mut self, path: PathBuf, format: StaticHostsFileFormat) {
+        self.file = Some(path);
+        self.file_format = format;
+    }
+
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = interval;
+    }
+
+    pub fn set_ttl(&mut self, ttl: u32) {
+        self.ttl = ttl;
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        match &self.file {
+            Some(path) => {
+                load_file(path, self.file_format)?;
+            }
+            None => {
+                if self.records.is_empty() {
+                    return Err(anyhow!("neither records nor file is set"));
+                }
+            }
+        }
+        if self.refresh_interval.is_zero() {
+            return Err(anyhow!("refresh interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    fn load_table(&self, file_table: Option<StaticHostsTable>) -> StaticHostsTable {
+        match file_table {
+            Some(mut table) => {
+                table.merge_override(&self.records);
+                table
+            }
+            None => self.records.clone(),
+        }
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
+        let Some(path) = self.file.clone() else {
+            return Ok(Box::new(StaticHostsResolver::new(
+                Arc::new(ArcSwap::from_pointee(self.records.clone())),
+                self.next.clone(),
+                self.ttl,
+                None,
+            )));
+        };
+
+        let mut last_modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let file_table = load_file(&path, self.file_format)?;
+        let table = Arc::new(ArcSwap::from_pointee(self.load_table(Some(file_table))));
+
+        let config = self.clone();
+        let table_w = table.clone();
+        let refresh_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.refresh_interval);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+
+                let path = path.clone();
+                let format = config.file_format;
+                let loaded = tokio::task::spawn_blocking(move || {
+                    let modified = std::fs::metadata(&path).and_then(|m| m.modified())?;
+                    if last_modified.is_some_and(|t: SystemTime| t == modified) {
+                        return Ok((modified, None));
+                    }
+                    Ok::<_, anyhow::Error>((modified, Some(load_file(&path, format)?)))
+                })
+                .await;
+                match loaded {
+                    Ok(Ok((modified, Some(file_table)))) => {
+                        debug!("reloaded static hosts file {}", config.path_display());
+                        table_w.store(Arc::new(config.load_table(Some(file_table))));
+                        last_modified = Some(modified);
+                    }
+                    Ok(Ok((_, None))) => {}
+                    Ok(Err(e)) => {
+                        warn!(
+                            "failed to reload static hosts file {}: {e:?}",
+                            config.path_display()
+                        );
+                    }
+                    Err(e) => {
+                        warn!("failed to join static hosts file reload task: {e}");
+                    }
+                }
+            }
+        });
+
+        Ok(Box::new(StaticHostsResolver::new(
+            table,
+            self.next.clone(),
+            self.ttl,
+            Some(refresh_handle.abort_handle()),
+        )))
+    }
+
+    fn path_display(&self) -> String {
+        self.file
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,100 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, YamlLoader, yaml};
+
+use super::{StaticHostsDriverConfig, StaticHostsFileFormat};
+use crate::driver::static_hosts::StaticHostsTable;
+
+impl StaticHostsTable {
+    /// Parse records from a yaml map, the value can be a single ip or a sequence of ips
+    pub fn parse_yaml_map(map: &yaml::Hash) -> anyhow::Result<Self> {
+        let mut table = StaticHostsTable::default();
+        g3_yaml::foreach_kv(map, |k, v| {
+            let ips = match v {
+                Yaml::Array(seq) => {
+                    let mut ips = Vec::with_capacity(seq.len());
+                    for (i, v) in seq.iter().enumerate() {
+                        let ip = g3_yaml::value::as_ipaddr(v)
+                            .context(format!("invalid ip address value for {k}#{i}"))?;
+                        ips.push(ip);
+                    }
+                    ips
+                }
+                _ => {
+                    let ip = g3_yaml::value::as_ipaddr(v)
+                        .context(format!("invalid ip address value for {k}"))?;
+                    vec![ip]
+                }
+            };
+            table.set(k, &ips)
+        })?;
+        Ok(table)
+    }
+
+    pub(crate) fn parse_yaml_doc(content: &str) -> anyhow::Result<Self> {
+        let docs =
+            YamlLoader::load_from_str(content).map_err(|e| anyhow!("invalid yaml content: {e}"))?;
+        match docs.first() {
+            Some(Yaml::Hash(map)) => Self::parse_yaml_map(map),
+            Some(_) => Err(anyhow!("yaml doc should be a map")),
+            None => Ok(StaticHostsTable::default()),
+        }
+    }
+}
+
+impl StaticHostsDriverConfig {
+    pub fn set_by_yaml_kv(
+        &mut self,
+        k: &str,
+        v: &Yaml,
+        lookup_dir: Option<&Path>,
+    ) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "records" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.records =
+                    StaticHostsTable::parse_yaml_map(map).context(format!("invalid {k} value"))?;
+                Ok(())
+            }
+            "file" | "hosts_file" => {
+                let lookup_dir = lookup_dir.ok_or_else(|| anyhow!("no lookup dir set"))?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                if let Some(ext) = path.extension()
+                    && (ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+                {
+                    self.file_format = StaticHostsFileFormat::Yaml;
+                }
+                self.file = Some(path);
+                Ok(())
+            }
+            "file_format" => {
+                let s = g3_yaml::value::as_string(v)?;
+                self.file_format = match s.to_ascii_lowercase().as_str() {
+                    "hosts" => StaticHostsFileFormat::Hosts,
+                    "yaml" | "yml" => StaticHostsFileFormat::Yaml,
+                    _ => return Err(anyhow!("unsupported file format {s}")),
+                };
+                Ok(())
+            }
+            "refresh_interval" => {
+                self.refresh_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "ttl" => {
+                self.ttl = g3_yaml::value::as_u32(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use arcstr::ArcStr;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+use super::StaticHostsTable;
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{
+    ResolveDriver, ResolveError, ResolveJob, ResolveServerError, ResolvedRecord, ResolverHandle,
+};
+
+pub(crate) struct StaticHostsResolver {
+    table: Arc<ArcSwap<StaticHostsTable>>,
+    next: Option<ResolverHandle>,
+    ttl: u32,
+    refresh_handle: Option<AbortHandle>,
+}
+
+impl Drop for StaticHostsResolver {
+    fn drop(&mut self) {
+        if let Some(handle) = self.refresh_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl StaticHostsResolver {
+    pub(super) fn new(
+        table: Arc<ArcSwap<StaticHostsTable>>,
+        next: Option<ResolverHandle>,
+        ttl: u32,
+        refresh_handle: Option<AbortHandle>,
+    ) -> Self {
+        StaticHostsResolver {
+            table,
+            next,
+            ttl,
+            refresh_handle,
+        }
+    }
+
+    fn build_record(&self, domain: ArcStr, ips: &[IpAddr]) -> ResolvedRecord {
+        if ips.is_empty() {
+            ResolvedRecord::empty(domain, self.ttl)
+        } else {
+            ResolvedRecord::resolved(domain, self.ttl, self.ttl, self.ttl, ips.to_vec())
+        }
+    }
+
+    /// Returns `None` if the query should be forwarded to the next resolver
+    fn query_local<F>(&self, domain: &ArcStr, select: F) -> Option<ResolvedRecord>
+    where
+        F: Fn(&super::StaticHostsEntry) -> &[IpAddr],
+    {
+        let table = self.table.load();
+        if let Some(entry) = table.lookup(domain) {
+            return Some(self.build_record(domain.clone(), select(entry)));
+        }
+        if self.next.is_some() {
+            None
+        } else {
+            Some(ResolvedRecord::failed(
+                domain.clone(),
+                self.ttl,
+                ResolveError::FromServer(ResolveServerError::NotFound),
+            ))
+        }
+    }
+
+    fn forward<F>(
+        &self,
+        domain: ArcStr,
+        job: Result<ResolveJob, crate::ResolveLocalError>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+        wrap: F,
+    ) where
+        F: Fn(ResolvedRecord) -> ResolveDriverResponse + Send + 'static,
+    {
+        let negative_ttl = self.ttl;
+        let job_timeout = config.protective_query_timeout;
+        let mut job = match job {
+            Ok(job) => job,
+            Err(e) => {
+                let record = ResolvedRecord::failed(domain, negative_ttl, e.into());
+                let _ = sender.send(wrap(record));
+                return;
+            }
+        };
+        tokio::spawn(async move {
+            let record = match tokio::time::timeout(job_timeout, job.recv()).await {
+                Ok(Ok((r, _))) => r.as_ref().clone(),
+                Ok(Err(e)) => ResolvedRecord::failed(domain, negative_ttl, e.into()),
+                Err(_) => ResolvedRecord::timed_out(domain, negative_ttl),
+            };
+            let _ = sender.send(wrap(record));
+        });
+    }
+}
+
+impl ResolveDriver for StaticHostsResolver {
+    fn query_v4(
+        &self,
+        domain: ArcStr,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        if let Some(record) = self.query_local(&domain, |e| e.v4()) {
+            let _ = sender.send(ResolveDriverResponse::V4(record));
+            return;
+        }
+        if let Some(next) = &self.next {
+            let job = next.get_v4(domain.clone());
+            self.forward(domain, job, config, sender, ResolveDriverResponse::V4);
+        }
+    }
+
+    fn query_v6(
+        &self,
+        domain: ArcStr,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        if let Some(record) = self.query_local(&domain, |e| e.v6()) {
+            let _ = sender.send(ResolveDriverResponse::V6(record));
+            return;
+        }
+        if let Some(next) = &self.next {
+            let job = next.get_v6(domain.clone());
+            self.forward(domain, job, config, sender, ResolveDriverResponse::V6);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+mod table;
+use table::StaticHostsEntry;
+pub use table::StaticHostsTable;
+
+mod config;
+pub use config::{StaticHostsDriverConfig, StaticHostsFileFormat};
+
+mod driver;
+use driver::StaticHostsResolver;
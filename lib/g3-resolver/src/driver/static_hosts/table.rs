@@ -0,0 +1,226 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use anyhow::{Context, anyhow};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct StaticHostsEntry {
+    v4: Vec<IpAddr>,
+    v6: Vec<IpAddr>,
+}
+
+impl StaticHostsEntry {
+    fn add(&mut self, ip: IpAddr) {
+        let ips = match ip {
+            IpAddr::V4(_) => &mut self.v4,
+            IpAddr::V6(_) => &mut self.v6,
+        };
+        if !ips.contains(&ip) {
+            ips.push(ip);
+        }
+    }
+
+    pub(crate) fn v4(&self) -> &[IpAddr] {
+        &self.v4
+    }
+
+    pub(crate) fn v6(&self) -> &[IpAddr] {
+        &self.v6
+    }
+}
+
+/// The static records table.
+///
+/// The following kinds of names are supported:
+///  - `www.example.net`: match the exact domain only
+///  - `*.example.net`: match all subdomains of example.net, but not itself
+///  - `.example.net`: match example.net itself and all its subdomains
+///
+/// The exact match takes precedence, then the longest wildcard / suffix match.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StaticHostsTable {
+    exact: AHashMap<String, StaticHostsEntry>,
+    wildcard: AHashMap<String, StaticHostsEntry>,
+    suffix: AHashMap<String, StaticHostsEntry>,
+}
+
+enum NameKey<'a> {
+    Exact(&'a str),
+    Wildcard(&'a str),
+    Suffix(&'a str),
+}
+
+impl<'a> NameKey<'a> {
+    fn parse(name: &'a str) -> anyhow::Result<Self> {
+        let name = name.strip_suffix('.').unwrap_or(name);
+        let key = if let Some(s) = name.strip_prefix("*.") {
+            NameKey::Wildcard(s)
+        } else if let Some(s) = name.strip_prefix('.') {
+            NameKey::Suffix(s)
+        } else {
+            NameKey::Exact(name)
+        };
+        let s = match key {
+            NameKey::Exact(s) | NameKey::Wildcard(s) | NameKey::Suffix(s) => s,
+        };
+        if s.is_empty() || s.starts_with('.') || s.contains('*') {
+            return Err(anyhow!("invalid domain name {name}"));
+        }
+        Ok(key)
+    }
+}
+
+impl StaticHostsTable {
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.wildcard.is_empty() && self.suffix.is_empty()
+    }
+
+    fn get_entry_mut(&mut self, name: &str) -> anyhow::Result<&mut StaticHostsEntry> {
+        let entry = match NameKey::parse(name)? {
+            NameKey::Exact(s) => self.exact.entry(s.to_ascii_lowercase()),
+            NameKey::Wildcard(s) => self.wildcard.entry(s.to_ascii_lowercase()),
+            NameKey::Suffix(s) => self.suffix.entry(s.to_ascii_lowercase()),
+        };
+        Ok(entry.or_default())
+    }
+
+    /// Add an ip address to the record of the name
+    pub fn add(&mut self, name: &str, ip: IpAddr) -> anyhow::Result<()> {
+        self.get_entry_mut(name)?.add(ip);
+        Ok(())
+    }
+
+    /// Set all ip addresses of the name, the existing record will be replaced
+    pub fn set(&mut self, name: &str, ips: &[IpAddr]) -> anyhow::Result<()> {
+        let entry = self.get_entry_mut(name)?;
+        *entry = StaticHostsEntry::default();
+        for ip in ips {
+            entry.add(*ip);
+        }
+        Ok(())
+    }
+
+    /// Merge records from another table, the records in it will override the existing ones
+    pub fn merge_override(&mut self, other: &StaticHostsTable) {
+        for (k, v) in &other.exact {
+            self.exact.insert(k.clone(), v.clone());
+        }
+        for (k, v) in &other.wildcard {
+            self.wildcard.insert(k.clone(), v.clone());
+        }
+        for (k, v) in &other.suffix {
+            self.suffix.insert(k.clone(), v.clone());
+        }
+    }
+
+    /// Parse records in /etc/hosts format
+    pub fn parse_hosts(content: &str) -> anyhow::Result<Self> {
+        let mut table = StaticHostsTable::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((s, _)) => s,
+                None => line,
+            };
+            let mut iter = line.split_ascii_whitespace();
+            let Some(ip) = iter.next() else {
+                continue;
+            };
+            let ip = IpAddr::from_str(ip)
+                .map_err(|e| anyhow!("invalid ip address {ip}: {e}"))
+                .context(format!("invalid record at line {}", i + 1))?;
+            for name in iter {
+                table
+                    .add(name, ip)
+                    .context(format!("invalid record at line {}", i + 1))?;
+            }
+        }
+        Ok(table)
+    }
+
+    pub(crate) fn lookup(&self, domain: &str) -> Option<&StaticHostsEntry> {
+        let domain = domain.strip_suffix('.').unwrap_or(domain);
+        let domain = domain.to_ascii_lowercase();
+        if let Some(entry) = self.exact.get(&domain) {
+            return Some(entry);
+        }
+        if let Some(entry) = self.suffix.get(&domain) {
+            return Some(entry);
+        }
+
+        let mut left = domain.as_str();
+        while let Some((_, parent)) = left.split_once('.') {
+            if let Some(entry) = self.wildcard.get(parent) {
+                return Some(entry);
+            }
+            if let Some(entry) = self.suffix.get(parent) {
+                return Some(entry);
+            }
+            left = parent;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn lookup_precedence() {
+        let mut table = StaticHostsTable::default();
+        table.add("www.example.net", ip("192.0.2.1")).unwrap();
+        table.add("*.example.net", ip("192.0.2.2")).unwrap();
+        table.add(".example.net", ip("192.0.2.3")).unwrap();
+        table.add("*.a.example.net", ip("2001:db8::1")).unwrap();
+
+        let e = table.lookup("WWW.Example.net.").unwrap();
+        assert_eq!(e.v4(), &[ip("192.0.2.1")]);
+        let e = table.lookup("foo.example.net").unwrap();
+        assert_eq!(e.v4(), &[ip("192.0.2.2")]);
+        let e = table.lookup("example.net").unwrap();
+        assert_eq!(e.v4(), &[ip("192.0.2.3")]);
+        let e = table.lookup("b.a.example.net").unwrap();
+        assert!(e.v4().is_empty());
+        assert_eq!(e.v6(), &[ip("2001:db8::1")]);
+        assert!(table.lookup("example.org").is_none());
+        assert!(table.lookup("net").is_none());
+    }
+
+    #[test]
+    fn parse_hosts() {
+        let content = "# comment\n\
+            127.0.0.1 localhost localhost.localdomain\n\
+            ::1       localhost # inline comment\n\
+            \n\
+            10.0.0.1  .svc.example.net\n";
+        let table = StaticHostsTable::parse_hosts(content).unwrap();
+        let e = table.lookup("localhost").unwrap();
+        assert_eq!(e.v4(), &[ip("127.0.0.1")]);
+        assert_eq!(e.v6(), &[ip("::1")]);
+        let e = table.lookup("a.svc.example.net").unwrap();
+        assert_eq!(e.v4(), &[ip("10.0.0.1")]);
+
+        assert!(StaticHostsTable::parse_hosts("10.0.0.300 a.example.net").is_err());
+        assert!(StaticHostsTable::parse_hosts("10.0.0.1 *.*.example.net").is_err());
+    }
+
+    #[test]
+    fn merge_override() {
+        let mut table = StaticHostsTable::parse_hosts("10.0.0.1 a.example.net").unwrap();
+        let mut other = StaticHostsTable::default();
+        other.set("a.example.net", &[ip("10.0.0.2")]).unwrap();
+        table.merge_override(&other);
+        let e = table.lookup("a.example.net").unwrap();
+        assert_eq!(e.v4(), &[ip("10.0.0.2")]);
+    }
+}
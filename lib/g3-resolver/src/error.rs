@@ -95,6 +95,8 @@ pub enum ResolveError {
     FromDriver(#[from] ResolveDriverError),
     #[error("local error: {0}")]
     FromLocal(#[from] ResolveLocalError),
+    #[error("dnssec validation failed")]
+    DnssecBogus,
     #[error("unexpected error: {0}")]
     UnexpectedError(&'static str),
 }
@@ -107,13 +109,14 @@ impl ResolveError {
             ResolveError::FromServer(_) => "ServerError",
             ResolveError::FromDriver(_) => "DriverError",
             ResolveError::FromLocal(_) => "LocalError",
+            ResolveError::DnssecBogus => "DnssecBogus",
             ResolveError::UnexpectedError(_) => "UnexpectedError",
         }
     }
 
     pub fn get_subtype(&self) -> &str {
         match self {
-            ResolveError::EmptyDomain | ResolveError::EmptyResult => "",
+            ResolveError::EmptyDomain | ResolveError::EmptyResult | ResolveError::DnssecBogus => "",
             ResolveError::FromServer(e) => e.get_type(),
             ResolveError::FromDriver(e) => e.get_type(),
             ResolveError::FromLocal(e) => e.get_type(),
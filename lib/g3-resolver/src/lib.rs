@@ -16,12 +16,14 @@ mod query;
 mod record;
 mod resolver;
 mod runtime;
+mod snapshot;
 mod stats;
 
 pub use config::{ResolverConfig, ResolverRuntimeConfig};
 pub use error::{ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError};
 pub use handle::{ResolveJob, ResolveJobRecvResult, ResolverHandle};
 pub use query::ResolveQueryType;
-pub use record::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource};
+pub use record::{ArcResolvedRecord, DnssecStatus, ResolvedRecord, ResolvedRecordSource};
 pub use resolver::{Resolver, ResolverBuilder};
+pub use snapshot::ResolverCacheSnapshotConfig;
 pub use stats::{ResolverMemorySnapshot, ResolverQuerySnapshot, ResolverSnapshot, ResolverStats};
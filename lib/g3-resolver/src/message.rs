@@ -8,10 +8,11 @@ use tokio::sync::oneshot;
 
 use super::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource, ResolverConfig};
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub(crate) enum ResolverCommand {
     Quit,
     Update(Box<ResolverConfig>),
+    SaveSnapshot(oneshot::Sender<anyhow::Result<()>>),
 }
 
 pub(crate) enum ResolveDriverRequest {
@@ -37,6 +37,15 @@ impl fmt::Display for ResolvedRecordSource {
     }
 }
 
+/// The DNSSEC validation status of a resolved record
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DnssecStatus {
+    /// There is a chain of trust from the trust anchors to the record
+    Secure,
+    /// The record is proven to be in an unsigned zone
+    Insecure,
+}
+
 #[derive(Clone, Debug)]
 pub struct ResolvedRecord {
     pub domain: ArcStr,
@@ -44,6 +53,8 @@ pub struct ResolvedRecord {
     pub expire: Option<Instant>,
     pub vanish: Option<Instant>,
     pub result: Result<Vec<IpAddr>, ResolveError>,
+    /// Set only if DNSSEC validation is enabled
+    pub dnssec: Option<DnssecStatus>,
 }
 
 pub type ArcResolvedRecord = Arc<ResolvedRecord>;
@@ -106,6 +117,7 @@ impl ResolvedRecord {
             expire,
             vanish,
             result: Ok(ips),
+            dnssec: None,
         }
     }
 
@@ -118,6 +130,7 @@ impl ResolvedRecord {
             expire,
             vanish: None,
             result: Ok(Vec::new()),
+            dnssec: None,
         }
     }
 
@@ -130,6 +143,7 @@ impl ResolvedRecord {
             expire,
             vanish: None,
             result: Err(err),
+            dnssec: None,
         }
     }
 }
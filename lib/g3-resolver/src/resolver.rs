@@ -7,8 +7,9 @@ use std::io;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
+use anyhow::anyhow;
 use log::warn;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use super::ResolverStats;
 use crate::config::ResolverConfig;
@@ -109,6 +110,25 @@ impl Resolver {
         Ok(())
     }
 
+    /// Save the cache to the snapshot file, the returned future will wait for the save result
+    pub fn save_cache_snapshot(&self) -> impl Future<Output = anyhow::Result<()>> + Send + use<> {
+        let receiver = if self.config.runtime.cache_snapshot.is_some() {
+            let (sender, receiver) = oneshot::channel();
+            let _ = self.ctl_sender.send(ResolverCommand::SaveSnapshot(sender));
+            Some(receiver)
+        } else {
+            None
+        };
+        async move {
+            match receiver {
+                Some(receiver) => receiver
+                    .await
+                    .map_err(|_| anyhow!("resolver runtime quit unexpectedly"))?,
+                None => Ok(()),
+            }
+        }
+    }
+
     fn stop(&self) {
         let _ = self.ctl_sender.send(ResolverCommand::Quit);
     }
@@ -12,12 +12,13 @@ use ahash::AHashMap;
 use arcstr::ArcStr;
 use log::{trace, warn};
 use tokio::sync::{mpsc, oneshot};
-use tokio::time::Instant;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
 use tokio_util::time::{DelayQueue, delay_queue};
 
 use super::stats::{ResolverMemoryStats, ResolverStats};
 use super::{ArcResolvedRecord, BoxResolverDriver, ResolvedRecordSource, ResolverConfig};
 use crate::message::{ResolveDriverRequest, ResolveDriverResponse, ResolverCommand};
+use crate::snapshot::{CacheSnapshotWriter, load_snapshot, save_snapshot};
 
 struct CachedRecord {
     inner: ArcResolvedRecord,
@@ -46,6 +47,7 @@ pub(crate) struct ResolverRuntime {
     trash_v4: AHashMap<ArcStr, TrashedRecord>,
     trash_v6: AHashMap<ArcStr, TrashedRecord>,
     driver: Option<BoxResolverDriver>,
+    snapshot_interval: Option<Interval>,
 }
 
 impl Drop for ResolverRuntime {
@@ -64,7 +66,7 @@ impl ResolverRuntime {
     ) -> Self {
         let initial_cache_capacity = config.runtime.initial_cache_capacity;
         let (rsp_sender, rsp_receiver) = mpsc::unbounded_channel();
-        ResolverRuntime {
+        let mut runtime = ResolverRuntime {
             config,
             stats,
             req_receiver,
@@ -80,7 +82,11 @@ impl ResolverRuntime {
             trash_v4: AHashMap::with_capacity(initial_cache_capacity),
             trash_v6: AHashMap::with_capacity(initial_cache_capacity),
             driver: None,
-        }
+            snapshot_interval: None,
+        };
+        runtime.load_cache_snapshot();
+        runtime.reset_snapshot_interval();
+        runtime
     }
 
     fn handle_cmd(&mut self, cmd: ResolverCommand) {
@@ -88,16 +94,103 @@ impl ResolverRuntime {
             ResolverCommand::Update(config) => match config.driver.spawn_resolver_driver() {
                 Ok(driver) => {
                     self.driver = Some(driver);
+                    let snapshot_changed =
+                        self.config.runtime.cache_snapshot != config.runtime.cache_snapshot;
                     self.config = *config;
+                    if snapshot_changed {
+                        self.reset_snapshot_interval();
+                    }
                 }
                 Err(e) => {
                     warn!("invalid resolver config {config:?} : {e}");
                 }
             },
+            ResolverCommand::SaveSnapshot(sender) => {
+                let _ = sender.send(self.save_cache_snapshot());
+            }
             ResolverCommand::Quit => {} // should be handled outside
         }
     }
 
+    fn reset_snapshot_interval(&mut self) {
+        self.snapshot_interval = self.config.runtime.cache_snapshot.as_ref().map(|c| {
+            let mut interval =
+                tokio::time::interval_at(Instant::now() + c.interval(), c.interval());
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+    }
+
+    fn load_cache_snapshot(&mut self) {
+        let Some(snapshot_config) = &self.config.runtime.cache_snapshot else {
+            return;
+        };
+        let snapshot = match load_snapshot(snapshot_config.file()) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!(
+                    "failed to load cache snapshot for resolver {}: {e:?}",
+                    self.config.name
+                );
+                return;
+            }
+        };
+        for record in snapshot.v4 {
+            if let Some(expire_at) = record.expire {
+                let record = Arc::new(record);
+                Self::update_cache(&mut self.cache_v4, &mut self.expired_v4, record, expire_at);
+            }
+        }
+        for record in snapshot.v6 {
+            if let Some(expire_at) = record.expire {
+                let record = Arc::new(record);
+                Self::update_cache(&mut self.cache_v6, &mut self.expired_v6, record, expire_at);
+            }
+        }
+        self.update_mem_stats();
+    }
+
+    fn build_cache_snapshot(&self) -> String {
+        let mut writer = CacheSnapshotWriter::new();
+        for r in self.cache_v4.values() {
+            writer.add_v4(&r.inner);
+        }
+        for r in self.cache_v6.values() {
+            writer.add_v6(&r.inner);
+        }
+        writer.finish()
+    }
+
+    fn save_cache_snapshot(&self) -> anyhow::Result<()> {
+        let Some(snapshot_config) = &self.config.runtime.cache_snapshot else {
+            return Ok(());
+        };
+        let content = self.build_cache_snapshot();
+        save_snapshot(snapshot_config.file(), &content).map_err(|e| {
+            anyhow::anyhow!(
+                "failed to save cache snapshot to file {}: {e}",
+                snapshot_config.file().display()
+            )
+        })
+    }
+
+    fn spawn_save_cache_snapshot(&self) {
+        let Some(snapshot_config) = &self.config.runtime.cache_snapshot else {
+            return;
+        };
+        let content = self.build_cache_snapshot();
+        let path = snapshot_config.file().to_path_buf();
+        let resolver_name = self.config.name.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = save_snapshot(&path, &content) {
+                warn!(
+                    "failed to save cache snapshot for resolver {resolver_name} to file {}: {e}",
+                    path.display()
+                );
+            }
+        });
+    }
+
     fn update_cache(
         cache: &mut AHashMap<ArcStr, CachedRecord>,
         expire_queue: &mut DelayQueue<ArcStr>,
@@ -347,6 +440,9 @@ impl ResolverRuntime {
             };
             if let Some(cmd) = cmd {
                 if matches!(cmd, ResolverCommand::Quit) {
+                    if let Err(e) = self.save_cache_snapshot() {
+                        warn!("resolver {}: {e}", self.config.name);
+                    }
                     break;
                 } else {
                     self.handle_cmd(cmd);
@@ -392,6 +488,12 @@ impl ResolverRuntime {
                 self.update_mem_stats();
             }
 
+            if let Some(interval) = &mut self.snapshot_interval
+                && interval.poll_tick(cx).is_ready()
+            {
+                self.spawn_save_cache_snapshot();
+            }
+
             // handle request
             for _ in 1..self.config.runtime.batch_request_count {
                 let req = match self.req_receiver.poll_recv(cx) {
@@ -0,0 +1,273 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::fmt::Write;
+use std::io;
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, anyhow};
+use arcstr::ArcStr;
+use tokio::time::Instant;
+
+use crate::ResolvedRecord;
+
+const SNAPSHOT_HEADER: &str = "# g3-resolver cache snapshot v1";
+
+const FAMILY_V4: &str = "A";
+const FAMILY_V6: &str = "AAAA";
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Each line contains: family, domain, created unix time, expire ttl, vanish ttl and the ip list.
+pub(crate) struct CacheSnapshotWriter {
+    buf: String,
+    now: Instant,
+    now_unix: u64,
+}
+
+impl CacheSnapshotWriter {
+    pub(crate) fn new() -> Self {
+        CacheSnapshotWriter::with_time(Instant::now(), unix_now())
+    }
+
+    fn with_time(now: Instant, now_unix: u64) -> Self {
+        let mut buf = String::with_capacity(4096);
+        buf.push_str(SNAPSHOT_HEADER);
+        buf.push('\n');
+        CacheSnapshotWriter { buf, now, now_unix }
+    }
+
+    pub(crate) fn add_v4(&mut self, record: &ResolvedRecord) {
+        self.add(FAMILY_V4, record);
+    }
+
+    pub(crate) fn add_v6(&mut self, record: &ResolvedRecord) {
+        self.add(FAMILY_V6, record);
+    }
+
+    fn add(&mut self, family: &str, record: &ResolvedRecord) {
+        let Ok(ips) = &record.result else {
+            return;
+        };
+        if ips.is_empty() {
+            return;
+        }
+        let Some(expire) = record.expire else {
+            return;
+        };
+        if expire <= self.now {
+            return;
+        }
+
+        let created_unix = self
+            .now_unix
+            .saturating_sub(self.now.saturating_duration_since(record.created).as_secs());
+        let expire_ttl = expire.saturating_duration_since(record.created).as_secs();
+        let _ = write!(
+            self.buf,
+            "{family} {} {created_unix} {expire_ttl}",
+            record.domain
+        );
+        match record.vanish {
+            Some(vanish) => {
+                let vanish_ttl = vanish.saturating_duration_since(record.created).as_secs();
+                let _ = write!(self.buf, " {vanish_ttl} ");
+            }
+            None => self.buf.push_str(" - "),
+        }
+        for (i, ip) in ips.iter().enumerate() {
+            if i > 0 {
+                self.buf.push(',');
+            }
+            let _ = write!(self.buf, "{ip}");
+        }
+        self.buf.push('\n');
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.buf
+    }
+}
+
+/// Write to a temp file first, so a crash in the middle won't leave a broken snapshot
+pub(crate) fn save_snapshot(path: &Path, content: &str) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, content)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[derive(Default)]
+pub(crate) struct LoadedCacheSnapshot {
+    pub(crate) v4: Vec<ResolvedRecord>,
+    pub(crate) v6: Vec<ResolvedRecord>,
+}
+
+/// Only records that are still not expired will be returned
+pub(crate) fn load_snapshot(path: &Path) -> anyhow::Result<LoadedCacheSnapshot> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(LoadedCacheSnapshot::default()),
+        Err(e) => return Err(anyhow!("failed to read file {}: {e}", path.display())),
+    };
+    parse_snapshot(&content, Instant::now(), unix_now())
+}
+
+fn parse_snapshot(
+    content: &str,
+    now: Instant,
+    now_unix: u64,
+) -> anyhow::Result<LoadedCacheSnapshot> {
+    let mut snapshot = LoadedCacheSnapshot::default();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_ascii_whitespace();
+        let family = parts.next().unwrap_or_default();
+        let Some(record) = parse_record(&mut parts, now, now_unix)
+            .context(format!("invalid snapshot record at line {}", i + 1))?
+        else {
+            continue;
+        };
+        match family {
+            FAMILY_V4 => snapshot.v4.push(record),
+            FAMILY_V6 => snapshot.v6.push(record),
+            _ => return Err(anyhow!("invalid family {family} at line {}", i + 1)),
+        }
+    }
+    Ok(snapshot)
+}
+
+fn parse_record<'a, I>(
+    parts: &mut I,
+    now: Instant,
+    now_unix: u64,
+) -> anyhow::Result<Option<ResolvedRecord>>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut next_part = |name: &str| parts.next().ok_or_else(|| anyhow!("no {name} field"));
+
+    let domain = next_part("domain")?;
+    let created_unix =
+        u64::from_str(next_part("created")?).map_err(|e| anyhow!("invalid created time: {e}"))?;
+    let expire_ttl =
+        u64::from_str(next_part("expire ttl")?).map_err(|e| anyhow!("invalid expire ttl: {e}"))?;
+    let vanish_ttl = match next_part("vanish ttl")? {
+        "-" => None,
+        s => Some(u64::from_str(s).map_err(|e| anyhow!("invalid vanish ttl: {e}"))?),
+    };
+    let mut ips = Vec::new();
+    for s in next_part("ip list")?.split(',') {
+        let ip = IpAddr::from_str(s).map_err(|e| anyhow!("invalid ip address {s}: {e}"))?;
+        ips.push(ip);
+    }
+
+    let Some(created) = now.checked_sub(Duration::from_secs(now_unix.saturating_sub(created_unix)))
+    else {
+        return Ok(None);
+    };
+    let Some(expire) = created.checked_add(Duration::from_secs(expire_ttl)) else {
+        return Ok(None);
+    };
+    if expire <= now {
+        return Ok(None);
+    }
+    let vanish = vanish_ttl.and_then(|ttl| created.checked_add(Duration::from_secs(ttl)));
+
+    Ok(Some(ResolvedRecord {
+        domain: ArcStr::from(domain),
+        created,
+        expire: Some(expire),
+        vanish,
+        result: Ok(ips),
+        dnssec: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_and_parse() {
+        let now = Instant::now();
+        let now_unix = 1_700_000_000;
+
+        let v4 = ResolvedRecord::resolved(
+            ArcStr::from("www.example.net"),
+            300,
+            30,
+            3600,
+            vec![
+                IpAddr::from_str("192.0.2.1").unwrap(),
+                IpAddr::from_str("192.0.2.2").unwrap(),
+            ],
+        );
+        let v6 = ResolvedRecord::resolved(
+            ArcStr::from("www.example.net"),
+            300,
+            30,
+            3600,
+            vec![IpAddr::from_str("2001:db8::1").unwrap()],
+        );
+        let empty = ResolvedRecord::empty(ArcStr::from("empty.example.net"), 30);
+
+        let mut writer = CacheSnapshotWriter::with_time(now, now_unix);
+        writer.add_v4(&v4);
+        writer.add_v4(&empty);
+        writer.add_v6(&v6);
+        let content = writer.finish();
+        assert_eq!(content.lines().count(), 3);
+
+        // load 10s later
+        let later = now + Duration::from_secs(10);
+        let snapshot = parse_snapshot(&content, later, now_unix + 10).unwrap();
+        assert_eq!(snapshot.v4.len(), 1);
+        assert_eq!(snapshot.v6.len(), 1);
+        let r = &snapshot.v4[0];
+        assert_eq!(r.domain.as_str(), "www.example.net");
+        assert_eq!(r.result.as_ref().unwrap().len(), 2);
+        assert!(!r.is_expired(later));
+        assert!(r.vanish.is_some());
+
+        // all expired
+        let snapshot = parse_snapshot(&content, later, now_unix + 3600).unwrap();
+        assert!(snapshot.v4.is_empty());
+        assert!(snapshot.v6.is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let now = Instant::now();
+        assert!(parse_snapshot("A www.example.net 1700000000 300\n", now, 1_700_000_010).is_err());
+        assert!(
+            parse_snapshot(
+                "MX www.example.net 1700000000 300 - 192.0.2.1\n",
+                now,
+                1_700_000_010
+            )
+            .is_err()
+        );
+        assert!(
+            parse_snapshot(
+                "A www.example.net 1700000000 300 - 192.0.2.x\n",
+                now,
+                1_700_000_010
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+mod file;
+pub(crate) use file::{CacheSnapshotWriter, load_snapshot, save_snapshot};
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+const DEFAULT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolverCacheSnapshotConfig {
+    file: PathBuf,
+    interval: Duration,
+}
+
+impl ResolverCacheSnapshotConfig {
+    pub fn new(file: PathBuf) -> Self {
+        ResolverCacheSnapshotConfig {
+            file,
+            interval: DEFAULT_SNAPSHOT_INTERVAL,
+        }
+    }
+
+    #[inline]
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    #[inline]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use super::ResolverCacheSnapshotConfig;
+
+impl ResolverCacheSnapshotConfig {
+    pub fn parse_yaml(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        match value {
+            Yaml::String(_) => {
+                let file = g3_yaml::value::as_file_path(value, lookup_dir, true)?;
+                Ok(ResolverCacheSnapshotConfig::new(file))
+            }
+            Yaml::Hash(map) => {
+                let v = g3_yaml::hash_get_required(map, "file")?;
+                let file = g3_yaml::value::as_file_path(v, lookup_dir, true)
+                    .context("invalid value for key file")?;
+                let mut config = ResolverCacheSnapshotConfig::new(file);
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "file" => Ok(()),
+                    "interval" => {
+                        config.interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "invalid yaml value type for cache snapshot config, expect string / map"
+            )),
+        }
+    }
+}
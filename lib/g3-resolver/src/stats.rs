@@ -6,7 +6,8 @@
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use super::{
-    ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError, ResolvedRecord,
+    DnssecStatus, ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError,
+    ResolvedRecord,
 };
 
 #[derive(Default)]
@@ -22,6 +23,9 @@ pub struct ResolverQueryStats {
     server_malformed: AtomicU64,
     server_not_found: AtomicU64,
     server_serv_fail: AtomicU64,
+    dnssec_secure: AtomicU64,
+    dnssec_insecure: AtomicU64,
+    dnssec_bogus: AtomicU64,
 }
 
 #[derive(Default)]
@@ -37,6 +41,9 @@ pub struct ResolverQuerySnapshot {
     pub server_malformed: u64,
     pub server_not_found: u64,
     pub server_serv_fail: u64,
+    pub dnssec_secure: u64,
+    pub dnssec_insecure: u64,
+    pub dnssec_bogus: u64,
 }
 
 impl ResolverQueryStats {
@@ -53,6 +60,9 @@ impl ResolverQueryStats {
             server_malformed: self.server_malformed.load(Ordering::Relaxed),
             server_not_found: self.server_not_found.load(Ordering::Relaxed),
             server_serv_fail: self.server_serv_fail.load(Ordering::Relaxed),
+            dnssec_secure: self.dnssec_secure.load(Ordering::Relaxed),
+            dnssec_insecure: self.dnssec_insecure.load(Ordering::Relaxed),
+            dnssec_bogus: self.dnssec_bogus.load(Ordering::Relaxed),
         }
     }
 
@@ -119,9 +129,29 @@ impl ResolverQueryStats {
         self.server_serv_fail.fetch_add(1, Ordering::Relaxed);
     }
 
+    #[inline]
+    fn add_dnssec_secure(&self) {
+        self.dnssec_secure.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn add_dnssec_insecure(&self) {
+        self.dnssec_insecure.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn add_dnssec_bogus(&self) {
+        self.dnssec_bogus.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub(crate) fn add_record(&self, record: &ResolvedRecord) {
-        if let Err(e) = &record.result {
-            self.add_error(e);
+        match &record.result {
+            Ok(_) => match record.dnssec {
+                Some(DnssecStatus::Secure) => self.add_dnssec_secure(),
+                Some(DnssecStatus::Insecure) => self.add_dnssec_insecure(),
+                None => {}
+            },
+            Err(e) => self.add_error(e),
         }
     }
 
@@ -151,6 +181,7 @@ impl ResolverQueryStats {
             ResolveError::FromServer(e) => self.add_server_error(e),
             ResolveError::FromDriver(e) => self.add_driver_error(e),
             ResolveError::FromLocal(ResolveLocalError::DriverTimedOut) => self.add_driver_timeout(),
+            ResolveError::DnssecBogus => self.add_dnssec_bogus(),
             _ => {}
         }
     }
@@ -160,6 +160,8 @@ impl BindAddr {
                 }
                 if addr.port() == 0 {
                     set_bind_address_no_port(socket, true)?;
+                } else {
+                    socket.set_reuse_address(true)?;
                 }
                 match addr {
                     SocketAddr::V4(_) => {
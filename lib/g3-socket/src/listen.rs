@@ -58,6 +58,25 @@ pub(super) fn set_udp_recv_pktinfo(socket: &Socket, addr: SocketAddr) -> io::Res
     }
 }
 
+#[cfg(target_os = "linux")]
+pub(super) fn set_udp_transparent(socket: &Socket, addr: SocketAddr) -> io::Result<()> {
+    match addr.ip() {
+        IpAddr::V4(_) => {
+            socket.set_ip_transparent_v4(true)?;
+            crate::sockopt::set_recv_orig_dst_addr_v4(socket, true)
+        }
+        IpAddr::V6(v6) => {
+            crate::sockopt::set_ip_transparent_v6(socket, true)?;
+            crate::sockopt::set_recv_orig_dst_addr_v6(socket, true)?;
+            if v6.is_unspecified() {
+                // ipv4 packets may also be received on the dual stack socket
+                crate::sockopt::set_recv_orig_dst_addr_v4(socket, true)?;
+            }
+            Ok(())
+        }
+    }
+}
+
 #[cfg(windows)]
 pub(super) fn set_udp_recv_pktinfo(
     socket: &Socket,
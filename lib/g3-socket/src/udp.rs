@@ -112,6 +112,10 @@ pub fn new_std_bind_listen(config: &UdpListenConfig) -> io::Result<UdpSocket> {
     if let Some(enable) = config.is_ipv6only() {
         super::listen::set_only_v6(&socket, addr, enable)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        super::listen::set_udp_transparent(&socket, addr)?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -141,6 +145,10 @@ pub fn new_std_rebind_listen(config: &UdpListenConfig, addr: SocketAddr) -> io::
     if let Some(enable) = config.is_ipv6only() {
         super::listen::set_only_v6(&socket, addr, enable)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        super::listen::set_udp_transparent(&socket, addr)?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     #[cfg(unix)]
@@ -52,6 +52,16 @@ impl RateLimitQuota {
     pub fn allow_burst(&mut self, max_burst: NonZeroU32) {
         self.max_burst = max_burst;
     }
+
+    #[inline]
+    pub fn max_burst(&self) -> NonZeroU32 {
+        self.max_burst
+    }
+
+    #[inline]
+    pub fn replenish_interval(&self) -> Duration {
+        Duration::from_nanos(self.replenish_nanos.get())
+    }
 }
 
 impl FromStr for RateLimitQuota {
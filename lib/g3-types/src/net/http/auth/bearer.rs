@@ -0,0 +1,41 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+
+use crate::auth::AuthParseError;
+
+pub struct HttpBearerAuth {
+    pub token: String,
+}
+
+impl HttpBearerAuth {
+    pub fn new(token: String) -> Self {
+        HttpBearerAuth { token }
+    }
+}
+
+impl FromStr for HttpBearerAuth {
+    type Err = AuthParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = s.trim(); // allow more space than spec
+        if token.is_empty() {
+            return Err(AuthParseError::InvalidPassword);
+        }
+        Ok(HttpBearerAuth {
+            token: token.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&HttpBearerAuth> for http::HeaderValue {
+    type Error = http::header::InvalidHeaderValue;
+
+    fn try_from(value: &HttpBearerAuth) -> Result<Self, Self::Error> {
+        let value = format!("Bearer {}", value.token);
+        http::HeaderValue::from_str(&value)
+    }
+}
@@ -13,9 +13,17 @@ use crate::net::HttpHeaderValue;
 mod basic;
 pub use basic::HttpBasicAuth;
 
+mod bearer;
+pub use bearer::HttpBearerAuth;
+
+mod negotiate;
+pub use negotiate::HttpNegotiateAuth;
+
 pub enum HttpAuth {
     None,
     Basic(HttpBasicAuth),
+    Bearer(HttpBearerAuth),
+    Negotiate(HttpNegotiateAuth),
 }
 
 impl HttpAuth {
@@ -26,6 +34,14 @@ impl HttpAuth {
                     let basic = HttpBasicAuth::from_str(&value[i + 1..])?;
                     Ok(HttpAuth::Basic(basic))
                 }
+                "bearer" => {
+                    let bearer = HttpBearerAuth::from_str(&value[i + 1..])?;
+                    Ok(HttpAuth::Bearer(bearer))
+                }
+                "negotiate" => {
+                    let negotiate = HttpNegotiateAuth::from_str(&value[i + 1..])?;
+                    Ok(HttpAuth::Negotiate(negotiate))
+                }
                 _ => Ok(HttpAuth::None),
             },
             None => Err(AuthParseError::UnsupportedAuthType),
@@ -87,6 +103,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_bearer() {
+        let value = "Bearer abc.def";
+        let info = HttpAuth::from_authorization(value).unwrap();
+        let HttpAuth::Bearer(bearer) = info else {
+            panic!("not bearer auth");
+        };
+        assert_eq!(bearer.token, "abc.def");
+
+        let value = "Bearer ";
+        assert!(HttpAuth::from_authorization(value).is_err());
+    }
+
+    #[test]
+    fn parse_negotiate() {
+        let value = "Negotiate YIIBAA==";
+        let info = HttpAuth::from_authorization(value).unwrap();
+        let HttpAuth::Negotiate(negotiate) = info else {
+            panic!("not negotiate auth");
+        };
+        assert_eq!(negotiate.token, [0x60, 0x82, 0x01, 0x00]);
+
+        let value = "Negotiate !";
+        assert!(HttpAuth::from_authorization(value).is_err());
+    }
+
     #[test]
     fn parse_scheme_only() {
         let value = "Basic ";
@@ -0,0 +1,58 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use std::str::FromStr;
+
+use base64::prelude::*;
+
+use crate::auth::AuthParseError;
+
+/// the SPNEGO token in Negotiate auth, see rfc4559
+pub struct HttpNegotiateAuth {
+    pub token: Vec<u8>,
+    encoded_value: String,
+}
+
+impl HttpNegotiateAuth {
+    pub fn new(token: Vec<u8>) -> Self {
+        let encoded_value = BASE64_STANDARD.encode(&token);
+        HttpNegotiateAuth {
+            token,
+            encoded_value,
+        }
+    }
+
+    #[inline]
+    pub fn encoded_value(&self) -> &str {
+        &self.encoded_value
+    }
+}
+
+impl FromStr for HttpNegotiateAuth {
+    type Err = AuthParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encoded_value = s.trim(); // allow more space than spec
+        let token = BASE64_STANDARD
+            .decode(encoded_value)
+            .map_err(|_| AuthParseError::InvalidBase64Encoding)?;
+        if token.is_empty() {
+            return Err(AuthParseError::InvalidPassword);
+        }
+        Ok(HttpNegotiateAuth {
+            token,
+            encoded_value: encoded_value.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&HttpNegotiateAuth> for http::HeaderValue {
+    type Error = http::header::InvalidHeaderValue;
+
+    fn try_from(value: &HttpNegotiateAuth) -> Result<Self, Self::Error> {
+        let value = format!("Negotiate {}", value.encoded_value());
+        http::HeaderValue::from_str(&value)
+    }
+}
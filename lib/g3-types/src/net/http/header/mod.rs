@@ -12,9 +12,11 @@ pub use name::HttpOriginalHeaderName;
 pub use value::HttpHeaderValue;
 
 mod forwarded;
+mod rewrite;
 mod server_id;
 
 pub use forwarded::{
     HttpForwardedHeaderType, HttpForwardedHeaderValue, HttpStandardForwardedHeaderValue,
 };
+pub use rewrite::{HttpHeaderRewriteAction, HttpHeaderRewriteRule};
 pub use server_id::HttpServerId;
@@ -0,0 +1,208 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2026 G3-OSS developers.
+ */
+
+use http::{HeaderName, HeaderValue};
+
+use super::{HttpHeaderMap, HttpHeaderValue};
+use crate::net::Host;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HttpHeaderRewriteAction {
+    /// append a new header value, keep existing values
+    Add(HeaderName, HeaderValue),
+    /// replace all existing values with the new one
+    Set(HeaderName, HeaderValue),
+    /// remove all values of the header
+    Remove(HeaderName),
+    /// move all values of the first header to the second one
+    Rename(HeaderName, HeaderName),
+}
+
+impl HttpHeaderRewriteAction {
+    pub fn apply(&self, headers: &mut HttpHeaderMap) {
+        match self {
+            HttpHeaderRewriteAction::Add(name, value) => {
+                headers.append(name.clone(), HttpHeaderValue::from(value.clone()));
+            }
+            HttpHeaderRewriteAction::Set(name, value) => {
+                headers.insert(name.clone(), HttpHeaderValue::from(value.clone()));
+            }
+            HttpHeaderRewriteAction::Remove(name) => {
+                headers.remove(name);
+            }
+            HttpHeaderRewriteAction::Rename(from, to) => {
+                let values: Vec<HeaderValue> = headers
+                    .get_all(from)
+                    .iter()
+                    .map(|v| v.inner().clone())
+                    .collect();
+                if values.is_empty() {
+                    return;
+                }
+                headers.remove(from);
+                headers.remove(to);
+                for value in values {
+                    headers.append(to.clone(), HttpHeaderValue::from(value));
+                }
+            }
+        }
+    }
+}
+
+/// A header rewrite rule.
+///
+/// All the set conditions should match, and for each condition, matching any of the
+/// values is enough. A rule without any condition will match all requests.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HttpHeaderRewriteRule {
+    pub hosts: Vec<Host>,
+    pub host_suffix: Vec<String>,
+    pub path_prefix: Vec<String>,
+    pub users: Vec<String>,
+    pub request: Vec<HttpHeaderRewriteAction>,
+    pub response: Vec<HttpHeaderRewriteAction>,
+}
+
+/// match the domain itself or any of its sub domains
+fn match_domain_suffix(domain: &str, suffix: &str) -> bool {
+    match domain.strip_suffix(suffix) {
+        Some(prefix) => prefix.is_empty() || prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+impl HttpHeaderRewriteRule {
+    pub fn is_match(&self, host: &Host, path: &str, user: Option<&str>) -> bool {
+        if !self.hosts.is_empty() && !self.hosts.contains(host) {
+            return false;
+        }
+
+        if !self.host_suffix.is_empty() {
+            let Host::Domain(domain) = host else {
+                return false;
+            };
+            if !self
+                .host_suffix
+                .iter()
+                .any(|s| match_domain_suffix(domain, s))
+            {
+                return false;
+            }
+        }
+
+        if !self.path_prefix.is_empty() && !self.path_prefix.iter().any(|p| path.starts_with(p)) {
+            return false;
+        }
+
+        if !self.users.is_empty() {
+            let Some(user) = user else {
+                return false;
+            };
+            if !self.users.iter().any(|u| u == user) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn rewrite_request(&self, headers: &mut HttpHeaderMap) {
+        self.request.iter().for_each(|a| a.apply(headers));
+    }
+
+    pub fn rewrite_response(&self, headers: &mut HttpHeaderMap) {
+        self.response.iter().for_each(|a| a.apply(headers));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn apply_actions() {
+        let mut headers = HttpHeaderMap::default();
+        headers.append(
+            HeaderName::from_static("x-internal"),
+            HttpHeaderValue::from_static("secret"),
+        );
+        headers.append(
+            HeaderName::from_static("x-old"),
+            HttpHeaderValue::from_static("a"),
+        );
+        headers.append(
+            HeaderName::from_static("x-old"),
+            HttpHeaderValue::from_static("b"),
+        );
+
+        HttpHeaderRewriteAction::Remove(HeaderName::from_static("x-internal")).apply(&mut headers);
+        assert!(!headers.contains_key("x-internal"));
+
+        HttpHeaderRewriteAction::Rename(
+            HeaderName::from_static("x-old"),
+            HeaderName::from_static("x-new"),
+        )
+        .apply(&mut headers);
+        assert!(!headers.contains_key("x-old"));
+        let values: Vec<_> = headers
+            .get_all("x-new")
+            .iter()
+            .map(|v| v.to_str())
+            .collect();
+        assert_eq!(values, vec!["a", "b"]);
+
+        HttpHeaderRewriteAction::Add(
+            HeaderName::from_static("x-new"),
+            HeaderValue::from_static("c"),
+        )
+        .apply(&mut headers);
+        assert_eq!(headers.get_all("x-new").iter().count(), 3);
+
+        HttpHeaderRewriteAction::Set(
+            HeaderName::from_static("x-new"),
+            HeaderValue::from_static("d"),
+        )
+        .apply(&mut headers);
+        let values: Vec<_> = headers
+            .get_all("x-new")
+            .iter()
+            .map(|v| v.to_str())
+            .collect();
+        assert_eq!(values, vec!["d"]);
+    }
+
+    #[test]
+    fn match_rule() {
+        let rule = HttpHeaderRewriteRule::default();
+        let host = Host::from_str("api.example.net").unwrap();
+        assert!(rule.is_match(&host, "/", None));
+
+        let rule = HttpHeaderRewriteRule {
+            host_suffix: vec!["example.net".to_string()],
+            path_prefix: vec!["/v1/".to_string()],
+            users: vec!["alice".to_string()],
+            ..Default::default()
+        };
+        assert!(rule.is_match(&host, "/v1/users", Some("alice")));
+        assert!(!rule.is_match(&host, "/v1/users", Some("bob")));
+        assert!(!rule.is_match(&host, "/v1/users", None));
+        assert!(!rule.is_match(&host, "/v2/users", Some("alice")));
+        let host = Host::from_str("example.net").unwrap();
+        assert!(rule.is_match(&host, "/v1/users", Some("alice")));
+        let host = Host::from_str("evilexample.net").unwrap();
+        assert!(!rule.is_match(&host, "/v1/users", Some("alice")));
+        let host = Host::from_str("api.example.org").unwrap();
+        assert!(!rule.is_match(&host, "/v1/users", Some("alice")));
+        let host = Host::from_str("127.0.0.1").unwrap();
+        assert!(!rule.is_match(&host, "/v1/users", Some("alice")));
+
+        let rule = HttpHeaderRewriteRule {
+            hosts: vec![Host::from_str("127.0.0.1").unwrap()],
+            ..Default::default()
+        };
+        assert!(rule.is_match(&host, "/", None));
+    }
+}
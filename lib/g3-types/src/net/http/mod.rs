@@ -10,7 +10,7 @@ mod keepalive;
 mod proxy;
 mod upgrade;
 
-pub use auth::{HttpAuth, HttpBasicAuth};
+pub use auth::{HttpAuth, HttpBasicAuth, HttpBearerAuth, HttpNegotiateAuth};
 pub use capability::*;
 pub use header::*;
 pub use keepalive::HttpKeepAliveConfig;
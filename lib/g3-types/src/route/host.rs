@@ -97,6 +97,17 @@ impl<T> HostMatch<T> {
             && self.child_domain.is_none()
             && self.default.is_none()
     }
+
+    /// iterate over all values, a value set for more than one host will be visited more than once
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        let exact_domain = self.exact_domain.iter().flat_map(|ht| ht.values());
+        let exact_ip = self.exact_ip.iter().flat_map(|ht| ht.values());
+        let child_domain = self.child_domain.iter().flat_map(|trie| trie.values());
+        exact_domain
+            .chain(exact_ip)
+            .chain(child_domain)
+            .chain(self.default.iter())
+    }
 }
 
 impl<T> HostMatch<Arc<T>> {
@@ -328,6 +339,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn values() {
+        let mut hm = HostMatch::default();
+        assert_eq!(hm.values().count(), 0);
+
+        hm.add_exact_domain(arcstr::literal!("example.com"), 1);
+        hm.add_exact_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 2);
+        hm.add_child_domain("test.com", 3);
+        hm.add_child_domain("sub.test.com", 3);
+        hm.set_default(4);
+
+        let mut values: Vec<i32> = hm.values().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 2, 3, 3, 4]);
+    }
+
     #[test]
     fn try_build_arc_success() {
         let mut hm = HostMatch::default();
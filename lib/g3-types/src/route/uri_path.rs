@@ -44,6 +44,17 @@ impl<T> UriPathMatch<T> {
 
         self.default.as_ref()
     }
+
+    /// get the value of the longest matched prefix, or the default value if no prefix matches
+    pub fn get_by_prefix(&self, path: &str) -> Option<&T> {
+        if let Some(trie) = &self.prefix
+            && let Some(v) = trie.get_ancestor_value(path)
+        {
+            return Some(v);
+        }
+
+        self.default.as_ref()
+    }
 }
 
 impl<'a, S, D, E> TryFrom<&'a UriPathMatch<Arc<S>>> for UriPathMatch<Arc<D>>
@@ -154,6 +165,23 @@ mod tests {
         assert_eq!(m.get("/home"), Some(&0));
     }
 
+    #[test]
+    fn get_by_prefix() {
+        // The get_by_prefix() method's behavior: longest prefix match or fall back to default
+        let mut m = UriPathMatch::default();
+        m.add_prefix("/api/".to_string(), 1);
+        m.add_prefix("/api/v2/".to_string(), 2);
+
+        assert_eq!(m.get_by_prefix("/api/"), Some(&1));
+        assert_eq!(m.get_by_prefix("/api/v1/users"), Some(&1));
+        assert_eq!(m.get_by_prefix("/api/v2/users"), Some(&2));
+        assert_eq!(m.get_by_prefix("/api"), None);
+        assert_eq!(m.get_by_prefix("/home"), None);
+
+        m.set_default(0);
+        assert_eq!(m.get_by_prefix("/home"), Some(&0));
+    }
+
     // Helper structs for TryFrom tests
     #[derive(Debug, PartialEq, Eq)]
     struct Src(i32);
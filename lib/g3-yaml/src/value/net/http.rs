@@ -7,11 +7,12 @@ use std::str::FromStr;
 
 use anyhow::{Context, anyhow};
 use http::uri::PathAndQuery;
-use http::{HeaderName, HeaderValue};
+use http::{HeaderName, HeaderValue, Method};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    HttpForwardCapability, HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId,
+    HttpForwardCapability, HttpForwardedHeaderType, HttpHeaderRewriteAction, HttpHeaderRewriteRule,
+    HttpKeepAliveConfig, HttpServerId,
 };
 
 pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig> {
@@ -127,6 +128,16 @@ pub fn as_http_header_name(value: &Yaml) -> anyhow::Result<HeaderName> {
     }
 }
 
+pub fn as_http_method(value: &Yaml) -> anyhow::Result<Method> {
+    if let Yaml::String(s) = value {
+        Method::from_bytes(s.to_ascii_uppercase().as_bytes()).map_err(|e| anyhow!(e))
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'HttpMethod' should be 'string'"
+        ))
+    }
+}
+
 pub fn as_http_header_value_string(value: &Yaml) -> anyhow::Result<String> {
     let s = crate::value::as_string(value).context("invalid yaml value for http header value")?;
     HeaderValue::from_str(&s).map_err(|e| anyhow!("invalid http header value string {s}: {e}"))?;
@@ -143,6 +154,120 @@ pub fn as_http_path_and_query(value: &Yaml) -> anyhow::Result<PathAndQuery> {
     }
 }
 
+fn foreach_header_kv<F>(value: &Yaml, f: F) -> anyhow::Result<()>
+where
+    F: FnMut(&str, &Yaml) -> anyhow::Result<()>,
+{
+    if let Yaml::Hash(map) = value {
+        crate::foreach_kv(map, f)
+    } else {
+        Err(anyhow!("yaml value type for http headers should be 'map'"))
+    }
+}
+
+fn as_http_header_rewrite_actions(
+    value: &Yaml,
+    actions: &mut Vec<HttpHeaderRewriteAction>,
+) -> anyhow::Result<()> {
+    match value {
+        Yaml::Hash(map) => crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "add" | "append" => foreach_header_kv(v, |name, value| {
+                let name = HeaderName::from_str(name).map_err(|e| anyhow!(e))?;
+                let value = as_http_header_value_string(value)?;
+                let value = HeaderValue::from_str(&value).map_err(|e| anyhow!(e))?;
+                actions.push(HttpHeaderRewriteAction::Add(name, value));
+                Ok(())
+            }),
+            "set" => foreach_header_kv(v, |name, value| {
+                let name = HeaderName::from_str(name).map_err(|e| anyhow!(e))?;
+                let value = as_http_header_value_string(value)?;
+                let value = HeaderValue::from_str(&value).map_err(|e| anyhow!(e))?;
+                actions.push(HttpHeaderRewriteAction::Set(name, value));
+                Ok(())
+            }),
+            "remove" | "delete" => {
+                let names = crate::value::as_list(v, as_http_header_name)
+                    .context(format!("invalid http header name list value for key {k}"))?;
+                for name in names {
+                    actions.push(HttpHeaderRewriteAction::Remove(name));
+                }
+                Ok(())
+            }
+            "rename" => foreach_header_kv(v, |from, to| {
+                let from = HeaderName::from_str(from).map_err(|e| anyhow!(e))?;
+                let to = as_http_header_name(to)?;
+                actions.push(HttpHeaderRewriteAction::Rename(from, to));
+                Ok(())
+            }),
+            _ => Err(anyhow!("invalid action {k}")),
+        }),
+        Yaml::Array(seq) => {
+            for (i, v) in seq.iter().enumerate() {
+                as_http_header_rewrite_actions(v, actions)
+                    .context(format!("invalid value for list element #{i}"))?;
+            }
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "yaml value type for 'HttpHeaderRewriteAction' should be 'map' or 'seq'"
+        )),
+    }
+}
+
+pub fn as_http_header_rewrite_rule(value: &Yaml) -> anyhow::Result<HttpHeaderRewriteRule> {
+    if let Yaml::Hash(map) = value {
+        let mut rule = HttpHeaderRewriteRule::default();
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "host" | "hosts" => {
+                rule.hosts = crate::value::as_list(v, crate::value::as_host)
+                    .context(format!("invalid host list value for key {k}"))?;
+                Ok(())
+            }
+            "host_suffix" => {
+                rule.host_suffix = crate::value::as_list(v, |v| {
+                    let suffix = crate::value::as_string(v)?;
+                    // the leading dot is optional, as sub domains are always matched
+                    let suffix = suffix.trim_start_matches('.');
+                    if suffix.is_empty() {
+                        return Err(anyhow!("empty host suffix"));
+                    }
+                    Ok(suffix.to_string())
+                })
+                .context(format!("invalid host suffix string list value for key {k}"))?;
+                Ok(())
+            }
+            "path_prefix" => {
+                rule.path_prefix = crate::value::as_list(v, |v| {
+                    let prefix = crate::value::as_string(v)?;
+                    if !prefix.starts_with('/') {
+                        return Err(anyhow!("path prefix {prefix} should start with '/'"));
+                    }
+                    Ok(prefix)
+                })
+                .context(format!("invalid path prefix string list value for key {k}"))?;
+                Ok(())
+            }
+            "user" | "users" => {
+                rule.users = crate::value::as_list(v, crate::value::as_string)
+                    .context(format!("invalid string list value for key {k}"))?;
+                Ok(())
+            }
+            "request" => as_http_header_rewrite_actions(v, &mut rule.request).context(format!(
+                "invalid http header rewrite actions value for key {k}"
+            )),
+            "response" => as_http_header_rewrite_actions(v, &mut rule.response).context(format!(
+                "invalid http header rewrite actions value for key {k}"
+            )),
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        Ok(rule)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'HttpHeaderRewriteRule' should be 'map'"
+        ))
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "http")]
 mod tests {
@@ -386,6 +511,32 @@ mod tests {
         assert!(as_http_header_name(&yaml).is_err());
     }
 
+    #[test]
+    fn as_http_method_ok() {
+        // Standard method in upper case
+        let yaml = yaml_str!("GET");
+        assert_eq!(as_http_method(&yaml).unwrap(), Method::GET);
+
+        // Standard method in lower case
+        let yaml = yaml_str!("post");
+        assert_eq!(as_http_method(&yaml).unwrap(), Method::POST);
+
+        // Extension method
+        let yaml = yaml_str!("PURGE");
+        assert_eq!(as_http_method(&yaml).unwrap().as_str(), "PURGE");
+    }
+
+    #[test]
+    fn as_http_method_err() {
+        // Invalid method token
+        let yaml = yaml_str!("GE T");
+        assert!(as_http_method(&yaml).is_err());
+
+        // Invalid type
+        let yaml = Yaml::Integer(123);
+        assert!(as_http_method(&yaml).is_err());
+    }
+
     #[test]
     fn as_http_header_value_string_ok() {
         // Valid header value
@@ -427,4 +578,74 @@ mod tests {
         let yaml = Yaml::Integer(123);
         assert!(as_http_path_and_query(&yaml).is_err());
     }
+
+    #[test]
+    fn as_http_header_rewrite_rule_ok() {
+        let yaml = yaml_doc!(
+            r#"
+                host_suffix: .example.net
+                path_prefix: /api/
+                request:
+                  - remove: [x-internal-token]
+                  - set:
+                      x-tenant-id: tenant-a
+                response:
+                  rename:
+                    x-upstream-server: x-backend
+                  add:
+                    x-frame-options: DENY
+            "#
+        );
+        let rule = as_http_header_rewrite_rule(&yaml).unwrap();
+        assert_eq!(rule.host_suffix, vec!["example.net".to_string()]);
+        assert_eq!(rule.path_prefix, vec!["/api/".to_string()]);
+        assert_eq!(
+            rule.request,
+            vec![
+                HttpHeaderRewriteAction::Remove(HeaderName::from_static("x-internal-token")),
+                HttpHeaderRewriteAction::Set(
+                    HeaderName::from_static("x-tenant-id"),
+                    HeaderValue::from_static("tenant-a")
+                ),
+            ]
+        );
+        assert_eq!(
+            rule.response,
+            vec![
+                HttpHeaderRewriteAction::Rename(
+                    HeaderName::from_static("x-upstream-server"),
+                    HeaderName::from_static("x-backend")
+                ),
+                HttpHeaderRewriteAction::Add(
+                    HeaderName::from_static("x-frame-options"),
+                    HeaderValue::from_static("DENY")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn as_http_header_rewrite_rule_err() {
+        // Invalid path prefix
+        let yaml = yaml_doc!(
+            r#"
+                path_prefix: api
+            "#
+        );
+        assert!(as_http_header_rewrite_rule(&yaml).is_err());
+
+        // Invalid action
+        let yaml = yaml_doc!(
+            r#"
+                request:
+                  replace:
+                    x-test: a
+            "#
+        );
+        assert!(as_http_header_rewrite_rule(&yaml).is_err());
+
+        // Invalid type
+        let yaml = yaml_str!("rule");
+        assert!(as_http_header_rewrite_rule(&yaml).is_err());
+    }
 }